@@ -1,3 +1,4 @@
 fn main() {
+    #[cfg(feature = "tauri")]
     tauri_build::build();
 }
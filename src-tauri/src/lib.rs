@@ -1,10 +1,19 @@
+//! BMS resource toolbox
+//!
+//! Features:
+//! - `tauri` (default): the desktop application entry point and Tauri plugins.
+
 #![recursion_limit = "512"]
 
+#[cfg(feature = "tauri")]
+use tauri::Manager;
+
 /// Run the Tauri application
 ///
 /// # Errors
 ///
 /// Returns an error if the Tauri application fails to start or run
+#[cfg(feature = "tauri")]
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() -> Result<(), tauri::Error> {
     // Tauri internally uses process::exit on unrecoverable errors.
@@ -13,9 +22,18 @@ pub fn run() -> Result<(), tauri::Error> {
     #[allow(clippy::exit)]
     {
         tauri::Builder::default()
+            // 再次启动时聚焦已有窗口，避免两个实例同时修改同一目录
+            .plugin(tauri_plugin_single_instance::init(|app, _args, _cwd| {
+                if let Some(window) = app.get_webview_window("main") {
+                    let _ = window.unminimize();
+                    let _ = window.set_focus();
+                }
+            }))
             .plugin(tauri_plugin_opener::init())
             .plugin(tauri_plugin_dialog::init())
             .plugin(tauri_plugin_fs::init())
+            .plugin(tauri_plugin_http::init())
+            .plugin(tauri_plugin_notification::init())
             .invoke_handler(tauri::generate_handler![
                 // 保留的命令（如果有）
                 // 所有业务逻辑已迁移到前端
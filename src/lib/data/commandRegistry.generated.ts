@@ -84,6 +84,97 @@ export const GENERATED_COMMAND_REGISTRY: CommandDefinition[] = [
     dangerous: true,
     isFrontendCommand: true
   },
+  {
+    id: 'rawpack_list_candidates',
+    name: '列出待编号文件',
+    category: CommandCategory.Pack,
+    description: `列出目录中尚未编号的压缩包（大小、修改时间、推测的标题），以及建议的下一个编号，供多选编号使用`,
+    parameters: [
+      {
+        key: 'dir',
+        type: ParameterType.String,
+        typeString: 'string',
+        required: true,
+        description: `- 目录路径`
+      },
+      {
+        key: 'exts',
+        type: ParameterType.String,
+        typeString: 'string[]',
+        required: false,
+        description: `- 包含的扩展名（为空时包含所有文件）`
+      }
+    ],
+    returnType: 'NumberingCandidates',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'rawpack_apply_numbers',
+    name: '应用文件编号',
+    category: CommandCategory.Pack,
+    description: `按前端选择的编号为多个文件添加编号前缀，任一文件失败时全部撤销`,
+    parameters: [
+      {
+        key: 'dir',
+        type: ParameterType.String,
+        typeString: 'string',
+        required: true,
+        description: `- 目录路径`
+      },
+      {
+        key: 'assignments',
+        type: ParameterType.Enum,
+        typeString: 'NumberAssignment[]',
+        required: true,
+        description: `- 文件名和编号`
+      },
+      {
+        key: 'dryRun',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: false,
+        description: `- 是否模拟运行（只检查不重命名）`,
+        defaultValue: true
+      }
+    ],
+    returnType: 'RenameOperation[]',
+    dangerous: true,
+    isFrontendCommand: true
+  },
+  {
+    id: 'rawpack_set_file_num_with_mapping',
+    name: '按映射设置文件编号',
+    category: CommandCategory.Pack,
+    description: `按 { 文件名: 编号 } 映射为多个文件添加编号前缀，任一文件失败时全部撤销`,
+    parameters: [
+      {
+        key: 'dir',
+        type: ParameterType.String,
+        typeString: 'string',
+        required: true,
+        description: `- 目录路径`
+      },
+      {
+        key: 'mapping',
+        type: ParameterType.Enum,
+        typeString: 'Record<string, number>',
+        required: true,
+        description: `- 文件名到编号的映射`
+      },
+      {
+        key: 'dryRun',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: false,
+        description: `- 是否模拟运行（只检查不重命名）`,
+        defaultValue: true
+      }
+    ],
+    returnType: 'RenameOperation[]',
+    dangerous: true,
+    isFrontendCommand: true
+  },
   {
     id: 'rawpack_unzip_numeric_to_bms_folder',
     name: '解压编号压缩包',
@@ -116,7 +207,7 @@ export const GENERATED_COMMAND_REGISTRY: CommandDefinition[] = [
         type: ParameterType.Boolean,
         typeString: 'boolean',
         required: true,
-        description: `- 是否确认`
+        description: `- 解压前列出压缩包并确认`
       },
       {
         key: 'replacePreset',
@@ -163,7 +254,7 @@ export const GENERATED_COMMAND_REGISTRY: CommandDefinition[] = [
         type: ParameterType.Boolean,
         typeString: 'boolean',
         required: true,
-        description: `- 是否确认`
+        description: `- 解压前列出压缩包并确认`
       },
       {
         key: 'replacePreset',
@@ -172,12 +263,158 @@ export const GENERATED_COMMAND_REGISTRY: CommandDefinition[] = [
         required: true,
         description: `- 文件替换策略`,
         defaultValue: "ReplacePreset.Default"
+      },
+      {
+        key: 'force',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: false,
+        description: `- 强制重新导入已导入过的压缩包`
       }
     ],
     returnType: 'void',
     dangerous: true,
     isFrontendCommand: true
   },
+  {
+    id: 'rawpack_review_quarantine',
+    name: '查看隔离区',
+    category: CommandCategory.Pack,
+    description: `列出解压后无法自动导入而被隔离的压缩包及其原因`,
+    parameters: [
+      {
+        key: 'packDir',
+        type: ParameterType.String,
+        typeString: 'string',
+        required: true,
+        description: `- 压缩包目录`
+      }
+    ],
+    returnType: 'QuarantineEntry[]',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'rawpack_retry_quarantine',
+    name: '重试隔离条目',
+    category: CommandCategory.Pack,
+    description: `重新检查隔离区中的条目，通过检查后导入到根目录`,
+    parameters: [
+      {
+        key: 'packDir',
+        type: ParameterType.String,
+        typeString: 'string',
+        required: true,
+        description: `- 压缩包目录`
+      },
+      {
+        key: 'rootDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 根目录`
+      },
+      {
+        key: 'entryName',
+        type: ParameterType.String,
+        typeString: 'string',
+        required: true,
+        description: `- 隔离条目名（压缩包名）`
+      },
+      {
+        key: 'replacePreset',
+        type: ParameterType.Enum,
+        typeString: 'ReplacePreset',
+        required: true,
+        description: `- 文件替换策略`,
+        defaultValue: "ReplacePreset.Default"
+      }
+    ],
+    returnType: 'boolean',
+    dangerous: true,
+    isFrontendCommand: true
+  },
+  {
+    id: 'rawpack_process_inbox',
+    name: '处理收件箱',
+    category: CommandCategory.Pack,
+    description: `解压收件箱中的压缩包并整理文件夹，检查通过的作品移入根目录并按 BMS 信息命名，有问题的作品移入收件箱的隔离区`,
+    parameters: [
+      {
+        key: 'inboxDir',
+        type: ParameterType.String,
+        typeString: 'string',
+        required: true,
+        description: `- 收件箱目录`
+      },
+      {
+        key: 'rootDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 根目录`
+      },
+      {
+        key: 'setType',
+        type: ParameterType.Enum,
+        typeString: 'BmsFolderSetNameType',
+        required: true,
+        description: `- 命名方式`
+      },
+      {
+        key: 'replacePreset',
+        type: ParameterType.Enum,
+        typeString: 'ReplacePreset',
+        required: true,
+        description: `- 文件替换策略`,
+        defaultValue: "ReplacePreset.Default"
+      },
+      {
+        key: 'dryRun',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: true,
+        description: `- 模拟运行（只列出收件箱中的条目）`,
+        defaultValue: true
+      }
+    ],
+    returnType: 'InboxReport',
+    dangerous: true,
+    isFrontendCommand: true
+  },
+  {
+    id: 'rawpack_clean_partials',
+    name: '清理未完成的下载',
+    category: CommandCategory.Pack,
+    description: `删除压缩包目录中超过指定时间未修改的 .part、.crdownload、.!ut 等未完成下载的临时文件，以及对应的空白占位文件`,
+    parameters: [
+      {
+        key: 'packDir',
+        type: ParameterType.String,
+        typeString: 'string',
+        required: true,
+        description: `- 压缩包目录`
+      },
+      {
+        key: 'maxAgeHours',
+        type: ParameterType.Number,
+        typeString: 'number',
+        required: false,
+        description: `- 超过该时间（小时）未修改的临时文件视为已放弃`
+      },
+      {
+        key: 'dryRun',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: false,
+        description: `- 模拟运行（只列出要删除的文件）`,
+        defaultValue: true
+      }
+    ],
+    returnType: 'PartialCleanResult',
+    dangerous: true,
+    isFrontendCommand: true
+  },
   {
     id: 'root_split_folders_with_first_char',
     name: '按首字符拆分文件夹',
@@ -200,7 +437,7 @@ export const GENERATED_COMMAND_REGISTRY: CommandDefinition[] = [
         defaultValue: true
       }
     ],
-    returnType: 'void',
+    returnType: 'DryRunPlan | null',
     dangerous: true,
     isFrontendCommand: true
   },
@@ -226,7 +463,7 @@ export const GENERATED_COMMAND_REGISTRY: CommandDefinition[] = [
         defaultValue: true
       }
     ],
-    returnType: 'void',
+    returnType: 'DryRunPlan | null',
     dangerous: true,
     isFrontendCommand: true
   },
@@ -259,7 +496,7 @@ export const GENERATED_COMMAND_REGISTRY: CommandDefinition[] = [
         defaultValue: true
       }
     ],
-    returnType: 'void',
+    returnType: 'DryRunPlan | null',
     dangerous: true,
     isFrontendCommand: true
   },
@@ -292,7 +529,7 @@ export const GENERATED_COMMAND_REGISTRY: CommandDefinition[] = [
         defaultValue: true
       }
     ],
-    returnType: 'void',
+    returnType: 'DryRunPlan | null',
     dangerous: true,
     isFrontendCommand: true
   },
@@ -318,7 +555,7 @@ export const GENERATED_COMMAND_REGISTRY: CommandDefinition[] = [
         defaultValue: true
       }
     ],
-    returnType: 'void',
+    returnType: 'DryRunPlan | null',
     dangerous: true,
     isFrontendCommand: true
   },
@@ -351,7 +588,7 @@ export const GENERATED_COMMAND_REGISTRY: CommandDefinition[] = [
         defaultValue: true
       }
     ],
-    returnType: 'void',
+    returnType: 'DryRunPlan | null',
     dangerous: true,
     isFrontendCommand: true
   },
@@ -377,7 +614,7 @@ export const GENERATED_COMMAND_REGISTRY: CommandDefinition[] = [
         defaultValue: true
       }
     ],
-    returnType: 'void',
+    returnType: 'DryRunPlan | null',
     dangerous: true,
     isFrontendCommand: true
   },
@@ -410,7 +647,7 @@ export const GENERATED_COMMAND_REGISTRY: CommandDefinition[] = [
         defaultValue: true
       }
     ],
-    returnType: 'void',
+    returnType: 'DryRunPlan | null',
     dangerous: true,
     isFrontendCommand: true
   },
@@ -434,9 +671,16 @@ export const GENERATED_COMMAND_REGISTRY: CommandDefinition[] = [
         required: true,
         description: `- 模拟运行（不实际执行）`,
         defaultValue: true
+      },
+      {
+        key: 'progressManager',
+        type: ParameterType.Enum,
+        typeString: 'IProgressManager',
+        required: false,
+        description: `- 进度管理器（可选）`
       }
     ],
-    returnType: 'void',
+    returnType: 'DryRunPlan | null',
     dangerous: true,
     isFrontendCommand: true
   },
@@ -444,7 +688,7 @@ export const GENERATED_COMMAND_REGISTRY: CommandDefinition[] = [
     id: 'root_scan_similar_folders',
     name: '扫描相似文件夹',
     category: CommandCategory.BMSFolder,
-    description: `扫描指定目录中名称相似的文件夹，用于发现重复或误操作`,
+    description: `扫描指定目录中名称相似的文件夹，用于发现重复或误操作；可选编辑距离、词集合（忽略词序）、最长公共子序列或加权综合算法`,
     parameters: [
       {
         key: 'rootDir',
@@ -459,6 +703,13 @@ export const GENERATED_COMMAND_REGISTRY: CommandDefinition[] = [
         typeString: 'number',
         required: false,
         description: `- 相似度触发阈值（默认 0.7）`
+      },
+      {
+        key: 'metric',
+        type: ParameterType.Enum,
+        typeString: 'SimilarityMetric',
+        required: false,
+        description: `- 相似度算法（默认编辑距离）`
       }
     ],
     returnType: '{ folder1: string; folder2: string; similarity: number; }[]',
@@ -466,46 +717,123 @@ export const GENERATED_COMMAND_REGISTRY: CommandDefinition[] = [
     isFrontendCommand: true
   },
   {
-    id: 'read_and_parse_bms_file',
-    name: '解析 BMS 文件',
-    category: CommandCategory.BMS,
-    description: `解析单个 BMS 文件，提取元数据信息（标题、艺术家等）`,
+    id: 'root_organize_by_genre',
+    name: '按流派整理文件夹',
+    category: CommandCategory.BigPack,
+    description: `将根目录下的作品按流派移动到流派子文件夹中（流派写法统一后分组），可撤销`,
     parameters: [
       {
-        key: 'filePath',
-        type: ParameterType.File,
+        key: 'rootDir',
+        type: ParameterType.Directory,
         typeString: 'string',
         required: true,
-        description: `- BMS 文件路径`
+        description: `- 根目录路径`
+      },
+      {
+        key: 'dryRun',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: true,
+        description: `- 模拟运行（不实际执行）`,
+        defaultValue: true
       }
     ],
-    returnType: 'BmsOutput | null',
-    dangerous: false,
+    returnType: 'void',
+    dangerous: true,
     isFrontendCommand: true
   },
   {
-    id: 'get_dir_bms_list',
-    name: '扫描目录 BMS 文件',
-    category: CommandCategory.BMS,
-    description: `扫描目录，解析所有 BMS 文件`,
+    id: 'root_undo_organize_by_genre',
+    name: '撤销按流派整理',
+    category: CommandCategory.BigPack,
+    description: `按日志将最近一次按流派整理移动的作品移回根目录，并删除变空的流派文件夹`,
     parameters: [
       {
-        key: 'dirPath',
+        key: 'rootDir',
         type: ParameterType.Directory,
         typeString: 'string',
         required: true,
-        description: `- 目录路径`
+        description: `- 根目录路径`
+      },
+      {
+        key: 'dryRun',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: true,
+        description: `- 模拟运行（不实际执行）`,
+        defaultValue: true
       }
     ],
-    returnType: 'BmsOutput[]',
-    dangerous: false,
+    returnType: 'void',
+    dangerous: true,
+    isFrontendCommand: true
+  },
+  {
+    id: 'root_generate_contents_files',
+    name: '生成作品目录文件',
+    category: CommandCategory.BigPack,
+    description: `在根目录下的每个子目录中生成 _contents.txt，列出其中的作品及标题、艺术家`,
+    parameters: [
+      {
+        key: 'rootDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 根目录路径`
+      }
+    ],
+    returnType: 'void',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'read_and_parse_bms_file',
+    name: '解析 BMS 文件',
+    category: CommandCategory.BMS,
+    description: `解析单个 BMS 文件，提取元数据信息（标题、艺术家等）`,
+    parameters: [
+      {
+        key: 'filePath',
+        type: ParameterType.File,
+        typeString: 'string',
+        required: true,
+        description: `- BMS 文件路径`
+      }
+    ],
+    returnType: 'BmsOutput | null',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'get_dir_bms_list',
+    name: '扫描目录 BMS 文件',
+    category: CommandCategory.BMS,
+    description: `扫描目录，解析所有 BMS 文件`,
+    parameters: [
+      {
+        key: 'dirPath',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 目录路径`
+      },
+      {
+        key: 'showWarnings',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: false,
+        description: `- 输出解析警告和被跳过的文件`
+      }
+    ],
+    returnType: 'BmsOutput[]',
+    dangerous: false,
     isFrontendCommand: true
   },
   {
     id: 'get_dir_bms_info',
     name: '读取目录 BMS 信息',
     category: CommandCategory.BMS,
-    description: `从目录的 info.toml 文件读取 BMS 摘要信息`,
+    description: `整合目录中所有谱面的标题、艺术家、流派和 #WAV / #BMP 定义，并检查每个定义的文件是否存在`,
     parameters: [
       {
         key: 'dirPath',
@@ -515,7 +843,7 @@ export const GENERATED_COMMAND_REGISTRY: CommandDefinition[] = [
         description: `- BMS 工作目录路径`
       }
     ],
-    returnType: 'BmsOutput | null',
+    returnType: 'WorkInfoDto | null',
     dangerous: false,
     isFrontendCommand: true
   },
@@ -555,6 +883,24 @@ export const GENERATED_COMMAND_REGISTRY: CommandDefinition[] = [
     dangerous: false,
     isFrontendCommand: true
   },
+  {
+    id: 'get_dir_bms_list_with_outcomes',
+    name: '扫描目录 BMS 文件（含解析结果）',
+    category: CommandCategory.BMS,
+    description: `扫描目录，返回解析成功的列表，以及每个谱面文件的警告、错误和跳过原因`,
+    parameters: [
+      {
+        key: 'dirPath',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 目录路径`
+      }
+    ],
+    returnType: 'BmsDirScanResult',
+    dangerous: false,
+    isFrontendCommand: true
+  },
   {
     id: 'extract_work_name',
     name: '提取作品名称',
@@ -587,6 +933,107 @@ export const GENERATED_COMMAND_REGISTRY: CommandDefinition[] = [
     dangerous: false,
     isFrontendCommand: true
   },
+  {
+    id: 'bms_quick_info',
+    name: '快速读取谱面信息',
+    category: CommandCategory.BMS,
+    description: `只读取谱面头部（标题、艺术家、BPM、等级、难度），不解析音符，适合列表显示`,
+    parameters: [
+      {
+        key: 'filePath',
+        type: ParameterType.File,
+        typeString: 'string',
+        required: true,
+        description: `- 谱面文件路径`
+      }
+    ],
+    returnType: 'BmsQuickInfo | null',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'set_level',
+    name: '批量修改谱面等级',
+    category: CommandCategory.BMS,
+    description: `按提案表（每行：谱面 SHA-256 或作品目录名, 等级, 难度）批量修改 #PLAYLEVEL / #DIFFICULTY，修改前备份谱面`,
+    parameters: [
+      {
+        key: 'rootDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 根目录路径`
+      },
+      {
+        key: 'filePath',
+        type: ParameterType.File,
+        typeString: 'string',
+        required: true,
+        description: `- 提案表文件（CSV 或 TSV）`
+      },
+      {
+        key: 'dryRun',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: true,
+        description: `- 模拟运行（只列出修改）`,
+        defaultValue: true
+      }
+    ],
+    returnType: 'SetLevelResult',
+    dangerous: true,
+    isFrontendCommand: true
+  },
+  {
+    id: 'diff_charts',
+    name: '比较谱面',
+    category: CommandCategory.BMS,
+    description: `按语义比较两个版本的谱面：头部变化、音符数变化、BPM 变化和有改动的小节`,
+    parameters: [
+      {
+        key: 'oldFile',
+        type: ParameterType.File,
+        typeString: 'string',
+        required: true,
+        description: `- 旧版本谱面文件`
+      },
+      {
+        key: 'newFile',
+        type: ParameterType.File,
+        typeString: 'string',
+        required: true,
+        description: `- 新版本谱面文件`
+      }
+    ],
+    returnType: 'ChartDiff',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'diff_work_dirs',
+    name: '比较作品版本',
+    category: CommandCategory.BMS,
+    description: `按文件名（其次按标题和等级）配对两个版本目录中的谱面，逐个比较差异`,
+    parameters: [
+      {
+        key: 'oldWorkDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 旧版本作品目录`
+      },
+      {
+        key: 'newWorkDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 新版本作品目录`
+      }
+    ],
+    returnType: 'WorkDirDiff',
+    dangerous: false,
+    isFrontendCommand: true
+  },
   {
     id: 'root_event_check_num_folder',
     name: '检查编号文件夹',
@@ -681,255 +1128,260 @@ export const GENERATED_COMMAND_REGISTRY: CommandDefinition[] = [
     isFrontendCommand: true
   },
   {
-    id: 'remove_empty_folders',
-    name: '删除空文件夹',
-    category: CommandCategory.BMSFolder,
-    description: `递归删除指定目录下的所有空文件夹`,
+    id: 'root_event_download_works',
+    name: '批量下载活动作品',
+    category: CommandCategory.BMSEvent,
+    description: `从作品页面提取下载链接并下载（支持断点续传、失败重试、并发数和带宽限制）`,
     parameters: [
       {
-        key: 'parentDir',
+        key: 'event',
+        type: ParameterType.Enum,
+        typeString: 'BMSEvent',
+        required: true,
+        description: `- BMS 活动类型`
+      },
+      {
+        key: 'startNum',
+        type: ParameterType.Number,
+        typeString: 'number',
+        required: true,
+        description: `- 起始编号`
+      },
+      {
+        key: 'endNum',
+        type: ParameterType.Number,
+        typeString: 'number',
+        required: true,
+        description: `- 结束编号`
+      },
+      {
+        key: 'outputDir',
         type: ParameterType.Directory,
         typeString: 'string',
         required: true,
-        description: `- 要清理的目录路径`
+        description: `- 下载目录路径`
       },
       {
-        key: 'dryRun',
-        type: ParameterType.Boolean,
-        typeString: 'boolean',
+        key: 'maxConcurrent',
+        type: ParameterType.Number,
+        typeString: 'number',
         required: true,
-        description: `- 模拟运行（不实际删除）`,
-        defaultValue: true
+        description: `- 最大同时下载数`
+      },
+      {
+        key: 'bandwidthLimitKb',
+        type: ParameterType.Number,
+        typeString: 'number',
+        required: true,
+        description: `- 带宽上限（KB/s，0 表示不限制）`
       }
     ],
-    returnType: 'void',
-    dangerous: true,
+    returnType: 'EventDownloadSummary',
+    dangerous: false,
     isFrontendCommand: true
   },
   {
-    id: 'work_get_media_info',
-    name: '获取媒体文件信息',
-    category: CommandCategory.Media,
-    description: `使用 ffprobe 获取音频或视频文件的详细信息`,
+    id: 'root_event_open_next_works',
+    name: '打开下一批作品',
+    category: CommandCategory.BMSEvent,
+    description: `按编号打开接下来几个尚未试听的作品的页面和文件夹，并标记为已打开`,
     parameters: [
       {
-        key: 'filePath',
-        type: ParameterType.File,
+        key: 'event',
+        type: ParameterType.Enum,
+        typeString: 'BMSEvent',
+        required: true,
+        description: `- BMS 活动类型`
+      },
+      {
+        key: 'rootDir',
+        type: ParameterType.Directory,
         typeString: 'string',
         required: true,
-        description: `- 媒体文件路径`
+        description: `- 活动作品根目录路径`
+      },
+      {
+        key: 'count',
+        type: ParameterType.Number,
+        typeString: 'number',
+        required: true,
+        description: `- 打开的作品数量`
       }
     ],
-    returnType: 'MediaInfo | null',
+    returnType: 'number[]',
     dangerous: false,
     isFrontendCommand: true
   },
   {
-    id: 'work_get_video_info',
-    name: '获取视频信息',
-    category: CommandCategory.Media,
-    description: `获取视频文件的尺寸和比特率信息`,
+    id: 'root_event_mark_work',
+    name: '标记作品',
+    category: CommandCategory.BMSEvent,
+    description: `将作品标记为已听、跳过，或重置为未听`,
     parameters: [
       {
-        key: 'filePath',
-        type: ParameterType.File,
-        typeString: 'string',
+        key: 'event',
+        type: ParameterType.Enum,
+        typeString: 'BMSEvent',
         required: true,
-        description: `- 视频文件路径`
+        description: `- BMS 活动类型`
+      },
+      {
+        key: 'workNum',
+        type: ParameterType.Number,
+        typeString: 'number',
+        required: true,
+        description: `- 作品编号`
+      },
+      {
+        key: 'status',
+        type: ParameterType.Enum,
+        typeString: 'ImpressionStatus',
+        required: true,
+        description: `- 试听状态`
       }
     ],
-    returnType: 'VideoInfo | null',
+    returnType: 'void',
     dangerous: false,
     isFrontendCommand: true
   },
   {
-    id: 'work_get_video_size',
-    name: '获取视频尺寸',
-    category: CommandCategory.Media,
-    description: `获取视频文件的宽度和高度`,
+    id: 'root_event_impression_progress',
+    name: '查看评价进度',
+    category: CommandCategory.BMSEvent,
+    description: `统计本地作品中已听、跳过、已打开和剩余的数量`,
     parameters: [
       {
-        key: 'filePath',
-        type: ParameterType.File,
+        key: 'event',
+        type: ParameterType.Enum,
+        typeString: 'BMSEvent',
+        required: true,
+        description: `- BMS 活动类型`
+      },
+      {
+        key: 'rootDir',
+        type: ParameterType.Directory,
         typeString: 'string',
         required: true,
-        description: `- 视频文件路径`
+        description: `- 活动作品根目录路径`
       }
     ],
-    returnType: '{ width: number; height: number; } | null',
+    returnType: 'ImpressionProgressSummary',
     dangerous: false,
     isFrontendCommand: true
   },
   {
-    id: 'work_get_media_duration',
-    name: '获取媒体时长',
-    category: CommandCategory.Media,
-    description: `获取音频或视频文件的时长（秒）`,
+    id: 'root_event_scrape_work_info',
+    name: '抓取活动作品信息',
+    category: CommandCategory.BMSEvent,
+    description: `读取作品页面中的标题、艺术家和流派并按编号保存，谱面缺少标题或艺术家时用于命名`,
     parameters: [
       {
-        key: 'filePath',
-        type: ParameterType.File,
-        typeString: 'string',
+        key: 'event',
+        type: ParameterType.Enum,
+        typeString: 'BMSEvent',
         required: true,
-        description: `- 媒体文件路径`
-      }
-    ],
-    returnType: 'number | null',
-    dangerous: false,
-    isFrontendCommand: true
-  },
-  {
-    id: 'work_remove_zero_sized_media_files',
-    name: '删除零字节媒体文件',
-    category: CommandCategory.BMSFolder,
-    description: `递归删除工作目录中所有零字节媒体文件`,
-    parameters: [
+        description: `- BMS 活动类型`
+      },
       {
-        key: 'dir',
-        type: ParameterType.String,
-        typeString: 'string',
+        key: 'startNum',
+        type: ParameterType.Number,
+        typeString: 'number',
         required: true,
-        description: `- 工作目录路径`
+        description: `- 起始编号`
       },
       {
-        key: 'dryRun',
-        type: ParameterType.Boolean,
-        typeString: 'boolean',
+        key: 'endNum',
+        type: ParameterType.Number,
+        typeString: 'number',
         required: true,
-        description: `- 模拟运行（不实际删除）`,
-        defaultValue: true
+        description: `- 结束编号`
       }
     ],
-    returnType: 'void',
-    dangerous: true,
+    returnType: 'number',
+    dangerous: false,
     isFrontendCommand: true
   },
   {
-    id: 'work_remove_unneed_media_files',
-    name: '删除重复格式媒体文件',
-    category: CommandCategory.BMSFolder,
-    description: `根据预设规则删除重复格式的媒体文件（保留高质量格式）`,
+    id: 'root_event_package_work',
+    name: '打包投稿作品',
+    category: CommandCategory.BMSEvent,
+    description: `检查编号作品目录（有错误时中止），规范谱面文件名，生成预览音频和 info.txt，然后打包为 "<活动名>_<编号>_<标题>.zip"（包内文件夹为 "<编号>. <标题> [<艺术家>]"）用于发布`,
     parameters: [
       {
         key: 'rootDir',
         type: ParameterType.Directory,
         typeString: 'string',
         required: true,
-        description: `- 根目录路径`
+        description: `- 活动根目录路径`
       },
       {
-        key: 'preset',
-        type: ParameterType.Enum,
-        typeString: 'RemoveMediaPreset',
+        key: 'num',
+        type: ParameterType.Number,
+        typeString: 'number',
         required: true,
-        description: `- 清理规则预设`,
-        defaultValue: "RemoveMediaPreset.Oraja"
-      }
-    ],
-    returnType: 'void',
-    dangerous: true,
-    isFrontendCommand: true
-  },
-  {
-    id: 'work_transfer_audio',
-    name: '音频文件转换',
-    category: CommandCategory.Media,
-    description: `转换BMS根目录下的音频文件`,
-    parameters: [
+        description: `- 作品编号`
+      },
       {
-        key: 'rootDir',
+        key: 'outputDir',
         type: ParameterType.Directory,
         typeString: 'string',
         required: true,
-        description: `- 根目录路径`
+        description: `- ZIP 输出目录`
       },
       {
-        key: 'presetNames',
-        type: ParameterType.Enum,
-        typeString: 'AudioPreset[]',
+        key: 'eventName',
+        type: ParameterType.String,
+        typeString: 'string',
         required: true,
-        description: `- 目标格式预设名称`
+        description: `- 活动名（用于 ZIP 文件名和信息文件）`
       },
       {
-        key: 'removeOriginFileWhenSuccess',
-        type: ParameterType.Boolean,
-        typeString: 'boolean',
+        key: 'chartNameScheme',
+        type: ParameterType.Enum,
+        typeString: 'ChartNameScheme',
         required: true,
-        description: `- 成功时删除原文件`
+        description: `- 谱面文件名的规范方式`
       },
       {
-        key: 'removeOriginFileWhenFailed',
+        key: 'withPreview',
         type: ParameterType.Boolean,
         typeString: 'boolean',
-        required: true,
-        description: `- 失败时删除原文件`
+        required: false,
+        description: `- 没有预览音频时生成`
       },
       {
-        key: 'skipOnFail',
+        key: 'dryRun',
         type: ParameterType.Boolean,
         typeString: 'boolean',
-        required: true,
-        description: `- 遇到失败时跳过`
-      },
-      {
-        key: 'progressManager',
-        type: ParameterType.Enum,
-        typeString: 'IProgressManager',
         required: false,
-        description: `- 进度管理器（可选）`
+        description: `- 模拟运行（只检查并列出将要进行的操作）`,
+        defaultValue: true
       }
     ],
-    returnType: 'void',
+    returnType: 'WorkPackageResult',
     dangerous: true,
     isFrontendCommand: true
   },
   {
-    id: 'work_transfer_video',
-    name: '视频文件转换',
-    category: CommandCategory.Media,
-    description: `转换BMS根目录下的视频文件`,
+    id: 'remove_empty_folders',
+    name: '删除空文件夹',
+    category: CommandCategory.BMSFolder,
+    description: `递归删除指定目录下的所有空文件夹`,
     parameters: [
       {
-        key: 'rootDir',
+        key: 'parentDir',
         type: ParameterType.Directory,
         typeString: 'string',
         required: true,
-        description: `- 根目录路径`
-      },
-      {
-        key: 'presetNames',
-        type: ParameterType.Enum,
-        typeString: 'VideoPreset[]',
-        required: true,
-        description: `- 目标格式预设名称`
-      },
-      {
-        key: 'removeOriginFile',
-        type: ParameterType.Boolean,
-        typeString: 'boolean',
-        required: true,
-        description: `- 成功时删除原文件`
-      },
-      {
-        key: 'removeExistingTargetFile',
-        type: ParameterType.Boolean,
-        typeString: 'boolean',
-        required: true,
-        description: `- 删除已存在的目标文件`
+        description: `- 要清理的目录路径`
       },
       {
-        key: 'usePrefered',
+        key: 'dryRun',
         type: ParameterType.Boolean,
         typeString: 'boolean',
         required: true,
-        description: `- 使用推荐预设`
-      },
-      {
-        key: 'progressManager',
-        type: ParameterType.Enum,
-        typeString: 'IProgressManager',
-        required: false,
-        description: `- 进度管理器（可选）`
+        description: `- 模拟运行（不实际删除）`,
+        defaultValue: true
       }
     ],
     returnType: 'void',
@@ -937,72 +1389,43 @@ export const GENERATED_COMMAND_REGISTRY: CommandDefinition[] = [
     isFrontendCommand: true
   },
   {
-    id: 'work_set_name_by_bms',
-    name: '根据 BMS 重命名工作目录',
+    id: 'clean_macos_artifacts',
+    name: '清理 macOS 附加文件',
     category: CommandCategory.BMSFolder,
-    description: `根据 BMS 文件信息重命名工作目录，支持多种命名策略`,
+    description: `递归删除 __MACOSX 目录、AppleDouble（._*）文件和 .DS_Store`,
     parameters: [
       {
-        key: 'workDir',
+        key: 'parentDir',
         type: ParameterType.Directory,
         typeString: 'string',
         required: true,
-        description: `- 工作目录路径`
-      },
-      {
-        key: 'setType',
-        type: ParameterType.Enum,
-        typeString: 'BmsFolderSetNameType',
-        required: true,
-        description: `- 命名方式`
+        description: `- 要清理的目录路径`
       },
       {
         key: 'dryRun',
         type: ParameterType.Boolean,
         typeString: 'boolean',
         required: true,
-        description: `- 模拟运行（不实际执行）`,
+        description: `- 模拟运行（不实际删除）`,
         defaultValue: true
-      },
-      {
-        key: 'replacePreset',
-        type: ParameterType.Enum,
-        typeString: 'ReplacePreset',
-        required: true,
-        description: `- 文件替换策略`,
-        defaultValue: "ReplacePreset.Default"
-      },
-      {
-        key: 'skipAlreadyFormatted',
-        type: ParameterType.Boolean,
-        typeString: 'boolean',
-        required: true,
-        description: `- 跳过已格式化的目录`
       }
     ],
-    returnType: 'void',
+    returnType: 'number',
     dangerous: true,
     isFrontendCommand: true
   },
   {
-    id: 'work_undo_set_name_by_bms',
-    name: '撤销工作目录重命名',
+    id: 'clean_cache',
+    name: '清理缓存',
     category: CommandCategory.BMSFolder,
-    description: `撤销之前的目录重命名操作，恢复原始目录名`,
+    description: `删除缓存目录中超过指定天数的条目（天数为 0 时清理全部）`,
     parameters: [
       {
-        key: 'workDir',
-        type: ParameterType.Directory,
-        typeString: 'string',
-        required: true,
-        description: `- 工作目录路径`
-      },
-      {
-        key: 'setType',
-        type: ParameterType.Enum,
-        typeString: 'BmsFolderSetNameType',
+        key: 'maxAgeDays',
+        type: ParameterType.Number,
+        typeString: 'number',
         required: true,
-        description: `- 命名方式`
+        description: `- 过期天数`
       },
       {
         key: 'dryRun',
@@ -1013,112 +1436,133 @@ export const GENERATED_COMMAND_REGISTRY: CommandDefinition[] = [
         defaultValue: true
       }
     ],
-    returnType: 'void',
+    returnType: 'CacheCleanResult',
     dangerous: true,
     isFrontendCommand: true
   },
   {
-    id: 'work_append_artist_name_by_bms',
-    name: '追加艺术家名称',
+    id: 'get_cache_usage',
+    name: '查看缓存占用',
     category: CommandCategory.BMSFolder,
-    description: `在作品文件夹名后追加艺术家名称`,
+    description: `显示缓存目录位置、各条目大小和总大小`,
+    parameters: [
+
+    ],
+    returnType: 'CacheUsage',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'remove_ignored_files',
+    name: '清理系统垃圾文件',
+    category: CommandCategory.BMSFolder,
+    description: `递归删除 Thumbs.db、.DS_Store、desktop.ini、__MACOSX 等忽略列表中的文件和目录`,
     parameters: [
       {
-        key: 'rootDir',
+        key: 'parentDir',
         type: ParameterType.Directory,
         typeString: 'string',
         required: true,
-        description: `- 根目录路径`
+        description: `- 要清理的目录路径`
       },
       {
         key: 'dryRun',
         type: ParameterType.Boolean,
         typeString: 'boolean',
         required: true,
-        description: `- 模拟运行（不实际执行）`,
+        description: `- 模拟运行（不实际删除）`,
         defaultValue: true
       }
     ],
-    returnType: 'void',
+    returnType: 'string[]',
     dangerous: true,
     isFrontendCommand: true
   },
   {
-    id: 'work_set_name_by_bms_optimized',
-    name: '根据 BMS 重命名工作目录（优化版）',
+    id: 'copy_dir',
+    name: '复制目录',
     category: CommandCategory.BMSFolder,
-    description: `根据 BMS 文件信息重命名工作目录，支持嵌套目录处理和相似度合并`,
+    description: `将源目录的内容递归复制到目标目录，冲突处理与移动相同，源目录保持不变`,
     parameters: [
       {
-        key: 'workDir',
+        key: 'fromDir',
         type: ParameterType.Directory,
         typeString: 'string',
         required: true,
-        description: `- 工作目录路径`
-      },
-      {
-        key: 'setType',
-        type: ParameterType.Enum,
-        typeString: 'BmsFolderSetNameType',
-        required: true,
-        description: `- 命名方式`
+        description: `- 源目录路径`
       },
       {
-        key: 'dryRun',
-        type: ParameterType.Boolean,
-        typeString: 'boolean',
+        key: 'toDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
         required: true,
-        description: `- 模拟运行（不实际执行）`,
-        defaultValue: true
+        description: `- 目标目录路径`
       },
       {
         key: 'replacePreset',
         type: ParameterType.Enum,
         typeString: 'ReplacePreset',
-        required: true,
-        description: `- 文件替换策略`,
+        required: false,
+        description: `- 替换策略`,
         defaultValue: "ReplacePreset.Default"
+      },
+      {
+        key: 'verify',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: false,
+        description: `- 复制后校验哈希`
+      },
+      {
+        key: 'dryRun',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: false,
+        description: `- 模拟运行（不实际复制）`,
+        defaultValue: true
       }
     ],
-    returnType: 'boolean',
+    returnType: 'CopyDirResult | null',
     dangerous: true,
     isFrontendCommand: true
   },
   {
-    id: 'root_root_set_name_by_bms',
-    name: '批量重命名工作目录',
-    category: CommandCategory.BMSFolder,
-    description: `递归为根目录下的所有工作目录设置名称`,
+    id: 'salvage_zip',
+    name: '抢救解压损坏的 ZIP',
+    category: CommandCategory.Pack,
+    description: `不读取中央目录，逐个扫描本地文件头解压，用于中央目录损坏、unzip 和 7z 都无法打开的压缩包，并报告丢失的文件`,
     parameters: [
       {
-        key: 'rootDir',
-        type: ParameterType.Directory,
+        key: 'file',
+        type: ParameterType.File,
         typeString: 'string',
         required: true,
-        description: `- 根目录路径`
+        description: `- ZIP 文件路径`
       },
       {
-        key: 'setType',
-        type: ParameterType.Enum,
-        typeString: 'BmsFolderSetNameType',
+        key: 'dest',
+        type: ParameterType.String,
+        typeString: 'string',
         required: true,
-        description: `- 命名方式`
-      },
-      {
-        key: 'dryRun',
-        type: ParameterType.Boolean,
-        typeString: 'boolean',
-        required: true,
-        description: `- 模拟运行（不实际执行）`,
-        defaultValue: true
-      },
+        description: `- 解压目标目录`
+      }
+    ],
+    returnType: 'ZipSalvageResult',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'normalize_permissions',
+    name: '整理文件权限',
+    category: CommandCategory.BMSFolder,
+    description: `递归整理目录中的文件权限：Windows 上清除只读属性，其他系统上将目录设为 755、文件设为 644（可在设置中修改），解决解压后无法移动或覆盖的问题`,
+    parameters: [
       {
-        key: 'replacePreset',
-        type: ParameterType.Enum,
-        typeString: 'ReplacePreset',
+        key: 'dir',
+        type: ParameterType.String,
+        typeString: 'string',
         required: true,
-        description: `- 文件替换策略`,
-        defaultValue: "ReplacePreset.Default"
+        description: `- 目录路径`
       }
     ],
     returnType: 'void',
@@ -1126,97 +1570,146 @@ export const GENERATED_COMMAND_REGISTRY: CommandDefinition[] = [
     isFrontendCommand: true
   },
   {
-    id: 'root_root_undo_set_name_by_bms',
-    name: '批量撤销重命名',
+    id: 'find_open_files',
+    name: '检查打开中的文件',
     category: CommandCategory.BMSFolder,
-    description: `递归撤销根目录下所有工作目录的名称设置`,
+    description: `列出目录中正被其他程序（如谱面编辑器）打开的文件；Unix 上使用 lsof，Windows 上检查谱面文件能否独占打开`,
     parameters: [
       {
-        key: 'rootDir',
+        key: 'dirPath',
         type: ParameterType.Directory,
         typeString: 'string',
         required: true,
-        description: `- 根目录路径`
+        description: `- 目录路径`
+      }
+    ],
+    returnType: 'OpenFileInfo[]',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'validate_target',
+    name: '检查目标目录',
+    category: CommandCategory.BMSFolder,
+    description: `开始耗时操作前检查目标目录能否写入和剩余空间，并按操作类型（复制、原始包 -> HQ、HQ -> LQ、双版本的 LQ 目录）估算空间是否足够`,
+    parameters: [
+      {
+        key: 'targetDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 目标目录路径`
       },
       {
-        key: 'setType',
+        key: 'operation',
         type: ParameterType.Enum,
-        typeString: 'BmsFolderSetNameType',
-        required: true,
-        description: `- 命名方式`
+        typeString: 'TargetOperation',
+        required: false,
+        description: `- 预计执行的操作`
       },
       {
-        key: 'dryRun',
-        type: ParameterType.Boolean,
-        typeString: 'boolean',
-        required: true,
-        description: `- 模拟运行（不实际执行）`,
-        defaultValue: true
+        key: 'sourceDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: false,
+        description: `- 源目录路径（估算需要的空间，可为空）`
       }
     ],
-    returnType: 'void',
-    dangerous: true,
+    returnType: 'TargetValidation',
+    dangerous: false,
     isFrontendCommand: true
   },
   {
-    id: 'root_copy_numbered_workdir_names',
-    name: '复制编号目录名称',
-    category: CommandCategory.BMSFolder,
-    description: `复制编号目录的名称到目标目录`,
+    id: 'work_get_media_info',
+    name: '获取媒体文件信息',
+    category: CommandCategory.Media,
+    description: `使用 ffprobe 获取音频或视频文件的详细信息`,
     parameters: [
       {
-        key: 'fromDir',
-        type: ParameterType.Directory,
+        key: 'filePath',
+        type: ParameterType.File,
         typeString: 'string',
         required: true,
-        description: `- 源目录路径`
-      },
+        description: `- 媒体文件路径`
+      }
+    ],
+    returnType: 'MediaInfo | null',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'work_get_video_info',
+    name: '获取视频信息',
+    category: CommandCategory.Media,
+    description: `获取视频文件的尺寸和比特率信息`,
+    parameters: [
       {
-        key: 'toDir',
-        type: ParameterType.Directory,
+        key: 'filePath',
+        type: ParameterType.File,
         typeString: 'string',
         required: true,
-        description: `- 目标目录路径`
-      },
+        description: `- 视频文件路径`
+      }
+    ],
+    returnType: 'VideoInfo | null',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'work_get_video_size',
+    name: '获取视频尺寸',
+    category: CommandCategory.Media,
+    description: `获取视频文件的宽度和高度`,
+    parameters: [
       {
-        key: 'dryRun',
-        type: ParameterType.Boolean,
-        typeString: 'boolean',
+        key: 'filePath',
+        type: ParameterType.File,
+        typeString: 'string',
         required: true,
-        description: `- 模拟运行（不实际执行）`,
-        defaultValue: true
+        description: `- 视频文件路径`
       }
     ],
-    returnType: 'void',
-    dangerous: true,
+    returnType: '{ width: number; height: number; } | null',
+    dangerous: false,
     isFrontendCommand: true
   },
   {
-    id: 'pack_setup_rawpack_to_hq',
-    name: '大包生成脚本：原包 -> HQ版大包',
-    category: CommandCategory.Pack,
-    description: `快速创建大包，从已编号的原始包到目标BMS文件夹`,
+    id: 'work_get_media_duration',
+    name: '获取媒体时长',
+    category: CommandCategory.Media,
+    description: `获取音频或视频文件的时长（秒）`,
     parameters: [
       {
-        key: 'packDir',
-        type: ParameterType.String,
+        key: 'filePath',
+        type: ParameterType.File,
         typeString: 'string',
         required: true,
-        description: `- 压缩包目录路径`
-      },
+        description: `- 媒体文件路径`
+      }
+    ],
+    returnType: 'number | null',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'work_remove_zero_sized_media_files',
+    name: '删除零字节媒体文件',
+    category: CommandCategory.BMSFolder,
+    description: `递归删除工作目录中所有零字节媒体文件`,
+    parameters: [
       {
-        key: 'rootDir',
-        type: ParameterType.Directory,
+        key: 'dir',
+        type: ParameterType.String,
         typeString: 'string',
         required: true,
-        description: `- 目标根目录路径`
+        description: `- 工作目录路径`
       },
       {
         key: 'dryRun',
         type: ParameterType.Boolean,
         typeString: 'boolean',
         required: true,
-        description: `- 模拟运行（不实际执行）`,
+        description: `- 模拟运行（不实际删除）`,
         defaultValue: true
       }
     ],
@@ -1225,50 +1718,43 @@ export const GENERATED_COMMAND_REGISTRY: CommandDefinition[] = [
     isFrontendCommand: true
   },
   {
-    id: 'pack_update_rawpack_to_hq',
-    name: '大包更新脚本：原包 -> HQ版大包',
-    category: CommandCategory.Pack,
-    description: `快速更新大包，从已编号的原始包到增量BMS文件夹`,
+    id: 'work_remove_unneed_media_files',
+    name: '删除重复格式媒体文件',
+    category: CommandCategory.BMSFolder,
+    description: `根据预设规则删除重复格式的媒体文件（保留高质量格式）`,
     parameters: [
-      {
-        key: 'packDir',
-        type: ParameterType.String,
-        typeString: 'string',
-        required: true,
-        description: `- 压缩包目录路径`
-      },
       {
         key: 'rootDir',
         type: ParameterType.Directory,
         typeString: 'string',
         required: true,
-        description: `- 增量根目录路径`
+        description: `- 根目录路径`
       },
       {
-        key: 'syncDir',
-        type: ParameterType.String,
-        typeString: 'string',
+        key: 'preset',
+        type: ParameterType.Enum,
+        typeString: 'RemoveMediaPreset',
         required: true,
-        description: `- 已存在的BMS文件夹路径（用于名称同步和文件检查）`
+        description: `- 清理规则预设`,
+        defaultValue: "RemoveMediaPreset.Oraja"
       },
       {
-        key: 'dryRun',
+        key: 'includeSplitBuckets',
         type: ParameterType.Boolean,
         typeString: 'boolean',
-        required: true,
-        description: `- 模拟运行（不实际执行）`,
-        defaultValue: true
+        required: false,
+        description: `- 同时处理已拆分的目录（如 "ABCD"、"Root [ABCD]"）中的作品`
       }
     ],
-    returnType: 'void',
+    returnType: 'MediaCleanupReport',
     dangerous: true,
     isFrontendCommand: true
   },
   {
-    id: 'pack_pack_hq_to_lq',
-    name: 'HQ 版本转 LQ 版本',
-    category: CommandCategory.Pack,
-    description: `将 HQ 版本转换为 LQ 版本，用于 LR2 玩家`,
+    id: 'work_transfer_audio',
+    name: '音频文件转换',
+    category: CommandCategory.Media,
+    description: `转换BMS根目录下的音频文件`,
     parameters: [
       {
         key: 'rootDir',
@@ -1278,30 +1764,57 @@ export const GENERATED_COMMAND_REGISTRY: CommandDefinition[] = [
         description: `- 根目录路径`
       },
       {
-        key: 'dryRun',
+        key: 'presetNames',
+        type: ParameterType.Enum,
+        typeString: 'AudioPreset[]',
+        required: true,
+        description: `- 目标格式预设名称`
+      },
+      {
+        key: 'removeOriginFileWhenSuccess',
         type: ParameterType.Boolean,
         typeString: 'boolean',
         required: true,
-        description: `- 模拟运行（不实际执行）`,
-        defaultValue: true
+        description: `- 成功时删除原文件（开启原文件保留时移入保留目录）`
+      },
+      {
+        key: 'removeOriginFileWhenFailed',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: true,
+        description: `- 失败时删除原文件`
+      },
+      {
+        key: 'skipOnFail',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: true,
+        description: `- 遇到失败时跳过`
+      },
+      {
+        key: 'preserveMetadata',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: false,
+        description: `- 保留标签和循环点（写成 OGG / FLAC 注释）`
       },
       {
         key: 'progressManager',
         type: ParameterType.Enum,
         typeString: 'IProgressManager',
         required: false,
-        description: ``
+        description: `- 进度管理器（可选）`
       }
     ],
-    returnType: 'void',
+    returnType: 'ConversionSummary',
     dangerous: true,
     isFrontendCommand: true
   },
   {
-    id: 'pack_pack_raw_to_hq',
-    name: 'BMS大包脚本：原包 -> HQ版大包',
-    category: CommandCategory.Pack,
-    description: `将Raw版本转换为HQ版本，用于beatoraja/Qwilight玩家`,
+    id: 'work_transfer_video',
+    name: '视频文件转换',
+    category: CommandCategory.Media,
+    description: `转换BMS根目录下的视频文件`,
     parameters: [
       {
         key: 'rootDir',
@@ -1311,28 +1824,1965 @@ export const GENERATED_COMMAND_REGISTRY: CommandDefinition[] = [
         description: `- 根目录路径`
       },
       {
-        key: 'dryRun',
+        key: 'presetNames',
+        type: ParameterType.Enum,
+        typeString: 'VideoPreset[]',
+        required: true,
+        description: `- 目标格式预设名称`
+      },
+      {
+        key: 'removeOriginFile',
         type: ParameterType.Boolean,
         typeString: 'boolean',
         required: true,
-        description: `- 模拟运行（不实际执行）`,
-        defaultValue: true
+        description: `- 成功时删除原文件（开启原文件保留时移入保留目录）`
       },
       {
-        key: 'progressManager',
-        type: ParameterType.Enum,
-        typeString: 'IProgressManager',
-        required: false,
-        description: `- 进度管理器（可选）`
-      }
-    ],
-    returnType: 'void',
+        key: 'removeExistingTargetFile',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: true,
+        description: `- 删除已存在的目标文件`
+      },
+      {
+        key: 'usePrefered',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: true,
+        description: `- 使用推荐预设`
+      },
+      {
+        key: 'progressManager',
+        type: ParameterType.Enum,
+        typeString: 'IProgressManager',
+        required: false,
+        description: `- 进度管理器（可选）`
+      }
+    ],
+    returnType: 'ConversionSummary',
+    dangerous: true,
+    isFrontendCommand: true
+  },
+  {
+    id: 'work_set_name_by_bms',
+    name: '根据 BMS 重命名工作目录',
+    category: CommandCategory.BMSFolder,
+    description: `根据 BMS 文件信息重命名工作目录，支持多种命名策略`,
+    parameters: [
+      {
+        key: 'workDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 工作目录路径`
+      },
+      {
+        key: 'setType',
+        type: ParameterType.Enum,
+        typeString: 'BmsFolderSetNameType',
+        required: true,
+        description: `- 命名方式`
+      },
+      {
+        key: 'dryRun',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: true,
+        description: `- 模拟运行（不实际执行）`,
+        defaultValue: true
+      },
+      {
+        key: 'replacePreset',
+        type: ParameterType.Enum,
+        typeString: 'ReplacePreset',
+        required: true,
+        description: `- 文件替换策略`,
+        defaultValue: "ReplacePreset.Default"
+      },
+      {
+        key: 'skipAlreadyFormatted',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: true,
+        description: `- 跳过已格式化的目录`
+      }
+    ],
+    returnType: 'DryRunPlan | null',
+    dangerous: true,
+    isFrontendCommand: true
+  },
+  {
+    id: 'work_undo_set_name_by_bms',
+    name: '撤销工作目录重命名',
+    category: CommandCategory.BMSFolder,
+    description: `撤销之前的目录重命名操作，恢复原始目录名`,
+    parameters: [
+      {
+        key: 'workDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 工作目录路径`
+      },
+      {
+        key: 'setType',
+        type: ParameterType.Enum,
+        typeString: 'BmsFolderSetNameType',
+        required: true,
+        description: `- 命名方式`
+      },
+      {
+        key: 'dryRun',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: true,
+        description: `- 模拟运行（不实际执行）`,
+        defaultValue: true
+      }
+    ],
+    returnType: 'void',
+    dangerous: true,
+    isFrontendCommand: true
+  },
+  {
+    id: 'work_append_artist_name_by_bms',
+    name: '追加艺术家名称',
+    category: CommandCategory.BMSFolder,
+    description: `在作品文件夹名后追加艺术家名称`,
+    parameters: [
+      {
+        key: 'rootDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 根目录路径`
+      },
+      {
+        key: 'dryRun',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: true,
+        description: `- 模拟运行（不实际执行）`,
+        defaultValue: true
+      }
+    ],
+    returnType: 'void',
+    dangerous: true,
+    isFrontendCommand: true
+  },
+  {
+    id: 'work_set_name_by_bms_optimized',
+    name: '根据 BMS 重命名工作目录（优化版）',
+    category: CommandCategory.BMSFolder,
+    description: `根据 BMS 文件信息重命名工作目录，支持嵌套目录处理和相似度合并`,
+    parameters: [
+      {
+        key: 'workDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 工作目录路径`
+      },
+      {
+        key: 'setType',
+        type: ParameterType.Enum,
+        typeString: 'BmsFolderSetNameType',
+        required: true,
+        description: `- 命名方式`
+      },
+      {
+        key: 'dryRun',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: true,
+        description: `- 模拟运行（不实际执行）`,
+        defaultValue: true
+      },
+      {
+        key: 'replacePreset',
+        type: ParameterType.Enum,
+        typeString: 'ReplacePreset',
+        required: true,
+        description: `- 文件替换策略`,
+        defaultValue: "ReplacePreset.Default"
+      }
+    ],
+    returnType: 'boolean',
+    dangerous: true,
+    isFrontendCommand: true
+  },
+  {
+    id: 'work_split_obj',
+    name: '分离 obj 谱面',
+    category: CommandCategory.BMSFolder,
+    description: `将他人制作的 obj 谱面及其独占的媒体文件移动到同级的 "<name> [obj]" 文件夹`,
+    parameters: [
+      {
+        key: 'workDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 作品目录路径`
+      },
+      {
+        key: 'copySharedMedia',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: true,
+        description: `- 同时复制共用的媒体文件，使分离出的文件夹可以单独游玩`
+      },
+      {
+        key: 'dryRun',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: true,
+        description: `- 模拟运行（不实际执行）`,
+        defaultValue: true
+      }
+    ],
+    returnType: 'SplitObjResult',
+    dangerous: true,
+    isFrontendCommand: true
+  },
+  {
+    id: 'work_merge_works',
+    name: '按谱面合并作品',
+    category: CommandCategory.BMSFolder,
+    description: `合并同一作品的两个版本：难度不同的同名谱面按难度改名，同一谱面的旧版本按所选方式处理`,
+    parameters: [
+      {
+        key: 'fromDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 源作品目录（合并后删除）`
+      },
+      {
+        key: 'toDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 目标作品目录`
+      },
+      {
+        key: 'strategy',
+        type: ParameterType.Enum,
+        typeString: 'ChartConflictStrategy',
+        required: true,
+        description: `- 同一谱面新旧版本的处理方式`
+      },
+      {
+        key: 'dryRun',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: true,
+        description: `- 模拟运行（不实际执行）`,
+        defaultValue: true
+      }
+    ],
+    returnType: 'ChartMergeResult',
     dangerous: true,
     isFrontendCommand: true
+  },
+  {
+    id: 'work_find_unreferenced_bga',
+    name: '查找未引用的 BGA 视频',
+    category: CommandCategory.BMSFolder,
+    description: `列出只有一个视频文件、但有谱面没有引用任何 BGA 的作品`,
+    parameters: [
+      {
+        key: 'rootDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 根目录路径`
+      }
+    ],
+    returnType: 'BgaMatchCandidate[]',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'work_add_missing_bga',
+    name: '补上 BGA 视频',
+    category: CommandCategory.BMSFolder,
+    description: `作品只有一个视频文件时，在没有 BGA 的谱面中加入 #BMPxx 定义和指定小节的 BGA 通道，修改前备份谱面`,
+    parameters: [
+      {
+        key: 'rootDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 根目录路径`
+      },
+      {
+        key: 'measure',
+        type: ParameterType.Number,
+        typeString: 'number',
+        required: true,
+        description: `- BGA 开始的小节号（0-999）`
+      },
+      {
+        key: 'dryRun',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: true,
+        description: `- 模拟运行（只列出要加入的行）`,
+        defaultValue: true
+      }
+    ],
+    returnType: 'BgaFixResult',
+    dangerous: true,
+    isFrontendCommand: true
+  },
+  {
+    id: 'work_normalize_chart_names',
+    name: '规范谱面文件名',
+    category: CommandCategory.BMSFolder,
+    description: `按 "<标题>_<难度>" 或原文件名重命名谱面，全角英数字转为半角、替换非法字符，重名时追加编号（谱面内容和哈希不变）`,
+    parameters: [
+      {
+        key: 'workDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 作品目录路径`
+      },
+      {
+        key: 'scheme',
+        type: ParameterType.Enum,
+        typeString: 'ChartNameScheme',
+        required: true,
+        description: `- 命名方式`
+      },
+      {
+        key: 'dryRun',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: true,
+        description: `- 模拟运行（只列出重命名）`,
+        defaultValue: true
+      }
+    ],
+    returnType: 'ChartRename[]',
+    dangerous: true,
+    isFrontendCommand: true
+  },
+  {
+    id: 'work_export_chart',
+    name: '导出单个谱面',
+    category: CommandCategory.BMSFolder,
+    description: `将谱面及其引用的媒体文件（自动匹配不同扩展名）复制到输出目录，生成只含该难度的最小文件夹`,
+    parameters: [
+      {
+        key: 'chartPath',
+        type: ParameterType.String,
+        typeString: 'string',
+        required: true,
+        description: `- 谱面文件路径`
+      },
+      {
+        key: 'outDir',
+        type: ParameterType.String,
+        typeString: 'string',
+        required: true,
+        description: `- 输出目录`
+      },
+      {
+        key: 'dryRun',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: true,
+        description: `- 模拟运行（只列出要复制的文件）`,
+        defaultValue: true
+      }
+    ],
+    returnType: 'ChartExportResult',
+    dangerous: true,
+    isFrontendCommand: true
+  },
+  {
+    id: 'work_pin',
+    name: '固定作品',
+    category: CommandCategory.BMSFolder,
+    description: `在作品目录中放置 .bms-toolbox-pin 标记，删除媒体文件、格式转换、合并等批量操作会跳过该作品`,
+    parameters: [
+      {
+        key: 'workDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 工作目录路径`
+      },
+      {
+        key: 'note',
+        type: ParameterType.String,
+        typeString: 'string',
+        required: false,
+        description: `- 备注（写入标记文件，如固定的原因）`
+      }
+    ],
+    returnType: 'void',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'work_unpin',
+    name: '取消固定作品',
+    category: CommandCategory.BMSFolder,
+    description: `删除作品目录中的 .bms-toolbox-pin 标记，批量操作将重新处理该作品`,
+    parameters: [
+      {
+        key: 'workDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 工作目录路径`
+      }
+    ],
+    returnType: 'void',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'work_list_pinned',
+    name: '列出固定的作品',
+    category: CommandCategory.BMSFolder,
+    description: `列出根目录中带有 .bms-toolbox-pin 标记、会被批量操作跳过的作品`,
+    parameters: [
+      {
+        key: 'rootDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 根目录路径`
+      }
+    ],
+    returnType: 'string[]',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'work_lint',
+    name: '检查作品',
+    category: CommandCategory.BMSFolder,
+    description: `检查谱面能否解析、标题和艺术家是否为空、引用的媒体文件是否存在、文件名是否合法以及零字节文件`,
+    parameters: [
+      {
+        key: 'workDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 作品目录路径`
+      }
+    ],
+    returnType: 'LintIssue[]',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'work_fix_case_collisions',
+    name: '修复大小写冲突',
+    category: CommandCategory.BMSFolder,
+    description: `将作品中仅大小写不同的文件（Windows 上解压时会互相覆盖）重命名为带编号的名称，并更新谱面中的引用`,
+    parameters: [
+      {
+        key: 'workDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 作品目录路径`
+      },
+      {
+        key: 'dryRun',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: true,
+        description: `- 模拟运行（不实际执行）`,
+        defaultValue: true
+      }
+    ],
+    returnType: 'CaseCollisionFix',
+    dangerous: true,
+    isFrontendCommand: true
+  },
+  {
+    id: 'work_summarize_bga',
+    name: '统计 BGA',
+    category: CommandCategory.BMSFolder,
+    description: `判断作品的 BGA 是视频还是图片序列，比较 BGA 时长和歌曲长度，读取分辨率（需要 ffprobe）`,
+    parameters: [
+      {
+        key: 'workDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 作品目录路径`
+      }
+    ],
+    returnType: 'BgaSummary',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'work_apply_chart_patch',
+    name: '应用谱面补丁',
+    category: CommandCategory.BMSFolder,
+    description: `将只包含谱面（可附带新增音频）的更新包解压到作品目录：备份被替换的谱面，检查引用的音频和图片都存在，并记录到导入来源中`,
+    parameters: [
+      {
+        key: 'archivePath',
+        type: ParameterType.String,
+        typeString: 'string',
+        required: true,
+        description: `- 补丁压缩包路径`
+      },
+      {
+        key: 'workDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 作品目录路径`
+      },
+      {
+        key: 'force',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: false,
+        description: `- 补丁已应用过或引用的文件缺失时仍然应用`
+      },
+      {
+        key: 'dryRun',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: false,
+        description: `- 模拟运行（不实际执行）`,
+        defaultValue: true
+      }
+    ],
+    returnType: 'ChartPatchResult',
+    dangerous: true,
+    isFrontendCommand: true
+  },
+  {
+    id: 'root_root_set_name_by_bms',
+    name: '批量重命名工作目录',
+    category: CommandCategory.BMSFolder,
+    description: `递归为根目录下的所有工作目录设置名称`,
+    parameters: [
+      {
+        key: 'rootDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 根目录路径`
+      },
+      {
+        key: 'setType',
+        type: ParameterType.Enum,
+        typeString: 'BmsFolderSetNameType',
+        required: true,
+        description: `- 命名方式`
+      },
+      {
+        key: 'dryRun',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: true,
+        description: `- 模拟运行（不实际执行）`,
+        defaultValue: true
+      },
+      {
+        key: 'replacePreset',
+        type: ParameterType.Enum,
+        typeString: 'ReplacePreset',
+        required: true,
+        description: `- 文件替换策略`,
+        defaultValue: "ReplacePreset.Default"
+      },
+      {
+        key: 'resume',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: false,
+        description: `- 从上次中断的位置继续`
+      },
+      {
+        key: 'includeSplitBuckets',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: false,
+        description: `- 同时处理已拆分的目录（如 "ABCD"、"Root [ABCD]"）中的作品`
+      }
+    ],
+    returnType: 'DryRunPlan | null',
+    dangerous: true,
+    isFrontendCommand: true
+  },
+  {
+    id: 'root_root_undo_set_name_by_bms',
+    name: '批量撤销重命名',
+    category: CommandCategory.BMSFolder,
+    description: `递归撤销根目录下所有工作目录的名称设置`,
+    parameters: [
+      {
+        key: 'rootDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 根目录路径`
+      },
+      {
+        key: 'setType',
+        type: ParameterType.Enum,
+        typeString: 'BmsFolderSetNameType',
+        required: true,
+        description: `- 命名方式`
+      },
+      {
+        key: 'dryRun',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: true,
+        description: `- 模拟运行（不实际执行）`,
+        defaultValue: true
+      },
+      {
+        key: 'includeSplitBuckets',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: false,
+        description: `- 同时处理已拆分的目录（如 "ABCD"、"Root [ABCD]"）中的作品`
+      }
+    ],
+    returnType: 'void',
+    dangerous: true,
+    isFrontendCommand: true
+  },
+  {
+    id: 'root_copy_numbered_workdir_names',
+    name: '复制编号目录名称',
+    category: CommandCategory.BMSFolder,
+    description: `复制编号目录的名称到目标目录`,
+    parameters: [
+      {
+        key: 'fromDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 源目录路径`
+      },
+      {
+        key: 'toDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 目标目录路径`
+      },
+      {
+        key: 'dryRun',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: true,
+        description: `- 模拟运行（不实际执行）`,
+        defaultValue: true
+      }
+    ],
+    returnType: 'void',
+    dangerous: true,
+    isFrontendCommand: true
+  },
+  {
+    id: 'root_compare_roots',
+    name: '对比根目录',
+    category: CommandCategory.BMSFolder,
+    description: `对比两个根目录，按目录名和谱面哈希分为：仅 A 有、仅 B 有、两边都有但有差异`,
+    parameters: [
+      {
+        key: 'rootDirA',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 根目录 A`
+      },
+      {
+        key: 'rootDirB',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 根目录 B`
+      }
+    ],
+    returnType: 'RootCompareResult',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'root_watch_library_root',
+    name: '监视根目录',
+    category: CommandCategory.BMSFolder,
+    description: `监视根目录中文件夹的新增、删除和重命名，并实时通知界面`,
+    parameters: [
+      {
+        key: 'rootDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 根目录路径`
+      }
+    ],
+    returnType: 'string[]',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'root_unwatch_library_root',
+    name: '停止监视根目录',
+    category: CommandCategory.BMSFolder,
+    description: `停止监视根目录的外部变更`,
+    parameters: [
+      {
+        key: 'rootDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 根目录路径`
+      }
+    ],
+    returnType: 'string[]',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'root_freeze_works',
+    name: '冻结作品',
+    category: CommandCategory.BMSFolder,
+    description: `将最后修改时间早于指定日期的作品压缩为 7z 归档并删除原目录（需要 7z）`,
+    parameters: [
+      {
+        key: 'rootDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 根目录路径`
+      },
+      {
+        key: 'olderThan',
+        type: ParameterType.String,
+        typeString: 'string',
+        required: true,
+        description: `- 日期（如 2024-01-01），早于该日期的作品会被冻结`
+      },
+      {
+        key: 'dryRun',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: true,
+        description: `- 模拟运行（不实际执行）`,
+        defaultValue: true
+      }
+    ],
+    returnType: 'FrozenWorkEntry[]',
+    dangerous: true,
+    isFrontendCommand: true
+  },
+  {
+    id: 'root_unfreeze_works',
+    name: '解冻作品',
+    category: CommandCategory.BMSFolder,
+    description: `将已冻结的作品从 7z 归档还原到根目录（作品名留空时解冻全部）`,
+    parameters: [
+      {
+        key: 'rootDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 根目录路径`
+      },
+      {
+        key: 'workName',
+        type: ParameterType.String,
+        typeString: 'string',
+        required: true,
+        description: `- 作品目录名（留空表示全部）`
+      },
+      {
+        key: 'dryRun',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: true,
+        description: `- 模拟运行（不实际执行）`,
+        defaultValue: true
+      }
+    ],
+    returnType: 'string[]',
+    dangerous: true,
+    isFrontendCommand: true
+  },
+  {
+    id: 'root_list_frozen_works',
+    name: '查看冻结作品',
+    category: CommandCategory.BMSFolder,
+    description: `列出根目录中已冻结的作品及其信息`,
+    parameters: [
+      {
+        key: 'rootDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 根目录路径`
+      }
+    ],
+    returnType: 'FrozenWorkEntry[]',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'root_promote',
+    name: '活动作品并入曲库',
+    category: CommandCategory.BMSFolder,
+    description: `按谱面哈希、目录名或标题匹配曲库中的作品，替换旧谱面并合并新键音，新作品直接移入曲库`,
+    parameters: [
+      {
+        key: 'fromDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 活动根目录`
+      },
+      {
+        key: 'toDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 曲库根目录`
+      },
+      {
+        key: 'dryRun',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: true,
+        description: `- 模拟运行（不实际执行）`,
+        defaultValue: true
+      }
+    ],
+    returnType: 'PromoteResult',
+    dangerous: true,
+    isFrontendCommand: true
+  },
+  {
+    id: 'root_audit_names',
+    name: '检查文件夹名',
+    category: CommandCategory.BMSFolder,
+    description: `检查非法字符、结尾的点或空格、保留名称、超过 255 字节、全半角括号混用和只有大小写不同的文件夹，给出修改方案（不修改文件夹）`,
+    parameters: [
+      {
+        key: 'rootDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 根目录路径`
+      }
+    ],
+    returnType: 'FolderNameFix[]',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'root_enforce_profile',
+    name: '按规范整理媒体格式',
+    category: CommandCategory.BMSFolder,
+    description: `检查每个作品的音视频格式是否符合目标规范（beatoraja HQ / LR2 LQ），删除已有规范格式的多余文件并转换其余文件；无法转换的文件只报告`,
+    parameters: [
+      {
+        key: 'rootDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 根目录路径`
+      },
+      {
+        key: 'profile',
+        type: ParameterType.Enum,
+        typeString: 'MediaProfile',
+        required: true,
+        description: `- 目标规范`
+      },
+      {
+        key: 'dryRun',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: true,
+        description: `- 模拟运行（只报告不符合规范的文件）`,
+        defaultValue: true
+      }
+    ],
+    returnType: 'ProfileReport',
+    dangerous: true,
+    isFrontendCommand: true
+  },
+  {
+    id: 'root_find_orphan_charts',
+    name: '检查散落谱面',
+    category: CommandCategory.BMSFolder,
+    description: `找出直接放在根目录（而不是作品文件夹）中的谱面文件，按标题分组并给出新建文件夹的方案`,
+    parameters: [
+      {
+        key: 'rootDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 根目录路径`
+      }
+    ],
+    returnType: 'OrphanChartGroup[]',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'root_fix_orphan_charts',
+    name: '整理散落谱面',
+    category: CommandCategory.BMSFolder,
+    description: `为直接放在根目录中的谱面按标题新建作品文件夹，并将谱面和同名的媒体文件移入`,
+    parameters: [
+      {
+        key: 'rootDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 根目录路径`
+      },
+      {
+        key: 'dryRun',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: true,
+        description: `- 模拟运行（不实际执行）`,
+        defaultValue: true
+      }
+    ],
+    returnType: 'OrphanChartGroup[]',
+    dangerous: true,
+    isFrontendCommand: true
+  },
+  {
+    id: 'root_find_convertibles',
+    name: '查找转换候选',
+    category: CommandCategory.BMSFolder,
+    description: `统计各扩展名的数量和大小，列出仍有 WAV、只有 FLAC 没有 OGG、或有超过指定大小 MP4 的作品，并估计转换可节省的空间`,
+    parameters: [
+      {
+        key: 'rootDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 根目录路径`
+      },
+      {
+        key: 'mp4SizeLimitMb',
+        type: ParameterType.Number,
+        typeString: 'number',
+        required: false,
+        description: `- MP4 超过该大小（MB）时列为缩小 BGA 的候选`
+      },
+      {
+        key: 'audioSamplesPerWork',
+        type: ParameterType.Number,
+        typeString: 'number',
+        required: false,
+        description: `- 每个作品用 ffprobe 抽查的音源数（0 表示不检查采样率和位深）`
+      }
+    ],
+    returnType: 'ConvertiblesReport',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'root_find_swapped_fields',
+    name: '检查标题和艺术家互换',
+    category: CommandCategory.BMSFolder,
+    description: `找出标题与其他作品的艺术家相同、标题含有 feat. 等写法、或艺术家含有难度括号的作品，这些作品的 #TITLE 和 #ARTIST 可能写反了`,
+    parameters: [
+      {
+        key: 'rootDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 根目录路径`
+      }
+    ],
+    returnType: 'SwappedFieldsSuspect[]',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'root_fix_swapped_fields',
+    name: '修正标题和艺术家互换',
+    category: CommandCategory.BMSFolder,
+    description: `对检查出的作品（或指定的作品）交换所有谱面中的 #TITLE 和 #ARTIST，修改前备份谱面，然后按新的标题和艺术家重新命名文件夹`,
+    parameters: [
+      {
+        key: 'rootDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 根目录路径`
+      },
+      {
+        key: 'works',
+        type: ParameterType.String,
+        typeString: 'string[]',
+        required: true,
+        description: `- 要修正的作品文件夹名（留空表示全部检查出的作品）`
+      },
+      {
+        key: 'setType',
+        type: ParameterType.Enum,
+        typeString: 'BmsFolderSetNameType',
+        required: true,
+        description: `- 重新命名的方式`
+      },
+      {
+        key: 'replacePreset',
+        type: ParameterType.Enum,
+        typeString: 'ReplacePreset',
+        required: true,
+        description: `- 目标文件夹已存在时的文件替换策略`,
+        defaultValue: "ReplacePreset.Default"
+      },
+      {
+        key: 'dryRun',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: true,
+        description: `- 模拟运行（不实际执行）`,
+        defaultValue: true
+      }
+    ],
+    returnType: 'SwappedFieldsSuspect[]',
+    dangerous: true,
+    isFrontendCommand: true
+  },
+  {
+    id: 'root_audio_report',
+    name: '音源采样率和位深检查',
+    category: CommandCategory.BMSFolder,
+    description: `对每个作品抽样若干个音源用 ffprobe 检查，列出含有超过指定采样率或位深（如 96kHz、24 位）音源的作品，部分播放器对这类音源重采样效果很差`,
+    parameters: [
+      {
+        key: 'rootDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 根目录路径`
+      },
+      {
+        key: 'samplesPerWork',
+        type: ParameterType.Number,
+        typeString: 'number',
+        required: false,
+        description: `- 每个作品抽样检查的音源数`
+      },
+      {
+        key: 'maxSampleRate',
+        type: ParameterType.Number,
+        typeString: 'number',
+        required: false,
+        description: `- 采样率上限（Hz）`
+      },
+      {
+        key: 'maxBitDepth',
+        type: ParameterType.Number,
+        typeString: 'number',
+        required: false,
+        description: `- 位深上限`
+      }
+    ],
+    returnType: 'AudioReport',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'root_check_remove_media_compat',
+    name: '预览媒体清理的播放器兼容性',
+    category: CommandCategory.BMSFolder,
+    description: `应用媒体清理规则前，按每个作品清理后剩余的格式，列出 LR2 / beatoraja / Qwilight 将无法播放的文件（如只保留 MP4 时 LR2 无法播放 BGA）`,
+    parameters: [
+      {
+        key: 'rootDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 根目录路径`
+      },
+      {
+        key: 'preset',
+        type: ParameterType.Enum,
+        typeString: 'RemoveMediaPreset',
+        required: true,
+        description: `- 清理规则预设`,
+        defaultValue: "RemoveMediaPreset.Oraja"
+      },
+      {
+        key: 'players',
+        type: ParameterType.String,
+        typeString: 'string[]',
+        required: false,
+        description: `- 目标播放器（lr2 / beatoraja / qwilight，每行一个，为空时检查全部）`
+      }
+    ],
+    returnType: 'MediaCompatReport',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'pack_setup_rawpack_to_hq',
+    name: '大包生成脚本：原包 -> HQ版大包',
+    category: CommandCategory.Pack,
+    description: `快速创建大包，从已编号的原始包到目标BMS文件夹`,
+    parameters: [
+      {
+        key: 'packDir',
+        type: ParameterType.String,
+        typeString: 'string',
+        required: true,
+        description: `- 压缩包目录路径`
+      },
+      {
+        key: 'rootDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 目标根目录路径`
+      },
+      {
+        key: 'dryRun',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: true,
+        description: `- 模拟运行（不实际执行）`,
+        defaultValue: true
+      },
+      {
+        key: 'progressManager',
+        type: ParameterType.Enum,
+        typeString: 'IProgressManager',
+        required: false,
+        description: `- 进度管理器（可选）`
+      }
+    ],
+    returnType: 'PipelineChangeset',
+    dangerous: true,
+    isFrontendCommand: true
+  },
+  {
+    id: 'pack_update_rawpack_to_hq',
+    name: '大包更新脚本：原包 -> HQ版大包',
+    category: CommandCategory.Pack,
+    description: `快速更新大包，从已编号的原始包到增量BMS文件夹`,
+    parameters: [
+      {
+        key: 'packDir',
+        type: ParameterType.String,
+        typeString: 'string',
+        required: true,
+        description: `- 压缩包目录路径`
+      },
+      {
+        key: 'rootDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 增量根目录路径`
+      },
+      {
+        key: 'syncDir',
+        type: ParameterType.String,
+        typeString: 'string',
+        required: true,
+        description: `- 已存在的BMS文件夹路径（用于名称同步和文件检查）`
+      },
+      {
+        key: 'dryRun',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: true,
+        description: `- 模拟运行（不实际执行）`,
+        defaultValue: true
+      },
+      {
+        key: 'progressManager',
+        type: ParameterType.Enum,
+        typeString: 'IProgressManager',
+        required: false,
+        description: `- 进度管理器（可选）`
+      }
+    ],
+    returnType: 'PipelineChangeset',
+    dangerous: true,
+    isFrontendCommand: true
+  },
+  {
+    id: 'pack_pack_hq_to_lq',
+    name: 'HQ 版本转 LQ 版本',
+    category: CommandCategory.Pack,
+    description: `将 HQ 版本转换为 LQ 版本，用于 LR2 玩家`,
+    parameters: [
+      {
+        key: 'rootDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 根目录路径`
+      },
+      {
+        key: 'dryRun',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: true,
+        description: `- 模拟运行（不实际执行）`,
+        defaultValue: true
+      },
+      {
+        key: 'progressManager',
+        type: ParameterType.Enum,
+        typeString: 'IProgressManager',
+        required: false,
+        description: `- 进度管理器（可选）`
+      }
+    ],
+    returnType: 'PipelineChangeset',
+    dangerous: true,
+    isFrontendCommand: true
+  },
+  {
+    id: 'pack_pack_raw_to_hq',
+    name: 'BMS大包脚本：原包 -> HQ版大包',
+    category: CommandCategory.Pack,
+    description: `将Raw版本转换为HQ版本，用于beatoraja/Qwilight玩家`,
+    parameters: [
+      {
+        key: 'rootDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 根目录路径`
+      },
+      {
+        key: 'dryRun',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: true,
+        description: `- 模拟运行（不实际执行）`,
+        defaultValue: true
+      },
+      {
+        key: 'progressManager',
+        type: ParameterType.Enum,
+        typeString: 'IProgressManager',
+        required: false,
+        description: `- 进度管理器（可选）`
+      }
+    ],
+    returnType: 'PipelineChangeset',
+    dangerous: true,
+    isFrontendCommand: true
+  },
+  {
+    id: 'pack_pack_dual_build',
+    name: '大包双版本生成：原包 -> HQ + LQ',
+    category: CommandCategory.Pack,
+    description: `从已编号的原始包生成 HQ 版大包，再把 HQ 版复制（同一磁盘上使用硬链接）到另一个目录转换为 LQ 版，HQ 版保持不变`,
+    parameters: [
+      {
+        key: 'packDir',
+        type: ParameterType.String,
+        typeString: 'string',
+        required: true,
+        description: `- 压缩包目录路径`
+      },
+      {
+        key: 'hqDir',
+        type: ParameterType.String,
+        typeString: 'string',
+        required: true,
+        description: `- HQ 版输出目录`
+      },
+      {
+        key: 'lqDir',
+        type: ParameterType.String,
+        typeString: 'string',
+        required: true,
+        description: `- LQ 版输出目录（不能已存在内容）`
+      },
+      {
+        key: 'dryRun',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: true,
+        description: `- 模拟运行（不实际执行）`,
+        defaultValue: true
+      },
+      {
+        key: 'progressManager',
+        type: ParameterType.Enum,
+        typeString: 'IProgressManager',
+        required: false,
+        description: `- 进度管理器（可选）`
+      }
+    ],
+    returnType: 'DualBuildResult',
+    dangerous: true,
+    isFrontendCommand: true
+  },
+  {
+    id: 'pack_list_history',
+    name: '查看运行历史',
+    category: CommandCategory.Pack,
+    description: `按时间倒序列出流水线的运行记录摘要（新增、删除、重命名的作品和删除的文件大小）`,
+    parameters: [
+      {
+        key: 'limit',
+        type: ParameterType.Number,
+        typeString: 'number',
+        required: false,
+        description: `- 最多列出的记录数`
+      }
+    ],
+    returnType: 'PipelineRunSummary[]',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'pack_show_history',
+    name: '查看运行记录',
+    category: CommandCategory.Pack,
+    description: `查看一次流水线运行的完整变更记录`,
+    parameters: [
+      {
+        key: 'id',
+        type: ParameterType.String,
+        typeString: 'string',
+        required: true,
+        description: `- 运行 ID（见运行历史）`
+      }
+    ],
+    returnType: 'PipelineChangeset | null',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'library_query',
+    name: '查询作品库',
+    category: CommandCategory.Library,
+    description: `按标题、艺术家、键位、等级范围、标签和 BGA 筛选已索引的作品，支持排序和分页`,
+    parameters: [
+      {
+        key: 'rootDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: false,
+        description: `- 根目录路径（留空查询所有已索引的根目录）`
+      },
+      {
+        key: 'title',
+        type: ParameterType.String,
+        typeString: 'string',
+        required: false,
+        description: `- 标题包含的文字（不区分大小写，也匹配文件夹名）`
+      },
+      {
+        key: 'artist',
+        type: ParameterType.String,
+        typeString: 'string',
+        required: false,
+        description: `- 艺术家包含的文字（不区分大小写）`
+      },
+      {
+        key: 'keymode',
+        type: ParameterType.String,
+        typeString: 'string',
+        required: false,
+        description: `- 键位（如 7K、14K）`
+      },
+      {
+        key: 'minLevel',
+        type: ParameterType.Number,
+        typeString: 'number',
+        required: false,
+        description: `- 最低等级`
+      },
+      {
+        key: 'maxLevel',
+        type: ParameterType.Number,
+        typeString: 'number',
+        required: false,
+        description: `- 最高等级`
+      },
+      {
+        key: 'tags',
+        type: ParameterType.String,
+        typeString: 'string',
+        required: false,
+        description: `- 必须包含的标签（逗号分隔）`
+      },
+      {
+        key: 'bga',
+        type: ParameterType.Enum,
+        typeString: 'LibraryBgaFilter',
+        required: false,
+        description: `- BGA 筛选（完整 BGA 需要先统计作品库 BGA）`
+      },
+      {
+        key: 'sortBy',
+        type: ParameterType.Enum,
+        typeString: 'LibrarySortKey',
+        required: false,
+        description: `- 排序字段`
+      },
+      {
+        key: 'descending',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: false,
+        description: `- 是否降序`
+      },
+      {
+        key: 'offset',
+        type: ParameterType.Number,
+        typeString: 'number',
+        required: false,
+        description: `- 跳过的作品数`
+      },
+      {
+        key: 'limit',
+        type: ParameterType.Number,
+        typeString: 'number',
+        required: false,
+        description: `- 返回的作品数`
+      }
+    ],
+    returnType: 'LibraryQueryResult',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'library_set_work_tags',
+    name: '设置作品标签',
+    category: CommandCategory.Library,
+    description: `为作品设置标签（逗号分隔，留空清除），可在作品库查询中按标签筛选`,
+    parameters: [
+      {
+        key: 'workDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 作品目录路径`
+      },
+      {
+        key: 'tags',
+        type: ParameterType.String,
+        typeString: 'string',
+        required: true,
+        description: `- 标签（逗号分隔）`
+      }
+    ],
+    returnType: 'string[]',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'library_index_root',
+    name: '更新作品索引',
+    category: CommandCategory.Library,
+    description: `读取根目录中各作品的标题、键位、等级和 BGA 信息，只重新读取修改过的作品`,
+    parameters: [
+      {
+        key: 'rootDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 根目录路径`
+      },
+      {
+        key: 'force',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: false,
+        description: `- 忽略修改时间，重新读取所有作品`
+      }
+    ],
+    returnType: 'LibraryIndexSummary',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'library_remove_index',
+    name: '删除作品索引',
+    category: CommandCategory.Library,
+    description: `从作品库中移除根目录的索引（不影响磁盘上的文件）`,
+    parameters: [
+      {
+        key: 'rootDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 根目录路径`
+      }
+    ],
+    returnType: 'boolean',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'library_search',
+    name: '搜索作品库',
+    category: CommandCategory.Library,
+    description: `在已索引作品的标题、副标题、艺术家、流派和文件夹名中搜索，假名可以用罗马字搜索`,
+    parameters: [
+      {
+        key: 'text',
+        type: ParameterType.String,
+        typeString: 'string',
+        required: true,
+        description: `- 搜索文字（空格分隔多个词）`
+      },
+      {
+        key: 'rootDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: false,
+        description: `- 根目录路径（留空搜索所有已索引的根目录）`
+      },
+      {
+        key: 'limit',
+        type: ParameterType.Number,
+        typeString: 'number',
+        required: false,
+        description: `- 最多返回的作品数`
+      }
+    ],
+    returnType: 'LibrarySearchHit[]',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'library_scrub',
+    name: '完整性校验',
+    category: CommandCategory.Library,
+    description: `重新读取根目录下所有文件并与上次记录的哈希比较，找出修改时间未变但内容已变的文件（位衰减）`,
+    parameters: [
+      {
+        key: 'rootDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 根目录路径`
+      },
+      {
+        key: 'acceptChanges',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: false,
+        description: `- 将损坏文件的当前内容记入基线（确认已处理后使用）`
+      }
+    ],
+    returnType: 'ScrubReport',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'library_set_scrub_schedule',
+    name: '设置定期完整性校验',
+    category: CommandCategory.Library,
+    description: `设置根目录的完整性校验间隔（天），0 表示取消；到期的校验由“运行到期的完整性校验”执行`,
+    parameters: [
+      {
+        key: 'rootDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 根目录路径`
+      },
+      {
+        key: 'intervalDays',
+        type: ParameterType.Number,
+        typeString: 'number',
+        required: true,
+        description: `- 校验间隔（天）`
+      }
+    ],
+    returnType: 'ScrubSchedule',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'library_scrub_due',
+    name: '运行到期的完整性校验',
+    category: CommandCategory.Library,
+    description: `对所有已到校验间隔的根目录运行完整性校验（可在启动时或定时调用）`,
+    parameters: [
+
+    ],
+    returnType: 'ScrubReport[]',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'library_changes',
+    name: '查看作品库变化',
+    category: CommandCategory.Library,
+    description: `比较当前索引与上一次（或指定日期前）的索引快照，列出新增、删除、重命名和内容有变化的作品，可用于编写更新日志`,
+    parameters: [
+      {
+        key: 'rootDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 根目录路径`
+      },
+      {
+        key: 'since',
+        type: ParameterType.String,
+        typeString: 'string',
+        required: false,
+        description: `- 比较基准："last" 表示上一次索引，或填写日期（如 2024-01-31）`
+      },
+      {
+        key: 'refresh',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: false,
+        description: `- 先更新索引再比较`
+      }
+    ],
+    returnType: 'LibraryChanges',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'library_shared_sound_report',
+    name: '共用音源报告',
+    category: CommandCategory.Library,
+    description: `按索引中的作品计算音频哈希，将相同音频比例超过阈值的作品聚类，列出可硬链接节省的空间，也可用于发现以新标题重新上传的作品`,
+    parameters: [
+      {
+        key: 'rootDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 根目录路径`
+      },
+      {
+        key: 'minSharedPercent',
+        type: ParameterType.Number,
+        typeString: 'number',
+        required: false,
+        description: `- 相同音频占较少一方的比例（%）达到该值时视为共用音源`
+      },
+      {
+        key: 'refresh',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: false,
+        description: `- 先更新索引再比较`
+      }
+    ],
+    returnType: 'SharedSoundReport',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'library_export_index',
+    name: '导出作品索引',
+    category: CommandCategory.Library,
+    description: `将根目录的作品索引（含副艺术家、注释、作者 URL 和邮箱）导出为 CSV 或 JSON（按文件扩展名），用于编写活动一览表或难度表；不覆盖已有文件`,
+    parameters: [
+      {
+        key: 'rootDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 根目录路径`
+      },
+      {
+        key: 'filePath',
+        type: ParameterType.File,
+        typeString: 'string',
+        required: true,
+        description: `- 输出文件路径（.json 输出 JSON，其他输出 CSV；文件已存在时报错）`
+      },
+      {
+        key: 'refresh',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: false,
+        description: `- 先强制重新索引（旧版索引中没有注释和 URL 等字段）`
+      }
+    ],
+    returnType: 'number',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'library_enrich_from_bms_search',
+    name: '从 bmssearch 补充作品信息',
+    category: CommandCategory.Library,
+    description: `按谱面哈希（查不到时按标题和艺术家）在 bmssearch.net 上查询索引中的作品，记录规范的标题、艺术家和首次发布的活动`,
+    parameters: [
+      {
+        key: 'rootDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 根目录路径`
+      },
+      {
+        key: 'onlyMissing',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: false,
+        description: `- 只查询尚未查询过的作品`
+      }
+    ],
+    returnType: 'number',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'library_index_bga',
+    name: '统计作品库 BGA',
+    category: CommandCategory.Library,
+    description: `统计索引中每个作品的 BGA 类型（视频或图片序列）、时长与歌曲长度的比例和分辨率，记录到索引中，用于筛选 BGA 完整的作品`,
+    parameters: [
+      {
+        key: 'rootDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 根目录路径`
+      },
+      {
+        key: 'onlyMissing',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: false,
+        description: `- 只统计尚未统计过的作品`
+      }
+    ],
+    returnType: 'number',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'logs_open_folder',
+    name: '打开日志文件夹',
+    category: CommandCategory.Logs,
+    description: `在文件管理器中打开保存运行日志的文件夹`,
+    parameters: [
+
+    ],
+    returnType: 'void',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'logs_export_archive',
+    name: '导出日志',
+    category: CommandCategory.Logs,
+    description: `将所有运行日志打包为 ZIP 文件保存到输出目录，用于反馈问题`,
+    parameters: [
+      {
+        key: 'outputDir',
+        type: ParameterType.Directory,
+        typeString: 'string',
+        required: true,
+        description: `- 输出目录`
+      }
+    ],
+    returnType: 'string',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'cancel_job',
+    name: '取消任务',
+    category: CommandCategory.Jobs,
+    description: `取消运行中的任务：批量处理在当前文件或目录处理完后停止，正在运行的 ffmpeg 等外部进程被终止，未完成的输出被丢弃`,
+    parameters: [
+      {
+        key: 'jobId',
+        type: ParameterType.String,
+        typeString: 'string',
+        required: true,
+        description: `- 任务 ID（也可以是命令 ID，取消该命令所有运行中的任务）`
+      }
+    ],
+    returnType: 'string[]',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'list_jobs',
+    name: '列出任务',
+    category: CommandCategory.Jobs,
+    description: `列出正在运行的任务（命令、目标目录、进度和开始时间），任务 ID 可用于查看或取消任务`,
+    parameters: [
+      {
+        key: 'includeFinished',
+        type: ParameterType.Boolean,
+        typeString: 'boolean',
+        required: false,
+        description: `- 同时列出最近结束的任务`
+      }
+    ],
+    returnType: 'JobSummary[]',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'get_job',
+    name: '查看任务',
+    category: CommandCategory.Jobs,
+    description: `查看任务的状态、进度、目标目录和错误信息`,
+    parameters: [
+      {
+        key: 'jobId',
+        type: ParameterType.String,
+        typeString: 'string',
+        required: true,
+        description: `- 任务 ID`
+      }
+    ],
+    returnType: 'JobSummary | null',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'get_resource_stats',
+    name: '查看资源使用',
+    category: CommandCategory.Logs,
+    description: `查看正在运行的外部进程数（ffmpeg 等）、并发池中排队和运行的任务数，以及读写速度，用于调整并发设置`,
+    parameters: [
+
+    ],
+    returnType: 'ResourceStats',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'get_settings',
+    name: '查看设置',
+    category: CommandCategory.Settings,
+    description: `查看当前的全部设置及设置文件位置`,
+    parameters: [
+
+    ],
+    returnType: 'AppSettings',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'load_settings',
+    name: '重新读取设置',
+    category: CommandCategory.Settings,
+    description: `重新读取设置文件（手动编辑 settings.json 后使用）并应用到各模块`,
+    parameters: [
+
+    ],
+    returnType: 'AppSettings',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'get_setting',
+    name: '查看设置项',
+    category: CommandCategory.Settings,
+    description: `查看一项设置的当前值（如 concurrency、executables、audioPresets）`,
+    parameters: [
+      {
+        key: 'key',
+        type: ParameterType.String,
+        typeString: 'string',
+        required: true,
+        description: `- 设置项名称`
+      }
+    ],
+    returnType: 'unknown',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'set_setting',
+    name: '修改设置项',
+    category: CommandCategory.Settings,
+    description: `修改一项设置并保存到设置文件，值按 JSON 解析，为空时恢复默认值`,
+    parameters: [
+      {
+        key: 'key',
+        type: ParameterType.String,
+        typeString: 'string',
+        required: true,
+        description: `- 设置项名称`
+      },
+      {
+        key: 'value',
+        type: ParameterType.String,
+        typeString: 'string',
+        required: true,
+        description: `- 新的值（JSON）`
+      }
+    ],
+    returnType: 'AppSettings',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'history_list',
+    name: '最近使用的目录',
+    category: CommandCategory.Settings,
+    description: `列出最近用作根目录、作品目录、压缩包目录或缓存目录的路径（最近的在前）`,
+    parameters: [
+      {
+        key: 'kind',
+        type: ParameterType.String,
+        typeString: 'string',
+        required: true,
+        description: `- 目录用途（root / work / pack / cache）`
+      }
+    ],
+    returnType: 'string[]',
+    dangerous: false,
+    isFrontendCommand: true
+  },
+  {
+    id: 'history_add',
+    name: '记录最近使用的目录',
+    category: CommandCategory.Settings,
+    description: `把目录加入最近使用的列表（命令执行成功后会自动记录参数中的目录）`,
+    parameters: [
+      {
+        key: 'kind',
+        type: ParameterType.String,
+        typeString: 'string',
+        required: true,
+        description: `- 目录用途（root / work / pack / cache）`
+      },
+      {
+        key: 'path',
+        type: ParameterType.String,
+        typeString: 'string',
+        required: true,
+        description: `- 目录路径`
+      }
+    ],
+    returnType: 'string[]',
+    dangerous: false,
+    isFrontendCommand: true
   }
 ];
 
 /**
  * 获取命令总数
  */
-export const COMMAND_COUNT = 46;
+export const COMMAND_COUNT = 133;
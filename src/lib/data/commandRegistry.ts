@@ -7,7 +7,20 @@
 
 import type { CategoryMetadata, CommandDefinition } from '../types/commands';
 import { CommandCategory, ParameterType } from '../types/enums';
-import { BMSEvent, BmsFolderSetNameType, RemoveMediaPreset, ReplacePreset } from '../types/enums';
+import {
+  BMSEvent,
+  BmsFolderSetNameType,
+  ChartConflictStrategy,
+  ChartNameScheme,
+  ImpressionStatus,
+  LibraryBgaFilter,
+  LibrarySortKey,
+  MediaProfile,
+  RemoveMediaPreset,
+  ReplacePreset,
+  SimilarityMetric,
+  TargetOperation,
+} from '../types/enums';
 import { GENERATED_COMMAND_REGISTRY } from './commandRegistry.generated';
 
 /**
@@ -63,6 +76,34 @@ export const CATEGORY_METADATA: Record<CommandCategory, CategoryMetadata> = {
     description: '特殊工具集',
     color: 'from-slate-500 to-gray-500',
   },
+  [CommandCategory.Library]: {
+    id: CommandCategory.Library,
+    name: '作品库',
+    icon: '🗂️',
+    description: '作品索引和查询',
+    color: 'from-teal-500 to-cyan-500',
+  },
+  [CommandCategory.Logs]: {
+    id: CommandCategory.Logs,
+    name: '日志',
+    icon: '📜',
+    description: '运行日志的查看和导出',
+    color: 'from-amber-500 to-orange-500',
+  },
+  [CommandCategory.Jobs]: {
+    id: CommandCategory.Jobs,
+    name: '任务',
+    icon: '⏳',
+    description: '后台任务的查看和取消',
+    color: 'from-sky-500 to-blue-500',
+  },
+  [CommandCategory.Settings]: {
+    id: CommandCategory.Settings,
+    name: '设置',
+    icon: '⚙️',
+    description: '应用设置的查看和修改',
+    color: 'from-gray-500 to-zinc-500',
+  },
 };
 
 /**
@@ -101,6 +142,51 @@ const ENUM_OPTIONS_MAP: Record<string, Array<{ value: unknown; label: string }>>
     { value: RemoveMediaPreset.WavFillFlac, label: '简单预设：wav -> flac' },
     { value: RemoveMediaPreset.MpgFillWmv, label: '简单预设：mpg -> wmv' },
   ],
+  ImpressionStatus: [
+    { value: ImpressionStatus.Listened, label: '已听' },
+    { value: ImpressionStatus.Skipped, label: '跳过' },
+    { value: ImpressionStatus.Unlistened, label: '重置为未听' },
+  ],
+  ChartConflictStrategy: [
+    { value: ChartConflictStrategy.RenameOld, label: '旧谱面改名为 [OLD]' },
+    { value: ChartConflictStrategy.DateSuffix, label: '旧谱面加上日期后缀' },
+    { value: ChartConflictStrategy.KeepNewer, label: '只保留较新的谱面' },
+  ],
+  LibrarySortKey: [
+    { value: LibrarySortKey.DirName, label: '文件夹名' },
+    { value: LibrarySortKey.Title, label: '标题' },
+    { value: LibrarySortKey.Artist, label: '艺术家' },
+    { value: LibrarySortKey.Level, label: '等级' },
+    { value: LibrarySortKey.Bpm, label: 'BPM' },
+    { value: LibrarySortKey.Modified, label: '修改时间' },
+  ],
+  LibraryBgaFilter: [
+    { value: LibraryBgaFilter.Any, label: '不限' },
+    { value: LibraryBgaFilter.With, label: '有 BGA' },
+    { value: LibraryBgaFilter.Without, label: '无 BGA' },
+    { value: LibraryBgaFilter.FullLength, label: '完整 BGA' },
+  ],
+  ChartNameScheme: [
+    { value: ChartNameScheme.TitleDifficulty, label: '标题_难度' },
+    { value: ChartNameScheme.KeepOriginal, label: '保留原文件名' },
+  ],
+  MediaProfile: [
+    { value: MediaProfile.OrajaHQ, label: 'beatoraja HQ（FLAC + MP4）' },
+    { value: MediaProfile.Lr2LQ, label: 'LR2 LQ（OGG + MPEG/WMV/AVI）' },
+  ],
+  TargetOperation: [
+    { value: TargetOperation.None, label: '只检查写入和剩余空间' },
+    { value: TargetOperation.Copy, label: '复制' },
+    { value: TargetOperation.RawToHq, label: '原始包 -> HQ' },
+    { value: TargetOperation.HqToLq, label: 'HQ -> LQ' },
+    { value: TargetOperation.DualBuildLq, label: '双版本的 LQ 目录（HQ -> 硬链接 + LQ）' },
+  ],
+  SimilarityMetric: [
+    { value: SimilarityMetric.Levenshtein, label: '编辑距离' },
+    { value: SimilarityMetric.TokenSet, label: '词集合（忽略词序）' },
+    { value: SimilarityMetric.Lcs, label: '最长公共子序列' },
+    { value: SimilarityMetric.Combined, label: '加权综合' },
+  ],
 };
 
 /**
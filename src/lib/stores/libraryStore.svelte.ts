@@ -0,0 +1,69 @@
+/**
+ * 根目录变更状态管理
+ *
+ * 监听 `library-changed` 事件，保存各根目录最近的外部变更
+ */
+
+import { listen } from '@tauri-apps/api/event';
+import type { UnlistenFn } from '@tauri-apps/api/event';
+import { LIBRARY_CHANGED_EVENT } from '../utils/root/watcher';
+import type { LibraryChangeDelta } from '../utils/root/watcher';
+
+/**
+ * 带接收时间的变更记录
+ */
+export interface LibraryChangeRecord extends LibraryChangeDelta {
+  receivedAt: Date;
+}
+
+class LibraryStore {
+  /** 最近的变更记录（最新的在最后） */
+  changes = $state<LibraryChangeRecord[]>([]);
+
+  /** 最大保留变更记录数量 */
+  private readonly MAX_CHANGES = 100;
+
+  private unlisten: UnlistenFn | null = null;
+
+  /**
+   * 开始监听变更事件
+   */
+  async start(): Promise<void> {
+    if (this.unlisten) {
+      return;
+    }
+
+    this.unlisten = await listen<LibraryChangeDelta>(LIBRARY_CHANGED_EVENT, (event) => {
+      this.changes.push({ ...event.payload, receivedAt: new Date() });
+
+      // 最多保留 MAX_CHANGES 条记录
+      if (this.changes.length > this.MAX_CHANGES) {
+        this.changes.shift();
+      }
+    });
+  }
+
+  /**
+   * 停止监听变更事件
+   */
+  stop(): void {
+    this.unlisten?.();
+    this.unlisten = null;
+  }
+
+  /**
+   * 获取指定根目录的变更记录
+   */
+  getByRoot(rootDir: string): LibraryChangeRecord[] {
+    return this.changes.filter((change) => change.rootDir === rootDir);
+  }
+
+  /**
+   * 清空变更记录
+   */
+  clear(): void {
+    this.changes = [];
+  }
+}
+
+export const libraryStore = new LibraryStore();
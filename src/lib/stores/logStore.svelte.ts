@@ -0,0 +1,63 @@
+/**
+ * 实时日志状态管理
+ *
+ * 订阅运行日志，保存命令执行期间最近的控制台输出，用于显示实时日志
+ */
+
+import { RunLog } from '../utils/logs/runLog';
+import type { LogRecord } from '../utils/logs/runLog';
+
+class LogStore {
+  /** 最近的日志记录（最新的在最后） */
+  records = $state<LogRecord[]>([]);
+
+  /** 最大保留日志记录数量 */
+  private readonly MAX_RECORDS = 1000;
+
+  private unsubscribe: (() => void) | null = null;
+
+  /**
+   * 开始接收日志记录
+   */
+  start(): void {
+    if (this.unsubscribe) {
+      return;
+    }
+
+    this.unsubscribe = RunLog.subscribe((records) => {
+      this.records.push(...records);
+
+      // 最多保留 MAX_RECORDS 条记录
+      if (this.records.length > this.MAX_RECORDS) {
+        this.records.splice(0, this.records.length - this.MAX_RECORDS);
+      }
+    });
+  }
+
+  /**
+   * 停止接收日志记录
+   */
+  stop(): void {
+    this.unsubscribe?.();
+    this.unsubscribe = null;
+  }
+
+  /**
+   * 获取指定命令输出的日志记录
+   */
+  getByModule(module: string, since?: string): LogRecord[] {
+    return this.records.filter(
+      (record) =>
+        record.module.split(',').includes(module) && (!since || record.timestamp >= since)
+    );
+  }
+
+  /**
+   * 清空日志记录
+   */
+  clear(): void {
+    this.records = [];
+  }
+}
+
+export const logStore = new LogStore();
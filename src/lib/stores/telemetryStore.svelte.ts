@@ -0,0 +1,40 @@
+/**
+ * 资源使用统计状态管理
+ *
+ * 监听 `resource-stats` 事件，保存任务运行期间最新的资源使用统计
+ */
+
+import { listen } from '@tauri-apps/api/event';
+import type { UnlistenFn } from '@tauri-apps/api/event';
+import { RESOURCE_STATS_EVENT } from '../utils/telemetry';
+import type { ResourceStats } from '../utils/telemetry';
+
+class TelemetryStore {
+  /** 最新的资源使用统计（没有任务运行时为 null） */
+  stats = $state<ResourceStats | null>(null);
+
+  private unlisten: UnlistenFn | null = null;
+
+  /**
+   * 开始监听资源统计事件
+   */
+  async start(): Promise<void> {
+    if (this.unlisten) {
+      return;
+    }
+
+    this.unlisten = await listen<ResourceStats>(RESOURCE_STATS_EVENT, (event) => {
+      this.stats = event.payload.jobs > 0 ? event.payload : null;
+    });
+  }
+
+  /**
+   * 停止监听资源统计事件
+   */
+  stop(): void {
+    this.unlisten?.();
+    this.unlisten = null;
+  }
+}
+
+export const telemetryStore = new TelemetryStore();
@@ -6,9 +6,9 @@
 
 import type { IProgressManager } from '../utils/progress';
 import type { AeryFixParams } from '../utils/wasted/aery_fix';
-import type { SetFileNumParams } from '../utils/rawpack/numbering';
+import type { NumberAssignment, SetFileNumParams } from '../utils/rawpack/numbering';
 
-export type { AeryFixParams, IProgressManager, SetFileNumParams };
+export type { AeryFixParams, IProgressManager, NumberAssignment, SetFileNumParams };
 
 export { AudioPreset } from '../utils/media/types';
 export { VideoPreset } from '../utils/media/types';
@@ -51,6 +51,60 @@ export enum BMSEvent {
   LetsBMSEdit4 = 104,
 }
 
+/**
+ * 活动作品试听状态
+ */
+export enum ImpressionStatus {
+  /** 未听 */
+  Unlistened = 'unlistened',
+  /** 已打开，尚未标记 */
+  Opened = 'opened',
+  /** 已听 */
+  Listened = 'listened',
+  /** 跳过 */
+  Skipped = 'skipped',
+}
+
+/**
+ * 合并作品时同名谱面冲突的处理方式
+ */
+export enum ChartConflictStrategy {
+  /** 旧谱面改名为 "<name> [OLD]" */
+  RenameOld = 'rename_old',
+  /** 旧谱面改名为 "<name> [YYYY-MM-DD]"（旧谱面的修改日期） */
+  DateSuffix = 'date_suffix',
+  /** 只保留较新的谱面 */
+  KeepNewer = 'keep_newer',
+}
+
+/**
+ * 作品库排序字段
+ */
+export enum LibrarySortKey {
+  /** 文件夹名 */
+  DirName = 'dir_name',
+  Title = 'title',
+  Artist = 'artist',
+  /** 最高等级 */
+  Level = 'level',
+  Bpm = 'bpm',
+  /** 文件夹修改时间 */
+  Modified = 'modified',
+}
+
+/**
+ * 作品库 BGA 筛选
+ */
+export enum LibraryBgaFilter {
+  Any = 'any',
+  /** 只包含有 BGA 的作品 */
+  With = 'with',
+  /** 只包含没有 BGA 的作品 */
+  Without = 'without',
+  /** 只包含 BGA 覆盖几乎整首歌的作品（需要先统计 BGA） */
+  FullLength = 'full_length',
+}
+
 /**
  * 媒体删除预设
  * 对应 Rust: RemoveMediaPreset
@@ -64,6 +118,56 @@ export enum RemoveMediaPreset {
   MpgFillWmv = 2,
 }
 
+/**
+ * 作品媒体格式规范
+ */
+export enum MediaProfile {
+  /** beatoraja HQ：只有 FLAC 音频和 MP4 视频 */
+  OrajaHQ = 'oraja_hq',
+  /** LR2 LQ：只有 OGG 音频和 MPEG-1 / WMV / AVI 视频，不含 FLAC */
+  Lr2LQ = 'lr2_lq',
+}
+
+/**
+ * 检查目标目录时预计执行的操作（用于估算需要的空间）
+ */
+export enum TargetOperation {
+  /** 只检查能否写入和剩余空间 */
+  None = 'none',
+  /** 复制到目标目录 */
+  Copy = 'copy',
+  /** 原始包 -> HQ（解压并转换为 FLAC） */
+  RawToHq = 'raw_to_hq',
+  /** HQ -> LQ（复制后转换为 OGG 和 MPEG） */
+  HqToLq = 'hq_to_lq',
+  /** 大包双版本的 LQ 目录（同一磁盘上以硬链接复制 HQ 版后转换） */
+  DualBuildLq = 'dual_build_lq',
+}
+
+/**
+ * 谱面文件命名方式
+ */
+export enum ChartNameScheme {
+  /** "<标题>_<难度>"（难度取 #DIFFICULTY，没有时取副标题） */
+  TitleDifficulty = 'title_difficulty',
+  /** 保留原文件名，只规范字符并去除重名 */
+  KeepOriginal = 'keep_original',
+}
+
+/**
+ * 文件夹名相似度算法
+ */
+export enum SimilarityMetric {
+  /** 归一化编辑距离 */
+  Levenshtein = 'levenshtein',
+  /** 词集合比较（忽略词序和重复词，"(Remix) Song" 与 "Song (Remix)" 相同） */
+  TokenSet = 'token_set',
+  /** 最长公共子序列 */
+  Lcs = 'lcs',
+  /** 以上三种的加权平均 */
+  Combined = 'combined',
+}
+
 /**
  * 参数类型
  * 用于前端动态渲染不同类型的输入控件
@@ -109,4 +213,12 @@ export enum CommandCategory {
   Media = 'media',
   /** Wasted - 特殊工具集 */
   Wasted = 'wasted',
+  /** 作品库 - 作品索引和查询 */
+  Library = 'library',
+  /** 日志 - 运行日志的查看和导出 */
+  Logs = 'logs',
+  /** 任务 - 后台任务的查看和取消 */
+  Jobs = 'jobs',
+  /** 设置 - 应用设置的查看和修改 */
+  Settings = 'settings',
 }
@@ -0,0 +1,91 @@
+/**
+ * 拆分目录的作品目录文件
+ *
+ * 在每个拆分出的目录中生成 `_contents.txt`，列出其中的作品及标题、艺术家，
+ * 分享拆分后的包时不需要另外附带表格。拆分和合并后自动重新生成
+ */
+
+import { exists, readDir, writeTextFile } from '@tauri-apps/plugin-fs';
+import { bmsQuickInfo } from '../bms/quickInfo';
+import { getWorkDirChartFiles } from '../bms/scanner';
+
+/**
+ * 作品目录文件名
+ */
+export const CONTENTS_FILE_NAME = '_contents.txt';
+
+/**
+ * 读取作品的标题和艺术家（取第一个有值的谱面）
+ */
+async function readWorkTitleArtist(workDir: string): Promise<{ title?: string; artist?: string }> {
+  const result: { title?: string; artist?: string } = {};
+  for (const chart of (await getWorkDirChartFiles(workDir)).sort()) {
+    const info = await bmsQuickInfo(`${workDir}/${chart}`);
+    result.title ||= info?.title?.trim();
+    result.artist ||= info?.artist?.trim();
+    if (result.title && result.artist) {
+      break;
+    }
+  }
+  return result;
+}
+
+/**
+ * 生成目录的作品目录文件
+ *
+ * @param dirPath - 拆分出的目录
+ * @returns 列出的作品数
+ */
+export async function writeContentsFile(dirPath: string): Promise<number> {
+  const dirName = dirPath.split(/[\\/]/).pop() ?? dirPath;
+  const workNames = (await readDir(dirPath))
+    .filter((entry) => entry.isDirectory && entry.name)
+    .map((entry) => entry.name)
+    .sort((a, b) => a.localeCompare(b));
+
+  const lines = [`${dirName} (${workNames.length} works)`, ''];
+  for (const workName of workNames) {
+    const { title, artist } = await readWorkTitleArtist(`${dirPath}/${workName}`);
+    lines.push(title || artist ? `${workName}\t${title ?? ''} / ${artist ?? ''}` : workName);
+  }
+
+  await writeTextFile(`${dirPath}/${CONTENTS_FILE_NAME}`, `${lines.join('\n')}\n`);
+  return workNames.length;
+}
+
+/**
+ * 目录中已有作品目录文件时重新生成（合并后调用）
+ */
+export async function refreshContentsFile(dirPath: string): Promise<void> {
+  try {
+    if (await exists(`${dirPath}/${CONTENTS_FILE_NAME}`)) {
+      await writeContentsFile(dirPath);
+    }
+  } catch (error) {
+    console.warn(` !_! Failed to update ${CONTENTS_FILE_NAME} in ${dirPath}:`, error);
+  }
+}
+
+/**
+ * 为根目录下的各拆分目录生成作品目录文件
+ *
+ * @command
+ * @category bigpack
+ * @dangerous false
+ * @name 生成作品目录文件
+ * @description 在根目录下的每个子目录中生成 _contents.txt，列出其中的作品及标题、艺术家
+ * @frontend true
+ *
+ * @param {string} rootDir - 根目录路径
+ *
+ * @returns {Promise<void>}
+ */
+export async function generateContentsFiles(rootDir: string): Promise<void> {
+  for (const entry of await readDir(rootDir)) {
+    if (!entry.isDirectory || !entry.name) {
+      continue;
+    }
+    const count = await writeContentsFile(`${rootDir}/${entry.name}`);
+    console.log(`Wrote ${CONTENTS_FILE_NAME} for ${entry.name}: ${count} works`);
+  }
+}
@@ -0,0 +1,215 @@
+/**
+ * 按流派整理作品文件夹
+ *
+ * 部分播放器按文件夹浏览，按流派分组的目录结构更方便选曲。
+ * 流派字符串先统一大小写、全半角和空白再分组（"RENAISSANCE" 与 "Renaissance" 归为一组），
+ * 也可以设置别名把不同写法合并到同一个流派。每次整理都记录日志，可以精确撤销
+ */
+
+import { exists, mkdir, readDir, remove, rename } from '@tauri-apps/plugin-fs';
+import { isWorkDir } from '../bms/scanner';
+import { getValidFileName } from '../fs/path';
+import { DryRunSizeTally } from '../fs/sizeTally';
+import { LibraryIndex } from '../library/workIndex';
+import { MoveJournal } from '../root/journal';
+
+/**
+ * 没有流派的作品放入的文件夹
+ */
+export const UNKNOWN_GENRE_FOLDER = '未分类';
+
+const JOURNAL_KIND = 'organize-by-genre';
+
+/**
+ * 统一流派字符串的写法（全半角、大小写、空白）
+ */
+export function normalizeGenreKey(genre: string): string {
+  return genre.normalize('NFKC').toLowerCase().replace(/\s+/g, ' ').trim();
+}
+
+/**
+ * 流派别名设置
+ */
+export class GenreAlias {
+  /** 统一写法后的流派 -> 流派文件夹名 */
+  private static aliases = new Map<string, string>();
+
+  /**
+   * 设置别名（如 { "hardcore techno": "Hardcore" }），为空时清除
+   */
+  static setAliases(aliases: Record<string, string> | null): void {
+    this.aliases = new Map(
+      Object.entries(aliases ?? {}).map(([alias, name]) => [normalizeGenreKey(alias), name])
+    );
+  }
+
+  static getAliases(): Record<string, string> {
+    return Object.fromEntries(this.aliases);
+  }
+
+  static resolve(key: string): string | undefined {
+    return this.aliases.get(key);
+  }
+}
+
+/**
+ * 计算每个流派分组使用的文件夹名
+ *
+ * 有别名时使用别名，否则使用组内出现次数最多的写法
+ */
+function pickGenreFolderNames(genres: string[]): Map<string, string> {
+  const spellings = new Map<string, Map<string, number>>();
+  for (const genre of genres) {
+    const key = normalizeGenreKey(genre);
+    const counts = spellings.get(key) ?? new Map<string, number>();
+    counts.set(genre.trim(), (counts.get(genre.trim()) ?? 0) + 1);
+    spellings.set(key, counts);
+  }
+
+  const names = new Map<string, string>();
+  for (const [key, counts] of spellings) {
+    const alias = GenreAlias.resolve(key);
+    const common = [...counts].sort((a, b) => b[1] - a[1])[0][0];
+    names.set(key, getValidFileName(alias ?? common));
+  }
+  return names;
+}
+
+/**
+ * 按流派整理作品文件夹
+ *
+ * @command
+ * @category bigpack
+ * @dangerous true
+ * @name 按流派整理文件夹
+ * @description 将根目录下的作品按流派移动到流派子文件夹中（流派写法统一后分组），可撤销
+ * @frontend true
+ *
+ * @param {string} rootDir - 根目录路径
+ * @param {boolean} dryRun - 模拟运行（不实际执行）
+ *
+ * @returns {Promise<void>}
+ */
+export async function organizeByGenre(rootDir: string, dryRun: boolean): Promise<void> {
+  await LibraryIndex.update(rootDir);
+  const works = (await LibraryIndex.get(rootDir))?.works ?? [];
+
+  const folderNames = pickGenreFolderNames(
+    works.map((work) => work.genre ?? '').filter((genre) => genre.trim())
+  );
+  const groups = new Map<string, string[]>();
+  for (const work of works) {
+    const genre = work.genre?.trim();
+    const folder = genre ? folderNames.get(normalizeGenreKey(genre))! : UNKNOWN_GENRE_FOLDER;
+    groups.set(folder, [...(groups.get(folder) ?? []), work.dirName]);
+  }
+
+  const journal = await MoveJournal.open(JOURNAL_KIND, rootDir);
+  const run = journal.startRun(new Date().toISOString());
+  const tally = new DryRunSizeTally(`Organize ${rootDir} by genre`);
+
+  for (const [folder, dirNames] of [...groups].sort((a, b) => a[0].localeCompare(b[0]))) {
+    const genreDir = `${rootDir}/${folder}`;
+    const genreDirExists = await exists(genreDir);
+
+    // 流派名与作品文件夹同名时跳过，避免把作品移进另一个作品
+    if (genreDirExists && (await isWorkDir(genreDir))) {
+      console.warn(` !_! ${genreDir} is a work folder, skipping genre "${folder}".`);
+      continue;
+    }
+
+    console.log(`Genre ${folder}: ${dirNames.length} works`);
+    if (!dryRun && !genreDirExists) {
+      await mkdir(genreDir, { recursive: true });
+      run.createdDirs.push(folder);
+    }
+
+    for (const dirName of dirNames) {
+      const from = `${rootDir}/${dirName}`;
+      const to = `${genreDir}/${dirName}`;
+      if (await exists(to)) {
+        console.warn(` !_! ${to} already exists, skipping.`);
+        continue;
+      }
+
+      if (dryRun) {
+        console.log(`[dry-run] Would move: ${from} -> ${to}`);
+        await tally.addMove(from, folder);
+        continue;
+      }
+
+      try {
+        await rename(from, to);
+        run.moves.push({ from: dirName, to: `${folder}/${dirName}` });
+      } catch (error) {
+        console.error(` !_! Failed to move ${from}:`, error);
+      }
+    }
+  }
+
+  if (dryRun) {
+    tally.print();
+    return;
+  }
+
+  await journal.save();
+  console.log(`Moved ${run.moves.length} works into ${groups.size} genre folders.`);
+}
+
+/**
+ * 撤销按流派整理
+ *
+ * @command
+ * @category bigpack
+ * @dangerous true
+ * @name 撤销按流派整理
+ * @description 按日志将最近一次按流派整理移动的作品移回根目录，并删除变空的流派文件夹
+ * @frontend true
+ *
+ * @param {string} rootDir - 根目录路径
+ * @param {boolean} dryRun - 模拟运行（不实际执行）
+ *
+ * @returns {Promise<void>}
+ */
+export async function undoOrganizeByGenre(rootDir: string, dryRun: boolean): Promise<void> {
+  const journal = await MoveJournal.open(JOURNAL_KIND, rootDir);
+  const run = journal.latestRun();
+  if (!run) {
+    console.warn(` !_! No organize-by-genre journal found for ${rootDir}.`);
+    return;
+  }
+
+  console.log(`Undo organize run from ${run.startedAt}: ${run.moves.length} works`);
+  for (const { from, to } of [...run.moves].reverse()) {
+    const fromPath = `${rootDir}/${from}`;
+    const toPath = `${rootDir}/${to}`;
+    if (!(await exists(toPath))) {
+      console.warn(` !_! ${to} no longer exists. Skipped.`);
+      continue;
+    }
+    if (await exists(fromPath)) {
+      console.warn(` !_! ${from} already exists, cannot restore ${to}. Skipped.`);
+      continue;
+    }
+
+    if (dryRun) {
+      console.log(`[dry-run] Would move: ${toPath} -> ${fromPath}`);
+    } else {
+      await rename(toPath, fromPath);
+    }
+  }
+
+  if (dryRun) {
+    return;
+  }
+
+  for (const folder of run.createdDirs) {
+    const genreDir = `${rootDir}/${folder}`;
+    if ((await exists(genreDir)) && (await readDir(genreDir)).length === 0) {
+      await remove(genreDir);
+    }
+  }
+
+  journal.removeRun(run);
+  await journal.save();
+}
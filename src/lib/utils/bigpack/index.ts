@@ -6,3 +6,5 @@
 export * from './split';
 export * from './merge';
 export * from './similarity';
+export * from './genre';
+export * from './contents';
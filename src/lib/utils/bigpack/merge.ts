@@ -5,6 +5,12 @@
 
 import { exists, readDir, remove } from '@tauri-apps/plugin-fs';
 import { moveElementsAcrossDir, replaceOptionsFromPreset, ReplacePreset } from '../fs/moving';
+import { DryRunPlanner } from '../fs/dryRunPlan';
+import type { DryRunPlan } from '../fs/dryRunPlan';
+import { formatSize } from '../fs/sizeTally';
+import type { IProgressManager } from '../progress';
+import { skipIfPinned } from '../work/pin';
+import { refreshContentsFile } from './contents';
 
 /**
  * 合并拆分的文件夹（将 `Title [Artist]` 合并到 `Title` 中）
@@ -18,10 +24,15 @@ import { moveElementsAcrossDir, replaceOptionsFromPreset, ReplacePreset } from '
  *
  * @param {string} rootDir - 根目录路径
  * @param {boolean} dryRun - 模拟运行（不实际执行）
+ * @param {IProgressManager} progressManager - 进度管理器（可选）
  *
- * @returns {Promise<void>}
+ * @returns {Promise<DryRunPlan | null>} 模拟运行时返回计划的操作（实际执行或没有可执行的操作时为 null）
  */
-export async function mergeSplitFolders(rootDir: string, dryRun: boolean): Promise<void> {
+export async function mergeSplitFolders(
+  rootDir: string,
+  dryRun: boolean,
+  progressManager?: IProgressManager
+): Promise<DryRunPlan | null> {
   const entries = await readDir(rootDir);
 
   // 只处理目录
@@ -63,6 +74,10 @@ export async function mergeSplitFolders(rootDir: string, dryRun: boolean): Promi
       continue;
     }
 
+    if ((await skipIfPinned(targetDirPath)) || (await skipIfPinned(`${rootDir}/${dirName}`))) {
+      continue;
+    }
+
     // 添加到合并列表
     pairs.push({ target: dirNameWithoutArtist, source: dirName });
   }
@@ -94,24 +109,50 @@ export async function mergeSplitFolders(rootDir: string, dryRun: boolean): Promi
   console.log(`There are ${pairs.length} merge actions.`);
 
   if (dryRun) {
+    const planner = new DryRunPlanner(`Merge split folders in ${rootDir}`);
+    for (const { source, target } of pairs) {
+      await planner.merge(`${rootDir}/${source}`, `${rootDir}/${target}`, target);
+    }
+    planner.print();
     console.log('[dry-run] Skipping actual merge operations.');
-    return;
+    return planner.plan();
   }
 
   // 在实际执行前，应该有确认对话框
   // 这里我们直接执行（前端可以处理确认）
 
-  for (const { source, target } of pairs) {
+  const lockedFiles: string[] = [];
+  progressManager?.start();
+  for (const [i, { source, target }] of pairs.entries()) {
+    if (progressManager?.getProgress().cancelled) {
+      console.log(`Merge cancelled, ${pairs.length - i} pairs left`);
+      break;
+    }
     const sourcePath = `${rootDir}/${source}`;
     const targetPath = `${rootDir}/${target}`;
 
     console.log(` - Merging: ${source} -> ${target}`);
+    progressManager?.setMessage(`合并 ${source} -> ${target}`);
 
-    await moveElementsAcrossDir(
+    const result = await moveElementsAcrossDir(
       sourcePath,
       targetPath,
-      replaceOptionsFromPreset(ReplacePreset.Default)
+      replaceOptionsFromPreset(ReplacePreset.Default),
+      { progressManager }
     );
+    console.log(
+      `   moved ${result.moved} files (${formatSize(result.bytes)}) and ${result.movedDirs} ` +
+        `folders, skipped ${result.skipped}, renamed ${result.renamed}`
+    );
+    lockedFiles.push(...result.locked);
+  }
+
+  // 被占用的文件留在源文件夹中，源文件夹不会被删除，可以稍后重新合并
+  if (lockedFiles.length > 0) {
+    console.warn(` !_! ${lockedFiles.length} files were locked and left in place:`);
+    for (const file of lockedFiles) {
+      console.warn(`   ${file}`);
+    }
   }
 
   // 删除空源文件夹
@@ -127,4 +168,7 @@ export async function mergeSplitFolders(rootDir: string, dryRun: boolean): Promi
       console.warn(`Failed to remove empty folder ${sourcePath}:`, error);
     }
   }
+
+  await refreshContentsFile(rootDir);
+  return null;
 }
@@ -4,7 +4,8 @@
  */
 
 import { readDir } from '@tauri-apps/plugin-fs';
-import { stringSimilarity } from '../fs/similarity';
+import { nameSimilarity } from '../fs/similarity';
+import { SimilarityMetric } from '../../types/enums';
 
 /**
  * 扫描相似文件夹名称
@@ -13,17 +14,19 @@ import { stringSimilarity } from '../fs/similarity';
  * @category bmsfolder
  * @dangerous false
  * @name 扫描相似文件夹
- * @description 扫描指定目录中名称相似的文件夹，用于发现重复或误操作
+ * @description 扫描指定目录中名称相似的文件夹，用于发现重复或误操作；可选编辑距离、词集合（忽略词序）、最长公共子序列或加权综合算法
  * @frontend true
  *
  * @param {string} rootDir - 要扫描的目录路径
  * @param {number} similarityTrigger - 相似度触发阈值（默认 0.7）
+ * @param {SimilarityMetric} metric - 相似度算法（默认编辑距离）
  *
  * @returns {Promise<Array<{ folder1: string; folder2: string; similarity: number }>>}
  */
 export async function scanSimilarFolders(
   rootDir: string,
-  similarityTrigger: number = 0.7
+  similarityTrigger: number = 0.7,
+  metric: SimilarityMetric = SimilarityMetric.Levenshtein
 ): Promise<Array<{ folder1: string; folder2: string; similarity: number }>> {
   const entries = await readDir(rootDir);
 
@@ -44,7 +47,7 @@ export async function scanSimilarFolders(
       const currentDirName = dirNames[j];
 
       // 计算相似度
-      const similarity = stringSimilarity(formerDirName, currentDirName);
+      const similarity = nameSimilarity(formerDirName, currentDirName, metric);
 
       if (similarity < similarityTrigger) {
         continue;
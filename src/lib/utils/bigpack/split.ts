@@ -3,8 +3,14 @@
  * 从 Python 代码迁移：legacy/options/bms_folder_bigpack.py
  */
 
-import { mkdir, readDir, remove, rename, stat } from '@tauri-apps/plugin-fs';
+import { exists, mkdir, readDir, remove, rename, stat } from '@tauri-apps/plugin-fs';
+import { isWorkDir } from '../bms/scanner';
+import { IgnoreList } from '../fs/ignore';
 import { moveElementsAcrossDir, replaceOptionsFromPreset, ReplacePreset } from '../fs/moving';
+import { DryRunPlanner } from '../fs/dryRunPlan';
+import type { DryRunPlan } from '../fs/dryRunPlan';
+import { skipIfPinned } from '../work/pin';
+import { CONTENTS_FILE_NAME, refreshContentsFile, writeContentsFile } from './contents';
 
 // 正则表达式
 const RE_JAPANESE_HIRAGANA = /[\u3040-\u309f]/;
@@ -56,6 +62,32 @@ const FIRST_CHAR_RULES: FirstCharRule[] = [
   { name: '+', match: (name) => name.length > 0 }, // 其他所有情况
 ];
 
+/**
+ * 是否为拆分目录名（规则名称本身，或 "Root [ABCD]" 形式）
+ */
+export function isSplitBucketName(name: string): boolean {
+  return FIRST_CHAR_RULES.some((rule) => name === rule.name || name.endsWith(` [${rule.name}]`));
+}
+
+/**
+ * 列出根目录下的拆分目录（名称符合拆分规则且本身不是作品目录）
+ *
+ * 根目录级操作开启 includeSplitBuckets 时对这些目录分别执行，已拆分的库不需要逐个目录处理
+ */
+export async function listSplitBuckets(rootDir: string): Promise<string[]> {
+  const buckets: string[] = [];
+  for (const entry of await readDir(rootDir)) {
+    if (!entry.isDirectory || !entry.name || !isSplitBucketName(entry.name)) {
+      continue;
+    }
+    const bucketDir = `${rootDir}/${entry.name}`;
+    if (!(await isWorkDir(bucketDir))) {
+      buckets.push(bucketDir);
+    }
+  }
+  return buckets.sort();
+}
+
 /**
  * 根据首字符规则查找分类
  * 对应 Python: _first_char_rules_find (bms_folder_bigpack.py:35-40)
@@ -86,7 +118,7 @@ function findFirstCharRule(name: string): string {
  *
  * @param {string} rootDir - 根目录路径
  * @param {boolean} dryRun - 是否模拟运行
- * @returns {Promise<void>}
+ * @returns {Promise<DryRunPlan | null>} 模拟运行时返回计划的操作（实际执行或没有可执行的操作时为 null）
  *
  * @example
  * ```typescript
@@ -94,14 +126,17 @@ function findFirstCharRule(name: string): string {
  * // 会创建类似 "0-9", "ABCD", "平假名", "汉字" 等子目录
  * ```
  */
-export async function splitFoldersWithFirstChar(rootDir: string, dryRun: boolean): Promise<void> {
+export async function splitFoldersWithFirstChar(
+  rootDir: string,
+  dryRun: boolean
+): Promise<DryRunPlan | null> {
   const entries = await readDir(rootDir);
   const rootFolderName = rootDir.split('/').pop() || rootDir.split('\\').pop() || rootDir;
 
   // 检查是否以 ']' 结尾
   if (rootFolderName.endsWith(']')) {
     console.log(`${rootDir} ends with ']', aborting...`);
-    return;
+    return null;
   }
 
   const charMap = new Map<string, string[]>();
@@ -124,6 +159,8 @@ export async function splitFoldersWithFirstChar(rootDir: string, dryRun: boolean
     charMap.get(groupName)!.push(`${rootDir}/${entry.name}`);
   }
 
+  const planner = new DryRunPlanner(`Split ${rootDir}`);
+
   // 创建拆分目录
   for (const [groupName, folders] of charMap) {
     const targetDir = `${rootDir}/${groupName}`;
@@ -141,20 +178,28 @@ export async function splitFoldersWithFirstChar(rootDir: string, dryRun: boolean
 
       if (dryRun) {
         console.log(`[dry-run] Would move: ${folder} -> ${targetPath}`);
+        await planner.move(folder, targetPath, groupName);
       } else {
         await rename(folder, targetPath);
       }
     }
+
+    if (!dryRun) {
+      await writeContentsFile(targetDir);
+    }
+  }
+
+  if (dryRun) {
+    planner.print();
+    return planner.plan();
   }
 
   // 移除原始文件夹（如果可能）
-  if (!dryRun) {
-    const entries = await readDir(rootDir);
-    const hasContent = entries.length > 0;
-    if (!hasContent) {
-      await remove(rootDir, { recursive: true });
-    }
+  const remaining = await readDir(rootDir);
+  if (remaining.length === 0) {
+    await remove(rootDir, { recursive: true });
   }
+  return null;
 }
 
 /**
@@ -171,13 +216,17 @@ export async function splitFoldersWithFirstChar(rootDir: string, dryRun: boolean
  * @param {string} rootDir - 根目录路径
  * @param {boolean} dryRun - 模拟运行（不实际执行）
  *
- * @returns {Promise<void>}
+ * @returns {Promise<DryRunPlan | null>} 模拟运行时返回计划的操作（实际执行或没有可执行的操作时为 null）
  */
-export async function undoSplitPack(rootDir: string, dryRun: boolean): Promise<void> {
+export async function undoSplitPack(
+  rootDir: string,
+  dryRun: boolean
+): Promise<DryRunPlan | null> {
   const entries = await readDir(rootDir);
 
   // 获取所有规则名称
   const ruleNames = FIRST_CHAR_RULES.map((r) => r.name);
+  const planner = new DryRunPlanner(`Undo split ${rootDir}`);
 
   for (const entry of entries) {
     if (!entry.isDirectory || !entry.name) {
@@ -204,6 +253,7 @@ export async function undoSplitPack(rootDir: string, dryRun: boolean): Promise<v
 
       if (dryRun) {
         console.log(`[dry-run] Would move: ${sourcePath} -> ${targetPath}`);
+        await planner.move(sourcePath, targetPath, rootDir);
       } else {
         await rename(sourcePath, targetPath);
       }
@@ -214,14 +264,23 @@ export async function undoSplitPack(rootDir: string, dryRun: boolean): Promise<v
       await remove(charDir, { recursive: true });
     }
   }
+
+  if (!dryRun) {
+    return null;
+  }
+  planner.print();
+  return planner.plan();
 }
 
 /**
  * 合并拆分的文件夹（撤销拆分）
  */
-export async function undoSplitAndMerge(rootDir: string, dryRun: boolean): Promise<void> {
+export async function undoSplitAndMerge(
+  rootDir: string,
+  dryRun: boolean
+): Promise<DryRunPlan | null> {
   // 类似于撤销拆分
-  await undoSplitPack(rootDir, dryRun);
+  return await undoSplitPack(rootDir, dryRun);
 }
 
 /**
@@ -239,13 +298,13 @@ export async function undoSplitAndMerge(rootDir: string, dryRun: boolean): Promi
  * @param {string} targetPackName - 目标包名称
  * @param {boolean} dryRun - 模拟运行（不实际执行）
  *
- * @returns {Promise<void>}
+ * @returns {Promise<DryRunPlan | null>} 模拟运行时返回计划的操作（实际执行或没有可执行的操作时为 null）
  */
 export async function moveWorksInPack(
   rootDir: string,
   targetPackName: string,
   dryRun: boolean
-): Promise<void> {
+): Promise<DryRunPlan | null> {
   const targetDir = `${rootDir}/${targetPackName}`;
 
   if (!dryRun) {
@@ -253,6 +312,7 @@ export async function moveWorksInPack(
   }
 
   const entries = await readDir(rootDir);
+  const planner = new DryRunPlanner(`Move works in ${rootDir}`);
 
   for (const entry of entries) {
     if (!entry.isDirectory || !entry.name) {
@@ -269,10 +329,18 @@ export async function moveWorksInPack(
 
     if (dryRun) {
       console.log(`[dry-run] Would move: ${sourcePath} -> ${targetPath}`);
+      await planner.move(sourcePath, targetPath, targetPackName);
     } else {
       await rename(sourcePath, targetPath);
     }
   }
+
+  if (dryRun) {
+    planner.print();
+    return planner.plan();
+  }
+  await refreshContentsFile(targetDir);
+  return null;
 }
 
 /**
@@ -290,16 +358,17 @@ export async function moveWorksInPack(
  * @param {string} sourcePackName - 源包名称
  * @param {boolean} dryRun - 模拟运行（不实际执行）
  *
- * @returns {Promise<void>}
+ * @returns {Promise<DryRunPlan | null>} 模拟运行时返回计划的操作（实际执行或没有可执行的操作时为 null）
  */
 export async function moveOutWorks(
   rootDir: string,
   sourcePackName: string,
   dryRun: boolean
-): Promise<void> {
+): Promise<DryRunPlan | null> {
   const sourceDir = `${rootDir}/${sourcePackName}`;
 
   const entries = await readDir(sourceDir);
+  const planner = new DryRunPlanner(`Move out works from ${sourceDir}`);
 
   for (const entry of entries) {
     if (!entry.isDirectory || !entry.name) {
@@ -311,18 +380,25 @@ export async function moveOutWorks(
 
     if (dryRun) {
       console.log(`[dry-run] Would move: ${sourcePath} -> ${targetPath}`);
+      await planner.move(sourcePath, targetPath, rootDir);
     } else {
       await rename(sourcePath, targetPath);
     }
   }
 
-  // 删除空目录
-  if (!dryRun) {
-    const subEntries = await readDir(sourceDir);
-    if (subEntries.length === 0) {
-      await remove(sourceDir, { recursive: true });
-    }
+  if (dryRun) {
+    planner.print();
+    return planner.plan();
   }
+
+  // 删除空目录（只剩目录清单和系统垃圾文件时也视为空）
+  const subEntries = (await readDir(sourceDir)).filter(
+    (entry) => entry.name !== CONTENTS_FILE_NAME && !IgnoreList.isIgnored(entry.name)
+  );
+  if (subEntries.length === 0) {
+    await remove(sourceDir, { recursive: true });
+  }
+  return null;
 }
 
 /**
@@ -338,12 +414,12 @@ export async function moveOutWorks(
  * @param {string} rootDir - 根目录路径
  * @param {boolean} dryRun - 模拟运行（不实际执行）
  *
- * @returns {Promise<void>}
+ * @returns {Promise<DryRunPlan | null>} 模拟运行时返回计划的操作（实际执行或没有可执行的操作时为 null）
  */
 export async function mergeFoldersWithSameNameWithinDir(
   rootDir: string,
   dryRun: boolean
-): Promise<void> {
+): Promise<DryRunPlan | null> {
   const entries = await readDir(rootDir);
   const nameMap = new Map<string, string[]>();
 
@@ -354,7 +430,7 @@ export async function mergeFoldersWithSameNameWithinDir(
     }
 
     const match = entry.name.match(/^\d+\s+(.+)$/);
-    if (match) {
+    if (match && !(await skipIfPinned(`${rootDir}/${entry.name}`))) {
       const name = match[1];
 
       if (!nameMap.has(name)) {
@@ -365,6 +441,8 @@ export async function mergeFoldersWithSameNameWithinDir(
     }
   }
 
+  const planner = new DryRunPlanner(`Merge same-name works in ${rootDir}`);
+
   // 合并同名文件夹
   for (const [name, folders] of nameMap) {
     if (folders.length <= 1) {
@@ -382,6 +460,7 @@ export async function mergeFoldersWithSameNameWithinDir(
 
       if (dryRun) {
         console.log(`[dry-run] Would merge: ${sourceFolder} -> ${targetFolder}`);
+        await planner.merge(sourceFolder, targetFolder);
       } else {
         await moveElementsAcrossDir(
           sourceFolder,
@@ -394,6 +473,13 @@ export async function mergeFoldersWithSameNameWithinDir(
       }
     }
   }
+
+  if (dryRun) {
+    planner.print();
+    return planner.plan();
+  }
+  await refreshContentsFile(rootDir);
+  return null;
 }
 
 /**
@@ -411,14 +497,15 @@ export async function mergeFoldersWithSameNameWithinDir(
  * @param {string} toDir - 目标目录路径
  * @param {boolean} dryRun - 模拟运行（不实际执行）
  *
- * @returns {Promise<void>}
+ * @returns {Promise<DryRunPlan | null>} 模拟运行时返回计划的操作（实际执行或没有可执行的操作时为 null）
  */
 export async function moveWorksInPackPython(
   fromDir: string,
   toDir: string,
   dryRun: boolean
-): Promise<void> {
+): Promise<DryRunPlan | null> {
   const entries = await readDir(fromDir);
+  const planner = new DryRunPlanner(`Move works ${fromDir} -> ${toDir}`);
   let moveCount = 0;
 
   for (const entry of entries) {
@@ -433,6 +520,11 @@ export async function moveWorksInPackPython(
 
     if (dryRun) {
       console.log(`[dry-run] Would move: ${fromPath} -> ${toPath}`);
+      if (await exists(toPath)) {
+        await planner.merge(fromPath, toPath, toDir);
+      } else {
+        await planner.move(fromPath, toPath, toDir);
+      }
     } else {
       await moveElementsAcrossDir(
         fromPath,
@@ -447,6 +539,11 @@ export async function moveWorksInPackPython(
   if (moveCount > 0) {
     console.log(`Move ${moveCount} songs.`);
   }
+  if (!dryRun) {
+    return null;
+  }
+  planner.print();
+  return planner.plan();
 }
 
 /**
@@ -465,7 +562,7 @@ export async function moveWorksInPackPython(
  * @param {string} rootDir - 根目录路径
  * @param {boolean} dryRun - 模拟运行（不实际执行）
  *
- * @returns {Promise<void>}
+ * @returns {Promise<DryRunPlan | null>} 模拟运行时返回计划的操作（实际执行或没有可执行的操作时为 null）
  *
  * @example
  * ```typescript
@@ -485,7 +582,7 @@ export async function moveWorksInPackPython(
 export async function moveWorksWithSameNameToSiblings(
   rootDir: string,
   dryRun: boolean
-): Promise<void> {
+): Promise<DryRunPlan | null> {
   // 验证输入路径
   try {
     const metadata = await stat(rootDir);
@@ -503,7 +600,7 @@ export async function moveWorksWithSameNameToSiblings(
 
   if (!baseName) {
     console.error('无法提取目录名称');
-    return;
+    return null;
   }
 
   // 获取源目录中的所有直接子文件夹
@@ -548,7 +645,7 @@ export async function moveWorksWithSameNameToSiblings(
 
   if (pairs.length === 0) {
     console.log('未找到可合并的文件夹对');
-    return;
+    return null;
   }
 
   console.log(`\n找到 ${pairs.length} 个合并操作：`);
@@ -559,8 +656,13 @@ export async function moveWorksWithSameNameToSiblings(
   }
 
   if (dryRun) {
+    const planner = new DryRunPlanner(`Merge ${rootDir} into siblings`);
+    for (const { from, target, sibling } of pairs) {
+      await planner.merge(from, target, sibling);
+    }
+    planner.print();
     console.log('\n[dry-run] 跳过实际合并操作');
-    return;
+    return planner.plan();
   }
 
   console.log('\n开始合并...');
@@ -575,7 +677,12 @@ export async function moveWorksWithSameNameToSiblings(
     await moveElementsAcrossDir(from, target, replaceOptionsFromPreset(ReplacePreset.Default));
   }
 
+  for (const sibling of new Set(pairs.map((pair) => pair.sibling))) {
+    await refreshContentsFile(`${parentDir}/${sibling}`);
+  }
+
   console.log(`\n合并完成，共 ${pairs.length} 个操作`);
+  return null;
 }
 
 /**
@@ -600,7 +707,7 @@ export async function moveWorksWithSameNameToSiblings(
  * @param {string} toDir - 目标文件夹路径
  * @param {boolean} dryRun - 模拟运行（不实际执行）
  *
- * @returns {Promise<void>}
+ * @returns {Promise<DryRunPlan | null>} 模拟运行时返回计划的操作（实际执行或没有可执行的操作时为 null）
  *
  * @example
  * ```typescript
@@ -620,7 +727,7 @@ export async function moveWorksWithSameName(
   fromDir: string,
   toDir: string,
   dryRun: boolean
-): Promise<void> {
+): Promise<DryRunPlan | null> {
   // 验证输入路径
   try {
     const fromMetadata = await stat(fromDir);
@@ -668,7 +775,9 @@ export async function moveWorksWithSameName(
         fromDirName.includes(toDirName)
       ) {
         const toPath = `${toDir}/${toDirName}`;
-        pairs.push({ fromDirName, fromPath, toDirName, toPath });
+        if (!(await skipIfPinned(fromPath)) && !(await skipIfPinned(toPath))) {
+          pairs.push({ fromDirName, fromPath, toDirName, toPath });
+        }
         break;
       }
     }
@@ -676,7 +785,7 @@ export async function moveWorksWithSameName(
 
   if (pairs.length === 0) {
     console.log('未找到可合并的文件夹对');
-    return;
+    return null;
   }
 
   console.log(`\n找到 ${pairs.length} 个合并操作：`);
@@ -685,8 +794,13 @@ export async function moveWorksWithSameName(
   }
 
   if (dryRun) {
+    const planner = new DryRunPlanner(`Merge ${fromDir} -> ${toDir}`);
+    for (const { fromPath, toPath } of pairs) {
+      await planner.merge(fromPath, toPath, toDir);
+    }
+    planner.print();
     console.log('\n[dry-run] 跳过实际合并操作');
-    return;
+    return planner.plan();
   }
 
   console.log('\n开始合并...');
@@ -709,5 +823,8 @@ export async function moveWorksWithSameName(
     }
   }
 
+  await refreshContentsFile(toDir);
+
   console.log(`\n合并完成，共 ${pairs.length} 个操作`);
+  return null;
 }
@@ -0,0 +1,477 @@
+/**
+ * 谱面差异比较
+ *
+ * 活动期间作品经常被悄悄更新，逐行文本比较很难看出改了什么。
+ * 这里按语义比较两个版本：头部命令的变化、音符数变化、BPM 变化以及内容有变化的小节；
+ * 也可以比较同一作品的两个版本目录，按文件名（其次按标题和等级）配对谱面
+ */
+
+import { readDir, readTextFile } from '@tauri-apps/plugin-fs';
+import { readBmsFile } from './encoding';
+import { parseBmsHeader } from './quickInfo';
+import { isBmsonFile, isChartFile } from './scanner';
+
+/**
+ * 控制流命令，不作为头部比较
+ */
+const CONTROL_COMMANDS = new Set([
+  'RANDOM',
+  'SETRANDOM',
+  'IF',
+  'ELSEIF',
+  'ELSE',
+  'ENDIF',
+  'ENDRANDOM',
+  'SWITCH',
+  'SETSWITCH',
+  'CASE',
+  'SKIP',
+  'DEF',
+  'ENDSW',
+]);
+
+const CHANNEL_LINE_PATTERN = /^#(\d{3})([0-9A-Z]{2}):(.*)$/i;
+
+/**
+ * 谱面中用于比较的内容
+ */
+interface ChartSnapshot {
+  headers: Map<string, string>;
+  noteCount: number;
+  initialBpm?: number;
+  /** 谱面中出现的所有 BPM（含变速） */
+  bpmValues: number[];
+  bpmChangeCount: number;
+  /** 小节号 -> 该小节内容（用于判断是否变化） */
+  measures: Map<number, string>;
+}
+
+/**
+ * 头部命令变化
+ */
+export interface HeaderChange {
+  key: string;
+  old?: string;
+  new?: string;
+}
+
+/**
+ * BPM 摘要
+ */
+export interface BpmSummary {
+  initial?: number;
+  min?: number;
+  max?: number;
+  changes: number;
+}
+
+/**
+ * 两个谱面的差异
+ */
+export interface ChartDiff {
+  oldFile: string;
+  newFile: string;
+  headerChanges: HeaderChange[];
+  oldNotes: number;
+  newNotes: number;
+  oldBpm: BpmSummary;
+  newBpm: BpmSummary;
+  changedMeasures: number[];
+  /** 没有任何差异 */
+  identical: boolean;
+}
+
+/**
+ * 两个作品目录的差异
+ */
+export interface WorkDirDiff {
+  /** 两边都有的谱面 */
+  charts: ChartDiff[];
+  /** 只在旧版本中的谱面 */
+  removed: string[];
+  /** 只在新版本中的谱面 */
+  added: string[];
+}
+
+/**
+ * 是否为音符通道（1P/2P 可见音符和长音符）
+ */
+function isNoteChannel(channel: string): boolean {
+  return /^[1256][1-9]$/.test(channel);
+}
+
+/**
+ * 读取 BMS 文本中的头部、音符数、BPM 和各小节内容
+ */
+function snapshotBms(content: string): ChartSnapshot {
+  const headers = new Map<string, string>();
+  const measureLines = new Map<number, string[]>();
+  const bpmDefs = new Map<string, number>();
+  const bpmRefs: string[] = [];
+  const bpmValues: number[] = [];
+  let normalNotes = 0;
+  let longNoteEnds = 0;
+
+  for (const rawLine of content.split(/\r?\n/)) {
+    const line = rawLine.trim();
+    if (!line.startsWith('#')) {
+      continue;
+    }
+
+    const channelMatch = line.match(CHANNEL_LINE_PATTERN);
+    if (channelMatch) {
+      const measure = parseInt(channelMatch[1], 10);
+      const channel = channelMatch[2].toUpperCase();
+      const data = channelMatch[3].trim().toUpperCase();
+      measureLines.set(measure, [...(measureLines.get(measure) ?? []), `${channel}:${data}`]);
+
+      if (channel === '02') {
+        continue;
+      }
+      const objects = data.match(/.{2}/g)?.filter((obj) => obj !== '00') ?? [];
+      if (isNoteChannel(channel)) {
+        if (channel[0] === '5' || channel[0] === '6') {
+          longNoteEnds += objects.length;
+        } else {
+          normalNotes += objects.length;
+        }
+      } else if (channel === '03') {
+        bpmValues.push(...objects.map((obj) => parseInt(obj, 16)));
+      } else if (channel === '08') {
+        bpmRefs.push(...objects);
+      }
+      continue;
+    }
+
+    const headerMatch = line.match(/^#(\S+)(?:\s+(.*))?$/);
+    if (!headerMatch) {
+      continue;
+    }
+    const key = headerMatch[1].toUpperCase();
+    if (CONTROL_COMMANDS.has(key)) {
+      continue;
+    }
+    const value = headerMatch[2]?.trim() ?? '';
+    headers.set(key, value);
+    const bpmDef = key.match(/^BPM([0-9A-Z]{2})$/);
+    if (bpmDef) {
+      bpmDefs.set(bpmDef[1], parseFloat(value));
+    }
+  }
+
+  for (const ref of bpmRefs) {
+    const bpm = bpmDefs.get(ref);
+    if (bpm !== undefined && Number.isFinite(bpm)) {
+      bpmValues.push(bpm);
+    }
+  }
+
+  const initialBpm = parseBmsHeader(content, '').bpm;
+  const measures = new Map<number, string>();
+  for (const [measure, lines] of measureLines) {
+    measures.set(measure, lines.sort().join('\n'));
+  }
+
+  return {
+    headers,
+    // 长音符的起点和终点各占一个对象
+    noteCount: normalNotes + Math.floor(longNoteEnds / 2),
+    initialBpm,
+    bpmValues: initialBpm !== undefined ? [initialBpm, ...bpmValues] : bpmValues,
+    bpmChangeCount: bpmValues.length,
+    measures,
+  };
+}
+
+/**
+ * 读取 BMSON 中的 info 字段、音符数、BPM 和各小节内容
+ */
+function snapshotBmson(content: string): ChartSnapshot {
+  const bmson = JSON.parse(content) as {
+    info?: Record<string, unknown>;
+    lines?: Array<{ y: number }>;
+    bpm_events?: Array<{ y: number; bpm: number }>;
+    sound_channels?: Array<{ name: string; notes?: Array<{ x: number; y: number; l: number }> }>;
+  };
+  const info = bmson.info ?? {};
+
+  const headers = new Map<string, string>();
+  for (const [key, value] of Object.entries(info)) {
+    headers.set(key, typeof value === 'string' ? value : JSON.stringify(value));
+  }
+
+  // 小节线位置，没有时按 4/4 拍计算
+  const resolution = typeof info.resolution === 'number' ? info.resolution : 240;
+  const lines = (bmson.lines ?? []).map((line) => line.y).sort((a, b) => a - b);
+  const measureOf = (y: number): number => {
+    if (lines.length === 0) {
+      return Math.floor(y / (resolution * 4));
+    }
+    let index = 0;
+    while (index + 1 < lines.length && lines[index + 1] <= y) {
+      index++;
+    }
+    return index;
+  };
+
+  const measureObjects = new Map<number, string[]>();
+  let noteCount = 0;
+  for (const channel of bmson.sound_channels ?? []) {
+    for (const note of channel.notes ?? []) {
+      if (note.x > 0) {
+        noteCount++;
+      }
+      const measure = measureOf(note.y);
+      const objects = measureObjects.get(measure) ?? [];
+      objects.push(`${channel.name}:${note.x}:${note.y}:${note.l}`);
+      measureObjects.set(measure, objects);
+    }
+  }
+
+  const measures = new Map<number, string>();
+  for (const [measure, objects] of measureObjects) {
+    measures.set(measure, objects.sort().join('\n'));
+  }
+
+  const initialBpm = typeof info.init_bpm === 'number' ? info.init_bpm : undefined;
+  const bpmChanges = (bmson.bpm_events ?? []).map((event) => event.bpm);
+  return {
+    headers,
+    noteCount,
+    initialBpm,
+    bpmValues: initialBpm !== undefined ? [initialBpm, ...bpmChanges] : bpmChanges,
+    bpmChangeCount: bpmChanges.length,
+    measures,
+  };
+}
+
+async function readSnapshot(filePath: string): Promise<ChartSnapshot> {
+  return isBmsonFile(filePath)
+    ? snapshotBmson(await readTextFile(filePath))
+    : snapshotBms(await readBmsFile(filePath));
+}
+
+function summarizeBpm(snapshot: ChartSnapshot): BpmSummary {
+  const values = snapshot.bpmValues.filter((bpm) => bpm > 0);
+  return {
+    initial: snapshot.initialBpm,
+    min: values.length > 0 ? Math.min(...values) : undefined,
+    max: values.length > 0 ? Math.max(...values) : undefined,
+    changes: snapshot.bpmChangeCount,
+  };
+}
+
+/**
+ * 把小节号列表压缩为范围（1, 2, 3, 7 -> "1-3, 7"）
+ */
+function formatMeasureRanges(measures: number[]): string {
+  const ranges: string[] = [];
+  for (let i = 0; i < measures.length; i++) {
+    let end = i;
+    while (end + 1 < measures.length && measures[end + 1] === measures[end] + 1) {
+      end++;
+    }
+    ranges.push(end === i ? `${measures[i]}` : `${measures[i]}-${measures[end]}`);
+    i = end;
+  }
+  return ranges.join(', ');
+}
+
+function formatBpm(bpm: BpmSummary): string {
+  const range = bpm.min === bpm.max ? `${bpm.min ?? '?'}` : `${bpm.min}-${bpm.max}`;
+  return `${bpm.initial ?? '?'} (range ${range}, ${bpm.changes} changes)`;
+}
+
+/**
+ * 输出差异摘要
+ */
+function printChartDiff(diff: ChartDiff): void {
+  if (diff.identical) {
+    console.log('No differences.');
+    return;
+  }
+
+  for (const change of diff.headerChanges) {
+    if (change.old === undefined) {
+      console.log(`+ #${change.key} ${change.new}`);
+    } else if (change.new === undefined) {
+      console.log(`- #${change.key} ${change.old}`);
+    } else {
+      console.log(`~ #${change.key} "${change.old}" -> "${change.new}"`);
+    }
+  }
+
+  const delta = diff.newNotes - diff.oldNotes;
+  if (delta !== 0) {
+    console.log(`Notes: ${diff.oldNotes} -> ${diff.newNotes} (${delta > 0 ? '+' : ''}${delta})`);
+  }
+
+  const oldBpm = formatBpm(diff.oldBpm);
+  const newBpm = formatBpm(diff.newBpm);
+  if (oldBpm !== newBpm) {
+    console.log(`BPM: ${oldBpm} -> ${newBpm}`);
+  }
+
+  if (diff.changedMeasures.length > 0) {
+    console.log(
+      `Changed measures (${diff.changedMeasures.length}): ` +
+        formatMeasureRanges(diff.changedMeasures)
+    );
+  }
+}
+
+/**
+ * 比较两个谱面
+ */
+async function compareCharts(oldFile: string, newFile: string): Promise<ChartDiff> {
+  const [oldChart, newChart] = await Promise.all([readSnapshot(oldFile), readSnapshot(newFile)]);
+
+  const headerChanges: HeaderChange[] = [];
+  const keys = [...new Set([...oldChart.headers.keys(), ...newChart.headers.keys()])].sort();
+  for (const key of keys) {
+    const oldValue = oldChart.headers.get(key);
+    const newValue = newChart.headers.get(key);
+    if (oldValue !== newValue) {
+      headerChanges.push({ key, old: oldValue, new: newValue });
+    }
+  }
+
+  const measures = new Set([...oldChart.measures.keys(), ...newChart.measures.keys()]);
+  const changedMeasures = [...measures]
+    .filter((measure) => oldChart.measures.get(measure) !== newChart.measures.get(measure))
+    .sort((a, b) => a - b);
+
+  const oldBpm = summarizeBpm(oldChart);
+  const newBpm = summarizeBpm(newChart);
+
+  return {
+    oldFile,
+    newFile,
+    headerChanges,
+    oldNotes: oldChart.noteCount,
+    newNotes: newChart.noteCount,
+    oldBpm,
+    newBpm,
+    changedMeasures,
+    identical:
+      headerChanges.length === 0 &&
+      changedMeasures.length === 0 &&
+      oldChart.noteCount === newChart.noteCount &&
+      JSON.stringify(oldBpm) === JSON.stringify(newBpm),
+  };
+}
+
+/**
+ * 比较两个谱面文件
+ *
+ * @command
+ * @category bms
+ * @dangerous false
+ * @name 比较谱面
+ * @description 按语义比较两个版本的谱面：头部变化、音符数变化、BPM 变化和有改动的小节
+ * @frontend true
+ *
+ * @param {string} oldFile - 旧版本谱面文件
+ * @param {string} newFile - 新版本谱面文件
+ *
+ * @returns {Promise<ChartDiff>} 差异
+ */
+export async function diffCharts(oldFile: string, newFile: string): Promise<ChartDiff> {
+  const diff = await compareCharts(oldFile, newFile);
+  console.log(`Diff ${oldFile} -> ${newFile}`);
+  printChartDiff(diff);
+  return diff;
+}
+
+/**
+ * 按谱面的标题、副标题和等级生成配对键
+ */
+async function chartMatchKey(filePath: string): Promise<string> {
+  if (isBmsonFile(filePath)) {
+    const content = await readTextFile(filePath);
+    const info = (JSON.parse(content) as { info?: Record<string, unknown> }).info ?? {};
+    return `${info.title ?? ''}\u0000${info.subtitle ?? ''}\u0000${info.level ?? ''}`;
+  }
+  const header = parseBmsHeader(await readBmsFile(filePath), filePath);
+  return `${header.title ?? ''}\u0000${header.subtitle ?? ''}\u0000${header.playLevel ?? ''}`;
+}
+
+async function listCharts(dirPath: string): Promise<string[]> {
+  return (await readDir(dirPath))
+    .filter((entry) => entry.isFile && isChartFile(entry.name))
+    .map((entry) => entry.name)
+    .sort();
+}
+
+/**
+ * 比较同一作品的两个版本目录
+ *
+ * @command
+ * @category bms
+ * @dangerous false
+ * @name 比较作品版本
+ * @description 按文件名（其次按标题和等级）配对两个版本目录中的谱面，逐个比较差异
+ * @frontend true
+ *
+ * @param {string} oldWorkDir - 旧版本作品目录
+ * @param {string} newWorkDir - 新版本作品目录
+ *
+ * @returns {Promise<WorkDirDiff>} 差异
+ */
+export async function diffWorkDirs(oldWorkDir: string, newWorkDir: string): Promise<WorkDirDiff> {
+  const oldCharts = await listCharts(oldWorkDir);
+  const newCharts = await listCharts(newWorkDir);
+
+  // 先按文件名（不区分大小写）配对
+  const pairs: [string, string][] = [];
+  const unmatchedNew = new Map(newCharts.map((name) => [name.toLowerCase(), name]));
+  const unmatchedOld: string[] = [];
+  for (const name of oldCharts) {
+    const match = unmatchedNew.get(name.toLowerCase());
+    if (match) {
+      pairs.push([name, match]);
+      unmatchedNew.delete(name.toLowerCase());
+    } else {
+      unmatchedOld.push(name);
+    }
+  }
+
+  // 再按标题和等级配对（文件改名的情况）
+  const newByKey = new Map<string, string>();
+  for (const name of unmatchedNew.values()) {
+    newByKey.set(await chartMatchKey(`${newWorkDir}/${name}`), name);
+  }
+  const removed: string[] = [];
+  for (const name of unmatchedOld) {
+    const key = await chartMatchKey(`${oldWorkDir}/${name}`);
+    const match = newByKey.get(key);
+    if (match) {
+      pairs.push([name, match]);
+      newByKey.delete(key);
+    } else {
+      removed.push(name);
+    }
+  }
+  const added = [...newByKey.values()].sort();
+
+  const charts: ChartDiff[] = [];
+  for (const [oldName, newName] of pairs) {
+    const diff = await compareCharts(`${oldWorkDir}/${oldName}`, `${newWorkDir}/${newName}`);
+    charts.push(diff);
+    console.log(oldName === newName ? `== ${oldName}` : `== ${oldName} -> ${newName}`);
+    printChartDiff(diff);
+  }
+  for (const name of removed) {
+    console.log(`- Removed chart: ${name}`);
+  }
+  for (const name of added) {
+    console.log(`+ Added chart: ${name}`);
+  }
+
+  const changed = charts.filter((diff) => !diff.identical).length;
+  console.log(
+    `${charts.length} charts compared, ${changed} changed, ` +
+      `${removed.length} removed, ${added.length} added.`
+  );
+  return { charts, removed, added };
+}
@@ -196,3 +196,22 @@ export async function readBmsFile(filePath: string, encoding?: string): Promise<
   const fileBytes = await readFile(filePath);
   return getBmsFileStr(new Uint8Array(fileBytes as unknown as ArrayBufferLike), encoding);
 }
+
+/**
+ * 单字节方式解码，修改后按原字节写回，不改变谱面编码
+ */
+export function bytesToLatin1(bytes: Uint8Array): string {
+  let text = '';
+  for (let i = 0; i < bytes.length; i += 0x8000) {
+    text += String.fromCharCode(...bytes.subarray(i, i + 0x8000));
+  }
+  return text;
+}
+
+export function latin1ToBytes(text: string): Uint8Array {
+  const bytes = new Uint8Array(text.length);
+  for (let i = 0; i < text.length; i++) {
+    bytes[i] = text.charCodeAt(i) & 0xff;
+  }
+  return bytes;
+}
@@ -6,5 +6,10 @@
 export * from './types';
 export * from './parser';
 export * from './scanner';
+export * from './workInfo';
+export * from './quickInfo';
 export * from './encoding';
 export * from './work';
+export * from './level';
+export * from './diff';
+export * from './scoreSafety';
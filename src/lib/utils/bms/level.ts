@@ -0,0 +1,281 @@
+/**
+ * 批量修改谱面等级
+ *
+ * 按提案表（谱面 SHA-256 或作品目录名 -> 等级 / 难度）批量修改 #PLAYLEVEL 和 #DIFFICULTY，
+ * 供维护自定义难度表的用户使用。修改前的谱面备份在应用数据目录中。
+ *
+ * 注意：修改头部会改变谱面哈希，结果中记录了新哈希，用于更新难度表
+ */
+
+import {
+  copyFile,
+  readDir,
+  readFile,
+  readTextFile,
+  writeFile,
+  writeTextFile,
+} from '@tauri-apps/plugin-fs';
+import { HashAlgorithm, HashService } from '../fs/hash';
+import { getAppDataPath, writeJsonFile } from '../storage';
+import { bytesToLatin1, latin1ToBytes } from './encoding';
+import { bmsQuickInfo } from './quickInfo';
+import { getWorkDirChartFiles, isBmsonFile } from './scanner';
+import { ScoreSafety } from './scoreSafety';
+
+/**
+ * #DIFFICULTY 的名称
+ */
+const DIFFICULTY_BY_NAME: Record<string, number> = {
+  BEGINNER: 1,
+  NORMAL: 2,
+  HYPER: 3,
+  ANOTHER: 4,
+  INSANE: 5,
+};
+
+/**
+ * 提案表中的一行
+ */
+interface LevelProposal {
+  /** 谱面 SHA-256（小写） */
+  hash?: string;
+  /** 作品目录名过滤（支持 * 通配符） */
+  folder?: RegExp;
+  level?: string;
+  difficulty?: number;
+}
+
+/**
+ * 单个谱面的修改
+ */
+export interface LevelChange {
+  /** 相对根目录的谱面路径 */
+  chart: string;
+  oldLevel?: string;
+  newLevel?: string;
+  oldDifficulty?: number;
+  newDifficulty?: number;
+  oldHash?: string;
+  /** 修改后的哈希（模拟运行时为空） */
+  newHash?: string;
+}
+
+/**
+ * 批量修改结果
+ */
+export interface SetLevelResult {
+  rootDir: string;
+  dryRun: boolean;
+  changes: LevelChange[];
+  /** 提案表中没有匹配到谱面的哈希 */
+  unmatchedHashes: string[];
+  /** 备份目录（模拟运行或没有修改时为空） */
+  backupDir?: string;
+}
+
+function folderPatternToRegExp(pattern: string): RegExp {
+  const escaped = pattern.replace(/[.+^${}()|[\]\\?]/g, '\\$&').replace(/\*/g, '.*');
+  return new RegExp(`^${escaped}$`, 'i');
+}
+
+/**
+ * 解析提案表
+ *
+ * 每行为 `谱面 SHA-256 或作品目录名, 等级, 难度`（逗号或制表符分隔），
+ * 等级或难度留空表示不修改；难度可以是 1-5 或 BEGINNER ~ INSANE。以 # 开头的行和表头被忽略
+ *
+ * 谱面按原始字节改写，无法按谱面编码写入非 ASCII 字符，等级含非 ASCII 字符（如 ★12、全角数字）时报错
+ */
+function parseProposals(content: string): LevelProposal[] {
+  const proposals: LevelProposal[] = [];
+
+  for (const rawLine of content.split(/\r?\n/)) {
+    const line = rawLine.trim();
+    if (!line || line.startsWith('#')) {
+      continue;
+    }
+
+    const cells = line.split(/\t|,/).map((cell) => cell.trim().replace(/^"(.*)"$/, '$1'));
+    const [key, level, difficultyText] = cells;
+    if (!key || /^(hash|sha256|folder|key)$/i.test(key)) {
+      continue;
+    }
+
+    if (level && !/^[\x20-\x7e]*$/.test(level)) {
+      throw new Error(`等级只能包含 ASCII 字符：${level}（${line}）`);
+    }
+
+    let difficulty: number | undefined;
+    if (difficultyText) {
+      difficulty = DIFFICULTY_BY_NAME[difficultyText.toUpperCase()] ?? parseInt(difficultyText);
+      if (!Number.isInteger(difficulty) || difficulty < 1 || difficulty > 5) {
+        console.warn(` !_! Invalid difficulty "${difficultyText}" in line: ${line}`);
+        difficulty = undefined;
+      }
+    }
+
+    const proposal: LevelProposal = { level: level || undefined, difficulty };
+    if (/^[0-9a-f]{64}$/i.test(key)) {
+      proposal.hash = key.toLowerCase();
+    } else {
+      proposal.folder = folderPatternToRegExp(key);
+    }
+    proposals.push(proposal);
+  }
+
+  return proposals;
+}
+
+/**
+ * 设置 BMS 头部命令，不存在时插入到 #TITLE 之后
+ */
+export function setBmsHeader(content: string, command: string, value: string): string {
+  const pattern = new RegExp(`^#${command}(?:[ \\t].*)?$`, 'im');
+  if (pattern.test(content)) {
+    // 用函数替换，避免值中的 $ 被当作替换模式
+    return content.replace(pattern, () => `#${command} ${value}`);
+  }
+
+  const newline = content.includes('\r\n') ? '\r\n' : '\n';
+  const title = content.match(/^#TITLE(?:[ \t].*)?$/im);
+  if (title?.index === undefined) {
+    return `#${command} ${value}${newline}${content}`;
+  }
+  const insertAt = title.index + title[0].length;
+  return `${content.slice(0, insertAt)}${newline}#${command} ${value}${content.slice(insertAt)}`;
+}
+
+/**
+ * 写入谱面的等级和难度（BMSON 没有难度字段，只修改等级）
+ */
+async function writeChartLevel(chartPath: string, change: LevelChange): Promise<void> {
+  if (isBmsonFile(chartPath)) {
+    const bmson = JSON.parse(await readTextFile(chartPath)) as { info?: Record<string, unknown> };
+    if (change.newLevel !== undefined) {
+      const level = Number(change.newLevel);
+      bmson.info = { ...bmson.info, level: Number.isFinite(level) ? level : change.newLevel };
+    }
+    await writeTextFile(chartPath, JSON.stringify(bmson));
+    return;
+  }
+
+  let content = bytesToLatin1(await readFile(chartPath));
+  if (change.newLevel !== undefined) {
+    content = setBmsHeader(content, 'PLAYLEVEL', change.newLevel);
+  }
+  if (change.newDifficulty !== undefined) {
+    content = setBmsHeader(content, 'DIFFICULTY', change.newDifficulty.toString());
+  }
+  await writeFile(chartPath, latin1ToBytes(content));
+}
+
+/**
+ * 批量修改谱面等级
+ *
+ * @command
+ * @category bms
+ * @dangerous true
+ * @name 批量修改谱面等级
+ * @description 按提案表（每行：谱面 SHA-256 或作品目录名, 等级, 难度）批量修改 #PLAYLEVEL / #DIFFICULTY，修改前备份谱面
+ * @frontend true
+ *
+ * @param {string} rootDir - 根目录路径
+ * @param {string} filePath - 提案表文件（CSV 或 TSV）
+ * @param {boolean} dryRun - 模拟运行（只列出修改）
+ *
+ * @returns {Promise<SetLevelResult>} 修改记录
+ */
+export async function setLevel(
+  rootDir: string,
+  filePath: string,
+  dryRun: boolean
+): Promise<SetLevelResult> {
+  const proposals = parseProposals(await readTextFile(filePath));
+  const byHash = new Map(
+    proposals.filter((proposal) => proposal.hash).map((proposal) => [proposal.hash!, proposal])
+  );
+  const folderProposals = proposals.filter((proposal) => proposal.folder);
+  const matchedHashes = new Set<string>();
+
+  const result: SetLevelResult = { rootDir, dryRun, changes: [], unmatchedHashes: [] };
+  const backupDir = `level-backups/${new Date().toISOString().replace(/[:.]/g, '-')}`;
+
+  // 先列出所有修改，检查成绩关联后再写入
+  const planned: { chartPath: string; change: LevelChange }[] = [];
+  for (const entry of await readDir(rootDir)) {
+    if (!entry.isDirectory || !entry.name) {
+      continue;
+    }
+
+    const workName = entry.name;
+    const folderProposal = folderProposals.find((proposal) => proposal.folder!.test(workName));
+    for (const chart of await getWorkDirChartFiles(`${rootDir}/${workName}`)) {
+      const chartPath = `${rootDir}/${workName}/${chart}`;
+      let proposal = folderProposal;
+      let hash: string | undefined;
+      if (byHash.size > 0) {
+        hash = await HashService.hashFile(chartPath, HashAlgorithm.SHA256);
+        if (byHash.has(hash)) {
+          proposal = byHash.get(hash);
+          matchedHashes.add(hash);
+        }
+      }
+      if (!proposal) {
+        continue;
+      }
+
+      const info = await bmsQuickInfo(chartPath);
+      const change: LevelChange = {
+        chart: `${workName}/${chart}`,
+        oldLevel: info?.playLevel,
+        oldDifficulty: info?.difficulty,
+        oldHash: hash,
+      };
+      if (proposal.level !== undefined && proposal.level !== info?.playLevel) {
+        change.newLevel = proposal.level;
+      }
+      if (
+        proposal.difficulty !== undefined &&
+        proposal.difficulty !== info?.difficulty &&
+        !isBmsonFile(chart)
+      ) {
+        change.newDifficulty = proposal.difficulty;
+      }
+      if (change.newLevel === undefined && change.newDifficulty === undefined) {
+        continue;
+      }
+      planned.push({ chartPath, change });
+    }
+  }
+
+  const hashChanged = planned.map(({ change }) => change.chart);
+  ScoreSafety.check('Set level', { hashChanged, pathChanged: [] }, dryRun);
+
+  for (const { chartPath, change } of planned) {
+    const description =
+      `${change.chart}: PLAYLEVEL ${change.oldLevel ?? '-'} -> ${change.newLevel ?? '(same)'}, ` +
+      `DIFFICULTY ${change.oldDifficulty ?? '-'} -> ${change.newDifficulty ?? '(same)'}`;
+    if (dryRun) {
+      console.log(`[dry-run] Would set ${description}`);
+    } else {
+      const backupPath = await getAppDataPath(`${backupDir}/${change.chart}`);
+      result.backupDir ??= backupPath.slice(0, -change.chart.length - 1);
+      await copyFile(chartPath, backupPath);
+      await writeChartLevel(chartPath, change);
+      change.newHash = await HashService.hashFile(chartPath, HashAlgorithm.SHA256);
+      console.log(`Set ${description}`);
+    }
+    result.changes.push(change);
+  }
+
+  result.unmatchedHashes = [...byHash.keys()].filter((hash) => !matchedHashes.has(hash));
+  for (const hash of result.unmatchedHashes) {
+    console.warn(` !_! No chart found for hash: ${hash}`);
+  }
+
+  if (result.backupDir) {
+    await writeJsonFile(`${result.backupDir}/changes.json`, result);
+  }
+  console.log(`${dryRun ? '[dry-run] ' : ''}Level changes: ${result.changes.length} charts`);
+  return result;
+}
@@ -0,0 +1,197 @@
+/**
+ * 谱面头部快速读取
+ *
+ * 只扫描 #TITLE、#ARTIST、#BPM 等头部命令，读到第一行音符数据就停止，
+ * 不构建音符模型，适合列表批量显示
+ */
+
+import { open, readTextFile } from '@tauri-apps/plugin-fs';
+import { getBmsFileStr } from './encoding';
+import { isBmsonFile } from './scanner';
+import type { BmsQuickInfo } from './types';
+
+/**
+ * 每次读取的块大小
+ */
+const READ_CHUNK_SIZE = 64 * 1024;
+
+/**
+ * 音符数据行（#xxxyy:），出现后不再有头部命令
+ */
+const CHANNEL_LINE_PATTERN = /^#\d{3}[0-9A-Z]{2}:/i;
+
+/**
+ * 读取谱面开头到第一行音符数据为止的字节
+ *
+ * 截断位置总在行首，不会切断多字节字符
+ */
+async function readHeaderBytes(filePath: string): Promise<Uint8Array> {
+  const file = await open(filePath, { read: true });
+  const chunks: Uint8Array[] = [];
+  const buffer = new Uint8Array(READ_CHUNK_SIZE);
+  const decoder = new TextDecoder('latin1');
+  let total = 0;
+
+  try {
+    while (true) {
+      const bytesRead = await file.read(buffer);
+      if (bytesRead === null || bytesRead === 0) {
+        break;
+      }
+
+      const chunk = buffer.slice(0, bytesRead);
+      chunks.push(chunk);
+      total += bytesRead;
+
+      // 用单字节解码检查是否已经进入音符数据
+      const text = decoder.decode(chunk);
+      const channelIndex = text.search(/(^|\n)#\d{3}[0-9A-Z]{2}:/im);
+      if (channelIndex >= 0) {
+        total -= bytesRead - channelIndex;
+        chunks[chunks.length - 1] = chunk.subarray(0, channelIndex);
+        break;
+      }
+    }
+  } finally {
+    await file.close();
+  }
+
+  const bytes = new Uint8Array(total);
+  let offset = 0;
+  for (const chunk of chunks) {
+    bytes.set(chunk, offset);
+    offset += chunk.length;
+  }
+  return bytes;
+}
+
+function parseNumber(value: string | undefined): number | undefined {
+  if (value === undefined) {
+    return undefined;
+  }
+  const num = parseFloat(value);
+  return Number.isFinite(num) ? num : undefined;
+}
+
+/**
+ * 解析 BMS 头部文本
+ */
+export function parseBmsHeader(content: string, filePath: string): BmsQuickInfo {
+  const info: BmsQuickInfo = { path: filePath };
+
+  for (const rawLine of content.split(/\r?\n/)) {
+    const line = rawLine.trim();
+    // 扩展字段（%URL、%EMAIL）
+    const extension = line.match(/^%(URL|EMAIL)\s+(.+)$/i);
+    if (extension) {
+      if (extension[1].toUpperCase() === 'URL') {
+        info.url ??= extension[2].trim();
+      } else {
+        info.email ??= extension[2].trim();
+      }
+      continue;
+    }
+    if (!line.startsWith('#')) {
+      continue;
+    }
+    if (CHANNEL_LINE_PATTERN.test(line)) {
+      break;
+    }
+
+    const match = line.match(/^#([A-Z]+)(?:\s+(.*))?$/i);
+    if (!match) {
+      continue;
+    }
+
+    const value = match[2]?.trim();
+    switch (match[1].toUpperCase()) {
+      case 'TITLE':
+        info.title ??= value;
+        break;
+      case 'SUBTITLE':
+        info.subtitle ??= value;
+        break;
+      case 'ARTIST':
+        info.artist ??= value;
+        break;
+      case 'SUBARTIST':
+        info.subartist ??= value;
+        break;
+      case 'GENRE':
+        info.genre ??= value;
+        break;
+      case 'BPM':
+        info.bpm ??= parseNumber(value);
+        break;
+      case 'PLAYLEVEL':
+        info.playLevel ??= value;
+        break;
+      case 'DIFFICULTY':
+        info.difficulty ??= parseNumber(value);
+        break;
+      case 'TOTAL':
+        info.total ??= parseNumber(value);
+        break;
+      case 'RANK':
+        info.rank ??= parseNumber(value);
+        break;
+      case 'PLAYER':
+        info.player ??= parseNumber(value);
+        break;
+      case 'COMMENT':
+        info.comment ??= value?.replace(/^"(.*)"$/, '$1');
+        break;
+    }
+  }
+
+  return info;
+}
+
+/**
+ * 从 BMSON 的 info 部分读取头部信息
+ */
+function parseBmsonHeader(content: string, filePath: string): BmsQuickInfo {
+  const info = (JSON.parse(content) as { info?: Record<string, unknown> }).info ?? {};
+  const text = (value: unknown) => (typeof value === 'string' ? value : undefined);
+  const num = (value: unknown) => (typeof value === 'number' ? value : undefined);
+
+  return {
+    path: filePath,
+    title: text(info.title),
+    subtitle: text(info.subtitle),
+    artist: text(info.artist),
+    subartist: Array.isArray(info.subartists) ? info.subartists.join(' ') : undefined,
+    genre: text(info.genre),
+    bpm: num(info.init_bpm),
+    playLevel: num(info.level)?.toString(),
+    total: num(info.total),
+  };
+}
+
+/**
+ * 快速读取谱面头部信息
+ *
+ * @command
+ * @category bms
+ * @dangerous false
+ * @name 快速读取谱面信息
+ * @description 只读取谱面头部（标题、艺术家、BPM、等级、难度），不解析音符，适合列表显示
+ * @frontend true
+ *
+ * @param {string} filePath - 谱面文件路径
+ *
+ * @returns {Promise<BmsQuickInfo | null>} 头部信息，读取失败时返回 null
+ */
+export async function bmsQuickInfo(filePath: string): Promise<BmsQuickInfo | null> {
+  try {
+    if (isBmsonFile(filePath)) {
+      return parseBmsonHeader(await readTextFile(filePath), filePath);
+    }
+
+    const content = getBmsFileStr(await readHeaderBytes(filePath));
+    return parseBmsHeader(content, filePath);
+  } catch (error) {
+    console.error(`Failed to read chart header: ${filePath}`, error);
+    return null;
+  }
+}
@@ -3,10 +3,11 @@
  * 用于扫描目录中的 BMS 文件
  */
 
-import { readDir, readFile, exists } from '@tauri-apps/plugin-fs';
-import type { BmsOutput } from './types';
+import { readDir, readFile, exists, stat } from '@tauri-apps/plugin-fs';
+import type { BmsDirScanResult, BmsOutput, BmsParseOutcome } from './types';
 import { BmsParser } from './parser';
 import { getBmsFileStr } from './encoding';
+import { getFileName } from '../fs/path';
 
 /**
  * BMS 文件扩展名
@@ -89,42 +90,54 @@ export function isChartFile(filePath: string): boolean {
 }
 
 /**
- * 读取并解析 BMS 文件
- *
- * @command
- * @category bms
- * @dangerous false
- * @name 解析 BMS 文件
- * @description 解析单个 BMS 文件，提取元数据信息（标题、艺术家等）
- * @frontend true
- *
- * @param {string} filePath - BMS 文件路径
- *
- * @returns {Promise<BmsOutput | null>} 解析结果，失败时返回 null
+ * 读取并解析 BMS 文件，保留错误信息
  */
-export async function readAndParseBmsFile(filePath: string): Promise<BmsOutput | null> {
+async function parseBmsFileWithError(
+  filePath: string
+): Promise<{ output: BmsOutput | null; error?: string }> {
   try {
     // 读取文件内容（使用 Tauri FS API）
 
     if (!(await exists(filePath))) {
-      return null;
+      return { output: null, error: 'File not found' };
     }
 
     // 根据扩展名选择解析器
     if (isBmsonFile(filePath)) {
       // BMSON 文件使用 UTF-8 读取
       const content = await readTextFile(filePath);
-      return BmsParser.parseBmson(content);
+      return { output: BmsParser.parseBmson(content) };
     } else {
       // 非 BMSON 文件使用编码感知读取
       const fileBytes = await readFile(filePath);
       const content = getBmsFileStr(new Uint8Array(fileBytes));
-      return BmsParser.parse(content);
+      return { output: BmsParser.parse(content) };
     }
   } catch (error) {
+    return { output: null, error: error instanceof Error ? error.message : String(error) };
+  }
+}
+
+/**
+ * 读取并解析 BMS 文件
+ *
+ * @command
+ * @category bms
+ * @dangerous false
+ * @name 解析 BMS 文件
+ * @description 解析单个 BMS 文件，提取元数据信息（标题、艺术家等）
+ * @frontend true
+ *
+ * @param {string} filePath - BMS 文件路径
+ *
+ * @returns {Promise<BmsOutput | null>} 解析结果，失败时返回 null
+ */
+export async function readAndParseBmsFile(filePath: string): Promise<BmsOutput | null> {
+  const { output, error } = await parseBmsFileWithError(filePath);
+  if (error !== undefined && error !== 'File not found') {
     console.error(`Failed to parse BMS file: ${filePath}`, error);
-    return null;
   }
+  return output;
 }
 
 /**
@@ -137,21 +150,11 @@ async function readTextFile(filePath: string): Promise<string> {
 }
 
 /**
- * 获取目录中的所有 BMS 文件
- *
- * @command
- * @category bms
- * @dangerous false
- * @name 扫描目录 BMS 文件
- * @description 扫描目录，解析所有 BMS 文件
- * @frontend true
- *
- * @param {string} dirPath - 目录路径
- *
- * @returns {Promise<BmsOutput[]>} 所有解析出的 BMS 信息列表
+ * 扫描目录中的谱面文件，返回成功列表和每个文件的解析结果
  */
-export async function getDirBmsList(dirPath: string): Promise<BmsOutput[]> {
-  const bmsOutputs: BmsOutput[] = [];
+export async function scanDirBms(dirPath: string): Promise<BmsDirScanResult> {
+  const outputs: BmsOutput[] = [];
+  const outcomes: BmsParseOutcome[] = [];
 
   try {
     const entries = await readDir(dirPath);
@@ -169,55 +172,159 @@ export async function getDirBmsList(dirPath: string): Promise<BmsOutput[]> {
 
       const filePath = `${dirPath}/${entry.name}`;
 
-      if (isChartFile(entry.name)) {
-        const output = await readAndParseBmsFile(filePath);
-        if (output) {
-          // 过滤掉有严重错误的 BMS
-          const hasCriticalError = output.warnings.some(
-            (warning) =>
-              warning.type === 'PlayingError' ||
-              (warning.type === 'PlayingWarning' && warning.warning === 'NoPlayableNotes')
-          );
-
-          if (!hasCriticalError) {
-            bmsOutputs.push(output);
-          }
-        }
+      if (!isChartFile(entry.name)) {
+        continue;
+      }
+
+      const { output, error } = await parseBmsFileWithError(filePath);
+      if (!output) {
+        outcomes.push({
+          path: filePath,
+          accepted: false,
+          warnings: [],
+          errors: error !== undefined ? [error] : [],
+          skippedReason: 'Failed to read or parse file',
+        });
+        continue;
+      }
+
+      // 过滤掉有严重错误的 BMS
+      const criticalWarning = output.warnings.find(
+        (warning) =>
+          warning.type === 'PlayingError' ||
+          (warning.type === 'PlayingWarning' && warning.warning === 'NoPlayableNotes')
+      );
+
+      const criticalDetail =
+        criticalWarning?.type === 'PlayingError' ? criticalWarning.error : criticalWarning?.warning;
+
+      outcomes.push({
+        path: filePath,
+        accepted: !criticalWarning,
+        warnings: output.warnings,
+        errors: [],
+        skippedReason: criticalWarning ? `Critical warning: ${criticalDetail}` : undefined,
+      });
+
+      if (!criticalWarning) {
+        outputs.push(output);
       }
     }
   } catch (error) {
     console.error(`Failed to read directory: ${dirPath}`, error);
   }
 
-  return bmsOutputs;
+  return { outputs, outcomes };
 }
 
 /**
- * 获取目录的 BMS 信息（整合版）
+ * 输出解析警告和跳过原因
+ */
+function printParseOutcomes(outcomes: BmsParseOutcome[]): void {
+  for (const outcome of outcomes) {
+    if (outcome.skippedReason) {
+      console.warn(`[skipped] ${outcome.path}: ${outcome.skippedReason}`);
+    }
+    for (const error of outcome.errors) {
+      console.warn(`[error] ${outcome.path}: ${error}`);
+    }
+    for (const warning of outcome.warnings) {
+      const detail = warning.type === 'PlayingError' ? warning.error : warning.warning;
+      console.warn(`[${warning.type}] ${outcome.path}: ${detail}`);
+    }
+  }
+}
+
+/**
+ * 获取目录中的所有 BMS 文件
  *
  * @command
  * @category bms
  * @dangerous false
- * @name 读取目录 BMS 信息
- * @description 从目录的 info.toml 文件读取 BMS 摘要信息
+ * @name 扫描目录 BMS 文件
+ * @description 扫描目录，解析所有 BMS 文件
  * @frontend true
  *
- * @param {string} dirPath - BMS 工作目录路径
+ * @param {string} dirPath - 目录路径
+ * @param {boolean} showWarnings - 输出解析警告和被跳过的文件
  *
- * @returns {Promise<BmsOutput | null>} 整合后的 BMS 信息
+ * @returns {Promise<BmsOutput[]>} 所有解析出的 BMS 信息列表
  */
-export async function getDirBmsInfo(dirPath: string): Promise<BmsOutput | null> {
-  const bmsList = await getDirBmsList(dirPath);
+export async function getDirBmsList(
+  dirPath: string,
+  showWarnings: boolean = false
+): Promise<BmsOutput[]> {
+  const { outputs, outcomes } = await scanDirBms(dirPath);
+
+  if (showWarnings) {
+    printParseOutcomes(outcomes);
+  }
+
+  return outputs;
+}
 
-  if (bmsList.length === 0) {
+/**
+ * 获取目录中的所有 BMS 文件及每个文件的解析结果
+ *
+ * @command
+ * @category bms
+ * @dangerous false
+ * @name 扫描目录 BMS 文件（含解析结果）
+ * @description 扫描目录，返回解析成功的列表，以及每个谱面文件的警告、错误和跳过原因
+ * @frontend true
+ *
+ * @param {string} dirPath - 目录路径
+ *
+ * @returns {Promise<BmsDirScanResult>} 成功列表和每个文件的解析结果
+ */
+export async function getDirBmsListWithOutcomes(dirPath: string): Promise<BmsDirScanResult> {
+  return await scanDirBms(dirPath);
+}
+
+/**
+ * 获取目录的 BMS 信息（整合版）
+ *
+ * 前端使用的版本见 getDirBmsInfo（./workInfo）
+ */
+export async function getDirBmsOutput(dirPath: string): Promise<BmsOutput | null> {
+  return (await getDirBmsInfoWithSources(dirPath))?.info ?? null;
+}
+
+/**
+ * 整合后的作品信息各字段取自哪个谱面文件
+ */
+export interface BmsInfoSources {
+  title?: string;
+  artist?: string;
+  genre?: string;
+}
+
+/**
+ * 获取目录的 BMS 信息（整合版），并记录标题、艺术家、流派分别取自哪个谱面
+ *
+ * 用于模拟运行时展示重命名依据，便于在执行前发现错误的元数据
+ */
+export async function getDirBmsInfoWithSources(
+  dirPath: string
+): Promise<{ info: BmsOutput; sources: BmsInfoSources } | null> {
+  const { outputs, outcomes } = await scanDirBms(dirPath);
+
+  if (outputs.length === 0) {
     return null;
   }
 
+  // outputs 与 accepted 的解析结果一一对应
+  const paths = outcomes.filter((outcome) => outcome.accepted).map((outcome) => outcome.path);
+  const sourceOf = (field: 'title' | 'artist' | 'genre'): string | undefined => {
+    const index = outputs.findIndex((output) => output.bms.musicInfo[field] !== undefined);
+    return index >= 0 ? getFileName(paths[index]) : undefined;
+  };
+
   // 整合多个 BMS 的信息
-  const bms = bmsList[0].bms;
+  const bms = outputs[0].bms;
 
   // 提取标题（使用第一个非空的标题）
-  const titles = bmsList
+  const titles = outputs
     .map((output) => output.bms.musicInfo.title)
     .filter((title) => title !== undefined);
 
@@ -226,27 +333,16 @@ export async function getDirBmsInfo(dirPath: string): Promise<BmsOutput | null>
   }
 
   // 提取艺术家
-  const artists = bmsList
+  const artists = outputs
     .map((output) => output.bms.musicInfo.artist)
     .filter((artist) => artist !== undefined);
 
   if (artists.length > 0) {
-    bms.musicInfo.artist = extractWorkName(artists, [
-      '/',
-      ':',
-      '：',
-      '-',
-      'obj',
-      'obj.',
-      'Obj',
-      'Obj.',
-      'OBJ',
-      'OBJ.',
-    ]);
+    bms.musicInfo.artist = extractWorkName(artists, ARTIST_NOISE_SUBSTRINGS);
   }
 
   // 提取流派
-  const genres = bmsList
+  const genres = outputs
     .map((output) => output.bms.musicInfo.genre)
     .filter((genre) => genre !== undefined);
 
@@ -255,12 +351,27 @@ export async function getDirBmsInfo(dirPath: string): Promise<BmsOutput | null>
   }
 
   // 合并 WAV 和 BMP
-  for (const output of bmsList) {
+  for (const output of outputs) {
     Object.assign(bms.wav, output.bms.wav);
     Object.assign(bms.bmp, output.bms.bmp);
   }
 
-  return { bms, warnings: [] };
+  return {
+    info: { bms, warnings: [] },
+    sources: { title: sourceOf('title'), artist: sourceOf('artist'), genre: sourceOf('genre') },
+  };
+}
+
+/**
+ * 作为作品艺术家时去除的分隔符和 obj 标记
+ */
+const ARTIST_NOISE_SUBSTRINGS = ['/', ':', '：', '-', 'obj', 'obj.', 'Obj', 'Obj.', 'OBJ', 'OBJ.'];
+
+/**
+ * 清理作为作品艺术家的名称（与 getDirBmsOutput 相同的规则）
+ */
+export function cleanWorkArtistName(artist: string): string {
+  return extractWorkName([artist], ARTIST_NOISE_SUBSTRINGS);
 }
 
 /**
@@ -304,25 +415,41 @@ function extractWorkName(names: (string | undefined)[], removeSubstrings?: strin
 export async function isWorkDir(dirPath: string): Promise<boolean> {
   try {
     const entries = await readDir(dirPath);
-
-    for (const entry of entries) {
-      // 只检查文件
-      if (entry.isDirectory) {
-        continue;
-      }
-
-      if (entry.name && isChartFile(entry.name)) {
-        return true;
-      }
-    }
-
-    return false;
+    // 只检查文件，找到第一个谱面即返回
+    return entries.some((entry) => !entry.isDirectory && entry.name && isChartFile(entry.name));
   } catch (error) {
     console.error(`Failed to check work directory: ${dirPath}`, error);
     return false;
   }
 }
 
+/**
+ * 获取工作目录中的谱面文件名（不是工作目录时返回空数组）
+ *
+ * 与 isWorkDir 读取同样的目录条目，需要谱面列表时可以省去再次读取目录
+ */
+export async function getWorkDirChartFiles(dirPath: string): Promise<string[]> {
+  try {
+    const entries = await readDir(dirPath);
+    return entries
+      .filter((entry) => !entry.isDirectory && entry.name && isChartFile(entry.name))
+      .map((entry) => entry.name);
+  } catch (error) {
+    console.error(`Failed to check work directory: ${dirPath}`, error);
+    return [];
+  }
+}
+
+/**
+ * 检查根目录时同时检查的子目录数（网络驱动器上逐个检查太慢，全部同时检查又会卡住）
+ */
+const ROOT_DIR_CHECK_CONCURRENCY = 16;
+
+/**
+ * 根目录检查结果缓存（按目录修改时间失效，第一层增删目录时修改时间会变化）
+ */
+const rootDirCache = new Map<string, { mtime: number; result: boolean }>();
+
 /**
  * 检查是否为根目录（包含工作目录的目录）
  *
@@ -339,23 +466,35 @@ export async function isWorkDir(dirPath: string): Promise<boolean> {
  */
 export async function isRootDir(dirPath: string): Promise<boolean> {
   try {
-    const entries = await readDir(dirPath);
-
-    for (const entry of entries) {
-      // 只检查子目录
-      if (!entry.isDirectory) {
-        continue;
-      }
+    const mtime = (await stat(dirPath)).mtime?.getTime();
+    const cached = rootDirCache.get(dirPath);
+    if (mtime !== undefined && cached?.mtime === mtime) {
+      return cached.result;
+    }
 
-      if (entry.name) {
-        const subDirPath = `${dirPath}/${entry.name}`;
-        if (await isWorkDir(subDirPath)) {
-          return true;
+    // 只检查子目录
+    const subDirs = (await readDir(dirPath))
+      .filter((entry) => entry.isDirectory && entry.name)
+      .map((entry) => `${dirPath}/${entry.name}`);
+
+    // 有限并发检查，找到第一个工作目录后其余任务不再继续
+    let found = false;
+    let next = 0;
+    const worker = async () => {
+      while (!found && next < subDirs.length) {
+        if (await isWorkDir(subDirs[next++])) {
+          found = true;
         }
       }
-    }
+    };
+    await Promise.all(
+      Array.from({ length: Math.min(ROOT_DIR_CHECK_CONCURRENCY, subDirs.length) }, worker)
+    );
 
-    return false;
+    if (mtime !== undefined) {
+      rootDirCache.set(dirPath, { mtime, result: found });
+    }
+    return found;
   } catch (error) {
     console.error(`Failed to check root directory: ${dirPath}`, error);
     return false;
@@ -0,0 +1,97 @@
+/**
+ * 成绩关联的安全检查
+ *
+ * 播放器和 IR 按谱面哈希记录成绩和通关灯：改写谱面内容（修改等级、交换标题、加入 BGA 等）后哈希改变，
+ * 旧成绩将无法对应；只重命名谱面文件时哈希不变，beatoraja 重新扫描后仍能对应，LR2 按路径和哈希对应可能丢失关联。
+ * 只重命名作品文件夹不影响成绩，不在此检查。
+ * 改写或重命名谱面的操作执行前先列出受影响的谱面，按设置只警告或中止
+ */
+
+/**
+ * 谱面哈希会改变时的处理方式
+ */
+export type ScoreSafetyMode = 'warn' | 'block';
+
+/**
+ * 操作对谱面的影响
+ */
+export interface ChartChangeImpact {
+  /** 内容被改写、哈希会改变的谱面 */
+  hashChanged: string[];
+  /** 只改变路径、哈希不变的谱面 */
+  pathChanged: string[];
+}
+
+/**
+ * 操作会改变谱面哈希，且设置为中止
+ */
+export class ScoreSafetyError extends Error {
+  constructor(
+    readonly operation: string,
+    readonly charts: string[]
+  ) {
+    super(
+      `${operation}会改写 ${charts.length} 个谱面并改变哈希，已有的成绩和通关灯将无法对应。` +
+        '如确认要修改，请在设置中将成绩关联检查改为仅警告'
+    );
+    this.name = 'ScoreSafetyError';
+  }
+}
+
+/**
+ * 列出时最多显示的谱面数
+ */
+const MAX_LISTED_CHARTS = 10;
+
+function listCharts(charts: string[]): void {
+  for (const chart of charts.slice(0, MAX_LISTED_CHARTS)) {
+    console.warn(`   ${chart}`);
+  }
+  if (charts.length > MAX_LISTED_CHARTS) {
+    console.warn(`   ... and ${charts.length - MAX_LISTED_CHARTS} more`);
+  }
+}
+
+/**
+ * 成绩关联检查设置
+ */
+export class ScoreSafety {
+  private static mode: ScoreSafetyMode = 'warn';
+
+  static setMode(mode: ScoreSafetyMode): void {
+    this.mode = mode;
+  }
+
+  static getMode(): ScoreSafetyMode {
+    return this.mode;
+  }
+
+  /**
+   * 在改写或重命名谱面前调用：列出受影响的谱面，设置为中止且会改变哈希时抛出 ScoreSafetyError
+   *
+   * 模拟运行时只列出，不会中止
+   *
+   * @param operation - 操作名称
+   */
+  static check(operation: string, impact: ChartChangeImpact, dryRun: boolean): void {
+    const { hashChanged, pathChanged } = impact;
+    if (hashChanged.length > 0) {
+      console.warn(
+        ` !_! ${operation}: ${hashChanged.length} charts will be rewritten and get a new hash, ` +
+          'scores and clear lamps recorded for the old hash will no longer match:'
+      );
+      listCharts(hashChanged);
+    }
+    if (pathChanged.length > 0) {
+      console.warn(
+        ` !_! ${operation}: ${pathChanged.length} charts will be renamed (hash unchanged), ` +
+          'beatoraja keeps scores after rescanning, LR2 may lose the association:'
+      );
+      listCharts(pathChanged);
+    }
+
+    if (!dryRun && this.mode === 'block' && hashChanged.length > 0) {
+      throw new ScoreSafetyError(operation, hashChanged);
+    }
+  }
+}
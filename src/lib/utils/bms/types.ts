@@ -74,6 +74,53 @@ export interface BmsOutput {
   warnings: BmsWarning[];
 }
 
+/**
+ * 单个谱面文件的解析结果
+ */
+export interface BmsParseOutcome {
+  /** 谱面文件路径 */
+  path: string;
+  /** 是否被纳入结果列表 */
+  accepted: boolean;
+  /** 解析警告 */
+  warnings: BmsWarning[];
+  /** 读取或解析过程中的错误 */
+  errors: string[];
+  /** 未纳入结果列表的原因 */
+  skippedReason?: string;
+}
+
+/**
+ * 目录扫描结果：成功列表和每个文件的解析结果
+ */
+export interface BmsDirScanResult {
+  outputs: BmsOutput[];
+  outcomes: BmsParseOutcome[];
+}
+
+/**
+ * 谱面头部信息（不解析音符）
+ */
+export interface BmsQuickInfo {
+  path: string;
+  title?: string;
+  subtitle?: string;
+  artist?: string;
+  subartist?: string;
+  genre?: string;
+  bpm?: number;
+  playLevel?: string;
+  difficulty?: number;
+  total?: number;
+  rank?: number;
+  player?: number;
+  comment?: string;
+  /** 扩展字段 %URL（作者主页） */
+  url?: string;
+  /** 扩展字段 %EMAIL */
+  email?: string;
+}
+
 /**
  * BMSON 数据结构（简化版）
  */
@@ -0,0 +1,133 @@
+/**
+ * 面向前端的作品信息
+ *
+ * 整合后的 BmsOutput 中 wav / bmp 是嵌套对象，前端展示时还要自己判断文件是否存在。
+ * 这里转换为简单的字符串映射，并附带数量和每个定义对应的文件是否存在
+ */
+
+import { readDir } from '@tauri-apps/plugin-fs';
+import { AUDIO_FILE_EXTS, getDirBmsOutput, IMAGE_FILE_EXTS, VIDEO_FILE_EXTS } from './scanner';
+import type { BmsBmp, BmsWav } from './types';
+import { getFileExtension, getFileStem } from '../fs/path';
+
+/**
+ * 一类定义（#WAV 或 #BMP）
+ */
+export interface DefinitionMapDto {
+  /** 定义编号 -> 文件名 */
+  files: Record<string, string>;
+  /** 定义编号 -> 文件是否存在 */
+  exists: Record<string, boolean>;
+  /** 定义数量 */
+  count: number;
+  /** 文件不存在的定义数量 */
+  missingCount: number;
+}
+
+/**
+ * 作品信息
+ */
+export interface WorkInfoDto {
+  dirPath: string;
+  title: string;
+  artist: string;
+  genre: string;
+  wav: DefinitionMapDto;
+  bmp: DefinitionMapDto;
+  /** 扩展 BPM 定义编号 -> BPM */
+  bpms: Record<string, number>;
+}
+
+/**
+ * 目录中的文件名（小写），按子目录缓存
+ */
+type DirFileIndex = Map<string, Promise<Set<string>>>;
+
+async function listDirFiles(index: DirFileIndex, dirPath: string): Promise<Set<string>> {
+  let files = index.get(dirPath);
+  if (!files) {
+    files = readDir(dirPath)
+      .then((entries) => new Set(entries.filter((e) => e.isFile).map((e) => e.name.toLowerCase())))
+      .catch(() => new Set<string>());
+    index.set(dirPath, files);
+  }
+  return await files;
+}
+
+/**
+ * 定义的文件是否存在
+ *
+ * 与播放器的行为一致：文件名不区分大小写，找不到时接受同名的其他格式（如 .wav 定义对应 .ogg 文件）
+ */
+async function definitionFileExists(
+  index: DirFileIndex,
+  dirPath: string,
+  file: string,
+  fallbackExts: readonly string[]
+): Promise<boolean> {
+  const relative = file.replace(/\\/g, '/');
+  const slash = relative.lastIndexOf('/');
+  const subDir = slash >= 0 ? `${dirPath}/${relative.slice(0, slash)}` : dirPath;
+  const name = (slash >= 0 ? relative.slice(slash + 1) : relative).toLowerCase();
+
+  const files = await listDirFiles(index, subDir);
+  if (files.has(name)) {
+    return true;
+  }
+  const stem = getFileStem(name);
+  return [...files].some(
+    (other) => getFileStem(other) === stem && fallbackExts.includes(getFileExtension(other))
+  );
+}
+
+async function toDefinitionMap(
+  index: DirFileIndex,
+  dirPath: string,
+  definitions: Record<string, BmsWav | BmsBmp>,
+  fallbackExts: readonly string[]
+): Promise<DefinitionMapDto> {
+  const map: DefinitionMapDto = { files: {}, exists: {}, count: 0, missingCount: 0 };
+  for (const [id, definition] of Object.entries(definitions)) {
+    const file = definition.path ?? definition.name;
+    map.files[id] = file;
+    map.exists[id] = await definitionFileExists(index, dirPath, file, fallbackExts);
+    map.count++;
+    if (!map.exists[id]) {
+      map.missingCount++;
+    }
+  }
+  return map;
+}
+
+/**
+ * 获取目录的 BMS 信息（整合版）
+ *
+ * @command
+ * @category bms
+ * @dangerous false
+ * @name 读取目录 BMS 信息
+ * @description 整合目录中所有谱面的标题、艺术家、流派和 #WAV / #BMP 定义，并检查每个定义的文件是否存在
+ * @frontend true
+ *
+ * @param {string} dirPath - BMS 工作目录路径
+ *
+ * @returns {Promise<WorkInfoDto | null>} 整合后的作品信息，目录中没有可解析的谱面时为 null
+ */
+export async function getDirBmsInfo(dirPath: string): Promise<WorkInfoDto | null> {
+  const output = await getDirBmsOutput(dirPath);
+  if (!output) {
+    return null;
+  }
+
+  const { musicInfo, wav, bmp, bpms } = output.bms;
+  const index: DirFileIndex = new Map();
+  return {
+    dirPath,
+    title: musicInfo.title ?? '',
+    artist: musicInfo.artist ?? '',
+    genre: musicInfo.genre ?? '',
+    wav: await toDefinitionMap(index, dirPath, wav, AUDIO_FILE_EXTS),
+    bmp: await toDefinitionMap(index, dirPath, bmp, [...IMAGE_FILE_EXTS, ...VIDEO_FILE_EXTS]),
+    bpms: { ...bpms },
+  };
+}
@@ -6,7 +6,7 @@
  */
 
 import type { CommandResult } from '$lib/types/api.js';
-import type { AeryFixParams, AudioPreset, BMSEvent, BmsFolderSetNameType, IProgressManager, RemoveMediaPreset, ReplacePreset, SetFileNumParams, VideoPreset } from '$lib/types/enums.js';
+import type { AeryFixParams, AudioPreset, BMSEvent, BmsFolderSetNameType, ChartConflictStrategy, ChartNameScheme, IProgressManager, ImpressionStatus, LibraryBgaFilter, LibrarySortKey, MediaProfile, NumberAssignment, RemoveMediaPreset, ReplacePreset, SetFileNumParams, SimilarityMetric, TargetOperation, VideoPreset } from '$lib/types/enums.js';
 
 /**
  * 自动生成的前端命令执行函数
@@ -40,6 +40,24 @@ export async function executeGeneratedFrontendCommand(
       return { success: true, data: result };
     }
 
+    if (commandId === 'rawpack_list_candidates') {
+      const { listCandidates } = await import('$lib/utils/rawpack/index.js');
+      const result = await listCandidates(params.dir as string, params.exts as string[]);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'rawpack_apply_numbers') {
+      const { applyNumbers } = await import('$lib/utils/rawpack/index.js');
+      const result = await applyNumbers(params.dir as string, params.assignments as NumberAssignment[], params.dryRun as boolean);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'rawpack_set_file_num_with_mapping') {
+      const { setFileNumWithMapping } = await import('$lib/utils/rawpack/index.js');
+      const result = await setFileNumWithMapping(params.dir as string, params.mapping as Record<string, number>, params.dryRun as boolean);
+      return { success: true, data: result };
+    }
+
     if (commandId === 'rawpack_unzip_numeric_to_bms_folder') {
       const { unzipNumericToBmsFolder } = await import('$lib/utils/rawpack/index.js');
       await unzipNumericToBmsFolder(params.packDir as string, params.cacheDir as string, params.rootDir as string, params.confirm as boolean, params.replacePreset as ReplacePreset);
@@ -48,70 +66,112 @@ export async function executeGeneratedFrontendCommand(
 
     if (commandId === 'rawpack_unzip_with_name_to_bms_folder') {
       const { unzipWithNameToBmsFolder } = await import('$lib/utils/rawpack/index.js');
-      await unzipWithNameToBmsFolder(params.packDir as string, params.cacheDir as string, params.rootDir as string, params.confirm as boolean, params.replacePreset as ReplacePreset);
+      await unzipWithNameToBmsFolder(params.packDir as string, params.cacheDir as string, params.rootDir as string, params.confirm as boolean, params.replacePreset as ReplacePreset, params.force as boolean);
       return { success: true, data: undefined };
     }
 
+    if (commandId === 'rawpack_review_quarantine') {
+      const { reviewQuarantine } = await import('$lib/utils/rawpack/index.js');
+      const result = await reviewQuarantine(params.packDir as string);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'rawpack_retry_quarantine') {
+      const { retryQuarantine } = await import('$lib/utils/rawpack/index.js');
+      const result = await retryQuarantine(params.packDir as string, params.rootDir as string, params.entryName as string, params.replacePreset as ReplacePreset);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'rawpack_process_inbox') {
+      const { processInbox } = await import('$lib/utils/rawpack/index.js');
+      const result = await processInbox(params.inboxDir as string, params.rootDir as string, params.setType as BmsFolderSetNameType, params.replacePreset as ReplacePreset, params.dryRun as boolean);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'rawpack_clean_partials') {
+      const { cleanPartials } = await import('$lib/utils/rawpack/index.js');
+      const result = await cleanPartials(params.packDir as string, params.maxAgeHours as number, params.dryRun as boolean);
+      return { success: true, data: result };
+    }
+
     if (commandId === 'root_split_folders_with_first_char') {
       const { splitFoldersWithFirstChar } = await import('$lib/utils/bigpack/split.js');
-      await splitFoldersWithFirstChar(params.rootDir as string, params.dryRun as boolean);
-      return { success: true, data: undefined };
+      const result = await splitFoldersWithFirstChar(params.rootDir as string, params.dryRun as boolean);
+      return { success: true, data: result };
     }
 
     if (commandId === 'root_undo_split_pack') {
       const { undoSplitPack } = await import('$lib/utils/bigpack/split.js');
-      await undoSplitPack(params.rootDir as string, params.dryRun as boolean);
-      return { success: true, data: undefined };
+      const result = await undoSplitPack(params.rootDir as string, params.dryRun as boolean);
+      return { success: true, data: result };
     }
 
     if (commandId === 'root_move_works_in_pack') {
       const { moveWorksInPack } = await import('$lib/utils/bigpack/split.js');
-      await moveWorksInPack(params.rootDir as string, params.targetPackName as string, params.dryRun as boolean);
-      return { success: true, data: undefined };
+      const result = await moveWorksInPack(params.rootDir as string, params.targetPackName as string, params.dryRun as boolean);
+      return { success: true, data: result };
     }
 
     if (commandId === 'root_move_out_works') {
       const { moveOutWorks } = await import('$lib/utils/bigpack/split.js');
-      await moveOutWorks(params.rootDir as string, params.sourcePackName as string, params.dryRun as boolean);
-      return { success: true, data: undefined };
+      const result = await moveOutWorks(params.rootDir as string, params.sourcePackName as string, params.dryRun as boolean);
+      return { success: true, data: result };
     }
 
     if (commandId === 'root_merge_folders_with_same_name_within_dir') {
       const { mergeFoldersWithSameNameWithinDir } = await import('$lib/utils/bigpack/split.js');
-      await mergeFoldersWithSameNameWithinDir(params.rootDir as string, params.dryRun as boolean);
-      return { success: true, data: undefined };
+      const result = await mergeFoldersWithSameNameWithinDir(params.rootDir as string, params.dryRun as boolean);
+      return { success: true, data: result };
     }
 
     if (commandId === 'root_move_works_in_pack_python') {
       const { moveWorksInPackPython } = await import('$lib/utils/bigpack/split.js');
-      await moveWorksInPackPython(params.fromDir as string, params.toDir as string, params.dryRun as boolean);
-      return { success: true, data: undefined };
+      const result = await moveWorksInPackPython(params.fromDir as string, params.toDir as string, params.dryRun as boolean);
+      return { success: true, data: result };
     }
 
     if (commandId === 'root_move_works_with_same_name_to_siblings') {
       const { moveWorksWithSameNameToSiblings } = await import('$lib/utils/bigpack/split.js');
-      await moveWorksWithSameNameToSiblings(params.rootDir as string, params.dryRun as boolean);
-      return { success: true, data: undefined };
+      const result = await moveWorksWithSameNameToSiblings(params.rootDir as string, params.dryRun as boolean);
+      return { success: true, data: result };
     }
 
     if (commandId === 'root_move_works_with_same_name') {
       const { moveWorksWithSameName } = await import('$lib/utils/bigpack/split.js');
-      await moveWorksWithSameName(params.fromDir as string, params.toDir as string, params.dryRun as boolean);
-      return { success: true, data: undefined };
+      const result = await moveWorksWithSameName(params.fromDir as string, params.toDir as string, params.dryRun as boolean);
+      return { success: true, data: result };
     }
 
     if (commandId === 'root_merge_split_folders') {
       const { mergeSplitFolders } = await import('$lib/utils/bigpack/merge.js');
-      await mergeSplitFolders(params.rootDir as string, params.dryRun as boolean);
-      return { success: true, data: undefined };
+      const result = await mergeSplitFolders(params.rootDir as string, params.dryRun as boolean, params.progressManager as IProgressManager);
+      return { success: true, data: result };
     }
 
     if (commandId === 'root_scan_similar_folders') {
       const { scanSimilarFolders } = await import('$lib/utils/bigpack/similarity.js');
-      const result = await scanSimilarFolders(params.rootDir as string, params.similarityTrigger as number);
+      const result = await scanSimilarFolders(params.rootDir as string, params.similarityTrigger as number, params.metric as SimilarityMetric);
       return { success: true, data: result };
     }
 
+    if (commandId === 'root_organize_by_genre') {
+      const { organizeByGenre } = await import('$lib/utils/bigpack/genre.js');
+      await organizeByGenre(params.rootDir as string, params.dryRun as boolean);
+      return { success: true, data: undefined };
+    }
+
+    if (commandId === 'root_undo_organize_by_genre') {
+      const { undoOrganizeByGenre } = await import('$lib/utils/bigpack/genre.js');
+      await undoOrganizeByGenre(params.rootDir as string, params.dryRun as boolean);
+      return { success: true, data: undefined };
+    }
+
+    if (commandId === 'root_generate_contents_files') {
+      const { generateContentsFiles } = await import('$lib/utils/bigpack/contents.js');
+      await generateContentsFiles(params.rootDir as string);
+      return { success: true, data: undefined };
+    }
+
     if (commandId === 'read_and_parse_bms_file') {
       const { readAndParseBmsFile } = await import('$lib/utils/bms/scanner.js');
       const result = await readAndParseBmsFile(params.filePath as string);
@@ -120,12 +180,12 @@ export async function executeGeneratedFrontendCommand(
 
     if (commandId === 'get_dir_bms_list') {
       const { getDirBmsList } = await import('$lib/utils/bms/scanner.js');
-      const result = await getDirBmsList(params.dirPath as string);
+      const result = await getDirBmsList(params.dirPath as string, params.showWarnings as boolean);
       return { success: true, data: result };
     }
 
     if (commandId === 'get_dir_bms_info') {
-      const { getDirBmsInfo } = await import('$lib/utils/bms/scanner.js');
+      const { getDirBmsInfo } = await import('$lib/utils/bms/workInfo.js');
       const result = await getDirBmsInfo(params.dirPath as string);
       return { success: true, data: result };
     }
@@ -142,12 +202,42 @@ export async function executeGeneratedFrontendCommand(
       return { success: true, data: result };
     }
 
+    if (commandId === 'get_dir_bms_list_with_outcomes') {
+      const { getDirBmsListWithOutcomes } = await import('$lib/utils/bms/scanner.js');
+      const result = await getDirBmsListWithOutcomes(params.dirPath as string);
+      return { success: true, data: result };
+    }
+
     if (commandId === 'extract_work_name') {
       const { extractWorkName } = await import('$lib/utils/bms/work.js');
       const result = await extractWorkName(params.titles as string[], params.removeUnlosedPair as boolean, params.removeTailingSignList as string[]);
       return { success: true, data: result };
     }
 
+    if (commandId === 'bms_quick_info') {
+      const { bmsQuickInfo } = await import('$lib/utils/bms/quickInfo.js');
+      const result = await bmsQuickInfo(params.filePath as string);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'set_level') {
+      const { setLevel } = await import('$lib/utils/bms/level.js');
+      const result = await setLevel(params.rootDir as string, params.filePath as string, params.dryRun as boolean);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'diff_charts') {
+      const { diffCharts } = await import('$lib/utils/bms/diff.js');
+      const result = await diffCharts(params.oldFile as string, params.newFile as string);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'diff_work_dirs') {
+      const { diffWorkDirs } = await import('$lib/utils/bms/diff.js');
+      const result = await diffWorkDirs(params.oldWorkDir as string, params.newWorkDir as string);
+      return { success: true, data: result };
+    }
+
     if (commandId === 'root_event_check_num_folder') {
       const { checkNumFolder } = await import('$lib/utils/event/folder.js');
       const result = await checkNumFolder(params.dir as string, params.max as number);
@@ -172,12 +262,102 @@ export async function executeGeneratedFrontendCommand(
       return { success: true, data: undefined };
     }
 
+    if (commandId === 'root_event_download_works') {
+      const { downloadWorks } = await import('$lib/utils/event/download.js');
+      const result = await downloadWorks(params.event as BMSEvent, params.startNum as number, params.endNum as number, params.outputDir as string, params.maxConcurrent as number, params.bandwidthLimitKb as number);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'root_event_open_next_works') {
+      const { openNextWorks } = await import('$lib/utils/event/impression.js');
+      const result = await openNextWorks(params.event as BMSEvent, params.rootDir as string, params.count as number);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'root_event_mark_work') {
+      const { markWork } = await import('$lib/utils/event/impression.js');
+      await markWork(params.event as BMSEvent, params.workNum as number, params.status as ImpressionStatus);
+      return { success: true, data: undefined };
+    }
+
+    if (commandId === 'root_event_impression_progress') {
+      const { impressionProgress } = await import('$lib/utils/event/impression.js');
+      const result = await impressionProgress(params.event as BMSEvent, params.rootDir as string);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'root_event_scrape_work_info') {
+      const { scrapeWorkInfo } = await import('$lib/utils/event/scrape.js');
+      const result = await scrapeWorkInfo(params.event as BMSEvent, params.startNum as number, params.endNum as number);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'root_event_package_work') {
+      const { packageWork } = await import('$lib/utils/event/package.js');
+      const result = await packageWork(params.rootDir as string, params.num as number, params.outputDir as string, params.eventName as string, params.chartNameScheme as ChartNameScheme, params.withPreview as boolean, params.dryRun as boolean);
+      return { success: true, data: result };
+    }
+
     if (commandId === 'remove_empty_folders') {
       const { removeEmptyFolders } = await import('$lib/utils/fs/cleanup.js');
       await removeEmptyFolders(params.parentDir as string, params.dryRun as boolean);
       return { success: true, data: undefined };
     }
 
+    if (commandId === 'clean_macos_artifacts') {
+      const { cleanMacosArtifacts } = await import('$lib/utils/fs/cleanup.js');
+      const result = await cleanMacosArtifacts(params.parentDir as string, params.dryRun as boolean);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'clean_cache') {
+      const { cleanCache } = await import('$lib/utils/fs/cache.js');
+      const result = await cleanCache(params.maxAgeDays as number, params.dryRun as boolean);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'get_cache_usage') {
+      const { getCacheUsage } = await import('$lib/utils/fs/cache.js');
+      const result = await getCacheUsage();
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'remove_ignored_files') {
+      const { removeIgnoredFiles } = await import('$lib/utils/fs/ignore.js');
+      const result = await removeIgnoredFiles(params.parentDir as string, params.dryRun as boolean);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'copy_dir') {
+      const { copyDir } = await import('$lib/utils/fs/copy.js');
+      const result = await copyDir(params.fromDir as string, params.toDir as string, params.replacePreset as ReplacePreset, params.verify as boolean, params.dryRun as boolean);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'salvage_zip') {
+      const { salvageZip } = await import('$lib/utils/fs/zipSalvage.js');
+      const result = await salvageZip(params.file as string, params.dest as string);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'normalize_permissions') {
+      const { normalizePermissions } = await import('$lib/utils/fs/permissions.js');
+      await normalizePermissions(params.dir as string);
+      return { success: true, data: undefined };
+    }
+
+    if (commandId === 'find_open_files') {
+      const { findOpenFiles } = await import('$lib/utils/fs/openFiles.js');
+      const result = await findOpenFiles(params.dirPath as string);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'validate_target') {
+      const { validateTarget } = await import('$lib/utils/fs/targetCheck.js');
+      const result = await validateTarget(params.targetDir as string, params.operation as TargetOperation, params.sourceDir as string);
+      return { success: true, data: result };
+    }
+
     if (commandId === 'work_get_media_info') {
       const { getMediaInfo } = await import('$lib/utils/media/index.js');
       const result = await getMediaInfo(params.filePath as string);
@@ -210,26 +390,26 @@ export async function executeGeneratedFrontendCommand(
 
     if (commandId === 'work_remove_unneed_media_files') {
       const { removeUnneedMediaFiles } = await import('$lib/utils/media/index.js');
-      await removeUnneedMediaFiles(params.rootDir as string, params.preset as RemoveMediaPreset);
-      return { success: true, data: undefined };
+      const result = await removeUnneedMediaFiles(params.rootDir as string, params.preset as RemoveMediaPreset, params.includeSplitBuckets as boolean);
+      return { success: true, data: result };
     }
 
     if (commandId === 'work_transfer_audio') {
       const { transferAudio } = await import('$lib/utils/media/index.js');
-      await transferAudio(params.rootDir as string, params.presetNames as AudioPreset[], params.removeOriginFileWhenSuccess as boolean, params.removeOriginFileWhenFailed as boolean, params.skipOnFail as boolean, params.progressManager as IProgressManager);
-      return { success: true, data: undefined };
+      const result = await transferAudio(params.rootDir as string, params.presetNames as AudioPreset[], params.removeOriginFileWhenSuccess as boolean, params.removeOriginFileWhenFailed as boolean, params.skipOnFail as boolean, params.preserveMetadata as boolean, params.progressManager as IProgressManager);
+      return { success: true, data: result };
     }
 
     if (commandId === 'work_transfer_video') {
       const { transferVideo } = await import('$lib/utils/media/index.js');
-      await transferVideo(params.rootDir as string, params.presetNames as VideoPreset[], params.removeOriginFile as boolean, params.removeExistingTargetFile as boolean, params.usePrefered as boolean, params.progressManager as IProgressManager);
-      return { success: true, data: undefined };
+      const result = await transferVideo(params.rootDir as string, params.presetNames as VideoPreset[], params.removeOriginFile as boolean, params.removeExistingTargetFile as boolean, params.usePrefered as boolean, params.progressManager as IProgressManager);
+      return { success: true, data: result };
     }
 
     if (commandId === 'work_set_name_by_bms') {
       const { setNameByBms } = await import('$lib/utils/work/rename.js');
-      await setNameByBms(params.workDir as string, params.setType as BmsFolderSetNameType, params.dryRun as boolean, params.replacePreset as ReplacePreset, params.skipAlreadyFormatted as boolean);
-      return { success: true, data: undefined };
+      const result = await setNameByBms(params.workDir as string, params.setType as BmsFolderSetNameType, params.dryRun as boolean, params.replacePreset as ReplacePreset, params.skipAlreadyFormatted as boolean);
+      return { success: true, data: result };
     }
 
     if (commandId === 'work_undo_set_name_by_bms') {
@@ -250,15 +430,93 @@ export async function executeGeneratedFrontendCommand(
       return { success: true, data: result };
     }
 
+    if (commandId === 'work_split_obj') {
+      const { splitObj } = await import('$lib/utils/work/splitObj.js');
+      const result = await splitObj(params.workDir as string, params.copySharedMedia as boolean, params.dryRun as boolean);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'work_merge_works') {
+      const { mergeWorks } = await import('$lib/utils/work/mergeCharts.js');
+      const result = await mergeWorks(params.fromDir as string, params.toDir as string, params.strategy as ChartConflictStrategy, params.dryRun as boolean);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'work_find_unreferenced_bga') {
+      const { findUnreferencedBga } = await import('$lib/utils/work/bga.js');
+      const result = await findUnreferencedBga(params.rootDir as string);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'work_add_missing_bga') {
+      const { addMissingBga } = await import('$lib/utils/work/bga.js');
+      const result = await addMissingBga(params.rootDir as string, params.measure as number, params.dryRun as boolean);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'work_normalize_chart_names') {
+      const { normalizeChartNames } = await import('$lib/utils/work/normalizeChartNames.js');
+      const result = await normalizeChartNames(params.workDir as string, params.scheme as ChartNameScheme, params.dryRun as boolean);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'work_export_chart') {
+      const { exportChart } = await import('$lib/utils/work/exportChart.js');
+      const result = await exportChart(params.chartPath as string, params.outDir as string, params.dryRun as boolean);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'work_pin') {
+      const { pin } = await import('$lib/utils/work/pin.js');
+      await pin(params.workDir as string, params.note as string);
+      return { success: true, data: undefined };
+    }
+
+    if (commandId === 'work_unpin') {
+      const { unpin } = await import('$lib/utils/work/pin.js');
+      await unpin(params.workDir as string);
+      return { success: true, data: undefined };
+    }
+
+    if (commandId === 'work_list_pinned') {
+      const { listPinned } = await import('$lib/utils/work/pin.js');
+      const result = await listPinned(params.rootDir as string);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'work_lint') {
+      const { lint } = await import('$lib/utils/work/lint.js');
+      const result = await lint(params.workDir as string);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'work_fix_case_collisions') {
+      const { fixCaseCollisions } = await import('$lib/utils/work/lint.js');
+      const result = await fixCaseCollisions(params.workDir as string, params.dryRun as boolean);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'work_summarize_bga') {
+      const { summarizeBga } = await import('$lib/utils/work/bgaSummary.js');
+      const result = await summarizeBga(params.workDir as string);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'work_apply_chart_patch') {
+      const { applyChartPatch } = await import('$lib/utils/work/chartPatch.js');
+      const result = await applyChartPatch(params.archivePath as string, params.workDir as string, params.force as boolean, params.dryRun as boolean);
+      return { success: true, data: result };
+    }
+
     if (commandId === 'root_root_set_name_by_bms') {
       const { rootSetNameByBms } = await import('$lib/utils/root/batch.js');
-      await rootSetNameByBms(params.rootDir as string, params.setType as BmsFolderSetNameType, params.dryRun as boolean, params.replacePreset as ReplacePreset);
-      return { success: true, data: undefined };
+      const result = await rootSetNameByBms(params.rootDir as string, params.setType as BmsFolderSetNameType, params.dryRun as boolean, params.replacePreset as ReplacePreset, params.resume as boolean, params.includeSplitBuckets as boolean);
+      return { success: true, data: result };
     }
 
     if (commandId === 'root_root_undo_set_name_by_bms') {
       const { rootUndoSetNameByBms } = await import('$lib/utils/root/batch.js');
-      await rootUndoSetNameByBms(params.rootDir as string, params.setType as BmsFolderSetNameType, params.dryRun as boolean);
+      await rootUndoSetNameByBms(params.rootDir as string, params.setType as BmsFolderSetNameType, params.dryRun as boolean, params.includeSplitBuckets as boolean);
       return { success: true, data: undefined };
     }
 
@@ -268,30 +526,294 @@ export async function executeGeneratedFrontendCommand(
       return { success: true, data: undefined };
     }
 
+    if (commandId === 'root_compare_roots') {
+      const { compareRoots } = await import('$lib/utils/root/compare.js');
+      const result = await compareRoots(params.rootDirA as string, params.rootDirB as string);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'root_watch_library_root') {
+      const { watchLibraryRoot } = await import('$lib/utils/root/watcher.js');
+      const result = await watchLibraryRoot(params.rootDir as string);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'root_unwatch_library_root') {
+      const { unwatchLibraryRoot } = await import('$lib/utils/root/watcher.js');
+      const result = await unwatchLibraryRoot(params.rootDir as string);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'root_freeze_works') {
+      const { freezeWorks } = await import('$lib/utils/root/freeze.js');
+      const result = await freezeWorks(params.rootDir as string, params.olderThan as string, params.dryRun as boolean);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'root_unfreeze_works') {
+      const { unfreezeWorks } = await import('$lib/utils/root/freeze.js');
+      const result = await unfreezeWorks(params.rootDir as string, params.workName as string, params.dryRun as boolean);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'root_list_frozen_works') {
+      const { listFrozenWorks } = await import('$lib/utils/root/freeze.js');
+      const result = await listFrozenWorks(params.rootDir as string);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'root_promote') {
+      const { promote } = await import('$lib/utils/root/promote.js');
+      const result = await promote(params.fromDir as string, params.toDir as string, params.dryRun as boolean);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'root_audit_names') {
+      const { auditNames } = await import('$lib/utils/root/auditNames.js');
+      const result = await auditNames(params.rootDir as string);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'root_enforce_profile') {
+      const { enforceProfile } = await import('$lib/utils/root/enforceProfile.js');
+      const result = await enforceProfile(params.rootDir as string, params.profile as MediaProfile, params.dryRun as boolean);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'root_find_orphan_charts') {
+      const { findOrphanCharts } = await import('$lib/utils/root/orphanCharts.js');
+      const result = await findOrphanCharts(params.rootDir as string);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'root_fix_orphan_charts') {
+      const { fixOrphanCharts } = await import('$lib/utils/root/orphanCharts.js');
+      const result = await fixOrphanCharts(params.rootDir as string, params.dryRun as boolean);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'root_find_convertibles') {
+      const { findConvertibles } = await import('$lib/utils/root/findConvertibles.js');
+      const result = await findConvertibles(params.rootDir as string, params.mp4SizeLimitMb as number, params.audioSamplesPerWork as number);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'root_find_swapped_fields') {
+      const { findSwappedFields } = await import('$lib/utils/root/swappedFields.js');
+      const result = await findSwappedFields(params.rootDir as string);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'root_fix_swapped_fields') {
+      const { fixSwappedFields } = await import('$lib/utils/root/swappedFields.js');
+      const result = await fixSwappedFields(params.rootDir as string, params.works as string[], params.setType as BmsFolderSetNameType, params.replacePreset as ReplacePreset, params.dryRun as boolean);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'root_audio_report') {
+      const { audioReport } = await import('$lib/utils/root/audioReport.js');
+      const result = await audioReport(params.rootDir as string, params.samplesPerWork as number, params.maxSampleRate as number, params.maxBitDepth as number);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'root_check_remove_media_compat') {
+      const { checkRemoveMediaCompat } = await import('$lib/utils/root/mediaCompat.js');
+      const result = await checkRemoveMediaCompat(params.rootDir as string, params.preset as RemoveMediaPreset, params.players as string[]);
+      return { success: true, data: result };
+    }
+
     if (commandId === 'pack_setup_rawpack_to_hq') {
       const { setupRawpackToHq } = await import('$lib/utils/pack/pack.js');
-      await setupRawpackToHq(params.packDir as string, params.rootDir as string, params.dryRun as boolean);
-      return { success: true, data: undefined };
+      const result = await setupRawpackToHq(params.packDir as string, params.rootDir as string, params.dryRun as boolean, params.progressManager as IProgressManager);
+      return { success: true, data: result };
     }
 
     if (commandId === 'pack_update_rawpack_to_hq') {
       const { updateRawpackToHq } = await import('$lib/utils/pack/pack.js');
-      await updateRawpackToHq(params.packDir as string, params.rootDir as string, params.syncDir as string, params.dryRun as boolean);
-      return { success: true, data: undefined };
+      const result = await updateRawpackToHq(params.packDir as string, params.rootDir as string, params.syncDir as string, params.dryRun as boolean, params.progressManager as IProgressManager);
+      return { success: true, data: result };
     }
 
     if (commandId === 'pack_pack_hq_to_lq') {
       const { packHqToLq } = await import('$lib/utils/pack/pack.js');
-      await packHqToLq(params.rootDir as string, params.dryRun as boolean, params.progressManager as IProgressManager);
-      return { success: true, data: undefined };
+      const result = await packHqToLq(params.rootDir as string, params.dryRun as boolean, params.progressManager as IProgressManager);
+      return { success: true, data: result };
     }
 
     if (commandId === 'pack_pack_raw_to_hq') {
       const { packRawToHq } = await import('$lib/utils/pack/pack.js');
-      await packRawToHq(params.rootDir as string, params.dryRun as boolean, params.progressManager as IProgressManager);
+      const result = await packRawToHq(params.rootDir as string, params.dryRun as boolean, params.progressManager as IProgressManager);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'pack_pack_dual_build') {
+      const { packDualBuild } = await import('$lib/utils/pack/pack.js');
+      const result = await packDualBuild(params.packDir as string, params.hqDir as string, params.lqDir as string, params.dryRun as boolean, params.progressManager as IProgressManager);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'pack_list_history') {
+      const { listHistory } = await import('$lib/utils/pack/history.js');
+      const result = await listHistory(params.limit as number);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'pack_show_history') {
+      const { showHistory } = await import('$lib/utils/pack/history.js');
+      const result = await showHistory(params.id as string);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'library_query') {
+      const { query } = await import('$lib/utils/library/query.js');
+      const result = await query(params.rootDir as string, params.title as string, params.artist as string, params.keymode as string, params.minLevel as number, params.maxLevel as number, params.tags as string, params.bga as LibraryBgaFilter, params.sortBy as LibrarySortKey, params.descending as boolean, params.offset as number, params.limit as number);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'library_set_work_tags') {
+      const { setWorkTags } = await import('$lib/utils/library/tags.js');
+      const result = await setWorkTags(params.workDir as string, params.tags as string);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'library_index_root') {
+      const { indexRoot } = await import('$lib/utils/library/workIndex.js');
+      const result = await indexRoot(params.rootDir as string, params.force as boolean);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'library_remove_index') {
+      const { removeIndex } = await import('$lib/utils/library/workIndex.js');
+      const result = await removeIndex(params.rootDir as string);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'library_search') {
+      const { search } = await import('$lib/utils/library/search.js');
+      const result = await search(params.text as string, params.rootDir as string, params.limit as number);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'library_scrub') {
+      const { scrub } = await import('$lib/utils/library/scrub.js');
+      const result = await scrub(params.rootDir as string, params.acceptChanges as boolean);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'library_set_scrub_schedule') {
+      const { setScrubSchedule } = await import('$lib/utils/library/scrub.js');
+      const result = await setScrubSchedule(params.rootDir as string, params.intervalDays as number);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'library_scrub_due') {
+      const { scrubDue } = await import('$lib/utils/library/scrub.js');
+      const result = await scrubDue();
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'library_changes') {
+      const { changes } = await import('$lib/utils/library/changes.js');
+      const result = await changes(params.rootDir as string, params.since as string, params.refresh as boolean);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'library_shared_sound_report') {
+      const { sharedSoundReport } = await import('$lib/utils/library/sharedSound.js');
+      const result = await sharedSoundReport(params.rootDir as string, params.minSharedPercent as number, params.refresh as boolean);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'library_export_index') {
+      const { exportIndex } = await import('$lib/utils/library/exportIndex.js');
+      const result = await exportIndex(params.rootDir as string, params.filePath as string, params.refresh as boolean);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'library_enrich_from_bms_search') {
+      const { enrichFromBmsSearch } = await import('$lib/utils/library/bmsSearch.js');
+      const result = await enrichFromBmsSearch(params.rootDir as string, params.onlyMissing as boolean);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'library_index_bga') {
+      const { indexBga } = await import('$lib/utils/library/bga.js');
+      const result = await indexBga(params.rootDir as string, params.onlyMissing as boolean);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'logs_open_folder') {
+      const { openFolder } = await import('$lib/utils/logs/manage.js');
+      await openFolder();
       return { success: true, data: undefined };
     }
 
+    if (commandId === 'logs_export_archive') {
+      const { exportArchive } = await import('$lib/utils/logs/manage.js');
+      const result = await exportArchive(params.outputDir as string);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'cancel_job') {
+      const { cancelJob } = await import('$lib/utils/jobs.js');
+      const result = await cancelJob(params.jobId as string);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'list_jobs') {
+      const { listJobs } = await import('$lib/utils/jobs.js');
+      const result = await listJobs(params.includeFinished as boolean);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'get_job') {
+      const { getJob } = await import('$lib/utils/jobs.js');
+      const result = await getJob(params.jobId as string);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'get_resource_stats') {
+      const { getResourceStats } = await import('$lib/utils/telemetry.js');
+      const result = await getResourceStats();
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'get_settings') {
+      const { getSettings } = await import('$lib/utils/settings.js');
+      const result = await getSettings();
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'load_settings') {
+      const { loadSettings } = await import('$lib/utils/settings.js');
+      const result = await loadSettings();
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'get_setting') {
+      const { getSetting } = await import('$lib/utils/settings.js');
+      const result = await getSetting(params.key as string);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'set_setting') {
+      const { setSetting } = await import('$lib/utils/settings.js');
+      const result = await setSetting(params.key as string, params.value as string);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'history_list') {
+      const { historyList } = await import('$lib/utils/recentDirs.js');
+      const result = await historyList(params.kind as string);
+      return { success: true, data: result };
+    }
+
+    if (commandId === 'history_add') {
+      const { historyAdd } = await import('$lib/utils/recentDirs.js');
+      const result = await historyAdd(params.kind as string, params.path as string);
+      return { success: true, data: result };
+    }
+
     return {
       success: false,
       error: '未知的前端命令'
@@ -312,8 +834,15 @@ export const FRONTEND_COMMAND_IDS: string[] = [
   'is_dir_having_content',
   'wasted_fix',
   'rawpack_batch_rename_with_num',
+  'rawpack_list_candidates',
+  'rawpack_apply_numbers',
+  'rawpack_set_file_num_with_mapping',
   'rawpack_unzip_numeric_to_bms_folder',
   'rawpack_unzip_with_name_to_bms_folder',
+  'rawpack_review_quarantine',
+  'rawpack_retry_quarantine',
+  'rawpack_process_inbox',
+  'rawpack_clean_partials',
   'root_split_folders_with_first_char',
   'root_undo_split_pack',
   'root_move_works_in_pack',
@@ -324,17 +853,40 @@ export const FRONTEND_COMMAND_IDS: string[] = [
   'root_move_works_with_same_name',
   'root_merge_split_folders',
   'root_scan_similar_folders',
+  'root_organize_by_genre',
+  'root_undo_organize_by_genre',
+  'root_generate_contents_files',
   'read_and_parse_bms_file',
   'get_dir_bms_list',
   'get_dir_bms_info',
   'is_work_dir',
   'is_root_dir',
+  'get_dir_bms_list_with_outcomes',
   'extract_work_name',
+  'bms_quick_info',
+  'set_level',
+  'diff_charts',
+  'diff_work_dirs',
   'root_event_check_num_folder',
   'root_event_create_num_folders',
   'root_event_generate_work_info_table',
   'root_event_jump_to_work_info',
+  'root_event_download_works',
+  'root_event_open_next_works',
+  'root_event_mark_work',
+  'root_event_impression_progress',
+  'root_event_scrape_work_info',
+  'root_event_package_work',
   'remove_empty_folders',
+  'clean_macos_artifacts',
+  'clean_cache',
+  'get_cache_usage',
+  'remove_ignored_files',
+  'copy_dir',
+  'salvage_zip',
+  'normalize_permissions',
+  'find_open_files',
+  'validate_target',
   'work_get_media_info',
   'work_get_video_info',
   'work_get_video_size',
@@ -347,11 +899,68 @@ export const FRONTEND_COMMAND_IDS: string[] = [
   'work_undo_set_name_by_bms',
   'work_append_artist_name_by_bms',
   'work_set_name_by_bms_optimized',
+  'work_split_obj',
+  'work_merge_works',
+  'work_find_unreferenced_bga',
+  'work_add_missing_bga',
+  'work_normalize_chart_names',
+  'work_export_chart',
+  'work_pin',
+  'work_unpin',
+  'work_list_pinned',
+  'work_lint',
+  'work_fix_case_collisions',
+  'work_summarize_bga',
+  'work_apply_chart_patch',
   'root_root_set_name_by_bms',
   'root_root_undo_set_name_by_bms',
   'root_copy_numbered_workdir_names',
+  'root_compare_roots',
+  'root_watch_library_root',
+  'root_unwatch_library_root',
+  'root_freeze_works',
+  'root_unfreeze_works',
+  'root_list_frozen_works',
+  'root_promote',
+  'root_audit_names',
+  'root_enforce_profile',
+  'root_find_orphan_charts',
+  'root_fix_orphan_charts',
+  'root_find_convertibles',
+  'root_find_swapped_fields',
+  'root_fix_swapped_fields',
+  'root_audio_report',
+  'root_check_remove_media_compat',
   'pack_setup_rawpack_to_hq',
   'pack_update_rawpack_to_hq',
   'pack_pack_hq_to_lq',
-  'pack_pack_raw_to_hq'
+  'pack_pack_raw_to_hq',
+  'pack_pack_dual_build',
+  'pack_list_history',
+  'pack_show_history',
+  'library_query',
+  'library_set_work_tags',
+  'library_index_root',
+  'library_remove_index',
+  'library_search',
+  'library_scrub',
+  'library_set_scrub_schedule',
+  'library_scrub_due',
+  'library_changes',
+  'library_shared_sound_report',
+  'library_export_index',
+  'library_enrich_from_bms_search',
+  'library_index_bga',
+  'logs_open_folder',
+  'logs_export_archive',
+  'cancel_job',
+  'list_jobs',
+  'get_job',
+  'get_resource_stats',
+  'get_settings',
+  'load_settings',
+  'get_setting',
+  'set_setting',
+  'history_list',
+  'history_add'
 ];
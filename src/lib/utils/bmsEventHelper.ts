@@ -4,8 +4,14 @@
  * 提供浏览器打开 BMS 活动相关页面的功能
  */
 
-import { BMSEvent } from '../types/enums';
+import { getCommandById } from '../data/commandRegistry';
+import { BMSEvent, ParameterType } from '../types/enums';
 import { executeGeneratedFrontendCommand, FRONTEND_COMMAND_IDS } from './bmsEventHelper.generated';
+import { withRootLocks } from './fs/lock';
+import { OpenFileGuard } from './fs/openFiles';
+import { ensureDirsWritable } from './fs/writable';
+import { JobRegistry } from './jobs';
+import { RunLog } from './logs/runLog';
 
 /**
  * 获取 BMS 活动列表页面 URL
@@ -86,16 +92,117 @@ export function isFrontendCommand(commandId: string): boolean {
   return FRONTEND_COMMAND_IDS.includes(commandId);
 }
 
+/**
+ * 不登记为任务的命令：查看或取消任务、读写设置和最近使用的目录
+ *
+ * 界面会频繁轮询这些命令，登记为任务会写入运行日志，并挤掉已完成的真实任务
+ */
+const UNTRACKED_COMMAND_IDS = new Set([
+  'list_jobs',
+  'get_job',
+  'cancel_job',
+  'get_settings',
+  'load_settings',
+  'get_setting',
+  'set_setting',
+  'history_list',
+  'history_add',
+]);
+
+/**
+ * 命令是否登记为任务
+ */
+export function isTrackedCommand(commandId: string): boolean {
+  return !UNTRACKED_COMMAND_IDS.has(commandId);
+}
+
+/**
+ * 命令参数中的目录
+ */
+function getDirParams(commandId: string, params: Record<string, unknown>): string[] {
+  return (getCommandById(commandId)?.parameters ?? [])
+    .filter((param) => param.type === ParameterType.Directory)
+    .map((param) => params[param.key])
+    .filter((value): value is string => typeof value === 'string' && value.length > 0);
+}
+
+/**
+ * 在后台启动前端命令，立即返回任务 ID
+ *
+ * 命令登记为任务，可以通过 list_jobs / get_job 查看进度，通过 cancel_job 取消；
+ * 结果通过 JobRegistry.wait 获取
+ *
+ * @param commandId - 命令 ID
+ * @param params - 命令参数
+ * @returns 任务 ID
+ * @throws 命令不登记为任务时
+ */
+export function startFrontendCommand(commandId: string, params: Record<string, unknown>): string {
+  if (!isTrackedCommand(commandId)) {
+    throw new Error(`该命令不登记为任务，不能在后台启动：${commandId}`);
+  }
+  const name = getCommandById(commandId)?.name ?? commandId;
+  const job = JobRegistry.start(commandId, name, getDirParams(commandId, params), (progress) =>
+    // 每次执行的输出都写入日志文件，出问题时可以追溯
+    RunLog.capture(commandId, params, async () => {
+      // 支持进度管理器的命令使用任务的进度管理器，可以被取消
+      const result = await runFrontendCommand(commandId, {
+        ...params,
+        progressManager: params.progressManager ?? progress,
+      });
+      if (!result.success) {
+        console.error(` !_! ${commandId} failed: ${result.error}`);
+      }
+      return result;
+    })
+  );
+  return job.id;
+}
+
 /**
  * 执行前端命令（使用自动生成的路由）
  *
+ * 危险命令（非模拟运行）执行前先确认目录参数都可以写入，只读位置直接报错中止；
+ * 执行期间锁定这些目录，其他操作正在使用时报错中止。所有命令的输出都记录到运行日志，
+ * 执行期间登记为任务，可以通过 cancel_job 取消（查看任务、设置等命令除外，直接执行）
+ *
  * @param commandId - 命令 ID
  * @param params - 命令参数
+ * @param onJobStarted - 任务登记后立即调用（用于显示进度或取消）
  * @returns 命令执行结果
  */
 export async function executeFrontendCommand(
+  commandId: string,
+  params: Record<string, unknown>,
+  onJobStarted?: (jobId: string) => void
+): Promise<{ success: boolean; data?: unknown; error?: string }> {
+  if (!isTrackedCommand(commandId)) {
+    return await executeGeneratedFrontendCommand(commandId, params);
+  }
+  const jobId = startFrontendCommand(commandId, params);
+  onJobStarted?.(jobId);
+  return await JobRegistry.wait(jobId);
+}
+
+async function runFrontendCommand(
   commandId: string,
   params: Record<string, unknown>
 ): Promise<{ success: boolean; data?: unknown; error?: string }> {
-  return executeGeneratedFrontendCommand(commandId, params);
+  const command = getCommandById(commandId);
+  if (!command?.dangerous || params.dryRun === true) {
+    return executeGeneratedFrontendCommand(commandId, params);
+  }
+
+  const dirPaths = getDirParams(commandId, params);
+  try {
+    await ensureDirsWritable(dirPaths);
+    // 目录中的文件正被编辑器打开时，重命名或移动可能导致编辑器保存时损坏谱面
+    await OpenFileGuard.check(dirPaths);
+    // 锁定操作的目录，防止其他窗口或进程同时修改
+    return await withRootLocks(dirPaths, command.name, () =>
+      executeGeneratedFrontendCommand(commandId, params)
+    );
+  } catch (error) {
+    return { success: false, error: error instanceof Error ? error.message : String(error) };
+  }
 }
@@ -6,25 +6,34 @@
 
 import { invoke } from '@tauri-apps/api/core';
 import type { CommandResult } from '../types/api';
-import { executeFrontendCommand, isFrontendCommand } from './bmsEventHelper';
+import { executeFrontendCommand, isFrontendCommand, startFrontendCommand } from './bmsEventHelper';
+import { RecentDirs } from './recentDirs';
 
 /**
  * 调用 Tauri 命令
  *
  * @param commandName - 命令名称（对应 Rust 中的命令名）
  * @param params - 命令参数
+ * @param onJobStarted - 前端命令登记为任务后调用（用于显示进度或取消）
  * @returns 命令执行结果
  */
 export async function invokeCommand<T = unknown>(
   commandName: string,
-  params?: Record<string, unknown>
+  params?: Record<string, unknown>,
+  onJobStarted?: (jobId: string) => void
 ): Promise<CommandResult<T>> {
   const startTime = performance.now();
 
   // 前端命令特殊处理
   if (isFrontendCommand(commandName)) {
-    const result = await executeFrontendCommand(commandName, params || {});
+    const result = await executeFrontendCommand(commandName, params || {}, onJobStarted);
     const executionTime = performance.now() - startTime;
+    if (result.success) {
+      // 记录参数中的目录，之后选择目录时可以直接选择
+      await RecentDirs.addFromParams(params || {}).catch((error) =>
+        console.warn(' !_! Failed to record recent directories:', error)
+      );
+    }
 
     return {
       success: result.success,
@@ -55,6 +64,21 @@ export async function invokeCommand<T = unknown>(
   }
 }
 
+/**
+ * 在后台启动前端命令，立即返回任务 ID（通过 list_jobs / get_job 查看进度，cancel_job 取消）
+ *
+ * @param commandName - 命令名称
+ * @param params - 命令参数
+ * @returns 任务 ID
+ * @throws 不是前端命令时（后端命令不登记任务）
+ */
+export function startCommand(commandName: string, params?: Record<string, unknown>): string {
+  if (!isFrontendCommand(commandName)) {
+    throw new Error(`只有前端命令可以在后台启动：${commandName}`);
+  }
+  return startFrontendCommand(commandName, params || {});
+}
+
 /**
  * 调用 Tauri 命令（带重试）
  *
@@ -0,0 +1,87 @@
+/**
+ * 执行中的确认
+ *
+ * 命令执行到需要用户确认的步骤时（如解压前确认压缩包和目标目录）调用 Confirmation.request，
+ * 默认弹出系统对话框询问；无界面运行（如脚本调用）时可以用 setHandler 换成其他确认方式，
+ * 例如从标准输入读取或总是确认
+ */
+
+import { ask } from '@tauri-apps/plugin-dialog';
+
+/**
+ * 确认请求
+ */
+export interface ConfirmRequest {
+  title: string;
+  message: string;
+  /** 详细列表（如将要处理的文件） */
+  details?: string[];
+}
+
+/**
+ * 确认方式：返回是否继续
+ */
+export type ConfirmHandler = (request: ConfirmRequest) => Promise<boolean>;
+
+/**
+ * 对话框中最多列出的详细项
+ */
+const MAX_DIALOG_DETAILS = 20;
+
+/**
+ * 通过系统对话框确认
+ */
+async function dialogHandler(request: ConfirmRequest): Promise<boolean> {
+  const details = request.details ?? [];
+  const lines = [request.message, '', ...details.slice(0, MAX_DIALOG_DETAILS)];
+  if (details.length > MAX_DIALOG_DETAILS) {
+    lines.push(`……还有 ${details.length - MAX_DIALOG_DETAILS} 项`);
+  }
+  return await ask(lines.join('\n'), {
+    title: request.title,
+    kind: 'warning',
+    okLabel: '继续',
+    cancelLabel: '取消',
+  });
+}
+
+/**
+ * 确认方式设置
+ */
+export class Confirmation {
+  private static handler: ConfirmHandler = dialogHandler;
+
+  /**
+   * 设置确认方式（null 恢复为系统对话框）
+   */
+  static setHandler(handler: ConfirmHandler | null): void {
+    this.handler = handler ?? dialogHandler;
+  }
+
+  /**
+   * 请求确认
+   *
+   * 任务已取消或确认失败（如对话框无法打开）时视为不继续
+   *
+   * @param signal - 任务的取消信号（可选）
+   * @returns 是否继续
+   */
+  static async request(request: ConfirmRequest, signal?: AbortSignal): Promise<boolean> {
+    if (signal?.aborted) {
+      return false;
+    }
+    console.log(`Waiting for confirmation: ${request.title}`);
+    for (const detail of request.details ?? []) {
+      console.log(`   ${detail}`);
+    }
+
+    try {
+      const accepted = (await this.handler(request)) && !signal?.aborted;
+      console.log(accepted ? 'Confirmed.' : 'Declined.');
+      return accepted;
+    } catch (error) {
+      console.warn(' !_! Confirmation failed, not continuing:', error);
+      return false;
+    }
+  }
+}
@@ -0,0 +1,361 @@
+/**
+ * HTTP 下载工具
+ *
+ * 支持断点续传（Range 请求）、并发数限制、失败重试（指数退避）和带宽上限，
+ * 用于长时间无人值守的批量下载。网盘分享链接会先通过 LinkResolverRegistry 解析为直链
+ */
+
+import { fetch } from '@tauri-apps/plugin-http';
+import { exists, open, remove, rename, stat } from '@tauri-apps/plugin-fs';
+import { ConcurrencyPool } from './media/concurrency';
+import { LinkResolverRegistry, UnsupportedLinkError } from './resolver';
+
+/**
+ * 下载选项
+ */
+export interface DownloadOptions {
+  /** 最大同时下载数 */
+  maxConcurrent: number;
+  /** 失败后的最大重试次数 */
+  maxRetries: number;
+  /** 重试的初始等待时间（毫秒），每次翻倍 */
+  retryBaseDelayMs: number;
+  /** 重试的最长等待时间（毫秒） */
+  retryMaxDelayMs: number;
+  /** 带宽上限（字节/秒），0 表示不限制 */
+  bandwidthLimit: number;
+  /** 对同一主机的两次请求之间的最小间隔（毫秒） */
+  hostIntervalMs: number;
+}
+
+/**
+ * 默认下载选项
+ */
+export const DEFAULT_DOWNLOAD_OPTIONS: DownloadOptions = {
+  maxConcurrent: 2,
+  maxRetries: 5,
+  retryBaseDelayMs: 2000,
+  retryMaxDelayMs: 5 * 60 * 1000,
+  bandwidthLimit: 0,
+  hostIntervalMs: 1000,
+};
+
+/**
+ * 下载结果
+ */
+export interface DownloadResult {
+  url: string;
+  filePath: string;
+  /** 文件大小（字节） */
+  size: number;
+  /** 是否从未完成的部分继续下载 */
+  resumed: boolean;
+  /** 尝试次数 */
+  attempts: number;
+  /** 服务器建议的文件名（来自 Content-Disposition 或 URL） */
+  suggestedName?: string;
+  contentType?: string;
+}
+
+/**
+ * HTTP 状态错误
+ */
+export class HttpStatusError extends Error {
+  constructor(
+    readonly status: number,
+    readonly retryAfterMs?: number
+  ) {
+    super(`HTTP ${status}`);
+  }
+
+  /** 429 和 5xx 可以重试，其余 4xx 直接失败 */
+  get retryable(): boolean {
+    return this.status === 429 || this.status >= 500;
+  }
+}
+
+/**
+ * 未完成下载的文件后缀
+ */
+export const PARTIAL_DOWNLOAD_SUFFIX = '.part';
+
+function sleep(ms: number): Promise<void> {
+  return new Promise((resolve) => setTimeout(resolve, ms));
+}
+
+/**
+ * 从响应头或 URL 获取文件名
+ */
+export function getSuggestedFileName(url: string, headers: Headers): string | undefined {
+  const disposition = headers.get('content-disposition');
+  if (disposition) {
+    const encoded = disposition.match(/filename\*\s*=\s*(?:UTF-8|utf-8)''([^;]+)/);
+    if (encoded) {
+      try {
+        return decodeURIComponent(encoded[1].trim());
+      } catch {
+        // 编码错误时继续尝试普通文件名
+      }
+    }
+
+    const plain = disposition.match(/filename\s*=\s*"?([^";]+)"?/);
+    if (plain) {
+      return plain[1].trim();
+    }
+  }
+
+  try {
+    const lastSegment = new URL(url).pathname.split('/').pop();
+    return lastSegment ? decodeURIComponent(lastSegment) : undefined;
+  } catch {
+    return undefined;
+  }
+}
+
+/**
+ * 令牌桶带宽限制（所有下载共享）
+ */
+class BandwidthLimiter {
+  private available: number;
+  private lastRefill = Date.now();
+
+  constructor(private readonly bytesPerSecond: number) {
+    this.available = bytesPerSecond;
+  }
+
+  /**
+   * 消耗指定字节数的额度，额度不足时等待
+   */
+  async consume(bytes: number): Promise<void> {
+    if (this.bytesPerSecond <= 0) {
+      return;
+    }
+
+    while (true) {
+      const now = Date.now();
+      this.available = Math.min(
+        this.bytesPerSecond,
+        this.available + ((now - this.lastRefill) / 1000) * this.bytesPerSecond
+      );
+      this.lastRefill = now;
+
+      if (this.available >= bytes || this.available >= this.bytesPerSecond) {
+        this.available -= bytes;
+        return;
+      }
+
+      await sleep(((bytes - this.available) / this.bytesPerSecond) * 1000);
+    }
+  }
+}
+
+/**
+ * 批量下载器
+ *
+ * @example
+ * ```typescript
+ * const downloader = new Downloader({ maxConcurrent: 2, bandwidthLimit: 1024 * 1024 });
+ * const result = await downloader.download('https://example.com/a.zip', '/path/to/a.zip');
+ * ```
+ */
+export class Downloader {
+  private readonly options: DownloadOptions;
+  private readonly pool: ConcurrencyPool<DownloadResult>;
+  private readonly limiter: BandwidthLimiter;
+  /** 各主机下一次允许请求的时间 */
+  private readonly hostNextRequest = new Map<string, number>();
+
+  constructor(options: Partial<DownloadOptions> = {}) {
+    this.options = { ...DEFAULT_DOWNLOAD_OPTIONS, ...options };
+    this.pool = new ConcurrencyPool<DownloadResult>(Math.max(1, this.options.maxConcurrent));
+    this.limiter = new BandwidthLimiter(this.options.bandwidthLimit);
+  }
+
+  /**
+   * 等待到可以再次请求该主机
+   */
+  async waitForHost(url: string): Promise<void> {
+    const host = new URL(url).host;
+    const now = Date.now();
+    const next = this.hostNextRequest.get(host) ?? now;
+    this.hostNextRequest.set(host, Math.max(now, next) + this.options.hostIntervalMs);
+    if (next > now) {
+      await sleep(next - now);
+    }
+  }
+
+  /**
+   * 发送请求（遵守主机请求间隔）
+   */
+  async request(url: string, init?: RequestInit): Promise<Response> {
+    await this.waitForHost(url);
+    return await fetch(url, init);
+  }
+
+  /**
+   * 请求文本内容（遵守主机请求间隔，失败时重试）
+   */
+  async fetchText(url: string): Promise<string> {
+    const { value } = await this.withRetry(url, async () => {
+      const response = await this.request(url);
+      this.checkResponse(response);
+      return await response.text();
+    });
+    return value;
+  }
+
+  /**
+   * 下载文件（在并发限制内执行）
+   *
+   * 下载中的数据写入 `<filePath>.part`，完成后重命名；再次下载同一文件时从断点继续
+   *
+   * @throws {UnsupportedLinkError} 链接所在站点不支持自动下载时
+   */
+  async download(url: string, filePath: string): Promise<DownloadResult> {
+    return await this.pool.add(async () => {
+      const partPath = `${filePath}${PARTIAL_DOWNLOAD_SUFFIX}`;
+      const resumed = await exists(partPath);
+      const { value, attempts } = await this.withRetry(url, async () => {
+        // 每次尝试重新解析，网盘的确认参数可能已过期
+        const directUrl = await LinkResolverRegistry.resolve(url, (target, init) =>
+          this.request(target, init)
+        );
+        return await this.downloadOnce(directUrl, partPath);
+      });
+
+      if (await exists(filePath)) {
+        await remove(filePath);
+      }
+      await rename(partPath, filePath);
+
+      return { url, filePath, resumed, attempts, ...value };
+    });
+  }
+
+  /**
+   * 等待所有下载完成
+   */
+  async drain(): Promise<void> {
+    await this.pool.drain();
+  }
+
+  private checkResponse(response: Response): void {
+    if (response.ok) {
+      return;
+    }
+
+    const retryAfter = Number(response.headers.get('retry-after'));
+    throw new HttpStatusError(
+      response.status,
+      Number.isFinite(retryAfter) && retryAfter > 0 ? retryAfter * 1000 : undefined
+    );
+  }
+
+  /**
+   * 执行请求，网络错误、连接中断和可重试的 HTTP 错误按指数退避重试
+   */
+  private async withRetry<T>(
+    url: string,
+    task: () => Promise<T>
+  ): Promise<{ value: T; attempts: number }> {
+    let attempt = 0;
+
+    while (true) {
+      attempt++;
+      try {
+        return { value: await task(), attempts: attempt };
+      } catch (error) {
+        const retryable =
+          error instanceof HttpStatusError
+            ? error.retryable
+            : !(error instanceof UnsupportedLinkError);
+        if (!retryable || attempt > this.options.maxRetries) {
+          throw error;
+        }
+
+        const backoff = Math.min(
+          this.options.retryMaxDelayMs,
+          this.options.retryBaseDelayMs * 2 ** (attempt - 1)
+        );
+        const delay =
+          error instanceof HttpStatusError && error.retryAfterMs !== undefined
+            ? error.retryAfterMs
+            : backoff + Math.random() * 1000;
+        console.warn(
+          `Download failed (${url}), retry ${attempt}/${this.options.maxRetries} ` +
+            `in ${Math.round(delay / 1000)}s: ${error}`
+        );
+        await sleep(delay);
+      }
+    }
+  }
+
+  /**
+   * 单次下载尝试：已有部分文件时使用 Range 请求继续
+   */
+  private async downloadOnce(
+    url: string,
+    partPath: string
+  ): Promise<Pick<DownloadResult, 'size' | 'suggestedName' | 'contentType'>> {
+    const offset = (await exists(partPath)) ? (await stat(partPath)).size : 0;
+
+    const response = await this.request(url, {
+      headers: offset > 0 ? { Range: `bytes=${offset}-` } : {},
+    });
+
+    const suggestedName = getSuggestedFileName(response.url || url, response.headers);
+    const contentType = response.headers.get('content-type') ?? undefined;
+
+    // 部分文件已经完整
+    if (response.status === 416 && offset > 0) {
+      return { size: offset, suggestedName, contentType };
+    }
+
+    this.checkResponse(response);
+
+    // 服务器不支持 Range 时返回 200，从头开始下载
+    const append = offset > 0 && response.status === 206;
+    if (offset > 0 && !append) {
+      console.log(`Server does not support resume, restarting: ${url}`);
+    }
+
+    const file = await open(partPath, {
+      write: true,
+      create: true,
+      append,
+      truncate: !append,
+    });
+    let size = append ? offset : 0;
+
+    // 已写入的数据保留在部分文件中，连接中断时下次重试从断点继续
+    try {
+      if (!response.body) {
+        throw new Error(`Empty response body: ${url}`);
+      }
+
+      const reader = response.body.getReader();
+      while (true) {
+        const { done, value } = await reader.read();
+        if (done) {
+          break;
+        }
+
+        await this.limiter.consume(value.length);
+        let written = 0;
+        while (written < value.length) {
+          written += await file.write(value.subarray(written));
+        }
+        size += value.length;
+      }
+    } finally {
+      await file.close();
+    }
+
+    const expected = Number(response.headers.get('content-length'));
+    if (Number.isFinite(expected) && expected > 0 && size - (append ? offset : 0) < expected) {
+      throw new Error(`Incomplete download: ${size} bytes`);
+    }
+
+    return { size, suggestedName, contentType };
+  }
+}
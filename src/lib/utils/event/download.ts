@@ -0,0 +1,245 @@
+/**
+ * BMS 活动作品下载
+ *
+ * 从活动作品页面提取下载链接，批量下载为 `<编号> <文件名>` 形式的压缩包，
+ * 可直接交给按编号解压的流程处理
+ */
+
+import { exists, mkdir, readDir, remove, rename } from '@tauri-apps/plugin-fs';
+import { BMSEvent } from '../../types/enums';
+import { Downloader, PARTIAL_DOWNLOAD_SUFFIX } from '../download';
+import type { DownloadResult } from '../download';
+import { UnsupportedLinkError } from '../resolver';
+import { getBMSEventWorkUrl } from './jumpToWorkInfo';
+import { extractEventWorkInfo, saveEventWorkInfo } from './scrape';
+import type { EventWorkInfo } from './scrape';
+
+/**
+ * 下载链接中常见的文件托管站点
+ */
+const DOWNLOAD_HOST_PATTERNS = [
+  /drive\.google\.com/,
+  /docs\.google\.com/,
+  /onedrive\.live\.com/,
+  /1drv\.ms/,
+  /sharepoint\.com/,
+  /dropbox\.com/,
+  /mega\.(nz|co\.nz)/,
+  /mediafire\.com/,
+  /github\.com\/.+\/releases/,
+  /axfc\.net/,
+  /uploader\.jp/,
+];
+
+/**
+ * 直链压缩包的扩展名
+ */
+const ARCHIVE_URL_PATTERN = /\.(zip|rar|7z|lzh)(\?|#|$)/i;
+
+/**
+ * 作品下载失败信息
+ */
+export interface EventDownloadFailure {
+  num: number;
+  reason: string;
+}
+
+/**
+ * 活动批量下载结果
+ */
+export interface EventDownloadSummary {
+  /** 下载完成的文件 */
+  downloaded: string[];
+  /** 已存在而跳过的编号 */
+  skipped: number[];
+  failed: EventDownloadFailure[];
+}
+
+/**
+ * 从作品页面 HTML 中提取可能的下载链接（按可能性排序）
+ *
+ * @param html - 作品页面 HTML
+ * @param pageUrl - 作品页面 URL（用于解析相对链接和排除站内链接）
+ */
+export function extractDownloadLinks(html: string, pageUrl: string): string[] {
+  const pageHost = new URL(pageUrl).host;
+  const scored: { url: string; score: number }[] = [];
+
+  const anchorPattern = /<a\s[^>]*href\s*=\s*["']([^"']+)["'][^>]*>([\s\S]*?)<\/a>/gi;
+
+  for (const match of html.matchAll(anchorPattern)) {
+    let url: URL;
+    try {
+      url = new URL(match[1].replace(/&amp;/g, '&'), pageUrl);
+    } catch {
+      continue;
+    }
+
+    if (!url.protocol.startsWith('http')) {
+      continue;
+    }
+
+    const href = url.toString();
+    const text = match[2].replace(/<[^>]+>/g, '').trim();
+    let score = 0;
+
+    if (ARCHIVE_URL_PATTERN.test(url.pathname)) {
+      score += 3;
+    }
+    if (DOWNLOAD_HOST_PATTERNS.some((pattern) => pattern.test(href))) {
+      score += 2;
+    }
+    if (/download|ダウンロード|\bDL\b|本体/i.test(text)) {
+      score += 2;
+    }
+    // 站内链接只有直链压缩包时才考虑
+    if (url.host === pageHost && score < 3) {
+      continue;
+    }
+
+    if (score > 0 && !scored.some((item) => item.url === href)) {
+      scored.push({ url: href, score });
+    }
+  }
+
+  return scored.sort((a, b) => b.score - a.score).map((item) => item.url);
+}
+
+/**
+ * 查找目录中已下载的编号文件（`<编号> <文件名>`，不含未完成的部分文件）
+ */
+async function findDownloadedNums(outputDir: string): Promise<Set<number>> {
+  const nums = new Set<number>();
+
+  for (const entry of await readDir(outputDir)) {
+    if (entry.isDirectory || !entry.name || entry.name.endsWith(PARTIAL_DOWNLOAD_SUFFIX)) {
+      continue;
+    }
+
+    const match = entry.name.match(/^(\d+) /);
+    if (match) {
+      nums.add(parseInt(match[1], 10));
+    }
+  }
+
+  return nums;
+}
+
+/**
+ * 批量下载活动作品
+ *
+ * @command
+ * @category BMSEvent
+ * @dangerous false
+ * @name 批量下载活动作品
+ * @description 从作品页面提取下载链接并下载（支持断点续传、失败重试、并发数和带宽限制）
+ * @frontend true
+ *
+ * @param {BMSEvent} event - BMS 活动类型
+ * @param {number} startNum - 起始编号
+ * @param {number} endNum - 结束编号
+ * @param {string} outputDir - 下载目录路径
+ * @param {number} maxConcurrent - 最大同时下载数
+ * @param {number} bandwidthLimitKb - 带宽上限（KB/s，0 表示不限制）
+ *
+ * @returns {Promise<EventDownloadSummary>} 下载结果
+ */
+export async function downloadWorks(
+  event: BMSEvent,
+  startNum: number,
+  endNum: number,
+  outputDir: string,
+  maxConcurrent: number,
+  bandwidthLimitKb: number
+): Promise<EventDownloadSummary> {
+  if (!(await exists(outputDir))) {
+    await mkdir(outputDir, { recursive: true });
+  }
+
+  const downloader = new Downloader({
+    maxConcurrent,
+    bandwidthLimit: Math.max(0, bandwidthLimitKb) * 1024,
+  });
+  const downloadedNums = await findDownloadedNums(outputDir);
+  const summary: EventDownloadSummary = { downloaded: [], skipped: [], failed: [] };
+  // 顺便保存作品页面中的信息，谱面缺少标题或艺术家时用于命名
+  const workInfos = new Map<number, EventWorkInfo>();
+
+  const downloadWork = async (num: number): Promise<void> => {
+    const pageUrl = getBMSEventWorkUrl(event, num);
+    const html = await downloader.fetchText(pageUrl);
+    const info = extractEventWorkInfo(html);
+    if (info.title || info.artist) {
+      workInfos.set(num, info);
+    }
+    const links = extractDownloadLinks(html, pageUrl);
+    if (links.length === 0) {
+      throw new Error('No download link found on work page');
+    }
+
+    const tempPath = `${outputDir}/${num}`;
+    let result: DownloadResult | null = null;
+    let lastError: unknown = null;
+
+    // 依次尝试各个链接，跳过不支持自动下载的站点
+    for (const link of links) {
+      try {
+        result = await downloader.download(link, tempPath);
+      } catch (error) {
+        if (!(error instanceof UnsupportedLinkError)) {
+          throw error;
+        }
+        console.warn(` - #${num}: ${error.message}`);
+        lastError = error;
+        continue;
+      }
+
+      // 返回网页说明链接不是直链，需要手动下载
+      if (result.contentType?.includes('text/html')) {
+        await remove(tempPath);
+        lastError = new UnsupportedLinkError(link, 'link returned a web page');
+        result = null;
+        continue;
+      }
+      break;
+    }
+
+    if (!result) {
+      throw lastError ?? new Error('No downloadable link found');
+    }
+
+    const fileName = (result.suggestedName ?? `${num}.zip`).replace(/[\\/:*?"<>|]/g, '_');
+    const finalPath = `${outputDir}/${num} ${fileName}`;
+    await rename(tempPath, finalPath);
+    summary.downloaded.push(finalPath);
+    console.log(
+      `Downloaded #${num}: ${finalPath} (${result.size} bytes` +
+        `${result.resumed ? ', resumed' : ''})`
+    );
+  };
+
+  const tasks: Promise<void>[] = [];
+  for (let num = startNum; num <= endNum; num++) {
+    if (downloadedNums.has(num)) {
+      summary.skipped.push(num);
+      continue;
+    }
+
+    tasks.push(
+      downloadWork(num).catch((error) => {
+        console.error(` !_! Failed to download #${num}:`, error);
+        summary.failed.push({ num, reason: String(error) });
+      })
+    );
+  }
+
+  await Promise.all(tasks);
+  await downloader.drain();
+  await saveEventWorkInfo(event, workInfos);
+
+  console.log(
+    `Downloaded ${summary.downloaded.length}, skipped ${summary.skipped.length}, ` +
+      `failed ${summary.failed.length}`
+  );
+  return summary;
+}
@@ -3,7 +3,7 @@
  */
 
 import { exists, mkdir, readDir } from '@tauri-apps/plugin-fs';
-import { getDirBmsInfo } from '../bms/scanner';
+import { getDirBmsOutput } from '../bms/scanner';
 
 /**
  * 检查数字文件夹（1 到 max）哪些不存在
@@ -107,7 +107,7 @@ export async function generateWorkInfoTable(rootDir: string): Promise<WorkInfoRo
     }
 
     const workDir = `${rootDir}/${entry.name}`;
-    const bmsInfo = await getDirBmsInfo(workDir);
+    const bmsInfo = await getDirBmsOutput(workDir);
 
     workInfoList.push({
       num,
@@ -0,0 +1,216 @@
+/**
+ * 活动评价（感想）进度管理
+ *
+ * 记录每个作品的试听状态：未听 → 已打开 → 已听 / 跳过，
+ * 方便按顺序打开下一批作品并统计评价进度
+ */
+
+import { readDir } from '@tauri-apps/plugin-fs';
+import { openPath } from '@tauri-apps/plugin-opener';
+import { BMSEvent, ImpressionStatus } from '../../types/enums';
+import { getAppDataPath, readJsonFile, writeJsonFile } from '../storage';
+import { getBMSEventWorkUrl } from './jumpToWorkInfo';
+
+/**
+ * 单个作品的评价记录
+ */
+export interface ImpressionRecord {
+  status: ImpressionStatus;
+  /** 最后更新时间（ISO 字符串） */
+  updatedAt: string;
+}
+
+/**
+ * 活动评价进度（按作品编号索引）
+ */
+type ImpressionProgressData = Record<string, ImpressionRecord>;
+
+/**
+ * 活动评价进度统计
+ */
+export interface ImpressionProgressSummary {
+  /** 本地作品总数 */
+  total: number;
+  listened: number;
+  skipped: number;
+  /** 已打开但尚未标记 */
+  opened: number;
+  /** 尚未打开 */
+  remaining: number;
+  /** 已听和跳过占总数的百分比 */
+  coverage: number;
+}
+
+async function getProgressPath(event: BMSEvent): Promise<string> {
+  return await getAppDataPath(`event-progress/${event}.json`);
+}
+
+async function loadProgress(event: BMSEvent): Promise<ImpressionProgressData> {
+  return await readJsonFile<ImpressionProgressData>(await getProgressPath(event), {});
+}
+
+async function saveProgress(event: BMSEvent, data: ImpressionProgressData): Promise<void> {
+  await writeJsonFile(await getProgressPath(event), data);
+}
+
+/**
+ * 获取根目录中按编号命名的作品目录（`<编号>` 或 `<编号> <名称>`），按编号排序
+ */
+async function getNumberedWorkDirs(rootDir: string): Promise<Map<number, string>> {
+  const works = new Map<number, string>();
+
+  for (const entry of await readDir(rootDir)) {
+    if (!entry.isDirectory || !entry.name) {
+      continue;
+    }
+
+    const match = entry.name.match(/^(\d+)(\s|$)/);
+    if (match) {
+      works.set(parseInt(match[1], 10), `${rootDir}/${entry.name}`);
+    }
+  }
+
+  return new Map([...works.entries()].sort((a, b) => a[0] - b[0]));
+}
+
+/**
+ * 打开下一批未听的作品
+ *
+ * @command
+ * @category BMSEvent
+ * @dangerous false
+ * @name 打开下一批作品
+ * @description 按编号打开接下来几个尚未试听的作品的页面和文件夹，并标记为已打开
+ * @frontend true
+ *
+ * @param {BMSEvent} event - BMS 活动类型
+ * @param {string} rootDir - 活动作品根目录路径
+ * @param {number} count - 打开的作品数量
+ *
+ * @returns {Promise<number[]>} 打开的作品编号
+ */
+export async function openNextWorks(
+  event: BMSEvent,
+  rootDir: string,
+  count: number
+): Promise<number[]> {
+  const progress = await loadProgress(event);
+  const works = await getNumberedWorkDirs(rootDir);
+  const opened: number[] = [];
+
+  for (const [num, workDir] of works) {
+    if (opened.length >= count) {
+      break;
+    }
+    if (progress[num]) {
+      continue;
+    }
+
+    globalThis.open(getBMSEventWorkUrl(event, num), '_blank');
+    await openPath(workDir).catch((error) => {
+      console.warn(`Failed to open folder: ${workDir}`, error);
+    });
+
+    progress[num] = { status: ImpressionStatus.Opened, updatedAt: new Date().toISOString() };
+    opened.push(num);
+  }
+
+  await saveProgress(event, progress);
+
+  if (opened.length === 0) {
+    console.log('All works have been opened');
+  } else {
+    console.log(`Opened works: ${opened.join(', ')}`);
+  }
+  return opened;
+}
+
+/**
+ * 标记作品的试听状态
+ *
+ * @command
+ * @category BMSEvent
+ * @dangerous false
+ * @name 标记作品
+ * @description 将作品标记为已听、跳过，或重置为未听
+ * @frontend true
+ *
+ * @param {BMSEvent} event - BMS 活动类型
+ * @param {number} workNum - 作品编号
+ * @param {ImpressionStatus} status - 试听状态
+ *
+ * @returns {Promise<void>}
+ */
+export async function markWork(
+  event: BMSEvent,
+  workNum: number,
+  status: ImpressionStatus
+): Promise<void> {
+  const progress = await loadProgress(event);
+
+  if (status === ImpressionStatus.Unlistened) {
+    delete progress[workNum];
+  } else {
+    progress[workNum] = { status, updatedAt: new Date().toISOString() };
+  }
+
+  await saveProgress(event, progress);
+  console.log(`Marked #${workNum} as ${status}`);
+}
+
+/**
+ * 查看评价进度
+ *
+ * @command
+ * @category BMSEvent
+ * @dangerous false
+ * @name 查看评价进度
+ * @description 统计本地作品中已听、跳过、已打开和剩余的数量
+ * @frontend true
+ *
+ * @param {BMSEvent} event - BMS 活动类型
+ * @param {string} rootDir - 活动作品根目录路径
+ *
+ * @returns {Promise<ImpressionProgressSummary>} 进度统计
+ */
+export async function impressionProgress(
+  event: BMSEvent,
+  rootDir: string
+): Promise<ImpressionProgressSummary> {
+  const progress = await loadProgress(event);
+  const works = await getNumberedWorkDirs(rootDir);
+  const summary: ImpressionProgressSummary = {
+    total: works.size,
+    listened: 0,
+    skipped: 0,
+    opened: 0,
+    remaining: 0,
+    coverage: 0,
+  };
+
+  for (const num of works.keys()) {
+    switch (progress[num]?.status) {
+      case ImpressionStatus.Listened:
+        summary.listened++;
+        break;
+      case ImpressionStatus.Skipped:
+        summary.skipped++;
+        break;
+      case ImpressionStatus.Opened:
+        summary.opened++;
+        break;
+      default:
+        summary.remaining++;
+    }
+  }
+
+  if (summary.total > 0) {
+    summary.coverage = Math.round(((summary.listened + summary.skipped) / summary.total) * 100);
+  }
+
+  console.log(
+    `Listened ${summary.listened}, skipped ${summary.skipped}, opened ${summary.opened}, ` +
+      `remaining ${summary.remaining} / ${summary.total} (${summary.coverage}%)`
+  );
+  return summary;
+}
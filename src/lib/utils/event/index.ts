@@ -4,4 +4,8 @@
  */
 
 export * from './folder';
+export * from './download';
+export * from './impression';
 export * from './table';
+export * from './scrape';
+export * from './package';
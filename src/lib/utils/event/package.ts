@@ -0,0 +1,227 @@
+/**
+ * 活动投稿作品打包
+ *
+ * 主办方收到投稿后通常要逐个检查谱面、整理文件名、补上预览音频和信息文件，再打包成统一命名的 ZIP 发布。
+ * 这里对编号作品目录一次完成这些步骤
+ */
+
+import { readDir, stat, writeTextFile } from '@tauri-apps/plugin-fs';
+import { bmsQuickInfo } from '../bms/quickInfo';
+import { AUDIO_FILE_EXTS, getWorkDirChartFiles } from '../bms/scanner';
+import { extractWorkName } from '../bms/work';
+import { compressionPolicyFromPreset, CompressionPreset } from '../fs/compression';
+import { getValidFsName, getWorkFolderName } from '../fs/name';
+import { getFileExtension, getFileStem } from '../fs/path';
+import { ZipWriter } from '../fs/zip';
+import { MediaProbe } from '../media/probe';
+import { ProcessRunner } from '../media/processRunner';
+import { lint } from '../work/lint';
+import type { LintIssue } from '../work/lint';
+import { normalizeChartNames } from '../work/normalizeChartNames';
+import type { ChartRename } from '../work/normalizeChartNames';
+import { ChartNameScheme } from '../../types/enums';
+
+/**
+ * 自动生成的预览音频文件名（beatoraja 等播放器会自动识别 preview 开头的音频）
+ */
+const PREVIEW_FILE_NAME = 'preview_auto_generator.ogg';
+
+/**
+ * 预览音频长度（秒）
+ */
+const PREVIEW_DURATION = 15;
+
+/**
+ * 作品信息文件名
+ */
+const INFO_FILE_NAME = 'info.txt';
+
+/**
+ * 作品打包结果
+ */
+export interface WorkPackageResult {
+  workDir: string;
+  /** 生成的 ZIP 文件（模拟运行或检查未通过时为空） */
+  zipPath?: string;
+  issues: LintIssue[];
+  renamedCharts: ChartRename[];
+  /** 生成的预览音频（已有预览或未生成时为空） */
+  preview?: string;
+}
+
+/**
+ * 按编号找到作品目录（"12"、"12 Title"、"012. Title" 等）
+ */
+async function findNumberedWorkDir(rootDir: string, num: number): Promise<string> {
+  const pattern = new RegExp(`^0*${num}(?:[\\s._-]|$)`);
+  const matches = (await readDir(rootDir))
+    .filter((entry) => entry.isDirectory && entry.name && pattern.test(entry.name))
+    .map((entry) => entry.name);
+  if (matches.length === 0) {
+    throw new Error(`没有找到编号为 ${num} 的作品目录：${rootDir}`);
+  }
+  if (matches.length > 1) {
+    throw new Error(`编号 ${num} 对应多个作品目录：${matches.join(', ')}`);
+  }
+  return `${rootDir}/${matches[0]}`;
+}
+
+/**
+ * 截取作品中最大的音频（通常是 BGM 或整曲）中段作为预览音频
+ *
+ * @returns 生成的文件名，已有预览音频或没有可用音频时为 null
+ */
+async function generatePreview(workDir: string, dryRun: boolean): Promise<string | null> {
+  const audios: { name: string; size: number }[] = [];
+  for (const entry of await readDir(workDir)) {
+    const ext = getFileExtension(entry.name);
+    if (!entry.isFile || !(AUDIO_FILE_EXTS as readonly string[]).includes(ext)) {
+      continue;
+    }
+    if (getFileStem(entry.name).toLowerCase().startsWith('preview')) {
+      console.log(`Preview already exists: ${entry.name}`);
+      return null;
+    }
+    audios.push({ name: entry.name, size: (await stat(`${workDir}/${entry.name}`)).size });
+  }
+
+  const source = audios.sort((a, b) => b.size - a.size)[0];
+  if (!source) {
+    console.warn(` !_! No audio to generate preview from: ${workDir}`);
+    return null;
+  }
+  if (dryRun) {
+    console.log(`[dry-run] Would generate ${PREVIEW_FILE_NAME} from ${source.name}`);
+    return PREVIEW_FILE_NAME;
+  }
+  if (!(await ProcessRunner.checkExecutable('ffmpeg'))) {
+    console.warn(' !_! ffmpeg not found, skipping preview generation');
+    return null;
+  }
+
+  const duration = (await MediaProbe.getMediaDuration(`${workDir}/${source.name}`)) ?? 0;
+  const start = Math.max(0, Math.min(duration * 0.3, duration - PREVIEW_DURATION));
+  const fadeOut = Math.max(0, Math.min(duration, PREVIEW_DURATION) - 1);
+  const result = await ProcessRunner.exec('ffmpeg', [
+    '-y',
+    '-ss',
+    start.toFixed(2),
+    '-t',
+    String(PREVIEW_DURATION),
+    '-i',
+    `${workDir}/${source.name}`,
+    '-af',
+    `afade=t=in:d=1,afade=t=out:st=${fadeOut}:d=1`,
+    '-c:a',
+    'libvorbis',
+    '-q:a',
+    '5',
+    `${workDir}/${PREVIEW_FILE_NAME}`,
+  ]);
+  if (!result.success) {
+    console.warn(` !_! Failed to generate preview: ${result.stderr}`);
+    return null;
+  }
+  console.log(`Generated ${PREVIEW_FILE_NAME} from ${source.name}`);
+  return PREVIEW_FILE_NAME;
+}
+
+/**
+ * 读取作品信息并生成信息文件内容
+ */
+async function buildInfo(
+  workDir: string,
+  eventName: string,
+  num: number
+): Promise<{ title: string; artist: string; text: string }> {
+  const charts = (await getWorkDirChartFiles(workDir)).sort();
+  const infos = await Promise.all(charts.map((chart) => bmsQuickInfo(`${workDir}/${chart}`)));
+  const titles = infos.map((info) => info?.title).filter((title): title is string => !!title);
+  const first = infos.find((info) => info) ?? null;
+  const title = titles.length > 0 ? (extractWorkName(titles) || titles[0]).trim() : '';
+  const artist = first?.artist?.trim() ?? '';
+
+  const lines = [
+    `Event: ${eventName}`,
+    `No.: ${num}`,
+    `Title: ${title}`,
+    `Artist: ${artist}`,
+    `Genre: ${first?.genre ?? ''}`,
+    `BPM: ${first?.bpm ?? ''}`,
+  ];
+  if (first?.url) {
+    lines.push(`URL: ${first.url}`);
+  }
+  lines.push('', 'Charts:');
+  charts.forEach((chart, i) => {
+    const level = infos[i]?.playLevel ? ` ☆${infos[i]?.playLevel}` : '';
+    lines.push(`  ${chart}: ${infos[i]?.title ?? ''}${level}`);
+  });
+  return { title, artist, text: lines.join('\r\n') + '\r\n' };
+}
+
+/**
+ * 打包活动投稿作品
+ *
+ * @command
+ * @category BMSEvent
+ * @dangerous true
+ * @name 打包投稿作品
+ * @description 检查编号作品目录（有错误时中止），规范谱面文件名，生成预览音频和 info.txt，然后打包为 "<活动名>_<编号>_<标题>.zip"（包内文件夹为 "<编号>. <标题> [<艺术家>]"）用于发布
+ * @frontend true
+ *
+ * @param {string} rootDir - 活动根目录路径
+ * @param {number} num - 作品编号
+ * @param {string} outputDir - ZIP 输出目录
+ * @param {string} eventName - 活动名（用于 ZIP 文件名和信息文件）
+ * @param {ChartNameScheme} chartNameScheme - 谱面文件名的规范方式
+ * @param {boolean} withPreview - 没有预览音频时生成
+ * @param {boolean} dryRun - 模拟运行（只检查并列出将要进行的操作）
+ *
+ * @returns {Promise<WorkPackageResult>} 检查结果和生成的 ZIP 文件
+ */
+export async function packageWork(
+  rootDir: string,
+  num: number,
+  outputDir: string,
+  eventName: string,
+  chartNameScheme: ChartNameScheme,
+  withPreview: boolean = true,
+  dryRun: boolean = false
+): Promise<WorkPackageResult> {
+  const workDir = await findNumberedWorkDir(rootDir, num);
+  const result: WorkPackageResult = { workDir, issues: [], renamedCharts: [] };
+
+  result.issues = await lint(workDir);
+  const errors = result.issues.filter((issue) => issue.level === 'error');
+  if (errors.length > 0) {
+    console.error(` !_! ${errors.length} errors found, not packaging: ${workDir}`);
+    return result;
+  }
+
+  result.renamedCharts = await normalizeChartNames(workDir, chartNameScheme, dryRun);
+  if (withPreview) {
+    result.preview = (await generatePreview(workDir, dryRun)) ?? undefined;
+  }
+
+  const info = await buildInfo(workDir, eventName, num);
+  const paddedNum = String(num).padStart(3, '0');
+  const zipPath = `${outputDir}/${getValidFsName(`${eventName}_${paddedNum}_${info.title}`)}.zip`;
+  const folderName = getWorkFolderName(paddedNum, info.title, info.artist);
+  if (dryRun) {
+    console.log(`[dry-run] Would write ${INFO_FILE_NAME} and create ${zipPath} (${folderName})`);
+    return result;
+  }
+
+  await writeTextFile(`${workDir}/${INFO_FILE_NAME}`, info.text);
+  const writer = await ZipWriter.create(zipPath);
+  await writer.addDirectoryTree(
+    workDir,
+    folderName,
+    compressionPolicyFromPreset(CompressionPreset.Auto)
+  );
+  await writer.close();
+  result.zipPath = zipPath;
+  console.log(`Packaged ${workDir} -> ${zipPath}`);
+  return result;
+}
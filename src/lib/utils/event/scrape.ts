@@ -0,0 +1,150 @@
+/**
+ * 活动作品信息抓取
+ *
+ * 从活动作品页面读取标题、艺术家和流派并按编号保存，
+ * 谱面缺少标题或艺术家时可按作品编号取用（见 work/fallback.ts）
+ */
+
+import { BMSEvent } from '../../types/enums';
+import { Downloader } from '../download';
+import { ConcurrencyPool } from '../media/concurrency';
+import { getAppDataPath, readJsonFile, writeJsonFile } from '../storage';
+import { getBMSEventWorkUrl } from './jumpToWorkInfo';
+
+/**
+ * 作品页面中读取到的信息
+ */
+export interface EventWorkInfo {
+  title?: string;
+  artist?: string;
+  genre?: string;
+}
+
+/**
+ * 活动作品信息（按作品编号索引）
+ */
+type EventWorkInfoData = Record<string, EventWorkInfo>;
+
+async function getWorkInfoPath(event: BMSEvent): Promise<string> {
+  return await getAppDataPath(`event-works/${event}.json`);
+}
+
+/**
+ * 读取已保存的活动作品信息
+ */
+export async function loadEventWorkInfo(event: BMSEvent): Promise<EventWorkInfoData> {
+  return await readJsonFile<EventWorkInfoData>(await getWorkInfoPath(event), {});
+}
+
+/**
+ * 合并保存活动作品信息
+ */
+export async function saveEventWorkInfo(
+  event: BMSEvent,
+  infos: Map<number, EventWorkInfo>
+): Promise<void> {
+  if (infos.size === 0) {
+    return;
+  }
+  const data = await loadEventWorkInfo(event);
+  for (const [num, info] of infos) {
+    data[num] = { ...data[num], ...info };
+  }
+  await writeJsonFile(await getWorkInfoPath(event), data);
+}
+
+function decodeHtmlText(text: string): string {
+  return text
+    .replace(/<[^>]+>/g, '')
+    .replace(/&lt;/g, '<')
+    .replace(/&gt;/g, '>')
+    .replace(/&quot;/g, '"')
+    .replace(/&#0?39;/g, "'")
+    .replace(/&nbsp;/g, ' ')
+    .replace(/&amp;/g, '&')
+    .trim();
+}
+
+/**
+ * 从作品页面 HTML 中读取作品信息
+ *
+ * 优先读取 "Title: ..."、"Artist: ..."、"Genre: ..." 形式的标注；
+ * 没有标注时使用页面标题，"标题 / 艺术家" 形式时按最后一个 " / " 拆分
+ */
+export function extractEventWorkInfo(html: string): EventWorkInfo {
+  const info: EventWorkInfo = {};
+  const text = decodeHtmlText(html.replace(/<br\s*\/?>|<\/(?:p|div|li|tr|h\d)>/gi, '\n'));
+
+  for (const field of ['title', 'artist', 'genre'] as const) {
+    const match = text.match(new RegExp(`^\\s*${field}\\s*[:：]\\s*(.+)$`, 'im'));
+    if (match && match[1].trim()) {
+      info[field] = match[1].trim();
+    }
+  }
+
+  if (!info.title) {
+    const pageTitle =
+      html.match(/<meta\s+property=["']og:title["']\s+content=["']([^"']+)["']/i)?.[1] ??
+      html.match(/<title[^>]*>([\s\S]*?)<\/title>/i)?.[1];
+    const decoded = pageTitle ? decodeHtmlText(pageTitle) : '';
+    const separator = decoded.lastIndexOf(' / ');
+    if (separator > 0) {
+      info.title = decoded.slice(0, separator).trim();
+      info.artist ??= decoded.slice(separator + 3).trim();
+    } else if (decoded) {
+      info.title = decoded;
+    }
+  }
+
+  return info;
+}
+
+/**
+ * 抓取活动作品信息
+ *
+ * @command
+ * @category BMSEvent
+ * @dangerous false
+ * @name 抓取活动作品信息
+ * @description 读取作品页面中的标题、艺术家和流派并按编号保存，谱面缺少标题或艺术家时用于命名
+ * @frontend true
+ *
+ * @param {BMSEvent} event - BMS 活动类型
+ * @param {number} startNum - 起始编号
+ * @param {number} endNum - 结束编号
+ *
+ * @returns {Promise<number>} 读取到信息的作品数
+ */
+export async function scrapeWorkInfo(
+  event: BMSEvent,
+  startNum: number,
+  endNum: number
+): Promise<number> {
+  const downloader = new Downloader();
+  const pool = new ConcurrencyPool<void>(4);
+  const infos = new Map<number, EventWorkInfo>();
+
+  const tasks: Promise<void>[] = [];
+  for (let num = startNum; num <= endNum; num++) {
+    tasks.push(
+      pool.add(async () => {
+        try {
+          const info = extractEventWorkInfo(
+            await downloader.fetchText(getBMSEventWorkUrl(event, num))
+          );
+          if (info.title || info.artist) {
+            infos.set(num, info);
+          }
+        } catch (error) {
+          console.error(` !_! Failed to fetch work page #${num}:`, error);
+        }
+      })
+    );
+  }
+
+  await Promise.all(tasks);
+  await saveEventWorkInfo(event, infos);
+
+  console.log(`Scraped work info for ${infos.size} of ${endNum - startNum + 1} works`);
+  return infos.size;
+}
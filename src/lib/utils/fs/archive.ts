@@ -1,10 +1,15 @@
 /**
- * 压缩包解压工具
- * 使用外部工具（7z、unzip、unrar）解压各种格式的压缩包
+ * 压缩包工具
+ * 使用外部工具（7z、unzip、unrar）解压各种格式的压缩包，并使用 7z 创建压缩包
  */
 
 import { Command } from '@tauri-apps/plugin-shell';
-import { getFileExtension } from './path';
+import { getFileExtension, getSafeRelativePath } from './path';
+import { compressionPolicyFromPreset, CompressionPreset } from './compression';
+import { IgnoreList } from './ignore';
+import { ZipWriter } from './zip';
+import { salvageZip } from './zipSalvage';
+import { formatSize } from './sizeTally';
 
 /**
  * 解压结果
@@ -15,10 +20,119 @@ export interface ExtractResult {
   stderr?: string;
 }
 
+/**
+ * 压缩包中的条目
+ */
+export interface ArchiveEntry {
+  path: string;
+  /** 解压后大小（字节） */
+  size: number;
+  isDirectory: boolean;
+}
+
+/**
+ * 解压的安全限制
+ *
+ * 工具箱解压的是从网上下载的、不可信的压缩包，需要防范解压炸弹和路径穿越
+ */
+export interface ArchiveLimits {
+  /** 解压后总大小上限（字节） */
+  maxTotalSize: number;
+  /** 条目数上限 */
+  maxEntryCount: number;
+}
+
+/**
+ * 默认限制：作品中包含较大的 BGA 时也很少超过数 GB，条目数通常在数千以内
+ */
+export const DEFAULT_ARCHIVE_LIMITS: ArchiveLimits = {
+  maxTotalSize: 20 * 1024 * 1024 * 1024,
+  maxEntryCount: 20000,
+};
+
 /**
  * 压缩包解压器
  */
 export class ArchiveExtractor {
+  /**
+   * unzip 和 7z 都失败时是否从本地文件头抢救解压 ZIP
+   */
+  private static salvageEnabled = true;
+
+  static setSalvageEnabled(enabled: boolean): void {
+    this.salvageEnabled = enabled;
+  }
+
+  static isSalvageEnabled(): boolean {
+    return this.salvageEnabled;
+  }
+
+  private static limits: ArchiveLimits = { ...DEFAULT_ARCHIVE_LIMITS };
+
+  static setLimits(limits: Partial<ArchiveLimits>): void {
+    this.limits = { ...this.limits, ...limits };
+  }
+
+  static getLimits(): ArchiveLimits {
+    return { ...this.limits };
+  }
+
+  /**
+   * 列出压缩包中的条目（使用 7z l -slt，支持 zip、7z、rar 和 tar）
+   */
+  static async listEntries(file: string): Promise<ArchiveEntry[]> {
+    const result = await Command.create('7z', ['l', '-slt', file]).execute();
+    if (result.code !== 0) {
+      throw new Error(`Failed to list archive: ${result.stderr}`);
+    }
+
+    // 分隔线之前是压缩包本身的信息，之后每个空行分隔的块是一个条目
+    const body = result.stdout.split(/\r?\n-{10}\r?\n/)[1] ?? '';
+    const entries: ArchiveEntry[] = [];
+    for (const block of body.split(/\r?\n\s*\r?\n/)) {
+      const fields = new Map<string, string>();
+      for (const line of block.split(/\r?\n/)) {
+        const separator = line.indexOf(' = ');
+        if (separator > 0) {
+          fields.set(line.slice(0, separator), line.slice(separator + 3));
+        }
+      }
+      const path = fields.get('Path');
+      if (!path) {
+        continue;
+      }
+      const attributes = fields.get('Attributes') ?? '';
+      entries.push({
+        path,
+        size: Number(fields.get('Size')) || 0,
+        isDirectory: fields.get('Folder') === '+' || attributes.startsWith('D'),
+      });
+    }
+    return entries;
+  }
+
+  /**
+   * 检查条目是否超出限制或包含不安全的路径
+   *
+   * @returns 违反限制的说明，没有问题时为 null
+   */
+  static checkEntries(entries: ArchiveEntry[], limits: ArchiveLimits = this.limits): string | null {
+    const unsafe = entries.filter((entry) => getSafeRelativePath(entry.path) === null);
+    if (unsafe.length > 0) {
+      const names = unsafe.slice(0, 5).map((entry) => entry.path);
+      return `包含指向解压目录之外的路径：${names.join(', ')}`;
+    }
+    if (entries.length > limits.maxEntryCount) {
+      return `条目数 ${entries.length} 超过上限 ${limits.maxEntryCount}`;
+    }
+    const totalSize = entries.reduce((sum, entry) => sum + entry.size, 0);
+    if (totalSize > limits.maxTotalSize) {
+      const limit = formatSize(limits.maxTotalSize);
+      return `解压后大小 ${formatSize(totalSize)} 超过上限 ${limit}`;
+    }
+    return null;
+  }
+
   /**
    * 自动检测格式并解压
    */
@@ -46,6 +160,9 @@ export class ArchiveExtractor {
 
   /**
    * 解压 ZIP 文件（使用 unzip）
+   *
+   * 部分 unzip 构建不支持 ZIP64（超过 4GB 或 65535 个条目），失败时改用 7z；
+   * 7z 也失败时（通常是中央目录损坏）从本地文件头抢救解压，只要恢复了文件就视为成功
    */
   static async extractZip(file: string, dest: string): Promise<void> {
     try {
@@ -55,7 +172,19 @@ export class ArchiveExtractor {
         throw new Error(`Failed to extract ZIP: ${result.stderr}`);
       }
     } catch (error) {
-      throw new Error(`Failed to extract ZIP file: ${error}`);
+      console.warn(`unzip failed for ${file}, retrying with 7z:`, error);
+      try {
+        await this.extract7z(file, dest);
+      } catch {
+        if (!this.salvageEnabled) {
+          throw new Error(`Failed to extract ZIP file: ${error}`);
+        }
+        console.warn(` !_! 7z failed for ${file}, salvaging from local headers`);
+        const salvage = await salvageZip(file, dest);
+        if (salvage.recovered.length + salvage.damaged.length === 0) {
+          throw new Error(`Failed to extract ZIP file: ${error}`);
+        }
+      }
     }
   }
 
@@ -156,3 +285,72 @@ export class ArchiveExtractor {
     return tools;
   }
 }
+
+/**
+ * 压缩包创建器
+ */
+export class ArchiveCreator {
+  /**
+   * 将目录压缩为 7Z 文件（使用 7z）
+   *
+   * 压缩包内保留目录本身作为顶层文件夹，解压到父目录即可还原
+   *
+   * @param sourceDir - 要压缩的目录
+   * @param archivePath - 输出的压缩包路径
+   * @param level - 压缩等级（0-9）
+   */
+  static async create7z(sourceDir: string, archivePath: string, level: number = 5): Promise<void> {
+    try {
+      const result = await Command.create('7z', [
+        'a',
+        '-t7z',
+        `-mx=${level}`,
+        '-y',
+        // 排除系统垃圾文件
+        ...IgnoreList.getPatterns().map((pattern) => `-xr!${pattern}`),
+        archivePath,
+        sourceDir,
+      ]).execute();
+
+      if (result.code !== 0) {
+        throw new Error(`Failed to create 7Z: ${result.stderr}`);
+      }
+    } catch (error) {
+      throw new Error(`Failed to create 7Z file: ${error}`);
+    }
+  }
+
+  /**
+   * 将目录压缩为 ZIP 文件（内置流式写入，不依赖外部工具）
+   *
+   * 文件逐块写出，超过 4GB 时自动使用 ZIP64；默认只压缩谱面和文本等文件，已压缩的媒体直接存储
+   *
+   * @param sourceDir - 要压缩的目录
+   * @param archivePath - 输出的压缩包路径
+   * @param preset - 压缩策略预设
+   */
+  static async createZip(
+    sourceDir: string,
+    archivePath: string,
+    preset: CompressionPreset = CompressionPreset.Auto
+  ): Promise<void> {
+    const dirName = sourceDir.replace(/[\\/]+$/, '').split(/[\\/]/).pop() ?? 'archive';
+    const writer = await ZipWriter.create(archivePath);
+    await writer.addDirectoryTree(sourceDir, dirName, compressionPolicyFromPreset(preset));
+    await writer.close();
+  }
+
+  /**
+   * 校验压缩包完整性（使用 7z）
+   *
+   * @returns 校验是否通过
+   */
+  static async test(archivePath: string): Promise<boolean> {
+    try {
+      const result = await Command.create('7z', ['t', archivePath]).execute();
+      return result.code === 0;
+    } catch {
+      return false;
+    }
+  }
+}
@@ -0,0 +1,41 @@
+/**
+ * BLAKE3 测试
+ *
+ * 官方测试向量，以及跨块、跨分块（1024 字节）的增量输入与一次性输入的结果一致
+ */
+
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+import { blake3, Blake3Hasher } from './blake3';
+import { bytesToHex } from './hash';
+
+test('BLAKE3 matches the official test vectors', () => {
+  assert.equal(
+    bytesToHex(blake3(new Uint8Array(0))),
+    'af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262'
+  );
+  assert.equal(
+    bytesToHex(blake3(new TextEncoder().encode('abc'))),
+    '6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85'
+  );
+  // 官方向量的输入为 i % 251
+  assert.equal(
+    bytesToHex(blake3(Uint8Array.from({ length: 1024 }, (_, i) => i % 251))),
+    '42214739f095a406f3fc83deb889744ac00df831c10daa55189b5d121c855af7'
+  );
+});
+
+test('incremental updates give the same digest as a single update', () => {
+  const data = Uint8Array.from({ length: 5000 }, (_, i) => i % 251);
+  for (const length of [0, 1, 63, 64, 65, 1023, 1024, 1025, 2048, 3073, 5000]) {
+    const input = data.subarray(0, length);
+    const expected = bytesToHex(blake3(input));
+    for (const chunkSize of [1, 64, 1000, 1024]) {
+      const hasher = new Blake3Hasher();
+      for (let i = 0; i < length; i += chunkSize) {
+        hasher.update(input.subarray(i, i + chunkSize));
+      }
+      assert.equal(bytesToHex(hasher.finalize()), expected, `${length}/${chunkSize}`);
+    }
+  }
+});
@@ -0,0 +1,410 @@
+/**
+ * BLAKE3 哈希实现
+ *
+ * 纯 TypeScript 实现（参考官方 reference implementation），支持增量计算，
+ * 用于大量文件内容比较时替代较慢的 SHA-512
+ */
+
+const OUT_LEN = 32;
+const BLOCK_LEN = 64;
+const CHUNK_LEN = 1024;
+
+const CHUNK_START = 1 << 0;
+const CHUNK_END = 1 << 1;
+const PARENT = 1 << 2;
+const ROOT = 1 << 3;
+
+const IV = new Uint32Array([
+  0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+]);
+
+const MSG_PERMUTATION = [2, 6, 3, 10, 7, 0, 4, 13, 1, 11, 12, 5, 9, 14, 15, 8];
+
+/**
+ * 预先计算 7 轮的消息字下标，避免每轮重新排列消息
+ */
+const MSG_SCHEDULE: number[][] = (() => {
+  const schedule: number[][] = [];
+  let current = Array.from({ length: 16 }, (_, i) => i);
+  for (let r = 0; r < 7; r++) {
+    schedule.push(current);
+    current = MSG_PERMUTATION.map((index) => current[index]);
+  }
+  return schedule;
+})();
+
+/**
+ * 压缩函数
+ *
+ * 状态使用局部变量保存以提高速度，结果写入 out（16 个字）
+ */
+function compress(
+  chainingValue: Uint32Array,
+  m: Uint32Array,
+  counter: number,
+  blockLen: number,
+  flags: number,
+  out: Uint32Array
+): Uint32Array {
+  let s0 = chainingValue[0];
+  let s1 = chainingValue[1];
+  let s2 = chainingValue[2];
+  let s3 = chainingValue[3];
+  let s4 = chainingValue[4];
+  let s5 = chainingValue[5];
+  let s6 = chainingValue[6];
+  let s7 = chainingValue[7];
+  let s8 = IV[0];
+  let s9 = IV[1];
+  let s10 = IV[2];
+  let s11 = IV[3];
+  let s12 = counter >>> 0;
+  let s13 = Math.floor(counter / 0x100000000) >>> 0;
+  let s14 = blockLen;
+  let s15 = flags;
+
+  for (let r = 0; r < 7; r++) {
+    const sc = MSG_SCHEDULE[r];
+
+    // 列：g(0, 4, 8, 12)
+    s0 = (s0 + s4 + m[sc[0]]) | 0;
+    s12 ^= s0;
+    s12 = (s12 >>> 16) | (s12 << 16);
+    s8 = (s8 + s12) | 0;
+    s4 ^= s8;
+    s4 = (s4 >>> 12) | (s4 << 20);
+    s0 = (s0 + s4 + m[sc[1]]) | 0;
+    s12 ^= s0;
+    s12 = (s12 >>> 8) | (s12 << 24);
+    s8 = (s8 + s12) | 0;
+    s4 ^= s8;
+    s4 = (s4 >>> 7) | (s4 << 25);
+
+    // g(1, 5, 9, 13)
+    s1 = (s1 + s5 + m[sc[2]]) | 0;
+    s13 ^= s1;
+    s13 = (s13 >>> 16) | (s13 << 16);
+    s9 = (s9 + s13) | 0;
+    s5 ^= s9;
+    s5 = (s5 >>> 12) | (s5 << 20);
+    s1 = (s1 + s5 + m[sc[3]]) | 0;
+    s13 ^= s1;
+    s13 = (s13 >>> 8) | (s13 << 24);
+    s9 = (s9 + s13) | 0;
+    s5 ^= s9;
+    s5 = (s5 >>> 7) | (s5 << 25);
+
+    // g(2, 6, 10, 14)
+    s2 = (s2 + s6 + m[sc[4]]) | 0;
+    s14 ^= s2;
+    s14 = (s14 >>> 16) | (s14 << 16);
+    s10 = (s10 + s14) | 0;
+    s6 ^= s10;
+    s6 = (s6 >>> 12) | (s6 << 20);
+    s2 = (s2 + s6 + m[sc[5]]) | 0;
+    s14 ^= s2;
+    s14 = (s14 >>> 8) | (s14 << 24);
+    s10 = (s10 + s14) | 0;
+    s6 ^= s10;
+    s6 = (s6 >>> 7) | (s6 << 25);
+
+    // g(3, 7, 11, 15)
+    s3 = (s3 + s7 + m[sc[6]]) | 0;
+    s15 ^= s3;
+    s15 = (s15 >>> 16) | (s15 << 16);
+    s11 = (s11 + s15) | 0;
+    s7 ^= s11;
+    s7 = (s7 >>> 12) | (s7 << 20);
+    s3 = (s3 + s7 + m[sc[7]]) | 0;
+    s15 ^= s3;
+    s15 = (s15 >>> 8) | (s15 << 24);
+    s11 = (s11 + s15) | 0;
+    s7 ^= s11;
+    s7 = (s7 >>> 7) | (s7 << 25);
+
+    // 对角线：g(0, 5, 10, 15)
+    s0 = (s0 + s5 + m[sc[8]]) | 0;
+    s15 ^= s0;
+    s15 = (s15 >>> 16) | (s15 << 16);
+    s10 = (s10 + s15) | 0;
+    s5 ^= s10;
+    s5 = (s5 >>> 12) | (s5 << 20);
+    s0 = (s0 + s5 + m[sc[9]]) | 0;
+    s15 ^= s0;
+    s15 = (s15 >>> 8) | (s15 << 24);
+    s10 = (s10 + s15) | 0;
+    s5 ^= s10;
+    s5 = (s5 >>> 7) | (s5 << 25);
+
+    // g(1, 6, 11, 12)
+    s1 = (s1 + s6 + m[sc[10]]) | 0;
+    s12 ^= s1;
+    s12 = (s12 >>> 16) | (s12 << 16);
+    s11 = (s11 + s12) | 0;
+    s6 ^= s11;
+    s6 = (s6 >>> 12) | (s6 << 20);
+    s1 = (s1 + s6 + m[sc[11]]) | 0;
+    s12 ^= s1;
+    s12 = (s12 >>> 8) | (s12 << 24);
+    s11 = (s11 + s12) | 0;
+    s6 ^= s11;
+    s6 = (s6 >>> 7) | (s6 << 25);
+
+    // g(2, 7, 8, 13)
+    s2 = (s2 + s7 + m[sc[12]]) | 0;
+    s13 ^= s2;
+    s13 = (s13 >>> 16) | (s13 << 16);
+    s8 = (s8 + s13) | 0;
+    s7 ^= s8;
+    s7 = (s7 >>> 12) | (s7 << 20);
+    s2 = (s2 + s7 + m[sc[13]]) | 0;
+    s13 ^= s2;
+    s13 = (s13 >>> 8) | (s13 << 24);
+    s8 = (s8 + s13) | 0;
+    s7 ^= s8;
+    s7 = (s7 >>> 7) | (s7 << 25);
+
+    // g(3, 4, 9, 14)
+    s3 = (s3 + s4 + m[sc[14]]) | 0;
+    s14 ^= s3;
+    s14 = (s14 >>> 16) | (s14 << 16);
+    s9 = (s9 + s14) | 0;
+    s4 ^= s9;
+    s4 = (s4 >>> 12) | (s4 << 20);
+    s3 = (s3 + s4 + m[sc[15]]) | 0;
+    s14 ^= s3;
+    s14 = (s14 >>> 8) | (s14 << 24);
+    s9 = (s9 + s14) | 0;
+    s4 ^= s9;
+    s4 = (s4 >>> 7) | (s4 << 25);
+  }
+
+  out[0] = s0 ^ s8;
+  out[1] = s1 ^ s9;
+  out[2] = s2 ^ s10;
+  out[3] = s3 ^ s11;
+  out[4] = s4 ^ s12;
+  out[5] = s5 ^ s13;
+  out[6] = s6 ^ s14;
+  out[7] = s7 ^ s15;
+  out[8] = s8 ^ chainingValue[0];
+  out[9] = s9 ^ chainingValue[1];
+  out[10] = s10 ^ chainingValue[2];
+  out[11] = s11 ^ chainingValue[3];
+  out[12] = s12 ^ chainingValue[4];
+  out[13] = s13 ^ chainingValue[5];
+  out[14] = s14 ^ chainingValue[6];
+  out[15] = s15 ^ chainingValue[7];
+  return out;
+}
+
+/**
+ * 压缩函数使用的临时缓冲区（单线程执行，可以复用）
+ */
+const scratchWords = new Uint32Array(16);
+const scratchOut = new Uint32Array(16);
+
+/**
+ * 从字节数组的指定位置读取 16 个小端序字
+ */
+function readBlockWords(bytes: Uint8Array, offset: number, out: Uint32Array): Uint32Array {
+  for (let i = 0; i < 16; i++) {
+    const p = offset + i * 4;
+    out[i] = bytes[p] | (bytes[p + 1] << 8) | (bytes[p + 2] << 16) | (bytes[p + 3] << 24);
+  }
+  return out;
+}
+
+/**
+ * 压缩函数的输入，可生成链值或根输出
+ */
+class Output {
+  constructor(
+    private readonly inputChainingValue: Uint32Array,
+    private readonly blockWords: Uint32Array,
+    private readonly counter: number,
+    private readonly blockLen: number,
+    private readonly flags: number
+  ) {}
+
+  chainingValue(): Uint32Array {
+    return compress(
+      this.inputChainingValue,
+      this.blockWords,
+      this.counter,
+      this.blockLen,
+      this.flags,
+      scratchOut
+    ).slice(0, 8);
+  }
+
+  rootOutputBytes(outLen: number): Uint8Array {
+    const out = new Uint8Array(outLen);
+    let outputBlockCounter = 0;
+    let offset = 0;
+
+    while (offset < outLen) {
+      const words = compress(
+        this.inputChainingValue,
+        this.blockWords,
+        outputBlockCounter,
+        this.blockLen,
+        this.flags | ROOT,
+        scratchOut
+      );
+      for (let i = 0; i < 16 && offset < outLen; i++) {
+        for (let j = 0; j < 4 && offset < outLen; j++) {
+          out[offset++] = (words[i] >>> (8 * j)) & 0xff;
+        }
+      }
+      outputBlockCounter++;
+    }
+
+    return out;
+  }
+}
+
+/**
+ * 单个 1024 字节分块的状态
+ */
+class ChunkState {
+  private readonly chainingValue: Uint32Array;
+  private readonly block = new Uint8Array(BLOCK_LEN);
+  private blockLen = 0;
+  private blocksCompressed = 0;
+
+  constructor(
+    key: Uint32Array,
+    readonly chunkCounter: number,
+    private readonly flags: number
+  ) {
+    this.chainingValue = key.slice();
+  }
+
+  get length(): number {
+    return BLOCK_LEN * this.blocksCompressed + this.blockLen;
+  }
+
+  private startFlag(): number {
+    return this.blocksCompressed === 0 ? CHUNK_START : 0;
+  }
+
+  private compressBlock(bytes: Uint8Array, offset: number): void {
+    const out = compress(
+      this.chainingValue,
+      readBlockWords(bytes, offset, scratchWords),
+      this.chunkCounter,
+      BLOCK_LEN,
+      this.flags | this.startFlag(),
+      scratchOut
+    );
+    this.chainingValue.set(out.subarray(0, 8));
+    this.blocksCompressed++;
+  }
+
+  update(input: Uint8Array): void {
+    let offset = 0;
+    while (offset < input.length) {
+      // 缓冲区已满且还有输入时才压缩，最后一个块留给 output()
+      if (this.blockLen === BLOCK_LEN) {
+        this.compressBlock(this.block, 0);
+        this.block.fill(0);
+        this.blockLen = 0;
+      }
+
+      // 快速路径：缓冲区为空时直接从输入压缩完整的块（保留最后一个块）
+      if (this.blockLen === 0) {
+        while (input.length - offset > BLOCK_LEN) {
+          this.compressBlock(input, offset);
+          offset += BLOCK_LEN;
+        }
+      }
+
+      const take = Math.min(BLOCK_LEN - this.blockLen, input.length - offset);
+      this.block.set(input.subarray(offset, offset + take), this.blockLen);
+      this.blockLen += take;
+      offset += take;
+    }
+  }
+
+  output(): Output {
+    return new Output(
+      this.chainingValue.slice(),
+      readBlockWords(this.block, 0, new Uint32Array(16)),
+      this.chunkCounter,
+      this.blockLen,
+      this.flags | this.startFlag() | CHUNK_END
+    );
+  }
+}
+
+function parentOutput(
+  leftChildCv: Uint32Array,
+  rightChildCv: Uint32Array,
+  key: Uint32Array,
+  flags: number
+): Output {
+  const blockWords = new Uint32Array(16);
+  blockWords.set(leftChildCv, 0);
+  blockWords.set(rightChildCv, 8);
+  return new Output(key, blockWords, 0, BLOCK_LEN, PARENT | flags);
+}
+
+/**
+ * BLAKE3 增量哈希计算器
+ */
+export class Blake3Hasher {
+  private chunkState: ChunkState;
+  private readonly key = IV;
+  private readonly cvStack: Uint32Array[] = [];
+  private readonly flags = 0;
+
+  constructor() {
+    this.chunkState = new ChunkState(this.key, 0, this.flags);
+  }
+
+  private addChunkChainingValue(newCv: Uint32Array, totalChunks: number): void {
+    let cv = newCv;
+    let chunks = totalChunks;
+    // 每个完整子树合并为父节点
+    while ((chunks & 1) === 0) {
+      cv = parentOutput(this.cvStack.pop()!, cv, this.key, this.flags).chainingValue();
+      chunks = Math.floor(chunks / 2);
+    }
+    this.cvStack.push(cv);
+  }
+
+  update(input: Uint8Array): void {
+    let offset = 0;
+    while (offset < input.length) {
+      // 当前分块已满且还有输入时才结束该分块
+      if (this.chunkState.length === CHUNK_LEN) {
+        const chunkCv = this.chunkState.output().chainingValue();
+        const totalChunks = this.chunkState.chunkCounter + 1;
+        this.addChunkChainingValue(chunkCv, totalChunks);
+        this.chunkState = new ChunkState(this.key, totalChunks, this.flags);
+      }
+
+      const take = Math.min(CHUNK_LEN - this.chunkState.length, input.length - offset);
+      this.chunkState.update(input.subarray(offset, offset + take));
+      offset += take;
+    }
+  }
+
+  finalize(outLen: number = OUT_LEN): Uint8Array {
+    let output = this.chunkState.output();
+    for (let i = this.cvStack.length - 1; i >= 0; i--) {
+      output = parentOutput(this.cvStack[i], output.chainingValue(), this.key, this.flags);
+    }
+    return output.rootOutputBytes(outLen);
+  }
+}
+
+/**
+ * 计算字节数据的 BLAKE3 哈希（32 字节）
+ */
+export function blake3(input: Uint8Array): Uint8Array {
+  const hasher = new Blake3Hasher();
+  hasher.update(input);
+  return hasher.finalize();
+}
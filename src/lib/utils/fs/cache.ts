@@ -0,0 +1,315 @@
+/**
+ * 缓存目录管理
+ *
+ * 统一分配解压等操作使用的临时缓存目录（默认位于应用数据目录下），
+ * 操作结束后释放，并定期清理失败残留的过期条目。
+ * 结果要移动到根目录中的操作（解压导入）默认在根目录下创建缓存，与根目录位于同一个卷上，
+ * 移动时可以直接重命名，不需要跨卷复制
+ */
+
+import { exists, mkdir, readDir, remove, stat } from '@tauri-apps/plugin-fs';
+import { getAppDataSubDir } from '../storage';
+import { getDirectoryPath, getFileName } from './path';
+
+/**
+ * 默认缓存目录（相对于应用数据目录）
+ */
+const DEFAULT_CACHE_DIR_NAME = 'cache';
+
+/**
+ * 目标目录下的缓存目录名
+ */
+const LOCAL_CACHE_DIR_NAME = 'CacheDir';
+
+/**
+ * 默认过期天数
+ */
+export const DEFAULT_CACHE_MAX_AGE_DAYS = 7;
+
+const DAY_MS = 24 * 60 * 60 * 1000;
+
+/**
+ * 缓存条目信息
+ */
+export interface CacheEntryInfo {
+  name: string;
+  path: string;
+  /** 大小（字节） */
+  size: number;
+  /** 最后修改时间（毫秒时间戳） */
+  modifiedAt: number;
+}
+
+/**
+ * 缓存占用信息
+ */
+export interface CacheUsage {
+  baseDir: string;
+  /** 总大小（字节） */
+  totalSize: number;
+  entries: CacheEntryInfo[];
+}
+
+/**
+ * 缓存清理结果
+ */
+export interface CacheCleanResult {
+  removed: CacheEntryInfo[];
+  /** 释放的空间（字节） */
+  freedSize: number;
+}
+
+/**
+ * 递归计算目录大小
+ */
+export async function getDirSize(dirPath: string): Promise<number> {
+  let total = 0;
+  const entries = await readDir(dirPath);
+
+  for (const entry of entries) {
+    if (!entry.name) {
+      continue;
+    }
+
+    const entryPath = `${dirPath}/${entry.name}`;
+    if (entry.isDirectory) {
+      total += await getDirSize(entryPath);
+    } else {
+      try {
+        total += (await stat(entryPath)).size;
+      } catch {
+        // 文件在遍历期间被删除
+      }
+    }
+  }
+
+  return total;
+}
+
+/**
+ * 缓存管理器
+ */
+export class CacheManager {
+  /** 自定义缓存位置，为 null 时使用默认位置 */
+  private static customBaseDir: string | null = null;
+
+  /** 过期天数 */
+  private static maxAgeDays = DEFAULT_CACHE_MAX_AGE_DAYS;
+
+  /** 本次会话是否已执行过自动清理 */
+  private static autoCleaned = false;
+
+  /**
+   * 设置缓存位置
+   *
+   * @param dir - 缓存目录，传 null 恢复默认位置
+   */
+  static setBaseDir(dir: string | null): void {
+    this.customBaseDir = dir ? dir.replace(/[\\/]+$/, '') : null;
+    this.autoCleaned = false;
+  }
+
+  /**
+   * 设置过期天数
+   */
+  static setMaxAgeDays(days: number): void {
+    this.maxAgeDays = days;
+  }
+
+  /**
+   * 获取缓存根目录（不存在时创建）
+   */
+  static async getBaseDir(): Promise<string> {
+    if (this.customBaseDir === null) {
+      return await getAppDataSubDir(DEFAULT_CACHE_DIR_NAME);
+    }
+
+    if (!(await exists(this.customBaseDir))) {
+      await mkdir(this.customBaseDir, { recursive: true });
+    }
+
+    return this.customBaseDir;
+  }
+
+  /**
+   * 获取与目标目录位于同一个卷上的缓存根目录
+   *
+   * 设置了自定义位置时使用自定义位置；否则使用目标目录下的 CacheDir，无法创建时使用默认位置
+   */
+  static async getBaseDirNear(targetDir: string): Promise<string> {
+    if (this.customBaseDir !== null) {
+      return await this.getBaseDir();
+    }
+
+    const localDir = `${targetDir.replace(/[\\/]+$/, '')}/${LOCAL_CACHE_DIR_NAME}`;
+    try {
+      await mkdir(localDir, { recursive: true });
+      return localDir;
+    } catch (error) {
+      console.warn(` !_! Failed to create cache dir ${localDir}, using the default:`, error);
+      return await this.getBaseDir();
+    }
+  }
+
+  /**
+   * 分配新的缓存条目
+   *
+   * 每个会话第一次分配时会自动清理过期条目
+   *
+   * @param prefix - 条目名前缀（用于区分用途）
+   * @param nearDir - 结果要移动到的目录，传入时缓存与该目录位于同一个卷上
+   * @returns 缓存条目目录
+   */
+  static async createEntry(prefix: string, nearDir?: string): Promise<string> {
+    if (!this.autoCleaned) {
+      this.autoCleaned = true;
+      await this.cleanStale(this.maxAgeDays, false).catch((error) => {
+        console.warn('Failed to clean stale cache entries:', error);
+      });
+    }
+
+    const baseDir = nearDir ? await this.getBaseDirNear(nearDir) : await this.getBaseDir();
+    const suffix = Math.random().toString(36).slice(2, 8);
+    const entryDir = `${baseDir}/${prefix}-${Date.now()}-${suffix}`;
+    await mkdir(entryDir, { recursive: true });
+
+    return entryDir;
+  }
+
+  /**
+   * 释放缓存条目（目标目录下的 CacheDir 变空时一并删除）
+   */
+  static async releaseEntry(entryDir: string): Promise<void> {
+    if (await exists(entryDir)) {
+      await remove(entryDir, { recursive: true });
+    }
+
+    const baseDir = getDirectoryPath(entryDir);
+    if (this.customBaseDir === null && getFileName(baseDir) === LOCAL_CACHE_DIR_NAME) {
+      if ((await readDir(baseDir)).length === 0) {
+        await remove(baseDir);
+      }
+    }
+  }
+
+  /**
+   * 在缓存条目中执行操作，结束后（包括失败时）自动释放
+   *
+   * @param nearDir - 结果要移动到的目录，传入时缓存与该目录位于同一个卷上
+   */
+  static async withEntry<T>(
+    prefix: string,
+    task: (entryDir: string) => Promise<T>,
+    nearDir?: string
+  ): Promise<T> {
+    const entryDir = await this.createEntry(prefix, nearDir);
+    try {
+      return await task(entryDir);
+    } finally {
+      await this.releaseEntry(entryDir).catch((error) => {
+        console.warn(`Failed to release cache entry: ${entryDir}`, error);
+      });
+    }
+  }
+
+  /**
+   * 获取缓存占用情况
+   */
+  static async getUsage(): Promise<CacheUsage> {
+    const baseDir = await this.getBaseDir();
+    const entries: CacheEntryInfo[] = [];
+
+    for (const entry of await readDir(baseDir)) {
+      if (!entry.name) {
+        continue;
+      }
+
+      const entryPath = `${baseDir}/${entry.name}`;
+      const meta = await stat(entryPath);
+      const size = entry.isDirectory ? await getDirSize(entryPath) : meta.size;
+
+      entries.push({
+        name: entry.name,
+        path: entryPath,
+        size,
+        modifiedAt: meta.mtime?.getTime() ?? 0,
+      });
+    }
+
+    return {
+      baseDir,
+      totalSize: entries.reduce((sum, entry) => sum + entry.size, 0),
+      entries,
+    };
+  }
+
+  /**
+   * 清理过期的缓存条目
+   *
+   * @param maxAgeDays - 过期天数，为 0 时清理全部条目
+   * @param dryRun - 模拟运行
+   */
+  static async cleanStale(maxAgeDays: number, dryRun: boolean): Promise<CacheCleanResult> {
+    const usage = await this.getUsage();
+    const deadline = Date.now() - maxAgeDays * DAY_MS;
+    const removed: CacheEntryInfo[] = [];
+
+    for (const entry of usage.entries) {
+      if (maxAgeDays > 0 && entry.modifiedAt >= deadline) {
+        continue;
+      }
+
+      if (dryRun) {
+        console.log(`[dry-run] Would remove cache entry: ${entry.path}`);
+      } else {
+        await remove(entry.path, { recursive: true });
+        console.log(`Removed cache entry: ${entry.path}`);
+      }
+      removed.push(entry);
+    }
+
+    return {
+      removed,
+      freedSize: removed.reduce((sum, entry) => sum + entry.size, 0),
+    };
+  }
+}
+
+/**
+ * 清理缓存
+ *
+ * @command
+ * @category bmsfolder
+ * @dangerous true
+ * @name 清理缓存
+ * @description 删除缓存目录中超过指定天数的条目（天数为 0 时清理全部）
+ * @frontend true
+ *
+ * @param {number} maxAgeDays - 过期天数
+ * @param {boolean} dryRun - 模拟运行（不实际执行）
+ *
+ * @returns {Promise<CacheCleanResult>} 清理结果
+ */
+export async function cleanCache(maxAgeDays: number, dryRun: boolean): Promise<CacheCleanResult> {
+  return await CacheManager.cleanStale(maxAgeDays, dryRun);
+}
+
+/**
+ * 查看缓存占用
+ *
+ * @command
+ * @category bmsfolder
+ * @dangerous false
+ * @name 查看缓存占用
+ * @description 显示缓存目录位置、各条目大小和总大小
+ * @frontend true
+ *
+ * @returns {Promise<CacheUsage>} 缓存占用信息
+ */
+export async function getCacheUsage(): Promise<CacheUsage> {
+  const usage = await CacheManager.getUsage();
+  console.log(
+    `Cache dir: ${usage.baseDir}, ${usage.entries.length} entries, ${usage.totalSize} bytes`
+  );
+  return usage;
+}
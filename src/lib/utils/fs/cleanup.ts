@@ -59,3 +59,53 @@ export async function removeEmptyFolders(parentDir: string, dryRun: boolean): Pr
     console.error(`Failed to remove empty folders in ${parentDir}:`, error);
   }
 }
+
+/**
+ * 是否为 macOS 生成的附加文件（__MACOSX 目录、AppleDouble `._*` 文件、.DS_Store）
+ */
+export function isMacosArtifact(name: string): boolean {
+  return name === '__MACOSX' || name === '.DS_Store' || name.startsWith('._');
+}
+
+/**
+ * 递归删除 macOS 生成的附加文件
+ *
+ * @command
+ * @category bmsfolder
+ * @dangerous true
+ * @name 清理 macOS 附加文件
+ * @description 递归删除 __MACOSX 目录、AppleDouble（._*）文件和 .DS_Store
+ * @frontend true
+ *
+ * @param {string} parentDir - 要清理的目录路径
+ * @param {boolean} dryRun - 模拟运行（不实际删除）
+ *
+ * @returns {Promise<number>} 删除的文件和目录数
+ */
+export async function cleanMacosArtifacts(parentDir: string, dryRun: boolean): Promise<number> {
+  let count = 0;
+
+  for (const entry of await readDir(parentDir)) {
+    if (!entry.name) {
+      continue;
+    }
+
+    const path = `${parentDir}/${entry.name}`;
+    if (isMacosArtifact(entry.name)) {
+      count++;
+      if (dryRun) {
+        console.log(`[dry-run] Would remove: ${path}`);
+        continue;
+      }
+      try {
+        await remove(path, { recursive: entry.isDirectory });
+      } catch (error) {
+        console.error(`Failed to remove ${path}:`, error);
+      }
+    } else if (entry.isDirectory) {
+      count += await cleanMacosArtifacts(path, dryRun);
+    }
+  }
+
+  return count;
+}
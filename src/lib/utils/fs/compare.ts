@@ -4,6 +4,7 @@
 
 import { isFileSameContent as checkFileSameContent } from './hash';
 import { readDir, stat } from '@tauri-apps/plugin-fs';
+import { IgnoreList } from './ignore';
 
 /**
  * 检查目录是否包含文件
@@ -24,8 +25,8 @@ export async function isDirHavingFile(dirPath: string): Promise<boolean> {
     const entries = await readDir(dirPath);
 
     for (const entry of entries) {
-      // 检查是否为文件
-      if (!entry.isDirectory) {
+      // 检查是否为文件（忽略系统垃圾文件）
+      if (!entry.isDirectory && !IgnoreList.isIgnored(entry.name)) {
         return true;
       }
     }
@@ -54,7 +55,7 @@ export async function isDirHavingFile(dirPath: string): Promise<boolean> {
 export async function isDirHavingContent(dirPath: string): Promise<boolean> {
   try {
     const entries = await readDir(dirPath);
-    return entries.length > 0;
+    return entries.some((entry) => !IgnoreList.isIgnored(entry.name));
   } catch (error) {
     console.error(`Failed to check directory: ${dirPath}`, error);
     return false;
@@ -0,0 +1,94 @@
+/**
+ * 压缩包的逐文件压缩策略
+ *
+ * 已压缩的音视频、图片和压缩包直接存储，谱面和文本等可压缩的文件才进行压缩，
+ * 打包速度更快，体积也几乎不变
+ */
+
+import { getFileExtension } from './path';
+
+/**
+ * ZIP 压缩方式
+ */
+export enum ZipMethod {
+  /** 仅存储（适合已压缩的音视频） */
+  Store = 0,
+  /** Deflate 压缩 */
+  Deflate = 8,
+}
+
+/**
+ * 压缩策略：根据文件路径决定压缩方式
+ */
+export type CompressionPolicy = (filePath: string) => ZipMethod;
+
+/**
+ * 压缩策略预设
+ */
+export enum CompressionPreset {
+  /** 按扩展名自动选择 */
+  Auto = 'auto',
+  /** 全部仅存储 */
+  StoreAll = 'store_all',
+  /** 全部压缩 */
+  DeflateAll = 'deflate_all',
+}
+
+/**
+ * 已经过压缩、再次压缩几乎没有收益的扩展名
+ */
+export const PRECOMPRESSED_FILE_EXTS = [
+  // 音频
+  'ogg',
+  'opus',
+  'flac',
+  'ape',
+  'mp3',
+  'm4a',
+  'aac',
+  'wma',
+  // 视频
+  'mp4',
+  'm4v',
+  'webm',
+  'mkv',
+  'avi',
+  'wmv',
+  'mpg',
+  'mpeg',
+  // 图片
+  'jpg',
+  'jpeg',
+  'png',
+  'webp',
+  'gif',
+  // 压缩包
+  'zip',
+  '7z',
+  'rar',
+  'gz',
+] as const;
+
+/**
+ * 默认策略：已压缩的文件直接存储，其余文件（谱面、文本、WAV、BMP 等）压缩
+ */
+export function defaultCompressionPolicy(filePath: string): ZipMethod {
+  const ext = getFileExtension(filePath);
+  return (PRECOMPRESSED_FILE_EXTS as readonly string[]).includes(ext)
+    ? ZipMethod.Store
+    : ZipMethod.Deflate;
+}
+
+/**
+ * 从预设获取压缩策略
+ */
+export function compressionPolicyFromPreset(preset: CompressionPreset): CompressionPolicy {
+  switch (preset) {
+    case CompressionPreset.Auto:
+      return defaultCompressionPolicy;
+    case CompressionPreset.StoreAll:
+      return () => ZipMethod.Store;
+    case CompressionPreset.DeflateAll:
+      return () => ZipMethod.Deflate;
+  }
+}
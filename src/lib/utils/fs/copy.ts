@@ -0,0 +1,326 @@
+/**
+ * 跨目录文件复制工具（含冲突处理、进度和校验）
+ *
+ * 冲突处理与 moveElementsAcrossDir 使用同一套 ReplaceOptions，源目录保持不变
+ */
+
+import { copyFile, exists, mkdir, readDir, remove, stat } from '@tauri-apps/plugin-fs';
+import type { IProgressManager } from '../progress';
+import { isFileSameContent } from './compare';
+import { HashService } from './hash';
+import { IgnoreList } from './ignore';
+import { getActionForPath, ReplaceAction, replaceOptionsFromPreset, ReplacePreset } from './moving';
+import type { ReplaceOptions } from './moving';
+import { getDirectoryPath, getFileExtension, getFileStem } from './path';
+import { formatSize } from './sizeTally';
+import { ResourceTelemetry } from '../telemetry';
+import { ProcessRunner } from '../media/processRunner';
+
+/**
+ * 复制进度
+ */
+export interface CopyProgress {
+  copiedFiles: number;
+  totalFiles: number;
+  copiedBytes: number;
+  totalBytes: number;
+  /** 当前处理的文件（相对源目录） */
+  currentFile: string;
+}
+
+/**
+ * 复制选项
+ */
+export interface CopyDirOptions {
+  /** 复制后重新读取两边文件比较哈希 */
+  verify?: boolean;
+  /** 每处理完一个文件调用一次 */
+  onProgress?: (progress: CopyProgress) => void;
+  /** 进度管理器（可选，支持暂停和取消） */
+  progressManager?: IProgressManager;
+}
+
+/**
+ * 复制结果
+ */
+export interface CopyDirResult {
+  copied: number;
+  /** 目标已存在、按策略跳过或内容相同的文件 */
+  skipped: number;
+  /** 目标已存在、以新名称复制的文件 */
+  renamed: number;
+  bytes: number;
+  /** 校验失败的文件（目标中的损坏副本已删除） */
+  verifyFailed: string[];
+  cancelled: boolean;
+}
+
+interface CopyEntry {
+  relative: string;
+  size: number;
+}
+
+/**
+ * 递归列出源目录下的文件和目录（跳过忽略列表中的文件）
+ */
+async function listEntries(
+  dirPath: string,
+  prefix: string,
+  files: CopyEntry[],
+  dirs: string[]
+): Promise<void> {
+  for (const entry of await readDir(dirPath)) {
+    if (!entry.name || IgnoreList.isIgnored(entry.name)) {
+      continue;
+    }
+    const relative = prefix ? `${prefix}/${entry.name}` : entry.name;
+    const path = `${dirPath}/${entry.name}`;
+    if (entry.isDirectory) {
+      dirs.push(relative);
+      await listEntries(path, relative, files, dirs);
+    } else {
+      const info = await stat(path);
+      files.push({ relative, size: info.size });
+    }
+  }
+}
+
+/**
+ * 为已存在的目标文件找一个可用的新名称（name.2.ext、name.3.ext ...）
+ *
+ * 已有同内容的副本时返回 null
+ */
+async function findRenameTarget(src: string, dst: string): Promise<string | null> {
+  const dir = getDirectoryPath(dst);
+  const fileName = dst.split('/').pop() ?? dst;
+  const stem = getFileStem(fileName);
+  const ext = getFileExtension(fileName);
+
+  for (let count = 1; count <= 1000; count++) {
+    const newName = count === 1 ? fileName : ext ? `${stem}.${count}.${ext}` : `${stem}.${count}`;
+    const candidate = `${dir}/${newName}`;
+    if (!(await exists(candidate))) {
+      return candidate;
+    }
+    if (await isFileSameContent(src, candidate)) {
+      return null;
+    }
+  }
+  throw new Error('重复文件过多');
+}
+
+/**
+ * 根据策略确定复制目标，返回 null 表示跳过
+ */
+async function resolveCopyTarget(
+  src: string,
+  dst: string,
+  replaceOptions: ReplaceOptions
+): Promise<{ target: string; renamed: boolean } | null> {
+  if (!(await exists(dst))) {
+    return { target: dst, renamed: false };
+  }
+
+  switch (await getActionForPath(replaceOptions, src)) {
+    case ReplaceAction.Replace:
+      return { target: dst, renamed: false };
+    case ReplaceAction.Skip:
+      return null;
+    case ReplaceAction.CheckReplace:
+      if (await isFileSameContent(src, dst)) {
+        return null;
+      }
+      break;
+  }
+
+  const target = await findRenameTarget(src, dst);
+  return target ? { target, renamed: target !== dst } : null;
+}
+
+/**
+ * 递归复制目录内容
+ *
+ * 目标目录不存在时会创建；目标中已存在的文件按 replaceOptions 处理
+ *
+ * @example
+ * ```typescript
+ * const result = await copyDirRecursive(fromDir, toDir, replaceOptionsFromPreset(preset), {
+ *   verify: true,
+ *   onProgress: (p) => console.log(`${p.copiedFiles}/${p.totalFiles}`),
+ * });
+ * ```
+ */
+export async function copyDirRecursive(
+  fromDir: string,
+  toDir: string,
+  replaceOptions: ReplaceOptions,
+  options: CopyDirOptions = {}
+): Promise<CopyDirResult> {
+  const { verify = false, onProgress, progressManager } = options;
+  const result: CopyDirResult = {
+    copied: 0,
+    skipped: 0,
+    renamed: 0,
+    bytes: 0,
+    verifyFailed: [],
+    cancelled: false,
+  };
+
+  if (fromDir === toDir) {
+    return result;
+  }
+
+  const files: CopyEntry[] = [];
+  const dirs: string[] = [];
+  await listEntries(fromDir, '', files, dirs);
+  const totalBytes = files.reduce((sum, file) => sum + file.size, 0);
+
+  await mkdir(toDir, { recursive: true });
+  for (const dir of dirs) {
+    await mkdir(`${toDir}/${dir}`, { recursive: true });
+  }
+
+  progressManager?.update(0, files.length, `复制 ${files.length} 个文件`);
+
+  let processedBytes = 0;
+  for (let i = 0; i < files.length; i++) {
+    if (progressManager?.shouldStop()) {
+      if (progressManager.getProgress().cancelled) {
+        result.cancelled = true;
+        break;
+      }
+      await progressManager.waitForResume();
+    }
+
+    const { relative, size } = files[i];
+    const src = `${fromDir}/${relative}`;
+    const resolved = await resolveCopyTarget(src, `${toDir}/${relative}`, replaceOptions);
+
+    if (!resolved) {
+      result.skipped++;
+    } else {
+      await copyFile(src, resolved.target);
+      ResourceTelemetry.addRead(size);
+      ResourceTelemetry.addWritten(size);
+      if (verify && !(await isCopyIntact(src, resolved.target))) {
+        console.error(` !_! Copy verification failed: ${src} -> ${resolved.target}`);
+        result.verifyFailed.push(relative);
+        await remove(resolved.target).catch(() => {});
+      } else {
+        result.copied++;
+        result.bytes += size;
+        if (resolved.renamed) {
+          result.renamed++;
+        }
+      }
+    }
+
+    processedBytes += size;
+    progressManager?.addProcessed(1, size);
+    progressManager?.update(i + 1, files.length, relative);
+    onProgress?.({
+      copiedFiles: i + 1,
+      totalFiles: files.length,
+      copiedBytes: processedBytes,
+      totalBytes,
+      currentFile: relative,
+    });
+  }
+
+  return result;
+}
+
+/**
+ * 重新读取源文件和副本比较哈希
+ */
+async function isCopyIntact(src: string, dst: string): Promise<boolean> {
+  const [srcHash, dstHash] = await Promise.all([
+    HashService.verifyFile(src),
+    HashService.verifyFile(dst),
+  ]);
+  return srcHash === dstHash;
+}
+
+/**
+ * 复制目录
+ *
+ * @command
+ * @category bmsfolder
+ * @dangerous true
+ * @name 复制目录
+ * @description 将源目录的内容递归复制到目标目录，冲突处理与移动相同，源目录保持不变
+ * @frontend true
+ *
+ * @param {string} fromDir - 源目录路径
+ * @param {string} toDir - 目标目录路径
+ * @param {ReplacePreset} replacePreset - 替换策略
+ * @param {boolean} verify - 复制后校验哈希
+ * @param {boolean} dryRun - 模拟运行（不实际复制）
+ *
+ * @returns {Promise<CopyDirResult | null>} 复制结果，模拟运行时为 null
+ */
+export async function copyDir(
+  fromDir: string,
+  toDir: string,
+  replacePreset: ReplacePreset = ReplacePreset.Default,
+  verify: boolean = false,
+  dryRun: boolean = false
+): Promise<CopyDirResult | null> {
+  if (dryRun) {
+    const files: CopyEntry[] = [];
+    await listEntries(fromDir, '', files, []);
+    const totalBytes = files.reduce((sum, file) => sum + file.size, 0);
+    console.log(
+      `[dry-run] Would copy ${files.length} files (${formatSize(totalBytes)}): ` +
+        `${fromDir} -> ${toDir}`
+    );
+    return null;
+  }
+
+  const result = await copyDirRecursive(fromDir, toDir, replaceOptionsFromPreset(replacePreset), {
+    verify,
+  });
+  console.log(
+    `Copied ${fromDir} -> ${toDir}: ${result.copied} files (${formatSize(result.bytes)}), ` +
+      `${result.skipped} skipped, ${result.renamed} renamed, ` +
+      `${result.verifyFailed.length} failed verification`
+  );
+  return result;
+}
+
+function isWindows(): boolean {
+  return navigator.platform?.toLowerCase().includes('win') ?? false;
+}
+
+/**
+ * 以硬链接复制目录，不支持时（Windows、跨文件系统、目标已存在）改为普通复制
+ *
+ * 硬链接的文件与源文件共用数据，只适合之后只会删除或替换、不会原地改写文件的副本
+ * （如转换媒体格式：转换结果写入新文件，原文件被删除）
+ *
+ * @returns 实际使用的方式
+ */
+export async function linkOrCopyDir(
+  fromDir: string,
+  toDir: string,
+  progressManager?: IProgressManager
+): Promise<'hardlink' | 'copy'> {
+  if (!isWindows() && !(await exists(toDir))) {
+    try {
+      const result = await ProcessRunner.exec('cp', ['-al', fromDir, toDir]);
+      if (result.success) {
+        return 'hardlink';
+      }
+      console.warn(` !_! Hardlink copy failed, copying instead: ${result.stderr.trim()}`);
+      await remove(toDir, { recursive: true }).catch(() => {});
+    } catch (error) {
+      console.warn(' !_! Hardlink copy unavailable, copying instead:', error);
+    }
+  }
+
+  await copyDirRecursive(fromDir, toDir, replaceOptionsFromPreset(ReplacePreset.Default), {
+    progressManager,
+  });
+  progressManager?.throwIfCancelled();
+  return 'copy';
+}
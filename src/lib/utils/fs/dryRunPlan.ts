@@ -0,0 +1,124 @@
+/**
+ * 模拟运行计划
+ *
+ * 模拟运行时除了打印日志，还记录计划执行的重命名、移动、合并和删除，作为命令结果返回，
+ * 前端可以在确认执行前以表格预览；同时汇总移动和删除的大小（见 DryRunSizeTally）
+ */
+
+import { DryRunSizeTally, getPathSize } from './sizeTally';
+import type { SizeTallySummary } from './sizeTally';
+
+/**
+ * 计划的操作类型
+ *
+ * - rename：目录或文件改名（同一位置，不移动内容）
+ * - move：整个移动到目标位置
+ * - merge：目标已存在，内容逐个移动合并进目标
+ * - delete：删除
+ */
+export type PlannedActionKind = 'rename' | 'move' | 'merge' | 'delete';
+
+/**
+ * 计划的单个操作
+ */
+export interface PlannedAction {
+  kind: PlannedActionKind;
+  source: string;
+  /** 目标路径（删除时为空） */
+  target?: string;
+  /** 涉及的字节数（改名时不统计） */
+  size?: number;
+}
+
+/**
+ * 模拟运行计划
+ */
+export interface DryRunPlan {
+  label: string;
+  actions: PlannedAction[];
+  /** 按目标汇总的移动大小和删除大小 */
+  summary: SizeTallySummary;
+}
+
+/**
+ * 模拟运行计划记录器
+ *
+ * @example
+ * ```typescript
+ * const planner = new DryRunPlanner('Split by first char');
+ * await planner.move(`${rootDir}/${name}`, `${rootDir}/ABCD/${name}`, 'ABCD');
+ * planner.print();
+ * return planner.plan();
+ * ```
+ */
+export class DryRunPlanner {
+  private readonly actions: PlannedAction[] = [];
+  private readonly tally: DryRunSizeTally;
+
+  constructor(private readonly label: string) {
+    this.tally = new DryRunSizeTally(label);
+  }
+
+  /**
+   * 记录改名
+   */
+  rename(source: string, target: string): void {
+    this.actions.push({ kind: 'rename', source, target });
+  }
+
+  /**
+   * 记录移动
+   *
+   * @param group - 汇总大小时的分组（默认为目标路径）
+   */
+  async move(source: string, target: string, group: string = target): Promise<void> {
+    const size = await getPathSize(source);
+    this.tally.addMoveSize(group, size);
+    this.actions.push({ kind: 'move', source, target, size });
+  }
+
+  /**
+   * 记录合并进已存在的目标
+   *
+   * @param group - 汇总大小时的分组（默认为目标路径）
+   */
+  async merge(source: string, target: string, group: string = target): Promise<void> {
+    const size = await getPathSize(source);
+    this.tally.addMoveSize(group, size);
+    this.actions.push({ kind: 'merge', source, target, size });
+  }
+
+  /**
+   * 记录删除
+   */
+  async remove(path: string): Promise<void> {
+    const size = await getPathSize(path);
+    this.tally.addRemoveSize(size);
+    this.actions.push({ kind: 'delete', source: path, size });
+  }
+
+  /**
+   * 并入另一份计划（如子目录的计划）
+   */
+  extend(plan: DryRunPlan | null): void {
+    for (const action of plan?.actions ?? []) {
+      this.actions.push(action);
+      if (action.kind === 'delete') {
+        this.tally.addRemoveSize(action.size ?? 0);
+      } else if (action.kind !== 'rename' && action.target) {
+        this.tally.addMoveSize(action.target, action.size ?? 0);
+      }
+    }
+  }
+
+  /**
+   * 打印大小摘要
+   */
+  print(): void {
+    this.tally.print();
+  }
+
+  plan(): DryRunPlan {
+    return { label: this.label, actions: [...this.actions], summary: this.tally.summary() };
+  }
+}
@@ -1,10 +1,17 @@
 /**
  * 文件哈希工具
- * 使用 Web Crypto API 计算 SHA512 哈希值
+ *
+ * 提供各模块共用的哈希服务：可插拔的哈希算法、分块读取、
+ * 按 (路径, 大小, 修改时间) 缓存结果（内存 LRU + 持久化），并限制每个磁盘的并发读取数
  */
 
 import { Command } from '@tauri-apps/plugin-shell';
-import { readFile, stat } from '@tauri-apps/plugin-fs';
+import { open, stat } from '@tauri-apps/plugin-fs';
+import { ConcurrencyPool } from '../media/concurrency';
+import { Blake3Hasher } from './blake3';
+import { Sha256Hasher, Sha512Hasher } from './sha2';
+import { getAppDataPath, readJsonFile, writeJsonFile } from '../storage';
+import { ResourceTelemetry } from '../telemetry';
 
 /**
  * 日期时间元组
@@ -18,22 +25,370 @@ export interface DateTimeTuple {
 }
 
 /**
- * 计算文件的 SHA512 哈希值
+ * 内置哈希算法
  */
-export async function calculateFileHash(filePath: string): Promise<string> {
-  const bytes = await readFile(filePath);
+export enum HashAlgorithm {
+  /** 内部内容比较的默认算法（增量计算，内存占用恒定） */
+  BLAKE3 = 'blake3',
+  /** 对外清单使用的算法 */
+  SHA256 = 'sha256',
+  SHA512 = 'sha512',
+}
+
+/**
+ * 对外清单（导出给其他工具校验）使用的哈希算法
+ */
+export const MANIFEST_HASH_ALGORITHM = HashAlgorithm.SHA256;
+
+/**
+ * 增量哈希计算器
+ */
+export interface Hasher {
+  update(chunk: Uint8Array): void;
+  digest(): Promise<string>;
+}
+
+/**
+ * 哈希计算器工厂
+ */
+export type HasherFactory = () => Hasher;
+
+/**
+ * 字节数组转十六进制字符串
+ */
+export function bytesToHex(bytes: Uint8Array): string {
+  return Array.from(bytes)
+    .map((b) => b.toString(16).padStart(2, '0'))
+    .join('');
+}
+
+/**
+ * 使用 Web Crypto 计算的最大数据量（字节）
+ *
+ * Web Crypto 不支持增量计算，数据需要全部保存到 digest 时拼接，峰值内存约为数据量的 2 倍
+ */
+const WEBCRYPTO_MAX_SIZE = 64 * 1024 * 1024;
+
+/**
+ * SHA-256 / SHA-512 哈希计算器
+ *
+ * 较小的数据使用 Web Crypto（速度快）；超过 WEBCRYPTO_MAX_SIZE 时改用增量实现，内存占用恒定
+ */
+function createShaHasher(
+  algorithm: 'SHA-256' | 'SHA-512',
+  createIncremental: () => Sha256Hasher | Sha512Hasher
+): HasherFactory {
+  return () => {
+    let chunks: Uint8Array[] = [];
+    let length = 0;
+    let incremental: Sha256Hasher | Sha512Hasher | null = null;
+
+    return {
+      update(chunk: Uint8Array) {
+        if (incremental) {
+          incremental.update(chunk);
+          return;
+        }
+        length += chunk.length;
+        if (length > WEBCRYPTO_MAX_SIZE) {
+          incremental = createIncremental();
+          for (const buffered of chunks) {
+            incremental.update(buffered);
+          }
+          incremental.update(chunk);
+          chunks = [];
+          return;
+        }
+        chunks.push(chunk.slice());
+      },
+      async digest() {
+        if (incremental) {
+          return bytesToHex(incremental.finalize());
+        }
+        const data = new Uint8Array(length);
+        let offset = 0;
+        for (const chunk of chunks) {
+          data.set(chunk, offset);
+          offset += chunk.length;
+        }
+        return bytesToHex(new Uint8Array(await crypto.subtle.digest(algorithm, data)));
+      },
+    };
+  };
+}
+
+/**
+ * BLAKE3 哈希计算器
+ */
+function createBlake3Hasher(): Hasher {
+  const hasher = new Blake3Hasher();
+  return {
+    update(chunk: Uint8Array) {
+      hasher.update(chunk);
+    },
+    async digest() {
+      return bytesToHex(hasher.finalize());
+    },
+  };
+}
+
+/**
+ * 文件哈希缓存的持久化文件名（位于应用数据目录）
+ */
+const HASH_CACHE_FILE = 'hash-cache.json';
+
+/**
+ * 分块读取大小
+ */
+const READ_CHUNK_SIZE = 8 * 1024 * 1024;
+
+/**
+ * 获取路径所在磁盘的标识
+ * Windows 使用盘符，其他平台使用第一级目录
+ */
+function getDiskKey(filePath: string): string {
+  const driveMatch = filePath.match(/^([A-Za-z]:)/);
+  if (driveMatch) {
+    return driveMatch[1].toUpperCase();
+  }
+
+  const firstSegment = filePath.split(/[\\/]/).find((segment) => segment !== '');
+  return `/${firstSegment ?? ''}`;
+}
+
+/**
+ * 共享哈希服务
+ */
+export class HashService {
+  /** 已注册的哈希算法 */
+  private static algorithms = new Map<string, HasherFactory>([
+    [HashAlgorithm.BLAKE3, createBlake3Hasher],
+    [HashAlgorithm.SHA256, createShaHasher('SHA-256', () => new Sha256Hasher())],
+    [HashAlgorithm.SHA512, createShaHasher('SHA-512', () => new Sha512Hasher())],
+  ]);
+
+  /** 内部比较使用的默认算法 */
+  private static defaultAlgorithm: string = HashAlgorithm.BLAKE3;
+
+  /** 内存缓存（Map 的插入顺序即 LRU 顺序） */
+  private static cache = new Map<string, string>();
+
+  /** 内存缓存容量 */
+  private static cacheCapacity = 20000;
+
+  /** 持久化缓存是否已加载 */
+  private static persistentLoaded: Promise<void> | null = null;
+
+  /** 持久化保存的定时器 */
+  private static saveTimer: ReturnType<typeof setTimeout> | null = null;
 
-  // 使用 Web Crypto API 计算 SHA512
-  const hashBuffer = await crypto.subtle.digest('SHA-512', bytes);
-  const hashArray = Array.from(new Uint8Array(hashBuffer));
+  /** 每个磁盘的读取池 */
+  private static readerPools = new Map<string, ConcurrencyPool<string>>();
 
-  // 转换为十六进制字符串
-  return hashArray.map((b) => b.toString(16).padStart(2, '0')).join('');
+  /** 每个磁盘的最大并发读取数 */
+  private static readersPerDisk = 4;
+
+  /**
+   * 注册哈希算法
+   */
+  static registerAlgorithm(name: string, factory: HasherFactory): void {
+    this.algorithms.set(name, factory);
+  }
+
+  /**
+   * 设置内部比较使用的默认算法
+   */
+  static setDefaultAlgorithm(name: string): void {
+    if (!this.algorithms.has(name)) {
+      throw new Error(`Unknown hash algorithm: ${name}`);
+    }
+    this.defaultAlgorithm = name;
+  }
+
+  /**
+   * 获取内部比较使用的默认算法
+   */
+  static getDefaultAlgorithm(): string {
+    return this.defaultAlgorithm;
+  }
+
+  /**
+   * 设置每个磁盘的最大并发读取数
+   */
+  static setReadersPerDisk(count: number): void {
+    this.readersPerDisk = Math.max(1, count);
+    this.readerPools.clear();
+  }
+
+  static getReadersPerDisk(): number {
+    return this.readersPerDisk;
+  }
+
+  /**
+   * 计算字节数据的哈希
+   */
+  static async hashBytes(
+    bytes: Uint8Array,
+    algorithm: string = this.defaultAlgorithm
+  ): Promise<string> {
+    const hasher = this.createHasher(algorithm);
+    hasher.update(bytes);
+    return await hasher.digest();
+  }
+
+  /**
+   * 计算文件哈希（带缓存）
+   *
+   * @param filePath - 文件路径
+   * @param algorithm - 哈希算法，默认使用内部比较算法
+   */
+  static async hashFile(
+    filePath: string,
+    algorithm: string = this.defaultAlgorithm
+  ): Promise<string> {
+    await this.loadPersistentCache();
+
+    const meta = await stat(filePath);
+    const cacheKey = `${algorithm}:${filePath}:${meta.size}:${meta.mtime?.getTime() ?? 0}`;
+
+    const cached = this.cache.get(cacheKey);
+    if (cached !== undefined) {
+      // 刷新 LRU 顺序
+      this.cache.delete(cacheKey);
+      this.cache.set(cacheKey, cached);
+      return cached;
+    }
+
+    const hash = await this.getReaderPool(filePath).add(() =>
+      this.hashFileUncached(filePath, algorithm)
+    );
+
+    this.remember(cacheKey, hash);
+    return hash;
+  }
+
+  /**
+   * 重新读取文件计算哈希，不使用缓存（用于校验文件内容是否损坏）
+   *
+   * @param filePath - 文件路径
+   * @param algorithm - 哈希算法，默认使用内部比较算法
+   */
+  static async verifyFile(
+    filePath: string,
+    algorithm: string = this.defaultAlgorithm
+  ): Promise<string> {
+    return await this.getReaderPool(filePath).add(() =>
+      this.hashFileUncached(filePath, algorithm)
+    );
+  }
+
+  /**
+   * 清空缓存（包括持久化缓存）
+   */
+  static async clearCache(): Promise<void> {
+    this.cache.clear();
+    await writeJsonFile(await getAppDataPath(HASH_CACHE_FILE), {});
+  }
+
+  private static createHasher(algorithm: string): Hasher {
+    const factory = this.algorithms.get(algorithm);
+    if (!factory) {
+      throw new Error(`Unknown hash algorithm: ${algorithm}`);
+    }
+    return factory();
+  }
+
+  private static getReaderPool(filePath: string): ConcurrencyPool<string> {
+    const diskKey = getDiskKey(filePath);
+    let pool = this.readerPools.get(diskKey);
+    if (!pool) {
+      pool = new ConcurrencyPool<string>(this.readersPerDisk);
+      this.readerPools.set(diskKey, pool);
+    }
+    return pool;
+  }
+
+  /**
+   * 分块读取文件并计算哈希
+   */
+  private static async hashFileUncached(filePath: string, algorithm: string): Promise<string> {
+    const hasher = this.createHasher(algorithm);
+    const file = await open(filePath, { read: true });
+    const buffer = new Uint8Array(READ_CHUNK_SIZE);
+
+    try {
+      while (true) {
+        const bytesRead = await file.read(buffer);
+        if (bytesRead === null || bytesRead === 0) {
+          break;
+        }
+        hasher.update(buffer.subarray(0, bytesRead));
+        ResourceTelemetry.addRead(bytesRead);
+      }
+    } finally {
+      await file.close();
+    }
+
+    return await hasher.digest();
+  }
+
+  private static remember(cacheKey: string, hash: string): void {
+    this.cache.set(cacheKey, hash);
+    while (this.cache.size > this.cacheCapacity) {
+      const oldestKey = this.cache.keys().next().value as string;
+      this.cache.delete(oldestKey);
+    }
+    this.scheduleSave();
+  }
+
+  private static loadPersistentCache(): Promise<void> {
+    if (!this.persistentLoaded) {
+      this.persistentLoaded = (async () => {
+        try {
+          const filePath = await getAppDataPath(HASH_CACHE_FILE);
+          const stored = await readJsonFile<Record<string, string>>(filePath, {});
+          for (const [key, hash] of Object.entries(stored)) {
+            if (!this.cache.has(key)) {
+              this.cache.set(key, hash);
+            }
+          }
+        } catch (error) {
+          console.warn('Failed to load hash cache:', error);
+        }
+      })();
+    }
+    return this.persistentLoaded;
+  }
+
+  private static scheduleSave(): void {
+    if (this.saveTimer !== null) {
+      return;
+    }
+
+    this.saveTimer = setTimeout(async () => {
+      this.saveTimer = null;
+      try {
+        await writeJsonFile(
+          await getAppDataPath(HASH_CACHE_FILE),
+          Object.fromEntries(this.cache.entries())
+        );
+      } catch (error) {
+        console.warn('Failed to save hash cache:', error);
+      }
+    }, 2000);
+  }
+}
+
+/**
+ * 计算文件的 SHA512 哈希值
+ */
+export async function calculateFileHash(filePath: string): Promise<string> {
+  return await HashService.hashFile(filePath, HashAlgorithm.SHA512);
 }
 
 /**
  * 比较两个文件的内容是否相同
- * 通过文件大小和 SHA512 哈希值进行比较
+ * 通过文件大小和哈希值（哈希服务的默认算法）进行比较
  */
 export async function isFileSameContent(file1: string, file2: string): Promise<boolean> {
   try {
@@ -45,7 +400,10 @@ export async function isFileSameContent(file1: string, file2: string): Promise<b
     }
 
     // 大小相同，再比较哈希值
-    const [hash1, hash2] = await Promise.all([calculateFileHash(file1), calculateFileHash(file2)]);
+    const [hash1, hash2] = await Promise.all([
+      HashService.hashFile(file1),
+      HashService.hashFile(file2),
+    ]);
 
     return hash1 === hash2;
   } catch (error) {
@@ -0,0 +1,30 @@
+/**
+ * 忽略列表测试
+ *
+ * 清理命令和移动/合并按 isIgnored 跳过或删除文件；作品的固定标记不论忽略模式如何都必须保留
+ */
+
+import assert from 'node:assert/strict';
+import { afterEach, test } from 'node:test';
+import { PIN_MARKER_FILE } from '../work/pin';
+import { IgnoreList } from './ignore';
+
+afterEach(() => IgnoreList.reset());
+
+test('default patterns ignore system files and the write probe', () => {
+  for (const name of ['Thumbs.db', 'thumbs.DB', '._song.wav', '__MACOSX', '.DS_Store']) {
+    assert.equal(IgnoreList.isIgnored(name), true, name);
+  }
+  assert.equal(IgnoreList.isIgnored('.bms-toolbox-write-probe-1700000000000'), true);
+  assert.equal(IgnoreList.isIgnored('song.bms'), false);
+});
+
+test('cleanup keeps the pin marker', () => {
+  assert.equal(IgnoreList.isIgnored(PIN_MARKER_FILE), false);
+
+  // 用户自定义的宽泛模式也不会匹配固定标记
+  IgnoreList.setPatterns(['.bms-toolbox*', '.*']);
+  assert.equal(IgnoreList.isIgnored(PIN_MARKER_FILE), false);
+  assert.equal(IgnoreList.isIgnored(PIN_MARKER_FILE.toUpperCase()), false);
+  assert.equal(IgnoreList.isIgnored('.bms-toolbox-write-probe-1'), true);
+});
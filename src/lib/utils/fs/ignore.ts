@@ -0,0 +1,129 @@
+/**
+ * 全局忽略列表
+ *
+ * 系统生成的文件（Thumbs.db、.DS_Store、desktop.ini、__MACOSX 等）不属于作品内容。
+ * 检查目录是否为空、移动、同步和打包时跳过这些文件，并提供递归清理命令
+ */
+
+import { readDir, remove } from '@tauri-apps/plugin-fs';
+import { PIN_MARKER_FILE } from '../work/pin';
+
+/**
+ * 默认忽略的文件和目录名（支持 * 通配符，不区分大小写）
+ */
+export const DEFAULT_IGNORE_PATTERNS = [
+  'Thumbs.db',
+  'ehthumbs.db',
+  'desktop.ini',
+  '.DS_Store',
+  '._*',
+  '__MACOSX',
+  '.Spotlight-V100',
+  '.Trashes',
+  '.fseventsd',
+  '.bms-toolbox-write-probe-*',
+];
+
+/**
+ * 始终不忽略的文件名（工具自身的标记文件，清理、移动和合并时必须保留）
+ */
+const PROTECTED_NAMES = new Set([PIN_MARKER_FILE.toLowerCase()]);
+
+/**
+ * 将通配符模式转换为正则表达式
+ */
+function patternToRegExp(pattern: string): RegExp {
+  const escaped = pattern
+    .replace(/[.+^${}()|[\]\\]/g, '\\$&')
+    .replace(/\*/g, '.*')
+    .replace(/\?/g, '.');
+  return new RegExp(`^${escaped}$`, 'i');
+}
+
+/**
+ * 忽略列表
+ */
+export class IgnoreList {
+  private static patterns: string[] = [...DEFAULT_IGNORE_PATTERNS];
+  private static regexps: RegExp[] = DEFAULT_IGNORE_PATTERNS.map(patternToRegExp);
+
+  /**
+   * 设置忽略的文件和目录名模式
+   */
+  static setPatterns(patterns: string[]): void {
+    this.patterns = patterns.map((pattern) => pattern.trim()).filter(Boolean);
+    this.regexps = this.patterns.map(patternToRegExp);
+  }
+
+  /**
+   * 获取当前的忽略模式
+   */
+  static getPatterns(): string[] {
+    return [...this.patterns];
+  }
+
+  /**
+   * 恢复默认的忽略模式
+   */
+  static reset(): void {
+    this.setPatterns(DEFAULT_IGNORE_PATTERNS);
+  }
+
+  /**
+   * 文件或目录名是否被忽略（固定标记等工具自身的文件不受忽略模式影响）
+   */
+  static isIgnored(name: string): boolean {
+    if (PROTECTED_NAMES.has(name.toLowerCase())) {
+      return false;
+    }
+    return this.regexps.some((regexp) => regexp.test(name));
+  }
+}
+
+/**
+ * 递归删除忽略列表中的文件和目录
+ *
+ * @command
+ * @category bmsfolder
+ * @dangerous true
+ * @name 清理系统垃圾文件
+ * @description 递归删除 Thumbs.db、.DS_Store、desktop.ini、__MACOSX 等忽略列表中的文件和目录
+ * @frontend true
+ *
+ * @param {string} parentDir - 要清理的目录路径
+ * @param {boolean} dryRun - 模拟运行（不实际删除）
+ *
+ * @returns {Promise<string[]>} 删除的路径
+ */
+export async function removeIgnoredFiles(parentDir: string, dryRun: boolean): Promise<string[]> {
+  const removed: string[] = [];
+
+  const walk = async (dirPath: string): Promise<void> => {
+    for (const entry of await readDir(dirPath)) {
+      if (!entry.name) {
+        continue;
+      }
+
+      const path = `${dirPath}/${entry.name}`;
+      if (IgnoreList.isIgnored(entry.name)) {
+        if (dryRun) {
+          console.log(`[dry-run] Would remove: ${path}`);
+        } else {
+          try {
+            await remove(path, { recursive: entry.isDirectory });
+          } catch (error) {
+            console.error(` !_! Failed to remove ${path}:`, error);
+            continue;
+          }
+        }
+        removed.push(path);
+      } else if (entry.isDirectory) {
+        await walk(path);
+      }
+    }
+  };
+
+  await walk(parentDir);
+  console.log(`Removed ${removed.length} ignored files in ${parentDir}`);
+  return removed;
+}
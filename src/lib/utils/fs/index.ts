@@ -4,11 +4,25 @@
  */
 
 export * from './hash';
+export * from './blake3';
 export * from './path';
 export * from './archive';
+export * from './zip';
+export * from './zipSalvage';
+export * from './compression';
 export * from './compare';
 export * from './cleanup';
+export * from './ignore';
 export * from './similarity';
 export * from './moving';
 export * from './sync';
 export * from './name';
+export * from './cache';
+export * from './sizeTally';
+export * from './dryRunPlan';
+export * from './writable';
+export * from './permissions';
+export * from './copy';
+export * from './lock';
+export * from './openFiles';
+export * from './targetCheck';
@@ -0,0 +1,228 @@
+/**
+ * 根目录操作锁
+ *
+ * 同一根目录上同时运行两个修改操作（例如两个窗口、或命令行与界面同时运行）会在合并途中损坏文件夹。
+ * 危险操作执行前在应用数据目录的锁登记目录中写入锁文件（文件名按目录路径区分），执行期间定时刷新。
+ * 锁定目录时同时检查上级目录和下级目录的锁，锁定根目录和锁定其中的作品目录互斥；
+ * 锁文件不放在被锁定的目录中，重命名或移动该目录不会留下孤立的锁。
+ * 超过一定时间未刷新的锁视为上次操作异常退出留下的，自动接管
+ */
+
+import { exists, readDir, readTextFile, remove, writeTextFile } from '@tauri-apps/plugin-fs';
+import { getAppDataPathForDir, getAppDataSubDir } from '../storage';
+import { normalizePath } from './path';
+
+/**
+ * 锁登记目录（相对于应用数据目录）
+ */
+const LOCK_DIR = 'locks';
+
+/** 刷新锁文件的间隔 */
+const HEARTBEAT_INTERVAL_MS = 30 * 1000;
+/** 超过该时间未刷新的锁视为失效 */
+const STALE_AFTER_MS = 2 * 60 * 1000;
+
+/**
+ * 锁文件内容
+ */
+export interface RootLockInfo {
+  id: string;
+  /** 被锁定的目录（规范化后的路径） */
+  path: string;
+  command: string;
+  startedAt: string;
+  heartbeatAt: string;
+}
+
+/**
+ * 目录正被另一个操作使用
+ */
+export class RootLockedError extends Error {
+  constructor(
+    readonly path: string,
+    readonly info: RootLockInfo
+  ) {
+    super(`另一个操作正在运行：${info.command}（开始于 ${info.startedAt}）：${path}`);
+    this.name = 'RootLockedError';
+  }
+}
+
+async function readLockInfo(lockPath: string): Promise<RootLockInfo | null> {
+  try {
+    return JSON.parse(await readTextFile(lockPath)) as RootLockInfo;
+  } catch {
+    return null;
+  }
+}
+
+function isStale(info: RootLockInfo): boolean {
+  const heartbeat = Date.parse(info.heartbeatAt);
+  return Number.isNaN(heartbeat) || Date.now() - heartbeat > STALE_AFTER_MS;
+}
+
+/**
+ * 规范化锁定的路径（统一分隔符、去掉末尾分隔符；Windows 路径不区分大小写）
+ */
+function normalizeLockPath(dirPath: string): string {
+  const normalized = normalizePath(dirPath).replace(/\/+$/, '');
+  return /^[a-zA-Z]:/.test(normalized) ? normalized.toLowerCase() : normalized;
+}
+
+/**
+ * 两个路径是否相同或互为上下级
+ */
+function isRelatedPath(a: string, b: string): boolean {
+  const [shorter, longer] = a.length <= b.length ? [a, b] : [b, a];
+  return longer === shorter || longer.startsWith(`${shorter}/`);
+}
+
+/**
+ * 查找与目录相同、位于其上级或下级的其他有效锁
+ *
+ * @param ownId - 自己的锁 ID（不视为冲突）
+ */
+async function findActiveLocks(path: string, ownId?: string): Promise<RootLockInfo[]> {
+  const lockDir = await getAppDataSubDir(LOCK_DIR);
+  const active: RootLockInfo[] = [];
+  for (const entry of await readDir(lockDir)) {
+    if (!entry.isFile || !entry.name.endsWith('.json')) {
+      continue;
+    }
+    const info = await readLockInfo(`${lockDir}/${entry.name}`);
+    if (info && info.id !== ownId && !isStale(info) && isRelatedPath(info.path, path)) {
+      active.push(info);
+    }
+  }
+  return active;
+}
+
+/**
+ * 根目录操作锁
+ */
+export class RootLock {
+  private heartbeat: ReturnType<typeof setInterval> | null = null;
+
+  private constructor(
+    readonly dirPath: string,
+    private readonly lockPath: string,
+    private readonly info: RootLockInfo
+  ) {}
+
+  /**
+   * 获取目录的操作锁，目录、其上级或下级目录已被锁定时抛出 RootLockedError
+   *
+   * @param dirPath - 要锁定的目录
+   * @param command - 当前操作名称（显示在其他操作的错误信息中）
+   */
+  static async acquire(dirPath: string, command: string): Promise<RootLock> {
+    const path = normalizeLockPath(dirPath);
+    const [active] = await findActiveLocks(path);
+    if (active) {
+      throw new RootLockedError(dirPath, active);
+    }
+
+    const lockPath = await getAppDataPathForDir(LOCK_DIR, path);
+    if (await exists(lockPath)) {
+      const stale = await readLockInfo(lockPath);
+      console.warn(
+        ` !_! Removing stale lock of ${dirPath}` + (stale ? ` (${stale.command})` : '') + '.'
+      );
+      await remove(lockPath);
+    }
+
+    const now = new Date().toISOString();
+    const info: RootLockInfo = {
+      id: crypto.randomUUID(),
+      path,
+      command,
+      startedAt: now,
+      heartbeatAt: now,
+    };
+    try {
+      // createNew：两个进程同时锁定同一目录时只有一个能写入成功
+      await writeTextFile(lockPath, JSON.stringify(info), { createNew: true });
+    } catch (error) {
+      const other = await readLockInfo(lockPath);
+      if (other) {
+        throw new RootLockedError(dirPath, other);
+      }
+      throw error;
+    }
+
+    const lock = new RootLock(dirPath, lockPath, info);
+
+    // 两个进程同时锁定上下级目录时都可能通过了检查，写入后再检查一次，较晚开始的一方放弃
+    const conflict = (await findActiveLocks(path, info.id)).find(
+      (other) =>
+        other.startedAt < info.startedAt ||
+        (other.startedAt === info.startedAt && other.id < info.id)
+    );
+    if (conflict) {
+      await lock.release();
+      throw new RootLockedError(dirPath, conflict);
+    }
+
+    lock.heartbeat = setInterval(() => void lock.touch(), HEARTBEAT_INTERVAL_MS);
+    return lock;
+  }
+
+  private async touch(): Promise<void> {
+    this.info.heartbeatAt = new Date().toISOString();
+    try {
+      await writeTextFile(this.lockPath, JSON.stringify(this.info));
+    } catch (error) {
+      console.warn(` !_! Failed to refresh lock of ${this.dirPath}:`, error);
+    }
+  }
+
+  /**
+   * 释放锁（锁文件已被其他操作接管时不删除）
+   */
+  async release(): Promise<void> {
+    if (this.heartbeat !== null) {
+      clearInterval(this.heartbeat);
+      this.heartbeat = null;
+    }
+    try {
+      const current = await readLockInfo(this.lockPath);
+      if (current?.id === this.info.id) {
+        await remove(this.lockPath);
+      }
+    } catch (error) {
+      console.warn(` !_! Failed to release lock of ${this.dirPath}:`, error);
+    }
+  }
+}
+
+/**
+ * 锁定所有目录后执行操作，结束后释放（不存在的目录不锁定）
+ *
+ * @param dirPaths - 要锁定的目录
+ * @param command - 当前操作名称
+ * @param fn - 要执行的操作
+ */
+export async function withRootLocks<T>(
+  dirPaths: string[],
+  command: string,
+  fn: () => Promise<T>
+): Promise<T> {
+  const locks: RootLock[] = [];
+  try {
+    // 先锁上级目录，已锁定目录下的子目录不再重复锁定
+    const normalized = dirPaths.map((dirPath) => dirPath.replace(/[\\/]+$/, ''));
+    for (const dirPath of [...new Set(normalized)].sort((a, b) => a.length - b.length)) {
+      const covered = locks.some(
+        (lock) =>
+          dirPath.startsWith(lock.dirPath) && /^[\\/]/.test(dirPath.slice(lock.dirPath.length))
+      );
+      if (!covered && (await exists(dirPath))) {
+        locks.push(await RootLock.acquire(dirPath, command));
+      }
+    }
+    return await fn();
+  } finally {
+    for (const lock of locks.reverse()) {
+      await lock.release();
+    }
+  }
+}
@@ -3,7 +3,10 @@
  */
 
 import { exists, readDir, remove, rename, stat } from '@tauri-apps/plugin-fs';
+import type { DirEntry } from '@tauri-apps/plugin-fs';
+import type { IProgressManager } from '../progress';
 import { isDirHavingContent, isFileSameContent } from './compare';
+import { IgnoreList } from './ignore';
 import { getFileExtension, getFileStem } from './path';
 
 /**
@@ -66,56 +69,180 @@ function replaceOptionsUpdatePack(): ReplaceOptions {
 /**
  * 获取特定文件的替换策略
  */
-async function getActionForPath(options: ReplaceOptions, filePath: string): Promise<ReplaceAction> {
+export async function getActionForPath(
+  options: ReplaceOptions,
+  filePath: string
+): Promise<ReplaceAction> {
   const ext = getFileExtension(filePath);
   return options.ext[ext] ?? options.default;
 }
 
 /**
- * 检查目录中是否有被跳过的文件
+ * 移动进度
  */
-async function hasSkippedFiles(
-  currentFrom: string,
-  replaceOptions: ReplaceOptions
-): Promise<boolean> {
-  const entries = await readDir(currentFrom);
-  for (const entry of entries) {
-    if (!entry.name || entry.isDirectory) {
-      continue;
+export interface MoveProgress {
+  /** 已处理的目录数 */
+  dirsDone: number;
+  /** 已发现的目录数（处理过程中增加） */
+  dirsTotal: number;
+  moved: number;
+  skipped: number;
+  renamed: number;
+  /** 已移动文件的字节数（整体移动的目录不计入） */
+  bytes: number;
+  /** 当前处理的目录 */
+  currentDir: string;
+}
+
+/**
+ * 移动选项
+ */
+export interface MoveOptions {
+  /** 每处理完一个目录、以及每移动 progressEvery 个文件调用一次 */
+  onProgress?: (progress: MoveProgress) => void;
+  /** 进度管理器（可选） */
+  progressManager?: IProgressManager;
+  /** 文件进度的报告间隔，默认 100 */
+  progressEvery?: number;
+  /** 文件被占用（如杀毒软件正在扫描）时的重试次数，默认 5 */
+  lockedRetries?: number;
+  /** 第一次重试前的等待时间（毫秒），之后逐次增加，默认 500 */
+  lockedRetryDelay?: number;
+}
+
+/**
+ * 移动结果
+ */
+export interface MoveResult {
+  /** 移动的文件数 */
+  moved: number;
+  /** 目标已存在、按策略跳过或内容相同的文件数 */
+  skipped: number;
+  /** 目标已存在、以新名称移动的文件数 */
+  renamed: number;
+  /** 目标不存在、整体移动的目录数 */
+  movedDirs: number;
+  bytes: number;
+  /** 重试后仍被占用、留在源目录中的文件 */
+  locked: string[];
+}
+
+type MoveOutcome = 'moved' | 'skipped' | 'renamed';
+
+/**
+ * 移动过程中的计数和进度报告
+ */
+class MoveTally {
+  readonly result: MoveResult = {
+    moved: 0,
+    skipped: 0,
+    renamed: 0,
+    movedDirs: 0,
+    bytes: 0,
+    locked: [],
+  };
+  dirsDone = 0;
+  dirsTotal = 1;
+  currentDir = '';
+  private sinceReport = 0;
+
+  constructor(readonly options: MoveOptions) {}
+
+  addFile(outcome: MoveOutcome, size: number): void {
+    this.result[outcome]++;
+    if (outcome !== 'skipped') {
+      this.result.bytes += size;
+      this.options.progressManager?.addProcessed(1, size);
     }
-    const action = await getActionForPath(replaceOptions, `${currentFrom}/${entry.name}`);
-    if (action === ReplaceAction.Skip) {
-      return true;
+    if (++this.sinceReport >= (this.options.progressEvery ?? 100)) {
+      this.report();
+    }
+  }
+
+  report(): void {
+    this.sinceReport = 0;
+    const { moved, skipped, renamed, bytes } = this.result;
+    this.options.progressManager?.update(
+      this.dirsDone,
+      this.dirsTotal,
+      `移动 ${moved} 个文件，跳过 ${skipped}，重命名 ${renamed}：${this.currentDir}`
+    );
+    this.options.onProgress?.({
+      dirsDone: this.dirsDone,
+      dirsTotal: this.dirsTotal,
+      moved,
+      skipped,
+      renamed,
+      bytes,
+      currentDir: this.currentDir,
+    });
+  }
+}
+
+const LOCKED_ERROR_PATTERN =
+  /os error (16|26|32|33)\b|EBUSY|ETXTBSY|sharing violation|being used by another process/i;
+
+/**
+ * 是否为文件被占用导致的错误
+ *
+ * Windows 的 ERROR_SHARING_VIOLATION (32) / ERROR_LOCK_VIOLATION (33)，
+ * 以及 Unix 的 EBUSY (16) / ETXTBSY (26)
+ */
+function isLockedError(error: unknown): boolean {
+  const message = error instanceof Error ? error.message : String(error);
+  return LOCKED_ERROR_PATTERN.test(message);
+}
+
+/**
+ * 重命名文件，文件被占用时等待后重试
+ */
+async function renameWithRetry(src: string, dst: string, options: MoveOptions): Promise<void> {
+  const retries = options.lockedRetries ?? 5;
+  const delay = options.lockedRetryDelay ?? 500;
+  for (let attempt = 0; ; attempt++) {
+    try {
+      await rename(src, dst);
+      return;
+    } catch (error) {
+      if (attempt >= retries || !isLockedError(error)) {
+        throw error;
+      }
+      console.warn(` !_! File is locked, retrying (${attempt + 1}/${retries}): ${src}`);
+      await new Promise((resolve) => setTimeout(resolve, delay * (attempt + 1)));
     }
   }
-  return false;
 }
 
 /**
  * 递归移动目录内容
+ *
+ * @returns 移动、跳过、重命名的文件数和移动的字节数
  */
 export async function moveElementsAcrossDir(
   fromDir: string,
   toDir: string,
-  replaceOptions: ReplaceOptions
-): Promise<void> {
+  replaceOptions: ReplaceOptions,
+  options: MoveOptions = {}
+): Promise<MoveResult> {
+  const tally = new MoveTally(options);
+
   // 检查源目录元数据
   let fromMeta;
   try {
     fromMeta = await stat(fromDir);
   } catch {
     // 源目录不存在，直接返回
-    return;
+    return tally.result;
   }
 
   // 源和目标相同，跳过
   if (fromDir === toDir) {
-    return;
+    return tally.result;
   }
 
   // 源不是目录，跳过
   if (!fromMeta.isDirectory) {
-    return;
+    return tally.result;
   }
 
   // 检查目标目录
@@ -124,7 +251,8 @@ export async function moveElementsAcrossDir(
   if (!toExists) {
     // 目标不存在，直接重命名整个目录
     await rename(fromDir, toDir);
-    return;
+    tally.result.movedDirs++;
+    return tally.result;
   }
 
   // 目标存在但不是目录
@@ -138,20 +266,31 @@ export async function moveElementsAcrossDir(
   const dirsToCleanup: string[] = [];
 
   while (queue.length > 0) {
+    // 取消时停在目录之间：已处理的目录保持一致，剩余内容留在源目录中，可以稍后继续移动
+    const progressManager = options.progressManager;
+    if (progressManager?.shouldStop()) {
+      if (progressManager.getProgress().cancelled) {
+        console.log(`移动已取消，剩余内容留在 ${fromDir}`);
+        break;
+      }
+      await progressManager.waitForResume();
+    }
+
     const [currentFrom, currentTo] = queue.shift()!;
+    tally.currentDir = currentFrom;
 
     // 处理当前目录
-    const subdirs = await processDirectory(currentFrom, currentTo, replaceOptions);
+    const subdirs = await processDirectory(currentFrom, currentTo, replaceOptions, tally);
 
     // 将发现的子目录加入队列
     queue.push(...subdirs);
+    tally.dirsTotal += subdirs.length;
+    tally.dirsDone++;
+    tally.report();
 
     // 延迟清理：只标记待删除的目录，在队列处理完毕后再删除
-    const subEntries = await readDir(currentFrom);
-    const hasSubdirs = subEntries.some((e) => e.isDirectory);
-    const hasContent = await isDirHavingContent(currentFrom);
-    const skippedFiles = await hasSkippedFiles(currentFrom, replaceOptions);
-    if (!hasSubdirs && !hasContent && !skippedFiles) {
+    // （被跳过的文件和子目录都会留在源目录中，检查一次是否还有内容即可）
+    if (!(await isDirHavingContent(currentFrom))) {
       dirsToCleanup.push(currentFrom);
     }
   }
@@ -168,169 +307,183 @@ export async function moveElementsAcrossDir(
       console.warn(`权限不足，无法删除 ${dir}:`, error);
     }
   }
+
+  return tally.result;
 }
 
 /**
  * 处理单个目录，返回需要进一步处理的子目录
+ *
+ * 目标目录只读取一次，用文件名判断目标是否存在，避免对每个文件调用 stat / exists；
+ * 大量小文件（按键音）的移动时间主要花在这些元数据调用上
  */
 async function processDirectory(
   fromDir: string,
   toDir: string,
-  replaceOptions: ReplaceOptions
+  replaceOptions: ReplaceOptions,
+  tally: MoveTally
 ): Promise<[string, string][]> {
   const subdirs: [string, string][] = [];
 
-  // 收集目录条目
-  const entries = await readDir(fromDir);
-  const pairs: Array<{ src: string; dst: string; name: string }> = [];
-
-  for (const entry of entries) {
-    if (!entry.name) continue;
+  const [entries, dstEntries] = await Promise.all([readDir(fromDir), readDir(toDir)]);
+  const dstByName = new Map(dstEntries.map((entry) => [entry.name, entry]));
+  const dstByLowerName = new Map(dstEntries.map((entry) => [entry.name.toLowerCase(), entry]));
+
+  /**
+   * 查找已存在的目标：名称完全相同时直接返回，只有大小写不同时
+   * 交给文件系统判断（不区分大小写的文件系统上视为同一个文件）
+   */
+  const findDst = async (name: string): Promise<DirEntry | undefined> => {
+    const entry = dstByName.get(name);
+    if (entry) {
+      return entry;
+    }
+    const caseMatch = dstByLowerName.get(name.toLowerCase());
+    return caseMatch && (await exists(`${toDir}/${name}`)) ? caseMatch : undefined;
+  };
 
-    pairs.push({
-      src: `${fromDir}/${entry.name}`,
-      dst: `${toDir}/${entry.name}`,
-      name: entry.name,
-    });
-  }
+  const dirDirectMoves: [string, string][] = [];
+  const fileOps: Array<{
+    src: string;
+    dst: string;
+    action: ReplaceAction;
+    dstExists: boolean;
+  }> = [];
 
-  // 预取元数据（并发）
-  const metas = await Promise.all(
-    pairs.map(async (pair) => {
-      try {
-        const srcMeta = await stat(pair.src);
-        let dstMeta;
+  for (const entry of entries) {
+    // 系统垃圾文件留在源目录，随源目录一起删除
+    if (!entry.name || IgnoreList.isIgnored(entry.name)) continue;
+
+    const src = `${fromDir}/${entry.name}`;
+    const dst = `${toDir}/${entry.name}`;
+    if (entry.isDirectory) {
+      const dstEntry = await findDst(entry.name);
+      if (!dstEntry || !dstEntry.isDirectory) {
+        dirDirectMoves.push([src, dst]);
+        continue;
+      }
+      // 目标子目录为空（或只有系统垃圾文件）时删除后整体移动，不再逐个文件处理；
+      // 读取失败时不能确定为空，按普通目录逐个合并
+      const dstListing = await readDir(dst).catch(() => null);
+      if (dstListing && dstListing.every((child) => IgnoreList.isIgnored(child.name))) {
         try {
-          dstMeta = await stat(pair.dst);
+          await remove(dst, { recursive: true });
+          dirDirectMoves.push([src, dst]);
         } catch {
-          dstMeta = null;
+          subdirs.push([src, dst]);
         }
-
-        return { ...pair, srcMeta, dstMeta };
-      } catch {
-        return { ...pair, srcMeta: null, dstMeta: null };
-      }
-    })
-  );
-
-  // 分类
-  const subdirBothExist: [string, string][] = [];
-  const dirDirectMoves: [string, string][] = [];
-  const fileSkipOps: Array<{ src: string; dst: string }> = [];
-  const fileRenameOps: Array<{ src: string; dst: string }> = [];
-  const fileReplaceOps: Array<{ src: string; dst: string }> = [];
-
-  for (const { src, dst, srcMeta, dstMeta } of metas) {
-    if (!srcMeta) continue;
-
-    if (srcMeta.isDirectory) {
-      if (dstMeta && dstMeta.isDirectory) {
-        subdirBothExist.push([src, dst]);
       } else {
-        dirDirectMoves.push([src, dst]);
+        subdirs.push([src, dst]);
       }
-    } else if (srcMeta.isFile) {
+    } else if (entry.isFile) {
       const action = await getActionForPath(replaceOptions, src);
-
-      switch (action) {
-        case ReplaceAction.Skip:
-          fileSkipOps.push({ src, dst });
-          break;
-        case ReplaceAction.Rename:
-          fileRenameOps.push({ src, dst });
-          break;
-        default:
-          fileReplaceOps.push({ src, dst });
-          break;
-      }
+      fileOps.push({ src, dst, action, dstExists: (await findDst(entry.name)) !== undefined });
     }
   }
 
-  // Stage 1: 双边都存在的子目录 -> 加入队列
-  subdirs.push(...subdirBothExist);
-
-  // Stage 2a: 目录直接移动（并发）
+  // 目录直接移动（并发）
   await Promise.all(
     dirDirectMoves.map(async ([src, dst]) => {
       try {
         await rename(src, dst);
+        tally.result.movedDirs++;
       } catch (error) {
         console.warn(`Failed to move directory ${src} -> ${dst}:`, error);
       }
     })
   );
 
-  // Stage 2b: 文件 Skip 操作（并发）
-  await Promise.all(
-    fileSkipOps.map(async ({ src, dst }) => {
-      const dstExists = await exists(dst);
-      if (dstExists) {
-        return; // 跳过
-      }
-      await moveFile(src, dst, replaceOptions);
-    })
-  );
-
-  // Stage 2c: 文件 Rename 操作（并发）
-  await Promise.all(
-    fileRenameOps.map(async ({ src, dst }) => {
-      await moveFileRename(src, dst);
-    })
-  );
-
-  // Stage 3: 剩余的覆盖操作（Replace / CheckReplace）（并发）
-  await Promise.all(
-    fileReplaceOps.map(async ({ src, dst }) => {
-      await moveFile(src, dst, replaceOptions);
-    })
-  );
+  // 文件移动分批，每批并发：先移动目标中不存在的文件，再处理冲突（跳过、重命名、覆盖）。
+  // 目标列表只在移动前读取，冲突批次只涉及原本就存在的文件；
+  // 重命名逐个检查候选名，不会覆盖前面批次刚移动过去的文件
+  const conflicts = fileOps.filter((op) => op.dstExists);
+  const stages = [
+    fileOps.filter((op) => !op.dstExists),
+    conflicts.filter(
+      (op) => op.action === ReplaceAction.Skip || op.action === ReplaceAction.Rename
+    ),
+    conflicts.filter(
+      (op) => op.action !== ReplaceAction.Skip && op.action !== ReplaceAction.Rename
+    ),
+  ];
+  for (const stage of stages) {
+    await Promise.all(
+      stage.map(async ({ src, dst, action, dstExists }) => {
+        const size = (await stat(src).catch(() => null))?.size ?? 0;
+        try {
+          const outcome = await moveFile(src, dst, action, dstExists, tally.options);
+          tally.addFile(outcome, size);
+        } catch (error) {
+          // 重试后仍被占用的文件留在源目录，记录后继续移动其他文件
+          if (!isLockedError(error)) {
+            throw error;
+          }
+          console.warn(` !_! File is still locked, leaving it in place: ${src}`);
+          tally.result.locked.push(src);
+        }
+      })
+    );
+  }
 
   return subdirs;
 }
 
 /**
  * 移动单个文件，根据策略处理冲突
+ *
+ * @param dstExists - 目标是否已存在（由调用方根据目标目录列表判断）
+ * @param options - 移动选项（文件被占用时的重试设置）
  */
-async function moveFile(src: string, dst: string, options: ReplaceOptions): Promise<void> {
-  const action = await getActionForPath(options, src);
-
+async function moveFile(
+  src: string,
+  dst: string,
+  action: ReplaceAction,
+  dstExists: boolean,
+  options: MoveOptions
+): Promise<MoveOutcome> {
   switch (action) {
     case ReplaceAction.Replace:
-      await rename(src, dst);
-      break;
+      await renameWithRetry(src, dst, options);
+      return 'moved';
 
     case ReplaceAction.Skip:
-      if (await exists(dst)) {
-        return; // 跳过
+      if (dstExists) {
+        return 'skipped'; // 跳过
       }
-      await rename(src, dst);
-      break;
+      await renameWithRetry(src, dst, options);
+      return 'moved';
 
     case ReplaceAction.Rename:
-      await moveFileRename(src, dst);
-      break;
+      if (!dstExists) {
+        await renameWithRetry(src, dst, options);
+        return 'moved';
+      }
+      return moveFileRename(src, dst, options);
 
     case ReplaceAction.CheckReplace:
-      if (await exists(dst)) {
+      if (dstExists) {
         const same = await isFileSameContent(src, dst);
         if (same) {
           // 内容相同，直接覆盖
-          await rename(src, dst);
-        } else {
-          // 内容不同，重命名
-          await moveFileRename(src, dst);
+          await renameWithRetry(src, dst, options);
+          return 'skipped';
         }
-      } else {
-        await rename(src, dst);
+        // 内容不同，重命名
+        return moveFileRename(src, dst, options);
       }
-      break;
+      await renameWithRetry(src, dst, options);
+      return 'moved';
   }
 }
 
 /**
  * 重命名移动文件（带重试）
  */
-async function moveFileRename(src: string, dstDir: string): Promise<void> {
+async function moveFileRename(
+  src: string,
+  dstDir: string,
+  options: MoveOptions
+): Promise<MoveOutcome> {
   const srcFileName = src.split('/').pop() || src.split('\\').pop() || 'file';
 
   const stem = getFileStem(srcFileName);
@@ -350,8 +503,8 @@ async function moveFileRename(src: string, dstDir: string): Promise<void> {
     const dst = `${dstDir.split('/').slice(0, -1).join('/')}/${newName}`;
 
     if (!(await exists(dst))) {
-      await rename(src, dst);
-      return;
+      await renameWithRetry(src, dst, options);
+      return count === 1 ? 'moved' : 'renamed';
     }
 
     // 检查内容是否相同
@@ -359,7 +512,7 @@ async function moveFileRename(src: string, dstDir: string): Promise<void> {
     if (same) {
       // 文件已存在且内容相同，删除源文件
       await remove(src);
-      return;
+      return 'skipped';
     }
 
     if (count > 1000) {
@@ -0,0 +1,188 @@
+/**
+ * 打开中的文件检测
+ *
+ * 作品文件夹在编辑器（如 iBMSC）中打开时重命名或移动它，编辑器保存后谱面会写到错误的位置或损坏。
+ * 危险操作执行前检查目录中是否有被其他程序打开的文件：Unix 上使用 lsof，
+ * Windows 上尝试以独占共享模式打开谱面文件（被其他程序打开时会失败）。按设置的策略警告或中止。
+ * 检查需要递归扫描命令操作的整个目录（通常是根目录），耗时较长，默认不检查
+ */
+
+import { CHART_FILE_EXTS } from '../bms/scanner';
+import { ProcessRunner } from '../media/processRunner';
+
+/**
+ * 发现打开中的文件时的处理策略
+ */
+export type OpenFilePolicy = 'ignore' | 'warn' | 'abort';
+
+/**
+ * 被其他程序打开的文件
+ */
+export interface OpenFileInfo {
+  path: string;
+  /** 打开该文件的程序（Windows 上无法得知） */
+  process?: string;
+  pid?: number;
+}
+
+/**
+ * 目录中有文件被其他程序打开
+ */
+export class OpenFilesError extends Error {
+  constructor(
+    readonly path: string,
+    readonly files: OpenFileInfo[]
+  ) {
+    const names = files.slice(0, 3).map((file) => file.path);
+    super(`${files.length} 个文件正被其他程序打开（${names.join(', ')}），请先关闭：${path}`);
+    this.name = 'OpenFilesError';
+  }
+}
+
+/**
+ * lsof 中不影响修改的描述符：当前目录、根目录、程序本身
+ */
+const IGNORED_LSOF_FDS = new Set(['cwd', 'rtd', 'txt']);
+
+/**
+ * 扫描打开中文件的超时（毫秒），扫描整个曲库时可能较慢
+ */
+const OPEN_FILES_TIMEOUT_MS = 5 * 60_000;
+
+function isWindows(): boolean {
+  return navigator.platform?.toLowerCase().includes('win') ?? false;
+}
+
+/**
+ * 使用 lsof 列出目录下被打开的文件
+ */
+async function findOpenFilesWithLsof(dirPath: string): Promise<OpenFileInfo[]> {
+  // lsof 在没有找到文件时返回 1
+  const result = await ProcessRunner.exec('lsof', ['-F', 'pcfn', '+D', dirPath], {
+    timeout: OPEN_FILES_TIMEOUT_MS,
+  });
+  if (result.timedOut || (result.exitCode !== 0 && result.exitCode !== 1)) {
+    throw new Error(`lsof failed: ${result.stderr}`);
+  }
+
+  const files: OpenFileInfo[] = [];
+  let pid: number | undefined;
+  let process: string | undefined;
+  let fd = '';
+  for (const line of result.stdout.split('\n')) {
+    const value = line.slice(1);
+    switch (line[0]) {
+      case 'p':
+        pid = parseInt(value, 10);
+        break;
+      case 'c':
+        process = value;
+        break;
+      case 'f':
+        fd = value;
+        break;
+      case 'n':
+        if (!IGNORED_LSOF_FDS.has(fd)) {
+          files.push({ path: value, process, pid });
+        }
+        break;
+    }
+  }
+  return files;
+}
+
+/**
+ * 在 Windows 上以独占共享模式尝试打开目录下的谱面文件，返回无法打开的文件
+ */
+async function findOpenFilesWithShareMode(dirPath: string): Promise<OpenFileInfo[]> {
+  const escapedPath = dirPath.replace(/\//g, '\\').replace(/'/g, "''");
+  const include = CHART_FILE_EXTS.map((ext) => `*.${ext}`).join(',');
+  const script =
+    `Get-ChildItem -LiteralPath '${escapedPath}' -Recurse -File -Include ${include} | ` +
+    'ForEach-Object { $f = $_.FullName; ' +
+    "try { [System.IO.File]::Open($f, 'Open', 'ReadWrite', 'None').Close() } catch { $f } }";
+  const result = await ProcessRunner.exec('powershell', ['-NoProfile', '-Command', script], {
+    timeout: OPEN_FILES_TIMEOUT_MS,
+  });
+  if (!result.success) {
+    throw new Error(`powershell failed: ${result.stderr}`);
+  }
+  return result.stdout
+    .split(/\r?\n/)
+    .map((line) => line.trim())
+    .filter((line) => line.length > 0)
+    .map((path) => ({ path }));
+}
+
+/**
+ * 列出目录中被其他程序打开的文件
+ *
+ * @command
+ * @category bmsfolder
+ * @dangerous false
+ * @name 检查打开中的文件
+ * @description 列出目录中正被其他程序（如谱面编辑器）打开的文件；Unix 上使用 lsof，Windows 上检查谱面文件能否独占打开
+ * @frontend true
+ *
+ * @param {string} dirPath - 目录路径
+ *
+ * @returns {Promise<OpenFileInfo[]>} 被打开的文件
+ */
+export async function findOpenFiles(dirPath: string): Promise<OpenFileInfo[]> {
+  const files = isWindows()
+    ? await findOpenFilesWithShareMode(dirPath)
+    : await findOpenFilesWithLsof(dirPath);
+  for (const file of files) {
+    const owner = file.process ? ` (${file.process}, pid ${file.pid})` : '';
+    console.log(`Open: ${file.path}${owner}`);
+  }
+  console.log(`${files.length} open files in ${dirPath}`);
+  return files;
+}
+
+/**
+ * 危险操作前的打开文件检查
+ */
+export class OpenFileGuard {
+  private static policy: OpenFilePolicy = 'ignore';
+
+  static setPolicy(policy: OpenFilePolicy): void {
+    this.policy = policy;
+  }
+
+  static getPolicy(): OpenFilePolicy {
+    return this.policy;
+  }
+
+  /**
+   * 检查所有目录，按策略输出警告或抛出 OpenFilesError
+   *
+   * 检测工具不可用（如没有安装 lsof）时只输出警告，不影响操作
+   */
+  static async check(dirPaths: string[]): Promise<void> {
+    if (this.policy === 'ignore') {
+      return;
+    }
+
+    for (const dirPath of dirPaths) {
+      let files: OpenFileInfo[];
+      try {
+        files = isWindows()
+          ? await findOpenFilesWithShareMode(dirPath)
+          : await findOpenFilesWithLsof(dirPath);
+      } catch (error) {
+        console.warn(` !_! Failed to check open files in ${dirPath}:`, error);
+        continue;
+      }
+      if (files.length === 0) {
+        continue;
+      }
+
+      const error = new OpenFilesError(dirPath, files);
+      if (this.policy === 'abort') {
+        throw error;
+      }
+      console.warn(` !_! ${error.message}`);
+    }
+  }
+}
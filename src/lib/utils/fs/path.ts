@@ -95,6 +95,23 @@ export function getRelativePath(from: string, to: string): string {
   return relativeParts.join('/');
 }
 
+/**
+ * 将压缩包条目路径转换为安全的相对路径
+ *
+ * 绝对路径或包含 ".." 的路径可能写到解压目录之外，返回 null
+ */
+export function getSafeRelativePath(entryPath: string): string | null {
+  const normalized = normalizePath(entryPath);
+  if (isAbsolutePath(normalized)) {
+    return null;
+  }
+  const parts = normalized.split('/').filter((part) => part !== '' && part !== '.');
+  if (parts.length === 0 || parts.includes('..')) {
+    return null;
+  }
+  return parts.join('/');
+}
+
 /**
  * 验证文件名（移除非法字符）
  */
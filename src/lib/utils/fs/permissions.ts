@@ -0,0 +1,118 @@
+/**
+ * 解压后的权限整理
+ *
+ * 7z 和 unrar 会保留压缩包中记录的属性：Windows 上常见只读属性，之后移动或覆盖文件时失败；
+ * Unix 上常见多余的可执行位，或缺少写权限的目录。解压后统一整理为普通权限
+ */
+
+import { Command } from '@tauri-apps/plugin-shell';
+
+/**
+ * 权限整理设置
+ */
+export interface PermissionSettings {
+  /** 解压后是否自动整理 */
+  enabled: boolean;
+  /** Windows：清除只读属性 */
+  clearReadOnly: boolean;
+  /** Unix：目录权限（八进制字符串） */
+  dirMode: string;
+  /** Unix：文件权限（八进制字符串） */
+  fileMode: string;
+}
+
+export const DEFAULT_PERMISSION_SETTINGS: PermissionSettings = {
+  enabled: true,
+  clearReadOnly: true,
+  dirMode: '755',
+  fileMode: '644',
+};
+
+function isWindows(): boolean {
+  return navigator.platform?.toLowerCase().includes('win') ?? false;
+}
+
+/**
+ * 权限整理
+ */
+export class PermissionNormalizer {
+  private static settings: PermissionSettings = { ...DEFAULT_PERMISSION_SETTINGS };
+
+  static setSettings(settings: Partial<PermissionSettings>): void {
+    const next = { ...this.settings, ...settings };
+    for (const mode of [next.dirMode, next.fileMode]) {
+      if (!/^[0-7]{3,4}$/.test(mode)) {
+        throw new Error(`无效的权限：${mode}（应为 755 这样的八进制数）`);
+      }
+    }
+    this.settings = next;
+  }
+
+  static getSettings(): PermissionSettings {
+    return { ...this.settings };
+  }
+
+  /**
+   * 解压后调用：未启用时不做任何事，失败时只输出警告
+   */
+  static async afterExtract(dir: string): Promise<void> {
+    if (!this.settings.enabled) {
+      return;
+    }
+    try {
+      await this.normalize(dir);
+    } catch (error) {
+      console.warn(` !_! Failed to normalize permissions in ${dir}:`, error);
+    }
+  }
+
+  /**
+   * 递归整理目录中所有文件和子目录的权限
+   */
+  static async normalize(dir: string): Promise<void> {
+    if (isWindows()) {
+      if (!this.settings.clearReadOnly) {
+        return;
+      }
+      const windowsDir = dir.replace(/\//g, '\\');
+      for (const args of [['-R', windowsDir], ['-R', `${windowsDir}\\*`, '/S', '/D']]) {
+        const result = await Command.create('attrib', args).execute();
+        if (result.code !== 0) {
+          throw new Error(`attrib failed: ${result.stderr || result.stdout}`);
+        }
+      }
+      return;
+    }
+
+    const { dirMode, fileMode } = this.settings;
+    for (const [type, mode] of [
+      ['d', dirMode],
+      ['f', fileMode],
+    ]) {
+      const args = [dir, '-type', type, '-exec', 'chmod', mode, '{}', '+'];
+      const result = await Command.create('find', args).execute();
+      if (result.code !== 0) {
+        throw new Error(`chmod failed: ${result.stderr}`);
+      }
+    }
+  }
+}
+
+/**
+ * 整理目录权限
+ *
+ * @command
+ * @category bmsfolder
+ * @dangerous true
+ * @name 整理文件权限
+ * @description 递归整理目录中的文件权限：Windows 上清除只读属性，其他系统上将目录设为 755、文件设为 644（可在设置中修改），解决解压后无法移动或覆盖的问题
+ * @frontend true
+ *
+ * @param {string} dir - 目录路径
+ *
+ * @returns {Promise<void>}
+ */
+export async function normalizePermissions(dir: string): Promise<void> {
+  await PermissionNormalizer.normalize(dir);
+  console.log(`Normalized permissions in ${dir}`);
+}
@@ -0,0 +1,80 @@
+/**
+ * SHA-256 / SHA-512 测试
+ *
+ * 标准测试向量（FIPS 180-4 示例），以及分块输入与一次性输入的结果一致
+ */
+
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+import { bytesToHex } from './hash';
+import { Sha256Hasher, Sha512Hasher } from './sha2';
+
+const encoder = new TextEncoder();
+
+function digest(hasher: Sha256Hasher | Sha512Hasher, ...chunks: Uint8Array[]): string {
+  for (const chunk of chunks) {
+    hasher.update(chunk);
+  }
+  return bytesToHex(hasher.finalize());
+}
+
+const VECTORS: { input: Uint8Array; sha256: string; sha512: string }[] = [
+  {
+    input: new Uint8Array(0),
+    sha256: 'e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855',
+    sha512:
+      'cf83e1357eefb8bdf1542850d66d8007d620e4050b5715dc83f4a921d36ce9ce' +
+      '47d0d13c5d85f2b0ff8318d2877eec2f63b931bd47417a81a538327af927da3e',
+  },
+  {
+    input: encoder.encode('abc'),
+    sha256: 'ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad',
+    sha512:
+      'ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a' +
+      '2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f',
+  },
+  {
+    // 56 字节：填充后需要额外一个块（SHA-256）
+    input: encoder.encode('abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq'),
+    sha256: '248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1',
+    sha512:
+      '204a8fc6dda82f0a0ced7beb8e08a41657c16ef468b228a8279be331a703c335' +
+      '96fd15c13b1b07f9aa1d3bea57789ca031ad85c7a71dd70354ec631238ca3445',
+  },
+  {
+    input: new Uint8Array(1_000_000).fill(0x61),
+    sha256: 'cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0',
+    sha512:
+      'e718483d0ce769644e2e42c7bc15b4638e1f98b13b2044285632a803afa973eb' +
+      'de0ff244877ea60a4cb0432ce577c31beb009c5c2c49aa2e4eadb217ad8cc09b',
+  },
+];
+
+test('SHA-256 matches the standard test vectors', () => {
+  for (const { input, sha256 } of VECTORS) {
+    assert.equal(digest(new Sha256Hasher(), input), sha256);
+  }
+});
+
+test('SHA-512 matches the standard test vectors', () => {
+  for (const { input, sha512 } of VECTORS) {
+    assert.equal(digest(new Sha512Hasher(), input), sha512);
+  }
+});
+
+test('chunked input gives the same digest around block and padding boundaries', () => {
+  const data = Uint8Array.from({ length: 1000 }, (_, i) => (i * 31) & 0xff);
+  for (const length of [1, 55, 56, 63, 64, 65, 111, 112, 119, 127, 128, 129, 1000]) {
+    const input = data.subarray(0, length);
+    for (const createHasher of [() => new Sha256Hasher(), () => new Sha512Hasher()]) {
+      const expected = digest(createHasher(), input);
+      for (const chunkSize of [1, 7, 64, 100]) {
+        const chunks: Uint8Array[] = [];
+        for (let i = 0; i < length; i += chunkSize) {
+          chunks.push(input.subarray(i, i + chunkSize));
+        }
+        assert.equal(digest(createHasher(), ...chunks), expected, `${length}/${chunkSize}`);
+      }
+    }
+  }
+});
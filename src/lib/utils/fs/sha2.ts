@@ -0,0 +1,308 @@
+/**
+ * SHA-256 / SHA-512 哈希实现
+ *
+ * 纯 TypeScript 实现（FIPS 180-4），支持增量计算。
+ * 用于大文件：Web Crypto 只能对完整数据计算摘要，需要把整个文件读入内存
+ */
+
+const K256 = new Uint32Array([
+  0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+  0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+  0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+  0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+  0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+  0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+  0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+  0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+]);
+
+const IV256 = new Uint32Array([
+  0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+]);
+
+/**
+ * SHA-512 的 64 位常量，按 [高 32 位, 低 32 位] 交替存放
+ */
+const K512 = new Uint32Array([
+  0x428a2f98, 0xd728ae22, 0x71374491, 0x23ef65cd, 0xb5c0fbcf, 0xec4d3b2f, 0xe9b5dba5, 0x8189dbbc,
+  0x3956c25b, 0xf348b538, 0x59f111f1, 0xb605d019, 0x923f82a4, 0xaf194f9b, 0xab1c5ed5, 0xda6d8118,
+  0xd807aa98, 0xa3030242, 0x12835b01, 0x45706fbe, 0x243185be, 0x4ee4b28c, 0x550c7dc3, 0xd5ffb4e2,
+  0x72be5d74, 0xf27b896f, 0x80deb1fe, 0x3b1696b1, 0x9bdc06a7, 0x25c71235, 0xc19bf174, 0xcf692694,
+  0xe49b69c1, 0x9ef14ad2, 0xefbe4786, 0x384f25e3, 0x0fc19dc6, 0x8b8cd5b5, 0x240ca1cc, 0x77ac9c65,
+  0x2de92c6f, 0x592b0275, 0x4a7484aa, 0x6ea6e483, 0x5cb0a9dc, 0xbd41fbd4, 0x76f988da, 0x831153b5,
+  0x983e5152, 0xee66dfab, 0xa831c66d, 0x2db43210, 0xb00327c8, 0x98fb213f, 0xbf597fc7, 0xbeef0ee4,
+  0xc6e00bf3, 0x3da88fc2, 0xd5a79147, 0x930aa725, 0x06ca6351, 0xe003826f, 0x14292967, 0x0a0e6e70,
+  0x27b70a85, 0x46d22ffc, 0x2e1b2138, 0x5c26c926, 0x4d2c6dfc, 0x5ac42aed, 0x53380d13, 0x9d95b3df,
+  0x650a7354, 0x8baf63de, 0x766a0abb, 0x3c77b2a8, 0x81c2c92e, 0x47edaee6, 0x92722c85, 0x1482353b,
+  0xa2bfe8a1, 0x4cf10364, 0xa81a664b, 0xbc423001, 0xc24b8b70, 0xd0f89791, 0xc76c51a3, 0x0654be30,
+  0xd192e819, 0xd6ef5218, 0xd6990624, 0x5565a910, 0xf40e3585, 0x5771202a, 0x106aa070, 0x32bbd1b8,
+  0x19a4c116, 0xb8d2d0c8, 0x1e376c08, 0x5141ab53, 0x2748774c, 0xdf8eeb99, 0x34b0bcb5, 0xe19b48a8,
+  0x391c0cb3, 0xc5c95a63, 0x4ed8aa4a, 0xe3418acb, 0x5b9cca4f, 0x7763e373, 0x682e6ff3, 0xd6b2b8a3,
+  0x748f82ee, 0x5defb2fc, 0x78a5636f, 0x43172f60, 0x84c87814, 0xa1f0ab72, 0x8cc70208, 0x1a6439ec,
+  0x90befffa, 0x23631e28, 0xa4506ceb, 0xde82bde9, 0xbef9a3f7, 0xb2c67915, 0xc67178f2, 0xe372532b,
+  0xca273ece, 0xea26619c, 0xd186b8c7, 0x21c0c207, 0xeada7dd6, 0xcde0eb1e, 0xf57d4f7f, 0xee6ed178,
+  0x06f067aa, 0x72176fba, 0x0a637dc5, 0xa2c898a6, 0x113f9804, 0xbef90dae, 0x1b710b35, 0x131c471b,
+  0x28db77f5, 0x23047d84, 0x32caab7b, 0x40c72493, 0x3c9ebe0a, 0x15c9bebc, 0x431d67c4, 0x9c100d4c,
+  0x4cc5d4be, 0xcb3e42b6, 0x597f299c, 0xfc657e2a, 0x5fcb6fab, 0x3ad6faec, 0x6c44198c, 0x4a475817,
+]);
+
+const IV512 = new Uint32Array([
+  0x6a09e667, 0xf3bcc908, 0xbb67ae85, 0x84caa73b, 0x3c6ef372, 0xfe94f82b, 0xa54ff53a, 0x5f1d36f1,
+  0x510e527f, 0xade682d1, 0x9b05688c, 0x2b3e6c1f, 0x1f83d9ab, 0xfb41bd6b, 0x5be0cd19, 0x137e2179,
+]);
+
+/**
+ * 分块累积数据，满一块时调用 processBlock
+ */
+abstract class BlockHasher {
+  private readonly buffer: Uint8Array;
+  private bufferLength = 0;
+  private totalLength = 0;
+
+  constructor(private readonly blockLen: number) {
+    this.buffer = new Uint8Array(blockLen);
+  }
+
+  protected abstract processBlock(data: Uint8Array, offset: number): void;
+
+  protected abstract output(): Uint8Array;
+
+  /**
+   * 追加数据
+   */
+  update(input: Uint8Array): void {
+    this.totalLength += input.length;
+    let offset = 0;
+
+    if (this.bufferLength > 0) {
+      const take = Math.min(this.blockLen - this.bufferLength, input.length);
+      this.buffer.set(input.subarray(0, take), this.bufferLength);
+      this.bufferLength += take;
+      offset = take;
+      if (this.bufferLength < this.blockLen) {
+        return;
+      }
+      this.processBlock(this.buffer, 0);
+      this.bufferLength = 0;
+    }
+
+    for (; offset + this.blockLen <= input.length; offset += this.blockLen) {
+      this.processBlock(input, offset);
+    }
+
+    this.buffer.set(input.subarray(offset), 0);
+    this.bufferLength = input.length - offset;
+  }
+
+  /**
+   * 填充并输出摘要（之后不能继续使用）
+   */
+  finalize(): Uint8Array {
+    // 长度字段占块的最后 1/8（SHA-256 为 8 字节，SHA-512 为 16 字节），只写入低 64 位
+    const lengthStart = this.blockLen - this.blockLen / 8;
+    const lengthOffset = this.blockLen - 8;
+    const bitsHigh = Math.floor(this.totalLength / 0x20000000);
+    const bitsLow = (this.totalLength * 8) >>> 0;
+
+    this.buffer[this.bufferLength++] = 0x80;
+    if (this.bufferLength > lengthStart) {
+      this.buffer.fill(0, this.bufferLength);
+      this.processBlock(this.buffer, 0);
+      this.bufferLength = 0;
+    }
+    this.buffer.fill(0, this.bufferLength);
+    const view = new DataView(this.buffer.buffer, this.buffer.byteOffset, this.blockLen);
+    view.setUint32(lengthOffset, bitsHigh);
+    view.setUint32(lengthOffset + 4, bitsLow);
+    this.processBlock(this.buffer, 0);
+
+    return this.output();
+  }
+}
+
+function rotr(x: number, n: number): number {
+  return (x >>> n) | (x << (32 - n));
+}
+
+/**
+ * 增量 SHA-256 哈希计算器
+ */
+export class Sha256Hasher extends BlockHasher {
+  private readonly state = new Uint32Array(IV256);
+  private readonly w = new Uint32Array(64);
+
+  constructor() {
+    super(64);
+  }
+
+  protected processBlock(data: Uint8Array, offset: number): void {
+    const w = this.w;
+    for (let t = 0; t < 16; t++) {
+      const i = offset + t * 4;
+      w[t] = (data[i] << 24) | (data[i + 1] << 16) | (data[i + 2] << 8) | data[i + 3];
+    }
+    for (let t = 16; t < 64; t++) {
+      const w15 = w[t - 15];
+      const w2 = w[t - 2];
+      const s0 = rotr(w15, 7) ^ rotr(w15, 18) ^ (w15 >>> 3);
+      const s1 = rotr(w2, 17) ^ rotr(w2, 19) ^ (w2 >>> 10);
+      w[t] = w[t - 16] + s0 + w[t - 7] + s1;
+    }
+
+    const state = this.state;
+    let a = state[0];
+    let b = state[1];
+    let c = state[2];
+    let d = state[3];
+    let e = state[4];
+    let f = state[5];
+    let g = state[6];
+    let h = state[7];
+    for (let t = 0; t < 64; t++) {
+      const s1 = rotr(e, 6) ^ rotr(e, 11) ^ rotr(e, 25);
+      const ch = (e & f) ^ (~e & g);
+      const t1 = (h + s1 + ch + K256[t] + w[t]) | 0;
+      const s0 = rotr(a, 2) ^ rotr(a, 13) ^ rotr(a, 22);
+      const maj = (a & b) ^ (a & c) ^ (b & c);
+      const t2 = (s0 + maj) | 0;
+      h = g;
+      g = f;
+      f = e;
+      e = (d + t1) | 0;
+      d = c;
+      c = b;
+      b = a;
+      a = (t1 + t2) | 0;
+    }
+    state[0] += a;
+    state[1] += b;
+    state[2] += c;
+    state[3] += d;
+    state[4] += e;
+    state[5] += f;
+    state[6] += g;
+    state[7] += h;
+  }
+
+  protected output(): Uint8Array {
+    const out = new Uint8Array(32);
+    const view = new DataView(out.buffer);
+    this.state.forEach((word, i) => view.setUint32(i * 4, word));
+    return out;
+  }
+}
+
+/*
+ * SHA-512 的 64 位运算以 (高 32 位, 低 32 位) 两个数表示：
+ * 循环右移展开为两半的移位，加法时低位按无符号数相加，进位加到高位
+ */
+
+const CARRY = 0x100000000;
+
+/**
+ * 增量 SHA-512 哈希计算器
+ */
+export class Sha512Hasher extends BlockHasher {
+  /** 状态，按 [高 32 位, 低 32 位] 交替存放 */
+  private readonly state = new Uint32Array(IV512);
+  private readonly wh = new Uint32Array(80);
+  private readonly wl = new Uint32Array(80);
+
+  constructor() {
+    super(128);
+  }
+
+  protected processBlock(data: Uint8Array, offset: number): void {
+    const { wh, wl } = this;
+    for (let t = 0; t < 16; t++) {
+      const i = offset + t * 8;
+      wh[t] = (data[i] << 24) | (data[i + 1] << 16) | (data[i + 2] << 8) | data[i + 3];
+      wl[t] = (data[i + 4] << 24) | (data[i + 5] << 16) | (data[i + 6] << 8) | data[i + 7];
+    }
+    for (let t = 16; t < 80; t++) {
+      const h15 = wh[t - 15];
+      const l15 = wl[t - 15];
+      const h2 = wh[t - 2];
+      const l2 = wl[t - 2];
+      const s0h = ((h15 >>> 1) | (l15 << 31)) ^ ((h15 >>> 8) | (l15 << 24)) ^ (h15 >>> 7);
+      const s0l =
+        ((l15 >>> 1) | (h15 << 31)) ^ ((l15 >>> 8) | (h15 << 24)) ^ ((l15 >>> 7) | (h15 << 25));
+      const s1h = ((h2 >>> 19) | (l2 << 13)) ^ ((l2 >>> 29) | (h2 << 3)) ^ (h2 >>> 6);
+      const s1l =
+        ((l2 >>> 19) | (h2 << 13)) ^ ((h2 >>> 29) | (l2 << 3)) ^ ((l2 >>> 6) | (h2 << 26));
+      const low = (s1l >>> 0) + wl[t - 7] + (s0l >>> 0) + wl[t - 16];
+      wl[t] = low;
+      wh[t] = s1h + wh[t - 7] + s0h + wh[t - 16] + Math.floor(low / CARRY);
+    }
+
+    const state = this.state;
+    let ah = state[0];
+    let al = state[1];
+    let bh = state[2];
+    let bl = state[3];
+    let ch = state[4];
+    let cl = state[5];
+    let dh = state[6];
+    let dl = state[7];
+    let eh = state[8];
+    let el = state[9];
+    let fh = state[10];
+    let fl = state[11];
+    let gh = state[12];
+    let gl = state[13];
+    let hh = state[14];
+    let hl = state[15];
+    for (let t = 0; t < 80; t++) {
+      const s1h =
+        ((eh >>> 14) | (el << 18)) ^ ((eh >>> 18) | (el << 14)) ^ ((el >>> 9) | (eh << 23));
+      const s1l =
+        ((el >>> 14) | (eh << 18)) ^ ((el >>> 18) | (eh << 14)) ^ ((eh >>> 9) | (el << 23));
+      const chh = (eh & fh) ^ (~eh & gh);
+      const chl = (el & fl) ^ (~el & gl);
+      const t1Low = hl + (s1l >>> 0) + (chl >>> 0) + K512[t * 2 + 1] + wl[t];
+      const t1l = t1Low >>> 0;
+      const t1h = (hh + s1h + chh + K512[t * 2] + wh[t] + Math.floor(t1Low / CARRY)) | 0;
+
+      const s0h = ((ah >>> 28) | (al << 4)) ^ ((al >>> 2) | (ah << 30)) ^ ((al >>> 7) | (ah << 25));
+      const s0l = ((al >>> 28) | (ah << 4)) ^ ((ah >>> 2) | (al << 30)) ^ ((ah >>> 7) | (al << 25));
+      const majh = (ah & bh) ^ (ah & ch) ^ (bh & ch);
+      const majl = (al & bl) ^ (al & cl) ^ (bl & cl);
+      const t2Low = (s0l >>> 0) + (majl >>> 0);
+      const t2l = t2Low >>> 0;
+      const t2h = (s0h + majh + Math.floor(t2Low / CARRY)) | 0;
+
+      hh = gh;
+      hl = gl;
+      gh = fh;
+      gl = fl;
+      fh = eh;
+      fl = el;
+      const eLow = dl + t1l;
+      eh = (dh + t1h + Math.floor(eLow / CARRY)) | 0;
+      el = eLow >>> 0;
+      dh = ch;
+      dl = cl;
+      ch = bh;
+      cl = bl;
+      bh = ah;
+      bl = al;
+      const aLow = t1l + t2l;
+      ah = (t1h + t2h + Math.floor(aLow / CARRY)) | 0;
+      al = aLow >>> 0;
+    }
+
+    const words = [ah, al, bh, bl, ch, cl, dh, dl, eh, el, fh, fl, gh, gl, hh, hl];
+    for (let i = 0; i < 16; i += 2) {
+      const low = state[i + 1] + (words[i + 1] >>> 0);
+      state[i + 1] = low;
+      state[i] = state[i] + words[i] + Math.floor(low / CARRY);
+    }
+  }
+
+  protected output(): Uint8Array {
+    const out = new Uint8Array(64);
+    const view = new DataView(out.buffer);
+    this.state.forEach((word, i) => view.setUint32(i * 4, word));
+    return out;
+  }
+}
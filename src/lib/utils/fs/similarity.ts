@@ -5,6 +5,7 @@
 
 import { readDir } from '@tauri-apps/plugin-fs';
 import { getFileExtension, getFileStem } from './path';
+import { SimilarityMetric } from '../../types/enums';
 
 /**
  * 媒体文件扩展名列表
@@ -198,3 +199,100 @@ export function calculateSimilarity(s1: string, s2: string): number {
   const maxLength = Math.max(len1, len2);
   return maxLength === 0 ? 1 : lcsLength / maxLength;
 }
+
+/**
+ * 综合相似度中各算法的权重
+ */
+export interface SimilarityWeights {
+  levenshtein: number;
+  tokenSet: number;
+  lcs: number;
+}
+
+/**
+ * 默认权重：词集合比较对 "(Remix) Song" 这类词序不同的重复作品最有效，权重最高
+ */
+export const DEFAULT_SIMILARITY_WEIGHTS: SimilarityWeights = {
+  levenshtein: 0.3,
+  tokenSet: 0.5,
+  lcs: 0.2,
+};
+
+/**
+ * 拆分为小写的词（括号、标点和空白都作为分隔符）
+ */
+function tokenize(str: string): string[] {
+  return str
+    .toLowerCase()
+    .split(/[^\p{L}\p{N}]+/u)
+    .filter((token) => token.length > 0);
+}
+
+/**
+ * 词集合相似度（token set ratio）
+ *
+ * 两边的词去重后分为共有部分和各自独有的部分，分别比较 "共有"、"共有 + A 独有"、"共有 + B 独有"
+ * 三个排序后的字符串，取最高的编辑距离相似度。词序不同或一边多出修饰词时仍能得到高分
+ *
+ * @param str1 - 第一个字符串
+ * @param str2 - 第二个字符串
+ * @returns 相似度 (0-1)
+ */
+export function tokenSetSimilarity(str1: string, str2: string): number {
+  const tokens1 = new Set(tokenize(str1));
+  const tokens2 = new Set(tokenize(str2));
+  if (tokens1.size === 0 || tokens2.size === 0) {
+    return stringSimilarity(str1.toLowerCase(), str2.toLowerCase());
+  }
+
+  const common = [...tokens1].filter((token) => tokens2.has(token)).sort();
+  const only1 = [...tokens1].filter((token) => !tokens2.has(token)).sort();
+  const only2 = [...tokens2].filter((token) => !tokens1.has(token)).sort();
+
+  const base = common.join(' ');
+  const combined1 = [...common, ...only1].join(' ');
+  const combined2 = [...common, ...only2].join(' ');
+
+  return Math.max(
+    common.length > 0 ? stringSimilarity(base, combined1) : 0,
+    common.length > 0 ? stringSimilarity(base, combined2) : 0,
+    stringSimilarity(combined1, combined2)
+  );
+}
+
+/**
+ * 按指定算法计算两个名称的相似度
+ *
+ * @param str1 - 第一个字符串
+ * @param str2 - 第二个字符串
+ * @param metric - 相似度算法
+ * @param weights - 综合相似度的权重（只在 Combined 时使用）
+ * @returns 相似度 (0-1)
+ */
+export function nameSimilarity(
+  str1: string,
+  str2: string,
+  metric: SimilarityMetric,
+  weights: SimilarityWeights = DEFAULT_SIMILARITY_WEIGHTS
+): number {
+  switch (metric) {
+    case SimilarityMetric.Levenshtein:
+      return stringSimilarity(str1, str2);
+    case SimilarityMetric.TokenSet:
+      return tokenSetSimilarity(str1, str2);
+    case SimilarityMetric.Lcs:
+      return calculateSimilarity(str1, str2);
+    case SimilarityMetric.Combined: {
+      const total = weights.levenshtein + weights.tokenSet + weights.lcs;
+      if (total <= 0) {
+        return 0;
+      }
+      return (
+        (weights.levenshtein * stringSimilarity(str1, str2) +
+          weights.tokenSet * tokenSetSimilarity(str1, str2) +
+          weights.lcs * calculateSimilarity(str1, str2)) /
+        total
+      );
+    }
+  }
+}
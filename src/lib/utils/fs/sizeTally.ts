@@ -0,0 +1,119 @@
+/**
+ * 模拟运行的大小统计
+ *
+ * 模拟运行时汇总将要移动到各目标的字节数和将要删除的字节数，
+ * 运行结束时打印一份摘要，便于一眼检查拆分或清理计划是否合理
+ */
+
+import { stat } from '@tauri-apps/plugin-fs';
+import { getDirSize } from './cache';
+
+/**
+ * 一组文件的数量和大小
+ */
+export interface SizeBucket {
+  count: number;
+  size: number;
+}
+
+/**
+ * 大小统计摘要
+ */
+export interface SizeTallySummary {
+  /** 目标 -> 移动的数量和大小 */
+  moved: Record<string, SizeBucket>;
+  removed: SizeBucket;
+}
+
+/**
+ * 格式化字节数
+ */
+export function formatSize(bytes: number): string {
+  const units = ['B', 'KB', 'MB', 'GB', 'TB'];
+  let value = bytes;
+  let unit = 0;
+  while (Math.abs(value) >= 1024 && unit < units.length - 1) {
+    value /= 1024;
+    unit++;
+  }
+  return `${unit === 0 ? value : value.toFixed(2)} ${units[unit]}`;
+}
+
+/**
+ * 文件或目录的大小（目录递归计算，读取失败时为 0）
+ */
+export async function getPathSize(path: string): Promise<number> {
+  try {
+    const info = await stat(path);
+    return info.isDirectory ? await getDirSize(path) : info.size;
+  } catch {
+    return 0;
+  }
+}
+
+/**
+ * 模拟运行的大小统计
+ *
+ * @example
+ * ```typescript
+ * const tally = new DryRunSizeTally('Split by first char');
+ * await tally.addMove(`${rootDir}/${name}`, 'ABCD');
+ * tally.print();
+ * ```
+ */
+export class DryRunSizeTally {
+  private readonly moved = new Map<string, SizeBucket>();
+  private readonly removed: SizeBucket = { count: 0, size: 0 };
+
+  constructor(private readonly label: string) {}
+
+  /**
+   * 记录将要移动到目标的文件或目录
+   */
+  async addMove(path: string, target: string): Promise<void> {
+    this.addMoveSize(target, await getPathSize(path));
+  }
+
+  /**
+   * 记录将要移动到目标的字节数（大小已知时使用）
+   */
+  addMoveSize(target: string, size: number): void {
+    const bucket = this.moved.get(target) ?? { count: 0, size: 0 };
+    bucket.count++;
+    bucket.size += size;
+    this.moved.set(target, bucket);
+  }
+
+  /**
+   * 记录将要删除的文件或目录
+   */
+  async addRemove(path: string): Promise<void> {
+    this.addRemoveSize(await getPathSize(path));
+  }
+
+  /**
+   * 记录将要删除的字节数（大小已知时使用）
+   */
+  addRemoveSize(size: number): void {
+    this.removed.count++;
+    this.removed.size += size;
+  }
+
+  summary(): SizeTallySummary {
+    return { moved: Object.fromEntries(this.moved), removed: { ...this.removed } };
+  }
+
+  /**
+   * 打印摘要
+   */
+  print(): void {
+    const totalMoved = [...this.moved.values()].reduce((sum, bucket) => sum + bucket.size, 0);
+    console.log(
+      `[dry-run] ${this.label}: move ${formatSize(totalMoved)}, ` +
+        `delete ${formatSize(this.removed.size)} (${this.removed.count} items)`
+    );
+    for (const [target, bucket] of [...this.moved].sort((a, b) => b[1].size - a[1].size)) {
+      console.log(`[dry-run]   -> ${target}: ${bucket.count} items, ${formatSize(bucket.size)}`);
+    }
+  }
+}
@@ -5,6 +5,7 @@
 import { copyFile, exists, mkdir, readDir, remove, rename, stat } from '@tauri-apps/plugin-fs';
 import { getFileExtension } from './path';
 import { isFileSameContent } from './compare';
+import { IgnoreList } from './ignore';
 
 /**
  * 同步执行类型
@@ -192,7 +193,7 @@ export async function syncFolder(
   const dstMap = new Map<string, (typeof dstEntries)[0]>();
 
   for (const entry of srcEntries) {
-    if (entry.name) srcMap.set(entry.name, { entry });
+    if (entry.name && !IgnoreList.isIgnored(entry.name)) srcMap.set(entry.name, { entry });
   }
 
   for (const entry of dstEntries) {
@@ -0,0 +1,206 @@
+/**
+ * 目标目录检查
+ *
+ * 大包转换等耗时操作常常在几小时后才因为磁盘已满或目标只读而失败。
+ * 开始前检查目标目录能否写入、剩余空间，并按操作类型估算需要的空间是否足够
+ */
+
+import { exists } from '@tauri-apps/plugin-fs';
+import { TargetOperation } from '../../types/enums';
+import { ProcessRunner } from '../media/processRunner';
+import { formatSize, getPathSize } from './sizeTally';
+import { findExistingDir, getMountLabel, isDirWritable } from './writable';
+
+/**
+ * 目标目录检查结果
+ */
+export interface TargetValidation {
+  targetDir: string;
+  operation: TargetOperation;
+  /** 目标目录是否已存在（不存在时检查最近的上级目录） */
+  exists: boolean;
+  writable: boolean;
+  /** 所在的挂载点（Windows 上为驱动器根目录） */
+  mount: string;
+  /** 剩余空间（字节，无法获取时为 null） */
+  freeSpace: number | null;
+  /** 源目录大小（字节，未指定源目录时为 null） */
+  sourceSize: number | null;
+  /** 预计需要的空间（字节） */
+  requiredSpace: number;
+  /** 预计空间是否足够（无法获取剩余空间时为 null） */
+  fits: boolean | null;
+  /** 发现的问题 */
+  problems: string[];
+}
+
+/**
+ * 估算时预留的余量
+ */
+const SPACE_MARGIN = 1.1;
+
+/**
+ * 查询磁盘信息的超时（毫秒）
+ */
+const DISK_INFO_TIMEOUT_MS = 30_000;
+
+function isWindows(): boolean {
+  return navigator.platform?.toLowerCase().includes('win') ?? false;
+}
+
+/**
+ * 目录所在的磁盘
+ */
+interface DiskInfo {
+  /** 挂载点（Windows 上为驱动器根目录） */
+  mount: string;
+  /** 剩余空间（字节） */
+  free: number;
+}
+
+/**
+ * 获取目录所在磁盘的挂载点和剩余空间
+ */
+async function getDiskInfo(dirPath: string): Promise<DiskInfo | null> {
+  try {
+    if (isWindows()) {
+      const escapedPath = dirPath.replace(/\//g, '\\').replace(/'/g, "''");
+      const script =
+        `$d = (Get-Item -LiteralPath '${escapedPath}').PSDrive; ` + '"$($d.Root)|$($d.Free)"';
+      const result = await ProcessRunner.exec('powershell', ['-NoProfile', '-Command', script], {
+        timeout: DISK_INFO_TIMEOUT_MS,
+      });
+      const [mount, free] = result.stdout.trim().split('|');
+      const bytes = parseInt(free ?? '', 10);
+      return result.success && mount && !isNaN(bytes) ? { mount, free: bytes } : null;
+    }
+
+    // POSIX 格式：Filesystem 1024-blocks Used Available Capacity Mounted on
+    const result = await ProcessRunner.exec('df', ['-Pk', dirPath], {
+      timeout: DISK_INFO_TIMEOUT_MS,
+    });
+    const fields = result.stdout.trim().split('\n').pop()?.trim().split(/\s+/) ?? [];
+    const available = parseInt(fields[3] ?? '', 10);
+    // 挂载点可能包含空格
+    const mount = fields.slice(5).join(' ');
+    return result.success && mount && !isNaN(available)
+      ? { mount, free: available * 1024 }
+      : null;
+  } catch (error) {
+    console.warn(` !_! Failed to get disk info of ${dirPath}:`, error);
+    return null;
+  }
+}
+
+/**
+ * 估算操作在目标磁盘上需要的空间
+ *
+ * - 复制：与源目录相同
+ * - 原始包 -> HQ：解压后的 WAV 通常比压缩包大，转换期间 WAV 和 FLAC 同时存在，约为压缩包的 2 倍
+ * - HQ -> LQ：先完整复制再转换，与源目录相同
+ * - 双版本的 LQ 目录：同一磁盘上以硬链接复制，只需要转换输出的空间（约为 HQ 的 40%）；
+ *   不同磁盘上或 Windows 上（不使用硬链接）需要完整复制
+ */
+function estimateRequiredSpace(
+  operation: TargetOperation,
+  sourceSize: number,
+  sameMount: boolean
+): number {
+  switch (operation) {
+    case TargetOperation.Copy:
+    case TargetOperation.HqToLq:
+      return sourceSize;
+    case TargetOperation.RawToHq:
+      return sourceSize * 2;
+    case TargetOperation.DualBuildLq:
+      return sameMount && !isWindows() ? sourceSize * 0.4 : sourceSize;
+    default:
+      return 0;
+  }
+}
+
+/**
+ * 检查目标目录
+ *
+ * @command
+ * @category bmsfolder
+ * @dangerous false
+ * @name 检查目标目录
+ * @description 开始耗时操作前检查目标目录能否写入和剩余空间，并按操作类型（复制、原始包 -> HQ、HQ -> LQ、双版本的 LQ 目录）估算空间是否足够
+ * @frontend true
+ *
+ * @param {string} targetDir - 目标目录路径
+ * @param {TargetOperation} operation - 预计执行的操作
+ * @param {string} sourceDir - 源目录路径（估算需要的空间，可为空）
+ *
+ * @returns {Promise<TargetValidation>} 检查结果
+ */
+export async function validateTarget(
+  targetDir: string,
+  operation: TargetOperation = TargetOperation.None,
+  sourceDir: string = ''
+): Promise<TargetValidation> {
+  const existingDir = await findExistingDir(targetDir);
+  const disk = existingDir ? await getDiskInfo(existingDir) : null;
+  const mount = disk?.mount ?? getMountLabel(targetDir);
+  const result: TargetValidation = {
+    targetDir,
+    operation,
+    exists: await exists(targetDir).catch(() => false),
+    writable: false,
+    mount,
+    freeSpace: disk?.free ?? null,
+    sourceSize: null,
+    requiredSpace: 0,
+    fits: null,
+    problems: [],
+  };
+
+  if (!existingDir) {
+    result.problems.push(`目标目录及其上级目录都不存在：${targetDir}`);
+    return result;
+  }
+
+  result.writable = await isDirWritable(existingDir);
+  if (!result.writable) {
+    result.problems.push(`${mount} 为只读位置，无法写入：${existingDir}`);
+  }
+
+  if (result.freeSpace === null) {
+    result.problems.push('无法获取剩余空间');
+  }
+
+  if (sourceDir && operation !== TargetOperation.None) {
+    if (!(await exists(sourceDir))) {
+      result.problems.push(`源目录不存在：${sourceDir}`);
+    } else {
+      result.sourceSize = await getPathSize(sourceDir);
+      // 无法确定挂载点时按不同磁盘估算（偏多）
+      const sourceDisk = await getDiskInfo(sourceDir);
+      const sameMount = !!disk && !!sourceDisk && sourceDisk.mount === disk.mount;
+      result.requiredSpace = Math.round(
+        estimateRequiredSpace(operation, result.sourceSize, sameMount)
+      );
+    }
+  }
+
+  if (result.freeSpace !== null) {
+    result.fits = result.requiredSpace * SPACE_MARGIN <= result.freeSpace;
+    if (!result.fits) {
+      result.problems.push(
+        `剩余空间不足：预计需要 ${formatSize(result.requiredSpace)}，` +
+          `剩余 ${formatSize(result.freeSpace)}`
+      );
+    }
+  }
+
+  console.log(
+    `${targetDir} (${mount}): ${result.writable ? 'writable' : 'read-only'}, ` +
+      `free ${result.freeSpace === null ? 'unknown' : formatSize(result.freeSpace)}, ` +
+      `required ${formatSize(result.requiredSpace)}`
+  );
+  for (const problem of result.problems) {
+    console.warn(` !_! ${problem}`);
+  }
+  return result;
+}
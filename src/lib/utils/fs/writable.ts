@@ -0,0 +1,93 @@
+/**
+ * 只读位置检测
+ *
+ * 在只读挂载（光盘镜像、锁定的网络共享等）上执行修改操作时，每个文件都会单独失败。
+ * 危险操作执行前先探测目标目录能否写入，不能写入时直接以一条错误中止
+ */
+
+import { exists, remove, writeFile } from '@tauri-apps/plugin-fs';
+import { getDirectoryPath } from './path';
+
+/**
+ * 目标位于只读位置
+ */
+export class ReadOnlyMountError extends Error {
+  constructor(
+    readonly path: string,
+    readonly mount: string
+  ) {
+    super(`${mount} 为只读位置，无法修改：${path}`);
+    this.name = 'ReadOnlyMountError';
+  }
+}
+
+/**
+ * 获取路径所在的挂载位置（用于错误提示）
+ *
+ * Windows 使用盘符或 UNC 共享，macOS 使用 /Volumes/<名称>，Linux 使用 /media、/mnt 下的挂载点
+ */
+export function getMountLabel(path: string): string {
+  const normalized = path.replace(/\\/g, '/');
+
+  const drive = normalized.match(/^([A-Za-z]:)/);
+  if (drive) {
+    return drive[1].toUpperCase();
+  }
+
+  const unc = normalized.match(/^\/\/[^/]+\/[^/]+/);
+  if (unc) {
+    return unc[0];
+  }
+
+  const mount =
+    normalized.match(/^\/(?:media|run\/media)\/[^/]+\/[^/]+/) ??
+    normalized.match(/^\/(?:Volumes|mnt)\/[^/]+/);
+  return mount ? mount[0] : '/';
+}
+
+/**
+ * 获取路径本身或最近的已存在的上级目录（都不存在时返回 null）
+ */
+export async function findExistingDir(dirPath: string): Promise<string | null> {
+  let dir = dirPath.replace(/[\\/]+$/, '');
+  while (dir && !(await exists(dir))) {
+    const parent = getDirectoryPath(dir);
+    if (!parent || parent === dir) {
+      return null;
+    }
+    dir = parent;
+  }
+  return dir || null;
+}
+
+/**
+ * 探测目录能否写入（写入并删除一个临时文件）
+ *
+ * 目录不存在时探测最近的已存在的上级目录
+ */
+export async function isDirWritable(dirPath: string): Promise<boolean> {
+  const probeDir = await findExistingDir(dirPath);
+  if (!probeDir) {
+    return true;
+  }
+
+  const probePath = `${probeDir}/.bms-toolbox-write-probe-${Date.now()}`;
+  try {
+    await writeFile(probePath, new Uint8Array());
+  } catch {
+    return false;
+  }
+  await remove(probePath).catch(() => {});
+  return true;
+}
+
+/**
+ * 确认所有目录都可以写入，否则抛出 ReadOnlyMountError
+ */
+export async function ensureDirsWritable(dirPaths: string[]): Promise<void> {
+  for (const dirPath of dirPaths) {
+    if (!(await isDirWritable(dirPath))) {
+      throw new ReadOnlyMountError(dirPath, getMountLabel(dirPath));
+    }
+  }
+}
@@ -0,0 +1,298 @@
+/**
+ * ZipWriter 测试
+ *
+ * 写入内存后按中央目录读回，检查内容、CRC 和 ZIP64 字段；
+ * 超过 4GB 的条目使用重复的零数据块生成，只保留头部记录，不占用内存
+ */
+
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+import { ZipMethod } from './compression';
+import { crc32, SIG_CENTRAL_DIR, SIG_LOCAL_FILE, ZIP64_EXTRA_ID, ZipWriter } from './zip';
+import type { ZipSink } from './zip';
+
+const SIG_END = 0x06054b50;
+const SIG_ZIP64_LOCATOR = 0x07064b50;
+const SIG_ZIP64_END = 0x06064b50;
+const ZIP32_MAX = 0xffffffff;
+
+/**
+ * 可按偏移读取的压缩包内容
+ */
+interface ZipBytes {
+  size: number;
+  readAt(offset: number, length: number): DataView;
+}
+
+/**
+ * 中央目录中的条目
+ */
+interface CentralEntry {
+  name: string;
+  method: number;
+  crc: number;
+  compressedSize: number;
+  uncompressedSize: number;
+  offset: number;
+  /** 是否带 ZIP64 扩展字段 */
+  zip64: boolean;
+}
+
+/**
+ * 保存全部写入数据的输出
+ */
+class MemorySink implements ZipSink, ZipBytes {
+  private chunks: Uint8Array[] = [];
+  private bytes: Uint8Array | null = null;
+  closed = false;
+
+  async write(data: Uint8Array): Promise<void> {
+    this.chunks.push(data.slice());
+    this.bytes = null;
+  }
+
+  async close(): Promise<void> {
+    this.closed = true;
+  }
+
+  get size(): number {
+    return this.chunks.reduce((sum, chunk) => sum + chunk.length, 0);
+  }
+
+  readAt(offset: number, length: number): DataView {
+    if (!this.bytes) {
+      this.bytes = new Uint8Array(this.size);
+      let position = 0;
+      for (const chunk of this.chunks) {
+        this.bytes.set(chunk, position);
+        position += chunk.length;
+      }
+    }
+    return new DataView(this.bytes.buffer, offset, length);
+  }
+
+  data(offset: number, length: number): Uint8Array {
+    const view = this.readAt(offset, length);
+    return new Uint8Array(view.buffer, view.byteOffset, view.byteLength);
+  }
+}
+
+/**
+ * 丢弃指定数据块、只保留其他写入（头部和目录）的输出
+ */
+class SparseSink implements ZipSink, ZipBytes {
+  private segments: { offset: number; data: Uint8Array }[] = [];
+  size = 0;
+
+  constructor(private readonly discarded: Uint8Array) {}
+
+  async write(data: Uint8Array): Promise<void> {
+    if (data !== this.discarded) {
+      this.segments.push({ offset: this.size, data: data.slice() });
+    }
+    this.size += data.length;
+  }
+
+  async close(): Promise<void> {}
+
+  readAt(offset: number, length: number): DataView {
+    const segment = this.segments.find(
+      (s) => offset >= s.offset && offset + length <= s.offset + s.data.length
+    );
+    assert.ok(segment, `no data kept at offset ${offset}`);
+    return new DataView(segment.data.buffer, offset - segment.offset, length);
+  }
+}
+
+function u64(view: DataView, offset: number): number {
+  return view.getUint32(offset, true) + view.getUint32(offset + 4, true) * 0x100000000;
+}
+
+/**
+ * 读取中央目录（ZIP64 时从 ZIP64 结束记录读取位置和条目数）
+ */
+function readCentralDirectory(zip: ZipBytes): CentralEntry[] {
+  const end = zip.readAt(zip.size - 22, 22);
+  assert.equal(end.getUint32(0, true), SIG_END);
+  let count = end.getUint16(10, true);
+  let offset = end.getUint32(16, true);
+
+  if (count === 0xffff || offset === ZIP32_MAX) {
+    const locator = zip.readAt(zip.size - 22 - 20, 20);
+    assert.equal(locator.getUint32(0, true), SIG_ZIP64_LOCATOR);
+    const zip64End = zip.readAt(u64(locator, 8), 56);
+    assert.equal(zip64End.getUint32(0, true), SIG_ZIP64_END);
+    count = u64(zip64End, 32);
+    offset = u64(zip64End, 48);
+  }
+
+  const decoder = new TextDecoder();
+  const entries: CentralEntry[] = [];
+  for (let i = 0; i < count; i++) {
+    const fixed = zip.readAt(offset, 46);
+    assert.equal(fixed.getUint32(0, true), SIG_CENTRAL_DIR);
+    const nameLength = fixed.getUint16(28, true);
+    const extraLength = fixed.getUint16(30, true);
+    const record = zip.readAt(offset, 46 + nameLength + extraLength);
+    const entry: CentralEntry = {
+      name: decoder.decode(new Uint8Array(record.buffer, record.byteOffset + 46, nameLength)),
+      method: record.getUint16(10, true),
+      crc: record.getUint32(16, true),
+      compressedSize: record.getUint32(20, true),
+      uncompressedSize: record.getUint32(24, true),
+      offset: record.getUint32(42, true),
+      zip64: false,
+    };
+
+    // ZIP64 扩展字段按原始大小、压缩后大小、偏移的顺序，只包含 32 位字段已满的值
+    for (let pos = 46 + nameLength; pos < 46 + nameLength + extraLength; ) {
+      const id = record.getUint16(pos, true);
+      const length = record.getUint16(pos + 2, true);
+      if (id === ZIP64_EXTRA_ID) {
+        entry.zip64 = true;
+        let field = pos + 4;
+        for (const key of ['uncompressedSize', 'compressedSize', 'offset'] as const) {
+          if (entry[key] === ZIP32_MAX) {
+            entry[key] = u64(record, field);
+            field += 8;
+          }
+        }
+      }
+      pos += 4 + length;
+    }
+
+    entries.push(entry);
+    offset += 46 + nameLength + extraLength;
+  }
+  return entries;
+}
+
+/**
+ * 读取条目的原始数据（按压缩方式解压）
+ */
+async function readEntryData(zip: MemorySink, entry: CentralEntry): Promise<Uint8Array> {
+  const local = zip.readAt(entry.offset, 30);
+  assert.equal(local.getUint32(0, true), SIG_LOCAL_FILE);
+  const dataStart = entry.offset + 30 + local.getUint16(26, true) + local.getUint16(28, true);
+  const data = zip.data(dataStart, entry.compressedSize);
+  if (entry.method === ZipMethod.Store) {
+    return data;
+  }
+
+  const stream = new Blob([data]).stream().pipeThrough(new DecompressionStream('deflate-raw'));
+  return new Uint8Array(await new Response(stream).arrayBuffer());
+}
+
+async function* chunksOf(...chunks: Uint8Array[]): AsyncGenerator<Uint8Array> {
+  yield* chunks;
+}
+
+test('crc32 matches the standard check value and supports incremental updates', () => {
+  const data = new TextEncoder().encode('123456789');
+  assert.equal(crc32(data), 0xcbf43926);
+  assert.equal(crc32(data.subarray(4), crc32(data.subarray(0, 4))), 0xcbf43926);
+  assert.equal(crc32(new Uint8Array(0)), 0);
+});
+
+test('stored and deflated entries round-trip', async () => {
+  const text = new TextEncoder().encode('#TITLE テスト\r\n'.repeat(1000));
+  const binary = Uint8Array.from({ length: 70_000 }, (_, i) => (i * 7919) & 0xff);
+
+  const sink = new MemorySink();
+  const writer = new ZipWriter(sink);
+  await writer.addDirectory('作品');
+  await writer.addEntry('作品/chart.bms', chunksOf(text.subarray(0, 5), text.subarray(5)), {
+    method: ZipMethod.Deflate,
+    size: text.length,
+  });
+  await writer.addEntry('作品/bgm.wav', chunksOf(binary), { method: ZipMethod.Store });
+  await writer.addEntry('作品/empty.txt', chunksOf(), {
+    method: ZipMethod.Deflate,
+    size: 0,
+  });
+  await writer.close();
+  assert.ok(sink.closed);
+
+  const entries = readCentralDirectory(sink);
+  assert.deepEqual(
+    entries.map((entry) => entry.name),
+    ['作品/', '作品/chart.bms', '作品/bgm.wav', '作品/empty.txt']
+  );
+
+  const expected = [new Uint8Array(0), text, binary, new Uint8Array(0)];
+  for (const [i, entry] of entries.entries()) {
+    const data = await readEntryData(sink, entry);
+    assert.deepEqual(data, expected[i], entry.name);
+    assert.equal(entry.uncompressedSize, expected[i].length, entry.name);
+    assert.equal(entry.crc, crc32(expected[i]), entry.name);
+  }
+
+  // 文本压缩后应明显变小，未知大小的条目（bgm.wav）按 ZIP64 写入
+  assert.ok(entries[1].compressedSize < text.length / 10);
+  assert.equal(entries[2].zip64, true);
+  assert.equal(entries[1].zip64, false);
+});
+
+test('closing twice writes the central directory once', async () => {
+  const sink = new MemorySink();
+  const writer = new ZipWriter(sink);
+  await writer.addEntry('a.txt', chunksOf(new Uint8Array([1, 2, 3])), { size: 3 });
+  await writer.close();
+  const size = sink.size;
+  await writer.close();
+  assert.equal(sink.size, size);
+  await assert.rejects(writer.addEntry('b.txt', chunksOf(), { size: 0 }));
+});
+
+test('more than 65535 entries use the ZIP64 end record', async () => {
+  const sink = new MemorySink();
+  const writer = new ZipWriter(sink);
+  const count = 0xffff + 10;
+  for (let i = 0; i < count; i++) {
+    await writer.addDirectory(`d${i}`);
+  }
+  await writer.close();
+
+  const end = sink.readAt(sink.size - 22, 22);
+  assert.equal(end.getUint16(10, true), 0xffff);
+  const entries = readCentralDirectory(sink);
+  assert.equal(entries.length, count);
+  assert.equal(entries[count - 1].name, `d${count - 1}/`);
+});
+
+test('entries larger than 4GB are written with ZIP64 sizes and offsets', async () => {
+  const chunk = new Uint8Array(64 * 1024 * 1024);
+  const chunkCount = 65;
+  const size = chunk.length * chunkCount;
+  assert.ok(size > ZIP32_MAX);
+
+  async function* zeros(): AsyncGenerator<Uint8Array> {
+    for (let i = 0; i < chunkCount; i++) {
+      yield chunk;
+    }
+  }
+
+  const sink = new SparseSink(chunk);
+  const writer = new ZipWriter(sink);
+  await writer.addEntry('big.bin', zeros(), { method: ZipMethod.Store, size });
+  await writer.addEntry('after.txt', chunksOf(new Uint8Array([42])), {
+    method: ZipMethod.Store,
+    size: 1,
+  });
+  await writer.close();
+
+  const end = sink.readAt(sink.size - 22, 22);
+  assert.equal(end.getUint32(16, true), ZIP32_MAX);
+
+  const [big, after] = readCentralDirectory(sink);
+  assert.equal(big.zip64, true);
+  assert.equal(big.uncompressedSize, size);
+  assert.equal(big.compressedSize, size);
+  assert.equal(big.offset, 0);
+
+  // 第二个条目位于 4GB 之后，偏移写在 ZIP64 扩展字段中
+  assert.equal(after.zip64, true);
+  assert.ok(after.offset > ZIP32_MAX);
+  assert.equal(after.uncompressedSize, 1);
+  assert.equal(sink.readAt(after.offset, 4).getUint32(0, true), SIG_LOCAL_FILE);
+});
@@ -0,0 +1,529 @@
+/**
+ * 流式 ZIP 写入器
+ *
+ * 逐块读取源文件并直接写出，不在内存中缓存整个条目；
+ * 条目或压缩包超过 4GB（或条目数超过 65535）时自动使用 ZIP64 扩展
+ */
+
+import { open, readDir, stat } from '@tauri-apps/plugin-fs';
+import { defaultCompressionPolicy, ZipMethod } from './compression';
+import type { CompressionPolicy } from './compression';
+import { IgnoreList } from './ignore';
+
+/**
+ * 读取源文件的块大小
+ */
+const READ_CHUNK_SIZE = 8 * 1024 * 1024;
+
+/**
+ * 32 位字段的最大值，达到该值时需要 ZIP64
+ */
+const ZIP32_MAX = 0xffffffff;
+
+/**
+ * 16 位条目数的最大值
+ */
+const ZIP16_MAX = 0xffff;
+
+/**
+ * 大小接近该值的条目直接按 ZIP64 写入本地头（压缩后可能略大于原大小）
+ */
+const ZIP64_ENTRY_THRESHOLD = 0xffff0000;
+
+export const SIG_LOCAL_FILE = 0x04034b50;
+export const SIG_DATA_DESCRIPTOR = 0x08074b50;
+export const SIG_CENTRAL_DIR = 0x02014b50;
+const SIG_ZIP64_END = 0x06064b50;
+const SIG_ZIP64_LOCATOR = 0x07064b50;
+const SIG_END = 0x06054b50;
+
+export const ZIP64_EXTRA_ID = 0x0001;
+
+/** 通用标志：大小和 CRC 写在数据描述符中 */
+export const FLAG_DATA_DESCRIPTOR = 1 << 3;
+/** 通用标志：文件名为 UTF-8 */
+export const FLAG_UTF8 = 1 << 11;
+
+const VERSION_DEFAULT = 20;
+const VERSION_ZIP64 = 45;
+
+/**
+ * ZIP 输出目标
+ */
+export interface ZipSink {
+  write(data: Uint8Array): Promise<void>;
+  close(): Promise<void>;
+}
+
+/**
+ * 条目选项
+ */
+export interface ZipEntryOptions {
+  method?: ZipMethod;
+  /** 修改时间，默认当前时间 */
+  mtime?: Date;
+  /** 预期的原始大小，未知时省略（按 ZIP64 写入本地头） */
+  size?: number;
+}
+
+/**
+ * 已写入条目的中央目录信息
+ */
+interface ZipCentralRecord {
+  name: Uint8Array;
+  method: ZipMethod;
+  dosTime: number;
+  dosDate: number;
+  crc: number;
+  compressedSize: number;
+  uncompressedSize: number;
+  offset: number;
+  isDirectory: boolean;
+  /** 本地头是否使用了 ZIP64 */
+  zip64Local: boolean;
+}
+
+const CRC_TABLE = (() => {
+  const table = new Uint32Array(256);
+  for (let n = 0; n < 256; n++) {
+    let c = n;
+    for (let k = 0; k < 8; k++) {
+      c = c & 1 ? 0xedb88320 ^ (c >>> 1) : c >>> 1;
+    }
+    table[n] = c >>> 0;
+  }
+  return table;
+})();
+
+/**
+ * 增量计算 CRC-32
+ *
+ * @param crc - 之前的结果（首次为 0）
+ */
+export function crc32(data: Uint8Array, crc: number = 0): number {
+  let c = ~crc;
+  for (let i = 0; i < data.length; i++) {
+    c = CRC_TABLE[(c ^ data[i]) & 0xff] ^ (c >>> 8);
+  }
+  return ~c >>> 0;
+}
+
+/**
+ * 小端序字节写入辅助
+ */
+class ByteWriter {
+  private readonly bytes: Uint8Array;
+  private readonly view: DataView;
+  private offset = 0;
+
+  constructor(length: number) {
+    this.bytes = new Uint8Array(length);
+    this.view = new DataView(this.bytes.buffer);
+  }
+
+  u16(value: number): this {
+    this.view.setUint16(this.offset, value, true);
+    this.offset += 2;
+    return this;
+  }
+
+  u32(value: number): this {
+    this.view.setUint32(this.offset, value >>> 0, true);
+    this.offset += 4;
+    return this;
+  }
+
+  u64(value: number): this {
+    this.view.setUint32(this.offset, value % 0x100000000, true);
+    this.view.setUint32(this.offset + 4, Math.floor(value / 0x100000000), true);
+    this.offset += 8;
+    return this;
+  }
+
+  raw(data: Uint8Array): this {
+    this.bytes.set(data, this.offset);
+    this.offset += data.length;
+    return this;
+  }
+
+  finish(): Uint8Array {
+    return this.bytes;
+  }
+}
+
+/**
+ * 转换为 DOS 日期和时间
+ */
+function toDosDateTime(date: Date): { dosDate: number; dosTime: number } {
+  const year = Math.max(1980, date.getFullYear());
+  return {
+    dosDate: ((year - 1980) << 9) | ((date.getMonth() + 1) << 5) | date.getDate(),
+    dosTime: (date.getHours() << 11) | (date.getMinutes() << 5) | (date.getSeconds() >> 1),
+  };
+}
+
+/**
+ * 流式 ZIP 写入器
+ *
+ * @example
+ * ```typescript
+ * const writer = await ZipWriter.create('/path/to/out.zip');
+ * await writer.addDirectoryTree('/path/to/work', 'work');
+ * await writer.close();
+ * ```
+ */
+export class ZipWriter {
+  private readonly records: ZipCentralRecord[] = [];
+  private readonly encoder = new TextEncoder();
+  private position = 0;
+  private closed = false;
+
+  constructor(private readonly sink: ZipSink) {}
+
+  /**
+   * 创建写入到文件的 ZIP 写入器
+   */
+  static async create(filePath: string): Promise<ZipWriter> {
+    const file = await open(filePath, { write: true, create: true, truncate: true });
+
+    return new ZipWriter({
+      async write(data: Uint8Array): Promise<void> {
+        let written = 0;
+        while (written < data.length) {
+          written += await file.write(data.subarray(written));
+        }
+      },
+      async close(): Promise<void> {
+        await file.close();
+      },
+    });
+  }
+
+  private async write(data: Uint8Array): Promise<void> {
+    await this.sink.write(data);
+    this.position += data.length;
+  }
+
+  /**
+   * 添加目录条目
+   *
+   * @param name - 压缩包内路径（不需要以 / 结尾）
+   */
+  async addDirectory(name: string, mtime: Date = new Date()): Promise<void> {
+    const dirName = name.endsWith('/') ? name : `${name}/`;
+    const nameBytes = this.encoder.encode(dirName);
+    const { dosDate, dosTime } = toDosDateTime(mtime);
+    const offset = this.position;
+
+    await this.write(
+      new ByteWriter(30 + nameBytes.length)
+        .u32(SIG_LOCAL_FILE)
+        .u16(VERSION_DEFAULT)
+        .u16(FLAG_UTF8)
+        .u16(ZipMethod.Store)
+        .u16(dosTime)
+        .u16(dosDate)
+        .u32(0)
+        .u32(0)
+        .u32(0)
+        .u16(nameBytes.length)
+        .u16(0)
+        .raw(nameBytes)
+        .finish()
+    );
+
+    this.records.push({
+      name: nameBytes,
+      method: ZipMethod.Store,
+      dosTime,
+      dosDate,
+      crc: 0,
+      compressedSize: 0,
+      uncompressedSize: 0,
+      offset,
+      isDirectory: true,
+      zip64Local: false,
+    });
+  }
+
+  /**
+   * 添加文件条目
+   *
+   * 数据按块写出，CRC 和大小写在条目后的数据描述符中
+   *
+   * @param name - 压缩包内路径
+   * @param source - 文件内容（按块提供）
+   */
+  async addEntry(
+    name: string,
+    source: AsyncIterable<Uint8Array>,
+    options: ZipEntryOptions = {}
+  ): Promise<void> {
+    if (this.closed) {
+      throw new Error('ZipWriter is already closed');
+    }
+
+    const method = options.method ?? ZipMethod.Store;
+    const nameBytes = this.encoder.encode(name);
+    const { dosDate, dosTime } = toDosDateTime(options.mtime ?? new Date());
+    const zip64Local = options.size === undefined || options.size >= ZIP64_ENTRY_THRESHOLD;
+    const offset = this.position;
+
+    // 本地头：CRC 和大小未知，ZIP64 时大小字段填 0xFFFFFFFF 并附带 ZIP64 扩展
+    const extraLength = zip64Local ? 20 : 0;
+    const header = new ByteWriter(30 + nameBytes.length + extraLength)
+      .u32(SIG_LOCAL_FILE)
+      .u16(zip64Local ? VERSION_ZIP64 : VERSION_DEFAULT)
+      .u16(FLAG_DATA_DESCRIPTOR | FLAG_UTF8)
+      .u16(method)
+      .u16(dosTime)
+      .u16(dosDate)
+      .u32(0)
+      .u32(zip64Local ? ZIP32_MAX : 0)
+      .u32(zip64Local ? ZIP32_MAX : 0)
+      .u16(nameBytes.length)
+      .u16(extraLength)
+      .raw(nameBytes);
+    if (zip64Local) {
+      header.u16(ZIP64_EXTRA_ID).u16(16).u64(0).u64(0);
+    }
+    await this.write(header.finish());
+
+    let crc = 0;
+    let uncompressedSize = 0;
+    const dataStart = this.position;
+
+    if (method === ZipMethod.Store) {
+      for await (const chunk of source) {
+        crc = crc32(chunk, crc);
+        uncompressedSize += chunk.length;
+        await this.write(chunk);
+      }
+    } else {
+      const compressor = new CompressionStream('deflate-raw');
+      const input = compressor.writable.getWriter();
+      const output = compressor.readable.getReader();
+
+      // 同时读取压缩输出，避免压缩流积压
+      const pump = (async () => {
+        while (true) {
+          const { done, value } = await output.read();
+          if (done) {
+            break;
+          }
+          await this.write(value);
+        }
+      })();
+
+      try {
+        for await (const chunk of source) {
+          crc = crc32(chunk, crc);
+          uncompressedSize += chunk.length;
+          // 复制一份，调用方可能复用读取缓冲区
+          await input.write(chunk.slice());
+        }
+        await input.close();
+      } catch (error) {
+        await input.abort(error).catch(() => {});
+        throw error;
+      } finally {
+        await pump;
+      }
+    }
+
+    const compressedSize = this.position - dataStart;
+
+    if (!zip64Local && (compressedSize >= ZIP32_MAX || uncompressedSize >= ZIP32_MAX)) {
+      throw new Error(`Entry ${name} exceeded its declared size: ${uncompressedSize} bytes`);
+    }
+
+    // 数据描述符：ZIP64 条目使用 8 字节大小
+    const descriptor = new ByteWriter(zip64Local ? 24 : 16).u32(SIG_DATA_DESCRIPTOR).u32(crc);
+    if (zip64Local) {
+      descriptor.u64(compressedSize).u64(uncompressedSize);
+    } else {
+      descriptor.u32(compressedSize).u32(uncompressedSize);
+    }
+    await this.write(descriptor.finish());
+
+    this.records.push({
+      name: nameBytes,
+      method,
+      dosTime,
+      dosDate,
+      crc,
+      compressedSize,
+      uncompressedSize,
+      offset,
+      isDirectory: false,
+      zip64Local,
+    });
+  }
+
+  /**
+   * 添加磁盘上的文件
+   */
+  async addFile(
+    name: string,
+    filePath: string,
+    method: ZipMethod = ZipMethod.Store
+  ): Promise<void> {
+    const info = await stat(filePath);
+    await this.addEntry(name, readFileChunks(filePath), {
+      method,
+      mtime: info.mtime ?? undefined,
+      size: info.size,
+    });
+  }
+
+  /**
+   * 递归添加目录
+   *
+   * @param dirPath - 磁盘上的目录
+   * @param name - 压缩包内的目录名
+   * @param policy - 压缩策略，或对所有文件使用的压缩方式
+   */
+  async addDirectoryTree(
+    dirPath: string,
+    name: string,
+    policy: CompressionPolicy | ZipMethod = defaultCompressionPolicy
+  ): Promise<void> {
+    const info = await stat(dirPath);
+    await this.addDirectory(name, info.mtime ?? undefined);
+
+    for (const entry of await readDir(dirPath)) {
+      if (!entry.name || IgnoreList.isIgnored(entry.name)) {
+        continue;
+      }
+
+      const entryPath = `${dirPath}/${entry.name}`;
+      const entryName = `${name}/${entry.name}`;
+      if (entry.isDirectory) {
+        await this.addDirectoryTree(entryPath, entryName, policy);
+      } else {
+        const method = typeof policy === 'function' ? policy(entryPath) : policy;
+        await this.addFile(entryName, entryPath, method);
+      }
+    }
+  }
+
+  /**
+   * 写出中央目录并关闭
+   */
+  async close(): Promise<void> {
+    if (this.closed) {
+      return;
+    }
+    this.closed = true;
+
+    const centralStart = this.position;
+
+    for (const record of this.records) {
+      const needSizes =
+        record.zip64Local ||
+        record.compressedSize >= ZIP32_MAX ||
+        record.uncompressedSize >= ZIP32_MAX;
+      const needOffset = record.offset >= ZIP32_MAX;
+      const extraDataLength = (needSizes ? 16 : 0) + (needOffset ? 8 : 0);
+      const extraLength = extraDataLength > 0 ? 4 + extraDataLength : 0;
+      const zip64 = extraLength > 0;
+
+      const header = new ByteWriter(46 + record.name.length + extraLength)
+        .u32(SIG_CENTRAL_DIR)
+        .u16(zip64 ? VERSION_ZIP64 : VERSION_DEFAULT)
+        .u16(zip64 ? VERSION_ZIP64 : VERSION_DEFAULT)
+        .u16(record.isDirectory ? FLAG_UTF8 : FLAG_DATA_DESCRIPTOR | FLAG_UTF8)
+        .u16(record.method)
+        .u16(record.dosTime)
+        .u16(record.dosDate)
+        .u32(record.crc)
+        .u32(needSizes ? ZIP32_MAX : record.compressedSize)
+        .u32(needSizes ? ZIP32_MAX : record.uncompressedSize)
+        .u16(record.name.length)
+        .u16(extraLength)
+        .u16(0)
+        .u16(0)
+        .u16(0)
+        .u32(record.isDirectory ? 0x10 : 0)
+        .u32(needOffset ? ZIP32_MAX : record.offset)
+        .raw(record.name);
+
+      if (zip64) {
+        header.u16(ZIP64_EXTRA_ID).u16(extraDataLength);
+        if (needSizes) {
+          header.u64(record.uncompressedSize).u64(record.compressedSize);
+        }
+        if (needOffset) {
+          header.u64(record.offset);
+        }
+      }
+
+      await this.write(header.finish());
+    }
+
+    const centralSize = this.position - centralStart;
+    const entryCount = this.records.length;
+    const needZip64End =
+      entryCount >= ZIP16_MAX || centralStart >= ZIP32_MAX || centralSize >= ZIP32_MAX;
+
+    if (needZip64End) {
+      const zip64EndOffset = this.position;
+      await this.write(
+        new ByteWriter(56 + 20)
+          .u32(SIG_ZIP64_END)
+          .u64(44)
+          .u16(VERSION_ZIP64)
+          .u16(VERSION_ZIP64)
+          .u32(0)
+          .u32(0)
+          .u64(entryCount)
+          .u64(entryCount)
+          .u64(centralSize)
+          .u64(centralStart)
+          .u32(SIG_ZIP64_LOCATOR)
+          .u32(0)
+          .u64(zip64EndOffset)
+          .u32(1)
+          .finish()
+      );
+    }
+
+    await this.write(
+      new ByteWriter(22)
+        .u32(SIG_END)
+        .u16(0)
+        .u16(0)
+        .u16(Math.min(entryCount, ZIP16_MAX))
+        .u16(Math.min(entryCount, ZIP16_MAX))
+        .u32(Math.min(centralSize, ZIP32_MAX))
+        .u32(Math.min(centralStart, ZIP32_MAX))
+        .u16(0)
+        .finish()
+    );
+
+    await this.sink.close();
+  }
+}
+
+/**
+ * 分块读取文件
+ */
+export async function* readFileChunks(
+  filePath: string,
+  chunkSize: number = READ_CHUNK_SIZE
+): AsyncGenerator<Uint8Array> {
+  const file = await open(filePath, { read: true });
+  const buffer = new Uint8Array(chunkSize);
+
+  try {
+    while (true) {
+      const bytesRead = await file.read(buffer);
+      if (bytesRead === null || bytesRead === 0) {
+        break;
+      }
+      yield buffer.subarray(0, bytesRead);
+    }
+  } finally {
+    await file.close();
+  }
+}
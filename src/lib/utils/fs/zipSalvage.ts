@@ -0,0 +1,411 @@
+/**
+ * 损坏 ZIP 的抢救解压
+ *
+ * 部分活动投稿的压缩包中央目录损坏（如上传中断或被截断），unzip 和 7z 都无法打开，
+ * 但各条目的本地文件头和数据仍然完好。这里不读取中央目录，而是从头扫描本地文件头逐个解压，
+ * 遇到损坏的区域时跳到下一个本地文件头，最后报告恢复和丢失的文件
+ */
+
+import { mkdir, open, remove, SeekMode, stat } from '@tauri-apps/plugin-fs';
+import type { FileHandle } from '@tauri-apps/plugin-fs';
+import { ZipMethod } from './compression';
+import { getDirectoryPath, getSafeRelativePath } from './path';
+import {
+  crc32,
+  FLAG_DATA_DESCRIPTOR,
+  FLAG_UTF8,
+  SIG_CENTRAL_DIR,
+  SIG_DATA_DESCRIPTOR,
+  SIG_LOCAL_FILE,
+  ZIP64_EXTRA_ID,
+} from './zip';
+
+/**
+ * 读取条目数据和扫描签名的块大小
+ */
+const SALVAGE_CHUNK_SIZE = 4 * 1024 * 1024;
+
+/**
+ * 本地文件头的固定部分长度
+ */
+const LOCAL_HEADER_SIZE = 30;
+
+const ZIP32_MAX = 0xffffffff;
+
+/** 通用标志：条目已加密 */
+const FLAG_ENCRYPTED = 1 << 0;
+
+/**
+ * 抢救解压结果
+ */
+export interface ZipSalvageResult {
+  /** 完整恢复的文件（压缩包内路径） */
+  recovered: string[];
+  /** 已写出但 CRC 校验不一致的文件 */
+  damaged: string[];
+  /** 无法恢复的文件及原因 */
+  lost: { name: string; reason: string }[];
+  /** 无法解析而跳过的字节数（其中的条目连文件名也无法得知） */
+  skippedBytes: number;
+}
+
+/**
+ * 从本地文件头解析出的条目
+ */
+interface LocalEntry {
+  name: string;
+  flags: number;
+  method: number;
+  crc: number;
+  compressedSize: number;
+  /** 数据起始位置 */
+  dataOffset: number;
+  /** 大小写在数据描述符中（本地文件头中的大小无效） */
+  sizeUnknown: boolean;
+}
+
+async function readAt(file: FileHandle, offset: number, length: number): Promise<Uint8Array> {
+  await file.seek(offset, SeekMode.Start);
+  const buffer = new Uint8Array(length);
+  let total = 0;
+  while (total < length) {
+    const bytesRead = await file.read(buffer.subarray(total));
+    if (bytesRead === null || bytesRead === 0) {
+      break;
+    }
+    total += bytesRead;
+  }
+  return buffer.subarray(0, total);
+}
+
+function readU64(view: DataView, offset: number): number {
+  return view.getUint32(offset, true) + view.getUint32(offset + 4, true) * 0x100000000;
+}
+
+/**
+ * 从指定位置开始查找第一个出现的签名
+ */
+async function findSignature(
+  file: FileHandle,
+  from: number,
+  fileSize: number,
+  signatures: number[]
+): Promise<{ offset: number; signature: number } | null> {
+  for (let start = from; start + 4 <= fileSize; start += SALVAGE_CHUNK_SIZE) {
+    // 多读 3 个字节，避免签名跨越两个块
+    const chunk = await readAt(file, start, Math.min(SALVAGE_CHUNK_SIZE + 3, fileSize - start));
+    const view = new DataView(chunk.buffer, chunk.byteOffset, chunk.byteLength);
+    for (let i = 0; i + 4 <= chunk.length; i++) {
+      if (chunk[i] !== 0x50 || chunk[i + 1] !== 0x4b) {
+        continue;
+      }
+      const signature = view.getUint32(i, true);
+      if (signatures.includes(signature)) {
+        return { offset: start + i, signature };
+      }
+    }
+  }
+  return null;
+}
+
+/**
+ * 解码文件名：未标记 UTF-8 时，不是有效 UTF-8 的按 Shift_JIS 解码（日本的压缩包大多如此）
+ */
+function decodeEntryName(bytes: Uint8Array, flags: number): string {
+  if (flags & FLAG_UTF8) {
+    return new TextDecoder('utf-8').decode(bytes);
+  }
+  try {
+    return new TextDecoder('utf-8', { fatal: true }).decode(bytes);
+  } catch {
+    return new TextDecoder('shift_jis').decode(bytes);
+  }
+}
+
+/**
+ * 解析本地文件头，签名不符或文件头不完整时返回 null
+ */
+async function readLocalEntry(file: FileHandle, offset: number): Promise<LocalEntry | null> {
+  const header = await readAt(file, offset, LOCAL_HEADER_SIZE);
+  if (header.length < LOCAL_HEADER_SIZE) {
+    return null;
+  }
+  const view = new DataView(header.buffer, header.byteOffset, header.byteLength);
+  if (view.getUint32(0, true) !== SIG_LOCAL_FILE) {
+    return null;
+  }
+
+  const flags = view.getUint16(6, true);
+  const nameLength = view.getUint16(26, true);
+  const extraLength = view.getUint16(28, true);
+  const variable = await readAt(file, offset + LOCAL_HEADER_SIZE, nameLength + extraLength);
+  if (variable.length < nameLength + extraLength) {
+    return null;
+  }
+
+  let compressedSize = view.getUint32(18, true);
+  const uncompressedSize = view.getUint32(22, true);
+  if (compressedSize === ZIP32_MAX) {
+    // ZIP64 扩展字段中依次为原始大小和压缩后大小
+    const extra = new DataView(variable.buffer, variable.byteOffset + nameLength, extraLength);
+    for (let p = 0; p + 4 <= extraLength; ) {
+      const id = extra.getUint16(p, true);
+      const size = extra.getUint16(p + 2, true);
+      if (id === ZIP64_EXTRA_ID) {
+        const field = p + 4 + (uncompressedSize === ZIP32_MAX ? 8 : 0);
+        if (field + 8 <= p + 4 + size && field + 8 <= extraLength) {
+          compressedSize = readU64(extra, field);
+        }
+        break;
+      }
+      p += 4 + size;
+    }
+  }
+
+  return {
+    name: decodeEntryName(variable.subarray(0, nameLength), flags),
+    flags,
+    method: view.getUint16(8, true),
+    crc: view.getUint32(14, true),
+    compressedSize,
+    dataOffset: offset + LOCAL_HEADER_SIZE + nameLength + extraLength,
+    sizeUnknown: (flags & FLAG_DATA_DESCRIPTOR) !== 0 && compressedSize === 0,
+  };
+}
+
+/**
+ * 对大小写在数据描述符中的条目，查找数据的结束位置
+ *
+ * 数据描述符的签名是可选的，因此也检查下一个文件头之前的 12 字节；
+ * 只有描述符中的压缩后大小与实际距离一致时才认为找到了结尾
+ *
+ * @returns 压缩后大小、CRC 和下一个条目的位置
+ */
+async function findEntryEnd(
+  file: FileHandle,
+  dataOffset: number,
+  fileSize: number
+): Promise<{ compressedSize: number; crc: number; next: number } | null> {
+  const signatures = [SIG_DATA_DESCRIPTOR, SIG_LOCAL_FILE, SIG_CENTRAL_DIR];
+  for (let from = dataOffset; ; ) {
+    const found = await findSignature(file, from, fileSize, signatures);
+    if (!found) {
+      return null;
+    }
+    const { offset, signature } = found;
+
+    if (signature === SIG_DATA_DESCRIPTOR) {
+      const bytes = await readAt(file, offset, 24);
+      const view = new DataView(bytes.buffer, bytes.byteOffset, bytes.byteLength);
+      const size = offset - dataOffset;
+      if (bytes.length >= 16 && view.getUint32(8, true) === size) {
+        return { compressedSize: size, crc: view.getUint32(4, true), next: offset + 16 };
+      }
+      if (bytes.length >= 24 && readU64(view, 8) === size) {
+        return { compressedSize: size, crc: view.getUint32(4, true), next: offset + 24 };
+      }
+    } else if (offset - 12 >= dataOffset) {
+      const bytes = await readAt(file, offset - 12, 12);
+      const view = new DataView(bytes.buffer, bytes.byteOffset, bytes.byteLength);
+      const size = offset - 12 - dataOffset;
+      if (view.getUint32(4, true) === size) {
+        return { compressedSize: size, crc: view.getUint32(0, true), next: offset };
+      }
+    }
+    from = offset + 1;
+  }
+}
+
+/**
+ * 分块读取文件中的一段数据
+ */
+async function* readRange(
+  file: FileHandle,
+  offset: number,
+  length: number
+): AsyncGenerator<Uint8Array> {
+  for (let done = 0; done < length; ) {
+    const chunk = await readAt(file, offset + done, Math.min(SALVAGE_CHUNK_SIZE, length - done));
+    if (chunk.length === 0) {
+      throw new Error('数据被截断');
+    }
+    done += chunk.length;
+    yield chunk;
+  }
+}
+
+/**
+ * 解压单个条目到目标文件
+ *
+ * @returns 解压后数据的 CRC
+ */
+async function extractEntry(
+  file: FileHandle,
+  entry: LocalEntry,
+  compressedSize: number,
+  targetPath: string
+): Promise<number> {
+  const out = await open(targetPath, { write: true, create: true, truncate: true });
+  let crc = 0;
+  const writeOut = async (chunk: Uint8Array) => {
+    crc = crc32(chunk, crc);
+    await out.write(chunk);
+  };
+
+  try {
+    const chunks = readRange(file, entry.dataOffset, compressedSize);
+    if (entry.method === ZipMethod.Store) {
+      for await (const chunk of chunks) {
+        await writeOut(chunk);
+      }
+      return crc;
+    }
+
+    const decompressor = new DecompressionStream('deflate-raw');
+    const input = decompressor.writable.getWriter();
+    const output = decompressor.readable.getReader();
+    const feed = (async () => {
+      try {
+        for await (const chunk of chunks) {
+          await input.write(chunk);
+        }
+        await input.close();
+      } catch (error) {
+        // 读取失败时中止解压流，否则读取输出的一端会一直等待
+        await input.abort(error).catch(() => {});
+        throw error;
+      }
+    })();
+    const pump = (async () => {
+      while (true) {
+        const { done, value } = await output.read();
+        if (done) {
+          break;
+        }
+        await writeOut(value);
+      }
+    })();
+
+    // 数据损坏时两端都会出错，等待两者结束后抛出第一个错误
+    const results = await Promise.allSettled([feed, pump]);
+    const failure = results.find((result) => result.status === 'rejected');
+    if (failure) {
+      throw (failure as PromiseRejectedResult).reason;
+    }
+    return crc;
+  } finally {
+    await out.close();
+  }
+}
+
+/**
+ * 从本地文件头抢救解压 ZIP 文件
+ *
+ * @command
+ * @category pack
+ * @dangerous false
+ * @name 抢救解压损坏的 ZIP
+ * @description 不读取中央目录，逐个扫描本地文件头解压，用于中央目录损坏、unzip 和 7z 都无法打开的压缩包，并报告丢失的文件
+ * @frontend true
+ *
+ * @param {string} file - ZIP 文件路径
+ * @param {string} dest - 解压目标目录
+ *
+ * @returns {Promise<ZipSalvageResult>} 恢复、校验不一致和丢失的文件
+ */
+export async function salvageZip(file: string, dest: string): Promise<ZipSalvageResult> {
+  const result: ZipSalvageResult = { recovered: [], damaged: [], lost: [], skippedBytes: 0 };
+  const fileSize = (await stat(file)).size;
+  const handle = await open(file, { read: true });
+
+  try {
+    let offset = 0;
+    while (offset + LOCAL_HEADER_SIZE <= fileSize) {
+      const entry = await readLocalEntry(handle, offset);
+      if (!entry) {
+        const head = await readAt(handle, offset, 4);
+        const view = new DataView(head.buffer, head.byteOffset, head.byteLength);
+        if (head.length === 4 && view.getUint32(0, true) === SIG_CENTRAL_DIR) {
+          break;
+        }
+        // 跳到下一个本地文件头；遇到中央目录说明条目已全部读完
+        const found = await findSignature(handle, offset + 1, fileSize, [
+          SIG_LOCAL_FILE,
+          SIG_CENTRAL_DIR,
+        ]);
+        result.skippedBytes += (found?.offset ?? fileSize) - offset;
+        if (found?.signature !== SIG_LOCAL_FILE) {
+          break;
+        }
+        offset = found.offset;
+        continue;
+      }
+
+      // 条目失败后从该文件头之后重新查找，不信任其中的大小
+      const resync = offset + 4;
+      let compressedSize = entry.compressedSize;
+      let expectedCrc = entry.crc;
+      let next = entry.dataOffset + compressedSize;
+      if (entry.sizeUnknown) {
+        const end = await findEntryEnd(handle, entry.dataOffset, fileSize);
+        if (!end) {
+          result.lost.push({ name: entry.name, reason: '找不到数据结尾' });
+          offset = resync;
+          continue;
+        }
+        ({ compressedSize, crc: expectedCrc, next } = end);
+      }
+
+      const relativePath = getSafeRelativePath(entry.name);
+      if (!relativePath) {
+        result.lost.push({ name: entry.name, reason: '不安全的路径' });
+        offset = next;
+        continue;
+      }
+      if (entry.name.endsWith('/') || entry.name.endsWith('\\')) {
+        await mkdir(`${dest}/${relativePath}`, { recursive: true });
+        offset = next;
+        continue;
+      }
+      if (entry.flags & FLAG_ENCRYPTED) {
+        result.lost.push({ name: entry.name, reason: '已加密' });
+        offset = next;
+        continue;
+      }
+      if (entry.method !== ZipMethod.Store && entry.method !== ZipMethod.Deflate) {
+        result.lost.push({ name: entry.name, reason: `不支持的压缩方式 ${entry.method}` });
+        offset = next;
+        continue;
+      }
+
+      const targetPath = `${dest}/${relativePath}`;
+      try {
+        await mkdir(getDirectoryPath(targetPath), { recursive: true });
+        const crc = await extractEntry(handle, entry, compressedSize, targetPath);
+        if (crc !== expectedCrc) {
+          result.damaged.push(relativePath);
+          console.warn(` !_! CRC mismatch, kept anyway: ${relativePath}`);
+        } else {
+          result.recovered.push(relativePath);
+        }
+        offset = next;
+      } catch (error) {
+        result.lost.push({ name: entry.name, reason: String(error) });
+        await remove(targetPath).catch(() => {});
+        offset = resync;
+      }
+    }
+  } finally {
+    await handle.close();
+  }
+
+  for (const { name, reason } of result.lost) {
+    console.warn(` !_! Lost: ${name} (${reason})`);
+  }
+  console.log(
+    `Salvaged ${result.recovered.length} files from ${file} ` +
+      `(${result.damaged.length} damaged, ${result.lost.length} lost, ` +
+      `${result.skippedBytes} bytes skipped)`
+  );
+  return result;
+}
@@ -0,0 +1,118 @@
+/**
+ * 自定义钩子
+ *
+ * 在导入、重命名、转换作品之后运行用户配置的 Shell 命令（如个人的谱面分析脚本），
+ * 作品信息通过环境变量传入，不需要修改工具本身就能加入自己的处理步骤
+ */
+
+import { getDirBmsOutput } from './bms/scanner';
+import { ProcessRunner } from './media/processRunner';
+
+/**
+ * 钩子的触发时机
+ */
+export type HookEvent = 'after_import' | 'after_rename' | 'after_conversion';
+
+/**
+ * 钩子配置
+ */
+export interface HookConfig {
+  event: HookEvent;
+  /** 在系统 Shell 中执行的命令（Windows 为 cmd，其他系统为 sh），工作目录为作品目录 */
+  command: string;
+  enabled: boolean;
+}
+
+/**
+ * 传给钩子的作品信息
+ */
+export interface HookContext {
+  workPath: string;
+  title?: string;
+  artist?: string;
+  /** 重命名前的作品目录 */
+  previousPath?: string;
+}
+
+function isWindows(): boolean {
+  return navigator.platform?.toLowerCase().includes('win') ?? false;
+}
+
+/**
+ * 钩子设置
+ */
+export class PostHooks {
+  private static hooks: HookConfig[] = [];
+
+  static setHooks(hooks: HookConfig[]): void {
+    this.hooks = hooks.filter((hook) => hook.command.trim().length > 0);
+  }
+
+  static getHooks(): HookConfig[] {
+    return this.hooks.map((hook) => ({ ...hook }));
+  }
+
+  static hasHooks(event: HookEvent): boolean {
+    return this.hooks.some((hook) => hook.enabled && hook.event === event);
+  }
+}
+
+/**
+ * 单个钩子的超时（毫秒）
+ */
+const HOOK_TIMEOUT_MS = 10 * 60_000;
+
+/**
+ * 运行某个时机的全部钩子
+ *
+ * 未传入标题和艺术家时从谱面读取。钩子失败只输出警告，不影响已完成的操作
+ */
+export async function runHooks(event: HookEvent, context: HookContext): Promise<void> {
+  if (!PostHooks.hasHooks(event)) {
+    return;
+  }
+
+  let { title, artist } = context;
+  if (title === undefined || artist === undefined) {
+    const info = await getDirBmsOutput(context.workPath).catch(() => null);
+    title ??= info?.bms.musicInfo.title;
+    artist ??= info?.bms.musicInfo.artist;
+  }
+
+  const { workPath } = context;
+  const env: Record<string, string> = {
+    BMS_HOOK_EVENT: event,
+    BMS_WORK_PATH: workPath,
+    BMS_WORK_DIR_NAME: workPath.split(/[/\\]/).pop() ?? workPath,
+    BMS_TITLE: title ?? '',
+    BMS_ARTIST: artist ?? '',
+    BMS_PREVIOUS_PATH: context.previousPath ?? '',
+  };
+
+  for (const hook of PostHooks.getHooks()) {
+    if (!hook.enabled || hook.event !== event) {
+      continue;
+    }
+    const [program, args]: [string, string[]] = isWindows()
+      ? ['cmd', ['/C', hook.command]]
+      : ['sh', ['-c', hook.command]];
+    try {
+      const result = await ProcessRunner.exec(program, args, {
+        timeout: HOOK_TIMEOUT_MS,
+        cwd: workPath,
+        env,
+      });
+      if (result.stdout.trim()) {
+        console.log(result.stdout.trimEnd());
+      }
+      if (!result.success) {
+        console.warn(` !_! Hook exited with code ${result.exitCode}: ${hook.command}`);
+        if (result.stderr.trim()) {
+          console.warn(result.stderr.trimEnd());
+        }
+      }
+    } catch (error) {
+      console.warn(` !_! Failed to run hook ${hook.command}:`, error);
+    }
+  }
+}
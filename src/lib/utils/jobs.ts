@@ -0,0 +1,243 @@
+/**
+ * 任务管理
+ *
+ * 每次执行前端命令都登记为一个任务，并分配一个进度管理器。前端启动命令后立即得到任务 ID，
+ * 可以列出运行中的任务（命令、目标目录、进度、开始时间），查看单个任务或取消任务；
+ * 取消任务时进度管理器发出取消信号，批量处理在文件或目录之间停下，正在运行的 ffmpeg 等外部进程被终止
+ */
+
+import { ProgressManager } from './progress';
+import type { ProgressRate } from './progress';
+import { ResourceTelemetry } from './telemetry';
+
+/**
+ * 任务状态
+ */
+export type JobStatus = 'running' | 'succeeded' | 'failed' | 'cancelled';
+
+/**
+ * 命令的执行结果
+ */
+export interface JobResult {
+  success: boolean;
+  data?: unknown;
+  error?: string;
+}
+
+/**
+ * 任务
+ */
+export interface Job {
+  /** 任务 ID（"<命令 ID>#<序号>"） */
+  id: string;
+  commandId: string;
+  /** 命令名称 */
+  name: string;
+  /** 命令操作的目录 */
+  targetDirs: string[];
+  startedAt: string;
+  finishedAt?: string;
+  status: JobStatus;
+  error?: string;
+  progress: ProgressManager;
+  result: Promise<JobResult>;
+}
+
+/**
+ * 任务概要（用于列出和查看）
+ */
+export interface JobSummary {
+  id: string;
+  commandId: string;
+  name: string;
+  targetDirs: string[];
+  status: JobStatus;
+  startedAt: string;
+  finishedAt?: string;
+  message: string;
+  current: number;
+  total: number;
+  percentage: number;
+  /** 处理速度（最近一段时间的移动平均） */
+  rate?: ProgressRate;
+  /** 预计剩余时间（秒） */
+  etaSeconds?: number;
+  error?: string;
+}
+
+/**
+ * 保留的已结束任务数（用于查看最近的任务）
+ */
+const MAX_FINISHED_JOBS = 50;
+
+/**
+ * 任务登记表
+ */
+export class JobRegistry {
+  private static jobs = new Map<string, Job>();
+  private static nextId = 1;
+
+  /**
+   * 登记任务并在后台执行，立即返回任务
+   */
+  static start(
+    commandId: string,
+    name: string,
+    targetDirs: string[],
+    fn: (progress: ProgressManager) => Promise<JobResult>
+  ): Job {
+    const progress = new ProgressManager();
+    const job: Job = {
+      id: `${commandId}#${this.nextId++}`,
+      commandId,
+      name,
+      targetDirs,
+      startedAt: new Date().toISOString(),
+      status: 'running',
+      progress,
+      result: Promise.resolve({ success: false }),
+    };
+    ResourceTelemetry.beginJob();
+    job.result = fn(progress)
+      .catch((error) => ({
+        success: false,
+        error: error instanceof Error ? error.message : String(error),
+      }))
+      .then((result) => {
+        job.finishedAt = new Date().toISOString();
+        job.error = result.error;
+        job.status = result.success
+          ? 'succeeded'
+          : progress.getProgress().cancelled
+            ? 'cancelled'
+            : 'failed';
+        ResourceTelemetry.endJob();
+        this.pruneFinished();
+        return result;
+      });
+    this.jobs.set(job.id, job);
+    return job;
+  }
+
+  /**
+   * 等待任务结束并返回结果
+   */
+  static async wait(jobId: string): Promise<JobResult> {
+    const job = this.jobs.get(jobId);
+    if (!job) {
+      return { success: false, error: `任务不存在：${jobId}` };
+    }
+    return await job.result;
+  }
+
+  static get(jobId: string): Job | undefined {
+    return this.jobs.get(jobId);
+  }
+
+  static list(includeFinished: boolean = false): Job[] {
+    return [...this.jobs.values()].filter((job) => includeFinished || job.status === 'running');
+  }
+
+  /**
+   * 取消任务
+   *
+   * @param jobId - 任务 ID，或命令 ID（取消该命令所有运行中的任务）
+   * @returns 取消的任务 ID
+   */
+  static cancel(jobId: string): string[] {
+    const jobs = this.list().filter((job) => job.id === jobId || job.commandId === jobId);
+    for (const job of jobs) {
+      job.progress.cancel();
+    }
+    return jobs.map((job) => job.id);
+  }
+
+  private static pruneFinished(): void {
+    const finished = this.list(true).filter((job) => job.status !== 'running');
+    for (const job of finished.slice(0, Math.max(0, finished.length - MAX_FINISHED_JOBS))) {
+      this.jobs.delete(job.id);
+    }
+  }
+}
+
+function summarize(job: Job): JobSummary {
+  const progress = job.progress.getProgress();
+  return {
+    id: job.id,
+    commandId: job.commandId,
+    name: job.name,
+    targetDirs: job.targetDirs,
+    status: job.status,
+    startedAt: job.startedAt,
+    finishedAt: job.finishedAt,
+    message: progress.message,
+    current: progress.current,
+    total: progress.total,
+    percentage: progress.percentage,
+    rate: progress.rate,
+    etaSeconds: progress.etaSeconds,
+    error: job.error,
+  };
+}
+
+/**
+ * 取消运行中的任务
+ *
+ * @command
+ * @category jobs
+ * @dangerous false
+ * @name 取消任务
+ * @description 取消运行中的任务：批量处理在当前文件或目录处理完后停止，正在运行的 ffmpeg 等外部进程被终止，未完成的输出被丢弃
+ * @frontend true
+ *
+ * @param {string} jobId - 任务 ID（也可以是命令 ID，取消该命令所有运行中的任务）
+ *
+ * @returns {Promise<string[]>} 取消的任务 ID
+ */
+export async function cancelJob(jobId: string): Promise<string[]> {
+  const cancelled = JobRegistry.cancel(jobId);
+  if (cancelled.length === 0) {
+    console.warn(` !_! No running job: ${jobId}`);
+  }
+  for (const id of cancelled) {
+    console.log(`Cancelling job: ${id}`);
+  }
+  return cancelled;
+}
+
+/**
+ * 列出任务
+ *
+ * @command
+ * @category jobs
+ * @dangerous false
+ * @name 列出任务
+ * @description 列出正在运行的任务（命令、目标目录、进度和开始时间），任务 ID 可用于查看或取消任务
+ * @frontend true
+ *
+ * @param {boolean} includeFinished - 同时列出最近结束的任务
+ *
+ * @returns {Promise<JobSummary[]>} 任务列表
+ */
+export async function listJobs(includeFinished: boolean = false): Promise<JobSummary[]> {
+  return JobRegistry.list(includeFinished).map(summarize);
+}
+
+/**
+ * 查看任务
+ *
+ * @command
+ * @category jobs
+ * @dangerous false
+ * @name 查看任务
+ * @description 查看任务的状态、进度、目标目录和错误信息
+ * @frontend true
+ *
+ * @param {string} jobId - 任务 ID
+ *
+ * @returns {Promise<JobSummary | null>} 任务信息，任务不存在（或已从记录中移除）时为 null
+ */
+export async function getJob(jobId: string): Promise<JobSummary | null> {
+  const job = JobRegistry.get(jobId);
+  return job ? summarize(job) : null;
+}
@@ -0,0 +1,49 @@
+/**
+ * 作品库 BGA 统计
+ *
+ * 统计需要读取谱面的 BGA 通道并用 ffprobe 读取视频，比建立索引慢得多，
+ * 因此单独运行，结果记录在索引中，供查询筛选 BGA 完整的作品和导出一览表
+ */
+
+import { summarizeBga } from '../work/bgaSummary';
+import { LibraryIndex } from './workIndex';
+import type { WorkIndexEntry } from './workIndex';
+
+/**
+ * 统计根目录中作品的 BGA
+ *
+ * @command
+ * @category library
+ * @dangerous false
+ * @name 统计作品库 BGA
+ * @description 统计索引中每个作品的 BGA 类型（视频或图片序列）、时长与歌曲长度的比例和分辨率，记录到索引中，用于筛选 BGA 完整的作品
+ * @frontend true
+ *
+ * @param {string} rootDir - 根目录路径
+ * @param {boolean} onlyMissing - 只统计尚未统计过的作品
+ *
+ * @returns {Promise<number>} 统计的作品数
+ */
+export async function indexBga(rootDir: string, onlyMissing: boolean = true): Promise<number> {
+  await LibraryIndex.update(rootDir);
+  const index = await LibraryIndex.get(rootDir);
+
+  const updates = new Map<string, Partial<WorkIndexEntry>>();
+  try {
+    for (const work of index?.works ?? []) {
+      if ((onlyMissing && work.bga) || !work.hasBga) {
+        continue;
+      }
+      try {
+        updates.set(work.dirName, { bga: await summarizeBga(work.path) });
+      } catch (error) {
+        console.warn(` !_! Failed to summarize BGA of ${work.dirName}:`, error);
+      }
+    }
+  } finally {
+    await LibraryIndex.annotate(rootDir, updates);
+  }
+
+  console.log(`Summarized BGA of ${updates.size} works`);
+  return updates.size;
+}
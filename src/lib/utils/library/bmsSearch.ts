@@ -0,0 +1,255 @@
+/**
+ * bmssearch 作品查询
+ *
+ * 按谱面哈希或标题、艺术家在 bmssearch.net 上查询作品，取得规范的标题、艺术家和首次发布的活动。
+ * 查询结果（包括查不到的结果）缓存在应用数据目录中，避免重复请求。
+ * 用于补充索引中的作品来源，也作为谱面头部损坏时的备用名称来源
+ */
+
+import { getWorkDirChartFiles } from '../bms/scanner';
+import { Downloader, HttpStatusError } from '../download';
+import { HashAlgorithm, HashService } from '../fs/hash';
+import { getAppDataPath, readJsonFile, writeJsonFile } from '../storage';
+import { LibraryIndex } from './workIndex';
+import type { WorkIndexEntry, WorkOrigin } from './workIndex';
+
+/**
+ * bmssearch API 地址
+ */
+const BMSSEARCH_API_BASE = 'https://api.bmssearch.net/v1';
+
+/**
+ * bmssearch 作品页面地址
+ */
+const BMSSEARCH_WORK_URL = 'https://bmssearch.net/bmses';
+
+/**
+ * 查询缓存文件（应用数据目录下）
+ */
+const CACHE_FILE = 'library/bmssearch-cache.json';
+
+/**
+ * 查询缓存的有效期，过期后重新查询（查不到的结果可能之后被收录）
+ */
+const CACHE_TTL_MS = 30 * 24 * 60 * 60 * 1000;
+
+interface CachedLookup {
+  origin: WorkOrigin | null;
+  cachedAt: number;
+}
+
+/**
+ * 查询结果写入缓存文件前等待的时间（毫秒），期间的查询合并为一次写入
+ */
+const CACHE_SAVE_DELAY_MS = 5_000;
+
+/**
+ * API 返回的 BMS（只列出使用的字段）
+ *
+ * - `GET /bmses/sha256/{sha256}`：单个 BMS，没有收录时返回 404
+ * - `GET /bmses/search?keyword=...`：BMS 列表
+ */
+interface BmsSearchBms {
+  id: string;
+  title: string;
+  artist: string;
+  /** 首次发布的活动 */
+  exhibition: { name: string } | null;
+}
+
+function nonEmpty(value: unknown): string | undefined {
+  return typeof value === 'string' && value.trim() ? value.trim() : undefined;
+}
+
+/**
+ * 检查 API 返回的 BMS，格式不符时抛出错误（不缓存，下次重新查询）
+ */
+function parseBms(data: unknown): BmsSearchBms {
+  const item = data as Partial<BmsSearchBms> | null;
+  if (typeof item?.id !== 'string' || typeof item.title !== 'string') {
+    throw new Error(`bmssearch 返回的数据格式不符：${JSON.stringify(data).slice(0, 200)}`);
+  }
+  return {
+    id: item.id,
+    title: item.title,
+    artist: typeof item.artist === 'string' ? item.artist : '',
+    exhibition: item.exhibition ?? null,
+  };
+}
+
+function toOrigin(bms: BmsSearchBms): WorkOrigin {
+  return {
+    source: 'bmssearch',
+    id: bms.id,
+    title: nonEmpty(bms.title),
+    artist: nonEmpty(bms.artist),
+    event: nonEmpty(bms.exhibition?.name),
+    url: `${BMSSEARCH_WORK_URL}/${bms.id}`,
+    lookedUpAt: new Date().toISOString(),
+  };
+}
+
+/**
+ * bmssearch 查询客户端
+ */
+export class BmsSearchClient {
+  private static cache: Record<string, CachedLookup> | null = null;
+  private static dirty = false;
+  private static saveTimer: ReturnType<typeof setTimeout> | null = null;
+  private static downloader = new Downloader({ maxRetries: 1, hostIntervalMs: 500 });
+
+  private static async loadCache(): Promise<Record<string, CachedLookup>> {
+    this.cache ??= await readJsonFile<Record<string, CachedLookup>>(
+      await getAppDataPath(CACHE_FILE),
+      {}
+    );
+    return this.cache;
+  }
+
+  /**
+   * 立即写入缓存文件（没有新的查询结果时不写入）
+   */
+  static async saveCache(): Promise<void> {
+    if (this.saveTimer) {
+      clearTimeout(this.saveTimer);
+      this.saveTimer = null;
+    }
+    if (this.cache && this.dirty) {
+      this.dirty = false;
+      await writeJsonFile(await getAppDataPath(CACHE_FILE), this.cache);
+    }
+  }
+
+  /**
+   * 稍后写入缓存文件，批量查询时合并为一次写入
+   */
+  private static scheduleSave(): void {
+    this.dirty = true;
+    this.saveTimer ??= setTimeout(() => {
+      this.saveTimer = null;
+      this.saveCache().catch((error) =>
+        console.warn(' !_! Failed to save bmssearch cache:', error)
+      );
+    }, CACHE_SAVE_DELAY_MS);
+  }
+
+  private static async query(
+    key: string,
+    url: string,
+    parse: (data: unknown) => BmsSearchBms | null
+  ): Promise<WorkOrigin | null> {
+    const cache = await this.loadCache();
+    const cached = cache[key];
+    if (cached && Date.now() - cached.cachedAt < CACHE_TTL_MS) {
+      return cached.origin;
+    }
+
+    let origin: WorkOrigin | null = null;
+    try {
+      const bms = parse(JSON.parse(await this.downloader.fetchText(url)));
+      origin = bms ? toOrigin(bms) : null;
+    } catch (error) {
+      // 404 表示没有收录，其他错误不缓存，下次重新查询
+      if (!(error instanceof HttpStatusError && error.status === 404)) {
+        throw error;
+      }
+    }
+    cache[key] = { origin, cachedAt: Date.now() };
+    this.scheduleSave();
+    return origin;
+  }
+
+  /**
+   * 按谱面的 SHA-256 查询
+   */
+  static async lookupByHash(sha256: string): Promise<WorkOrigin | null> {
+    const url = `${BMSSEARCH_API_BASE}/bmses/sha256/${sha256}`;
+    return await this.query(`sha256:${sha256}`, url, parseBms);
+  }
+
+  /**
+   * 按标题和艺术家查询（取第一个结果，艺术家不一致时视为没有找到）
+   */
+  static async lookupByText(title: string, artist?: string): Promise<WorkOrigin | null> {
+    const params = new URLSearchParams({ keyword: title });
+    const key = `text:${title}\n${artist ?? ''}`;
+    const url = `${BMSSEARCH_API_BASE}/bmses/search?${params}`;
+    const origin = await this.query(key, url, (data) => {
+      if (!Array.isArray(data)) {
+        throw new Error('bmssearch 搜索结果不是列表');
+      }
+      return data.length > 0 ? parseBms(data[0]) : null;
+    });
+    const normalize = (name: string) => name.normalize('NFKC').toLowerCase().replace(/\s+/g, '');
+    if (origin && artist && origin.artist && normalize(origin.artist) !== normalize(artist)) {
+      return null;
+    }
+    return origin;
+  }
+
+  /**
+   * 按作品中各谱面的哈希依次查询，返回第一个查到的结果
+   */
+  static async lookupWorkDir(workDir: string): Promise<WorkOrigin | null> {
+    for (const chart of await getWorkDirChartFiles(workDir)) {
+      const hash = await HashService.hashFile(`${workDir}/${chart}`, HashAlgorithm.SHA256);
+      const origin = await this.lookupByHash(hash);
+      if (origin) {
+        return origin;
+      }
+    }
+    return null;
+  }
+}
+
+/**
+ * 从 bmssearch 补充作品来源
+ *
+ * @command
+ * @category library
+ * @dangerous false
+ * @name 从 bmssearch 补充作品信息
+ * @description 按谱面哈希（查不到时按标题和艺术家）在 bmssearch.net 上查询索引中的作品，记录规范的标题、艺术家和首次发布的活动
+ * @frontend true
+ *
+ * @param {string} rootDir - 根目录路径
+ * @param {boolean} onlyMissing - 只查询尚未查询过的作品
+ *
+ * @returns {Promise<number>} 查到信息的作品数
+ */
+export async function enrichFromBmsSearch(
+  rootDir: string,
+  onlyMissing: boolean = true
+): Promise<number> {
+  const index = await LibraryIndex.get(rootDir);
+  if (!index) {
+    throw new Error(`根目录尚未建立索引：${rootDir}`);
+  }
+
+  const updates = new Map<string, Partial<WorkIndexEntry>>();
+  try {
+    for (const work of index.works) {
+      if (onlyMissing && work.origin) {
+        continue;
+      }
+      try {
+        const origin =
+          (await BmsSearchClient.lookupWorkDir(work.path)) ??
+          (work.title ? await BmsSearchClient.lookupByText(work.title, work.artist) : null);
+        if (origin) {
+          updates.set(work.dirName, { origin });
+          const event = origin.event ? ` (${origin.event})` : '';
+          console.log(`${work.dirName}: ${origin.title} [${origin.artist}]${event}`);
+        }
+      } catch (error) {
+        console.warn(` !_! Failed to look up ${work.dirName}:`, error);
+      }
+    }
+  } finally {
+    await BmsSearchClient.saveCache();
+    await LibraryIndex.annotate(rootDir, updates);
+  }
+
+  console.log(`Found ${updates.size} works on bmssearch`);
+  return updates.size;
+}
@@ -0,0 +1,159 @@
+/**
+ * 作品库变化
+ *
+ * 比较根目录当前的索引和之前某次索引的快照，列出新增、删除、重命名和内容有变化的作品，
+ * 便于为发布的大包自动编写更新日志
+ */
+
+import { LibraryIndex, toSnapshot } from './workIndex';
+import type { LibrarySnapshot, WorkSnapshotEntry } from './workIndex';
+
+/**
+ * 作品重命名
+ */
+export interface LibraryRenamedWork {
+  from: string;
+  to: string;
+}
+
+/**
+ * 两次索引之间的变化
+ */
+export interface LibraryChanges {
+  rootDir: string;
+  /** 比较基准的索引时间 */
+  since: string;
+  /** 当前索引的时间 */
+  until: string;
+  added: string[];
+  removed: string[];
+  renamed: LibraryRenamedWork[];
+  /** 文件夹或索引字段有变化的作品 */
+  changed: string[];
+}
+
+/**
+ * 选择比较基准的快照
+ *
+ * @param since - "last"（上一次索引）或日期（取该时间之前最近的一次索引）
+ */
+function pickSnapshot(snapshots: LibrarySnapshot[], since: string): LibrarySnapshot | undefined {
+  if (since.trim().toLowerCase() === 'last' || since.trim() === '') {
+    return snapshots[snapshots.length - 1];
+  }
+
+  const time = Date.parse(since);
+  if (Number.isNaN(time)) {
+    throw new Error(`无法解析的时间：${since}（应为 "last" 或日期，如 2024-01-31）`);
+  }
+  const before = snapshots.filter((snapshot) => Date.parse(snapshot.indexedAt) <= time);
+  if (before.length === 0 && snapshots.length > 0) {
+    console.warn(` !_! No index snapshot before ${since}, using the oldest one.`);
+    return snapshots[0];
+  }
+  return before[before.length - 1];
+}
+
+/**
+ * 重命名的匹配键：标题、艺术家和谱面数都相同
+ */
+function renameKey(work: WorkSnapshotEntry): string | null {
+  if (!work.title) {
+    return null;
+  }
+  return `${work.title}\u0000${work.artist ?? ''}\u0000${work.chartCount}`;
+}
+
+/**
+ * 列出作品库的变化
+ *
+ * @command
+ * @category library
+ * @dangerous false
+ * @name 查看作品库变化
+ * @description 比较当前索引与上一次（或指定日期前）的索引快照，列出新增、删除、重命名和内容有变化的作品，可用于编写更新日志
+ * @frontend true
+ *
+ * @param {string} rootDir - 根目录路径
+ * @param {string} since - 比较基准："last" 表示上一次索引，或填写日期（如 2024-01-31）
+ * @param {boolean} refresh - 先更新索引再比较
+ *
+ * @returns {Promise<LibraryChanges>} 作品的变化
+ */
+export async function changes(
+  rootDir: string,
+  since: string = 'last',
+  refresh: boolean = false
+): Promise<LibraryChanges> {
+  if (refresh) {
+    await LibraryIndex.update(rootDir);
+  }
+
+  const current = await LibraryIndex.get(rootDir);
+  if (!current) {
+    throw new Error(`根目录尚未建立索引：${rootDir}`);
+  }
+  const base = pickSnapshot(await LibraryIndex.getSnapshots(rootDir), since);
+  if (!base) {
+    throw new Error(`没有之前的索引快照，请在整理后再次更新索引：${rootDir}`);
+  }
+
+  const before = new Map(base.works.map((work) => [work.dirName, work]));
+  const after = new Map(toSnapshot(current).works.map((work) => [work.dirName, work]));
+  const result: LibraryChanges = {
+    rootDir,
+    since: base.indexedAt,
+    until: current.indexedAt,
+    added: [],
+    removed: [],
+    renamed: [],
+    changed: [],
+  };
+
+  for (const [dirName, work] of after) {
+    const old = before.get(dirName);
+    if (!old) {
+      result.added.push(dirName);
+    } else if (old.modifiedAt !== work.modifiedAt || old.signature !== work.signature) {
+      result.changed.push(dirName);
+    }
+  }
+  for (const dirName of before.keys()) {
+    if (!after.has(dirName)) {
+      result.removed.push(dirName);
+    }
+  }
+
+  // 删除和新增的作品中标题、艺术家和谱面数都相同的视为重命名
+  const removedByKey = new Map<string, string[]>();
+  for (const dirName of result.removed) {
+    const key = renameKey(before.get(dirName)!);
+    if (key) {
+      removedByKey.set(key, [...(removedByKey.get(key) ?? []), dirName]);
+    }
+  }
+  for (const dirName of [...result.added]) {
+    const key = renameKey(after.get(dirName)!);
+    const from = key ? removedByKey.get(key)?.shift() : undefined;
+    if (from) {
+      result.renamed.push({ from, to: dirName });
+      result.added.splice(result.added.indexOf(dirName), 1);
+      result.removed.splice(result.removed.indexOf(from), 1);
+    }
+  }
+
+  for (const list of [result.added, result.removed, result.changed]) {
+    list.sort((a, b) => a.localeCompare(b));
+  }
+
+  console.log(`Changes in ${rootDir} since ${result.since}:`);
+  result.added.forEach((dirName) => console.log(`+ ${dirName}`));
+  result.removed.forEach((dirName) => console.log(`- ${dirName}`));
+  result.renamed.forEach(({ from, to }) => console.log(`> ${from} -> ${to}`));
+  result.changed.forEach((dirName) => console.log(`~ ${dirName}`));
+  console.log(
+    `${result.added.length} added, ${result.removed.length} removed, ` +
+      `${result.renamed.length} renamed, ${result.changed.length} changed`
+  );
+  return result;
+}
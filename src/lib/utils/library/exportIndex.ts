@@ -0,0 +1,95 @@
+/**
+ * 作品索引导出
+ *
+ * 将根目录的作品索引导出为 CSV 或 JSON，供编写活动一览表或难度表使用；
+ * 除标题、艺术家和等级外还包含谱面中的 #SUBARTIST、#COMMENT 和扩展字段 %URL、%EMAIL
+ */
+
+import { exists, writeTextFile } from '@tauri-apps/plugin-fs';
+import { getFileExtension } from '../fs/path';
+import { escapeCsvField } from '../root/journal';
+import { LibraryIndex } from './workIndex';
+import type { WorkIndexEntry } from './workIndex';
+
+/**
+ * 导出的列
+ */
+const EXPORT_COLUMNS: [string, (work: WorkIndexEntry) => string | number | undefined][] = [
+  ['dirName', (work) => work.dirName],
+  ['title', (work) => work.title],
+  ['subtitle', (work) => work.subtitle],
+  ['artist', (work) => work.artist],
+  ['subartist', (work) => work.subartist],
+  ['genre', (work) => work.genre],
+  ['bpm', (work) => work.bpm],
+  ['keymodes', (work) => work.keymodes.join(' ')],
+  ['levels', (work) => work.levels.join(' ')],
+  ['chartCount', (work) => work.chartCount],
+  ['bga', (work) => work.bga?.kind ?? (work.hasBga ? 'yes' : 'none')],
+  [
+    'bgaResolution',
+    (work) => (work.bga?.width ? `${work.bga.width}x${work.bga.height}` : undefined),
+  ],
+  [
+    'bgaCoverage',
+    (work) =>
+      work.bga?.coverage !== undefined ? Math.round(work.bga.coverage * 100) : undefined,
+  ],
+  ['url', (work) => work.url],
+  ['email', (work) => work.email],
+  ['comment', (work) => work.comment],
+];
+
+/**
+ * 导出作品索引
+ *
+ * @command
+ * @category library
+ * @dangerous false
+ * @name 导出作品索引
+ * @description 将根目录的作品索引（含副艺术家、注释、作者 URL 和邮箱）导出为 CSV 或 JSON（按文件扩展名），用于编写活动一览表或难度表；不覆盖已有文件
+ * @frontend true
+ *
+ * @param {string} rootDir - 根目录路径
+ * @param {string} filePath - 输出文件路径（.json 输出 JSON，其他输出 CSV；文件已存在时报错）
+ * @param {boolean} refresh - 先强制重新索引（旧版索引中没有注释和 URL 等字段）
+ *
+ * @returns {Promise<number>} 导出的作品数
+ */
+export async function exportIndex(
+  rootDir: string,
+  filePath: string,
+  refresh: boolean = false
+): Promise<number> {
+  if (await exists(filePath)) {
+    throw new Error(`输出文件已存在：${filePath}`);
+  }
+  if (refresh) {
+    await LibraryIndex.update(rootDir, true);
+  }
+  let index = await LibraryIndex.get(rootDir);
+  if (!index) {
+    await LibraryIndex.update(rootDir);
+    index = await LibraryIndex.get(rootDir);
+  }
+  const works = [...(index?.works ?? [])].sort((a, b) => a.dirName.localeCompare(b.dirName));
+
+  if (getFileExtension(filePath) === 'json') {
+    const rows = works.map((work) =>
+      Object.fromEntries(EXPORT_COLUMNS.map(([key, read]) => [key, read(work) ?? null]))
+    );
+    await writeTextFile(filePath, JSON.stringify(rows, null, 2));
+  } else {
+    const lines = [
+      EXPORT_COLUMNS.map(([key]) => key).join(','),
+      ...works.map((work) =>
+        EXPORT_COLUMNS.map(([, read]) => escapeCsvField(String(read(work) ?? ''))).join(',')
+      ),
+    ];
+    // 带 BOM，可直接用表格软件打开
+    await writeTextFile(filePath, `\ufeff${lines.join('\r\n')}\r\n`);
+  }
+
+  console.log(`Exported ${works.length} works to ${filePath}`);
+  return works.length;
+}
@@ -0,0 +1,16 @@
+/**
+ * 作品库模块
+ * 导出所有公共 API
+ */
+
+export * from './bga';
+export * from './bmsSearch';
+export * from './changes';
+export * from './exportIndex';
+export * from './kana';
+export * from './query';
+export * from './scrub';
+export * from './search';
+export * from './sharedSound';
+export * from './tags';
+export * from './workIndex';
@@ -0,0 +1,109 @@
+/**
+ * 假名罗马字转换
+ *
+ * 用于搜索时把假名和罗马字统一成同一种写法，使 "aikotoba" 可以匹配 "アイコトバ"。
+ * 汉字没有读音信息，保持原样
+ */
+
+/**
+ * 平假名 → 罗马字（拗音在前，优先匹配两个字符）
+ */
+const KANA_ROMAJI: Record<string, string> = Object.fromEntries(
+  `
+  きゃ:kya きゅ:kyu きょ:kyo しゃ:sha しゅ:shu しょ:sho しぇ:she ちゃ:cha
+  ちゅ:chu ちょ:cho ちぇ:che にゃ:nya にゅ:nyu にょ:nyo ひゃ:hya ひゅ:hyu
+  ひょ:hyo みゃ:mya みゅ:myu みょ:myo りゃ:rya りゅ:ryu りょ:ryo ぎゃ:gya
+  ぎゅ:gyu ぎょ:gyo じゃ:ja じゅ:ju じょ:jo じぇ:je びゃ:bya びゅ:byu
+  びょ:byo ぴゃ:pya ぴゅ:pyu ぴょ:pyo ふぁ:fa ふぃ:fi ふぇ:fe ふぉ:fo てぃ:ti
+  でぃ:di とぅ:tu どぅ:du うぃ:wi うぇ:we うぉ:wo ゔぁ:va ゔぃ:vi ゔぇ:ve
+  ゔぉ:vo つぁ:tsa つぃ:tsi つぇ:tse つぉ:tso あ:a い:i う:u え:e お:o
+  か:ka き:ki く:ku け:ke こ:ko さ:sa し:shi す:su せ:se そ:so た:ta
+  ち:chi つ:tsu て:te と:to な:na に:ni ぬ:nu ね:ne の:no は:ha ひ:hi
+  ふ:fu へ:he ほ:ho ま:ma み:mi む:mu め:me も:mo や:ya ゆ:yu よ:yo
+  ら:ra り:ri る:ru れ:re ろ:ro わ:wa ゐ:i ゑ:e を:o ん:n が:ga ぎ:gi
+  ぐ:gu げ:ge ご:go ざ:za じ:ji ず:zu ぜ:ze ぞ:zo だ:da ぢ:ji づ:zu
+  で:de ど:do ば:ba び:bi ぶ:bu べ:be ぼ:bo ぱ:pa ぴ:pi ぷ:pu ぺ:pe
+  ぽ:po ゔ:vu ぁ:a ぃ:i ぅ:u ぇ:e ぉ:o ゃ:ya ゅ:yu ょ:yo ゎ:wa
+  `
+    .trim()
+    .split(/\s+/)
+    .map((pair) => pair.split(':'))
+);
+
+/**
+ * 罗马字的不同拼写方式统一为训令式（双方使用同样的转换，只需保持一致）
+ */
+const ROMAJI_VARIANTS: [RegExp, string][] = [
+  [/jy/g, 'j'],
+  [/shi/g, 'si'],
+  [/chi/g, 'ti'],
+  [/tsu/g, 'tu'],
+  [/fu/g, 'hu'],
+  [/ji/g, 'zi'],
+  [/sh/g, 'sy'],
+  [/ch/g, 'ty'],
+  [/j/g, 'zy'],
+  // 长音：ou、oo、uu 等统一为单个元音
+  [/ou/g, 'o'],
+  [/([aeiou])\1/g, '$1'],
+  [/[āâ]/g, 'a'],
+  [/[īî]/g, 'i'],
+  [/[ūû]/g, 'u'],
+  [/[ēê]/g, 'e'],
+  [/[ōô]/g, 'o'],
+];
+
+/**
+ * 片假名转平假名
+ */
+function katakanaToHiragana(text: string): string {
+  return text.replace(/[ァ-ヶ]/g, (char) => String.fromCharCode(char.charCodeAt(0) - 0x60));
+}
+
+/**
+ * 假名转罗马字（非假名字符保持不变）
+ */
+export function kanaToRomaji(text: string): string {
+  const hiragana = katakanaToHiragana(text);
+  let result = '';
+  let doubleNext = false;
+
+  for (let i = 0; i < hiragana.length; i++) {
+    const char = hiragana[i];
+    if (char === 'っ') {
+      doubleNext = true;
+      continue;
+    }
+    if (char === 'ー') {
+      // 长音在统一拼写时会被合并，这里直接省略
+      continue;
+    }
+
+    const pair = hiragana.slice(i, i + 2);
+    let romaji = KANA_ROMAJI[pair];
+    if (romaji) {
+      i++;
+    } else {
+      romaji = KANA_ROMAJI[char] ?? char;
+    }
+
+    if (doubleNext && /^[a-z]/.test(romaji)) {
+      romaji = (romaji.startsWith('ch') ? 't' : romaji[0]) + romaji;
+    }
+    doubleNext = false;
+    result += romaji;
+  }
+
+  return result;
+}
+
+/**
+ * 统一搜索文本：全角转半角、转小写、假名转罗马字、统一罗马字拼写，并去掉空白和符号
+ */
+export function normalizeSearchText(text: string): string {
+  let result = kanaToRomaji(text.normalize('NFKC').toLowerCase());
+  for (const [pattern, replacement] of ROMAJI_VARIANTS) {
+    result = result.replace(pattern, replacement);
+  }
+  return result.replace(/[^\p{L}\p{N}]/gu, '');
+}
@@ -0,0 +1,194 @@
+/**
+ * 作品库查询
+ *
+ * 在作品索引上筛选、排序并分页，前端每次只取一页结果，
+ * 大型作品库也不需要一次性加载全部作品
+ */
+
+import { LibraryBgaFilter, LibrarySortKey } from '../../types/enums';
+import { WorkTagStore, parseTags } from './tags';
+import { LibraryIndex } from './workIndex';
+import type { WorkIndexEntry } from './workIndex';
+
+/**
+ * 单页最多返回的作品数
+ */
+const MAX_PAGE_SIZE = 500;
+
+/**
+ * 视为完整 BGA 的覆盖比例（开头和结尾允许留几秒空白）
+ */
+const FULL_LENGTH_BGA_COVERAGE = 0.9;
+
+/**
+ * 作品是否有 BGA（统计过 BGA 时以统计结果为准）
+ */
+function workHasBga(work: WorkIndexEntry): boolean {
+  return work.bga ? work.bga.kind !== 'none' : work.hasBga;
+}
+
+/**
+ * 查询结果中的作品（附带标签）
+ */
+export interface LibraryWork extends WorkIndexEntry {
+  tags: string[];
+}
+
+/**
+ * 作品库查询结果
+ */
+export interface LibraryQueryResult {
+  /** 符合条件的作品总数 */
+  total: number;
+  offset: number;
+  limit: number;
+  works: LibraryWork[];
+}
+
+/**
+ * 排序用的字段值（缺失的值排在最后）
+ */
+function getSortValue(work: WorkIndexEntry, sortBy: LibrarySortKey): string | number | undefined {
+  switch (sortBy) {
+    case LibrarySortKey.Title:
+      return work.title?.toLowerCase();
+    case LibrarySortKey.Artist:
+      return work.artist?.toLowerCase();
+    case LibrarySortKey.Level:
+      return work.maxLevel;
+    case LibrarySortKey.Bpm:
+      return work.bpm;
+    case LibrarySortKey.Modified:
+      return work.modifiedAt;
+    default:
+      return work.dirName.toLowerCase();
+  }
+}
+
+function compareWorks(
+  a: WorkIndexEntry,
+  b: WorkIndexEntry,
+  sortBy: LibrarySortKey,
+  descending: boolean
+): number {
+  const valueA = getSortValue(a, sortBy);
+  const valueB = getSortValue(b, sortBy);
+
+  if (valueA === undefined || valueB === undefined) {
+    if (valueA === valueB) {
+      return a.dirName.localeCompare(b.dirName);
+    }
+    return valueA === undefined ? 1 : -1;
+  }
+
+  const order =
+    typeof valueA === 'number' && typeof valueB === 'number'
+      ? valueA - valueB
+      : String(valueA).localeCompare(String(valueB));
+  return (descending ? -order : order) || a.dirName.localeCompare(b.dirName);
+}
+
+/**
+ * 查询作品库
+ *
+ * @command
+ * @category library
+ * @dangerous false
+ * @name 查询作品库
+ * @description 按标题、艺术家、键位、等级范围、标签和 BGA 筛选已索引的作品，支持排序和分页
+ * @frontend true
+ *
+ * @param {string} rootDir - 根目录路径（留空查询所有已索引的根目录）
+ * @param {string} title - 标题包含的文字（不区分大小写，也匹配文件夹名）
+ * @param {string} artist - 艺术家包含的文字（不区分大小写）
+ * @param {string} keymode - 键位（如 7K、14K）
+ * @param {number} minLevel - 最低等级
+ * @param {number} maxLevel - 最高等级
+ * @param {string} tags - 必须包含的标签（逗号分隔）
+ * @param {LibraryBgaFilter} bga - BGA 筛选（完整 BGA 需要先统计作品库 BGA）
+ * @param {LibrarySortKey} sortBy - 排序字段
+ * @param {boolean} descending - 是否降序
+ * @param {number} offset - 跳过的作品数
+ * @param {number} limit - 返回的作品数
+ *
+ * @returns {Promise<LibraryQueryResult>} 当前页的作品和符合条件的总数
+ */
+export async function query(
+  rootDir: string = '',
+  title?: string,
+  artist?: string,
+  keymode?: string,
+  minLevel?: number,
+  maxLevel?: number,
+  tags?: string,
+  bga: LibraryBgaFilter = LibraryBgaFilter.Any,
+  sortBy: LibrarySortKey = LibrarySortKey.DirName,
+  descending: boolean = false,
+  offset: number = 0,
+  limit: number = 100
+): Promise<LibraryQueryResult> {
+  const roots = rootDir ? [rootDir] : await LibraryIndex.getIndexedRoots();
+  let works: WorkIndexEntry[] = [];
+  for (const root of roots) {
+    let index = await LibraryIndex.get(root);
+    if (!index) {
+      // 首次查询时自动建立索引
+      await LibraryIndex.update(root);
+      index = await LibraryIndex.get(root);
+    }
+    works = works.concat(index?.works ?? []);
+  }
+
+  const allTags = await WorkTagStore.getAll();
+  const titleText = title?.trim().toLowerCase();
+  const artistText = artist?.trim().toLowerCase();
+  const keymodeText = keymode?.trim().toUpperCase();
+  const requiredTags = parseTags(tags);
+
+  const matched = works.filter((work) => {
+    if (
+      titleText &&
+      !work.title?.toLowerCase().includes(titleText) &&
+      !work.dirName.toLowerCase().includes(titleText)
+    ) {
+      return false;
+    }
+    if (artistText && !work.artist?.toLowerCase().includes(artistText)) {
+      return false;
+    }
+    if (keymodeText && !work.keymodes.includes(keymodeText)) {
+      return false;
+    }
+    // 等级范围与作品的等级区间有交集即可
+    if (minLevel !== undefined && (work.maxLevel === undefined || work.maxLevel < minLevel)) {
+      return false;
+    }
+    if (maxLevel !== undefined && (work.minLevel === undefined || work.minLevel > maxLevel)) {
+      return false;
+    }
+    if (bga === LibraryBgaFilter.With && !workHasBga(work)) {
+      return false;
+    }
+    if (bga === LibraryBgaFilter.Without && workHasBga(work)) {
+      return false;
+    }
+    if (
+      bga === LibraryBgaFilter.FullLength &&
+      (work.bga?.coverage ?? 0) < FULL_LENGTH_BGA_COVERAGE
+    ) {
+      return false;
+    }
+    const workTags = allTags[work.path] ?? [];
+    return requiredTags.every((tag) => workTags.includes(tag));
+  });
+
+  matched.sort((a, b) => compareWorks(a, b, sortBy, descending));
+
+  const start = Math.max(0, Math.floor(offset));
+  const pageSize = Math.min(MAX_PAGE_SIZE, Math.max(1, Math.floor(limit)));
+  const page = matched
+    .slice(start, start + pageSize)
+    .map((work) => ({ ...work, tags: allTags[work.path] ?? [] }));
+
+  return { total: matched.length, offset: start, limit: pageSize, works: page };
+}
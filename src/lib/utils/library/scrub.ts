@@ -0,0 +1,285 @@
+/**
+ * 完整性快照与位衰减检测
+ *
+ * 记录根目录下每个文件的大小、修改时间和哈希，之后定期重新读取校验：
+ * 修改时间和大小都没变、内容却变了的文件视为位衰减（老化硬盘上的静默损坏）。
+ * 基线和校验报告保存在应用数据目录中
+ */
+
+import { readDir, stat } from '@tauri-apps/plugin-fs';
+import { HashService } from '../fs/hash';
+import { IgnoreList } from '../fs/ignore';
+import { ConcurrencyPool } from '../media/concurrency';
+import { getAppDataPath, getAppDataPathForDir, readJsonFile, writeJsonFile } from '../storage';
+
+/**
+ * 基线中的文件记录
+ */
+interface ScrubFileRecord {
+  size: number;
+  mtime: number;
+  hash: string;
+}
+
+/**
+ * 根目录的完整性基线
+ */
+interface ScrubBaseline {
+  rootDir: string;
+  algorithm: string;
+  /** 相对根目录的路径 -> 文件记录 */
+  files: Record<string, ScrubFileRecord>;
+}
+
+/**
+ * 根目录的校验计划
+ */
+export interface ScrubSchedule {
+  rootDir: string;
+  /** 校验间隔（天），0 表示不自动校验 */
+  intervalDays: number;
+  lastRunAt?: string;
+  /** 最近一次报告的路径 */
+  lastReport?: string;
+}
+
+/**
+ * 内容损坏的文件
+ */
+export interface CorruptedFile {
+  path: string;
+  size: number;
+  expectedHash: string;
+  actualHash: string;
+}
+
+/**
+ * 校验报告
+ */
+export interface ScrubReport {
+  rootDir: string;
+  algorithm: string;
+  startedAt: string;
+  finishedAt: string;
+  /** 校验的文件数 */
+  checked: number;
+  /** 新加入基线的文件 */
+  added: string[];
+  /** 修改时间或大小变化、正常更新的文件 */
+  modified: string[];
+  /** 基线中存在但已删除的文件 */
+  missing: string[];
+  /** 修改时间和大小未变但内容变化的文件（疑似位衰减） */
+  corrupted: CorruptedFile[];
+  /** 读取失败的文件 */
+  unreadable: string[];
+  /** 报告文件路径 */
+  reportPath?: string;
+}
+
+async function getSchedulePath(): Promise<string> {
+  return await getAppDataPath('library/scrub/schedule.json');
+}
+
+/**
+ * 递归列出根目录下的文件（跳过忽略列表中的文件）
+ */
+async function listFiles(dirPath: string, prefix: string = ''): Promise<string[]> {
+  const files: string[] = [];
+  for (const entry of await readDir(dirPath)) {
+    if (!entry.name || IgnoreList.isIgnored(entry.name)) {
+      continue;
+    }
+    const relative = prefix ? `${prefix}/${entry.name}` : entry.name;
+    if (entry.isDirectory) {
+      files.push(...(await listFiles(`${dirPath}/${entry.name}`, relative)));
+    } else {
+      files.push(relative);
+    }
+  }
+  return files;
+}
+
+/**
+ * 校验根目录的完整性
+ *
+ * 首次运行时建立基线；之后重新计算所有文件的哈希并与基线比较
+ *
+ * @command
+ * @category library
+ * @dangerous false
+ * @name 完整性校验
+ * @description 重新读取根目录下所有文件并与上次记录的哈希比较，找出修改时间未变但内容已变的文件（位衰减）
+ * @frontend true
+ *
+ * @param {string} rootDir - 根目录路径
+ * @param {boolean} acceptChanges - 将损坏文件的当前内容记入基线（确认已处理后使用）
+ *
+ * @returns {Promise<ScrubReport>} 校验报告
+ */
+export async function scrub(rootDir: string, acceptChanges: boolean = false): Promise<ScrubReport> {
+  const algorithm = HashService.getDefaultAlgorithm();
+  const baselinePath = await getAppDataPathForDir('library/scrub', rootDir);
+  const stored = await readJsonFile<ScrubBaseline | null>(baselinePath, null);
+  // 算法变化后旧哈希无法比较，重新建立基线
+  const baseline: ScrubBaseline =
+    stored?.algorithm === algorithm ? stored : { rootDir, algorithm, files: {} };
+
+  const report: ScrubReport = {
+    rootDir,
+    algorithm,
+    startedAt: new Date().toISOString(),
+    finishedAt: '',
+    checked: 0,
+    added: [],
+    modified: [],
+    missing: [],
+    corrupted: [],
+    unreadable: [],
+  };
+
+  const files = await listFiles(rootDir);
+  const nextFiles: Record<string, ScrubFileRecord> = {};
+  const pool = new ConcurrencyPool<void>(32);
+
+  await Promise.all(
+    files.map((path) =>
+      pool.add(async () => {
+        const fullPath = `${rootDir}/${path}`;
+        try {
+          const info = await stat(fullPath);
+          const record: ScrubFileRecord = {
+            size: info.size,
+            mtime: info.mtime?.getTime() ?? 0,
+            hash: await HashService.verifyFile(fullPath, algorithm),
+          };
+          report.checked++;
+
+          const previous = baseline.files[path];
+          if (!previous) {
+            report.added.push(path);
+          } else if (previous.size !== record.size || previous.mtime !== record.mtime) {
+            report.modified.push(path);
+          } else if (previous.hash !== record.hash) {
+            report.corrupted.push({
+              path,
+              size: record.size,
+              expectedHash: previous.hash,
+              actualHash: record.hash,
+            });
+            if (!acceptChanges) {
+              // 保留原哈希，下次校验仍会报告
+              record.hash = previous.hash;
+            }
+          }
+          nextFiles[path] = record;
+        } catch (error) {
+          console.warn(` !_! Failed to verify ${fullPath}:`, error);
+          report.unreadable.push(path);
+          if (baseline.files[path]) {
+            nextFiles[path] = baseline.files[path];
+          }
+        }
+      })
+    )
+  );
+
+  report.missing = Object.keys(baseline.files).filter(
+    (path) => !(path in nextFiles) && !report.unreadable.includes(path)
+  );
+  for (const list of [report.added, report.modified, report.missing, report.unreadable]) {
+    list.sort();
+  }
+  report.corrupted.sort((a, b) => a.path.localeCompare(b.path));
+  report.finishedAt = new Date().toISOString();
+
+  await writeJsonFile(baselinePath, { rootDir, algorithm, files: nextFiles });
+  report.reportPath = await getAppDataPath(
+    `library/scrub/reports/${report.startedAt.replace(/[:.]/g, '-')}.json`
+  );
+  await writeJsonFile(report.reportPath, report);
+
+  const schedules = await readJsonFile<Record<string, ScrubSchedule>>(await getSchedulePath(), {});
+  schedules[rootDir] = {
+    ...(schedules[rootDir] ?? { rootDir, intervalDays: 0 }),
+    lastRunAt: report.finishedAt,
+    lastReport: report.reportPath,
+  };
+  await writeJsonFile(await getSchedulePath(), schedules);
+
+  for (const file of report.corrupted) {
+    console.error(` !_! Content changed without mtime change: ${rootDir}/${file.path}`);
+  }
+  console.log(
+    `Scrubbed ${rootDir}: ${report.checked} files, ${report.added.length} new, ` +
+      `${report.modified.length} modified, ${report.missing.length} missing, ` +
+      `${report.corrupted.length} corrupted`
+  );
+  return report;
+}
+
+/**
+ * 设置根目录的定期校验
+ *
+ * @command
+ * @category library
+ * @dangerous false
+ * @name 设置定期完整性校验
+ * @description 设置根目录的完整性校验间隔（天），0 表示取消；到期的校验由“运行到期的完整性校验”执行
+ * @frontend true
+ *
+ * @param {string} rootDir - 根目录路径
+ * @param {number} intervalDays - 校验间隔（天）
+ *
+ * @returns {Promise<ScrubSchedule>} 校验计划
+ */
+export async function setScrubSchedule(
+  rootDir: string,
+  intervalDays: number
+): Promise<ScrubSchedule> {
+  const schedulePath = await getSchedulePath();
+  const schedules = await readJsonFile<Record<string, ScrubSchedule>>(schedulePath, {});
+  const schedule: ScrubSchedule = {
+    ...schedules[rootDir],
+    rootDir,
+    intervalDays: Math.max(0, intervalDays),
+  };
+  schedules[rootDir] = schedule;
+  await writeJsonFile(schedulePath, schedules);
+  return schedule;
+}
+
+/**
+ * 运行所有到期的定期校验
+ *
+ * @command
+ * @category library
+ * @dangerous false
+ * @name 运行到期的完整性校验
+ * @description 对所有已到校验间隔的根目录运行完整性校验（可在启动时或定时调用）
+ * @frontend true
+ *
+ * @returns {Promise<ScrubReport[]>} 各根目录的校验报告
+ */
+export async function scrubDue(): Promise<ScrubReport[]> {
+  const schedules = await readJsonFile<Record<string, ScrubSchedule>>(await getSchedulePath(), {});
+  const now = Date.now();
+  const reports: ScrubReport[] = [];
+
+  for (const schedule of Object.values(schedules)) {
+    if (schedule.intervalDays <= 0) {
+      continue;
+    }
+    const lastRun = schedule.lastRunAt ? new Date(schedule.lastRunAt).getTime() : 0;
+    if (now - lastRun < schedule.intervalDays * 24 * 60 * 60 * 1000) {
+      continue;
+    }
+    try {
+      reports.push(await scrub(schedule.rootDir));
+    } catch (error) {
+      console.error(` !_! Scheduled scrub failed for ${schedule.rootDir}:`, error);
+    }
+  }
+
+  return reports;
+}
@@ -0,0 +1,209 @@
+/**
+ * 作品库全文搜索
+ *
+ * 在作品索引的标题、副标题、艺术家、流派和文件夹名上建立三字组倒排索引。
+ * 文本先经过假名罗马字转换，搜索 "aikotoba" 可以找到 "アイコトバ"
+ */
+
+import { normalizeSearchText } from './kana';
+import { WorkTagStore } from './tags';
+import { LibraryIndex } from './workIndex';
+import type { LibraryWork } from './query';
+import type { WorkIndexEntry } from './workIndex';
+
+/**
+ * 参与搜索的字段及权重
+ */
+const SEARCH_FIELDS: [keyof WorkIndexEntry, number][] = [
+  ['title', 5],
+  ['artist', 3],
+  ['subtitle', 2],
+  ['dirName', 2],
+  ['genre', 1],
+];
+
+/**
+ * 搜索结果（附带匹配得分）
+ */
+export interface LibrarySearchHit extends LibraryWork {
+  score: number;
+}
+
+/**
+ * 单个作品的搜索文档
+ */
+interface SearchDocument {
+  work: WorkIndexEntry;
+  /** 各字段统一后的文本（与 SEARCH_FIELDS 顺序相同） */
+  fields: string[];
+}
+
+/**
+ * 取文本中的三字组
+ */
+function trigrams(text: string): Set<string> {
+  const grams = new Set<string>();
+  for (let i = 0; i + 3 <= text.length; i++) {
+    grams.add(text.slice(i, i + 3));
+  }
+  return grams;
+}
+
+/**
+ * 全文搜索索引（由作品索引派生，只保存在内存中）
+ */
+export class LibrarySearchIndex {
+  private static documents: SearchDocument[] = [];
+  /** 三字组 → 包含它的文档序号 */
+  private static postings = new Map<string, number[]>();
+  private static builtRevision = -1;
+
+  /**
+   * 作品索引变化后重建搜索索引
+   */
+  private static async ensureBuilt(): Promise<void> {
+    if (this.builtRevision === LibraryIndex.revision) {
+      return;
+    }
+
+    const revision = LibraryIndex.revision;
+    const documents: SearchDocument[] = [];
+    const postings = new Map<string, number[]>();
+
+    for (const rootDir of await LibraryIndex.getIndexedRoots()) {
+      for (const work of (await LibraryIndex.get(rootDir))?.works ?? []) {
+        const fields = SEARCH_FIELDS.map(([key]) => normalizeSearchText(String(work[key] ?? '')));
+        const docId = documents.length;
+        documents.push({ work, fields });
+
+        const grams = new Set(fields.flatMap((field) => [...trigrams(field)]));
+        for (const gram of grams) {
+          const list = postings.get(gram);
+          if (list) {
+            list.push(docId);
+          } else {
+            postings.set(gram, [docId]);
+          }
+        }
+      }
+    }
+
+    this.documents = documents;
+    this.postings = postings;
+    this.builtRevision = revision;
+  }
+
+  /**
+   * 通过三字组筛选可能包含该词的文档，词少于三个字符时返回 null（需要全部检查）
+   */
+  private static candidates(term: string): Set<number> | null {
+    const grams = [...trigrams(term)];
+    if (grams.length === 0) {
+      return null;
+    }
+
+    const lists = grams
+      .map((gram) => this.postings.get(gram) ?? [])
+      .sort((a, b) => a.length - b.length);
+    let result = new Set(lists[0]);
+    for (const list of lists.slice(1)) {
+      const next = new Set(list);
+      result = new Set([...result].filter((docId) => next.has(docId)));
+    }
+    return result;
+  }
+
+  /**
+   * 搜索作品，空格分隔的每个词都必须匹配某个字段
+   *
+   * @param rootDir - 只搜索该根目录（空字符串搜索所有根目录）
+   */
+  static async search(
+    text: string,
+    rootDir: string,
+    limit: number
+  ): Promise<{ work: WorkIndexEntry; score: number }[]> {
+    await this.ensureBuilt();
+
+    const terms = text
+      .split(/\s+/)
+      .map((term) => normalizeSearchText(term))
+      .filter((term) => term.length > 0);
+    if (terms.length === 0) {
+      return [];
+    }
+
+    let docIds: Set<number> | null = null;
+    for (const term of terms) {
+      const termDocs = this.candidates(term);
+      if (termDocs) {
+        docIds = docIds ? new Set([...docIds].filter((docId) => termDocs.has(docId))) : termDocs;
+      }
+    }
+
+    const hits: { work: WorkIndexEntry; score: number }[] = [];
+    const ids = docIds ? [...docIds] : this.documents.map((_, docId) => docId);
+
+    for (const docId of ids) {
+      const { work, fields } = this.documents[docId];
+      if (rootDir && work.rootDir !== rootDir) {
+        continue;
+      }
+
+      let score = 0;
+      const matchedAll = terms.every((term) => {
+        let termScore = 0;
+        fields.forEach((field, index) => {
+          if (field.includes(term)) {
+            const weight = SEARCH_FIELDS[index][1];
+            // 字段开头匹配或完全匹配时加分
+            termScore += weight * (field === term ? 3 : field.startsWith(term) ? 2 : 1);
+          }
+        });
+        score += termScore;
+        return termScore > 0;
+      });
+
+      if (matchedAll) {
+        hits.push({ work, score });
+      }
+    }
+
+    return hits
+      .sort((a, b) => b.score - a.score || a.work.dirName.localeCompare(b.work.dirName))
+      .slice(0, limit);
+  }
+}
+
+/**
+ * 全文搜索作品库
+ *
+ * @command
+ * @category library
+ * @dangerous false
+ * @name 搜索作品库
+ * @description 在已索引作品的标题、副标题、艺术家、流派和文件夹名中搜索，假名可以用罗马字搜索
+ * @frontend true
+ *
+ * @param {string} text - 搜索文字（空格分隔多个词）
+ * @param {string} rootDir - 根目录路径（留空搜索所有已索引的根目录）
+ * @param {number} limit - 最多返回的作品数
+ *
+ * @returns {Promise<LibrarySearchHit[]>} 按匹配程度排序的作品
+ */
+export async function search(
+  text: string,
+  rootDir: string = '',
+  limit: number = 50
+): Promise<LibrarySearchHit[]> {
+  if (rootDir && !(await LibraryIndex.get(rootDir))) {
+    // 首次搜索时自动建立索引
+    await LibraryIndex.update(rootDir);
+  }
+
+  const allTags = await WorkTagStore.getAll();
+  const hits = await LibrarySearchIndex.search(text, rootDir, Math.max(1, Math.floor(limit)));
+  console.log(`Found ${hits.length} works for "${text}"`);
+
+  return hits.map(({ work, score }) => ({ ...work, tags: allTags[work.path] ?? [], score }));
+}
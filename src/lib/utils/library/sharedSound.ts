@@ -0,0 +1,246 @@
+/**
+ * 跨作品的共用音源检测
+ *
+ * 同一艺术家的不同作品经常复用大部分相同的音源；以新标题重新上传的作品也会与旧作品几乎完全相同。
+ * 这里按索引中的作品计算音频文件的哈希，找出相同音频所占比例超过阈值的作品并聚类，
+ * 用于决定去重或硬链接，以及发现重复上传
+ */
+
+import { readDir, stat } from '@tauri-apps/plugin-fs';
+import { AUDIO_FILE_EXTS, getFileExtension } from '../bms/scanner';
+import { HashService } from '../fs/hash';
+import { IgnoreList } from '../fs/ignore';
+import { formatSize } from '../fs/sizeTally';
+import { ConcurrencyPool } from '../media/concurrency';
+import { LibraryIndex } from './workIndex';
+
+/**
+ * 同时计算哈希的文件数
+ */
+const HASH_CONCURRENCY = 16;
+
+/**
+ * 出现在超过该数量作品中的音频视为通用素材（如空白音），不参与比较
+ */
+const COMMON_SOUND_WORK_LIMIT = 50;
+
+/**
+ * 两个作品的共用音源
+ */
+export interface SharedSoundPair {
+  workA: string;
+  workB: string;
+  /** 相同的音频数 */
+  sharedCount: number;
+  /** 相同音频占较少一方音频数的百分比 */
+  sharedPercent: number;
+  /** 相同音频的大小（字节，按一份计算） */
+  sharedSize: number;
+}
+
+/**
+ * 共用音源的作品组
+ */
+export interface SharedSoundCluster {
+  /** 作品文件夹名 */
+  works: string[];
+  pairs: SharedSoundPair[];
+  /** 组内重复的音频大小（字节），即硬链接或去重可节省的空间 */
+  duplicateSize: number;
+}
+
+/**
+ * 共用音源报告
+ */
+export interface SharedSoundReport {
+  rootDir: string;
+  minSharedPercent: number;
+  /** 参与比较的作品数 */
+  workCount: number;
+  /** 按可节省空间降序排列的作品组 */
+  clusters: SharedSoundCluster[];
+}
+
+/**
+ * 递归列出作品中的音频文件
+ */
+async function listAudioFiles(dirPath: string): Promise<string[]> {
+  const files: string[] = [];
+  for (const entry of await readDir(dirPath)) {
+    if (!entry.name || IgnoreList.isIgnored(entry.name)) {
+      continue;
+    }
+    const path = `${dirPath}/${entry.name}`;
+    if (entry.isDirectory) {
+      files.push(...(await listAudioFiles(path)));
+    } else if ((AUDIO_FILE_EXTS as readonly string[]).includes(getFileExtension(entry.name))) {
+      files.push(path);
+    }
+  }
+  return files;
+}
+
+/**
+ * 计算作品中音频文件的哈希
+ *
+ * @returns 哈希 -> 文件大小
+ */
+async function hashWorkSounds(
+  workDir: string,
+  pool: ConcurrencyPool<void>
+): Promise<Map<string, number>> {
+  const sounds = new Map<string, number>();
+  const files = await listAudioFiles(workDir);
+  await Promise.all(
+    files.map((file) =>
+      pool.add(async () => {
+        try {
+          const [hash, info] = await Promise.all([HashService.hashFile(file), stat(file)]);
+          sounds.set(hash, info.size);
+        } catch (error) {
+          console.warn(` !_! Failed to hash ${file}:`, error);
+        }
+      })
+    )
+  );
+  return sounds;
+}
+
+/**
+ * 找出共用音源的作品
+ *
+ * @command
+ * @category library
+ * @dangerous false
+ * @name 共用音源报告
+ * @description 按索引中的作品计算音频哈希，将相同音频比例超过阈值的作品聚类，列出可硬链接节省的空间，也可用于发现以新标题重新上传的作品
+ * @frontend true
+ *
+ * @param {string} rootDir - 根目录路径
+ * @param {number} minSharedPercent - 相同音频占较少一方的比例（%）达到该值时视为共用音源
+ * @param {boolean} refresh - 先更新索引再比较
+ *
+ * @returns {Promise<SharedSoundReport>} 共用音源的作品组
+ */
+export async function sharedSoundReport(
+  rootDir: string,
+  minSharedPercent: number = 80,
+  refresh: boolean = false
+): Promise<SharedSoundReport> {
+  if (refresh) {
+    await LibraryIndex.update(rootDir);
+  }
+  const index = await LibraryIndex.get(rootDir);
+  if (!index) {
+    throw new Error(`根目录尚未建立索引：${rootDir}`);
+  }
+
+  const pool = new ConcurrencyPool<void>(HASH_CONCURRENCY);
+  const works: { dirName: string; sounds: Map<string, number> }[] = [];
+  for (const work of index.works) {
+    try {
+      const sounds = await hashWorkSounds(work.path, pool);
+      if (sounds.size > 0) {
+        works.push({ dirName: work.dirName, sounds });
+      }
+    } catch (error) {
+      console.warn(` !_! Failed to read work: ${work.path}`, error);
+    }
+  }
+
+  // 哈希 -> 包含该音频的作品序号
+  const worksByHash = new Map<string, number[]>();
+  works.forEach((work, i) => {
+    for (const hash of work.sounds.keys()) {
+      worksByHash.set(hash, [...(worksByHash.get(hash) ?? []), i]);
+    }
+  });
+
+  // 统计每对作品的相同音频
+  const pairCounts = new Map<string, { a: number; b: number; count: number; size: number }>();
+  for (const [hash, indexes] of worksByHash) {
+    if (indexes.length < 2 || indexes.length > COMMON_SOUND_WORK_LIMIT) {
+      continue;
+    }
+    const size = works[indexes[0]].sounds.get(hash) ?? 0;
+    for (let i = 0; i < indexes.length; i++) {
+      for (let j = i + 1; j < indexes.length; j++) {
+        const key = `${indexes[i]},${indexes[j]}`;
+        const pair = pairCounts.get(key) ?? { a: indexes[i], b: indexes[j], count: 0, size: 0 };
+        pair.count++;
+        pair.size += size;
+        pairCounts.set(key, pair);
+      }
+    }
+  }
+
+  // 超过阈值的作品对按并查集聚类
+  const parent = works.map((_, i) => i);
+  const find = (i: number): number => (parent[i] === i ? i : (parent[i] = find(parent[i])));
+  const pairs: { a: number; b: number; pair: SharedSoundPair }[] = [];
+  for (const { a, b, count, size } of pairCounts.values()) {
+    const percent = (count / Math.min(works[a].sounds.size, works[b].sounds.size)) * 100;
+    if (percent < minSharedPercent) {
+      continue;
+    }
+    pairs.push({
+      a,
+      b,
+      pair: {
+        workA: works[a].dirName,
+        workB: works[b].dirName,
+        sharedCount: count,
+        sharedPercent: Math.round(percent * 10) / 10,
+        sharedSize: size,
+      },
+    });
+    parent[find(a)] = find(b);
+  }
+
+  const clusterMap = new Map<number, { members: Set<number>; pairs: SharedSoundPair[] }>();
+  for (const { a, b, pair } of pairs) {
+    const root = find(a);
+    const cluster = clusterMap.get(root) ?? { members: new Set<number>(), pairs: [] };
+    cluster.members.add(a).add(b);
+    cluster.pairs.push(pair);
+    clusterMap.set(root, cluster);
+  }
+
+  const clusters: SharedSoundCluster[] = [...clusterMap.values()].map((cluster) => {
+    // 组内每个音频只需保留一份，其余副本即可节省的空间
+    const copies = new Map<string, { count: number; size: number }>();
+    for (const i of cluster.members) {
+      for (const [hash, size] of works[i].sounds) {
+        const copy = copies.get(hash) ?? { count: 0, size };
+        copy.count++;
+        copies.set(hash, copy);
+      }
+    }
+    let duplicateSize = 0;
+    for (const { count, size } of copies.values()) {
+      duplicateSize += (count - 1) * size;
+    }
+    return {
+      works: [...cluster.members].map((i) => works[i].dirName).sort((a, b) => a.localeCompare(b)),
+      pairs: cluster.pairs.sort((a, b) => b.sharedPercent - a.sharedPercent),
+      duplicateSize,
+    };
+  });
+  clusters.sort((a, b) => b.duplicateSize - a.duplicateSize);
+
+  for (const cluster of clusters) {
+    console.log(`[${formatSize(cluster.duplicateSize)}] ${cluster.works.join(' | ')}`);
+    for (const pair of cluster.pairs) {
+      console.log(
+        `  ${pair.workA} <-> ${pair.workB}: ${pair.sharedCount} sounds (${pair.sharedPercent}%)`
+      );
+    }
+  }
+  const totalSize = clusters.reduce((sum, cluster) => sum + cluster.duplicateSize, 0);
+  console.log(
+    `${clusters.length} clusters of works sharing >= ${minSharedPercent}% sounds ` +
+      `among ${works.length} works, ${formatSize(totalSize)} duplicated`
+  );
+
+  return { rootDir, minSharedPercent, workCount: works.length, clusters };
+}
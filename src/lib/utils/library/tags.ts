@@ -0,0 +1,86 @@
+/**
+ * 作品标签
+ *
+ * 用户为作品添加的标签，按作品目录路径保存在应用数据目录中，
+ * 与作品索引分开存放，重建索引时不会丢失
+ */
+
+import { getAppDataPath, readJsonFile, writeJsonFile } from '../storage';
+
+/**
+ * 解析逗号分隔的标签（支持全角逗号），去除空白和重复项
+ */
+export function parseTags(value: string | undefined): string[] {
+  if (!value) {
+    return [];
+  }
+  return [
+    ...new Set(
+      value
+        .split(/[,，]/)
+        .map((tag) => tag.trim())
+        .filter((tag) => tag.length > 0)
+    ),
+  ];
+}
+
+/**
+ * 作品标签存储
+ */
+export class WorkTagStore {
+  private static cache: Record<string, string[]> | null = null;
+
+  private static async getTagsPath(): Promise<string> {
+    return await getAppDataPath('library/tags.json');
+  }
+
+  /**
+   * 获取所有作品的标签（作品目录路径 → 标签）
+   */
+  static async getAll(): Promise<Record<string, string[]>> {
+    this.cache ??= await readJsonFile<Record<string, string[]>>(await this.getTagsPath(), {});
+    return this.cache;
+  }
+
+  /**
+   * 获取作品的标签
+   */
+  static async get(workDir: string): Promise<string[]> {
+    return (await this.getAll())[workDir] ?? [];
+  }
+
+  /**
+   * 设置作品的标签，标签为空时删除记录
+   */
+  static async set(workDir: string, tags: string[]): Promise<void> {
+    const all = await this.getAll();
+    if (tags.length === 0) {
+      delete all[workDir];
+    } else {
+      all[workDir] = tags;
+    }
+    await writeJsonFile(await this.getTagsPath(), all);
+  }
+}
+
+/**
+ * 设置作品标签
+ *
+ * @command
+ * @category library
+ * @dangerous false
+ * @name 设置作品标签
+ * @description 为作品设置标签（逗号分隔，留空清除），可在作品库查询中按标签筛选
+ * @frontend true
+ *
+ * @param {string} workDir - 作品目录路径
+ * @param {string} tags - 标签（逗号分隔）
+ *
+ * @returns {Promise<string[]>} 设置后的标签
+ */
+export async function setWorkTags(workDir: string, tags: string): Promise<string[]> {
+  const parsed = parseTags(tags);
+  await WorkTagStore.set(workDir, parsed);
+  console.log(`Set tags of ${workDir}: ${parsed.length > 0 ? parsed.join(', ') : '(none)'}`);
+  return parsed;
+}
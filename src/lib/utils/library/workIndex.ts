@@ -0,0 +1,560 @@
+/**
+ * 作品索引
+ *
+ * 为根目录中的每个作品记录标题、艺术家、键位、等级范围、BGA 和音视频格式等信息，
+ * 保存在应用数据目录中。重建时按文件夹修改时间增量更新，只重新读取有变化的作品
+ */
+
+import { readDir, readFile, stat } from '@tauri-apps/plugin-fs';
+import { getBmsFileStr } from '../bms/encoding';
+import { parseBmsHeader } from '../bms/quickInfo';
+import { getFileExtension, isBmsonFile, isChartFile, VIDEO_FILE_EXTS } from '../bms/scanner';
+import type { BmsQuickInfo } from '../bms/types';
+import { extractWorkName } from '../bms/work';
+import { ConcurrencyPool } from '../media/concurrency';
+import { getQualityFingerprint } from '../media/quality';
+import type { QualityFingerprint } from '../media/quality';
+import type { BgaSummary } from '../work/bgaSummary';
+import { getAppDataPath, getAppDataPathForDir, readJsonFile, writeJsonFile } from '../storage';
+
+/**
+ * 同时读取的作品数（按 CPU 核数）
+ */
+const INDEX_CONCURRENCY = Math.max(2, globalThis.navigator?.hardwareConcurrency ?? 8);
+
+/**
+ * 作品索引条目
+ */
+export interface WorkIndexEntry {
+  rootDir: string;
+  /** 作品文件夹名 */
+  dirName: string;
+  path: string;
+  title?: string;
+  subtitle?: string;
+  artist?: string;
+  genre?: string;
+  subartist?: string;
+  /** 谱面中的 #COMMENT、%URL、%EMAIL（旧版索引中没有，强制更新后才有） */
+  comment?: string;
+  url?: string;
+  email?: string;
+  bpm?: number;
+  /** 谱面键位（5K、7K、9K、10K、14K、24K） */
+  keymodes: string[];
+  /** 各谱面的等级（去重后升序） */
+  levels: number[];
+  minLevel?: number;
+  maxLevel?: number;
+  chartCount: number;
+  /** 是否有 BGA（视频文件或 BGA 通道） */
+  hasBga: boolean;
+  /** BGA 类型、时长和分辨率（统计 BGA 后才有，作品未变化时重建索引保留） */
+  bga?: BgaSummary;
+  /** 音视频质量指纹（旧版索引中没有） */
+  quality?: QualityFingerprint;
+  /** 在 bmssearch 上查到的作品信息（查询后才有，重建索引时保留） */
+  origin?: WorkOrigin;
+  /** 文件夹修改时间（毫秒），用于增量更新 */
+  modifiedAt: number;
+}
+
+/**
+ * 外部数据库中的作品信息
+ */
+export interface WorkOrigin {
+  source: 'bmssearch';
+  id: string;
+  /** 数据库中的标题和艺术家（规范名称） */
+  title?: string;
+  artist?: string;
+  /** 首次发布的活动 */
+  event?: string;
+  url: string;
+  /** 查询时间（ISO 字符串） */
+  lookedUpAt: string;
+}
+
+/**
+ * 根目录索引
+ */
+export interface LibraryRootIndex {
+  rootDir: string;
+  /** 索引更新时间（ISO 字符串） */
+  indexedAt: string;
+  works: WorkIndexEntry[];
+}
+
+/**
+ * 快照中的作品（只保留比较变化所需的字段）
+ */
+export interface WorkSnapshotEntry {
+  dirName: string;
+  title?: string;
+  artist?: string;
+  chartCount: number;
+  modifiedAt: number;
+  /** 索引字段的摘要，字段变化时视为内容变化 */
+  signature: string;
+}
+
+/**
+ * 根目录在某次索引时的快照
+ */
+export interface LibrarySnapshot {
+  indexedAt: string;
+  works: WorkSnapshotEntry[];
+}
+
+/**
+ * 每个根目录保留的快照数
+ */
+const MAX_SNAPSHOTS = 30;
+
+/**
+ * 计算作品索引字段的摘要
+ */
+export function getWorkSignature(work: WorkIndexEntry): string {
+  return JSON.stringify([
+    work.title,
+    work.subtitle,
+    work.artist,
+    work.genre,
+    work.keymodes,
+    work.levels,
+    work.chartCount,
+    work.hasBga,
+    work.quality,
+  ]);
+}
+
+/**
+ * 生成根目录索引的快照
+ */
+export function toSnapshot(index: LibraryRootIndex): LibrarySnapshot {
+  return {
+    indexedAt: index.indexedAt,
+    works: index.works.map((work) => ({
+      dirName: work.dirName,
+      title: work.title,
+      artist: work.artist,
+      chartCount: work.chartCount,
+      modifiedAt: work.modifiedAt,
+      signature: getWorkSignature(work),
+    })),
+  };
+}
+
+/**
+ * 索引更新统计
+ */
+export interface LibraryIndexSummary {
+  rootDir: string;
+  total: number;
+  /** 重新读取的作品数 */
+  updated: number;
+  /** 未变化而沿用的作品数 */
+  unchanged: number;
+  /** 已不存在而移除的作品数 */
+  removed: number;
+}
+
+/**
+ * BMSON mode_hint 对应的键位
+ */
+const BMSON_MODE_KEYMODES: Record<string, string> = {
+  'beat-5k': '5K',
+  'beat-7k': '7K',
+  'beat-10k': '10K',
+  'beat-14k': '14K',
+  'popn-9k': '9K',
+  'keyboard-24k': '24K',
+};
+
+/**
+ * 单个谱面的索引信息
+ */
+interface ChartIndexInfo {
+  header: BmsQuickInfo;
+  keymode?: string;
+  hasBgaChannel: boolean;
+}
+
+/**
+ * 从等级字符串中取数值（如 "★12"、"12+"）
+ */
+function parseLevel(value: string | undefined): number | undefined {
+  const match = value?.match(/-?\d+(\.\d+)?/);
+  return match ? parseFloat(match[0]) : undefined;
+}
+
+/**
+ * 根据音符通道判断 BMS 键位
+ *
+ * 1P 可见通道 11-19 / 长条 51-59，2P 为 21-29 / 61-69；通道 18、19 为 6、7 键
+ */
+function detectBmsKeymode(content: string, filePath: string, player?: number): string {
+  if (getFileExtension(filePath) === 'pms') {
+    return '9K';
+  }
+
+  let doublePlay = player === 3;
+  let sevenKey = false;
+
+  for (const match of content.matchAll(/^#\d{3}([1256])([1-9]):(.*)$/gim)) {
+    if (!/[1-9A-Z]/i.test(match[3])) {
+      continue;
+    }
+    if (match[1] === '2' || match[1] === '6') {
+      doublePlay = true;
+    }
+    if (match[2] === '8' || match[2] === '9') {
+      sevenKey = true;
+    }
+  }
+
+  if (doublePlay) {
+    return sevenKey ? '14K' : '10K';
+  }
+  return sevenKey ? '7K' : '5K';
+}
+
+/**
+ * 读取单个谱面的索引信息
+ */
+async function readChartIndexInfo(filePath: string): Promise<ChartIndexInfo | null> {
+  try {
+    const bytes = new Uint8Array(await readFile(filePath));
+
+    if (isBmsonFile(filePath)) {
+      const bmson = JSON.parse(new TextDecoder('utf-8').decode(bytes)) as {
+        info?: Record<string, unknown>;
+        bga?: { bga_events?: unknown[] };
+      };
+      const info = bmson.info ?? {};
+      return {
+        header: {
+          path: filePath,
+          title: typeof info.title === 'string' ? info.title : undefined,
+          subtitle: typeof info.subtitle === 'string' ? info.subtitle : undefined,
+          artist: typeof info.artist === 'string' ? info.artist : undefined,
+          genre: typeof info.genre === 'string' ? info.genre : undefined,
+          subartist: Array.isArray(info.subartists) ? info.subartists.join(' ') : undefined,
+          bpm: typeof info.init_bpm === 'number' ? info.init_bpm : undefined,
+          playLevel: typeof info.level === 'number' ? info.level.toString() : undefined,
+        },
+        keymode: BMSON_MODE_KEYMODES[String(info.mode_hint ?? 'beat-7k')],
+        hasBgaChannel: (bmson.bga?.bga_events?.length ?? 0) > 0,
+      };
+    }
+
+    const content = getBmsFileStr(bytes);
+    const header = parseBmsHeader(content, filePath);
+    return {
+      header,
+      keymode: detectBmsKeymode(content, filePath, header.player),
+      hasBgaChannel: /^#\d{3}04:(?!(00)+\s*$)/im.test(content),
+    };
+  } catch (error) {
+    console.warn(` !_! Failed to read chart: ${filePath}`, error);
+    return null;
+  }
+}
+
+/**
+ * 从多个谱面的同一字段中取作品的值（优先取共同部分）
+ */
+function pickCommonName(values: (string | undefined)[]): string | undefined {
+  const names = values.filter((value): value is string => !!value);
+  if (names.length === 0) {
+    return undefined;
+  }
+  return extractWorkName(names) || names[0];
+}
+
+/**
+ * 读取单个作品的索引条目
+ */
+async function buildWorkEntry(
+  rootDir: string,
+  dirName: string,
+  modifiedAt: number
+): Promise<WorkIndexEntry | null> {
+  const path = `${rootDir}/${dirName}`;
+  const entries = await readDir(path);
+  const chartNames = entries
+    .filter((entry) => !entry.isDirectory && entry.name && isChartFile(entry.name))
+    .map((entry) => entry.name);
+  if (chartNames.length === 0) {
+    return null;
+  }
+
+  const hasVideo = entries.some(
+    (entry) =>
+      !entry.isDirectory &&
+      (VIDEO_FILE_EXTS as readonly string[]).includes(getFileExtension(entry.name))
+  );
+
+  const charts: ChartIndexInfo[] = [];
+  for (const name of chartNames) {
+    const info = await readChartIndexInfo(`${path}/${name}`);
+    if (info) {
+      charts.push(info);
+    }
+  }
+
+  const levels = [
+    ...new Set(
+      charts
+        .map((chart) => parseLevel(chart.header.playLevel))
+        .filter((level): level is number => level !== undefined)
+    ),
+  ].sort((a, b) => a - b);
+
+  return {
+    rootDir,
+    dirName,
+    path,
+    title: pickCommonName(charts.map((chart) => chart.header.title)),
+    subtitle: pickCommonName(charts.map((chart) => chart.header.subtitle)),
+    artist: pickCommonName(charts.map((chart) => chart.header.artist)),
+    genre: pickCommonName(charts.map((chart) => chart.header.genre)),
+    subartist: pickCommonName(charts.map((chart) => chart.header.subartist)),
+    comment: charts.find((chart) => chart.header.comment)?.header.comment,
+    url: charts.find((chart) => chart.header.url)?.header.url,
+    email: charts.find((chart) => chart.header.email)?.header.email,
+    bpm: charts.find((chart) => chart.header.bpm !== undefined)?.header.bpm,
+    keymodes: [
+      ...new Set(charts.map((chart) => chart.keymode).filter((mode) => mode !== undefined)),
+    ],
+    levels,
+    minLevel: levels[0],
+    maxLevel: levels[levels.length - 1],
+    chartCount: chartNames.length,
+    hasBga: hasVideo || charts.some((chart) => chart.hasBgaChannel),
+    quality: getQualityFingerprint(entries),
+    modifiedAt,
+  };
+}
+
+/**
+ * 作品索引存储（所有根目录保存在同一个文件中，首次使用时加载到内存）
+ */
+export class LibraryIndex {
+  private static cache: Record<string, LibraryRootIndex> | null = null;
+  private static revisionCounter = 0;
+
+  /**
+   * 索引修改次数（每次更新或删除后递增，供派生的缓存判断是否过期）
+   */
+  static get revision(): number {
+    return this.revisionCounter;
+  }
+
+  private static async getIndexPath(): Promise<string> {
+    return await getAppDataPath('library/index.json');
+  }
+
+  private static async load(): Promise<Record<string, LibraryRootIndex>> {
+    this.cache ??= await readJsonFile<Record<string, LibraryRootIndex>>(
+      await this.getIndexPath(),
+      {}
+    );
+    return this.cache;
+  }
+
+  /**
+   * 获取根目录的索引，尚未建立索引时返回 null
+   */
+  static async get(rootDir: string): Promise<LibraryRootIndex | null> {
+    return (await this.load())[rootDir] ?? null;
+  }
+
+  /**
+   * 获取所有已建立索引的根目录
+   */
+  static async getIndexedRoots(): Promise<string[]> {
+    return Object.keys(await this.load());
+  }
+
+  /**
+   * 更新根目录的索引
+   *
+   * @param force - 为 true 时忽略修改时间，重新读取所有作品
+   */
+  static async update(rootDir: string, force: boolean = false): Promise<LibraryIndexSummary> {
+    const indexes = await this.load();
+    const previous = new Map(
+      (indexes[rootDir]?.works ?? []).map((entry) => [entry.dirName, entry])
+    );
+    const summary: LibraryIndexSummary = {
+      rootDir,
+      total: 0,
+      updated: 0,
+      unchanged: 0,
+      removed: 0,
+    };
+
+    const pool = new ConcurrencyPool<WorkIndexEntry | null>(INDEX_CONCURRENCY);
+    const tasks: Promise<WorkIndexEntry | null>[] = [];
+    const seen = new Set<string>();
+
+    for (const entry of await readDir(rootDir)) {
+      if (!entry.isDirectory || !entry.name) {
+        continue;
+      }
+
+      const dirName = entry.name;
+      seen.add(dirName);
+      tasks.push(
+        pool.add(async () => {
+          const info = await stat(`${rootDir}/${dirName}`);
+          const modifiedAt = info.mtime?.getTime() ?? 0;
+          const cached = previous.get(dirName);
+          if (!force && cached?.quality && cached.modifiedAt === modifiedAt) {
+            summary.unchanged++;
+            return cached;
+          }
+
+          const work = await buildWorkEntry(rootDir, dirName, modifiedAt).catch((error) => {
+            console.warn(` !_! Failed to index work: ${rootDir}/${dirName}`, error);
+            return null;
+          });
+          if (work) {
+            work.origin ??= cached?.origin;
+            if (cached?.modifiedAt === modifiedAt) {
+              work.bga ??= cached.bga;
+            }
+            summary.updated++;
+          }
+          return work;
+        })
+      );
+    }
+
+    const works = (await Promise.all(tasks)).filter((work) => work !== null);
+    summary.total = works.length;
+    summary.removed = [...previous.keys()].filter((dirName) => !seen.has(dirName)).length;
+
+    // 覆盖前保存上一次索引的快照，用于列出两次索引之间的变化
+    if (indexes[rootDir]) {
+      await this.saveSnapshot(rootDir, toSnapshot(indexes[rootDir]));
+    }
+
+    indexes[rootDir] = { rootDir, indexedAt: new Date().toISOString(), works };
+    this.revisionCounter++;
+    await writeJsonFile(await this.getIndexPath(), indexes);
+
+    return summary;
+  }
+
+  private static async getSnapshotPath(rootDir: string): Promise<string> {
+    return await getAppDataPathForDir('library/snapshots', rootDir);
+  }
+
+  /**
+   * 获取根目录之前的索引快照（按时间升序）
+   */
+  static async getSnapshots(rootDir: string): Promise<LibrarySnapshot[]> {
+    return await readJsonFile<LibrarySnapshot[]>(await this.getSnapshotPath(rootDir), []);
+  }
+
+  private static async saveSnapshot(rootDir: string, snapshot: LibrarySnapshot): Promise<void> {
+    const snapshots = await this.getSnapshots(rootDir);
+    snapshots.push(snapshot);
+    await writeJsonFile(await this.getSnapshotPath(rootDir), snapshots.slice(-MAX_SNAPSHOTS));
+  }
+
+  /**
+   * 修改索引中作品的字段
+   *
+   * @param fields - 作品文件夹名 -> 要修改的字段
+   * @returns 修改的作品数
+   */
+  static async annotate(
+    rootDir: string,
+    fields: Map<string, Partial<WorkIndexEntry>>
+  ): Promise<number> {
+    const index = (await this.load())[rootDir];
+    let count = 0;
+    for (const work of index?.works ?? []) {
+      const update = fields.get(work.dirName);
+      if (update) {
+        Object.assign(work, update);
+        count++;
+      }
+    }
+
+    if (count > 0) {
+      this.revisionCounter++;
+      await writeJsonFile(await this.getIndexPath(), await this.load());
+    }
+    return count;
+  }
+
+  /**
+   * 删除根目录的索引
+   *
+   * @returns 之前是否存在该根目录的索引
+   */
+  static async remove(rootDir: string): Promise<boolean> {
+    const indexes = await this.load();
+    if (!indexes[rootDir]) {
+      return false;
+    }
+
+    delete indexes[rootDir];
+    this.revisionCounter++;
+    await writeJsonFile(await this.getIndexPath(), indexes);
+    return true;
+  }
+}
+
+/**
+ * 更新根目录的作品索引
+ *
+ * @command
+ * @category library
+ * @dangerous false
+ * @name 更新作品索引
+ * @description 读取根目录中各作品的标题、键位、等级和 BGA 信息，只重新读取修改过的作品
+ * @frontend true
+ *
+ * @param {string} rootDir - 根目录路径
+ * @param {boolean} force - 忽略修改时间，重新读取所有作品
+ *
+ * @returns {Promise<LibraryIndexSummary>} 索引更新统计
+ */
+export async function indexRoot(
+  rootDir: string,
+  force: boolean = false
+): Promise<LibraryIndexSummary> {
+  const summary = await LibraryIndex.update(rootDir, force);
+  console.log(
+    `Indexed ${summary.total} works in ${rootDir} (updated ${summary.updated}, ` +
+      `unchanged ${summary.unchanged}, removed ${summary.removed})`
+  );
+  return summary;
+}
+
+/**
+ * 删除根目录的作品索引
+ *
+ * @command
+ * @category library
+ * @dangerous false
+ * @name 删除作品索引
+ * @description 从作品库中移除根目录的索引（不影响磁盘上的文件）
+ * @frontend true
+ *
+ * @param {string} rootDir - 根目录路径
+ *
+ * @returns {Promise<boolean>} 之前是否存在该根目录的索引
+ */
+export async function removeIndex(rootDir: string): Promise<boolean> {
+  const removed = await LibraryIndex.remove(rootDir);
+  if (!removed) {
+    console.warn(`Root dir is not indexed: ${rootDir}`);
+  }
+  return removed;
+}
@@ -0,0 +1,7 @@
+/**
+ * 日志模块
+ * 导出所有公共 API
+ */
+
+export * from './manage';
+export * from './runLog';
@@ -0,0 +1,62 @@
+/**
+ * 日志文件的查看和导出
+ */
+
+import { stat } from '@tauri-apps/plugin-fs';
+import { openPath } from '@tauri-apps/plugin-opener';
+import { readFileChunks, ZipWriter } from '../fs/zip';
+import { getAppDataSubDir } from '../storage';
+import { LOG_DIR, listLogFiles, RunLog } from './runLog';
+
+/**
+ * 打开日志文件夹
+ *
+ * @command
+ * @category logs
+ * @dangerous false
+ * @name 打开日志文件夹
+ * @description 在文件管理器中打开保存运行日志的文件夹
+ * @frontend true
+ *
+ * @returns {Promise<void>}
+ */
+export async function openFolder(): Promise<void> {
+  await RunLog.flush();
+  await openPath(await getAppDataSubDir(LOG_DIR));
+}
+
+/**
+ * 导出日志
+ *
+ * @command
+ * @category logs
+ * @dangerous false
+ * @name 导出日志
+ * @description 将所有运行日志打包为 ZIP 文件保存到输出目录，用于反馈问题
+ * @frontend true
+ *
+ * @param {string} outputDir - 输出目录
+ *
+ * @returns {Promise<string>} ZIP 文件路径
+ */
+export async function exportArchive(outputDir: string): Promise<string> {
+  await RunLog.flush();
+
+  const stamp = new Date().toISOString().replace(/[:.]/g, '-');
+  const zipPath = `${outputDir}/bms-toolbox-logs-${stamp}.zip`;
+  const writer = await ZipWriter.create(zipPath);
+
+  const files = await listLogFiles();
+  for (const file of files) {
+    const info = await stat(file);
+    const name = file.split('/').pop() ?? file;
+    await writer.addEntry(name, readFileChunks(file), {
+      size: info.size,
+      mtime: info.mtime ?? undefined,
+    });
+  }
+  await writer.close();
+
+  console.log(`Exported ${files.length} log files to ${zipPath}`);
+  return zipPath;
+}
@@ -0,0 +1,276 @@
+/**
+ * 运行日志文件
+ *
+ * 每次执行命令时把控制台输出连同操作 ID 写入应用数据目录下的日志文件，
+ * 用户反馈"转换后文件不见了"时可以查到当时每一步做了什么。
+ * 日志按日期分文件，单个文件超过大小上限时续写到新文件，总量超过上限时删除最旧的文件。
+ * 执行期间的控制台输出同时推送给订阅者，前端可以实时显示
+ */
+
+import { exists, readDir, remove, stat, writeTextFile } from '@tauri-apps/plugin-fs';
+import { getAppDataSubDir } from '../storage';
+
+/**
+ * 日志目录（相对于应用数据目录）
+ */
+export const LOG_DIR = 'logs';
+
+/** 单个日志文件的大小上限 */
+const MAX_LOG_FILE_BYTES = 5 * 1024 * 1024;
+/** 保留的日志文件总大小上限 */
+const MAX_TOTAL_LOG_BYTES = 50 * 1024 * 1024;
+
+const LOG_FILE_PATTERN = /^toolbox-\d{4}-\d{2}-\d{2}(\.\d+)?\.log$/;
+
+/** 日志记录的推送间隔（合并为一批，避免大量输出时频繁刷新界面） */
+const LOG_PUBLISH_INTERVAL_MS = 100;
+
+/**
+ * 一条日志记录
+ */
+export interface LogRecord {
+  timestamp: string;
+  level: 'info' | 'warn' | 'error';
+  /** 输出这条日志的命令 ID（同时执行多个命令时以逗号分隔） */
+  module: string;
+  message: string;
+}
+
+/**
+ * 日志记录订阅者
+ */
+export type LogRecordListener = (records: LogRecord[]) => void;
+
+type ConsoleLevel = 'log' | 'info' | 'warn' | 'error';
+
+const CONSOLE_LEVELS: ConsoleLevel[] = ['log', 'info', 'warn', 'error'];
+
+/**
+ * 把控制台参数格式化为一行文本
+ */
+function formatArgs(args: unknown[]): string {
+  return args
+    .map((arg) => {
+      if (typeof arg === 'string') {
+        return arg;
+      }
+      if (arg instanceof Error) {
+        return arg.stack ?? `${arg.name}: ${arg.message}`;
+      }
+      try {
+        return JSON.stringify(arg);
+      } catch {
+        return String(arg);
+      }
+    })
+    .join(' ');
+}
+
+/**
+ * 运行日志
+ */
+export class RunLog {
+  private static enabled = true;
+  /** 正在执行的操作 ID -> 命令 ID */
+  private static activeRuns = new Map<string, string>();
+  private static originalConsole: Partial<Record<ConsoleLevel, (...args: unknown[]) => void>> = {};
+  private static pending: string[] = [];
+  /** 串行写入，保证行的顺序 */
+  private static writing: Promise<void> = Promise.resolve();
+  private static outgoing: LogRecord[] = [];
+  private static publishTimer: ReturnType<typeof setTimeout> | null = null;
+  private static listeners = new Set<LogRecordListener>();
+
+  static setEnabled(enabled: boolean): void {
+    this.enabled = enabled;
+  }
+
+  static isEnabled(): boolean {
+    return this.enabled;
+  }
+
+  /**
+   * 订阅执行期间的日志记录
+   *
+   * @returns 取消订阅的函数
+   */
+  static subscribe(listener: LogRecordListener): () => void {
+    this.listeners.add(listener);
+    return () => {
+      this.listeners.delete(listener);
+    };
+  }
+
+  /**
+   * 记录一次操作的全部控制台输出（关闭日志时直接执行，不捕获输出，也不推送日志记录）
+   *
+   * @param command - 命令 ID
+   * @param params - 命令参数（写入开始行）
+   * @param fn - 要执行的操作
+   */
+  static async capture<T>(
+    command: string,
+    params: Record<string, unknown>,
+    fn: () => Promise<T>
+  ): Promise<T> {
+    if (!this.enabled) {
+      return await fn();
+    }
+
+    const runId = crypto.randomUUID().slice(0, 8);
+    const startedAt = Date.now();
+    this.begin(runId, command);
+    this.append(runId, 'INFO', `=== start ${command} ${formatArgs([params])}`);
+
+    try {
+      const result = await fn();
+      this.append(runId, 'INFO', `=== end ${command} (${(Date.now() - startedAt) / 1000}s)`);
+      return result;
+    } catch (error) {
+      this.append(runId, 'ERROR', `=== failed ${command}: ${formatArgs([error])}`);
+      throw error;
+    } finally {
+      this.end(runId);
+      await this.flush();
+    }
+  }
+
+  /**
+   * 等待已记录的行写入文件
+   */
+  static async flush(): Promise<void> {
+    const lines = this.pending.splice(0);
+    if (lines.length > 0) {
+      this.writing = this.writing.then(() => this.writeLines(lines));
+    }
+    await this.writing;
+  }
+
+  private static begin(runId: string, command: string): void {
+    if (this.activeRuns.size === 0) {
+      for (const level of CONSOLE_LEVELS) {
+        const original = console[level].bind(console);
+        this.originalConsole[level] = original;
+        console[level] = (...args: unknown[]) => {
+          original(...args);
+          const message = formatArgs(args);
+          this.append([...this.activeRuns.keys()].join(','), level.toUpperCase(), message);
+          this.publish({
+            timestamp: new Date().toISOString(),
+            level: level === 'log' ? 'info' : level,
+            module: [...new Set(this.activeRuns.values())].join(','),
+            message,
+          });
+        };
+      }
+    }
+    this.activeRuns.set(runId, command);
+  }
+
+  private static end(runId: string): void {
+    this.activeRuns.delete(runId);
+    if (this.activeRuns.size === 0) {
+      for (const level of CONSOLE_LEVELS) {
+        const original = this.originalConsole[level];
+        if (original) {
+          console[level] = original;
+        }
+      }
+      this.originalConsole = {};
+    }
+  }
+
+  private static append(runId: string, level: string, message: string): void {
+    if (!this.enabled) {
+      return;
+    }
+    this.pending.push(`${new Date().toISOString()} [${runId}] ${level.padEnd(5)} ${message}`);
+    // 长时间运行的操作中途也写入，避免异常退出时丢失
+    if (this.pending.length >= 200) {
+      void this.flush();
+    }
+  }
+
+  /**
+   * 合并一段时间内的日志记录后推送给订阅者
+   */
+  private static publish(record: LogRecord): void {
+    if (this.listeners.size === 0) {
+      return;
+    }
+    this.outgoing.push(record);
+    this.publishTimer ??= setTimeout(() => {
+      this.publishTimer = null;
+      const records = this.outgoing.splice(0);
+      for (const listener of this.listeners) {
+        listener(records);
+      }
+    }, LOG_PUBLISH_INTERVAL_MS);
+  }
+
+  private static async writeLines(lines: string[]): Promise<void> {
+    try {
+      const logPath = await this.currentLogPath();
+      await writeTextFile(logPath, `${lines.join('\n')}\n`, { append: true, create: true });
+    } catch (error) {
+      this.originalConsole.warn?.(' !_! Failed to write log file:', error);
+    }
+  }
+
+  /**
+   * 当天的日志文件（超过大小上限时使用下一个编号），切换文件时清理旧日志
+   */
+  private static async currentLogPath(): Promise<string> {
+    const logDir = await getAppDataSubDir(LOG_DIR);
+    const date = new Date().toISOString().slice(0, 10);
+
+    for (let index = 0; ; index++) {
+      const logPath = `${logDir}/toolbox-${date}${index === 0 ? '' : `.${index}`}.log`;
+      if (!(await exists(logPath))) {
+        await pruneLogFiles();
+        return logPath;
+      }
+      if ((await stat(logPath)).size < MAX_LOG_FILE_BYTES) {
+        return logPath;
+      }
+    }
+  }
+}
+
+/**
+ * 同一天的第一个文件没有编号，排序时视为 .0
+ */
+function logSortKey(name: string): string {
+  return name.replace(/^(toolbox-\d{4}-\d{2}-\d{2})\.log$/, '$1.0.log');
+}
+
+/**
+ * 列出日志文件（按时间从旧到新）
+ */
+export async function listLogFiles(): Promise<string[]> {
+  const logDir = await getAppDataSubDir(LOG_DIR);
+  return (await readDir(logDir))
+    .filter((entry) => entry.isFile && LOG_FILE_PATTERN.test(entry.name))
+    .map((entry) => entry.name)
+    .sort((a, b) => logSortKey(a).localeCompare(logSortKey(b), undefined, { numeric: true }))
+    .map((name) => `${logDir}/${name}`);
+}
+
+/**
+ * 日志总大小超过上限时从最旧的文件开始删除
+ */
+async function pruneLogFiles(): Promise<void> {
+  const files = await listLogFiles();
+  const sizes = await Promise.all(files.map(async (file) => (await stat(file)).size));
+  let total = sizes.reduce((sum, size) => sum + size, 0);
+
+  for (let i = 0; i < files.length && total > MAX_TOTAL_LOG_BYTES; i++) {
+    try {
+      await remove(files[i]);
+      total -= sizes[i];
+    } catch {
+      // 删除失败时保留，下次再试
+      continue;
+    }
+  }
+}
@@ -7,9 +7,33 @@ import * as fs from '@tauri-apps/plugin-fs';
 import { ProcessRunner } from './processRunner';
 import { ConcurrencyPool } from './concurrency';
 import { AUDIO_PRESETS } from './presets';
-import type { AudioPreset, AudioProcessParams } from './types';
+import type {
+  AudioPreset,
+  AudioProcessParams,
+  ConversionFailure,
+  ConversionSummary,
+} from './types';
 import type { IProgressManager } from '../progress';
+import { runHooks } from '../hooks';
+import { notifyCompletion } from '../notify';
+import { ResourceTelemetry } from '../telemetry';
+import { skipIfPinned } from '../work/pin';
 import { AUDIO_FILE_EXTS } from '../bms/scanner';
+import { getPreserveArgs } from './audioMetadata';
+import { ORIGINALS_DIR_NAME, OriginalsArchive, disposeOriginal } from './originals';
+import {
+  ConverterLog,
+  ConverterTimeout,
+  formatCommandLine,
+  recordFailure,
+  truncateStderr,
+} from './converterLog';
+import {
+  commitTempOutput,
+  discardTempOutput,
+  getTempOutputPath,
+  removeTempOutputs,
+} from './tempOutput';
 
 /**
  * 音频转换器类
@@ -22,7 +46,7 @@ export class AudioConverter {
    * @param params - 音频处理参数
    * @throws 如果目录操作或音频处理失败
    */
-  static async processBmsFolders(params: AudioProcessParams): Promise<void> {
+  static async processBmsFolders(params: AudioProcessParams): Promise<ConversionSummary> {
     const {
       rootDir,
       inputExtensions,
@@ -30,11 +54,21 @@ export class AudioConverter {
       removeOnSuccess,
       removeOnFail,
       skipOnFail,
+      skipDirs,
+      preserveMetadata = false,
+      keepOriginals = OriginalsArchive.isEnabled(),
       progressManager,
     } = params;
 
     // 启动进度管理器
     progressManager?.start();
+    const summary: ConversionSummary = {
+      processed: 0,
+      failed: 0,
+      skipped: 0,
+      cancelled: false,
+      failures: [],
+    };
 
     try {
       // 解析预设名称为预设对象
@@ -51,7 +85,9 @@ export class AudioConverter {
 
       // 遍历根目录下的所有子目录
       const entries = await fs.readDir(rootDir);
-      const folders = entries.filter((e) => e.isDirectory && e.name);
+      const folders = entries.filter(
+        (e) => e.isDirectory && e.name && e.name !== ORIGINALS_DIR_NAME
+      );
 
       // 更新总进度
       progressManager?.update(0, folders.length, `找到 ${folders.length} 个文件夹`);
@@ -61,7 +97,8 @@ export class AudioConverter {
         if (progressManager?.shouldStop()) {
           if (progressManager.getProgress().cancelled) {
             console.log('任务已取消');
-            return;
+            summary.cancelled = true;
+            return summary;
           }
           // 等待恢复
           await progressManager.waitForResume();
@@ -69,8 +106,13 @@ export class AudioConverter {
 
         const entry = folders[i];
         const dirPath = `${rootDir}/${entry.name}`;
+        if (skipDirs?.has(entry.name) || (await skipIfPinned(dirPath))) {
+          summary.skipped++;
+          continue;
+        }
 
         progressManager?.update(i, folders.length, `处理 ${entry.name}`);
+        summary.processed++;
 
         try {
           const success = await this.convertInDirectory(
@@ -80,13 +122,18 @@ export class AudioConverter {
             removeOnSuccess,
             removeOnFail,
             true, // 总是覆盖已存在的文件
-            progressManager
+            preserveMetadata,
+            progressManager,
+            summary.failures,
+            keepOriginals
           );
 
           if (success) {
             console.log(`Successfully processed ${dirPath}`);
+            await runHooks('after_conversion', { workPath: dirPath });
           } else {
             console.error(`Errors occurred in ${dirPath}`);
+            summary.failed++;
             // 遇到错误时跳过，不抛出异常
             if (skipOnFail) {
               console.error('Skipping remaining folders due to error');
@@ -95,6 +142,7 @@ export class AudioConverter {
           }
         } catch (error) {
           console.error(`Error processing ${dirPath}:`, error);
+          summary.failed++;
           // 遇到错误时跳过，不抛出异常
           if (skipOnFail) {
             break;
@@ -104,6 +152,7 @@ export class AudioConverter {
 
       // 完成
       progressManager?.update(folders.length, folders.length, '音频转换完成');
+      return summary;
     } catch (error) {
       progressManager?.reportError(error instanceof Error ? error.message : String(error));
       throw error;
@@ -120,7 +169,10 @@ export class AudioConverter {
    * @param removeOnSuccess - 成功时是否删除原文件
    * @param removeOnFail - 失败时是否删除原文件
    * @param removeExisting - 是否删除已存在的输出文件
+   * @param preserveMetadata - 是否保留标签和循环点
    * @param progressManager - 进度管理器（可选）
+   * @param failures - 记录转换失败的文件详情（可选）
+   * @param keepOriginals - 删除原文件时是否改为移入保留目录
    * @returns 是否完全成功
    */
   static async convertInDirectory(
@@ -130,9 +182,13 @@ export class AudioConverter {
     removeOnSuccess: boolean,
     removeOnFail: boolean,
     removeExisting: boolean,
-    progressManager?: IProgressManager
+    preserveMetadata: boolean,
+    progressManager?: IProgressManager,
+    failures?: ConversionFailure[],
+    keepOriginals: boolean = OriginalsArchive.isEnabled()
   ): Promise<boolean> {
-    // 收集需要处理的文件
+    // 清理上次中断时残留的临时输出，再收集需要处理的文件
+    await removeTempOutputs(dirPath);
     const files = await this.collectFiles(dirPath, inputExtensions);
     const totalFiles = files.length;
 
@@ -144,11 +200,11 @@ export class AudioConverter {
     // 预检查可执行文件是否存在
     await this.checkExecutables(presets);
 
-    const failures: string[] = [];
+    const failedFiles: string[] = [];
     let hadError = false;
 
     // 使用并发池处理文件
-    const pool = new ConcurrencyPool(64);
+    const pool = new ConcurrencyPool();
 
     const promises: Promise<unknown>[] = [];
     for (const filePath of files) {
@@ -166,17 +222,21 @@ export class AudioConverter {
         pool.add(async () => {
           const success = await this.convertFile(
             filePath,
+            dirPath,
             presets,
             removeOnSuccess,
             removeOnFail,
             removeExisting,
-            progressManager
+            preserveMetadata,
+            progressManager,
+            failures,
+            keepOriginals
           );
 
           if (!success) {
             hadError = true;
             const fileName = filePath.split('/').pop() || filePath.split('\\').pop() || '';
-            failures.push(fileName);
+            failedFiles.push(fileName);
           }
         })
       );
@@ -191,8 +251,8 @@ export class AudioConverter {
     if (totalFiles > 0) {
       console.log(`Processed ${totalFiles} files in ${dirPath}`);
     }
-    if (failures.length > 0) {
-      console.log(`${failures.length} files failed all presets:`, failures);
+    if (failedFiles.length > 0) {
+      console.log(`${failedFiles.length} files failed all presets:`, failedFiles);
     }
     if (hadError && removeOnFail) {
       console.log('Original files for failed conversions were removed');
@@ -206,23 +266,32 @@ export class AudioConverter {
    * 对应 Rust 中的文件处理逻辑 (audio.rs:200-282)
    *
    * @param filePath - 文件路径
+   * @param workDir - 文件所在的作品目录
    * @param presets - 音频预设列表
-   * @param removeOnSuccess - 成功时是否删除原文件
+   * @param removeOnSuccess - 成功时是否删除原文件（开启原文件保留时移入保留目录）
    * @param removeOnFail - 失败时是否删除原文件
    * @param removeExisting - 是否删除已存在的输出文件
+   * @param preserveMetadata - 是否保留标签和循环点
    * @param progressManager - 进度管理器（可选）
+   * @param failures - 所有预设都失败时记录最后一次尝试（可选）
+   * @param keepOriginals - 删除原文件时是否改为移入保留目录
    * @returns 是否成功
    */
   private static async convertFile(
     filePath: string,
+    workDir: string,
     presets: Array<{ executor: string; outputFormat: string; arguments?: string[] }>,
     removeOnSuccess: boolean,
     removeOnFail: boolean,
     removeExisting: boolean,
-    progressManager?: IProgressManager
+    preserveMetadata: boolean,
+    progressManager?: IProgressManager,
+    failures?: ConversionFailure[],
+    keepOriginals: boolean = OriginalsArchive.isEnabled()
   ): Promise<boolean> {
     let currentPresetIndex = 0;
     let success = false;
+    let lastFailure: ConversionFailure | null = null;
 
     while (currentPresetIndex < presets.length) {
       // 检查是否应该停止（暂停或取消）
@@ -260,14 +329,38 @@ export class AudioConverter {
       }
 
       // 构建并执行命令
-      const args = this.buildCommandArgs(filePath, outputPath, preset);
+      const preserve = await getPreserveArgs(filePath, preset, preserveMetadata);
+      if (preserve.loopLost) {
+        console.warn(` !_! Loop points will be lost: ${filePath} -> ${outputPath}`);
+      }
+      const args = this.buildCommandArgs(
+        filePath,
+        getTempOutputPath(outputPath),
+        preset,
+        preserve.args
+      );
 
-      const result = await ProcessRunner.exec(preset.executor, args);
+      // 先写入临时文件，成功后再重命名，避免中断时留下残缺的输出
+      const result = await ProcessRunner.exec(preset.executor, args, {
+        signal: progressManager?.signal,
+        timeout: ConverterTimeout.timeoutMs(),
+      });
+      const committed =
+        result.success &&
+        (await commitTempOutput(outputPath).then(
+          () => true,
+          (error) => {
+            console.error(`Failed to rename temp output: ${outputPath}`, error);
+            return false;
+          }
+        ));
 
-      if (result.success) {
+      if (committed) {
+        const readBytes = await ResourceTelemetry.addFiles([filePath], [outputPath]);
+        progressManager?.addProcessed(1, readBytes);
         if (removeOnSuccess) {
           try {
-            await fs.remove(filePath);
+            await disposeOriginal(filePath, workDir, keepOriginals);
           } catch (error) {
             console.error(`Error deleting original file: ${filePath}`, error);
           }
@@ -275,16 +368,32 @@ export class AudioConverter {
         success = true;
         break;
       } else {
+        await discardTempOutput(outputPath);
+        lastFailure = {
+          file: filePath,
+          preset: `${preset.executor} -> ${preset.outputFormat}`,
+          commandLine: formatCommandLine(preset.executor, args),
+          exitCode: result.exitCode,
+          stderr: truncateStderr(result.stderr),
+        };
         console.log(
-          `Preset failed [${preset.executor}]: ${filePath} -> ${outputPath}`,
-          result.stderr
+          `Preset failed [${preset.executor}]: ${filePath} -> ${outputPath}\n` +
+            `  ${lastFailure.commandLine}\n${lastFailure.stderr}`
         );
+        // 超时的文件多半已损坏，换预设也会卡住，直接记为失败
+        if (result.timedOut) {
+          break;
+        }
       }
 
       currentPresetIndex++;
     }
 
-    if (!success) {
+    // 被取消而中断的转换不算失败，保留原文件
+    if (!success && !progressManager?.getProgress().cancelled) {
+      if (lastFailure && failures) {
+        recordFailure(failures, lastFailure);
+      }
       if (removeOnFail) {
         try {
           await fs.remove(filePath);
@@ -304,23 +413,26 @@ export class AudioConverter {
    * @param inputPath - 输入文件路径
    * @param outputPath - 输出文件路径
    * @param preset - 音频预设
+   * @param metadataArgs - 保留标签和循环点的附加参数
    * @returns 命令参数数组
    */
   private static buildCommandArgs(
     inputPath: string,
     outputPath: string,
-    preset: { executor: string; outputFormat: string; arguments?: string[] }
+    preset: { executor: string; outputFormat: string; arguments?: string[] },
+    metadataArgs: string[] = []
   ): string[] {
     const args: string[] = [];
 
     switch (preset.executor) {
       case 'ffmpeg': {
-        args.push('-hide_banner', '-loglevel', 'panic', '-i', inputPath);
+        args.push('-hide_banner', ...ConverterLog.ffmpegArgs(), '-i', inputPath);
         args.push('-f', preset.outputFormat);
         args.push('-map_metadata', '0');
         if (preset.arguments) {
           args.push(...preset.arguments);
         }
+        args.push(...metadataArgs);
         args.push(outputPath);
         break;
       }
@@ -328,6 +440,7 @@ export class AudioConverter {
         if (preset.arguments) {
           args.push(...preset.arguments);
         }
+        args.push(...metadataArgs);
         args.push(inputPath, '-o', outputPath);
         break;
       }
@@ -461,12 +574,13 @@ export class AudioConverter {
  *
  * @param {string} rootDir - 根目录路径
  * @param {AudioPreset[]} presetNames - 目标格式预设名称
- * @param {boolean} removeOriginFileWhenSuccess - 成功时删除原文件
+ * @param {boolean} removeOriginFileWhenSuccess - 成功时删除原文件（开启原文件保留时移入保留目录）
  * @param {boolean} removeOriginFileWhenFailed - 失败时删除原文件
  * @param {boolean} skipOnFail - 遇到失败时跳过
+ * @param {boolean} preserveMetadata - 保留标签和循环点（写成 OGG / FLAC 注释）
  * @param {IProgressManager} progressManager - 进度管理器（可选）
  *
- * @returns {Promise<void>}
+ * @returns {Promise<ConversionSummary>} 处理和失败的作品文件夹数
  */
 export async function transferAudio(
   rootDir: string,
@@ -474,15 +588,22 @@ export async function transferAudio(
   removeOriginFileWhenSuccess: boolean,
   removeOriginFileWhenFailed: boolean,
   skipOnFail: boolean,
+  preserveMetadata: boolean = false,
   progressManager?: IProgressManager
-): Promise<void> {
-  await AudioConverter.processBmsFolders({
+): Promise<ConversionSummary> {
+  const summary = await AudioConverter.processBmsFolders({
     rootDir,
     inputExtensions: [...AUDIO_FILE_EXTS],
     presetNames,
     removeOnSuccess: removeOriginFileWhenSuccess,
     removeOnFail: removeOriginFileWhenFailed,
     skipOnFail,
+    preserveMetadata,
     progressManager,
   });
+  await notifyCompletion(
+    '音频转换完成',
+    `${summary.processed} 个作品，${summary.failed} 个失败${summary.cancelled ? '（已取消）' : ''}`
+  );
+  return summary;
 }
@@ -0,0 +1,195 @@
+/**
+ * 音频转换时保留标签和循环点
+ *
+ * 部分按键音在 WAV 的 smpl 块中记录了循环点。flac 命令通过 --keep-foreign-metadata
+ * 可以原样保留这些块，但 oggenc 和 ffmpeg 会丢弃它们，也不会复制原文件的标签。
+ * 这里读取原文件的循环点和标签，转换为 OGG / FLAC 时写成 LOOPSTART / LOOPLENGTH 注释
+ */
+
+import { open, SeekMode } from '@tauri-apps/plugin-fs';
+import type { FileHandle } from '@tauri-apps/plugin-fs';
+import { getFileExtension } from '../bms/scanner';
+import { MediaProbe } from './probe';
+
+/**
+ * 循环点（单位：采样）
+ */
+export interface LoopPoint {
+  start: number;
+  length: number;
+}
+
+/**
+ * 转换时附加的参数及循环点是否会丢失
+ */
+export interface PreserveArgs {
+  args: string[];
+  /** 原文件有循环点但输出无法保留 */
+  loopLost: boolean;
+}
+
+/** 可以写入注释的输出格式 */
+const COMMENT_OUTPUT_FORMATS = new Set(['ogg', 'flac']);
+
+/** 标签中不复制的字段（编码器信息由新的编码器写入） */
+const SKIPPED_TAGS = new Set(['encoder', 'encoded_by', 'vendor']);
+
+async function readAt(file: FileHandle, offset: number, length: number): Promise<Uint8Array> {
+  await file.seek(offset, SeekMode.Start);
+  const buffer = new Uint8Array(length);
+  let total = 0;
+  while (total < length) {
+    const bytesRead = await file.read(buffer.subarray(total));
+    if (bytesRead === null || bytesRead === 0) {
+      break;
+    }
+    total += bytesRead;
+  }
+  return buffer.subarray(0, total);
+}
+
+function readFourCC(bytes: Uint8Array, offset: number): string {
+  return String.fromCharCode(...bytes.subarray(offset, offset + 4));
+}
+
+/**
+ * 解析 smpl 块数据中的第一个循环
+ *
+ * 块头 36 字节之后是循环列表，每个循环依次为 ID、类型、起点、终点（包含）
+ */
+function parseSmplChunk(data: Uint8Array): LoopPoint | null {
+  if (data.length < 60) {
+    return null;
+  }
+  const view = new DataView(data.buffer, data.byteOffset, data.byteLength);
+  if (view.getUint32(28, true) === 0) {
+    return null;
+  }
+  const start = view.getUint32(44, true);
+  const end = view.getUint32(48, true);
+  return end >= start ? { start, length: end - start + 1 } : null;
+}
+
+/**
+ * 遍历 WAV 文件的块，找到 smpl 块
+ */
+async function readWavLoop(file: FileHandle): Promise<LoopPoint | null> {
+  const header = await readAt(file, 0, 12);
+  if (header.length < 12 || readFourCC(header, 0) !== 'RIFF' || readFourCC(header, 8) !== 'WAVE') {
+    return null;
+  }
+
+  let offset = 12;
+  while (true) {
+    const chunkHeader = await readAt(file, offset, 8);
+    if (chunkHeader.length < 8) {
+      return null;
+    }
+    const size = new DataView(chunkHeader.buffer).getUint32(4, true);
+    if (readFourCC(chunkHeader, 0) === 'smpl') {
+      return parseSmplChunk(await readAt(file, offset + 8, size));
+    }
+    // 块大小为奇数时有一个填充字节
+    offset += 8 + size + (size % 2);
+  }
+}
+
+/**
+ * 遍历 FLAC 元数据块，找到 flac 保存的 WAV smpl 块（APPLICATION "riff"）
+ */
+async function readFlacLoop(file: FileHandle): Promise<LoopPoint | null> {
+  if (readFourCC(await readAt(file, 0, 4), 0) !== 'fLaC') {
+    return null;
+  }
+
+  let offset = 4;
+  let last = false;
+  while (!last) {
+    const blockHeader = await readAt(file, offset, 4);
+    if (blockHeader.length < 4) {
+      return null;
+    }
+    last = (blockHeader[0] & 0x80) !== 0;
+    const type = blockHeader[0] & 0x7f;
+    const size = (blockHeader[1] << 16) | (blockHeader[2] << 8) | blockHeader[3];
+
+    if (type === 2 && size >= 12) {
+      const block = await readAt(file, offset + 4, size);
+      if (readFourCC(block, 0) === 'riff' && readFourCC(block, 4) === 'smpl') {
+        return parseSmplChunk(block.subarray(12));
+      }
+    }
+    offset += 4 + size;
+  }
+  return null;
+}
+
+/**
+ * 读取 WAV 或 FLAC 文件中的循环点
+ */
+export async function readLoopPoint(filePath: string): Promise<LoopPoint | null> {
+  const ext = getFileExtension(filePath);
+  if (ext !== 'wav' && ext !== 'flac') {
+    return null;
+  }
+
+  try {
+    const file = await open(filePath, { read: true });
+    try {
+      return ext === 'wav' ? await readWavLoop(file) : await readFlacLoop(file);
+    } finally {
+      await file.close();
+    }
+  } catch (error) {
+    console.warn(` !_! Failed to read loop point: ${filePath}`, error);
+    return null;
+  }
+}
+
+/**
+ * 计算保留标签和循环点所需的附加参数
+ *
+ * flac 命令的预设已经带有 --keep-foreign-metadata-if-present，不需要附加参数；
+ * ffmpeg 已通过 -map_metadata 复制标签，只需补上循环点；oggenc 需要逐个传入标签
+ *
+ * @param inputPath - 输入文件路径
+ * @param preset - 音频预设
+ * @param preserveMetadata - 是否保留标签和循环点（为 false 时只检查循环点是否丢失）
+ */
+export async function getPreserveArgs(
+  inputPath: string,
+  preset: { executor: string; outputFormat: string },
+  preserveMetadata: boolean
+): Promise<PreserveArgs> {
+  if (preset.executor === 'flac') {
+    return { args: [], loopLost: false };
+  }
+
+  const loop = await readLoopPoint(inputPath);
+  const canWriteComments = COMMENT_OUTPUT_FORMATS.has(preset.outputFormat);
+  const result: PreserveArgs = {
+    args: [],
+    loopLost: loop !== null && !(preserveMetadata && canWriteComments),
+  };
+  if (!preserveMetadata || !canWriteComments) {
+    return result;
+  }
+
+  const comments: [string, string][] = [];
+  if (preset.executor === 'oggenc') {
+    const tags = (await MediaProbe.getMediaInfo(inputPath))?.tags ?? {};
+    for (const [key, value] of Object.entries(tags)) {
+      if (!SKIPPED_TAGS.has(key.toLowerCase())) {
+        comments.push([key.toUpperCase(), value]);
+      }
+    }
+  }
+  if (loop) {
+    comments.push(['LOOPSTART', loop.start.toString()]);
+    comments.push(['LOOPLENGTH', loop.length.toString()]);
+  }
+
+  const flag = preset.executor === 'oggenc' ? '-c' : '-metadata';
+  result.args = comments.flatMap(([key, value]) => [flag, `${key}=${value}`]);
+  return result;
+}
@@ -6,9 +6,41 @@
  */
 
 import * as fs from '@tauri-apps/plugin-fs';
+import { listSplitBuckets } from '../bigpack/split';
 import { REMOVE_MEDIA_RULES } from './presets';
 import { RemoveMediaPreset } from '../../types/enums';
-import type { RemoveMediaRule } from './types';
+import { AUDIO_FILE_EXTS } from '../bms/scanner';
+import { skipIfPinned } from '../work/pin';
+import type { MediaCleanupReport, RemoveMediaRule, WorkMediaStats } from './types';
+
+/**
+ * 合并作品统计到报告中
+ */
+function addWorkStats(report: MediaCleanupReport, stats: WorkMediaStats): void {
+  report.works.push(stats);
+  report.removed += stats.removed;
+  for (const [ext, count] of Object.entries(stats.extensions)) {
+    report.extensions[ext] = (report.extensions[ext] ?? 0) + count;
+  }
+
+  const audioFormats = Object.keys(stats.extensions).filter((ext) =>
+    (AUDIO_FILE_EXTS as readonly string[]).includes(ext)
+  );
+  if (audioFormats.length === 1) {
+    const format = audioFormats[0];
+    report.singleAudioFormatWorks[format] = (report.singleAudioFormatWorks[format] ?? 0) + 1;
+  }
+}
+
+/**
+ * 按数量降序格式化扩展名统计（如 "flac 1200, ogg 30"）
+ */
+function formatExtensionCounts(counts: Record<string, number>): string {
+  return Object.entries(counts)
+    .sort((a, b) => b[1] - a[1])
+    .map(([ext, count]) => `${ext || '(none)'} ${count}`)
+    .join(', ');
+}
 
 /**
  * 媒体文件清理器类
@@ -114,20 +146,40 @@ export class MediaCleaner {
    *
    * @param {string} rootDir - 根目录路径
    * @param {RemoveMediaPreset} preset - 清理规则预设
+   * @param {boolean} includeSplitBuckets - 同时处理已拆分的目录（如 "ABCD"、"Root [ABCD]"）中的作品
    *
-   * @returns {Promise<void>}
+   * @returns {Promise<MediaCleanupReport>} 各作品和整个根目录的文件类型统计
    * @throws 如果目录操作失败
    */
-  static async removeUnneedMediaFiles(rootDir: string, preset: RemoveMediaPreset): Promise<void> {
+  static async removeUnneedMediaFiles(
+    rootDir: string,
+    preset: RemoveMediaPreset,
+    includeSplitBuckets: boolean = false
+  ): Promise<MediaCleanupReport> {
     const rules = REMOVE_MEDIA_RULES[preset];
     console.log('Selected rules:', rules);
 
+    const report: MediaCleanupReport = {
+      works: [],
+      removed: 0,
+      extensions: {},
+      singleAudioFormatWorks: {},
+    };
+
+    const buckets = includeSplitBuckets ? await listSplitBuckets(rootDir) : [];
+    for (const bucketDir of buckets) {
+      console.log(`Split bucket: ${bucketDir}`);
+      const bucketReport = await this.removeUnneedMediaFiles(bucketDir, preset);
+      bucketReport.works.forEach((stats) => addWorkStats(report, stats));
+    }
+
     // 遍历根目录下的所有子目录
     const entries = await fs.readDir(rootDir);
     for (const entry of entries) {
       if (entry.name === '.' || entry.name === '..') continue;
 
       const bmsDirPath = `${rootDir}/${entry.name}`;
+      if (buckets.includes(bmsDirPath)) continue;
 
       // 检查是否是目录
       let isDir = false;
@@ -138,24 +190,34 @@ export class MediaCleaner {
         continue;
       }
 
-      if (!isDir) continue;
+      if (!isDir || (await skipIfPinned(bmsDirPath))) continue;
+
+      addWorkStats(report, await this.removeUnneedMediaFilesInDir(bmsDirPath, rules));
+    }
 
-      await this.removeUnneedMediaFilesInDir(bmsDirPath, rules);
+    console.log(
+      `Removed ${report.removed} files in ${report.works.length} works. ` +
+        `Extensions: ${formatExtensionCounts(report.extensions) || '(none)'}`
+    );
+    const singleFormat = formatExtensionCounts(report.singleAudioFormatWorks);
+    if (singleFormat) {
+      console.log(`Works with a single audio format: ${singleFormat}`);
     }
+    return report;
   }
 
   /**
-   * 删除单个目录中不需要的媒体文件
-   * 对应 Rust: workdir_remove_unneed_media_files (root_bigpack.rs:538-636)
+   * 按规则列出单个目录中将被删除的媒体文件（不删除）
    *
    * @param workDir - 工作目录路径
    * @param rules - 删除规则列表
+   * @returns [保留的文件, 将被删除的文件] 路径对
    * @throws 如果目录操作失败
    */
-  private static async removeUnneedMediaFilesInDir(
+  static async planRemovals(
     workDir: string,
     rules: RemoveMediaRule[]
-  ): Promise<void> {
+  ): Promise<Array<[string, string]>> {
     const removePairs: Array<[string, string]> = [];
     const removedFiles = new Set<string>();
 
@@ -211,11 +273,29 @@ export class MediaCleaner {
       }
     }
 
+    return removePairs;
+  }
+
+  /**
+   * 删除单个目录中不需要的媒体文件
+   * 对应 Rust: workdir_remove_unneed_media_files (root_bigpack.rs:538-636)
+   *
+   * @param workDir - 工作目录路径
+   * @param rules - 删除规则列表
+   * @returns 清理后的文件类型统计
+   * @throws 如果目录操作失败
+   */
+  private static async removeUnneedMediaFilesInDir(
+    workDir: string,
+    rules: RemoveMediaRule[]
+  ): Promise<WorkMediaStats> {
+    const removePairs = await this.planRemovals(workDir, rules);
     if (removePairs.length > 0) {
       console.log(`Entering: ${workDir}`);
     }
 
     // 删除文件
+    let removed = 0;
     for (const [filePath, replacingFilePath] of removePairs) {
       const replacingFileName =
         replacingFilePath.split('/').pop() || replacingFilePath.split('\\').pop() || '';
@@ -224,6 +304,7 @@ export class MediaCleaner {
 
       try {
         await fs.remove(replacingFilePath);
+        removed++;
       } catch (error) {
         console.error(`Failed to remove file: ${replacingFilePath}`, error);
       }
@@ -262,6 +343,12 @@ export class MediaCleaner {
     if (extCount['mp4'] && extCount['mp4'].length > 1) {
       console.log(` - Tips: ${workDir} has more than 1 mp4 files!`, extCount['mp4']);
     }
+
+    const extensions: Record<string, number> = {};
+    for (const [ext, files] of Object.entries(extCount)) {
+      extensions[ext] = files.length;
+    }
+    return { workDir, removed, extensions };
   }
 
   /**
@@ -3,20 +3,33 @@
  * 用于控制并发任务数量，替代 Rust 的 futures::stream::buffer_unordered(64)
  */
 
+import { ResourceTelemetry } from '../telemetry';
+
 /**
  * 并发控制池类
  * 限制同时运行的任务数量，超过限制时等待其他任务完成
  */
 export class ConcurrencyPool<T> {
+  /** 未指定并发数时使用的默认值（与 Rust 代码一致为 64，可在设置中修改） */
+  private static defaultConcurrency = 64;
+
   private concurrency: number;
   private running: Set<Promise<T>>;
 
+  static setDefaultConcurrency(concurrency: number): void {
+    this.defaultConcurrency = Math.max(1, Math.floor(concurrency));
+  }
+
+  static getDefaultConcurrency(): number {
+    return this.defaultConcurrency;
+  }
+
   /**
    * 创建并发控制池
    *
-   * @param concurrency - 最大并发数（默认 64，与 Rust 代码一致）
+   * @param concurrency - 最大并发数（默认使用设置中的默认并发数）
    */
-  constructor(concurrency: number = 64) {
+  constructor(concurrency: number = ConcurrencyPool.defaultConcurrency) {
     this.concurrency = concurrency;
     this.running = new Set();
   }
@@ -30,14 +43,20 @@ export class ConcurrencyPool<T> {
    */
   async add(task: () => Promise<T>): Promise<T> {
     // 如果已达到并发限制，等待其他任务完成
-    while (this.running.size >= this.concurrency) {
-      const done = Promise.race(this.running);
-      await done.catch(() => {});
+    ResourceTelemetry.poolChanged(1, 0);
+    try {
+      while (this.running.size >= this.concurrency) {
+        const done = Promise.race(this.running);
+        await done.catch(() => {});
+      }
+    } finally {
+      ResourceTelemetry.poolChanged(-1, 0);
     }
 
     // 创建任务并添加到运行集合
     const promise = task();
     this.running.add(promise);
+    ResourceTelemetry.poolChanged(0, 1);
 
     try {
       // 等待任务完成并返回结果
@@ -45,6 +64,7 @@ export class ConcurrencyPool<T> {
     } finally {
       // 任务完成后从运行集合中移除
       this.running.delete(promise);
+      ResourceTelemetry.poolChanged(0, -1);
     }
   }
 
@@ -0,0 +1,109 @@
+/**
+ * 转换器日志
+ *
+ * ffmpeg 的日志级别和单个文件的转换超时设置，以及转换失败时记入结果的错误输出和命令行。
+ * 失败详情随转换结果返回前端，命令行可以直接复制到终端重现问题
+ */
+
+import type { ConversionFailure } from './types';
+
+/**
+ * ffmpeg 日志级别（-loglevel）
+ */
+export type FfmpegLogLevel =
+  | 'quiet'
+  | 'panic'
+  | 'fatal'
+  | 'error'
+  | 'warning'
+  | 'info'
+  | 'verbose'
+  | 'debug';
+
+/**
+ * 结果中保留的错误输出长度（字符，保留末尾）
+ */
+const MAX_STDERR_LENGTH = 2000;
+
+/**
+ * 一次批量转换最多记录的失败文件数
+ */
+const MAX_REPORTED_FAILURES = 100;
+
+/**
+ * 转换器日志设置
+ */
+export class ConverterLog {
+  /** 默认只输出致命错误；需要在失败详情中看到 ffmpeg 的错误输出时调高为 error */
+  private static logLevel: FfmpegLogLevel = 'panic';
+
+  static setLogLevel(level: FfmpegLogLevel): void {
+    this.logLevel = level;
+  }
+
+  static getLogLevel(): FfmpegLogLevel {
+    return this.logLevel;
+  }
+
+  /**
+   * ffmpeg 的日志参数
+   */
+  static ffmpegArgs(): string[] {
+    return ['-loglevel', this.logLevel];
+  }
+}
+
+/**
+ * 单个文件的转换超时
+ *
+ * 损坏的文件可能让 ffmpeg 一直不退出，卡住整个转换；超时后终止进程，
+ * 该文件记为失败（不再尝试其他预设），继续转换其他文件
+ */
+export class ConverterTimeout {
+  /** 超时秒数（0 表示不限） */
+  private static seconds = 1800;
+
+  static setTimeout(seconds: number): void {
+    this.seconds = Math.max(0, seconds);
+  }
+
+  static getTimeout(): number {
+    return this.seconds;
+  }
+
+  /**
+   * 传给 ProcessRunner.exec 的超时毫秒数
+   */
+  static timeoutMs(): number {
+    return this.seconds * 1000;
+  }
+}
+
+/**
+ * 格式化命令行（含空格或引号的参数加上引号）
+ */
+export function formatCommandLine(program: string, args: string[]): string {
+  const quote = (arg: string) =>
+    arg === '' || /[\s"'$`\\]/.test(arg) ? `"${arg.replace(/(["$`\\])/g, '\\$1')}"` : arg;
+  return [program, ...args].map(quote).join(' ');
+}
+
+/**
+ * 截断错误输出，只保留末尾（ffmpeg 的错误原因通常在最后几行）
+ */
+export function truncateStderr(stderr: string): string {
+  const trimmed = stderr.trim();
+  if (trimmed.length <= MAX_STDERR_LENGTH) {
+    return trimmed;
+  }
+  return `...${trimmed.slice(-MAX_STDERR_LENGTH)}`;
+}
+
+/**
+ * 记录一个转换失败的文件（超过上限后不再记录）
+ */
+export function recordFailure(failures: ConversionFailure[], failure: ConversionFailure): void {
+  if (failures.length < MAX_REPORTED_FAILURES) {
+    failures.push(failure);
+  }
+}
@@ -11,28 +11,47 @@ import { MediaCleaner } from './cleanup';
 // 类型定义
 export type {
   AudioProcessParams,
+  AudioStreamInfo,
+  ConversionFailure,
+  ConversionSummary,
   MediaCleanupParams,
+  MediaCleanupReport,
   ProcessResult,
   RemoveMediaRule,
   VideoInfo,
   VideoProcessParams,
+  WorkMediaStats,
 } from './types';
+export type { QualityFingerprint } from './quality';
+export type { LoopPoint } from './audioMetadata';
 
 // 枚举（既是类型也是值）
 export { AudioPreset, VideoPreset } from './types';
+export { QualityProfile } from './quality';
 
 // 预设配置
 export { AUDIO_PRESETS, MEDIA_EXT_LIST, REMOVE_MEDIA_RULES, VIDEO_PRESETS } from './presets';
 
 // 核心工具类
-export { ProcessRunner } from './processRunner';
+export { ExecutablePaths, ProcessRunner } from './processRunner';
 export { ConcurrencyPool } from './concurrency';
+export { ConverterLog, ConverterTimeout, formatCommandLine } from './converterLog';
+export type { FfmpegLogLevel } from './converterLog';
 
 // 功能模块
 export { AudioConverter } from './audio';
 export { VideoConverter } from './video';
 export { MediaCleaner } from './cleanup';
 export { MediaProbe } from './probe';
+export { getQualityFingerprint, matchesQualityProfile } from './quality';
+export { readLoopPoint } from './audioMetadata';
+export { removeTempOutputs, sweepTempOutputs } from './tempOutput';
+export {
+  ORIGINALS_DIR_NAME,
+  ORIGINALS_ROOT_SUFFIX,
+  OriginalsArchive,
+  disposeOriginal,
+} from './originals';
 
 // 功能函数
 export { transferAudio } from './audio';
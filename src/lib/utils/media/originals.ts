@@ -0,0 +1,95 @@
+/**
+ * 原文件保留
+ *
+ * 转换成功后默认删除原文件（wav、mp4 等）。开启保留后改为移入镜像目录：
+ * 与根目录同级的 `<根目录名>_originals/<作品>/`，或单独指定的根目录下的 `<作品>/`，保持作品内的相对路径。
+ * 保留目录不在根目录内，改名、检查、打包等遍历根目录的操作不会把它当作作品。
+ * 主曲库保持精简，之后仍可从原文件重新生成高质量版本
+ */
+
+import { copyFile, exists, mkdir, remove, rename } from '@tauri-apps/plugin-fs';
+import { getDirectoryPath, getFileName } from '../fs/path';
+
+/**
+ * 早期版本在根目录下保留原文件的目录名（批量转换时跳过）
+ */
+export const ORIGINALS_DIR_NAME = '_originals';
+
+/**
+ * 默认保留目录名的后缀（与根目录同级）
+ */
+export const ORIGINALS_ROOT_SUFFIX = '_originals';
+
+/**
+ * 原文件保留设置
+ */
+export class OriginalsArchive {
+  private static enabled = false;
+  /** 单独的保留根目录（为空时使用与作品所在根目录同级的 `<根目录名>_originals`） */
+  private static root: string | null = null;
+
+  static setEnabled(enabled: boolean): void {
+    this.enabled = enabled;
+  }
+
+  static isEnabled(): boolean {
+    return this.enabled;
+  }
+
+  /**
+   * 设置单独的保留根目录（null 表示使用与作品所在根目录同级的 `<根目录名>_originals`）
+   */
+  static setRoot(root: string | null): void {
+    this.root = root?.trim().replace(/[\\/]+$/, '') || null;
+  }
+
+  static getRoot(): string | null {
+    return this.root;
+  }
+
+  /**
+   * 获取作品中的文件在保留目录中的路径
+   *
+   * @param filePath - 文件路径
+   * @param workDir - 文件所在的作品目录
+   */
+  static getArchivedPath(filePath: string, workDir: string): string {
+    const libraryRoot = getDirectoryPath(workDir);
+    const root = this.root ?? `${libraryRoot}${ORIGINALS_ROOT_SUFFIX}`;
+    const relative = filePath.slice(workDir.length + 1);
+    return `${root}/${getFileName(workDir)}/${relative}`;
+  }
+}
+
+/**
+ * 转换成功后处理原文件：保留时移入保留目录，否则删除
+ *
+ * 保留目录在另一个磁盘上时先复制再删除
+ *
+ * @param filePath - 原文件路径
+ * @param workDir - 文件所在的作品目录
+ * @param keep - 是否保留（默认按原文件保留设置；打包流程传入 false）
+ */
+export async function disposeOriginal(
+  filePath: string,
+  workDir: string,
+  keep: boolean = OriginalsArchive.isEnabled()
+): Promise<void> {
+  if (!keep) {
+    await remove(filePath);
+    return;
+  }
+
+  const target = OriginalsArchive.getArchivedPath(filePath, workDir);
+  const targetDir = getDirectoryPath(target);
+  if (!(await exists(targetDir))) {
+    await mkdir(targetDir, { recursive: true });
+  }
+  try {
+    await rename(filePath, target);
+  } catch {
+    await copyFile(filePath, target);
+    await remove(filePath);
+  }
+  console.log(`Kept original: ${filePath} -> ${target}`);
+}
@@ -6,7 +6,7 @@
  */
 
 import { ProcessRunner } from './processRunner';
-import type { VideoInfo } from './types';
+import type { AudioStreamInfo, VideoInfo } from './types';
 
 /**
  * ffprobe 输出的媒体信息结构
@@ -17,6 +17,7 @@ interface FFProbeOutput {
     duration?: string;
     size?: string;
     bit_rate?: string;
+    tags?: Record<string, string>;
   };
 }
 
@@ -29,16 +30,28 @@ interface FFProbeStream {
   codec_name?: string;
   width?: number;
   height?: number;
+  sample_rate?: string;
+  channels?: number;
+  bits_per_sample?: number;
+  bits_per_raw_sample?: string;
   bit_rate?: string;
   duration?: string;
+  tags?: Record<string, string>;
 }
 
+/**
+ * 位深有意义的无损音频编码
+ */
+const LOSSLESS_AUDIO_CODEC_PATTERN = /^(pcm_|flac$|ape$|alac$|wavpack$|tta$)/;
+
 /**
  * 媒体信息
  */
 export interface MediaInfo {
   /** 视频信息（如果是视频文件） */
   video?: VideoInfo;
+  /** 第一个音频流的信息 */
+  audio?: AudioStreamInfo;
   /** 媒体时长（秒） */
   duration?: number;
   /** 文件大小（字节） */
@@ -47,6 +60,8 @@ export interface MediaInfo {
   bitRate?: number;
   /** 包含的流类型 */
   streamTypes: string[];
+  /** 标签（容器和音频流的标签合并，如 title、artist） */
+  tags?: Record<string, string>;
 }
 
 /**
@@ -113,12 +128,33 @@ export class MediaProbe {
           const parsed = parseInt(bitRateStr, 10);
           mediaInfo.bitRate = isNaN(parsed) ? undefined : parsed;
         }
+        if (probeOutput.format.tags) {
+          mediaInfo.tags = { ...probeOutput.format.tags };
+        }
       }
 
       // 解析流信息
       for (const stream of probeOutput.streams) {
         mediaInfo.streamTypes.push(stream.codec_type);
 
+        // OGG 的 Vorbis 注释在流标签中
+        if (stream.codec_type === 'audio' && stream.tags) {
+          mediaInfo.tags = { ...mediaInfo.tags, ...stream.tags };
+        }
+
+        if (stream.codec_type === 'audio' && !mediaInfo.audio) {
+          const codec = stream.codec_name ?? '';
+          // FLAC 的 bits_per_sample 为 0，位深在 bits_per_raw_sample 中
+          const bitDepth =
+            parseInt(stream.bits_per_raw_sample ?? '', 10) || stream.bits_per_sample || undefined;
+          mediaInfo.audio = {
+            codec,
+            sampleRate: parseInt(stream.sample_rate ?? '', 10) || 0,
+            channels: stream.channels ?? 0,
+            bitDepth: LOSSLESS_AUDIO_CODEC_PATTERN.test(codec) ? bitDepth : undefined,
+          };
+        }
+
         if (stream.codec_type === 'video' && stream.width && stream.height) {
           const bitRateStr = stream.bit_rate;
           const bitRate = bitRateStr && bitRateStr !== 'N/A' ? parseInt(bitRateStr, 10) : 0;
@@ -3,37 +3,132 @@
  * 封装 Tauri Shell Command API，用于执行外部工具（ffmpeg、flac、oggenc 等）
  */
 
+import { exists } from '@tauri-apps/plugin-fs';
 import { Command } from '@tauri-apps/plugin-shell';
+import type { Child } from '@tauri-apps/plugin-shell';
+import { ResourceTelemetry } from '../telemetry';
 import type { ProcessResult } from './types';
 
+/**
+ * 外部程序路径设置
+ *
+ * 未设置路径的程序按名称在 PATH 中查找
+ */
+export class ExecutablePaths {
+  private static paths = new Map<string, string>();
+
+  /**
+   * 设置程序路径（如 { ffmpeg: 'D:/tools/ffmpeg.exe' }），为空时全部按名称查找
+   */
+  static setPaths(paths: Record<string, string> | null): void {
+    this.paths = new Map(
+      Object.entries(paths ?? {})
+        .map(([program, path]) => [program, path.trim()] as const)
+        .filter(([, path]) => path !== '')
+    );
+  }
+
+  static getPaths(): Record<string, string> {
+    return Object.fromEntries(this.paths);
+  }
+
+  /**
+   * 获取实际执行的程序（已设置路径时为路径，否则为名称）
+   */
+  static resolve(program: string): string {
+    return this.paths.get(program) ?? program;
+  }
+}
+
 /**
  * 进程执行器类
  */
 export class ProcessRunner {
+  /** 正在运行的子进程（退出应用时终止） */
+  private static children = new Map<Child, string>();
+
   /**
    * 执行命令并返回结果
    *
+   * 传入 signal 时，信号触发（任务被取消）后终止子进程，返回的结果视为失败；
+   * 传入 timeout 时，超过时间仍未结束的子进程同样被终止，结果标记为 timedOut
+   *
    * @param program - 可执行文件名（如 'ffmpeg', 'flac'）
    * @param args - 命令行参数数组
-   * @param _options - 可选配置（timeout 暂不支持，仅保留接口兼容）
+   * @param options - 可选配置（timeout 为超时毫秒数，0 或不传表示不限；signal 用于取消；
+   *   cwd、env 为子进程的工作目录和环境变量）
    * @returns 执行结果
    */
   static async exec(
     program: string,
     args: string[],
-    _options?: { timeout?: number } // eslint-disable-line @typescript-eslint/no-unused-vars
+    options?: {
+      timeout?: number;
+      signal?: AbortSignal;
+      cwd?: string;
+      env?: Record<string, string>;
+    }
   ): Promise<ProcessResult> {
+    const signal = options?.signal;
+    if (signal?.aborted) {
+      return { success: false, stdout: '', stderr: '任务已取消', exitCode: null };
+    }
+
     try {
-      const command = Command.create(program, args);
+      const command = Command.create(ExecutablePaths.resolve(program), args, {
+        cwd: options?.cwd,
+        env: options?.env,
+      });
+      const stdout: string[] = [];
+      const stderr: string[] = [];
+      command.stdout.on('data', (line) => stdout.push(line.replace(/\r?\n$/, '')));
+      command.stderr.on('data', (line) => stderr.push(line.replace(/\r?\n$/, '')));
 
-      const result = await command.execute();
+      const closed = new Promise<number | null>((resolve, reject) => {
+        command.on('close', ({ code }) => resolve(code));
+        command.on('error', (error) => reject(new Error(error)));
+      });
+      const child = await command.spawn();
+      this.children.set(child, program);
+      ResourceTelemetry.processStarted(program);
 
-      return {
-        success: result.code === 0,
-        stdout: result.stdout,
-        stderr: result.stderr,
-        exitCode: result.code,
+      // 取消或超时时终止子进程（ffmpeg 等写入的是临时输出，由调用方丢弃）
+      const kill = () => {
+        child.kill().catch((error) => console.warn(` !_! Failed to kill ${program}:`, error));
       };
+      let timedOut = false;
+      const timeout = options?.timeout ?? 0;
+      const timer =
+        timeout > 0
+          ? setTimeout(() => {
+              timedOut = true;
+              console.warn(` !_! ${program} did not finish in ${timeout / 1000}s, killing it`);
+              kill();
+            }, timeout)
+          : null;
+      signal?.addEventListener('abort', kill, { once: true });
+      try {
+        const code = await closed;
+        if (signal?.aborted) {
+          stderr.push('任务已取消');
+        } else if (timedOut) {
+          stderr.push(`超过 ${timeout / 1000} 秒未完成，已终止`);
+        }
+        return {
+          success: code === 0 && !signal?.aborted && !timedOut,
+          stdout: stdout.join('\n'),
+          stderr: stderr.join('\n'),
+          exitCode: code,
+          timedOut,
+        };
+      } finally {
+        if (timer) {
+          clearTimeout(timer);
+        }
+        this.children.delete(child);
+        ResourceTelemetry.processEnded(program);
+        signal?.removeEventListener('abort', kill);
+      }
     } catch (error) {
       return {
         success: false,
@@ -44,6 +139,24 @@ export class ProcessRunner {
     }
   }
 
+  /**
+   * 终止所有正在运行的子进程（退出应用时调用，避免留下无人管理的 ffmpeg 等进程）
+   *
+   * @returns 终止的进程数
+   */
+  static async killAll(): Promise<number> {
+    const children = [...this.children];
+    await Promise.all(
+      children.map(([child, program]) =>
+        child.kill().catch((error) => console.warn(` !_! Failed to kill ${program}:`, error))
+      )
+    );
+    if (children.length > 0) {
+      console.log(`Killed ${children.length} running processes`);
+    }
+    return children.length;
+  }
+
   /**
    * 检查可执行文件是否存在
    *
@@ -51,6 +164,12 @@ export class ProcessRunner {
    * @returns 是否存在
    */
   static async checkExecutable(program: string): Promise<boolean> {
+    const resolved = ExecutablePaths.resolve(program);
+    if (resolved !== program) {
+      // 已设置路径时直接检查该路径
+      return await exists(resolved).catch(() => false);
+    }
+
     try {
       // 尝试使用 which 命令（Unix/Linux/macOS）
       const result = await Command.create('which', [program]).execute();
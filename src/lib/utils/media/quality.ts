@@ -0,0 +1,86 @@
+/**
+ * 作品的音视频质量指纹
+ *
+ * 记录作品中的音频格式和视频格式（按扩展名区分编码：mpg 为 MPEG-1，wmv 为 WMV2，
+ * mp4 / webm / mkv 为现代编码），用于判断作品是否已经是 HQ 或 LQ 版本，
+ * 避免重复运行大包转换时再次转换已经转换过的作品
+ */
+
+import type { DirEntry } from '@tauri-apps/plugin-fs';
+import { AUDIO_FILE_EXTS, getFileExtension, VIDEO_FILE_EXTS } from '../bms/scanner';
+
+/**
+ * 质量指纹
+ */
+export interface QualityFingerprint {
+  /** 数量最多的音频格式（扩展名），没有音频时为空 */
+  audio?: string;
+  /** 作品中出现的所有音频格式 */
+  audioFormats: string[];
+  /** 数量最多的视频格式（扩展名），没有视频时为空 */
+  video?: string;
+  /** 作品中出现的所有视频格式 */
+  videoFormats: string[];
+}
+
+/**
+ * 大包版本
+ */
+export enum QualityProfile {
+  /** beatoraja / Qwilight 用（FLAC 或 OGG 音频，视频不限） */
+  HQ = 'hq',
+  /** LR2 用（OGG 音频，MPEG-1 / WMV / AVI 视频） */
+  LQ = 'lq',
+}
+
+/**
+ * 各版本允许的格式（为空表示不限）
+ */
+const PROFILE_FORMATS: Record<QualityProfile, { audio?: string[]; video?: string[] }> = {
+  [QualityProfile.HQ]: { audio: ['flac', 'ogg'] },
+  [QualityProfile.LQ]: { audio: ['ogg'], video: ['mpg', 'mpeg', 'wmv', 'avi'] },
+};
+
+/**
+ * 统计各格式的数量，按数量降序返回
+ */
+function countFormats(names: string[], exts: readonly string[]): string[] {
+  const counts = new Map<string, number>();
+  for (const name of names) {
+    const ext = getFileExtension(name);
+    if (exts.includes(ext)) {
+      counts.set(ext, (counts.get(ext) ?? 0) + 1);
+    }
+  }
+  return [...counts.entries()].sort((a, b) => b[1] - a[1]).map(([ext]) => ext);
+}
+
+/**
+ * 根据作品目录的文件列表计算质量指纹
+ */
+export function getQualityFingerprint(entries: DirEntry[]): QualityFingerprint {
+  const names = entries.filter((entry) => !entry.isDirectory && entry.name).map((e) => e.name);
+  const audioFormats = countFormats(names, AUDIO_FILE_EXTS);
+  const videoFormats = countFormats(names, VIDEO_FILE_EXTS);
+  return {
+    audio: audioFormats[0],
+    audioFormats: [...audioFormats].sort(),
+    video: videoFormats[0],
+    videoFormats: [...videoFormats].sort(),
+  };
+}
+
+/**
+ * 作品的音频或视频是否已经全部是目标版本的格式
+ *
+ * 只看数量最多的格式并不够（少数残留的 FLAC 仍需转换），因此要求出现的所有格式都符合
+ */
+export function matchesQualityProfile(
+  fingerprint: QualityFingerprint,
+  profile: QualityProfile,
+  media: 'audio' | 'video'
+): boolean {
+  const allowed = PROFILE_FORMATS[profile][media];
+  const formats = media === 'audio' ? fingerprint.audioFormats : fingerprint.videoFormats;
+  return !allowed || formats.every((format) => allowed.includes(format));
+}
@@ -0,0 +1,84 @@
+/**
+ * 转换输出的临时文件
+ *
+ * 转换器先写入输出路径旁的 `.tmp` 文件，成功后再重命名为最终文件名。
+ * 这样中途崩溃或取消时只会留下 `.tmp` 文件，不会留下看起来已经转换完成的残缺文件。
+ * 应用启动时清理最近使用的根目录和作品目录中残留的 `.tmp` 文件，每次批量转换开始时再清理一次
+ */
+
+import { exists, readDir, remove, rename } from '@tauri-apps/plugin-fs';
+import { MEDIA_FILE_EXTS } from '../bms/scanner';
+import { RecentDirs } from '../recentDirs';
+
+/** 临时输出文件的后缀 */
+export const TEMP_OUTPUT_SUFFIX = '.tmp';
+
+const TEMP_OUTPUT_PATTERN = new RegExp(`\\.(${MEDIA_FILE_EXTS.join('|')})\\.tmp$`, 'i');
+
+/**
+ * 获取输出文件对应的临时文件路径
+ */
+export function getTempOutputPath(outputPath: string): string {
+  return `${outputPath}${TEMP_OUTPUT_SUFFIX}`;
+}
+
+/**
+ * 转换成功后把临时文件重命名为最终文件（同一目录内的重命名是原子操作）
+ */
+export async function commitTempOutput(outputPath: string): Promise<void> {
+  await rename(getTempOutputPath(outputPath), outputPath);
+}
+
+/**
+ * 删除转换失败时留下的临时文件（不存在时忽略）
+ */
+export async function discardTempOutput(outputPath: string): Promise<void> {
+  await remove(getTempOutputPath(outputPath)).catch(() => undefined);
+}
+
+/**
+ * 清理目录中残留的临时输出文件
+ *
+ * @returns 删除的文件数
+ */
+export async function removeTempOutputs(dirPath: string): Promise<number> {
+  let removed = 0;
+  for (const entry of await readDir(dirPath)) {
+    if (!entry.isFile || !TEMP_OUTPUT_PATTERN.test(entry.name)) {
+      continue;
+    }
+    try {
+      await remove(`${dirPath}/${entry.name}`);
+      console.log(`Removed leftover temp output: ${dirPath}/${entry.name}`);
+      removed++;
+    } catch (error) {
+      console.warn(` !_! Failed to remove temp output: ${dirPath}/${entry.name}`, error);
+    }
+  }
+  return removed;
+}
+
+/**
+ * 启动时清理最近使用的根目录（其下各作品目录）和作品目录中残留的临时输出文件
+ *
+ * @returns 删除的文件数
+ */
+export async function sweepTempOutputs(): Promise<number> {
+  const workDirs = new Set(await RecentDirs.list('work'));
+  for (const rootDir of await RecentDirs.list('root')) {
+    const entries = (await exists(rootDir)) ? await readDir(rootDir).catch(() => []) : [];
+    for (const entry of entries) {
+      if (entry.isDirectory) {
+        workDirs.add(`${rootDir}/${entry.name}`);
+      }
+    }
+  }
+
+  let removed = 0;
+  for (const workDir of workDirs) {
+    if (await exists(workDir)) {
+      removed += await removeTempOutputs(workDir).catch(() => 0);
+    }
+  }
+  return removed;
+}
@@ -52,6 +52,8 @@ export interface ProcessResult {
   stderr: string;
   /** 退出码 */
   exitCode: number | null;
+  /** 是否因超时被终止 */
+  timedOut?: boolean;
 }
 
 /**
@@ -66,10 +68,16 @@ export interface AudioProcessParams {
   presetNames: AudioPreset[];
   /** 成功时是否删除原文件 */
   removeOnSuccess: boolean;
+  /** 删除原文件时是否改为移入保留目录（默认按原文件保留设置，打包流程传入 false） */
+  keepOriginals?: boolean;
   /** 失败时是否删除原文件 */
   removeOnFail: boolean;
   /** 失败时是否跳过后续处理 */
   skipOnFail: boolean;
+  /** 跳过的作品文件夹名（已经是目标格式的作品） */
+  skipDirs?: Set<string>;
+  /** 是否保留标签和循环点（写成 OGG / FLAC 注释） */
+  preserveMetadata?: boolean;
   /** 进度管理器（可选） */
   progressManager?: IProgressManager;
 }
@@ -86,14 +94,48 @@ export interface VideoProcessParams {
   presetNames: VideoPreset[];
   /** 成功时是否删除原文件 */
   removeOriginal: boolean;
+  /** 删除原文件时是否改为移入保留目录（默认按原文件保留设置，打包流程传入 false） */
+  keepOriginals?: boolean;
   /** 是否删除已存在的输出文件 */
   removeExisting: boolean;
   /** 是否使用推荐预设（根据视频宽高比） */
   usePreferred: boolean;
+  /** 跳过的作品文件夹名（已经是目标格式的作品） */
+  skipDirs?: Set<string>;
   /** 进度管理器（可选） */
   progressManager?: IProgressManager;
 }
 
+/**
+ * 单个文件转换失败的详情
+ */
+export interface ConversionFailure {
+  /** 输入文件 */
+  file: string;
+  /** 最后尝试的预设 */
+  preset: string;
+  /** 最后一次尝试的完整命令行（可复制到终端重现） */
+  commandLine: string;
+  exitCode: number | null;
+  /** 转换器的错误输出（过长时只保留末尾） */
+  stderr: string;
+}
+
+/**
+ * 批量转换结果
+ */
+export interface ConversionSummary {
+  /** 处理的作品文件夹数 */
+  processed: number;
+  /** 出现错误的作品文件夹数 */
+  failed: number;
+  /** 已经是目标格式而跳过的作品文件夹数 */
+  skipped: number;
+  cancelled: boolean;
+  /** 所有预设都失败的文件（最多记录 100 个） */
+  failures: ConversionFailure[];
+}
+
 /**
  * 媒体清理参数
  */
@@ -104,6 +146,30 @@ export interface MediaCleanupParams {
   dryRun: boolean;
 }
 
+/**
+ * 单个作品的文件类型统计
+ */
+export interface WorkMediaStats {
+  workDir: string;
+  /** 删除的重复格式文件数 */
+  removed: number;
+  /** 清理后各扩展名（小写，不带点）的文件数 */
+  extensions: Record<string, number>;
+}
+
+/**
+ * 媒体清理报告
+ */
+export interface MediaCleanupReport {
+  works: WorkMediaStats[];
+  /** 删除的重复格式文件总数 */
+  removed: number;
+  /** 所有作品合计的扩展名文件数 */
+  extensions: Record<string, number>;
+  /** 音频只有一种格式的作品数（按格式统计，如 HQ 转换后仍只有 wav 的作品） */
+  singleAudioFormatWorks: Record<string, number>;
+}
+
 /**
  * 媒体删除规则
  * [保留格式列表, 删除格式列表]
@@ -127,3 +193,15 @@ export interface VideoInfo {
   /** 比特率 */
   bitRate: number;
 }
+
+/**
+ * 音频流信息
+ */
+export interface AudioStreamInfo {
+  codec: string;
+  /** 采样率（Hz） */
+  sampleRate: number;
+  channels: number;
+  /** 位深（只有 PCM、FLAC 等无损格式有意义，有损格式为空） */
+  bitDepth?: number;
+}
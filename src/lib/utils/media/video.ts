@@ -6,10 +6,47 @@
 import * as fs from '@tauri-apps/plugin-fs';
 import { ProcessRunner } from './processRunner';
 import { ConcurrencyPool } from './concurrency';
+import { ORIGINALS_DIR_NAME, OriginalsArchive, disposeOriginal } from './originals';
 import { VIDEO_PRESETS } from './presets';
-import type { VideoInfo, VideoPreset, VideoProcessParams } from './types';
+import type {
+  ConversionFailure,
+  ConversionSummary,
+  VideoInfo,
+  VideoPreset,
+  VideoProcessParams,
+} from './types';
 import type { IProgressManager } from '../progress';
+import { runHooks } from '../hooks';
+import { notifyCompletion } from '../notify';
+import { ResourceTelemetry } from '../telemetry';
+import { skipIfPinned } from '../work/pin';
 import { VIDEO_FILE_EXTS } from '../bms/scanner';
+import {
+  ConverterLog,
+  ConverterTimeout,
+  formatCommandLine,
+  recordFailure,
+  truncateStderr,
+} from './converterLog';
+import {
+  commitTempOutput,
+  discardTempOutput,
+  getTempOutputPath,
+  removeTempOutputs,
+} from './tempOutput';
+
+/**
+ * 输出扩展名对应的 ffmpeg 封装格式
+ */
+const VIDEO_MUXERS: Record<string, string> = {
+  avi: 'avi',
+  wmv: 'asf',
+  mpg: 'mpeg',
+  mpeg: 'mpeg',
+  mp4: 'mp4',
+  mkv: 'matroska',
+  webm: 'webm',
+};
 
 /**
  * 视频转换器类
@@ -22,7 +59,7 @@ export class VideoConverter {
    * @param params - 视频处理参数
    * @throws 如果目录操作或视频处理失败
    */
-  static async processBmsFolders(params: VideoProcessParams): Promise<void> {
+  static async processBmsFolders(params: VideoProcessParams): Promise<ConversionSummary> {
     const {
       rootDir,
       inputExtensions,
@@ -30,11 +67,20 @@ export class VideoConverter {
       removeOriginal,
       removeExisting,
       usePreferred,
+      skipDirs,
+      keepOriginals = OriginalsArchive.isEnabled(),
       progressManager,
     } = params;
 
     // 启动进度管理器
     progressManager?.start();
+    const summary: ConversionSummary = {
+      processed: 0,
+      failed: 0,
+      skipped: 0,
+      cancelled: false,
+      failures: [],
+    };
 
     try {
       // 验证预设名称
@@ -60,7 +106,7 @@ export class VideoConverter {
           continue;
         }
 
-        if (!isDir) continue;
+        if (!isDir || entry.name === ORIGINALS_DIR_NAME) continue;
 
         folders.push({
           name: entry.name,
@@ -76,7 +122,8 @@ export class VideoConverter {
         if (progressManager?.shouldStop()) {
           if (progressManager.getProgress().cancelled) {
             console.log('任务已取消');
-            return;
+            summary.cancelled = true;
+            return summary;
           }
           // 等待恢复
           await progressManager.waitForResume();
@@ -84,10 +131,15 @@ export class VideoConverter {
 
         const folder = folders[i];
         const dirPath = folder.path;
+        if (skipDirs?.has(folder.name) || (await skipIfPinned(dirPath))) {
+          summary.skipped++;
+          continue;
+        }
 
         progressManager?.update(i, folders.length, `处理 ${folder.name}`);
 
         console.log(`Processing BMS folder: ${dirPath}`);
+        summary.processed++;
 
         try {
           const success = await this.convertInDirectory(
@@ -97,22 +149,28 @@ export class VideoConverter {
             removeOriginal,
             removeExisting,
             usePreferred,
-            progressManager
+            progressManager,
+            summary.failures,
+            keepOriginals
           );
 
           if (success) {
             console.log(`Successfully processed ${dirPath}`);
+            await runHooks('after_conversion', { workPath: dirPath });
           } else {
             console.error(`Errors occurred in ${dirPath}`);
+            summary.failed++;
           }
         } catch (error) {
           console.error(`Error processing ${dirPath}:`, error);
+          summary.failed++;
           // 遇到错误时跳过，不抛出异常
         }
       }
 
       // 完成
       progressManager?.update(folders.length, folders.length, '视频转换完成');
+      return summary;
     } catch (error) {
       progressManager?.reportError(error instanceof Error ? error.message : String(error));
       throw error;
@@ -130,6 +188,8 @@ export class VideoConverter {
    * @param removeExisting - 是否删除已存在的输出文件
    * @param usePreferred - 是否使用推荐预设
    * @param progressManager - 进度管理器（可选）
+   * @param failures - 记录所有预设都失败的文件详情（可选）
+   * @param keepOriginals - 删除原文件时是否改为移入保留目录
    * @returns 是否成功
    */
   static async convertInDirectory(
@@ -139,18 +199,21 @@ export class VideoConverter {
     removeOriginal: boolean,
     removeExisting: boolean,
     usePreferred: boolean,
-    progressManager?: IProgressManager
+    progressManager?: IProgressManager,
+    failures?: ConversionFailure[],
+    keepOriginals: boolean = OriginalsArchive.isEnabled()
   ): Promise<boolean> {
     // 预检查可执行文件是否存在
     await this.checkExecutables(presetNames);
 
-    // 收集需要处理的文件
+    // 清理上次中断时残留的临时输出，再收集需要处理的文件
+    await removeTempOutputs(dirPath);
     const files = await this.collectFiles(dirPath, inputExtensions);
 
     const hadError = { value: false };
 
     // 使用并发池处理文件
-    const pool = new ConcurrencyPool(64);
+    const pool = new ConcurrencyPool();
 
     for (const filePath of files) {
       // 检查是否应该停止（暂停或取消）
@@ -182,6 +245,7 @@ export class VideoConverter {
         }
 
         let success = false;
+        let lastFailure: ConversionFailure | null = null;
         for (const presetName of presetsToTry) {
           const preset = VIDEO_PRESETS[presetName];
           if (!preset) continue;
@@ -207,31 +271,58 @@ export class VideoConverter {
           }
 
           // 构建命令
-          const args = this.buildCommandArgs(filePath, outputPath, preset);
+          const args = this.buildCommandArgs(filePath, getTempOutputPath(outputPath), preset);
           console.log(`Executing: ${preset.executor} ${args.join(' ')}`);
 
-          const result = await ProcessRunner.exec(preset.executor, args);
+          // 先写入临时文件，成功后再重命名，避免中断时留下残缺的输出
+          const result = await ProcessRunner.exec(preset.executor, args, {
+            signal: progressManager?.signal,
+            timeout: ConverterTimeout.timeoutMs(),
+          });
+          const committed =
+            result.success &&
+            (await commitTempOutput(outputPath).then(
+              () => true,
+              (error) => {
+                console.error(`Failed to rename temp output: ${outputPath}`, error);
+                return false;
+              }
+            ));
 
-          if (result.success) {
+          if (committed) {
             console.log(`Successfully converted: ${outputPath}`);
+            const readBytes = await ResourceTelemetry.addFiles([filePath], [outputPath]);
+            progressManager?.addProcessed(1, readBytes);
             success = true;
             if (removeOriginal) {
               try {
-                await fs.remove(filePath);
+                await disposeOriginal(filePath, dirPath, keepOriginals);
               } catch (error) {
                 console.error(`Failed to remove original file: ${filePath}`, error);
               }
             }
             break;
           } else {
-            console.error(`Conversion failed for preset ${presetName}: ${result.stderr}`);
-            // 删除失败的输出文件
-            if (await this.fileExists(outputPath)) {
-              try {
-                await fs.remove(outputPath);
-              } catch (error) {
-                console.error(`Failed to remove failed output: ${outputPath}`, error);
-              }
+            lastFailure = {
+              file: filePath,
+              preset: presetName,
+              commandLine: formatCommandLine(preset.executor, args),
+              exitCode: result.exitCode,
+              stderr: truncateStderr(result.stderr),
+            };
+            console.error(
+              `Conversion failed for preset ${presetName}:\n` +
+                `  ${lastFailure.commandLine}\n${lastFailure.stderr}`
+            );
+            // 删除失败的临时输出
+            await discardTempOutput(outputPath);
+            // 被取消时不再尝试其他预设，也不算作失败
+            if (progressManager?.getProgress().cancelled) {
+              return;
+            }
+            // 超时的文件多半已损坏，换预设也会卡住，直接记为失败
+            if (result.timedOut) {
+              break;
             }
           }
         }
@@ -239,6 +330,9 @@ export class VideoConverter {
         if (!success) {
           hadError.value = true;
           console.error(`All presets failed for: ${filePath}`);
+          if (lastFailure && failures) {
+            recordFailure(failures, lastFailure);
+          }
         }
       });
     }
@@ -350,6 +444,7 @@ export class VideoConverter {
     }
   ): string[] {
     return [
+      ...(preset.executor === 'ffmpeg' ? ConverterLog.ffmpegArgs() : []),
       ...preset.inputArgs,
       inputPath,
       ...preset.filterArgs,
@@ -358,6 +453,9 @@ export class VideoConverter {
       '-c:v',
       preset.outputCodec,
       ...preset.extraArgs,
+      // 输出到临时文件时无法从扩展名推断格式
+      '-f',
+      VIDEO_MUXERS[preset.outputExt] ?? preset.outputExt,
       outputPath,
     ];
   }
@@ -481,12 +579,12 @@ export class VideoConverter {
  *
  * @param {string} rootDir - 根目录路径
  * @param {VideoPreset[]} presetNames - 目标格式预设名称
- * @param {boolean} removeOriginFile - 成功时删除原文件
+ * @param {boolean} removeOriginFile - 成功时删除原文件（开启原文件保留时移入保留目录）
  * @param {boolean} removeExistingTargetFile - 删除已存在的目标文件
  * @param {boolean} usePrefered - 使用推荐预设
  * @param {IProgressManager} progressManager - 进度管理器（可选）
  *
- * @returns {Promise<void>}
+ * @returns {Promise<ConversionSummary>} 处理和失败的作品文件夹数
  */
 export async function transferVideo(
   rootDir: string,
@@ -495,8 +593,8 @@ export async function transferVideo(
   removeExistingTargetFile: boolean,
   usePrefered: boolean,
   progressManager?: IProgressManager
-): Promise<void> {
-  await VideoConverter.processBmsFolders({
+): Promise<ConversionSummary> {
+  const summary = await VideoConverter.processBmsFolders({
     rootDir,
     inputExtensions: [...VIDEO_FILE_EXTS],
     presetNames,
@@ -505,4 +603,9 @@ export async function transferVideo(
     usePreferred: usePrefered,
     progressManager,
   });
+  await notifyCompletion(
+    '视频转换完成',
+    `${summary.processed} 个作品，${summary.failed} 个失败${summary.cancelled ? '（已取消）' : ''}`
+  );
+  return summary;
 }
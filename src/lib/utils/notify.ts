@@ -0,0 +1,51 @@
+/**
+ * 桌面通知
+ *
+ * 大包流水线、批量转换等耗时操作结束时发送一条桌面通知（处理数量、失败数量），
+ * 不必一直盯着日志等待完成
+ */
+
+import {
+  isPermissionGranted,
+  requestPermission,
+  sendNotification,
+} from '@tauri-apps/plugin-notification';
+
+/**
+ * 完成通知设置
+ */
+export class CompletionNotification {
+  private static enabled = true;
+
+  static setEnabled(enabled: boolean): void {
+    this.enabled = enabled;
+  }
+
+  static isEnabled(): boolean {
+    return this.enabled;
+  }
+}
+
+/**
+ * 发送操作完成通知（未开启或没有通知权限时不发送）
+ *
+ * @param title - 操作名称
+ * @param body - 结果摘要
+ */
+export async function notifyCompletion(title: string, body: string): Promise<void> {
+  if (!CompletionNotification.isEnabled()) {
+    return;
+  }
+
+  try {
+    let granted = await isPermissionGranted();
+    if (!granted) {
+      granted = (await requestPermission()) === 'granted';
+    }
+    if (granted) {
+      sendNotification({ title, body });
+    }
+  } catch (error) {
+    console.warn(' !_! Failed to send notification:', error);
+  }
+}
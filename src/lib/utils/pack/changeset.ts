@@ -0,0 +1,401 @@
+/**
+ * 流水线变更记录
+ *
+ * 在流水线运行前后对根目录做快照并比较，生成机器可读的变更记录
+ * （新增 / 删除 / 重命名的作品、更新的谱面、转换的媒体文件、删除的文件及大小），
+ * 保存在应用数据目录的运行历史中
+ */
+
+import { readDir, stat } from '@tauri-apps/plugin-fs';
+import { isChartFile } from '../bms/scanner';
+import { getFileExtension, getFileStem } from '../fs/path';
+import { ConcurrencyPool } from '../media/concurrency';
+import type { ConversionFailure, ConversionSummary } from '../media/types';
+import { notifyCompletion } from '../notify';
+import { getAppDataPath, writeJsonFile } from '../storage';
+
+/**
+ * 判断两个作品目录是重命名关系所需的最少相同文件比例
+ */
+const RENAME_MATCH_RATIO = 0.5;
+
+/**
+ * 文件及大小
+ */
+export interface FileChange {
+  /** 相对根目录的路径 */
+  path: string;
+  size: number;
+}
+
+/**
+ * 整个新增或删除的作品
+ */
+export interface WorkChange {
+  name: string;
+  fileCount: number;
+  size: number;
+}
+
+/**
+ * 作品目录重命名
+ */
+export interface WorkRename {
+  from: string;
+  to: string;
+}
+
+/**
+ * 媒体文件转换（同目录下同名文件换了扩展名）
+ */
+export interface MediaConversion {
+  from: string;
+  to: string;
+  sizeBefore: number;
+  sizeAfter: number;
+}
+
+/**
+ * 单个根目录的变更
+ */
+export interface RootChangeset {
+  rootDir: string;
+  worksAdded: WorkChange[];
+  worksRemoved: WorkChange[];
+  worksRenamed: WorkRename[];
+  chartsAdded: string[];
+  chartsUpdated: string[];
+  mediaConverted: MediaConversion[];
+  /** 已有作品中新增的其他文件 */
+  filesAdded: FileChange[];
+  /** 已有作品中删除的文件（不含转换的媒体文件） */
+  filesRemoved: FileChange[];
+  sizeBefore: number;
+  sizeAfter: number;
+}
+
+/**
+ * 一次流水线运行的变更记录
+ */
+export interface PipelineChangeset {
+  /** 运行 ID（同时是历史文件名） */
+  id: string;
+  pipeline: string;
+  startedAt: string;
+  finishedAt: string;
+  dryRun: boolean;
+  roots: RootChangeset[];
+  /** 运行失败时的错误信息（变更记录到失败为止） */
+  error?: string;
+  /** 媒体转换出错的作品文件夹数 */
+  conversionFailures?: number;
+  /** 已经是目标格式而跳过转换的作品文件夹数 */
+  conversionSkipped?: number;
+  /** 转换失败的文件（含命令行和错误输出） */
+  conversionErrors?: ConversionFailure[];
+}
+
+/**
+ * 文件快照信息
+ */
+interface FileSnapshot {
+  size: number;
+  mtime: number;
+}
+
+/**
+ * 根目录快照
+ */
+interface RootSnapshot {
+  works: Set<string>;
+  /** 相对路径 -> 文件信息 */
+  files: Map<string, FileSnapshot>;
+}
+
+/**
+ * 对根目录做快照（记录所有文件的大小和修改时间）
+ */
+async function snapshotRoot(rootDir: string): Promise<RootSnapshot> {
+  const snapshot: RootSnapshot = { works: new Set(), files: new Map() };
+  const pool = new ConcurrencyPool<void>(32);
+  const tasks: Promise<void>[] = [];
+
+  const walk = async (dirPath: string, relative: string): Promise<void> => {
+    for (const entry of await readDir(dirPath)) {
+      if (!entry.name) {
+        continue;
+      }
+
+      const entryPath = `${dirPath}/${entry.name}`;
+      const entryRelative = relative ? `${relative}/${entry.name}` : entry.name;
+      if (entry.isDirectory) {
+        if (!relative) {
+          snapshot.works.add(entry.name);
+        }
+        await walk(entryPath, entryRelative);
+      } else if (relative) {
+        tasks.push(
+          pool.add(async () => {
+            const info = await stat(entryPath);
+            snapshot.files.set(entryRelative, {
+              size: info.size,
+              mtime: info.mtime?.getTime() ?? 0,
+            });
+          })
+        );
+      }
+    }
+  };
+
+  try {
+    await walk(rootDir, '');
+  } catch (error) {
+    // 根目录可能还不存在（如首次生成大包）
+    console.warn(`Failed to snapshot ${rootDir}:`, error);
+  }
+  await Promise.all(tasks);
+  return snapshot;
+}
+
+function workOf(path: string): string {
+  return path.slice(0, path.indexOf('/'));
+}
+
+/**
+ * 按作品分组的文件
+ */
+function groupFilesByWork(
+  files: Map<string, FileSnapshot>
+): Map<string, Map<string, FileSnapshot>> {
+  const groups = new Map<string, Map<string, FileSnapshot>>();
+  for (const [path, info] of files) {
+    const work = workOf(path);
+    let group = groups.get(work);
+    if (!group) {
+      group = new Map();
+      groups.set(work, group);
+    }
+    group.set(path.slice(work.length + 1), info);
+  }
+  return groups;
+}
+
+function summarizeWork(name: string, files: Map<string, FileSnapshot> | undefined): WorkChange {
+  const sizes = [...(files?.values() ?? [])].map((info) => info.size);
+  return { name, fileCount: sizes.length, size: sizes.reduce((sum, size) => sum + size, 0) };
+}
+
+/**
+ * 比较运行前后的快照
+ */
+function diffSnapshots(rootDir: string, before: RootSnapshot, after: RootSnapshot): RootChangeset {
+  const beforeWorks = groupFilesByWork(before.files);
+  const afterWorks = groupFilesByWork(after.files);
+  const removedWorks = [...before.works].filter((work) => !after.works.has(work));
+  const addedWorks = new Set([...after.works].filter((work) => !before.works.has(work)));
+
+  // 删除的作品与新增的作品有足够多相同文件（文件名和大小）时视为重命名
+  const worksRenamed: WorkRename[] = [];
+  for (const from of removedWorks) {
+    const fromFiles = beforeWorks.get(from);
+    if (!fromFiles || fromFiles.size === 0) {
+      continue;
+    }
+
+    let best: { to: string; matched: number } | null = null;
+    for (const to of addedWorks) {
+      const toFiles = afterWorks.get(to);
+      let matched = 0;
+      for (const [name, info] of fromFiles) {
+        if (toFiles?.get(name)?.size === info.size) {
+          matched++;
+        }
+      }
+      if (matched > (best?.matched ?? 0)) {
+        best = { to, matched };
+      }
+    }
+
+    if (best && best.matched / fromFiles.size >= RENAME_MATCH_RATIO) {
+      worksRenamed.push({ from, to: best.to });
+      addedWorks.delete(best.to);
+    }
+  }
+
+  // 重命名的作品按新名称比较文件
+  const renamedTo = new Map(worksRenamed.map((rename) => [rename.from, rename.to]));
+  const beforeFiles = new Map<string, FileSnapshot>();
+  for (const [path, info] of before.files) {
+    const work = workOf(path);
+    const newWork = renamedTo.get(work);
+    beforeFiles.set(newWork ? `${newWork}${path.slice(work.length)}` : path, info);
+  }
+
+  const changeset: RootChangeset = {
+    rootDir,
+    worksAdded: [...addedWorks].map((work) => summarizeWork(work, afterWorks.get(work))),
+    worksRemoved: removedWorks
+      .filter((work) => !renamedTo.has(work))
+      .map((work) => summarizeWork(work, beforeWorks.get(work))),
+    worksRenamed,
+    chartsAdded: [],
+    chartsUpdated: [],
+    mediaConverted: [],
+    filesAdded: [],
+    filesRemoved: [],
+    sizeBefore: [...before.files.values()].reduce((sum, info) => sum + info.size, 0),
+    sizeAfter: [...after.files.values()].reduce((sum, info) => sum + info.size, 0),
+  };
+
+  const wholeWorks = new Set([...addedWorks, ...changeset.worksRemoved.map((work) => work.name)]);
+  const removed = new Map<string, FileSnapshot>();
+  const added = new Map<string, FileSnapshot>();
+
+  for (const [path, info] of beforeFiles) {
+    const current = after.files.get(path);
+    if (!current) {
+      if (!wholeWorks.has(workOf(path))) {
+        removed.set(path, info);
+      }
+    } else if (isChartFile(path) && (current.size !== info.size || current.mtime !== info.mtime)) {
+      changeset.chartsUpdated.push(path);
+    }
+  }
+  for (const [path, info] of after.files) {
+    if (!beforeFiles.has(path) && !wholeWorks.has(workOf(path))) {
+      added.set(path, info);
+    }
+  }
+
+  // 同目录下同名不同扩展名的删除 / 新增配对为媒体转换
+  const addedByStem = new Map<string, string>();
+  for (const path of added.keys()) {
+    if (!isChartFile(path)) {
+      const dir = path.slice(0, path.lastIndexOf('/'));
+      addedByStem.set(`${dir}/${getFileStem(path.slice(dir.length + 1))}`, path);
+    }
+  }
+  for (const [path, info] of removed) {
+    const dir = path.slice(0, path.lastIndexOf('/'));
+    const target = addedByStem.get(`${dir}/${getFileStem(path.slice(dir.length + 1))}`);
+    if (target && added.has(target) && getFileExtension(target) !== getFileExtension(path)) {
+      changeset.mediaConverted.push({
+        from: path,
+        to: target,
+        sizeBefore: info.size,
+        sizeAfter: added.get(target)!.size,
+      });
+      removed.delete(path);
+      added.delete(target);
+    }
+  }
+
+  for (const [path, info] of added) {
+    if (isChartFile(path)) {
+      changeset.chartsAdded.push(path);
+    } else {
+      changeset.filesAdded.push({ path, size: info.size });
+    }
+  }
+  changeset.filesRemoved = [...removed].map(([path, info]) => ({ path, size: info.size }));
+
+  return changeset;
+}
+
+/**
+ * 流水线变更记录器
+ *
+ * @example
+ * ```typescript
+ * const recorder = await PipelineRecorder.start('pack_raw_to_hq', [rootDir], dryRun);
+ * // ... 执行流水线 ...
+ * return await recorder.finish();
+ * ```
+ */
+export class PipelineRecorder {
+  private conversionFailures = 0;
+  private conversionSkipped = 0;
+  private conversionErrors: ConversionFailure[] = [];
+
+  private constructor(
+    private readonly pipeline: string,
+    private readonly rootDirs: string[],
+    private readonly dryRun: boolean,
+    private readonly startedAt: Date,
+    private readonly snapshots: RootSnapshot[]
+  ) {}
+
+  /**
+   * 开始记录（模拟运行时不做快照）
+   */
+  static async start(
+    pipeline: string,
+    rootDirs: string[],
+    dryRun: boolean
+  ): Promise<PipelineRecorder> {
+    const startedAt = new Date();
+    const snapshots = dryRun ? [] : await Promise.all(rootDirs.map((dir) => snapshotRoot(dir)));
+    return new PipelineRecorder(pipeline, rootDirs, dryRun, startedAt, snapshots);
+  }
+
+  /**
+   * 记录一次批量媒体转换的结果（用于统计失败数和跳过数）
+   */
+  recordConversion(summary: ConversionSummary): void {
+    this.conversionFailures += summary.failed;
+    this.conversionSkipped += summary.skipped;
+    this.conversionErrors.push(...summary.failures);
+  }
+
+  /**
+   * 结束记录，比较快照并保存到运行历史
+   *
+   * @param error - 流水线失败时的错误
+   */
+  async finish(error?: unknown): Promise<PipelineChangeset> {
+    const changeset: PipelineChangeset = {
+      id: `${this.startedAt.toISOString().replace(/[:.]/g, '-')}_${this.pipeline}`,
+      pipeline: this.pipeline,
+      startedAt: this.startedAt.toISOString(),
+      finishedAt: new Date().toISOString(),
+      dryRun: this.dryRun,
+      roots: [],
+      conversionFailures: this.conversionFailures,
+      conversionSkipped: this.conversionSkipped,
+      conversionErrors: this.conversionErrors,
+    };
+    if (error !== undefined) {
+      changeset.error = error instanceof Error ? error.message : String(error);
+    }
+
+    if (this.dryRun) {
+      return changeset;
+    }
+
+    for (const [index, rootDir] of this.rootDirs.entries()) {
+      const after = await snapshotRoot(rootDir);
+      changeset.roots.push(diffSnapshots(rootDir, this.snapshots[index], after));
+    }
+
+    await writeJsonFile(await getAppDataPath(`history/${changeset.id}.json`), changeset);
+    for (const root of changeset.roots) {
+      console.log(
+        `Changeset ${root.rootDir}: +${root.worksAdded.length} -${root.worksRemoved.length} ` +
+          `~${root.worksRenamed.length} works, ${root.chartsUpdated.length} charts updated, ` +
+          `${root.mediaConverted.length} media converted, ${root.filesRemoved.length} files removed`
+      );
+    }
+
+    const count = (pick: (root: RootChangeset) => unknown[]): number =>
+      changeset.roots.reduce((sum, root) => sum + pick(root).length, 0);
+    await notifyCompletion(
+      `${this.pipeline} ${changeset.error ? '失败' : '完成'}`,
+      changeset.error ??
+        `新增 ${count((root) => root.worksAdded)} 个作品，` +
+          `转换 ${count((root) => root.mediaConverted)} 个媒体文件，` +
+          `${this.conversionFailures} 个作品转换失败，` +
+          `${this.conversionSkipped} 个作品已是目标格式而跳过`
+    );
+    return changeset;
+  }
+}
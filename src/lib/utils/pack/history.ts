@@ -0,0 +1,127 @@
+/**
+ * 流水线运行历史
+ *
+ * 查看流水线每次运行保存的变更记录
+ */
+
+import { readDir } from '@tauri-apps/plugin-fs';
+import { getAppDataSubDir, readJsonFile } from '../storage';
+import type { PipelineChangeset } from './changeset';
+
+/**
+ * 运行历史摘要
+ */
+export interface PipelineRunSummary {
+  id: string;
+  pipeline: string;
+  startedAt: string;
+  finishedAt: string;
+  error?: string;
+  worksAdded: number;
+  worksRemoved: number;
+  worksRenamed: number;
+  chartsUpdated: number;
+  mediaConverted: number;
+  filesRemoved: number;
+  /** 删除的文件总大小（字节，含删除的作品） */
+  removedSize: number;
+  /** 运行前后根目录大小的变化（字节） */
+  sizeDelta: number;
+}
+
+/**
+ * 读取运行记录，不存在时返回 null
+ */
+export async function loadPipelineChangeset(id: string): Promise<PipelineChangeset | null> {
+  const historyDir = await getAppDataSubDir('history');
+  return await readJsonFile<PipelineChangeset | null>(`${historyDir}/${id}.json`, null);
+}
+
+function summarizeChangeset(changeset: PipelineChangeset): PipelineRunSummary {
+  const sum = (values: number[]) => values.reduce((total, value) => total + value, 0);
+  const roots = changeset.roots;
+
+  return {
+    id: changeset.id,
+    pipeline: changeset.pipeline,
+    startedAt: changeset.startedAt,
+    finishedAt: changeset.finishedAt,
+    error: changeset.error,
+    worksAdded: sum(roots.map((root) => root.worksAdded.length)),
+    worksRemoved: sum(roots.map((root) => root.worksRemoved.length)),
+    worksRenamed: sum(roots.map((root) => root.worksRenamed.length)),
+    chartsUpdated: sum(roots.map((root) => root.chartsUpdated.length)),
+    mediaConverted: sum(roots.map((root) => root.mediaConverted.length)),
+    filesRemoved: sum(roots.map((root) => root.filesRemoved.length)),
+    removedSize: sum(
+      roots.flatMap((root) => [
+        ...root.filesRemoved.map((file) => file.size),
+        ...root.worksRemoved.map((work) => work.size),
+      ])
+    ),
+    sizeDelta: sum(roots.map((root) => root.sizeAfter - root.sizeBefore)),
+  };
+}
+
+/**
+ * 列出流水线运行历史
+ *
+ * @command
+ * @category pack
+ * @dangerous false
+ * @name 查看运行历史
+ * @description 按时间倒序列出流水线的运行记录摘要（新增、删除、重命名的作品和删除的文件大小）
+ * @frontend true
+ *
+ * @param {number} limit - 最多列出的记录数
+ *
+ * @returns {Promise<PipelineRunSummary[]>} 运行记录摘要
+ */
+export async function listHistory(limit: number = 20): Promise<PipelineRunSummary[]> {
+  const historyDir = await getAppDataSubDir('history');
+  const ids = (await readDir(historyDir))
+    .filter((entry) => !entry.isDirectory && entry.name?.endsWith('.json'))
+    .map((entry) => entry.name.slice(0, -'.json'.length))
+    .sort()
+    .reverse()
+    .slice(0, Math.max(1, limit));
+
+  const summaries: PipelineRunSummary[] = [];
+  for (const id of ids) {
+    const changeset = await loadPipelineChangeset(id);
+    if (changeset) {
+      summaries.push(summarizeChangeset(changeset));
+    }
+  }
+
+  for (const summary of summaries) {
+    console.log(
+      `${summary.id}: +${summary.worksAdded} -${summary.worksRemoved} ~${summary.worksRenamed} ` +
+        `works, ${summary.mediaConverted} media converted, ${summary.filesRemoved} files removed` +
+        `${summary.error ? ` (failed: ${summary.error})` : ''}`
+    );
+  }
+  return summaries;
+}
+
+/**
+ * 查看一次运行的完整变更记录
+ *
+ * @command
+ * @category pack
+ * @dangerous false
+ * @name 查看运行记录
+ * @description 查看一次流水线运行的完整变更记录
+ * @frontend true
+ *
+ * @param {string} id - 运行 ID（见运行历史）
+ *
+ * @returns {Promise<PipelineChangeset | null>} 变更记录，不存在时返回 null
+ */
+export async function showHistory(id: string): Promise<PipelineChangeset | null> {
+  const changeset = await loadPipelineChangeset(id);
+  if (!changeset) {
+    console.warn(`Run not found: ${id}`);
+  }
+  return changeset;
+}
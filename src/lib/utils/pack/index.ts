@@ -4,3 +4,14 @@
  */
 
 export { setupRawpackToHq, updateRawpackToHq, packHqToLq, packRawToHq } from './pack';
+export { PipelineRecorder } from './changeset';
+export type {
+  FileChange,
+  MediaConversion,
+  PipelineChangeset,
+  RootChangeset,
+  WorkChange,
+  WorkRename,
+} from './changeset';
+export { listHistory, loadPipelineChangeset, showHistory } from './history';
+export type { PipelineRunSummary } from './history';
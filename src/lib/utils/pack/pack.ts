@@ -3,19 +3,47 @@
  * Raw -> HQ -> LQ
  */
 
-import { mkdir, readDir, remove } from '@tauri-apps/plugin-fs';
+import { exists, mkdir, readDir, remove } from '@tauri-apps/plugin-fs';
 import { AudioConverter } from '../media/audio';
 import { MediaCleaner } from '../media/cleanup';
 import { VideoConverter } from '../media/video';
-import { RemoveMediaPreset } from '../../types/enums';
 import { BmsFolderSetNameType, setNameByBms } from '../work/rename';
 import { ReplacePreset } from '../fs/moving';
 import { copyNumberedWorkdirNames } from '../root/batch';
 import { presetForAppend, syncFolder } from '../fs/sync';
+import { linkOrCopyDir } from '../fs/copy';
 import { removeEmptyFolders } from '../fs/cleanup';
+import { CacheManager } from '../fs/cache';
 import { AudioPreset, VideoPreset } from '../media/types';
+import { matchesQualityProfile, QualityProfile } from '../media/quality';
+import { LibraryIndex } from '../library/workIndex';
 import type { IProgressManager } from '../progress';
 import { unzipNumericToBmsFolder } from '../rawpack/unzip';
+import { Settings } from '../settings';
+import { PipelineRecorder } from './changeset';
+import type { PipelineChangeset } from './changeset';
+
+/**
+ * 更新作品索引，找出音频或视频已经是目标版本格式的作品（转换时跳过）
+ */
+async function findWorksInProfile(
+  rootDir: string,
+  profile: QualityProfile,
+  media: 'audio' | 'video'
+): Promise<Set<string>> {
+  await LibraryIndex.update(rootDir);
+  const works = (await LibraryIndex.get(rootDir))?.works ?? [];
+  const dirNames = new Set(
+    works
+      .filter((work) => work.quality && matchesQualityProfile(work.quality, profile, media))
+      .map((work) => work.dirName)
+  );
+  console.log(
+    `${dirNames.size} of ${works.length} works already have ${profile.toUpperCase()} ${media}, ` +
+      `skipping them`
+  );
+  return dirNames;
+}
 
 /**
  * Pack 生成脚本：Raw pack -> HQ pack
@@ -31,82 +59,99 @@ import { unzipNumericToBmsFolder } from '../rawpack/unzip';
  * @param {string} packDir - 压缩包目录路径
  * @param {string} rootDir - 目标根目录路径
  * @param {boolean} dryRun - 模拟运行（不实际执行）
- * @returns {Promise<void>}
+ * @param {IProgressManager} progressManager - 进度管理器（可选）
+ * @returns {Promise<PipelineChangeset>} 本次运行的变更记录
  */
 export async function setupRawpackToHq(
   packDir: string,
   rootDir: string,
-  dryRun: boolean
-): Promise<void> {
-  // Setup
-  if (!dryRun) {
-    await mkdir(rootDir, { recursive: true });
-  } else {
-    console.log('[dry-run] Would create directory:', rootDir);
-  }
-
-  // 1. 解压包
-  console.log(` > 1. Unzip packs from ${packDir} to ${rootDir}`);
-  const cacheDir = `${rootDir}/CacheDir`;
-  if (!dryRun) {
-    await mkdir(cacheDir, { recursive: true });
-  }
+  dryRun: boolean,
+  progressManager?: IProgressManager
+): Promise<PipelineChangeset> {
+  progressManager?.start();
+  const recorder = await PipelineRecorder.start('setup_rawpack_to_hq', [rootDir], dryRun);
 
-  if (!dryRun) {
-    await unzipNumericToBmsFolder(packDir, cacheDir, rootDir, false, ReplacePreset.UpdatePack);
-  } else {
-    console.log('[dry-run] Would unzip packs from', packDir, 'to', rootDir);
-  }
+  try {
+    // Setup
+    if (!dryRun) {
+      await mkdir(rootDir, { recursive: true });
+    } else {
+      console.log('[dry-run] Would create directory:', rootDir);
+    }
 
-  // 检查缓存目录是否为空，删除如果为空
-  if (!dryRun) {
-    const cacheEntries = await readDir(cacheDir);
-    if (cacheEntries.length === 0) {
-      await remove(cacheDir, { recursive: true });
+    // 1. 解压包（缓存目录位于根目录所在的卷上，结束后自动释放）
+    console.log(` > 1. Unzip packs from ${packDir} to ${rootDir}`);
+    if (!dryRun) {
+      await CacheManager.withEntry(
+        'rawpack',
+        (cacheDir) =>
+          unzipNumericToBmsFolder(packDir, cacheDir, rootDir, false, ReplacePreset.UpdatePack),
+        rootDir
+      );
+    } else {
+      console.log('[dry-run] Would unzip packs from', packDir, 'to', rootDir);
     }
-  }
 
-  // 2. 同步文件夹名称
-  console.log(` > 2. Setting dir names from BMS Files`);
-  if (!dryRun) {
-    const entries = await readDir(rootDir);
-    for (const entry of entries) {
-      if (entry.isDirectory && entry.name) {
-        const path = `${rootDir}/${entry.name}`;
-        await setNameByBms(
-          path,
-          BmsFolderSetNameType.AppendTitleArtist,
-          false,
-          ReplacePreset.UpdatePack,
-          true
-        );
+    progressManager?.throwIfCancelled();
+
+    // 2. 同步文件夹名称
+    console.log(` > 2. Setting dir names from BMS Files`);
+    if (!dryRun) {
+      const entries = await readDir(rootDir);
+      for (const entry of entries) {
+        if (entry.isDirectory && entry.name) {
+          const path = `${rootDir}/${entry.name}`;
+          await setNameByBms(
+            path,
+            BmsFolderSetNameType.AppendTitleArtist,
+            false,
+            ReplacePreset.UpdatePack,
+            true
+          );
+        }
       }
+    } else {
+      console.log('[dry-run] Would set dir names from BMS files in', rootDir);
     }
-  } else {
-    console.log('[dry-run] Would set dir names from BMS files in', rootDir);
-  }
 
-  // 3. 音频转换 (WAV -> FLAC)
-  console.log(` > 3. Media processing (WAV -> FLAC)`);
-  if (dryRun) {
-    console.log('[dry-run] Would convert WAV to FLAC in', rootDir);
-  } else {
-    await AudioConverter.processBmsFolders({
-      rootDir,
-      inputExtensions: ['wav'],
-      presetNames: [AudioPreset.FLAC, AudioPreset.FLAC_FFMPEG],
-      removeOnSuccess: true,
-      removeOnFail: true,
-      skipOnFail: false,
-    });
-  }
+    progressManager?.throwIfCancelled();
 
-  // 4. 清理冗余媒体文件
-  console.log(` > 4. Clean up redundant media files`);
-  if (dryRun) {
-    console.log('[dry-run] Would clean up redundant media files in', rootDir);
-  } else {
-    await MediaCleaner.removeUnneedMediaFiles(rootDir, RemoveMediaPreset.Oraja);
+    // 3. 音频转换 (WAV -> FLAC)
+    console.log(` > 3. Media processing (WAV -> FLAC)`);
+    if (dryRun) {
+      console.log('[dry-run] Would convert WAV to FLAC in', rootDir);
+    } else {
+      recorder.recordConversion(
+        await AudioConverter.processBmsFolders({
+          rootDir,
+          inputExtensions: ['wav'],
+          presetNames: [AudioPreset.FLAC, AudioPreset.FLAC_FFMPEG],
+          removeOnSuccess: true,
+          keepOriginals: false,
+          removeOnFail: true,
+          skipOnFail: false,
+          preserveMetadata: true,
+          skipDirs: await findWorksInProfile(rootDir, QualityProfile.HQ, 'audio'),
+          progressManager,
+        })
+      );
+    }
+
+    progressManager?.throwIfCancelled();
+
+    // 4. 清理冗余媒体文件
+    console.log(` > 4. Clean up redundant media files`);
+    if (dryRun) {
+      console.log('[dry-run] Would clean up redundant media files in', rootDir);
+    } else {
+      await MediaCleaner.removeUnneedMediaFiles(rootDir, Settings.get().removeMediaPreset);
+    }
+
+    return await recorder.finish();
+  } catch (error) {
+    progressManager?.reportError(error instanceof Error ? error.message : String(error));
+    await recorder.finish(error);
+    throw error;
   }
 }
 
@@ -125,79 +170,108 @@ export async function setupRawpackToHq(
  * @param {string} rootDir - 增量根目录路径
  * @param {string} syncDir - 已存在的BMS文件夹路径（用于名称同步和文件检查）
  * @param {boolean} dryRun - 模拟运行（不实际执行）
- * @returns {Promise<void>}
+ * @param {IProgressManager} progressManager - 进度管理器（可选）
+ * @returns {Promise<PipelineChangeset>} 本次运行的变更记录
  */
 export async function updateRawpackToHq(
   packDir: string,
   rootDir: string,
   syncDir: string,
-  dryRun: boolean
-): Promise<void> {
-  // Setup
-  if (!dryRun) {
-    await mkdir(rootDir, { recursive: true });
-  } else {
-    console.log('[dry-run] Would create directory:', rootDir);
-  }
+  dryRun: boolean,
+  progressManager?: IProgressManager
+): Promise<PipelineChangeset> {
+  progressManager?.start();
+  const recorder = await PipelineRecorder.start('update_rawpack_to_hq', [rootDir, syncDir], dryRun);
 
-  // 1. 解压包
-  console.log(` > 1. Unzip packs from ${packDir} to ${rootDir}`);
-  const cacheDir = `${rootDir}/CacheDir`;
-  if (!dryRun) {
-    await mkdir(cacheDir, { recursive: true });
-  }
+  try {
+    // Setup
+    if (!dryRun) {
+      await mkdir(rootDir, { recursive: true });
+    } else {
+      console.log('[dry-run] Would create directory:', rootDir);
+    }
 
-  if (!dryRun) {
-    await unzipNumericToBmsFolder(packDir, cacheDir, rootDir, false, ReplacePreset.UpdatePack);
-  } else {
-    console.log('[dry-run] Would unzip packs from', packDir, 'to', rootDir);
-  }
+    // 1. 解压包（缓存目录位于根目录所在的卷上，结束后自动释放）
+    console.log(` > 1. Unzip packs from ${packDir} to ${rootDir}`);
+    if (!dryRun) {
+      await CacheManager.withEntry(
+        'rawpack',
+        (cacheDir) =>
+          unzipNumericToBmsFolder(packDir, cacheDir, rootDir, false, ReplacePreset.UpdatePack),
+        rootDir
+      );
+    } else {
+      console.log('[dry-run] Would unzip packs from', packDir, 'to', rootDir);
+    }
 
-  // 2. 同步文件夹名称
-  console.log(` > 2. Syncing dir name from ${syncDir} to ${rootDir}`);
-  if (!dryRun) {
-    await copyNumberedWorkdirNames(syncDir, rootDir, false);
-  } else {
-    console.log('[dry-run] Would copy numbered directory names from', syncDir, 'to', rootDir);
-  }
+    progressManager?.throwIfCancelled();
 
-  // 3. 音频转换 (WAV -> FLAC)
-  console.log(` > 3. Media processing (WAV -> FLAC)`);
-  if (dryRun) {
-    console.log('[dry-run] Would convert WAV to FLAC in', rootDir);
-  } else {
-    await AudioConverter.processBmsFolders({
-      rootDir,
-      inputExtensions: ['wav'],
-      presetNames: [AudioPreset.FLAC, AudioPreset.FLAC_FFMPEG],
-      removeOnSuccess: true,
-      removeOnFail: true,
-      skipOnFail: false,
-    });
-  }
+    // 2. 同步文件夹名称
+    console.log(` > 2. Syncing dir name from ${syncDir} to ${rootDir}`);
+    if (!dryRun) {
+      await copyNumberedWorkdirNames(syncDir, rootDir, false);
+    } else {
+      console.log('[dry-run] Would copy numbered directory names from', syncDir, 'to', rootDir);
+    }
 
-  // 4. 清理冗余媒体文件
-  console.log(` > 4. Clean up redundant media files`);
-  if (dryRun) {
-    console.log('[dry-run] Would clean up redundant media files in', rootDir);
-  } else {
-    await MediaCleaner.removeUnneedMediaFiles(rootDir, RemoveMediaPreset.Oraja);
-  }
+    progressManager?.throwIfCancelled();
 
-  // 5. 软同步
-  console.log(` > 5. Syncing dir files from ${rootDir} to ${syncDir}`);
-  if (!dryRun) {
-    await syncFolder(rootDir, syncDir, presetForAppend());
-  } else {
-    console.log('[dry-run] Would sync files from', rootDir, 'to', syncDir);
-  }
+    // 3. 音频转换 (WAV -> FLAC)
+    console.log(` > 3. Media processing (WAV -> FLAC)`);
+    if (dryRun) {
+      console.log('[dry-run] Would convert WAV to FLAC in', rootDir);
+    } else {
+      recorder.recordConversion(
+        await AudioConverter.processBmsFolders({
+          rootDir,
+          inputExtensions: ['wav'],
+          presetNames: [AudioPreset.FLAC, AudioPreset.FLAC_FFMPEG],
+          removeOnSuccess: true,
+          keepOriginals: false,
+          removeOnFail: true,
+          skipOnFail: false,
+          preserveMetadata: true,
+          skipDirs: await findWorksInProfile(rootDir, QualityProfile.HQ, 'audio'),
+          progressManager,
+        })
+      );
+    }
+
+    progressManager?.throwIfCancelled();
+
+    // 4. 清理冗余媒体文件
+    console.log(` > 4. Clean up redundant media files`);
+    if (dryRun) {
+      console.log('[dry-run] Would clean up redundant media files in', rootDir);
+    } else {
+      await MediaCleaner.removeUnneedMediaFiles(rootDir, Settings.get().removeMediaPreset);
+    }
+
+    progressManager?.throwIfCancelled();
 
-  // 6. 删除空文件夹
-  console.log(` > 6. Remove empty folder in ${rootDir}`);
-  if (!dryRun) {
-    await removeEmptyFolders(rootDir, false);
-  } else {
-    console.log('[dry-run] Would remove empty folders in', rootDir);
+    // 5. 软同步
+    console.log(` > 5. Syncing dir files from ${rootDir} to ${syncDir}`);
+    if (!dryRun) {
+      await syncFolder(rootDir, syncDir, presetForAppend());
+    } else {
+      console.log('[dry-run] Would sync files from', rootDir, 'to', syncDir);
+    }
+
+    progressManager?.throwIfCancelled();
+
+    // 6. 删除空文件夹
+    console.log(` > 6. Remove empty folder in ${rootDir}`);
+    if (!dryRun) {
+      await removeEmptyFolders(rootDir, false);
+    } else {
+      console.log('[dry-run] Would remove empty folders in', rootDir);
+    }
+
+    return await recorder.finish();
+  } catch (error) {
+    progressManager?.reportError(error instanceof Error ? error.message : String(error));
+    await recorder.finish(error);
+    throw error;
   }
 }
 
@@ -214,55 +288,70 @@ export async function updateRawpackToHq(
  *
  * @param {string} rootDir - 根目录路径
  * @param {boolean} dryRun - 模拟运行（不实际执行）
- * @returns {Promise<void>}
+ * @param {IProgressManager} progressManager - 进度管理器（可选）
+ * @returns {Promise<PipelineChangeset>} 本次运行的变更记录
  */
 export async function packHqToLq(
   rootDir: string,
   dryRun: boolean,
   progressManager?: IProgressManager
-): Promise<void> {
+): Promise<PipelineChangeset> {
   // 启动进度管理器
   progressManager?.start();
+  const recorder = await PipelineRecorder.start('pack_hq_to_lq', [rootDir], dryRun);
 
   try {
     // 1. 音频转换 (FLAC -> OGG)
     console.log(' > 1. Audio conversion: FLAC -> OGG');
     progressManager?.setMessage('音频转换: FLAC -> OGG');
 
-    await AudioConverter.processBmsFolders({
-      rootDir,
-      inputExtensions: ['flac'],
-      presetNames: [AudioPreset.OGG_Q10],
-      removeOnSuccess: true,
-      removeOnFail: false,
-      skipOnFail: false,
-      progressManager,
-    });
+    recorder.recordConversion(
+      await AudioConverter.processBmsFolders({
+        rootDir,
+        inputExtensions: ['flac'],
+        presetNames: [AudioPreset.OGG_Q10],
+        removeOnSuccess: true,
+        keepOriginals: false,
+        removeOnFail: false,
+        skipOnFail: false,
+        preserveMetadata: true,
+        skipDirs: await findWorksInProfile(rootDir, QualityProfile.LQ, 'audio'),
+        progressManager,
+      })
+    );
+
+    progressManager?.throwIfCancelled();
 
     // 2. 视频转换 (MP4 -> MPEG)
     console.log(' > 2. Video conversion: MP4 -> MPEG');
     progressManager?.setMessage('视频转换: MP4 -> MPEG');
 
-    await VideoConverter.processBmsFolders({
-      rootDir,
-      inputExtensions: ['mp4'],
-      presetNames: [
-        VideoPreset.MPEG1VIDEO_512X512,
-        VideoPreset.WMV2_512X512,
-        VideoPreset.AVI_512X512,
-      ],
-      removeOriginal: true,
-      removeExisting: true,
-      usePreferred: false,
-      progressManager,
-    });
+    recorder.recordConversion(
+      await VideoConverter.processBmsFolders({
+        rootDir,
+        inputExtensions: ['mp4'],
+        presetNames: [
+          VideoPreset.MPEG1VIDEO_512X512,
+          VideoPreset.WMV2_512X512,
+          VideoPreset.AVI_512X512,
+        ],
+        removeOriginal: true,
+        keepOriginals: false,
+        removeExisting: true,
+        usePreferred: false,
+        skipDirs: await findWorksInProfile(rootDir, QualityProfile.LQ, 'video'),
+        progressManager,
+      })
+    );
+
+    progressManager?.throwIfCancelled();
 
     // 3. 清理冗余媒体文件
     console.log(' > 3. Cleaning up redundant media files');
     progressManager?.setMessage('清理冗余媒体文件');
 
     if (!dryRun) {
-      await MediaCleaner.removeUnneedMediaFiles(rootDir, RemoveMediaPreset.Oraja);
+      await MediaCleaner.removeUnneedMediaFiles(rootDir, Settings.get().removeMediaPreset);
     } else {
       console.log('[dry-run] Would clean up redundant media files');
     }
@@ -270,8 +359,10 @@ export async function packHqToLq(
     // 完成
     progressManager?.update(100, 100, 'HQ -> LQ 转换完成');
     console.log('HQ -> LQ conversion completed successfully');
+    return await recorder.finish();
   } catch (error) {
     progressManager?.reportError(error instanceof Error ? error.message : String(error));
+    await recorder.finish(error);
     throw error;
   }
 }
@@ -291,14 +382,15 @@ export async function packHqToLq(
  * @param {string} rootDir - 根目录路径
  * @param {boolean} dryRun - 模拟运行（不实际执行）
  * @param {IProgressManager} progressManager - 进度管理器（可选）
- * @returns {Promise<void>}
+ * @returns {Promise<PipelineChangeset>} 本次运行的变更记录
  */
 export async function packRawToHq(
   rootDir: string,
   dryRun: boolean,
   progressManager?: IProgressManager
-): Promise<void> {
+): Promise<PipelineChangeset> {
   progressManager?.start();
+  const recorder = await PipelineRecorder.start('pack_raw_to_hq', [rootDir], dryRun);
 
   try {
     // 1. 音频转换 (WAV -> FLAC)
@@ -308,17 +400,24 @@ export async function packRawToHq(
     if (dryRun) {
       console.log('[dry-run] Would convert WAV to FLAC in', rootDir);
     } else {
-      await AudioConverter.processBmsFolders({
-        rootDir,
-        inputExtensions: ['wav'],
-        presetNames: [AudioPreset.FLAC, AudioPreset.FLAC_FFMPEG],
-        removeOnSuccess: true,
-        removeOnFail: true,
-        skipOnFail: false,
-        progressManager,
-      });
+      recorder.recordConversion(
+        await AudioConverter.processBmsFolders({
+          rootDir,
+          inputExtensions: ['wav'],
+          presetNames: [AudioPreset.FLAC, AudioPreset.FLAC_FFMPEG],
+          removeOnSuccess: true,
+          keepOriginals: false,
+          removeOnFail: true,
+          skipOnFail: false,
+          preserveMetadata: true,
+          skipDirs: await findWorksInProfile(rootDir, QualityProfile.HQ, 'audio'),
+          progressManager,
+        })
+      );
     }
 
+    progressManager?.throwIfCancelled();
+
     // 2. 清理冗余媒体文件
     console.log(' > 2. Clean up redundant media files');
     progressManager?.setMessage('清理冗余媒体文件');
@@ -326,13 +425,80 @@ export async function packRawToHq(
     if (dryRun) {
       console.log('[dry-run] Would clean up redundant media files in', rootDir);
     } else {
-      await MediaCleaner.removeUnneedMediaFiles(rootDir, RemoveMediaPreset.Oraja);
+      await MediaCleaner.removeUnneedMediaFiles(rootDir, Settings.get().removeMediaPreset);
     }
 
     progressManager?.update(100, 100, 'Raw -> HQ 转换完成');
     console.log('Raw -> HQ conversion completed successfully');
+    return await recorder.finish();
   } catch (error) {
     progressManager?.reportError(error instanceof Error ? error.message : String(error));
+    await recorder.finish(error);
     throw error;
   }
 }
+
+/**
+ * HQ / LQ 双版本生成结果
+ */
+export interface DualBuildResult {
+  hq: PipelineChangeset;
+  /** 模拟运行时为空 */
+  lq: PipelineChangeset | null;
+  /** LQ 目录的生成方式 */
+  lqSource: 'hardlink' | 'copy' | null;
+}
+
+/**
+ * 大包双版本生成：原包 -> HQ 版大包 + LQ 版大包
+ *
+ * @command
+ * @category pack
+ * @dangerous true
+ * @name 大包双版本生成：原包 -> HQ + LQ
+ * @description 从已编号的原始包生成 HQ 版大包，再把 HQ 版复制（同一磁盘上使用硬链接）到另一个目录转换为 LQ 版，HQ 版保持不变
+ * @frontend true
+ *
+ * @param {string} packDir - 压缩包目录路径
+ * @param {string} hqDir - HQ 版输出目录
+ * @param {string} lqDir - LQ 版输出目录（不能已存在内容）
+ * @param {boolean} dryRun - 模拟运行（不实际执行）
+ * @param {IProgressManager} progressManager - 进度管理器（可选）
+ * @returns {Promise<DualBuildResult>} 两个版本的变更记录
+ */
+export async function packDualBuild(
+  packDir: string,
+  hqDir: string,
+  lqDir: string,
+  dryRun: boolean,
+  progressManager?: IProgressManager
+): Promise<DualBuildResult> {
+  if (hqDir === lqDir) {
+    throw new Error('HQ 和 LQ 输出目录不能相同');
+  }
+  if ((await exists(lqDir)) && (await readDir(lqDir)).length > 0) {
+    throw new Error(`LQ 输出目录已有内容：${lqDir}`);
+  }
+
+  console.log(` > Building HQ pack in ${hqDir}`);
+  const hq = await setupRawpackToHq(packDir, hqDir, dryRun, progressManager);
+  progressManager?.throwIfCancelled();
+
+  if (dryRun) {
+    console.log(`[dry-run] Would copy ${hqDir} to ${lqDir} and convert it to LQ`);
+    return { hq, lq: null, lqSource: null };
+  }
+
+  // LQ 转换只会写入新文件并删除原文件，不会原地改写，可以与 HQ 版共用硬链接
+  console.log(` > Copying ${hqDir} -> ${lqDir}`);
+  progressManager?.setMessage('复制 HQ 版到 LQ 目录');
+  if (await exists(lqDir)) {
+    await remove(lqDir, { recursive: true });
+  }
+  const lqSource = await linkOrCopyDir(hqDir, lqDir, progressManager);
+  console.log(`LQ directory created by ${lqSource}`);
+
+  console.log(` > Converting ${lqDir} to LQ`);
+  const lq = await packHqToLq(lqDir, dryRun, progressManager);
+  return { hq, lq, lqSource };
+}
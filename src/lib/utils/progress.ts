@@ -3,6 +3,18 @@
  * 支持暂停/恢复/取消，保持中间状态
  */
 
+import { formatSize } from './fs/sizeTally';
+
+/**
+ * 处理速度（最近一段时间的移动平均）
+ */
+export interface ProgressRate {
+  /** 每秒处理的文件数 */
+  filesPerSecond: number;
+  /** 每秒处理的字节数 */
+  bytesPerSecond: number;
+}
+
 /**
  * 进度事件
  */
@@ -23,6 +35,10 @@ export interface ProgressEvent {
   completed: boolean;
   /** 错误信息（如果有） */
   error?: string;
+  /** 处理速度（没有通过 addProcessed 记录处理量，或数据不足时为空） */
+  rate?: ProgressRate;
+  /** 预计剩余时间（秒，数据不足时为空） */
+  etaSeconds?: number;
 }
 
 /**
@@ -55,6 +71,9 @@ export interface IProgressManager {
   /** 设置消息 */
   setMessage(message: string): void;
 
+  /** 记录处理完的文件数和数据量（用于计算处理速度，随下一次进度更新发送） */
+  addProcessed(files: number, bytes: number): void;
+
   /** 报告错误 */
   reportError(error: string): void;
 
@@ -69,6 +88,70 @@ export interface IProgressManager {
 
   /** 等待恢复（如果暂停中） */
   waitForResume(): Promise<void>;
+
+  /** 取消时触发的信号（用于终止正在运行的外部进程） */
+  readonly signal: AbortSignal;
+
+  /** 已取消时抛出 JobCancelledError（用于多步骤流水线在步骤之间中止） */
+  throwIfCancelled(): void;
+}
+
+/**
+ * 任务已被取消
+ */
+export class JobCancelledError extends Error {
+  constructor() {
+    super('任务已取消');
+    this.name = 'JobCancelledError';
+  }
+}
+
+/**
+ * 计算处理速度的时间窗口（毫秒）
+ */
+const RATE_WINDOW_MS = 60_000;
+
+/**
+ * 数据不足这段时间时不计算速度和剩余时间（毫秒）
+ */
+const MIN_RATE_ELAPSED_MS = 2_000;
+
+/**
+ * 进度采样
+ */
+interface ProgressSample {
+  time: number;
+  current: number;
+  files: number;
+  bytes: number;
+}
+
+/**
+ * 格式化剩余时间
+ */
+export function formatEta(seconds: number): string {
+  const total = Math.ceil(seconds);
+  const hours = Math.floor(total / 3600);
+  const minutes = Math.floor((total % 3600) / 60);
+  if (hours > 0) {
+    return `${hours} 小时 ${minutes} 分`;
+  }
+  return minutes > 0 ? `${minutes} 分 ${total % 60} 秒` : `${total} 秒`;
+}
+
+/**
+ * 格式化处理速度和剩余时间（都没有时为空字符串）
+ */
+export function formatProgressRate(event: ProgressEvent): string {
+  const parts: string[] = [];
+  if (event.rate) {
+    parts.push(`${event.rate.filesPerSecond.toFixed(1)} 文件/秒`);
+    parts.push(`${formatSize(event.rate.bytesPerSecond)}/秒`);
+  }
+  if (event.etaSeconds !== undefined) {
+    parts.push(`剩余 ${formatEta(event.etaSeconds)}`);
+  }
+  return parts.join('，');
 }
 
 /**
@@ -84,11 +167,31 @@ export class ProgressManager implements IProgressManager {
   private started: boolean = false;
   private error?: string;
   private callbacks: Set<ProgressCallback> = new Set();
+  private abortController = new AbortController();
+  private processedFiles = 0;
+  private processedBytes = 0;
+  /** 时间窗口内的采样（最早的在前） */
+  private samples: ProgressSample[] = [];
+  /** 开始时间（恢复时扣除暂停的时间） */
+  private startTime = 0;
+  private pausedAt = 0;
+
+  /**
+   * 取消时触发的信号
+   */
+  get signal(): AbortSignal {
+    return this.abortController.signal;
+  }
 
   /**
    * 启动任务
+   *
+   * 流水线中的每个步骤都会调用 start，已取消的任务保持取消状态，不会被后续步骤重新启动
    */
   start(): void {
+    if (this.cancelled) {
+      return;
+    }
     this.started = true;
     this.paused = false;
     this.cancelled = false;
@@ -96,6 +199,11 @@ export class ProgressManager implements IProgressManager {
     this.current = 0;
     this.error = undefined;
     this.message = '开始处理...';
+    this.processedFiles = 0;
+    this.processedBytes = 0;
+    this.startTime = Date.now();
+    this.samples = [];
+    this.sample();
     this.notify();
   }
 
@@ -106,6 +214,9 @@ export class ProgressManager implements IProgressManager {
     if (!this.started || this.completed || this.cancelled) {
       return;
     }
+    if (!this.paused) {
+      this.pausedAt = Date.now();
+    }
     this.paused = true;
     this.message = '已暂停';
     this.notify();
@@ -118,6 +229,12 @@ export class ProgressManager implements IProgressManager {
     if (!this.started || this.completed || this.cancelled) {
       return;
     }
+    // 暂停期间不计入速度
+    if (this.paused) {
+      this.startTime += Date.now() - this.pausedAt;
+      this.samples = [];
+      this.sample();
+    }
     this.paused = false;
     this.message = '继续处理...';
     this.notify();
@@ -127,12 +244,14 @@ export class ProgressManager implements IProgressManager {
    * 取消任务
    */
   cancel(): void {
-    if (!this.started || this.completed) {
+    // 多步骤任务中某一步完成后仍可取消后续步骤；启动前取消的任务不会再启动
+    if (this.cancelled) {
       return;
     }
     this.cancelled = true;
     this.paused = false;
     this.message = '已取消';
+    this.abortController.abort();
     this.notify();
   }
 
@@ -153,6 +272,7 @@ export class ProgressManager implements IProgressManager {
       this.message = message || '处理完成';
     }
 
+    this.sample();
     this.notify();
   }
 
@@ -171,6 +291,17 @@ export class ProgressManager implements IProgressManager {
     this.notify();
   }
 
+  /**
+   * 记录处理完的文件数和数据量
+   *
+   * 不单独通知订阅者，速度随下一次进度更新发送（大量小文件时避免频繁通知）
+   */
+  addProcessed(files: number, bytes: number): void {
+    this.processedFiles += files;
+    this.processedBytes += bytes;
+    this.sample();
+  }
+
   /**
    * 报告错误
    */
@@ -195,9 +326,61 @@ export class ProgressManager implements IProgressManager {
       cancelled: this.cancelled,
       completed: this.completed,
       error: this.error,
+      ...this.getRate(),
     };
   }
 
+  /**
+   * 记录一次采样，丢弃时间窗口以外的采样（保留窗口前的最后一个作为起点）
+   */
+  private sample(): void {
+    const time = Date.now();
+    this.samples.push({
+      time,
+      current: this.current,
+      files: this.processedFiles,
+      bytes: this.processedBytes,
+    });
+    while (this.samples.length > 2 && time - this.samples[1].time > RATE_WINDOW_MS) {
+      this.samples.shift();
+    }
+  }
+
+  /**
+   * 按时间窗口内的采样计算处理速度和剩余时间
+   *
+   * 窗口内进度没有变化（如一个目录中有大量文件）时按开始以来的平均速度估算剩余时间
+   */
+  private getRate(): Pick<ProgressEvent, 'rate' | 'etaSeconds'> {
+    const oldest = this.samples[0];
+    if (!this.started || this.paused || this.completed || this.cancelled || !oldest) {
+      return {};
+    }
+    const now = Date.now();
+    if (now - oldest.time < MIN_RATE_ELAPSED_MS) {
+      return {};
+    }
+    const elapsed = (now - oldest.time) / 1000;
+
+    const result: Pick<ProgressEvent, 'rate' | 'etaSeconds'> = {};
+    if (this.processedFiles > 0) {
+      result.rate = {
+        filesPerSecond: (this.processedFiles - oldest.files) / elapsed,
+        bytesPerSecond: (this.processedBytes - oldest.bytes) / elapsed,
+      };
+    }
+
+    const remaining = this.total - this.current;
+    let itemsPerSecond = (this.current - oldest.current) / elapsed;
+    if (itemsPerSecond <= 0) {
+      itemsPerSecond = this.current / ((now - this.startTime) / 1000);
+    }
+    if (remaining > 0 && itemsPerSecond > 0) {
+      result.etaSeconds = remaining / itemsPerSecond;
+    }
+    return result;
+  }
+
   /**
    * 订阅进度更新
    * 返回取消订阅的函数
@@ -231,6 +414,15 @@ export class ProgressManager implements IProgressManager {
     }
   }
 
+  /**
+   * 已取消时抛出 JobCancelledError
+   */
+  throwIfCancelled(): void {
+    if (this.cancelled) {
+      throw new JobCancelledError();
+    }
+  }
+
   /**
    * 通知所有订阅者
    */
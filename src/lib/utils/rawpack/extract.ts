@@ -0,0 +1,276 @@
+/**
+ * 解压结果整理工具
+ * 展平缓存目录中的嵌套结构，并检查解压结果是否可以直接导入根目录
+ */
+
+import { exists, mkdir, readDir, remove, rename } from '@tauri-apps/plugin-fs';
+import { ArchiveExtractor } from '../fs/archive';
+import { cleanMacosArtifacts } from '../fs/cleanup';
+import { copyDirRecursive } from '../fs/copy';
+import { IgnoreList } from '../fs/ignore';
+import { moveElementsAcrossDir, replaceOptionsFromPreset, ReplacePreset } from '../fs/moving';
+import { PermissionNormalizer } from '../fs/permissions';
+import { formatSize, getPathSize } from '../fs/sizeTally';
+import { isChartFile, scanDirBms } from '../bms/scanner';
+import type { ImportAudit } from '../root/journal';
+
+/**
+ * 解压结果的问题类型
+ */
+export enum ExtractedDirIssue {
+  /** 解压后存在多个文件夹，无法判断作品目录 */
+  MultipleFolders = 'multiple_folders',
+  /** 解压结果为空 */
+  Empty = 'empty',
+  /** 没有谱面文件 */
+  NoCharts = 'no_charts',
+  /** 所有谱面都无法解析 */
+  ParseErrors = 'parse_errors',
+  /** 压缩包超出解压限制或包含不安全的路径，没有解压 */
+  UnsafeArchive = 'unsafe_archive',
+}
+
+/**
+ * 解压结果检查失败的详情
+ */
+export interface ExtractedDirProblem {
+  issue: ExtractedDirIssue;
+  detail: string;
+}
+
+/**
+ * 在安全限制内解压压缩包
+ *
+ * 解压前列出条目检查路径、条目数和总大小；无法列出时（如中央目录损坏）无法确认路径安全，
+ * 不解压，按不安全的压缩包处理（隔离）。解压后再检查一次总大小，超出时删除解压结果，
+ * 并整理文件权限，避免只读属性导致之后无法移动
+ *
+ * @returns 违反限制时的问题详情
+ */
+export async function extractArchiveChecked(
+  file: string,
+  dest: string
+): Promise<ExtractedDirProblem | null> {
+  const limits = ArchiveExtractor.getLimits();
+  try {
+    const violation = ArchiveExtractor.checkEntries(await ArchiveExtractor.listEntries(file));
+    if (violation) {
+      return { issue: ExtractedDirIssue.UnsafeArchive, detail: violation };
+    }
+  } catch (error) {
+    console.warn(` !_! Failed to list ${file}, not extracting:`, error);
+    const message = error instanceof Error ? error.message : String(error);
+    return {
+      issue: ExtractedDirIssue.UnsafeArchive,
+      detail: `无法列出压缩包条目，无法检查路径和大小：${message}`,
+    };
+  }
+
+  await ArchiveExtractor.extractAuto(file, dest);
+  await PermissionNormalizer.afterExtract(dest);
+
+  const size = await getPathSize(dest);
+  if (size > limits.maxTotalSize) {
+    await remove(dest, { recursive: true });
+    await mkdir(dest, { recursive: true });
+    return {
+      issue: ExtractedDirIssue.UnsafeArchive,
+      detail: `解压后大小 ${formatSize(size)} 超过上限 ${formatSize(limits.maxTotalSize)}`,
+    };
+  }
+  return null;
+}
+
+/**
+ * 移出缓存文件夹中的文件
+ * 展平嵌套的目录结构
+ */
+export async function moveOutFilesInFolderInCacheDir(
+  cacheDirPath: string,
+  replacePreset: ReplacePreset
+): Promise<ExtractedDirProblem | null> {
+  let done = false;
+  let error = false;
+
+  // 在循环外部声明计数器，用于最终检查
+  let totalFolderCount = 0;
+  let totalFileCount = 0;
+
+  // 先递归删除 __MACOSX 和 ._* 等 macOS 附加文件，避免被当作作品内容
+  const artifactCount = await cleanMacosArtifacts(cacheDirPath, false);
+  if (artifactCount > 0) {
+    console.log(` - Removed ${artifactCount} macOS artifacts in ${cacheDirPath}`);
+  }
+
+  while (true) {
+    // 每个循环开始时重置计数器
+    let cacheFolderCount = 0;
+    let cacheFileCount = 0;
+    let innerDirName: string | null = null;
+
+    // 重新扫描目录
+    const entries = await readDir(cacheDirPath);
+
+    for (const entry of entries) {
+      if (!entry.name) {
+        continue;
+      }
+
+      if (entry.isDirectory) {
+        cacheFolderCount++;
+        innerDirName = entry.name;
+      } else {
+        cacheFileCount++;
+      }
+    }
+
+    // 保存计数器的值用于最终检查
+    totalFolderCount = cacheFolderCount;
+    totalFileCount = cacheFileCount;
+
+    if (cacheFolderCount === 0) {
+      done = true;
+    }
+
+    if (cacheFolderCount === 1 && cacheFileCount >= 10) {
+      done = true;
+    }
+
+    if (cacheFolderCount > 1) {
+      console.log(` !_! ${cacheDirPath}: has more than 1 folders, please do it manually.`);
+      error = true;
+    }
+
+    if (done || error) {
+      break;
+    }
+
+    // 移动内部目录的文件到当前层级
+    if (innerDirName) {
+      const innerPath = `${cacheDirPath}/${innerDirName}`;
+      const innerInnerPath = `${innerPath}/${innerDirName}`;
+
+      if (await exists(innerInnerPath)) {
+        console.log(` - Renaming inner inner dir name: ${innerInnerPath}`);
+        await rename(innerInnerPath, `${innerInnerPath}-rep`);
+      }
+
+      console.log(` - Moving inner files in ${innerPath} to ${cacheDirPath}`);
+      await moveElementsAcrossDir(innerPath, cacheDirPath, replaceOptionsFromPreset(replacePreset));
+
+      // 删除内部目录
+      await remove(innerPath, { recursive: true }).catch(() => {});
+    }
+  }
+
+  if (error) {
+    return {
+      issue: ExtractedDirIssue.MultipleFolders,
+      detail: `解压后有 ${totalFolderCount} 个文件夹，无法确定作品目录`,
+    };
+  }
+
+  if (totalFolderCount === 0 && totalFileCount === 0) {
+    console.log(` !_! ${cacheDirPath}: Cache is Empty!`);
+    await remove(cacheDirPath, { recursive: true });
+    return { issue: ExtractedDirIssue.Empty, detail: '解压结果为空' };
+  }
+
+  return null;
+}
+
+/**
+ * 检查展平后的缓存目录中的谱面
+ *
+ * 没有谱面，或所有谱面都无法解析时返回问题详情
+ */
+export async function checkExtractedCharts(
+  cacheDirPath: string
+): Promise<ExtractedDirProblem | null> {
+  const entries = await readDir(cacheDirPath);
+  const chartNames = entries
+    .filter((entry) => !entry.isDirectory && entry.name && isChartFile(entry.name))
+    .map((entry) => entry.name);
+
+  if (chartNames.length === 0) {
+    return { issue: ExtractedDirIssue.NoCharts, detail: '解压结果中没有谱面文件' };
+  }
+
+  const { outputs, outcomes } = await scanDirBms(cacheDirPath);
+  if (outputs.length === 0) {
+    const reasons = outcomes.map(
+      (outcome) => `${outcome.path.split('/').pop()} (${outcome.skippedReason ?? 'unknown'})`
+    );
+    return {
+      issue: ExtractedDirIssue.ParseErrors,
+      detail: `所有谱面都无法解析: ${reasons.join(', ')}`,
+    };
+  }
+
+  return null;
+}
+
+/**
+ * 整理缓存目录：展平嵌套结构并检查谱面
+ */
+export async function prepareExtractedDir(
+  cacheDirPath: string,
+  replacePreset: ReplacePreset
+): Promise<ExtractedDirProblem | null> {
+  const problem = await moveOutFilesInFolderInCacheDir(cacheDirPath, replacePreset);
+  if (problem) {
+    return problem;
+  }
+
+  return await checkExtractedCharts(cacheDirPath);
+}
+
+/**
+ * 将整理好的缓存目录放入根目录
+ *
+ * 缓存目录可能与根目录不在同一个卷上，此时先复制到目标旁边的临时目录，再按原逻辑移动。
+ * 合并到已有目录时，跳过或被占用而没有移动的文件不删除，临时目录改名保留在目标旁边
+ *
+ * @param audit - 导入审计日志，传入时在移动前记录每个文件
+ * @returns 保留未合并文件的目录，全部合并时为 null
+ */
+export async function placeExtractedDir(
+  cacheDirPath: string,
+  targetDir: string,
+  replacePreset: ReplacePreset,
+  audit?: ImportAudit | null
+): Promise<string | null> {
+  await audit?.recordDir('place', cacheDirPath, targetDir);
+
+  const stagingDir = `${targetDir}.__importing__`;
+
+  try {
+    await rename(cacheDirPath, stagingDir);
+  } catch {
+    // 跨卷无法直接重命名，改为复制
+    await copyDirRecursive(cacheDirPath, stagingDir, replaceOptionsFromPreset(replacePreset));
+    await remove(cacheDirPath, { recursive: true });
+  }
+
+  if (!(await exists(targetDir))) {
+    await rename(stagingDir, targetDir);
+    return null;
+  }
+
+  await moveElementsAcrossDir(stagingDir, targetDir, replaceOptionsFromPreset(replacePreset));
+  if (!(await exists(stagingDir))) {
+    return null;
+  }
+
+  const left = await readDir(stagingDir);
+  if (left.every((entry) => IgnoreList.isIgnored(entry.name))) {
+    await remove(stagingDir, { recursive: true });
+    return null;
+  }
+
+  // 改名后下次导入同一目录时不会与残留内容混在一起
+  const leftoverDir = `${targetDir}.__leftover-${Date.now()}__`;
+  await rename(stagingDir, leftoverDir);
+  console.warn(` !_! Files not merged into ${targetDir} were kept in ${leftoverDir}`);
+  return leftoverDir;
+}
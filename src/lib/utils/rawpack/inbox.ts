@@ -0,0 +1,257 @@
+/**
+ * 收件箱工作流
+ *
+ * 用户把新下载的作品（压缩包或文件夹）放进收件箱目录，一条命令完成日常导入：
+ * 解压压缩包、展平嵌套目录、清理系统垃圾文件、检查谱面，通过检查的作品移入根目录并按 BMS 信息命名，
+ * 有问题的作品移入收件箱下的隔离区（可用“查看隔离区”和“重试隔离条目”继续处理）
+ */
+
+import { exists, mkdir, readDir, remove } from '@tauri-apps/plugin-fs';
+import { getDirBmsOutput } from '../bms/scanner';
+import { IgnoreList, removeIgnoredFiles } from '../fs/ignore';
+import { getFileExtension } from '../fs/path';
+import { ReplacePreset } from '../fs/moving';
+import { runHooks } from '../hooks';
+import { ImportAudit } from '../root/journal';
+import { BmsFolderSetNameType, setNameByInfo } from '../work/rename';
+import {
+  extractArchiveChecked,
+  ExtractedDirIssue,
+  placeExtractedDir,
+  prepareExtractedDir,
+} from './extract';
+import { ProvenanceIndex } from './provenance';
+import {
+  getQuarantineDir,
+  QUARANTINE_DIR_NAME,
+  quarantineArchive,
+  quarantineExtractedDir,
+} from './quarantine';
+
+/**
+ * 收件箱中解压压缩包用的临时目录名（与收件箱在同一个卷上，隔离时可以直接重命名）
+ */
+const INBOX_STAGING_DIR_NAME = '_inbox_staging';
+
+/**
+ * 收件箱识别的压缩包格式
+ */
+const INBOX_ARCHIVE_EXTS = ['zip', '7z', 'rar', 'tar', 'gz'];
+
+/**
+ * 收件箱条目的处理结果
+ */
+export type InboxItemStatus = 'imported' | 'quarantined' | 'skipped';
+
+/**
+ * 单个收件箱条目
+ */
+export interface InboxItem {
+  /** 条目名（压缩包文件名或文件夹名） */
+  name: string;
+  kind: 'archive' | 'folder';
+  status: InboxItemStatus;
+  /** 导入后的作品目录 */
+  targetDir?: string;
+  /** 隔离或跳过的原因 */
+  detail?: string;
+}
+
+/**
+ * 收件箱处理报告
+ */
+export interface InboxReport {
+  dryRun: boolean;
+  items: InboxItem[];
+  imported: number;
+  quarantined: number;
+  skipped: number;
+}
+
+/**
+ * 整理并检查目录，通过后移入根目录并命名；有问题时移入隔离区
+ *
+ * @returns 导入后的作品目录，隔离或内容为空时为 null
+ */
+async function importDir(
+  item: InboxItem,
+  dirPath: string,
+  inboxDir: string,
+  rootDir: string,
+  setType: BmsFolderSetNameType,
+  replacePreset: ReplacePreset,
+  audit: ImportAudit | null
+): Promise<string | null> {
+  const baseName = item.kind === 'archive' ? item.name.replace(/\.[^.]+$/, '') : item.name;
+
+  const problem = await prepareExtractedDir(dirPath, replacePreset);
+  if (problem?.issue === ExtractedDirIssue.Empty) {
+    item.status = 'skipped';
+    item.detail = problem.detail;
+    return null;
+  }
+  if (problem) {
+    if (item.kind === 'archive') {
+      // 原压缩包随隔离条目一起保存，避免下次处理收件箱时重复解压
+      const archivePath = `${inboxDir}/${item.name}`;
+      await quarantineArchive(inboxDir, dirPath, archivePath, baseName, problem, audit);
+    } else {
+      await quarantineExtractedDir(inboxDir, dirPath, item.name, baseName, problem, audit);
+    }
+    item.status = 'quarantined';
+    item.detail = problem.detail;
+    return null;
+  }
+
+  await removeIgnoredFiles(dirPath, false);
+  const info = await getDirBmsOutput(dirPath);
+
+  const targetDir = `${rootDir}/${baseName}`;
+  await placeExtractedDir(dirPath, targetDir, replacePreset, audit);
+
+  const { title, artist } = info?.bms.musicInfo ?? {};
+  const { dirName } = await setNameByInfo(
+    targetDir,
+    title,
+    artist,
+    setType,
+    false,
+    replacePreset,
+    true
+  );
+  item.status = 'imported';
+  item.targetDir = `${rootDir}/${dirName}`;
+  await runHooks('after_import', { workPath: item.targetDir, title, artist });
+  return item.targetDir;
+}
+
+/**
+ * 处理收件箱中的新作品
+ *
+ * @command
+ * @category pack
+ * @dangerous true
+ * @name 处理收件箱
+ * @description 解压收件箱中的压缩包并整理文件夹，检查通过的作品移入根目录并按 BMS 信息命名，有问题的作品移入收件箱的隔离区
+ * @frontend true
+ *
+ * @param {string} inboxDir - 收件箱目录
+ * @param {string} rootDir - 根目录
+ * @param {BmsFolderSetNameType} setType - 命名方式
+ * @param {ReplacePreset} replacePreset - 文件替换策略
+ * @param {boolean} dryRun - 模拟运行（只列出收件箱中的条目）
+ *
+ * @returns {Promise<InboxReport>} 每个条目的处理结果
+ */
+export async function processInbox(
+  inboxDir: string,
+  rootDir: string,
+  setType: BmsFolderSetNameType,
+  replacePreset: ReplacePreset,
+  dryRun: boolean
+): Promise<InboxReport> {
+  const report: InboxReport = { dryRun, items: [], imported: 0, quarantined: 0, skipped: 0 };
+  const stagingDir = `${inboxDir}/${INBOX_STAGING_DIR_NAME}`;
+  const provenance = await ProvenanceIndex.load();
+  const audit = dryRun ? null : await ImportAudit.start('inbox');
+
+  if (!dryRun) {
+    await mkdir(rootDir, { recursive: true });
+  }
+
+  const entries = (await readDir(inboxDir))
+    .filter((entry) => entry.name && !IgnoreList.isIgnored(entry.name))
+    .filter((entry) => entry.name !== QUARANTINE_DIR_NAME && entry.name !== INBOX_STAGING_DIR_NAME)
+    .sort((a, b) => a.name.localeCompare(b.name));
+
+  for (const entry of entries) {
+    const entryPath = `${inboxDir}/${entry.name}`;
+    const ext = getFileExtension(entry.name);
+    const isArchive = !entry.isDirectory && INBOX_ARCHIVE_EXTS.includes(ext);
+    const item: InboxItem = {
+      name: entry.name,
+      kind: isArchive ? 'archive' : 'folder',
+      status: 'skipped',
+    };
+    report.items.push(item);
+
+    if (!entry.isDirectory && !isArchive) {
+      item.detail = '不是压缩包或文件夹';
+      console.log(`Skipping ${entryPath}: not an archive or folder`);
+      continue;
+    }
+
+    if (dryRun) {
+      const action = isArchive ? 'extract and import' : 'import';
+      console.log(`[dry-run] Would ${action}: ${entryPath}`);
+      continue;
+    }
+
+    try {
+      if (!isArchive) {
+        await importDir(item, entryPath, inboxDir, rootDir, setType, replacePreset, audit);
+      } else {
+        const { record, archiveHash } = await provenance.lookup(entryPath);
+        if (record) {
+          item.detail = `已导入到 ${record.targetDir}`;
+          console.log(`Skipping ${entryPath}: already imported to ${record.targetDir}`);
+          continue;
+        }
+
+        const workStagingDir = `${stagingDir}/${entry.name.replace(/\.[^.]+$/, '')}`;
+        if (await exists(workStagingDir)) {
+          await remove(workStagingDir, { recursive: true });
+        }
+        await mkdir(workStagingDir, { recursive: true });
+
+        console.log(`Extracting ${entryPath} to ${workStagingDir}`);
+        const unsafe = await extractArchiveChecked(entryPath, workStagingDir);
+        if (unsafe) {
+          const baseName = entry.name.replace(/\.[^.]+$/, '');
+          await quarantineArchive(inboxDir, workStagingDir, entryPath, baseName, unsafe, audit);
+          item.status = 'quarantined';
+          item.detail = unsafe.detail;
+          await audit?.save();
+          continue;
+        }
+        await audit?.record('extract', entryPath, workStagingDir, entryPath);
+
+        const targetDir = await importDir(
+          item,
+          workStagingDir,
+          inboxDir,
+          rootDir,
+          setType,
+          replacePreset,
+          audit
+        );
+        if (targetDir) {
+          await provenance.record(entryPath, targetDir, archiveHash);
+          await audit?.record('remove', entryPath, '', entryPath);
+          await remove(entryPath);
+        }
+      }
+    } catch (error) {
+      item.status = 'skipped';
+      item.detail = String(error);
+      console.error(` !_! Failed to process ${entryPath}:`, error);
+    }
+
+    if (item.status === 'imported') {
+      console.log(`${entry.name}: imported to ${item.targetDir}`);
+    }
+    await audit?.save();
+  }
+
+  await remove(stagingDir, { recursive: true }).catch(() => {});
+
+  report.imported = report.items.filter((item) => item.status === 'imported').length;
+  report.quarantined = report.items.filter((item) => item.status === 'quarantined').length;
+  report.skipped = report.items.filter((item) => item.status === 'skipped').length;
+  console.log(
+    `${dryRun ? '[dry-run] ' : ''}Inbox: ${report.imported} imported, ` +
+      `${report.quarantined} quarantined (see ${getQuarantineDir(inboxDir)}), ` +
+      `${report.skipped} skipped`
+  );
+  return report;
+}
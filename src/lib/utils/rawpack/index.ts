@@ -6,6 +6,11 @@
 import { FileNumberSetter } from './numbering';
 
 export * from './unzip';
+export * from './extract';
+export * from './quarantine';
+export * from './inbox';
 export * from './numbering';
+export * from './partials';
 
-export const { batchRenameWithNum } = FileNumberSetter;
+export const { batchRenameWithNum, listCandidates, applyNumbers, setFileNumWithMapping } =
+  FileNumberSetter;
@@ -53,6 +53,52 @@ export interface RenameOperation {
   num: number;
 }
 
+/**
+ * 编号候选文件（供 GUI 多选编号使用）
+ */
+export interface NumberingCandidate {
+  /** 原始文件名 */
+  originalName: string;
+  /** 文件大小（字节） */
+  size: number;
+  /** 最后修改时间（毫秒） */
+  modifiedAt: number;
+  /** 文件扩展名 */
+  extension: string;
+  /** 从文件名推测的作品标题 */
+  inferredTitle: string;
+}
+
+/**
+ * 编号候选列表
+ */
+export interface NumberingCandidates {
+  candidates: NumberingCandidate[];
+  /** 建议的下一个编号（目录中已有编号的最大值 + 1） */
+  nextNumber: number;
+}
+
+/**
+ * 单个文件的编号分配
+ */
+export interface NumberAssignment {
+  /** 原始文件名 */
+  fileName: string;
+  /** 要添加的编号 */
+  num: number;
+}
+
+/**
+ * 从压缩包文件名推测作品标题：去掉扩展名，下划线视为空格
+ */
+function inferTitleFromFileName(fileName: string): string {
+  return fileName
+    .replace(/\.[^.]+$/, '')
+    .replace(/_/g, ' ')
+    .replace(/\s+/g, ' ')
+    .trim();
+}
+
 /**
  * 原始包文件编号工具类
  */
@@ -238,11 +284,170 @@ export class FileNumberSetter {
     return operations;
   }
 
+  /**
+   * 列出可以编号的文件
+   *
+   * @command
+   * @category pack
+   * @dangerous false
+   * @name 列出待编号文件
+   * @description 列出目录中尚未编号的压缩包（大小、修改时间、推测的标题），以及建议的下一个编号，供多选编号使用
+   * @frontend true
+   *
+   * @param {string} dir - 目录路径
+   * @param {string[]} exts - 包含的扩展名（为空时包含所有文件）
+   * @returns {Promise<NumberingCandidates>} 候选文件和建议的下一个编号
+   */
+  static async listCandidates(dir: string, exts: string[] = []): Promise<NumberingCandidates> {
+    const extensions = exts.map((ext) => ext.replace(/^\./, '').toLowerCase());
+    const files = await FileNumberSetter.getRenameableFiles(dir, extensions, [], exts.length === 0);
+
+    const candidates: NumberingCandidate[] = [];
+    for (const file of files) {
+      const info = await fs.stat(file.path);
+      candidates.push({
+        originalName: file.originalName,
+        size: file.size,
+        modifiedAt: info.mtime?.getTime() ?? 0,
+        extension: file.extension,
+        inferredTitle: inferTitleFromFileName(file.originalName),
+      });
+    }
+
+    let maxNumber = 0;
+    for (const entry of await fs.readDir(dir)) {
+      const match = entry.name?.match(/^(\d+) /);
+      if (match) {
+        maxNumber = Math.max(maxNumber, parseInt(match[1], 10));
+      }
+    }
+
+    return { candidates, nextNumber: maxNumber + 1 };
+  }
+
+  /**
+   * 按指定的编号批量重命名文件
+   *
+   * 先检查所有分配（文件存在、编号不重复且未被目录中已编号的文件占用、目标文件名未被占用），再依次重命名；
+   * 任何一次重命名失败时，撤销已完成的重命名
+   *
+   * @command
+   * @category pack
+   * @dangerous true
+   * @name 应用文件编号
+   * @description 按前端选择的编号为多个文件添加编号前缀，任一文件失败时全部撤销
+   * @frontend true
+   *
+   * @param {string} dir - 目录路径
+   * @param {NumberAssignment[]} assignments - 文件名和编号
+   * @param {boolean} dryRun - 是否模拟运行（只检查不重命名）
+   * @returns {Promise<RenameOperation[]>} 执行的重命名操作列表
+   */
+  static async applyNumbers(
+    dir: string,
+    assignments: NumberAssignment[],
+    dryRun: boolean = false
+  ): Promise<RenameOperation[]> {
+    const existingNums = new Map<number, string>();
+    for (const entry of await fs.readDir(dir)) {
+      const match = entry.name?.match(/^(\d+) /);
+      if (match) {
+        existingNums.set(parseInt(match[1], 10), entry.name);
+      }
+    }
+
+    const usedNums = new Set<number>();
+    const usedNames = new Set<string>();
+    for (const { fileName, num } of assignments) {
+      if (!Number.isInteger(num) || num < 0) {
+        throw new Error(`无效的编号：${num}（${fileName}）`);
+      }
+      if (usedNums.has(num)) {
+        throw new Error(`编号重复：${num}`);
+      }
+      const occupant = existingNums.get(num);
+      if (occupant !== undefined) {
+        throw new Error(`编号已被占用：${num}（${occupant}）`);
+      }
+      if (usedNames.has(fileName)) {
+        throw new Error(`文件重复：${fileName}`);
+      }
+      if (!(await fs.exists(`${dir}/${fileName}`))) {
+        throw new Error(`文件不存在：${dir}/${fileName}`);
+      }
+      if (await fs.exists(`${dir}/${num} ${fileName}`)) {
+        throw new Error(`目标文件已存在：${dir}/${num} ${fileName}`);
+      }
+      usedNums.add(num);
+      usedNames.add(fileName);
+    }
+
+    const operations: RenameOperation[] = [];
+    try {
+      for (const { fileName, num } of assignments) {
+        const operation = await FileNumberSetter.renameFileWithNum(dir, fileName, num, dryRun);
+        operations.push(operation);
+        const action = dryRun ? '[dry-run] Would rename' : 'Rename';
+        console.log(`${action}: ${fileName} -> ${operation.newName}`);
+      }
+    } catch (error) {
+      console.error(` !_! Rename failed, rolling back ${operations.length} renames:`, error);
+      for (const operation of [...operations].reverse()) {
+        await fs
+          .rename(`${dir}/${operation.newName}`, operation.path)
+          .catch((rollbackError) =>
+            console.error(` !_! Failed to roll back ${operation.newName}:`, rollbackError)
+          );
+      }
+      throw error;
+    }
+
+    return operations;
+  }
+
+  /**
+   * 按文件名到编号的映射一次性设置编号
+   *
+   * 非交互版本的 set_file_num：编号由调用方给出，不逐个询问。
+   * 检查和撤销规则与 applyNumbers 相同，按文件名排序后依次重命名
+   *
+   * @command
+   * @category pack
+   * @dangerous true
+   * @name 按映射设置文件编号
+   * @description 按 { 文件名: 编号 } 映射为多个文件添加编号前缀，任一文件失败时全部撤销
+   * @frontend true
+   *
+   * @param {string} dir - 目录路径
+   * @param {Record<string, number>} mapping - 文件名到编号的映射
+   * @param {boolean} dryRun - 是否模拟运行（只检查不重命名）
+   * @returns {Promise<RenameOperation[]>} 执行的重命名操作列表
+   *
+   * @example
+   * ```typescript
+   * const operations = await FileNumberSetter.setFileNumWithMapping(
+   *   '/path/to/packs',
+   *   { 'song_a.zip': 12, 'song_b.rar': 13 },
+   *   false
+   * );
+   * ```
+   */
+  static async setFileNumWithMapping(
+    dir: string,
+    mapping: Record<string, number>,
+    dryRun: boolean = false
+  ): Promise<RenameOperation[]> {
+    const assignments = Object.entries(mapping)
+      .sort(([a], [b]) => a.localeCompare(b))
+      .map(([fileName, num]) => ({ fileName, num }));
+    return await FileNumberSetter.applyNumbers(dir, assignments, dryRun);
+  }
+
   /**
    * 交互式文件编号设置
    * 对应 Rust: set_file_num (rawpack.py:211-213)
    *
-   * 注意：此函数设计用于命令行界面，GUI 应用应使用 `batchRenameWithNum`
+   * 注意：此函数设计用于命令行界面，GUI 应用应使用 `setFileNumWithMapping` 或 `applyNumbers`
    *
    * @param dir - 目录路径
    * @param allowExtensions - 允许的扩展名列表
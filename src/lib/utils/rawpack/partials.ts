@@ -0,0 +1,119 @@
+/**
+ * 未完成下载的清理
+ *
+ * 浏览器和下载工具在下载过程中写入 `.part`、`.crdownload`、`.!ut` 等临时文件，
+ * 下载中断后这些文件会一直留在压缩包目录中（编号时也会跳过对应的压缩包）。
+ * 这里找出超过指定时间未修改的临时文件并删除；Firefox 同时创建的空白占位文件也一并删除
+ */
+
+import { exists, readDir, remove, stat } from '@tauri-apps/plugin-fs';
+import { formatSize } from '../fs/sizeTally';
+
+/**
+ * 未完成下载的扩展名（Firefox、Chrome、µTorrent、qBittorrent、Safari 等）
+ */
+const PARTIAL_DOWNLOAD_EXTS = ['part', 'partial', 'crdownload', '!ut', '!qb', 'download'];
+
+/**
+ * 未完成的下载文件
+ */
+export interface PartialDownload {
+  name: string;
+  path: string;
+  size: number;
+  /** 最后修改时间（毫秒） */
+  modifiedAt: number;
+  /** 同时删除的空白占位文件 */
+  placeholder?: string;
+}
+
+/**
+ * 清理结果
+ */
+export interface PartialCleanResult {
+  /** 删除（或模拟运行时将要删除）的文件 */
+  removed: PartialDownload[];
+  /** 修改时间较近、可能仍在下载而保留的文件 */
+  recent: string[];
+  freedSize: number;
+}
+
+/**
+ * 文件名是否为未完成下载的临时文件
+ */
+function isPartialDownload(name: string): boolean {
+  const dot = name.lastIndexOf('.');
+  return dot > 0 && PARTIAL_DOWNLOAD_EXTS.includes(name.slice(dot + 1).toLowerCase());
+}
+
+/**
+ * 清理压缩包目录中未完成的下载
+ *
+ * @command
+ * @category pack
+ * @dangerous true
+ * @name 清理未完成的下载
+ * @description 删除压缩包目录中超过指定时间未修改的 .part、.crdownload、.!ut 等未完成下载的临时文件，以及对应的空白占位文件
+ * @frontend true
+ *
+ * @param {string} packDir - 压缩包目录
+ * @param {number} maxAgeHours - 超过该时间（小时）未修改的临时文件视为已放弃
+ * @param {boolean} dryRun - 模拟运行（只列出要删除的文件）
+ *
+ * @returns {Promise<PartialCleanResult>} 删除的文件和保留的文件
+ */
+export async function cleanPartials(
+  packDir: string,
+  maxAgeHours: number = 24,
+  dryRun: boolean = false
+): Promise<PartialCleanResult> {
+  const result: PartialCleanResult = { removed: [], recent: [], freedSize: 0 };
+  const deadline = Date.now() - maxAgeHours * 60 * 60 * 1000;
+
+  for (const entry of await readDir(packDir)) {
+    if (!entry.isFile || !entry.name || !isPartialDownload(entry.name)) {
+      continue;
+    }
+
+    const path = `${packDir}/${entry.name}`;
+    const info = await stat(path);
+    const modifiedAt = info.mtime?.getTime() ?? 0;
+    if (modifiedAt > deadline) {
+      result.recent.push(entry.name);
+      console.log(`Keeping recent partial download: ${entry.name}`);
+      continue;
+    }
+
+    const partial: PartialDownload = { name: entry.name, path, size: info.size, modifiedAt };
+
+    // Firefox 下载时会创建一个同名的空白文件（去掉 .part），下载失败后同样需要删除
+    const placeholderPath = path.slice(0, path.lastIndexOf('.'));
+    if ((await exists(placeholderPath)) && (await stat(placeholderPath)).size === 0) {
+      partial.placeholder = placeholderPath;
+    }
+
+    const targets = partial.placeholder ? [path, partial.placeholder] : [path];
+    if (dryRun) {
+      targets.forEach((target) => console.log(`[dry-run] Would remove: ${target}`));
+    } else {
+      try {
+        for (const target of targets) {
+          await remove(target);
+          console.log(`Removed: ${target}`);
+        }
+      } catch (error) {
+        console.error(` !_! Failed to remove ${path}:`, error);
+        continue;
+      }
+    }
+
+    result.removed.push(partial);
+    result.freedSize += partial.size;
+  }
+
+  console.log(
+    `${dryRun ? '[dry-run] ' : ''}Removed ${result.removed.length} partial downloads ` +
+      `(${formatSize(result.freedSize)}), kept ${result.recent.length} recent ones`
+  );
+  return result;
+}
@@ -0,0 +1,132 @@
+/**
+ * 导入来源记录
+ *
+ * 记录已成功导入的压缩包（哈希、大小、目标作品目录），
+ * 用于重复运行解压命令时跳过已导入的压缩包。只更新谱面的补丁包也记录在这里，并记下替换的文件
+ */
+
+import { stat } from '@tauri-apps/plugin-fs';
+import { calculateFileHash } from '../fs/hash';
+import { getAppDataPath, readJsonFile, writeJsonFile } from '../storage';
+
+/**
+ * 来源记录文件名（位于应用数据目录）
+ */
+const PROVENANCE_FILE = 'provenance.json';
+
+/**
+ * 单条导入记录
+ */
+export interface ProvenanceRecord {
+  /** 压缩包哈希 */
+  archiveHash: string;
+  /** 压缩包文件名 */
+  archiveName: string;
+  /** 压缩包大小（字节） */
+  archiveSize: number;
+  /** 导入到的作品目录 */
+  targetDir: string;
+  /** 导入时间（ISO 格式） */
+  importedAt: string;
+  /** 导入方式：完整导入作品，或只更新谱面的补丁（未记录时为完整导入） */
+  kind?: 'import' | 'chart_patch';
+  /** 补丁写入的文件（相对作品目录） */
+  files?: string[];
+}
+
+/**
+ * 导入来源索引
+ */
+export class ProvenanceIndex {
+  private records: ProvenanceRecord[];
+  private readonly filePath: string;
+
+  private constructor(filePath: string, records: ProvenanceRecord[]) {
+    this.filePath = filePath;
+    this.records = records;
+  }
+
+  /**
+   * 从应用数据目录加载索引
+   */
+  static async load(): Promise<ProvenanceIndex> {
+    const filePath = await getAppDataPath(PROVENANCE_FILE);
+    const records = await readJsonFile<ProvenanceRecord[]>(filePath, []);
+    return new ProvenanceIndex(filePath, records);
+  }
+
+  /**
+   * 按哈希查找导入记录
+   */
+  findByHash(archiveHash: string): ProvenanceRecord | undefined {
+    return this.records.find((record) => record.archiveHash === archiveHash);
+  }
+
+  /**
+   * 按作品目录查找最近一次完整导入记录（不含谱面补丁）
+   */
+  findByTargetDir(targetDir: string): ProvenanceRecord | undefined {
+    return [...this.records]
+      .reverse()
+      .find((record) => record.targetDir === targetDir && record.kind !== 'chart_patch');
+  }
+
+  /**
+   * 列出作品目录的谱面补丁记录（按应用顺序）
+   */
+  findPatches(targetDir: string): ProvenanceRecord[] {
+    return this.records.filter(
+      (record) => record.targetDir === targetDir && record.kind === 'chart_patch'
+    );
+  }
+
+  /**
+   * 查找压缩包的导入记录
+   *
+   * 先按大小筛选，只有存在同样大小的记录时才计算哈希
+   *
+   * @returns 导入记录和压缩包哈希（未计算时为 null）
+   */
+  async lookup(
+    archivePath: string
+  ): Promise<{ record: ProvenanceRecord | undefined; archiveHash: string | null }> {
+    const { size } = await stat(archivePath);
+    if (!this.records.some((record) => record.archiveSize === size)) {
+      return { record: undefined, archiveHash: null };
+    }
+
+    const archiveHash = await calculateFileHash(archivePath);
+    return { record: this.findByHash(archiveHash), archiveHash };
+  }
+
+  /**
+   * 记录一次成功导入并保存
+   *
+   * @param archivePath - 压缩包路径
+   * @param targetDir - 导入到的作品目录
+   * @param archiveHash - 已计算的哈希（可选）
+   * @param details - 导入方式和写入的文件（谱面补丁时填写）
+   */
+  async record(
+    archivePath: string,
+    targetDir: string,
+    archiveHash?: string | null,
+    details?: Pick<ProvenanceRecord, 'kind' | 'files'>
+  ): Promise<void> {
+    const { size } = await stat(archivePath);
+    const hash = archiveHash ?? (await calculateFileHash(archivePath));
+    const archiveName = archivePath.split(/[\\/]/).pop() ?? archivePath;
+
+    this.records = this.records.filter((record) => record.archiveHash !== hash);
+    this.records.push({
+      archiveHash: hash,
+      archiveName,
+      archiveSize: size,
+      targetDir,
+      importedAt: new Date().toISOString(),
+      ...details,
+    });
+
+    await writeJsonFile(this.filePath, this.records);
+  }
+}
@@ -0,0 +1,298 @@
+/**
+ * 隔离区工具
+ * 解压结果无法自动导入时，将内容移入压缩包目录下的 `_quarantine/<压缩包名>/`，
+ * 并写入原因文件，之后可以列出并重试
+ */
+
+import {
+  copyFile,
+  exists,
+  mkdir,
+  readDir,
+  readTextFile,
+  remove,
+  rename,
+  writeTextFile,
+} from '@tauri-apps/plugin-fs';
+import { copyDirRecursive } from '../fs/copy';
+import { ReplacePreset, replaceOptionsFromPreset } from '../fs/moving';
+import { getFileName } from '../fs/path';
+import { runHooks } from '../hooks';
+import { ImportAudit } from '../root/journal';
+import {
+  ExtractedDirIssue,
+  type ExtractedDirProblem,
+  placeExtractedDir,
+  prepareExtractedDir,
+} from './extract';
+
+/**
+ * 隔离区目录名
+ */
+export const QUARANTINE_DIR_NAME = '_quarantine';
+
+/**
+ * 原因文件名
+ */
+export const QUARANTINE_REASON_FILE = 'reason.json';
+
+/**
+ * 隔离内容所在的子目录名
+ */
+const QUARANTINE_CONTENT_DIR = 'content';
+
+/**
+ * 隔离原因
+ */
+export interface QuarantineInfo {
+  /** 原压缩包文件名 */
+  archive: string;
+  /** 问题类型 */
+  issue: ExtractedDirIssue;
+  /** 问题说明 */
+  detail: string;
+  /** 导入成功时应使用的作品目录名 */
+  targetDirName: string;
+  /** 隔离时间（ISO 格式） */
+  quarantinedAt: string;
+}
+
+/**
+ * 隔离区条目
+ */
+export interface QuarantineEntry {
+  /** 条目名（压缩包名） */
+  name: string;
+  /** 条目路径 */
+  path: string;
+  /** 隔离原因，原因文件缺失时为 null */
+  info: QuarantineInfo | null;
+}
+
+/**
+ * 获取隔离区目录
+ */
+export function getQuarantineDir(packDir: string): string {
+  return `${packDir}/${QUARANTINE_DIR_NAME}`;
+}
+
+/**
+ * 将缓存目录中的内容移入隔离区
+ *
+ * @param packDir - 压缩包目录
+ * @param cacheDirPath - 解压缓存目录
+ * @param archive - 原压缩包文件名
+ * @param targetDirName - 导入成功时应使用的作品目录名
+ * @param problem - 问题详情
+ * @param audit - 导入审计日志，传入时在移动前记录每个文件
+ */
+export async function quarantineExtractedDir(
+  packDir: string,
+  cacheDirPath: string,
+  archive: string,
+  targetDirName: string,
+  problem: ExtractedDirProblem,
+  audit?: ImportAudit | null
+): Promise<string> {
+  const entryDir = `${getQuarantineDir(packDir)}/${archive}`;
+  const contentDir = `${entryDir}/${QUARANTINE_CONTENT_DIR}`;
+  if (audit && (await exists(cacheDirPath))) {
+    await audit.recordDir('quarantine', cacheDirPath, contentDir);
+  }
+
+  // 同名条目已存在时，用新的解压结果覆盖
+  if (await exists(entryDir)) {
+    await remove(entryDir, { recursive: true });
+  }
+  await mkdir(entryDir, { recursive: true });
+
+  if (await exists(cacheDirPath)) {
+    try {
+      await rename(cacheDirPath, contentDir);
+    } catch {
+      // 缓存目录和压缩包目录不在同一个卷上，改为复制
+      await copyDirRecursive(
+        cacheDirPath,
+        contentDir,
+        replaceOptionsFromPreset(ReplacePreset.Default)
+      );
+      await remove(cacheDirPath, { recursive: true });
+    }
+  } else {
+    await mkdir(contentDir, { recursive: true });
+  }
+
+  const info: QuarantineInfo = {
+    archive,
+    issue: problem.issue,
+    detail: problem.detail,
+    targetDirName,
+    quarantinedAt: new Date().toISOString(),
+  };
+  await writeTextFile(`${entryDir}/${QUARANTINE_REASON_FILE}`, JSON.stringify(info, null, 2));
+
+  console.log(` !_! ${archive}: ${problem.detail}, moved to ${entryDir}`);
+  return entryDir;
+}
+
+/**
+ * 将原压缩包连同（可能为空的）解压结果一起移入隔离区
+ *
+ * 之后处理压缩包目录时不会再次解压同一个压缩包
+ *
+ * @param archivePath - 原压缩包路径（位于压缩包目录中）
+ */
+export async function quarantineArchive(
+  packDir: string,
+  cacheDirPath: string,
+  archivePath: string,
+  targetDirName: string,
+  problem: ExtractedDirProblem,
+  audit?: ImportAudit | null
+): Promise<string> {
+  const archive = getFileName(archivePath);
+  const entryDir = await quarantineExtractedDir(
+    packDir,
+    cacheDirPath,
+    archive,
+    targetDirName,
+    problem,
+    audit
+  );
+  await audit?.record('quarantine', archivePath, `${entryDir}/${archive}`, archivePath);
+  try {
+    await rename(archivePath, `${entryDir}/${archive}`);
+  } catch {
+    await copyFile(archivePath, `${entryDir}/${archive}`);
+    await remove(archivePath);
+  }
+  return entryDir;
+}
+
+/**
+ * 读取隔离区条目的原因文件
+ */
+async function readQuarantineInfo(entryDir: string): Promise<QuarantineInfo | null> {
+  const reasonFile = `${entryDir}/${QUARANTINE_REASON_FILE}`;
+  if (!(await exists(reasonFile))) {
+    return null;
+  }
+
+  try {
+    return JSON.parse(await readTextFile(reasonFile)) as QuarantineInfo;
+  } catch (error) {
+    console.warn(`Failed to read quarantine reason: ${reasonFile}`, error);
+    return null;
+  }
+}
+
+/**
+ * 列出隔离区中的条目
+ *
+ * @command
+ * @category pack
+ * @dangerous false
+ * @name 查看隔离区
+ * @description 列出解压后无法自动导入而被隔离的压缩包及其原因
+ * @frontend true
+ *
+ * @param {string} packDir - 压缩包目录
+ *
+ * @returns {Promise<QuarantineEntry[]>} 隔离区条目列表
+ */
+export async function reviewQuarantine(packDir: string): Promise<QuarantineEntry[]> {
+  const quarantineDir = getQuarantineDir(packDir);
+  if (!(await exists(quarantineDir))) {
+    return [];
+  }
+
+  const result: QuarantineEntry[] = [];
+  const entries = await readDir(quarantineDir);
+
+  for (const entry of entries) {
+    if (!entry.isDirectory || !entry.name) {
+      continue;
+    }
+
+    const entryDir = `${quarantineDir}/${entry.name}`;
+    const info = await readQuarantineInfo(entryDir);
+    result.push({ name: entry.name, path: entryDir, info });
+
+    console.log(`${entry.name}: ${info ? `[${info.issue}] ${info.detail}` : '(missing reason)'}`);
+  }
+
+  return result;
+}
+
+/**
+ * 重试导入隔离区中的条目
+ *
+ * 手动修正隔离内容后调用：重新整理并检查内容，通过则移入根目录并删除条目，
+ * 否则更新原因文件
+ *
+ * @command
+ * @category pack
+ * @dangerous true
+ * @name 重试隔离条目
+ * @description 重新检查隔离区中的条目，通过检查后导入到根目录
+ * @frontend true
+ *
+ * @param {string} packDir - 压缩包目录
+ * @param {string} rootDir - 根目录
+ * @param {string} entryName - 隔离条目名（压缩包名）
+ * @param {ReplacePreset} replacePreset - 文件替换策略
+ *
+ * @returns {Promise<boolean>} 是否导入成功
+ */
+export async function retryQuarantine(
+  packDir: string,
+  rootDir: string,
+  entryName: string,
+  replacePreset: ReplacePreset
+): Promise<boolean> {
+  const entryDir = `${getQuarantineDir(packDir)}/${entryName}`;
+  const contentDir = `${entryDir}/${QUARANTINE_CONTENT_DIR}`;
+
+  if (!(await exists(contentDir))) {
+    throw new Error(`Quarantine entry not found: ${entryDir}`);
+  }
+
+  const info = await readQuarantineInfo(entryDir);
+  const targetDirName = info?.targetDirName ?? entryName.replace(/\.[^.]+$/, '');
+  if (info?.issue === ExtractedDirIssue.UnsafeArchive) {
+    // 压缩包没有解压，重试没有意义；确认安全后请调整解压限制并手动处理
+    console.log(` !_! ${entryName}: archive was not extracted (${info.detail})`);
+    return false;
+  }
+
+  const problem = await prepareExtractedDir(contentDir, replacePreset);
+  if (problem?.issue === ExtractedDirIssue.Empty) {
+    // 空内容已被清理，条目没有保留的意义
+    await remove(entryDir, { recursive: true });
+    console.log(` !_! ${entryName}: content is empty, entry removed`);
+    return false;
+  }
+
+  if (problem) {
+    const updated: QuarantineInfo = {
+      archive: info?.archive ?? entryName,
+      issue: problem.issue,
+      detail: problem.detail,
+      targetDirName,
+      quarantinedAt: new Date().toISOString(),
+    };
+    await writeTextFile(`${entryDir}/${QUARANTINE_REASON_FILE}`, JSON.stringify(updated, null, 2));
+    console.log(` !_! ${entryName}: still ${problem.detail}`);
+    return false;
+  }
+
+  const audit = await ImportAudit.start('quarantine-retry');
+  await mkdir(rootDir, { recursive: true });
+  await placeExtractedDir(contentDir, `${rootDir}/${targetDirName}`, replacePreset, audit);
+  await remove(entryDir, { recursive: true });
+  await audit?.save();
+  await runHooks('after_import', { workPath: `${rootDir}/${targetDirName}` });
+
+  console.log(`${entryName}: imported to ${rootDir}/${targetDirName}`);
+  return true;
+}
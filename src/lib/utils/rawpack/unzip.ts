@@ -2,9 +2,33 @@
  * 原始包解压工具
  */
 
-import { exists, mkdir, readDir, remove, rename } from '@tauri-apps/plugin-fs';
-import { ArchiveExtractor } from '../fs/archive';
-import { moveElementsAcrossDir, replaceOptionsFromPreset, ReplacePreset } from '../fs/moving';
+import { mkdir, readDir } from '@tauri-apps/plugin-fs';
+import { Confirmation } from '../confirm';
+import { ReplacePreset } from '../fs/moving';
+import { runHooks } from '../hooks';
+import { ImportAudit } from '../root/journal';
+import {
+  extractArchiveChecked,
+  ExtractedDirIssue,
+  placeExtractedDir,
+  prepareExtractedDir,
+} from './extract';
+import { ProvenanceIndex } from './provenance';
+import { quarantineArchive, quarantineExtractedDir } from './quarantine';
+
+/**
+ * 解压前列出将要导入的压缩包并请求确认
+ */
+async function confirmImport(fileNames: string[], rootDir: string): Promise<boolean> {
+  if (fileNames.length === 0) {
+    return true;
+  }
+  return await Confirmation.request({
+    title: '确认导入',
+    message: `将解压 ${fileNames.length} 个压缩包到 ${rootDir}，是否继续？`,
+    details: fileNames,
+  });
+}
 
 /**
  * 解压数字编号压缩包到 BMS 文件夹
@@ -20,7 +44,7 @@ import { moveElementsAcrossDir, replaceOptionsFromPreset, ReplacePreset } from '
  * @param {string} packDir - 压缩包目录
  * @param {string} cacheDir - 缓存目录
  * @param {string} rootDir - 根目录
- * @param {boolean} confirm - 是否确认
+ * @param {boolean} confirm - 解压前列出压缩包并确认
  * @param {ReplacePreset} replacePreset - 文件替换策略
  *
  * @returns {Promise<void>}
@@ -38,6 +62,10 @@ export async function unzipNumericToBmsFolder(
 
   // 获取数字编号文件列表
   const fileNames = await getNumSetFileNames(packDir);
+  if (confirm && !(await confirmImport(fileNames, rootDir))) {
+    return;
+  }
+  const audit = await ImportAudit.start('unzip-numeric');
 
   for (const fileName of fileNames) {
     // 提取编号
@@ -55,12 +83,30 @@ export async function unzipNumericToBmsFolder(
 
     // 解压文件
     console.log(`Extracting ${packFile} to ${workCacheDir}`);
-    await ArchiveExtractor.extractAuto(packFile, workCacheDir);
+    const unsafe = await extractArchiveChecked(packFile, workCacheDir);
+    if (unsafe) {
+      const targetName = `${num} ${_originalName}`;
+      await quarantineArchive(packDir, workCacheDir, packFile, targetName, unsafe, audit);
+      await audit?.save();
+      continue;
+    }
+    await audit?.record('extract', packFile, workCacheDir, packFile);
 
-    // 移出文件夹中的文件
-    const success = await moveOutFilesInFolderInCacheDir(workCacheDir, replacePreset);
-    if (!success) {
+    // 移出文件夹中的文件，并检查谱面
+    const problem = await prepareExtractedDir(workCacheDir, replacePreset);
+    if (problem) {
       console.log(`Failed to process ${packFile}, skipping`);
+      if (problem.issue !== ExtractedDirIssue.Empty) {
+        await quarantineExtractedDir(
+          packDir,
+          workCacheDir,
+          fileName,
+          `${num} ${_originalName}`,
+          problem,
+          audit
+        );
+      }
+      await audit?.save();
       continue;
     }
 
@@ -80,11 +126,9 @@ export async function unzipNumericToBmsFolder(
     const targetDir = `${rootDir}/${targetDirName}`;
 
     // 移动到根目录
-    if (!(await exists(targetDir))) {
-      await rename(workCacheDir, targetDir);
-    } else {
-      await moveElementsAcrossDir(workCacheDir, targetDir, replaceOptionsFromPreset(replacePreset));
-    }
+    await placeExtractedDir(workCacheDir, targetDir, replacePreset, audit);
+    await audit?.save();
+    await runHooks('after_import', { workPath: targetDir });
   }
 }
 
@@ -102,8 +146,9 @@ export async function unzipNumericToBmsFolder(
  * @param {string} packDir - 压缩包目录
  * @param {string} cacheDir - 缓存目录
  * @param {string} rootDir - 根目录
- * @param {boolean} confirm - 是否确认
+ * @param {boolean} confirm - 解压前列出压缩包并确认
  * @param {ReplacePreset} replacePreset - 文件替换策略
+ * @param {boolean} force - 强制重新导入已导入过的压缩包
  *
  * @returns {Promise<void>}
  */
@@ -112,7 +157,8 @@ export async function unzipWithNameToBmsFolder(
   cacheDir: string,
   rootDir: string,
   confirm: boolean,
-  replacePreset: ReplacePreset
+  replacePreset: ReplacePreset,
+  force: boolean = false
 ): Promise<void> {
   // 确保缓存目录和根目录存在
   await mkdir(cacheDir, { recursive: true });
@@ -120,33 +166,51 @@ export async function unzipWithNameToBmsFolder(
 
   // 获取所有压缩包文件
   const entries = await readDir(packDir);
-  const packFiles = entries.filter((e) => !e.isDirectory);
+  const packFiles = entries
+    .filter((e) => !e.isDirectory && e.name)
+    .map((e) => e.name)
+    .filter((name) => ['zip', '7z', 'rar'].includes(name.split('.').pop()?.toLowerCase() || ''));
+  if (confirm && !(await confirmImport(packFiles, rootDir))) {
+    return;
+  }
 
-  for (const entry of packFiles) {
-    if (!entry.name) {
-      continue;
-    }
+  // 已导入压缩包记录
+  const provenance = await ProvenanceIndex.load();
+  const audit = await ImportAudit.start('unzip-named');
 
-    const ext = entry.name.split('.').pop()?.toLowerCase();
-    if (!['zip', '7z', 'rar'].includes(ext || '')) {
+  for (const fileName of packFiles) {
+    const packFile = `${packDir}/${fileName}`;
+    const baseName = fileName.replace(/\.[^.]+$/, '');
+    const workCacheDir = `${cacheDir}/${baseName}`;
+
+    // 跳过已成功导入过的压缩包
+    const { record, archiveHash } = await provenance.lookup(packFile);
+    if (record && !force) {
+      console.log(`Skipping ${packFile}: already imported to ${record.targetDir}`);
       continue;
     }
 
-    const packFile = `${packDir}/${entry.name}`;
-    const baseName = entry.name.replace(/\.[^.]+$/, '');
-    const workCacheDir = `${cacheDir}/${baseName}`;
-
     // 创建工作缓存目录
     await mkdir(workCacheDir, { recursive: true });
 
     // 解压文件
     console.log(`Extracting ${packFile} to ${workCacheDir}`);
-    await ArchiveExtractor.extractAuto(packFile, workCacheDir);
+    const unsafe = await extractArchiveChecked(packFile, workCacheDir);
+    if (unsafe) {
+      await quarantineArchive(packDir, workCacheDir, packFile, baseName, unsafe, audit);
+      await audit?.save();
+      continue;
+    }
+    await audit?.record('extract', packFile, workCacheDir, packFile);
 
-    // 移出文件夹中的文件
-    const success = await moveOutFilesInFolderInCacheDir(workCacheDir, replacePreset);
-    if (!success) {
+    // 移出文件夹中的文件，并检查谱面
+    const problem = await prepareExtractedDir(workCacheDir, replacePreset);
+    if (problem) {
       console.log(`Failed to process ${packFile}, skipping`);
+      if (problem.issue !== ExtractedDirIssue.Empty) {
+        await quarantineExtractedDir(packDir, workCacheDir, fileName, baseName, problem, audit);
+      }
+      await audit?.save();
       continue;
     }
 
@@ -155,107 +219,11 @@ export async function unzipWithNameToBmsFolder(
     const targetDir = `${rootDir}/${targetDirName}`;
 
     // 移动到根目录
-    if (!(await exists(targetDir))) {
-      await rename(workCacheDir, targetDir);
-    } else {
-      await moveElementsAcrossDir(workCacheDir, targetDir, replaceOptionsFromPreset(replacePreset));
-    }
-  }
-}
-
-/**
- * 移出缓存文件夹中的文件
- * 展平嵌套的目录结构
- */
-async function moveOutFilesInFolderInCacheDir(
-  cacheDirPath: string,
-  replacePreset: ReplacePreset
-): Promise<boolean> {
-  let done = false;
-  let error = false;
-
-  // 在循环外部声明计数器，用于最终检查
-  let totalFolderCount = 0;
-  let totalFileCount = 0;
-
-  while (true) {
-    // 每个循环开始时重置计数器
-    let cacheFolderCount = 0;
-    let cacheFileCount = 0;
-    let innerDirName: string | null = null;
-
-    // 重新扫描目录
-    const entries = await readDir(cacheDirPath);
-
-    for (const entry of entries) {
-      if (!entry.name) {
-        continue;
-      }
-
-      if (entry.isDirectory) {
-        // 跳过 __MACOSX 目录
-        if (entry.name === '__MACOSX') {
-          await remove(`${cacheDirPath}/${entry.name}`, { recursive: true });
-          continue;
-        }
-
-        cacheFolderCount++;
-        innerDirName = entry.name;
-      } else {
-        cacheFileCount++;
-      }
-    }
-
-    if (cacheFolderCount === 0) {
-      done = true;
-    }
-
-    if (cacheFolderCount === 1 && cacheFileCount >= 10) {
-      done = true;
-    }
-
-    if (cacheFolderCount > 1) {
-      console.log(` !_! ${cacheDirPath}: has more than 1 folders, please do it manually.`);
-      error = true;
-    }
-
-    if (done || error) {
-      break;
-    }
-
-    // 移动内部目录的文件到当前层级
-    if (innerDirName) {
-      const innerPath = `${cacheDirPath}/${innerDirName}`;
-      const innerInnerPath = `${innerPath}/${innerDirName}`;
-
-      if (await exists(innerInnerPath)) {
-        console.log(` - Renaming inner inner dir name: ${innerInnerPath}`);
-        await rename(innerInnerPath, `${innerInnerPath}-rep`);
-      }
-
-      console.log(` - Moving inner files in ${innerPath} to ${cacheDirPath}`);
-      await moveElementsAcrossDir(innerPath, cacheDirPath, replaceOptionsFromPreset(replacePreset));
-
-      // 删除内部目录
-      await remove(innerPath, { recursive: true }).catch(() => {});
-    }
-
-    // 保存计数器的值用于最终检查
-    totalFolderCount = cacheFolderCount;
-    totalFileCount = cacheFileCount;
-  }
-
-  if (error) {
-    return false;
+    await placeExtractedDir(workCacheDir, targetDir, replacePreset, audit);
+    await provenance.record(packFile, targetDir, archiveHash);
+    await audit?.save();
+    await runHooks('after_import', { workPath: targetDir });
   }
-
-  if (totalFolderCount === 0 && totalFileCount === 0) {
-    console.log(` !_! ${cacheDirPath}: Cache is Empty!`);
-    await remove(cacheDirPath, { recursive: true });
-    return false;
-  }
-
-  return true;
 }
 
 /**
@@ -0,0 +1,137 @@
+/**
+ * 最近使用的目录
+ *
+ * 记录最近用作根目录、作品目录、压缩包目录和缓存目录的路径（保存在应用数据目录，重启后保留），
+ * 选择目录时可以直接从列表中选择，不必每次都在对话框中逐层查找
+ */
+
+import { getAppDataPath, readJsonFile, writeJsonFile } from './storage';
+
+/**
+ * 目录的用途
+ */
+export type RecentDirKind = 'root' | 'work' | 'pack' | 'cache';
+
+/**
+ * 每种用途保留的目录数
+ */
+const MAX_RECENT_DIRS = 20;
+
+const RECENT_DIRS_FILE = 'recent-dirs.json';
+
+const RECENT_DIR_KINDS: readonly RecentDirKind[] = ['root', 'work', 'pack', 'cache'];
+
+/**
+ * 命令参数名对应的目录用途
+ */
+const PARAM_KINDS: Record<string, RecentDirKind> = {
+  rootDir: 'root',
+  rootDirA: 'root',
+  rootDirB: 'root',
+  hqDir: 'root',
+  lqDir: 'root',
+  syncDir: 'root',
+  workDir: 'work',
+  oldWorkDir: 'work',
+  newWorkDir: 'work',
+  packDir: 'pack',
+  inboxDir: 'pack',
+  cacheDir: 'cache',
+};
+
+/**
+ * 获取命令参数对应的目录用途（不记录的参数返回 null）
+ */
+export function recentDirKindForParam(paramKey: string): RecentDirKind | null {
+  return PARAM_KINDS[paramKey] ?? null;
+}
+
+function checkKind(kind: string): RecentDirKind {
+  if (!RECENT_DIR_KINDS.includes(kind as RecentDirKind)) {
+    throw new Error(`未知的目录用途：${kind}（可用：${RECENT_DIR_KINDS.join(', ')}）`);
+  }
+  return kind as RecentDirKind;
+}
+
+/**
+ * 最近使用的目录记录
+ */
+export class RecentDirs {
+  private static dirs: Partial<Record<RecentDirKind, string[]>> | null = null;
+
+  private static async load(): Promise<Partial<Record<RecentDirKind, string[]>>> {
+    this.dirs ??= await readJsonFile(await getAppDataPath(RECENT_DIRS_FILE), {});
+    return this.dirs;
+  }
+
+  /**
+   * 获取某种用途最近使用的目录（最近的在前）
+   */
+  static async list(kind: RecentDirKind): Promise<string[]> {
+    return [...((await this.load())[kind] ?? [])];
+  }
+
+  /**
+   * 记录使用了一个目录（已在列表中时移到最前）
+   */
+  static async add(kind: RecentDirKind, path: string): Promise<string[]> {
+    const dir = path.trim().replace(/[\\/]+$/, '');
+    if (!dir) {
+      return await this.list(kind);
+    }
+    const dirs = await this.load();
+    dirs[kind] = [dir, ...(dirs[kind] ?? []).filter((item) => item !== dir)].slice(
+      0,
+      MAX_RECENT_DIRS
+    );
+    await writeJsonFile(await getAppDataPath(RECENT_DIRS_FILE), dirs);
+    return [...dirs[kind]];
+  }
+
+  /**
+   * 记录命令参数中的目录（命令执行成功后调用）
+   */
+  static async addFromParams(params: Record<string, unknown>): Promise<void> {
+    for (const [key, value] of Object.entries(params)) {
+      const kind = recentDirKindForParam(key);
+      if (kind && typeof value === 'string') {
+        await this.add(kind, value);
+      }
+    }
+  }
+}
+
+/**
+ * 查看最近使用的目录
+ *
+ * @command
+ * @category settings
+ * @dangerous false
+ * @name 最近使用的目录
+ * @description 列出最近用作根目录、作品目录、压缩包目录或缓存目录的路径（最近的在前）
+ * @frontend true
+ *
+ * @param {string} kind - 目录用途（root / work / pack / cache）
+ * @returns {Promise<string[]>} 目录列表
+ */
+export async function historyList(kind: string): Promise<string[]> {
+  return await RecentDirs.list(checkKind(kind));
+}
+
+/**
+ * 记录最近使用的目录
+ *
+ * @command
+ * @category settings
+ * @dangerous false
+ * @name 记录最近使用的目录
+ * @description 把目录加入最近使用的列表（命令执行成功后会自动记录参数中的目录）
+ * @frontend true
+ *
+ * @param {string} kind - 目录用途（root / work / pack / cache）
+ * @param {string} path - 目录路径
+ * @returns {Promise<string[]>} 更新后的目录列表
+ */
+export async function historyAdd(kind: string, path: string): Promise<string[]> {
+  return await RecentDirs.add(checkKind(kind), path);
+}
@@ -0,0 +1,227 @@
+/**
+ * 下载链接解析
+ *
+ * 将网盘分享页面链接（Google Drive、OneDrive 等）转换为可以直接下载的链接。
+ * 解析器按注册顺序匹配，未匹配的链接按直链处理
+ */
+
+/**
+ * 解析器使用的请求函数（由下载器提供，遵守主机请求间隔）
+ */
+export type ResolverFetch = (url: string, init?: RequestInit) => Promise<Response>;
+
+/**
+ * 链接解析器
+ */
+export interface LinkResolver {
+  /** 解析器名称（用于日志） */
+  name: string;
+  /** 是否处理该链接 */
+  matches(url: URL): boolean;
+  /** 返回直接下载链接 */
+  resolve(url: URL, fetchFn: ResolverFetch): Promise<string>;
+}
+
+/**
+ * 不支持自动下载的链接
+ */
+export class UnsupportedLinkError extends Error {
+  constructor(
+    readonly url: string,
+    reason: string
+  ) {
+    super(`Unsupported download link (${reason}): ${url}`);
+  }
+}
+
+/**
+ * 解析 HTML 中的隐藏表单字段
+ */
+function parseHiddenInputs(html: string): Record<string, string> {
+  const fields: Record<string, string> = {};
+  for (const match of html.matchAll(/<input[^>]+type="hidden"[^>]*>/gi)) {
+    const name = match[0].match(/name="([^"]+)"/)?.[1];
+    const value = match[0].match(/value="([^"]*)"/)?.[1];
+    if (name && value !== undefined) {
+      fields[name] = value.replace(/&amp;/g, '&');
+    }
+  }
+  return fields;
+}
+
+/**
+ * Google Drive 文件 ID
+ */
+function getGoogleDriveFileId(url: URL): string | null {
+  const pathMatch = url.pathname.match(/\/file\/d\/([\w-]+)/);
+  if (pathMatch) {
+    return pathMatch[1];
+  }
+  return url.searchParams.get('id');
+}
+
+/**
+ * Google Drive 解析器
+ *
+ * 大文件会先返回病毒扫描警告页，需要带上页面中的 confirm 和 uuid 参数再请求
+ */
+const googleDriveResolver: LinkResolver = {
+  name: 'Google Drive',
+
+  matches(url) {
+    return /(^|\.)(drive|docs)\.google\.com$/.test(url.hostname);
+  },
+
+  async resolve(url, fetchFn) {
+    if (url.pathname.includes('/folders/')) {
+      throw new UnsupportedLinkError(url.toString(), 'Google Drive folder');
+    }
+
+    const fileId = getGoogleDriveFileId(url);
+    if (!fileId) {
+      throw new UnsupportedLinkError(url.toString(), 'no Google Drive file id');
+    }
+
+    const directUrl = `https://drive.usercontent.google.com/download?id=${fileId}&export=download`;
+    const response = await fetchFn(directUrl);
+    if (!response.headers.get('content-type')?.includes('text/html')) {
+      // 已经是文件内容，丢弃本次响应，由下载器重新请求
+      await response.body?.cancel();
+      return directUrl;
+    }
+
+    const html = await response.text();
+    const fields = parseHiddenInputs(html);
+    if (!fields.confirm) {
+      // 没有确认表单，通常是文件不存在或没有公开权限
+      throw new UnsupportedLinkError(url.toString(), 'Google Drive file is not shared publicly');
+    }
+
+    const action =
+      html.match(/<form[^>]+id="download-form"[^>]+action="([^"]+)"/)?.[1] ??
+      'https://drive.usercontent.google.com/download';
+    const confirmUrl = new URL(action.replace(/&amp;/g, '&'));
+    for (const [name, value] of Object.entries(fields)) {
+      confirmUrl.searchParams.set(name, value);
+    }
+    return confirmUrl.toString();
+  },
+};
+
+/**
+ * OneDrive 个人版解析器
+ *
+ * 使用分享 API：`u!` + 分享链接的 base64url 编码，`/root/content` 会重定向到文件
+ */
+const oneDriveResolver: LinkResolver = {
+  name: 'OneDrive',
+
+  matches(url) {
+    return url.hostname === '1drv.ms' || url.hostname.endsWith('onedrive.live.com');
+  },
+
+  async resolve(url) {
+    const bytes = new TextEncoder().encode(url.toString());
+    const base64 = btoa(String.fromCharCode(...bytes))
+      .replace(/=+$/, '')
+      .replace(/\//g, '_')
+      .replace(/\+/g, '-');
+    return `https://api.onedrive.com/v1.0/shares/u!${base64}/root/content`;
+  },
+};
+
+/**
+ * SharePoint / OneDrive 商业版解析器（添加 download=1 参数）
+ */
+const sharePointResolver: LinkResolver = {
+  name: 'SharePoint',
+
+  matches(url) {
+    return url.hostname.endsWith('.sharepoint.com');
+  },
+
+  async resolve(url) {
+    const directUrl = new URL(url);
+    directUrl.searchParams.set('download', '1');
+    return directUrl.toString();
+  },
+};
+
+/**
+ * Dropbox 解析器（设置 dl=1）
+ */
+const dropboxResolver: LinkResolver = {
+  name: 'Dropbox',
+
+  matches(url) {
+    return /(^|\.)dropbox\.com$/.test(url.hostname);
+  },
+
+  async resolve(url) {
+    const directUrl = new URL(url);
+    directUrl.searchParams.set('dl', '1');
+    return directUrl.toString();
+  },
+};
+
+/**
+ * 需要在浏览器中操作（验证码、客户端解密等），无法自动下载的站点
+ */
+const UNSUPPORTED_HOSTS: [RegExp, string][] = [
+  [/(^|\.)mega\.(nz|co\.nz|io)$/, 'MEGA requires client-side decryption'],
+  [/(^|\.)mediafire\.com$/, 'MediaFire requires a browser'],
+  [/(^|\.)axfc\.net$/, 'axfc requires a browser'],
+  [/(^|\.)uploader\.jp$/, 'uploader.jp requires a password or browser'],
+];
+
+/**
+ * 下载链接解析注册表
+ */
+export class LinkResolverRegistry {
+  private static resolvers: LinkResolver[] = [
+    googleDriveResolver,
+    oneDriveResolver,
+    sharePointResolver,
+    dropboxResolver,
+  ];
+
+  /**
+   * 注册解析器（优先于已注册的解析器匹配）
+   */
+  static register(resolver: LinkResolver): void {
+    this.resolvers.unshift(resolver);
+  }
+
+  /**
+   * 获取处理该链接的解析器
+   */
+  static find(url: URL): LinkResolver | undefined {
+    return this.resolvers.find((resolver) => resolver.matches(url));
+  }
+
+  /**
+   * 将链接解析为直接下载链接
+   *
+   * @throws {UnsupportedLinkError} 站点不支持自动下载时
+   */
+  static async resolve(rawUrl: string, fetchFn: ResolverFetch): Promise<string> {
+    const url = new URL(rawUrl);
+
+    const resolver = this.find(url);
+    if (resolver) {
+      const resolved = await resolver.resolve(url, fetchFn);
+      if (resolved !== rawUrl) {
+        console.log(`Resolved ${resolver.name} link: ${rawUrl} -> ${resolved}`);
+      }
+      return resolved;
+    }
+
+    for (const [pattern, reason] of UNSUPPORTED_HOSTS) {
+      if (pattern.test(url.hostname)) {
+        throw new UnsupportedLinkError(rawUrl, reason);
+      }
+    }
+
+    return rawUrl;
+  }
+}
@@ -0,0 +1,190 @@
+/**
+ * 音源采样率和位深统计
+ *
+ * 部分播放器对超过 48kHz 或 24/32 位的音源重采样效果很差（爆音、延迟）。
+ * 这里对每个作品抽样若干个音源用 ffprobe 检查，列出含有高采样率或高位深音源的作品，
+ * 这些作品也会作为转换候选（重新编码为 44.1kHz / 16 位）
+ */
+
+import { readDir } from '@tauri-apps/plugin-fs';
+import { AUDIO_FILE_EXTS, getFileExtension } from '../bms/scanner';
+import { ConcurrencyPool } from '../media/concurrency';
+import { MediaProbe } from '../media/probe';
+import { ProcessRunner } from '../media/processRunner';
+
+/**
+ * 同时运行的 ffprobe 数
+ */
+const PROBE_CONCURRENCY = 8;
+
+/**
+ * 超出限制的音源
+ */
+export interface HiresAudioFile {
+  file: string;
+  codec: string;
+  sampleRate: number;
+  bitDepth?: number;
+}
+
+/**
+ * 单个作品的音源统计
+ */
+export interface WorkAudioReport {
+  workDir: string;
+  /** 作品中的音源数 */
+  audioCount: number;
+  /** 抽样检查的音源数 */
+  sampled: number;
+  hiresFiles: HiresAudioFile[];
+  maxSampleRate: number;
+  maxBitDepth?: number;
+}
+
+/**
+ * 根目录的音源统计
+ */
+export interface AudioReport {
+  rootDir: string;
+  maxSampleRate: number;
+  maxBitDepth: number;
+  /** 检查的作品数 */
+  workCount: number;
+  /** 含有超出限制音源的作品 */
+  works: WorkAudioReport[];
+}
+
+/**
+ * 从列表中均匀抽取指定数量的元素
+ */
+function pickSamples<T>(items: T[], count: number): T[] {
+  if (items.length <= count) {
+    return items;
+  }
+  const step = items.length / count;
+  return Array.from({ length: count }, (_, i) => items[Math.floor(i * step)]);
+}
+
+/**
+ * 抽样检查作品中的音源
+ *
+ * @param files - 作品中的文件名（只检查音频文件）
+ * @param samples - 抽样数
+ * @param maxSampleRate - 采样率上限（Hz）
+ * @param maxBitDepth - 位深上限
+ */
+export async function probeWorkAudio(
+  workDir: string,
+  files: string[],
+  samples: number,
+  maxSampleRate: number,
+  maxBitDepth: number,
+  pool: ConcurrencyPool<void> = new ConcurrencyPool<void>(PROBE_CONCURRENCY)
+): Promise<WorkAudioReport> {
+  const audioFiles = files
+    .filter((name) => (AUDIO_FILE_EXTS as readonly string[]).includes(getFileExtension(name)))
+    .sort();
+  const sampled = pickSamples(audioFiles, samples);
+  const report: WorkAudioReport = {
+    workDir,
+    audioCount: audioFiles.length,
+    sampled: sampled.length,
+    hiresFiles: [],
+    maxSampleRate: 0,
+  };
+
+  await Promise.all(
+    sampled.map((file) =>
+      pool.add(async () => {
+        const audio = (await MediaProbe.getMediaInfo(`${workDir}/${file}`))?.audio;
+        if (!audio) {
+          return;
+        }
+        report.maxSampleRate = Math.max(report.maxSampleRate, audio.sampleRate);
+        if (audio.bitDepth !== undefined) {
+          report.maxBitDepth = Math.max(report.maxBitDepth ?? 0, audio.bitDepth);
+        }
+        if (audio.sampleRate > maxSampleRate || (audio.bitDepth ?? 0) > maxBitDepth) {
+          const { codec, sampleRate, bitDepth } = audio;
+          report.hiresFiles.push({ file, codec, sampleRate, bitDepth });
+        }
+      })
+    )
+  );
+
+  report.hiresFiles.sort((a, b) => a.file.localeCompare(b.file));
+  return report;
+}
+
+/**
+ * 检查根目录中音源的采样率和位深
+ *
+ * @command
+ * @category bmsfolder
+ * @dangerous false
+ * @name 音源采样率和位深检查
+ * @description 对每个作品抽样若干个音源用 ffprobe 检查，列出含有超过指定采样率或位深（如 96kHz、24 位）音源的作品，部分播放器对这类音源重采样效果很差
+ * @frontend true
+ *
+ * @param {string} rootDir - 根目录路径
+ * @param {number} samplesPerWork - 每个作品抽样检查的音源数
+ * @param {number} maxSampleRate - 采样率上限（Hz）
+ * @param {number} maxBitDepth - 位深上限
+ *
+ * @returns {Promise<AudioReport>} 含有超出限制音源的作品
+ */
+export async function audioReport(
+  rootDir: string,
+  samplesPerWork: number = 8,
+  maxSampleRate: number = 48000,
+  maxBitDepth: number = 16
+): Promise<AudioReport> {
+  if (!(await ProcessRunner.checkExecutable('ffprobe'))) {
+    throw new Error('Executable not found: ffprobe');
+  }
+
+  const workDirs = (await readDir(rootDir))
+    .filter((entry) => entry.isDirectory && entry.name)
+    .map((entry) => `${rootDir}/${entry.name}`)
+    .sort((a, b) => a.localeCompare(b));
+
+  const pool = new ConcurrencyPool<void>(PROBE_CONCURRENCY);
+  const report: AudioReport = {
+    rootDir,
+    maxSampleRate,
+    maxBitDepth,
+    workCount: workDirs.length,
+    works: [],
+  };
+  for (const workDir of workDirs) {
+    try {
+      const files = (await readDir(workDir))
+        .filter((entry) => entry.isFile && entry.name)
+        .map((entry) => entry.name);
+      const work = await probeWorkAudio(
+        workDir,
+        files,
+        samplesPerWork,
+        maxSampleRate,
+        maxBitDepth,
+        pool
+      );
+      if (work.hiresFiles.length > 0) {
+        report.works.push(work);
+        const bitDepth = work.maxBitDepth ? `/${work.maxBitDepth}bit` : '';
+        console.log(
+          `${workDir}: ${work.hiresFiles.length}/${work.sampled} sampled sounds exceed limits ` +
+            `(max ${work.maxSampleRate}Hz${bitDepth})`
+        );
+      }
+    } catch (error) {
+      console.warn(` !_! Failed to read work: ${workDir}`, error);
+    }
+  }
+
+  console.log(
+    `${report.works.length} of ${report.workCount} works contain sounds over ` +
+      `${maxSampleRate}Hz or ${maxBitDepth}bit`
+  );
+  return report;
+}
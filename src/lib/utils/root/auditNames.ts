@@ -0,0 +1,162 @@
+/**
+ * 发布前的文件夹名检查
+ *
+ * 在 Linux 上整理的包发布后，Windows 用户解压时会因为文件夹名失败：非法字符、
+ * 结尾的点或空格、保留名称、超过 255 字节的名称，以及只有大小写不同的同名文件夹。
+ * 这里只检查并给出修改方案（使用 getValidFsName 替换非法字符），不修改文件夹
+ */
+
+import { readDir } from '@tauri-apps/plugin-fs';
+import { getValidFsName } from '../fs/name';
+
+/**
+ * 文件夹名问题
+ */
+export type FolderNameIssue =
+  | 'invalid_chars'
+  | 'trailing_dot_or_space'
+  | 'reserved_name'
+  | 'too_long'
+  | 'mixed_width_brackets'
+  | 'case_collision';
+
+/**
+ * 单个文件夹的修改方案
+ */
+export interface FolderNameFix {
+  from: string;
+  to: string;
+  issues: FolderNameIssue[];
+}
+
+/** 大多数文件系统的文件名长度上限（UTF-8 字节） */
+const MAX_NAME_BYTES = 255;
+
+// eslint-disable-next-line no-control-regex
+const INVALID_CHARS_PATTERN = /[<>:"/\\|?*\x00-\x1F]/;
+const RESERVED_NAME_PATTERN = /^(CON|PRN|AUX|NUL|COM[1-9]|LPT[1-9])(\..*)?$/i;
+
+const BRACKET_PAIRS: Record<string, string> = {
+  '[': ']',
+  '［': '］',
+  '(': ')',
+  '（': '）',
+};
+const CLOSING_BRACKETS = new Set(Object.values(BRACKET_PAIRS));
+const HALF_WIDTH_BRACKETS: Record<string, string> = { '［': '[', '］': ']', '（': '(', '）': ')' };
+
+const encoder = new TextEncoder();
+
+/**
+ * 括号是否混用全角和半角（如 "Title ［Artist]"）
+ */
+function hasMixedWidthBrackets(name: string): boolean {
+  const stack: string[] = [];
+  for (const char of name) {
+    if (char in BRACKET_PAIRS) {
+      stack.push(char);
+    } else if (CLOSING_BRACKETS.has(char)) {
+      const open = stack.pop();
+      if (open !== undefined && BRACKET_PAIRS[open] !== char) {
+        return true;
+      }
+    }
+  }
+  return false;
+}
+
+/**
+ * 按 UTF-8 字节数截断，不切断字符
+ */
+function truncateToBytes(name: string, maxBytes: number): string {
+  let result = '';
+  let bytes = 0;
+  for (const char of name) {
+    const charBytes = encoder.encode(char).length;
+    if (bytes + charBytes > maxBytes) {
+      break;
+    }
+    result += char;
+    bytes += charBytes;
+  }
+  return result;
+}
+
+/**
+ * 检查单个文件夹名并计算修改后的名称
+ */
+function fixFolderName(name: string): FolderNameFix {
+  const issues: FolderNameIssue[] = [];
+  let fixed = name;
+
+  if (INVALID_CHARS_PATTERN.test(fixed)) {
+    issues.push('invalid_chars');
+    // eslint-disable-next-line no-control-regex
+    fixed = getValidFsName(fixed).replace(/[\x00-\x1F]/g, '');
+  }
+  if (hasMixedWidthBrackets(fixed)) {
+    issues.push('mixed_width_brackets');
+    fixed = fixed.replace(/[［］（）]/g, (char) => HALF_WIDTH_BRACKETS[char]);
+  }
+  if (encoder.encode(fixed).length > MAX_NAME_BYTES) {
+    issues.push('too_long');
+    fixed = truncateToBytes(fixed, MAX_NAME_BYTES);
+  }
+  if (/[. ]$/.test(fixed)) {
+    issues.push('trailing_dot_or_space');
+    fixed = fixed.replace(/[. ]+$/, '');
+  }
+  if (RESERVED_NAME_PATTERN.test(fixed)) {
+    issues.push('reserved_name');
+    // 保留名称只看第一个点之前的部分（"CON.txt" 同样是保留名称），在这部分后追加下划线
+    while (RESERVED_NAME_PATTERN.test(fixed)) {
+      fixed = fixed.replace(/^[^.]*/, (base) => `${base}_`);
+    }
+  }
+
+  return { from: name, to: fixed || '_', issues };
+}
+
+/**
+ * 检查根目录下的文件夹名能否在其他系统上使用
+ *
+ * @command
+ * @category bmsfolder
+ * @dangerous false
+ * @name 检查文件夹名
+ * @description 检查非法字符、结尾的点或空格、保留名称、超过 255 字节、全半角括号混用和只有大小写不同的文件夹，给出修改方案（不修改文件夹）
+ * @frontend true
+ *
+ * @param {string} rootDir - 根目录路径
+ *
+ * @returns {Promise<FolderNameFix[]>} 需要修改的文件夹及修改后的名称
+ */
+export async function auditNames(rootDir: string): Promise<FolderNameFix[]> {
+  const names = (await readDir(rootDir))
+    .filter((entry) => entry.isDirectory && entry.name)
+    .map((entry) => entry.name)
+    .sort((a, b) => a.localeCompare(b));
+
+  const fixes = names.map(fixFolderName);
+
+  // Windows 和 macOS 不区分大小写，修改后名称相同时追加编号（不需要修改的文件夹优先保留原名）
+  const taken = new Set<string>();
+  for (const fix of [...fixes].sort((a, b) => a.issues.length - b.issues.length)) {
+    let target = fix.to;
+    for (let count = 2; taken.has(target.toLowerCase()); count++) {
+      target = `${fix.to} (${count})`;
+    }
+    if (target !== fix.to) {
+      fix.issues.push('case_collision');
+      fix.to = target;
+    }
+    taken.add(target.toLowerCase());
+  }
+
+  const plan = fixes.filter((fix) => fix.issues.length > 0);
+  for (const fix of plan) {
+    console.log(`${fix.from} -> ${fix.to} (${fix.issues.join(', ')})`);
+  }
+  console.log(`Checked ${names.length} folders, ${plan.length} need renaming.`);
+  return plan;
+}
@@ -2,13 +2,34 @@
  * 根目录批量操作工具
  */
 
-import { readDir, rename } from '@tauri-apps/plugin-fs';
-import { BmsFolderSetNameType, setNameByBms, undoSetNameByBms } from '../work/rename';
+import { exists, readDir, remove, rename } from '@tauri-apps/plugin-fs';
+import { listSplitBuckets } from '../bigpack/split';
+import { cleanWorkArtistName, getDirBmsOutput } from '../bms/scanner';
+import { LibraryIndex } from '../library/workIndex';
+import { getAppDataPathForDir, readJsonFile, writeJsonFile } from '../storage';
+import { BmsFolderSetNameType, planSetName, setNameByInfo, undoSetNameByBms } from '../work/rename';
+import { DryRunPlanner } from '../fs/dryRunPlan';
+import type { DryRunPlan } from '../fs/dryRunPlan';
 import { ReplacePreset } from '../fs/moving';
+import { RenameJournal } from './journal';
+
+/**
+ * 批量重命名的进度记录
+ */
+interface SetNameProgress {
+  rootDir: string;
+  setType: BmsFolderSetNameType;
+  startedAt: string;
+  /** 已完成的目录（原目录名和重命名后的目录名） */
+  completed: string[];
+}
 
 /**
  * 递归设置目录名（根目录版本）
  *
+ * 每处理完一个目录就记录进度，中途失败时保留进度文件；
+ * 使用 resume 重新运行时跳过已完成的目录（包括已重命名的目录）
+ *
  * @command
  * @category bmsfolder
  * @dangerous true
@@ -20,15 +41,55 @@ import { ReplacePreset } from '../fs/moving';
  * @param {BmsFolderSetNameType} setType - 命名方式
  * @param {boolean} dryRun - 模拟运行（不实际执行）
  * @param {ReplacePreset} replacePreset - 文件替换策略
+ * @param {boolean} resume - 从上次中断的位置继续
+ * @param {boolean} includeSplitBuckets - 同时处理已拆分的目录（如 "ABCD"、"Root [ABCD]"）中的作品
  *
- * @returns {Promise<void>}
+ * @returns {Promise<DryRunPlan | null>} 模拟运行时返回计划的操作（实际执行时为 null）
  */
 export async function rootSetNameByBms(
   rootDir: string,
   setType: BmsFolderSetNameType,
   dryRun: boolean,
-  replacePreset: ReplacePreset
-): Promise<void> {
+  replacePreset: ReplacePreset,
+  resume: boolean = false,
+  includeSplitBuckets: boolean = false
+): Promise<DryRunPlan | null> {
+  const planner = new DryRunPlanner(`Set names in ${rootDir}`);
+  const buckets = includeSplitBuckets ? await listSplitBuckets(rootDir) : [];
+  for (const bucketDir of buckets) {
+    console.log(`Split bucket: ${bucketDir}`);
+    planner.extend(await rootSetNameByBms(bucketDir, setType, dryRun, replacePreset, resume));
+  }
+
+  const progressPath = await getAppDataPathForDir('resume/root-set-name', rootDir);
+  const previous = resume ? await readJsonFile<SetNameProgress | null>(progressPath, null) : null;
+  if (resume && !previous) {
+    console.log(`No interrupted run found for ${rootDir}, starting from the beginning.`);
+  }
+  if (previous && previous.setType !== setType) {
+    throw new Error(`上次中断的运行使用了不同的命名方式（${previous.setType}），无法继续`);
+  }
+
+  const progress: SetNameProgress = previous ?? {
+    rootDir,
+    setType,
+    startedAt: new Date().toISOString(),
+    completed: [],
+  };
+  const completed = new Set(progress.completed);
+  if (completed.size > 0) {
+    console.log(`Resuming: ${completed.size} folders already processed`);
+  }
+
+  const journal = await RenameJournal.open(rootDir);
+  const journalRun = journal.startRun(setType, progress.startedAt);
+
+  // 通过作品索引并行读取作品信息（未变化的作品直接使用缓存），重命名按顺序执行
+  await LibraryIndex.update(rootDir);
+  const works = new Map(
+    ((await LibraryIndex.get(rootDir))?.works ?? []).map((work) => [work.dirName, work])
+  );
+
   const entries = await readDir(rootDir);
 
   for (const entry of entries) {
@@ -37,18 +98,67 @@ export async function rootSetNameByBms(
       continue;
     }
 
-    if (!entry.name) {
+    if (!entry.name || completed.has(entry.name)) {
       continue;
     }
 
     const workDir = `${rootDir}/${entry.name}`;
-    await setNameByBms(workDir, setType, dryRun, replacePreset, false);
+    if (buckets.includes(workDir)) {
+      continue;
+    }
+    const work = works.get(entry.name);
+    let title = work?.title;
+    let artist = work?.artist && cleanWorkArtistName(work.artist);
+    if (!work) {
+      // 索引中没有的目录（如谱面在子目录中）按原方式读取
+      const bmsInfo = await getDirBmsOutput(workDir);
+      if (!bmsInfo) {
+        console.log(`BMS file not found, skipping: ${workDir}`);
+        continue;
+      }
+      title = bmsInfo.bms.musicInfo.title;
+      artist = bmsInfo.bms.musicInfo.artist;
+    }
+
+    const outcome = await setNameByInfo(
+      workDir,
+      title,
+      artist,
+      setType,
+      dryRun,
+      replacePreset,
+      false
+    );
+
+    if (dryRun) {
+      await planSetName(planner, workDir, outcome);
+    } else {
+      if (outcome.dirName !== entry.name) {
+        journalRun.renames.push({ from: entry.name, to: outcome.dirName, merged: outcome.merged });
+        await journal.save();
+      }
+      completed.add(entry.name);
+      completed.add(outcome.dirName);
+      progress.completed = [...completed];
+      await writeJsonFile(progressPath, progress);
+    }
+  }
+
+  if (dryRun) {
+    planner.print();
+    return planner.plan();
   }
+
+  // 全部完成后删除进度文件
+  await remove(progressPath).catch(() => {});
+  return null;
 }
 
 /**
  * 递归撤销目录名设置（根目录版本）
  *
+ * 按最近一次批量重命名的日志精确还原；没有日志时才从目录名推测原名称
+ *
  * @command
  * @category bmsfolder
  * @dangerous true
@@ -59,14 +169,60 @@ export async function rootSetNameByBms(
  * @param {string} rootDir - 根目录路径
  * @param {BmsFolderSetNameType} setType - 命名方式
  * @param {boolean} dryRun - 模拟运行（不实际执行）
+ * @param {boolean} includeSplitBuckets - 同时处理已拆分的目录（如 "ABCD"、"Root [ABCD]"）中的作品
  *
  * @returns {Promise<void>}
  */
 export async function rootUndoSetNameByBms(
   rootDir: string,
   setType: BmsFolderSetNameType,
-  dryRun: boolean
+  dryRun: boolean,
+  includeSplitBuckets: boolean = false
 ): Promise<void> {
+  const buckets = includeSplitBuckets ? await listSplitBuckets(rootDir) : [];
+  for (const bucketDir of buckets) {
+    console.log(`Split bucket: ${bucketDir}`);
+    await rootUndoSetNameByBms(bucketDir, setType, dryRun);
+  }
+
+  // 有重命名日志时按记录精确还原
+  const journal = await RenameJournal.open(rootDir);
+  const run = journal.latestRun(setType);
+  if (run) {
+    console.log(`Undo rename run from ${run.startedAt}: ${run.renames.length} folders`);
+    for (const { from, to, merged } of [...run.renames].reverse()) {
+      const fromPath = `${rootDir}/${from}`;
+      const toPath = `${rootDir}/${to}`;
+      if (merged) {
+        console.warn(` !_! ${from} was merged into existing ${to}, cannot undo exactly. Skipped.`);
+        continue;
+      }
+      if (!(await exists(toPath))) {
+        console.warn(` !_! ${to} no longer exists. Skipped.`);
+        continue;
+      }
+      if (from.toLowerCase() !== to.toLowerCase() && (await exists(fromPath))) {
+        console.warn(` !_! ${from} already exists, cannot restore ${to}. Skipped.`);
+        continue;
+      }
+
+      if (dryRun) {
+        console.log(`[dry-run] Would rename: ${toPath} -> ${fromPath}`);
+      } else {
+        await rename(toPath, fromPath);
+      }
+    }
+
+    if (!dryRun) {
+      journal.removeRun(run);
+      await journal.save();
+    }
+    return;
+  }
+
+  console.warn(
+    ` !_! No rename journal found for ${rootDir}, guessing original names from folder names.`
+  );
   const entries = await readDir(rootDir);
 
   for (const entry of entries) {
@@ -80,6 +236,9 @@ export async function rootUndoSetNameByBms(
     }
 
     const workDir = `${rootDir}/${entry.name}`;
+    if (buckets.includes(workDir)) {
+      continue;
+    }
     await undoSetNameByBms(workDir, setType, dryRun);
   }
 }
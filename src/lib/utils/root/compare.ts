@@ -0,0 +1,255 @@
+/**
+ * 根目录对比工具
+ * 按作品目录名和谱面哈希对比两个根目录，便于合并他人的曲库前预览差异
+ */
+
+import { readDir } from '@tauri-apps/plugin-fs';
+import { getWorkDirChartFiles } from '../bms/scanner';
+import { calculateFileHash } from '../fs/hash';
+import { ConcurrencyPool } from '../media/concurrency';
+
+/**
+ * 两侧都存在但内容不同的作品
+ */
+export interface RootCompareDiff {
+  /** A 侧目录名 */
+  nameA: string;
+  /** B 侧目录名（按谱面哈希匹配时可能与 A 不同） */
+  nameB: string;
+  /** 仅存在于 A 的谱面 */
+  chartsOnlyInA: string[];
+  /** 仅存在于 B 的谱面 */
+  chartsOnlyInB: string[];
+  /** 同名但内容不同的谱面 */
+  chartsChanged: string[];
+}
+
+/**
+ * 根目录对比结果
+ */
+export interface RootCompareResult {
+  rootDirA: string;
+  rootDirB: string;
+  /** 仅存在于 A 的作品目录 */
+  onlyInA: string[];
+  /** 仅存在于 B 的作品目录 */
+  onlyInB: string[];
+  /** 两侧都存在但有差异的作品 */
+  different: RootCompareDiff[];
+  /** 两侧完全一致的作品 */
+  identical: string[];
+  /** 树形文本输出 */
+  tree: string;
+}
+
+/**
+ * 作品目录的谱面哈希表：文件名 -> 哈希
+ */
+export type ChartHashMap = Map<string, string>;
+
+/**
+ * 读取根目录下所有作品目录的谱面哈希
+ */
+export async function collectChartHashes(rootDir: string): Promise<Map<string, ChartHashMap>> {
+  const result = new Map<string, ChartHashMap>();
+  const pool = new ConcurrencyPool<void>(16);
+  const tasks: Promise<void>[] = [];
+
+  const entries = await readDir(rootDir);
+  for (const entry of entries) {
+    if (!entry.isDirectory || !entry.name) {
+      continue;
+    }
+
+    const workName = entry.name;
+    const workDir = `${rootDir}/${workName}`;
+    const hashes: ChartHashMap = new Map();
+    result.set(workName, hashes);
+
+    for (const chartName of await getWorkDirChartFiles(workDir)) {
+      tasks.push(
+        pool.add(async () => {
+          hashes.set(chartName, await calculateFileHash(`${workDir}/${chartName}`));
+        })
+      );
+    }
+  }
+
+  await Promise.all(tasks);
+  return result;
+}
+
+/**
+ * 对比两个作品目录的谱面哈希
+ */
+function diffCharts(
+  nameA: string,
+  nameB: string,
+  chartsA: ChartHashMap,
+  chartsB: ChartHashMap
+): RootCompareDiff | null {
+  const diff: RootCompareDiff = {
+    nameA,
+    nameB,
+    chartsOnlyInA: [],
+    chartsOnlyInB: [],
+    chartsChanged: [],
+  };
+
+  for (const [chart, hash] of chartsA) {
+    const hashB = chartsB.get(chart);
+    if (hashB === undefined) {
+      diff.chartsOnlyInA.push(chart);
+    } else if (hashB !== hash) {
+      diff.chartsChanged.push(chart);
+    }
+  }
+
+  for (const chart of chartsB.keys()) {
+    if (!chartsA.has(chart)) {
+      diff.chartsOnlyInB.push(chart);
+    }
+  }
+
+  const isSame =
+    nameA === nameB &&
+    diff.chartsOnlyInA.length === 0 &&
+    diff.chartsOnlyInB.length === 0 &&
+    diff.chartsChanged.length === 0;
+
+  return isSame ? null : diff;
+}
+
+/**
+ * 生成树形文本输出
+ */
+function formatCompareTree(result: Omit<RootCompareResult, 'tree'>): string {
+  const lines: string[] = [`A: ${result.rootDirA}`, `B: ${result.rootDirB}`];
+
+  const pushGroup = (title: string, items: string[][], isLast: boolean) => {
+    lines.push(`${isLast ? '└──' : '├──'} ${title} (${items.length})`);
+    const indent = isLast ? '    ' : '│   ';
+    items.forEach((item, index) => {
+      const isLastItem = index === items.length - 1;
+      lines.push(`${indent}${isLastItem ? '└──' : '├──'} ${item[0]}`);
+      const childIndent = `${indent}${isLastItem ? '    ' : '│   '}`;
+      item.slice(1).forEach((child, childIndex) => {
+        const isLastChild = childIndex === item.length - 2;
+        lines.push(`${childIndent}${isLastChild ? '└──' : '├──'} ${child}`);
+      });
+    });
+  };
+
+  pushGroup(
+    '仅存在于 A',
+    result.onlyInA.map((name) => [name]),
+    false
+  );
+  pushGroup(
+    '仅存在于 B',
+    result.onlyInB.map((name) => [name]),
+    false
+  );
+  pushGroup(
+    '两侧均存在但有差异',
+    result.different.map((diff) => [
+      diff.nameA === diff.nameB ? diff.nameA : `${diff.nameA} <-> ${diff.nameB}`,
+      ...diff.chartsOnlyInA.map((chart) => `仅 A: ${chart}`),
+      ...diff.chartsOnlyInB.map((chart) => `仅 B: ${chart}`),
+      ...diff.chartsChanged.map((chart) => `内容不同: ${chart}`),
+    ]),
+    true
+  );
+
+  return lines.join('\n');
+}
+
+/**
+ * 对比两个根目录
+ *
+ * 先按作品目录名匹配，未匹配的作品再按谱面哈希匹配（处理目录改名的情况）
+ *
+ * @command
+ * @category bmsfolder
+ * @dangerous false
+ * @name 对比根目录
+ * @description 对比两个根目录，按目录名和谱面哈希分为：仅 A 有、仅 B 有、两边都有但有差异
+ * @frontend true
+ *
+ * @param {string} rootDirA - 根目录 A
+ * @param {string} rootDirB - 根目录 B
+ *
+ * @returns {Promise<RootCompareResult>} 对比结果（含树形文本）
+ */
+export async function compareRoots(rootDirA: string, rootDirB: string): Promise<RootCompareResult> {
+  const [worksA, worksB] = await Promise.all([
+    collectChartHashes(rootDirA),
+    collectChartHashes(rootDirB),
+  ]);
+
+  const onlyInA: string[] = [];
+  const different: RootCompareDiff[] = [];
+  const identical: string[] = [];
+  const unmatchedB = new Set(worksB.keys());
+
+  // 第一轮：按目录名匹配
+  const unmatchedA: string[] = [];
+  for (const [nameA, chartsA] of worksA) {
+    const chartsB = worksB.get(nameA);
+    if (chartsB === undefined) {
+      unmatchedA.push(nameA);
+      continue;
+    }
+
+    unmatchedB.delete(nameA);
+    const diff = diffCharts(nameA, nameA, chartsA, chartsB);
+    if (diff) {
+      different.push(diff);
+    } else {
+      identical.push(nameA);
+    }
+  }
+
+  // 第二轮：按谱面哈希匹配剩余作品
+  const hashOwnerB = new Map<string, string>();
+  for (const nameB of unmatchedB) {
+    for (const hash of worksB.get(nameB)!.values()) {
+      hashOwnerB.set(hash, nameB);
+    }
+  }
+
+  for (const nameA of unmatchedA) {
+    const chartsA = worksA.get(nameA)!;
+    let matchedB: string | undefined;
+    for (const hash of chartsA.values()) {
+      const owner = hashOwnerB.get(hash);
+      if (owner !== undefined && unmatchedB.has(owner)) {
+        matchedB = owner;
+        break;
+      }
+    }
+
+    if (matchedB === undefined) {
+      onlyInA.push(nameA);
+      continue;
+    }
+
+    unmatchedB.delete(matchedB);
+    different.push(diffCharts(nameA, matchedB, chartsA, worksB.get(matchedB)!)!);
+  }
+
+  const sortByName = (a: string, b: string) => a.localeCompare(b);
+  const partial = {
+    rootDirA,
+    rootDirB,
+    onlyInA: onlyInA.sort(sortByName),
+    onlyInB: Array.from(unmatchedB).sort(sortByName),
+    different: different.sort((a, b) => sortByName(a.nameA, b.nameA)),
+    identical: identical.sort(sortByName),
+  };
+
+  const tree = formatCompareTree(partial);
+  console.log(tree);
+
+  return { ...partial, tree };
+}
@@ -0,0 +1,267 @@
+/**
+ * 作品媒体格式规范检查
+ *
+ * 按目标规范（如 beatoraja HQ 只保留 FLAC 和 MP4，LR2 LQ 只保留 OGG 和 MPEG/WMV/AVI）
+ * 检查根目录中每个作品的音视频格式，列出不符合的文件并按需修正：
+ * 已有同名的规范格式文件时删除多余的文件，否则调用对应的转换器转换
+ */
+
+import { readDir, remove, stat } from '@tauri-apps/plugin-fs';
+import { AUDIO_FILE_EXTS, VIDEO_FILE_EXTS } from '../bms/scanner';
+import { getFileExtension, getFileStem } from '../fs/path';
+import { AudioConverter } from '../media/audio';
+import { AUDIO_PRESETS } from '../media/presets';
+import { AudioPreset, VideoPreset } from '../media/types';
+import { VideoConverter } from '../media/video';
+import { skipIfPinned } from '../work/pin';
+import { MediaProfile } from '../../types/enums';
+
+/**
+ * 规范中某类媒体允许的格式及转换方式
+ */
+interface MediaRule {
+  /** 允许的扩展名 */
+  allowed: string[];
+  /** 可以转换为规范格式的扩展名 */
+  convertFrom: string[];
+}
+
+interface MediaProfileRule {
+  audio: MediaRule & { presets: AudioPreset[] };
+  video: MediaRule & { presets: VideoPreset[] };
+}
+
+/**
+ * 各规范的定义
+ *
+ * 有损格式之间不互相转换（OGG 转 FLAC 不会提高音质，MP3 转 OGG 会再次损失音质），这些文件只报告
+ */
+const MEDIA_PROFILES: Record<MediaProfile, MediaProfileRule> = {
+  [MediaProfile.OrajaHQ]: {
+    audio: {
+      allowed: ['flac'],
+      convertFrom: ['wav', 'ape'],
+      presets: [AudioPreset.FLAC, AudioPreset.FLAC_FFMPEG],
+    },
+    video: { allowed: ['mp4'], convertFrom: [], presets: [] },
+  },
+  [MediaProfile.Lr2LQ]: {
+    audio: {
+      allowed: ['ogg'],
+      convertFrom: ['flac', 'wav', 'ape'],
+      presets: [AudioPreset.OGG_Q10, AudioPreset.OGG_FFMPEG],
+    },
+    video: {
+      allowed: ['mpg', 'mpeg', 'wmv', 'avi'],
+      convertFrom: ['mp4', 'webm', 'mkv'],
+      presets: [VideoPreset.MPEG1VIDEO_512X512, VideoPreset.WMV2_512X512, VideoPreset.AVI_512X512],
+    },
+  },
+};
+
+/**
+ * 单个作品中不符合规范的文件
+ */
+export interface ProfileDeviation {
+  workDir: string;
+  /** 已有同名规范格式文件，需要删除的文件 */
+  remove: string[];
+  /** 需要转换的文件 */
+  convert: string[];
+  /** 无法自动修正的文件（如 LR2 LQ 中的 MP3） */
+  unfixable: string[];
+  /** 转换失败（模拟运行时为 false） */
+  failed: boolean;
+}
+
+/**
+ * 规范检查报告
+ */
+export interface ProfileReport {
+  profile: MediaProfile;
+  dryRun: boolean;
+  /** 检查的作品数 */
+  checked: number;
+  /** 不符合规范的作品 */
+  deviations: ProfileDeviation[];
+  removed: number;
+  converted: number;
+  unfixable: number;
+}
+
+/**
+ * 同名文件是否存在且不为空
+ */
+async function isNonEmptyFile(path: string): Promise<boolean> {
+  try {
+    return (await stat(path)).size > 0;
+  } catch {
+    return false;
+  }
+}
+
+/**
+ * 检查单个作品
+ */
+async function checkWork(workDir: string, rule: MediaProfileRule): Promise<ProfileDeviation> {
+  const deviation: ProfileDeviation = {
+    workDir,
+    remove: [],
+    convert: [],
+    unfixable: [],
+    failed: false,
+  };
+  const files = (await readDir(workDir)).filter((entry) => entry.isFile).map((e) => e.name);
+  const byStem = new Map<string, string[]>();
+  for (const name of files) {
+    const stem = getFileStem(name).toLowerCase();
+    byStem.set(stem, [...(byStem.get(stem) ?? []), name]);
+  }
+
+  for (const name of files) {
+    const ext = getFileExtension(name);
+    const mediaRule = (AUDIO_FILE_EXTS as readonly string[]).includes(ext)
+      ? rule.audio
+      : (VIDEO_FILE_EXTS as readonly string[]).includes(ext)
+        ? rule.video
+        : null;
+    if (!mediaRule || mediaRule.allowed.includes(ext)) {
+      continue;
+    }
+
+    const replacement = byStem
+      .get(getFileStem(name).toLowerCase())
+      ?.find((other) => mediaRule.allowed.includes(getFileExtension(other)));
+    if (replacement && (await isNonEmptyFile(`${workDir}/${replacement}`))) {
+      deviation.remove.push(name);
+    } else if (mediaRule.convertFrom.includes(ext)) {
+      deviation.convert.push(name);
+    } else {
+      deviation.unfixable.push(name);
+    }
+  }
+
+  return deviation;
+}
+
+/**
+ * 修正单个作品：先删除多余的文件，再转换剩下的文件
+ */
+async function fixWork(deviation: ProfileDeviation, rule: MediaProfileRule): Promise<void> {
+  const { workDir } = deviation;
+  for (const name of deviation.remove) {
+    try {
+      await remove(`${workDir}/${name}`);
+    } catch (error) {
+      console.error(`Failed to remove file: ${workDir}/${name}`, error);
+    }
+  }
+
+  const convertExts = new Set(deviation.convert.map(getFileExtension));
+  const audioExts = rule.audio.convertFrom.filter((ext) => convertExts.has(ext));
+  const videoExts = rule.video.convertFrom.filter((ext) => convertExts.has(ext));
+
+  if (audioExts.length > 0) {
+    const success = await AudioConverter.convertInDirectory(
+      workDir,
+      audioExts,
+      rule.audio.presets.map((name) => AUDIO_PRESETS[name]),
+      true,
+      false,
+      true,
+      true
+    );
+    deviation.failed ||= !success;
+  }
+  if (videoExts.length > 0) {
+    const success = await VideoConverter.convertInDirectory(
+      workDir,
+      videoExts,
+      rule.video.presets,
+      true,
+      true,
+      true
+    );
+    deviation.failed ||= !success;
+  }
+}
+
+/**
+ * 按媒体格式规范检查并修正根目录中的作品
+ *
+ * @command
+ * @category bmsfolder
+ * @dangerous true
+ * @name 按规范整理媒体格式
+ * @description 检查每个作品的音视频格式是否符合目标规范（beatoraja HQ / LR2 LQ），删除已有规范格式的多余文件并转换其余文件；无法转换的文件只报告
+ * @frontend true
+ *
+ * @param {string} rootDir - 根目录路径
+ * @param {MediaProfile} profile - 目标规范
+ * @param {boolean} dryRun - 模拟运行（只报告不符合规范的文件）
+ *
+ * @returns {Promise<ProfileReport>} 不符合规范的作品及修正结果
+ */
+export async function enforceProfile(
+  rootDir: string,
+  profile: MediaProfile,
+  dryRun: boolean
+): Promise<ProfileReport> {
+  const rule = MEDIA_PROFILES[profile];
+  if (!rule) {
+    throw new Error(`未知的媒体格式规范：${profile}`);
+  }
+
+  const report: ProfileReport = {
+    profile,
+    dryRun,
+    checked: 0,
+    deviations: [],
+    removed: 0,
+    converted: 0,
+    unfixable: 0,
+  };
+
+  for (const entry of await readDir(rootDir)) {
+    if (!entry.isDirectory || !entry.name || (await skipIfPinned(`${rootDir}/${entry.name}`))) {
+      continue;
+    }
+    report.checked++;
+
+    const deviation = await checkWork(`${rootDir}/${entry.name}`, rule);
+    const { remove: toRemove, convert, unfixable } = deviation;
+    if (toRemove.length + convert.length + unfixable.length === 0) {
+      continue;
+    }
+    report.deviations.push(deviation);
+    report.unfixable += unfixable.length;
+
+    const prefix = dryRun ? '[dry-run] Would ' : '';
+    console.log(`${entry.name}:`);
+    if (toRemove.length > 0) {
+      console.log(`  ${prefix}remove ${toRemove.join(', ')}`);
+    }
+    if (convert.length > 0) {
+      console.log(`  ${prefix}convert ${convert.join(', ')}`);
+    }
+    if (unfixable.length > 0) {
+      console.warn(`   !_! Cannot fix: ${unfixable.join(', ')}`);
+    }
+
+    if (!dryRun) {
+      await fixWork(deviation, rule);
+      report.removed += toRemove.length;
+      report.converted += convert.length;
+      if (deviation.failed) {
+        console.error(`Errors occurred while fixing ${entry.name}`);
+      }
+    }
+  }
+
+  console.log(
+    `${dryRun ? '[dry-run] ' : ''}${report.deviations.length} of ${report.checked} works ` +
+      `deviate from ${profile}: removed ${report.removed}, converted ${report.converted}, ` +
+      `${report.unfixable} files cannot be fixed`
+  );
+  return report;
+}
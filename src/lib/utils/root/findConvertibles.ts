@@ -0,0 +1,216 @@
+/**
+ * 转换候选查找
+ *
+ * 统计根目录中各扩展名的文件数量和大小，并找出值得转换的作品：
+ * 仍有 WAV 的作品（可转为 FLAC 制作 HQ 版）、只有 FLAC 没有 OGG 的作品（可制作 LQ 版），
+ * 以及超过指定大小的 MP4（可缩小 BGA 分辨率）。按估计可节省的空间排序，便于决定先转换哪些作品。
+ * 指定抽样数时还会用 ffprobe 抽查音源，列出含有高采样率或高位深音源的作品（可重新编码为 44.1kHz / 16 位）
+ */
+
+import { readDir, stat } from '@tauri-apps/plugin-fs';
+import { getFileExtension } from '../bms/scanner';
+import type { SizeBucket } from '../fs/sizeTally';
+import { formatSize } from '../fs/sizeTally';
+import { ConcurrencyPool } from '../media/concurrency';
+import { probeWorkAudio } from './audioReport';
+
+/**
+ * 转换候选类型
+ */
+export type ConvertibleKind = 'wav_to_flac' | 'flac_to_ogg' | 'large_mp4' | 'hires_audio';
+
+/**
+ * 单个作品中的一类转换候选
+ */
+export interface ConvertibleCandidate {
+  workDir: string;
+  kind: ConvertibleKind;
+  /** 涉及的文件 */
+  files: string[];
+  /** 涉及文件的总大小（字节） */
+  size: number;
+  /** 估计可节省的空间（字节） */
+  estimatedSavings: number;
+}
+
+/**
+ * 查找结果
+ */
+export interface ConvertiblesReport {
+  /** 全根目录的扩展名统计 */
+  extensions: Record<string, SizeBucket>;
+  /** 按估计节省空间降序排列的候选 */
+  candidates: ConvertibleCandidate[];
+  /** 各类候选的估计节省空间 */
+  savingsByKind: Record<ConvertibleKind, number>;
+  totalSavings: number;
+}
+
+/**
+ * 转换后大小与原大小的估计比例
+ *
+ * WAV -> FLAC 通常减少约一半；FLAC -> OGG (q10) 约为原来的三分之一；
+ * 512x512 的 BGA 通常只有原 MP4 的四分之一左右；96kHz / 24 位的无损音源降到 44.1kHz / 16 位约为一半以下
+ * （与 wav_to_flac 的估计可能重叠）
+ */
+const ESTIMATED_SIZE_RATIO: Record<ConvertibleKind, number> = {
+  wav_to_flac: 0.55,
+  flac_to_ogg: 0.35,
+  large_mp4: 0.25,
+  hires_audio: 0.45,
+};
+
+/**
+ * 高采样率 / 高位深检查的上限
+ */
+const HIRES_MAX_SAMPLE_RATE = 48000;
+const HIRES_MAX_BIT_DEPTH = 16;
+
+/**
+ * 无损音源的扩展名
+ */
+const LOSSLESS_AUDIO_EXTS = ['wav', 'flac', 'ape'];
+
+const STAT_CONCURRENCY = 16;
+
+/**
+ * 读取作品目录中的文件大小（不递归）
+ */
+async function readWorkFiles(workDir: string): Promise<{ name: string; size: number }[]> {
+  const entries = (await readDir(workDir)).filter((entry) => entry.isFile && entry.name);
+  return await Promise.all(
+    entries.map(async (entry) => ({
+      name: entry.name,
+      size: (await stat(`${workDir}/${entry.name}`)).size,
+    }))
+  );
+}
+
+function makeCandidate(
+  workDir: string,
+  kind: ConvertibleKind,
+  files: { name: string; size: number }[]
+): ConvertibleCandidate {
+  const size = files.reduce((sum, file) => sum + file.size, 0);
+  return {
+    workDir,
+    kind,
+    files: files.map((file) => file.name),
+    size,
+    estimatedSavings: Math.round(size * (1 - ESTIMATED_SIZE_RATIO[kind])),
+  };
+}
+
+/**
+ * 查找根目录中的转换候选
+ *
+ * @command
+ * @category bmsfolder
+ * @dangerous false
+ * @name 查找转换候选
+ * @description 统计各扩展名的数量和大小，列出仍有 WAV、只有 FLAC 没有 OGG、或有超过指定大小 MP4 的作品，并估计转换可节省的空间
+ * @frontend true
+ *
+ * @param {string} rootDir - 根目录路径
+ * @param {number} mp4SizeLimitMb - MP4 超过该大小（MB）时列为缩小 BGA 的候选
+ * @param {number} audioSamplesPerWork - 每个作品用 ffprobe 抽查的音源数（0 表示不检查采样率和位深）
+ *
+ * @returns {Promise<ConvertiblesReport>} 扩展名统计和转换候选
+ */
+export async function findConvertibles(
+  rootDir: string,
+  mp4SizeLimitMb: number = 50,
+  audioSamplesPerWork: number = 0
+): Promise<ConvertiblesReport> {
+  const report: ConvertiblesReport = {
+    extensions: {},
+    candidates: [],
+    savingsByKind: { wav_to_flac: 0, flac_to_ogg: 0, large_mp4: 0, hires_audio: 0 },
+    totalSavings: 0,
+  };
+  const mp4SizeLimit = mp4SizeLimitMb * 1024 * 1024;
+  const workDirs = (await readDir(rootDir))
+    .filter((entry) => entry.isDirectory && entry.name)
+    .map((entry) => `${rootDir}/${entry.name}`);
+
+  const pool = new ConcurrencyPool<void>(STAT_CONCURRENCY);
+  const probePool = new ConcurrencyPool<void>(STAT_CONCURRENCY / 2);
+  await Promise.all(
+    workDirs.map((workDir) =>
+      pool.add(async () => {
+        let files: { name: string; size: number }[];
+        try {
+          files = await readWorkFiles(workDir);
+        } catch (error) {
+          console.warn(` !_! Failed to read work: ${workDir}`, error);
+          return;
+        }
+
+        for (const file of files) {
+          const ext = getFileExtension(file.name) || '(none)';
+          const bucket = (report.extensions[ext] ??= { count: 0, size: 0 });
+          bucket.count++;
+          bucket.size += file.size;
+        }
+
+        const byExt = (ext: string) => files.filter((file) => getFileExtension(file.name) === ext);
+        const wavs = byExt('wav');
+        const flacs = byExt('flac');
+        const largeMp4s = byExt('mp4').filter((file) => file.size > mp4SizeLimit);
+
+        if (wavs.length > 0) {
+          report.candidates.push(makeCandidate(workDir, 'wav_to_flac', wavs));
+        }
+        // 只有 FLAC（没有 OGG）时才需要为 LQ 版转换
+        if (flacs.length > 0 && byExt('ogg').length === 0) {
+          report.candidates.push(makeCandidate(workDir, 'flac_to_ogg', flacs));
+        }
+        if (largeMp4s.length > 0) {
+          report.candidates.push(makeCandidate(workDir, 'large_mp4', largeMp4s));
+        }
+        if (audioSamplesPerWork > 0) {
+          const audio = await probeWorkAudio(
+            workDir,
+            files.map((file) => file.name),
+            audioSamplesPerWork,
+            HIRES_MAX_SAMPLE_RATE,
+            HIRES_MAX_BIT_DEPTH,
+            probePool
+          );
+          const lossless = files.filter((file) =>
+            LOSSLESS_AUDIO_EXTS.includes(getFileExtension(file.name))
+          );
+          if (audio.hiresFiles.length > 0 && lossless.length > 0) {
+            report.candidates.push(makeCandidate(workDir, 'hires_audio', lossless));
+          }
+        }
+      })
+    )
+  );
+
+  report.candidates.sort((a, b) => b.estimatedSavings - a.estimatedSavings);
+  for (const candidate of report.candidates) {
+    report.savingsByKind[candidate.kind] += candidate.estimatedSavings;
+    report.totalSavings += candidate.estimatedSavings;
+  }
+
+  const extensions = Object.entries(report.extensions).sort((a, b) => b[1].size - a[1].size);
+  for (const [ext, bucket] of extensions) {
+    console.log(`.${ext}: ${bucket.count} files, ${formatSize(bucket.size)}`);
+  }
+  for (const candidate of report.candidates) {
+    console.log(
+      `[${candidate.kind}] ${candidate.workDir}: ${candidate.files.length} files, ` +
+        `${formatSize(candidate.size)}, saves ~${formatSize(candidate.estimatedSavings)}`
+    );
+  }
+  console.log(
+    `${report.candidates.length} candidates, ` +
+      `estimated savings ${formatSize(report.totalSavings)} ` +
+      `(wav: ${formatSize(report.savingsByKind.wav_to_flac)}, ` +
+      `flac: ${formatSize(report.savingsByKind.flac_to_ogg)}, ` +
+      `mp4: ${formatSize(report.savingsByKind.large_mp4)}, ` +
+      `hires: ${formatSize(report.savingsByKind.hires_audio)})`
+  );
+  return report;
+}
@@ -0,0 +1,262 @@
+/**
+ * 作品归档（冻结 / 解冻）
+ *
+ * 将长期未改动的作品目录压缩为单独的 7z 文件，放在根目录的 `_archive/` 下并删除原目录；
+ * 归档索引记录了作品信息，冻结后仍可浏览，需要时再解冻还原
+ */
+
+import { exists, mkdir, readDir, remove, stat } from '@tauri-apps/plugin-fs';
+import { ArchiveCreator, ArchiveExtractor } from '../fs/archive';
+import { getDirSize } from '../fs/cache';
+import { getDirBmsOutput } from '../bms/scanner';
+import { readJsonFile, writeJsonFile } from '../storage';
+
+/**
+ * 归档目录名（位于根目录下）
+ */
+export const FROZEN_DIR_NAME = '_archive';
+
+/**
+ * 归档索引文件名（位于归档目录下）
+ */
+const FROZEN_INDEX_FILE = 'index.json';
+
+/**
+ * 已冻结作品的索引条目
+ */
+export interface FrozenWorkEntry {
+  /** 原作品目录名 */
+  name: string;
+  /** 归档文件名 */
+  archive: string;
+  title?: string;
+  artist?: string;
+  genre?: string;
+  /** 原目录大小（字节） */
+  originalSize: number;
+  /** 归档文件大小（字节） */
+  archiveSize: number;
+  /** 原目录最后修改时间（毫秒时间戳） */
+  lastModified: number;
+  /** 冻结时间（ISO 字符串） */
+  frozenAt: string;
+}
+
+/**
+ * 获取归档目录路径
+ */
+export function getFrozenDir(rootDir: string): string {
+  return `${rootDir}/${FROZEN_DIR_NAME}`;
+}
+
+async function loadFrozenIndex(rootDir: string): Promise<FrozenWorkEntry[]> {
+  return await readJsonFile<FrozenWorkEntry[]>(
+    `${getFrozenDir(rootDir)}/${FROZEN_INDEX_FILE}`,
+    []
+  );
+}
+
+async function saveFrozenIndex(rootDir: string, entries: FrozenWorkEntry[]): Promise<void> {
+  await writeJsonFile(`${getFrozenDir(rootDir)}/${FROZEN_INDEX_FILE}`, entries);
+}
+
+/**
+ * 递归获取目录中最新的修改时间（毫秒时间戳）
+ */
+async function getLatestMtime(dirPath: string): Promise<number> {
+  let latest = (await stat(dirPath)).mtime?.getTime() ?? 0;
+
+  for (const entry of await readDir(dirPath)) {
+    if (!entry.name) {
+      continue;
+    }
+
+    const entryPath = `${dirPath}/${entry.name}`;
+    const mtime = entry.isDirectory
+      ? await getLatestMtime(entryPath)
+      : ((await stat(entryPath)).mtime?.getTime() ?? 0);
+    latest = Math.max(latest, mtime);
+  }
+
+  return latest;
+}
+
+/**
+ * 冻结长期未改动的作品
+ *
+ * @command
+ * @category bmsfolder
+ * @dangerous true
+ * @name 冻结作品
+ * @description 将最后修改时间早于指定日期的作品压缩为 7z 归档并删除原目录（需要 7z）
+ * @frontend true
+ *
+ * @param {string} rootDir - 根目录路径
+ * @param {string} olderThan - 日期（如 2024-01-01），早于该日期的作品会被冻结
+ * @param {boolean} dryRun - 模拟运行（不实际执行）
+ *
+ * @returns {Promise<FrozenWorkEntry[]>} 冻结的作品
+ */
+export async function freezeWorks(
+  rootDir: string,
+  olderThan: string,
+  dryRun: boolean
+): Promise<FrozenWorkEntry[]> {
+  const cutoff = new Date(olderThan).getTime();
+  if (Number.isNaN(cutoff)) {
+    throw new Error(`Invalid date: ${olderThan}`);
+  }
+
+  const frozenDir = getFrozenDir(rootDir);
+  const index = await loadFrozenIndex(rootDir);
+  const frozen: FrozenWorkEntry[] = [];
+
+  if (!dryRun && !(await exists(frozenDir))) {
+    await mkdir(frozenDir, { recursive: true });
+  }
+
+  for (const entry of await readDir(rootDir)) {
+    if (!entry.isDirectory || !entry.name || entry.name === FROZEN_DIR_NAME) {
+      continue;
+    }
+
+    const workDir = `${rootDir}/${entry.name}`;
+    const lastModified = await getLatestMtime(workDir);
+    if (lastModified >= cutoff) {
+      continue;
+    }
+
+    const archiveName = `${entry.name}.7z`;
+    const archivePath = `${frozenDir}/${archiveName}`;
+    if (await exists(archivePath)) {
+      console.warn(` !_! Archive already exists, skipping: ${archivePath}`);
+      continue;
+    }
+
+    const info = await getDirBmsOutput(workDir);
+    const originalSize = await getDirSize(workDir);
+
+    if (dryRun) {
+      console.log(`[dry-run] Would freeze: ${workDir} -> ${archivePath}`);
+      continue;
+    }
+
+    await ArchiveCreator.create7z(workDir, archivePath);
+    if (!(await ArchiveCreator.test(archivePath))) {
+      // 校验失败时保留原目录
+      console.error(` !_! Archive verification failed, keeping ${workDir}`);
+      await remove(archivePath).catch(() => {});
+      continue;
+    }
+
+    await remove(workDir, { recursive: true });
+
+    const frozenEntry: FrozenWorkEntry = {
+      name: entry.name,
+      archive: archiveName,
+      title: info?.bms.musicInfo.title,
+      artist: info?.bms.musicInfo.artist,
+      genre: info?.bms.musicInfo.genre,
+      originalSize,
+      archiveSize: (await stat(archivePath)).size,
+      lastModified,
+      frozenAt: new Date().toISOString(),
+    };
+    index.push(frozenEntry);
+    frozen.push(frozenEntry);
+    // 每冻结一个作品就保存索引，避免中断后索引与归档不一致
+    await saveFrozenIndex(rootDir, index);
+    console.log(`Frozen: ${workDir} -> ${archivePath}`);
+  }
+
+  return frozen;
+}
+
+/**
+ * 解冻作品
+ *
+ * @command
+ * @category bmsfolder
+ * @dangerous true
+ * @name 解冻作品
+ * @description 将已冻结的作品从 7z 归档还原到根目录（作品名留空时解冻全部）
+ * @frontend true
+ *
+ * @param {string} rootDir - 根目录路径
+ * @param {string} workName - 作品目录名（留空表示全部）
+ * @param {boolean} dryRun - 模拟运行（不实际执行）
+ *
+ * @returns {Promise<string[]>} 解冻的作品目录名
+ */
+export async function unfreezeWorks(
+  rootDir: string,
+  workName: string,
+  dryRun: boolean
+): Promise<string[]> {
+  const frozenDir = getFrozenDir(rootDir);
+  const index = await loadFrozenIndex(rootDir);
+  const targets = workName ? index.filter((entry) => entry.name === workName) : [...index];
+  if (workName && targets.length === 0) {
+    throw new Error(`Work is not frozen: ${workName}`);
+  }
+
+  const restored: string[] = [];
+
+  for (const entry of targets) {
+    const archivePath = `${frozenDir}/${entry.archive}`;
+    const workDir = `${rootDir}/${entry.name}`;
+
+    if (await exists(workDir)) {
+      console.warn(` !_! Target dir already exists, skipping: ${workDir}`);
+      continue;
+    }
+    if (!(await exists(archivePath))) {
+      console.warn(` !_! Archive not found, skipping: ${archivePath}`);
+      continue;
+    }
+
+    if (dryRun) {
+      console.log(`[dry-run] Would unfreeze: ${archivePath} -> ${workDir}`);
+      continue;
+    }
+
+    await ArchiveExtractor.extract7z(archivePath, rootDir);
+    if (!(await exists(workDir))) {
+      console.error(` !_! Extracted archive did not produce ${workDir}, keeping archive`);
+      continue;
+    }
+
+    await remove(archivePath);
+    index.splice(index.indexOf(entry), 1);
+    await saveFrozenIndex(rootDir, index);
+    restored.push(entry.name);
+    console.log(`Unfrozen: ${archivePath} -> ${workDir}`);
+  }
+
+  return restored;
+}
+
+/**
+ * 列出已冻结的作品
+ *
+ * @command
+ * @category bmsfolder
+ * @dangerous false
+ * @name 查看冻结作品
+ * @description 列出根目录中已冻结的作品及其信息
+ * @frontend true
+ *
+ * @param {string} rootDir - 根目录路径
+ *
+ * @returns {Promise<FrozenWorkEntry[]>} 已冻结的作品
+ */
+export async function listFrozenWorks(rootDir: string): Promise<FrozenWorkEntry[]> {
+  const index = await loadFrozenIndex(rootDir);
+
+  for (const entry of index) {
+    const label = [entry.title, entry.artist].filter(Boolean).join(' / ');
+    console.log(` - ${entry.name}${label ? ` (${label})` : ''}: ${entry.archiveSize} bytes`);
+  }
+
+  return index;
+}
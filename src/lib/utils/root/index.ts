@@ -3,5 +3,17 @@
  * 导出所有公共 API
  */
 
+export * from './audioReport';
+export * from './auditNames';
 export * from './batch';
+export * from './compare';
+export * from './enforceProfile';
+export * from './findConvertibles';
+export * from './freeze';
+export * from './journal';
+export * from './mediaCompat';
+export * from './orphanCharts';
+export * from './promote';
 export * from './similarity';
+export * from './swappedFields';
+export * from './watcher';
@@ -0,0 +1,274 @@
+/**
+ * 批量重命名和移动日志
+ *
+ * 记录根目录批量重命名中每个目录的原名称和新名称，撤销时按记录精确还原，
+ * 不再从目录名中猜测原名称；批量移动（如按流派整理）同样按记录撤销。
+ * 导入压缩包时还可以输出审计 CSV，记录每个被处理的文件
+ */
+
+import { readDir, writeTextFile } from '@tauri-apps/plugin-fs';
+import { HashService, MANIFEST_HASH_ALGORITHM } from '../fs/hash';
+import { getAppDataPath, getAppDataPathForDir, readJsonFile, writeJsonFile } from '../storage';
+import type { BmsFolderSetNameType } from '../work/rename';
+
+/**
+ * 一次目录重命名
+ */
+export interface RenameJournalEntry {
+  from: string;
+  to: string;
+  /** 目标已存在、内容合并进了已有目录（无法精确撤销） */
+  merged: boolean;
+}
+
+/**
+ * 一次批量重命名的记录
+ */
+export interface RenameJournalRun {
+  setType: BmsFolderSetNameType;
+  startedAt: string;
+  renames: RenameJournalEntry[];
+}
+
+/**
+ * 批量重命名日志存储（每个根目录一个文件，按运行顺序保存）
+ */
+export class RenameJournal {
+  private constructor(
+    private readonly path: string,
+    private readonly runs: RenameJournalRun[]
+  ) {}
+
+  /**
+   * 打开根目录的日志
+   */
+  static async open(rootDir: string): Promise<RenameJournal> {
+    const path = await getAppDataPathForDir('journal/root-set-name', rootDir);
+    return new RenameJournal(path, await readJsonFile<RenameJournalRun[]>(path, []));
+  }
+
+  /**
+   * 开始记录一次批量重命名；继续中断的运行时沿用同一条记录
+   *
+   * @param startedAt - 运行开始时间（同时作为记录的标识）
+   */
+  startRun(setType: BmsFolderSetNameType, startedAt: string): RenameJournalRun {
+    let run = this.runs.find((item) => item.startedAt === startedAt);
+    if (!run) {
+      run = { setType, startedAt, renames: [] };
+      this.runs.push(run);
+    }
+    return run;
+  }
+
+  /**
+   * 最近一次指定命名方式的批量重命名
+   */
+  latestRun(setType: BmsFolderSetNameType): RenameJournalRun | undefined {
+    return [...this.runs]
+      .reverse()
+      .find((run) => run.setType === setType && run.renames.length > 0);
+  }
+
+  /**
+   * 删除一次记录（撤销完成后调用）
+   */
+  removeRun(run: RenameJournalRun): void {
+    const index = this.runs.indexOf(run);
+    if (index >= 0) {
+      this.runs.splice(index, 1);
+    }
+  }
+
+  async save(): Promise<void> {
+    await writeJsonFile(this.path, this.runs);
+  }
+}
+
+/**
+ * 一次目录移动（相对根目录的路径）
+ */
+export interface MoveJournalEntry {
+  from: string;
+  to: string;
+}
+
+/**
+ * 一次批量移动的记录
+ */
+export interface MoveJournalRun {
+  startedAt: string;
+  moves: MoveJournalEntry[];
+  /** 本次运行新建的目录（撤销时为空则删除） */
+  createdDirs: string[];
+}
+
+/**
+ * 批量移动日志存储（按操作类型和根目录分别保存）
+ */
+export class MoveJournal {
+  private constructor(
+    private readonly path: string,
+    private readonly runs: MoveJournalRun[]
+  ) {}
+
+  /**
+   * 打开根目录的日志
+   *
+   * @param kind - 操作类型（如 organize-by-genre）
+   */
+  static async open(kind: string, rootDir: string): Promise<MoveJournal> {
+    const path = await getAppDataPathForDir(`journal/${kind}`, rootDir);
+    return new MoveJournal(path, await readJsonFile<MoveJournalRun[]>(path, []));
+  }
+
+  startRun(startedAt: string): MoveJournalRun {
+    const run: MoveJournalRun = { startedAt, moves: [], createdDirs: [] };
+    this.runs.push(run);
+    return run;
+  }
+
+  /**
+   * 最近一次有移动记录的运行
+   */
+  latestRun(): MoveJournalRun | undefined {
+    return [...this.runs].reverse().find((run) => run.moves.length > 0);
+  }
+
+  removeRun(run: MoveJournalRun): void {
+    const index = this.runs.indexOf(run);
+    if (index >= 0) {
+      this.runs.splice(index, 1);
+    }
+  }
+
+  async save(): Promise<void> {
+    await writeJsonFile(this.path, this.runs);
+  }
+}
+
+/**
+ * 审计记录的操作类型
+ */
+export type ImportAuditAction = 'extract' | 'place' | 'quarantine' | 'remove';
+
+/**
+ * 审计 CSV 的一行
+ */
+export interface ImportAuditRow {
+  timestamp: string;
+  action: ImportAuditAction;
+  src: string;
+  dst: string;
+  /** 文件的 SHA-256，目录或已删除的文件为空 */
+  hash: string;
+}
+
+/**
+ * 审计 CSV 的列
+ */
+const AUDIT_CSV_COLUMNS: (keyof ImportAuditRow)[] = ['timestamp', 'action', 'src', 'dst', 'hash'];
+
+/**
+ * 转义 CSV 字段（含逗号、引号或换行时加引号）
+ */
+export function escapeCsvField(value: string): string {
+  return /[",\r\n]/.test(value) ? `"${value.replace(/"/g, '""')}"` : value;
+}
+
+/**
+ * 压缩包导入的审计日志
+ *
+ * 部分活动规则要求保留原始投稿，启用后每次导入把解压、放置、隔离和删除的每个文件
+ * 连同哈希写入应用数据目录下的 `audit/<类型>-<时间>.csv`（带 BOM，可直接用表格软件打开）
+ */
+export class ImportAudit {
+  private static enabled = false;
+
+  private readonly rows: ImportAuditRow[] = [];
+
+  private constructor(private readonly path: string) {}
+
+  /**
+   * 启用或停用审计 CSV
+   */
+  static setEnabled(enabled: boolean): void {
+    this.enabled = enabled;
+  }
+
+  static isEnabled(): boolean {
+    return this.enabled;
+  }
+
+  /**
+   * 开始一次导入的审计，未启用时返回 null
+   *
+   * @param kind - 导入类型（如 unzip-numeric、inbox）
+   */
+  static async start(kind: string): Promise<ImportAudit | null> {
+    if (!this.enabled) {
+      return null;
+    }
+    const timestamp = new Date().toISOString().replace(/[:.]/g, '-');
+    return new ImportAudit(await getAppDataPath(`audit/${kind}-${timestamp}.csv`));
+  }
+
+  /**
+   * 记录一次操作
+   *
+   * @param hashPath - 用于计算哈希的文件（操作前后仍存在的一侧），为空时不计算
+   */
+  async record(
+    action: ImportAuditAction,
+    src: string,
+    dst: string,
+    hashPath?: string
+  ): Promise<void> {
+    let hash = '';
+    if (hashPath) {
+      try {
+        hash = await HashService.hashFile(hashPath, MANIFEST_HASH_ALGORITHM);
+      } catch (error) {
+        console.warn(`Failed to hash file for audit: ${hashPath}`, error);
+      }
+    }
+    this.rows.push({ timestamp: new Date().toISOString(), action, src, dst, hash });
+  }
+
+  /**
+   * 记录目录中的每个文件（在移动目录之前调用，哈希按源文件计算）
+   */
+  async recordDir(action: ImportAuditAction, srcDir: string, dstDir: string): Promise<void> {
+    const walk = async (relativeDir: string): Promise<void> => {
+      const dirPath = relativeDir ? `${srcDir}/${relativeDir}` : srcDir;
+      for (const entry of await readDir(dirPath)) {
+        if (!entry.name) {
+          continue;
+        }
+        const relativePath = relativeDir ? `${relativeDir}/${entry.name}` : entry.name;
+        if (entry.isDirectory) {
+          await walk(relativePath);
+        } else {
+          const srcPath = `${srcDir}/${relativePath}`;
+          await this.record(action, srcPath, `${dstDir}/${relativePath}`, srcPath);
+        }
+      }
+    };
+    await walk('');
+  }
+
+  /**
+   * 写出 CSV（没有记录时不创建文件）
+   */
+  async save(): Promise<void> {
+    if (this.rows.length === 0) {
+      return;
+    }
+    const lines = [
+      AUDIT_CSV_COLUMNS.join(','),
+      ...this.rows.map((row) => AUDIT_CSV_COLUMNS.map((key) => escapeCsvField(row[key])).join(',')),
+    ];
+    await writeTextFile(this.path, `\ufeff${lines.join('\r\n')}\r\n`);
+    console.log(`Audit CSV written: ${this.path} (${this.rows.length} rows)`);
+  }
+}
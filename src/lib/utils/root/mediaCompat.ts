@@ -0,0 +1,169 @@
+/**
+ * 媒体清理的播放器兼容性预览
+ *
+ * 清理规则按"保留高质量格式"删除同名的其他格式，但各播放器能播放的格式不同：
+ * 例如同时有 MP4 和 WMV 时删除 WMV，LR2 就无法播放这个 BGA 了。
+ * 应用清理规则前，按每个作品清理后剩余的格式，列出各目标播放器将无法播放的文件
+ */
+
+import { readDir } from '@tauri-apps/plugin-fs';
+import { AUDIO_FILE_EXTS, VIDEO_FILE_EXTS } from '../bms/scanner';
+import { getFileExtension, getFileName, getFileStem } from '../fs/path';
+import { MediaCleaner } from '../media/cleanup';
+import { REMOVE_MEDIA_RULES } from '../media/presets';
+import { skipIfPinned } from '../work/pin';
+import type { RemoveMediaPreset } from '../../types/enums';
+
+/**
+ * 目标播放器
+ */
+export type TargetPlayer = 'lr2' | 'beatoraja' | 'qwilight';
+
+/**
+ * 各播放器能播放的音频和视频格式
+ */
+export const PLAYER_MEDIA_FORMATS: Record<TargetPlayer, readonly string[]> = {
+  lr2: ['wav', 'ogg', 'mpg', 'mpeg', 'wmv', 'avi'],
+  beatoraja: ['wav', 'ogg', 'flac', 'mp3', 'mp4', 'mpg', 'mpeg', 'wmv', 'avi', 'webm', 'mkv'],
+  qwilight: ['wav', 'ogg', 'flac', 'mp3', 'mp4', 'mpg', 'mpeg', 'wmv', 'avi', 'webm', 'mkv'],
+};
+
+const TARGET_PLAYERS = Object.keys(PLAYER_MEDIA_FORMATS) as TargetPlayer[];
+
+const MEDIA_EXTS = new Set<string>([...AUDIO_FILE_EXTS, ...VIDEO_FILE_EXTS]);
+
+/**
+ * 单个作品中清理后无法播放的文件
+ */
+export interface WorkCompatLoss {
+  workDir: string;
+  /** 清理规则将删除的文件 */
+  removed: string[];
+  /** 各播放器失去播放的文件（删除后同名文件中再没有该播放器能播放的格式） */
+  lost: Partial<Record<TargetPlayer, string[]>>;
+}
+
+/**
+ * 兼容性预览报告
+ */
+export interface MediaCompatReport {
+  preset: RemoveMediaPreset;
+  players: TargetPlayer[];
+  /** 清理规则将删除的文件数 */
+  plannedRemovals: number;
+  /** 有播放器会失去播放的作品 */
+  works: WorkCompatLoss[];
+  /** 各播放器受影响的作品数 */
+  affectedWorks: Record<TargetPlayer, number>;
+}
+
+/**
+ * 解析目标播放器（为空时为全部）
+ */
+function parsePlayers(players: string[]): TargetPlayer[] {
+  const names = players.map((player) => player.trim().toLowerCase()).filter(Boolean);
+  for (const name of names) {
+    if (!(name in PLAYER_MEDIA_FORMATS)) {
+      throw new Error(`未知的播放器：${name}（可用：${TARGET_PLAYERS.join(', ')}）`);
+    }
+  }
+  return names.length > 0 ? [...new Set(names as TargetPlayer[])] : [...TARGET_PLAYERS];
+}
+
+/**
+ * 检查单个作品
+ */
+async function checkWork(
+  workDir: string,
+  preset: RemoveMediaPreset,
+  players: TargetPlayer[]
+): Promise<WorkCompatLoss | null> {
+  const removals = await MediaCleaner.planRemovals(workDir, REMOVE_MEDIA_RULES[preset]);
+  if (removals.length === 0) {
+    return null;
+  }
+
+  // 按文件名（不含扩展名）分组：播放器找不到谱面写的文件时会尝试同名的其他格式
+  const groups = new Map<string, string[]>();
+  for (const entry of await readDir(workDir)) {
+    if (!entry.isFile || !MEDIA_EXTS.has(getFileExtension(entry.name))) {
+      continue;
+    }
+    const stem = getFileStem(entry.name).toLowerCase();
+    groups.set(stem, [...(groups.get(stem) ?? []), entry.name]);
+  }
+
+  const removed = new Set(removals.map(([, removedPath]) => getFileName(removedPath)));
+  const lost: WorkCompatLoss['lost'] = {};
+  for (const name of removed) {
+    const before = groups.get(getFileStem(name).toLowerCase()) ?? [name];
+    const after = before.filter((file) => !removed.has(file));
+    for (const player of players) {
+      const playable = (files: string[]) =>
+        files.some((file) => PLAYER_MEDIA_FORMATS[player].includes(getFileExtension(file)));
+      if (playable(before) && !playable(after)) {
+        (lost[player] ??= []).push(name);
+      }
+    }
+  }
+
+  return { workDir, removed: [...removed], lost };
+}
+
+/**
+ * 预览媒体清理对各播放器的影响
+ *
+ * @command
+ * @category bmsfolder
+ * @dangerous false
+ * @name 预览媒体清理的播放器兼容性
+ * @description 应用媒体清理规则前，按每个作品清理后剩余的格式，列出 LR2 / beatoraja / Qwilight 将无法播放的文件（如只保留 MP4 时 LR2 无法播放 BGA）
+ * @frontend true
+ *
+ * @param {string} rootDir - 根目录路径
+ * @param {RemoveMediaPreset} preset - 清理规则预设
+ * @param {string[]} players - 目标播放器（lr2 / beatoraja / qwilight，每行一个，为空时检查全部）
+ *
+ * @returns {Promise<MediaCompatReport>} 各播放器受影响的作品和文件
+ */
+export async function checkRemoveMediaCompat(
+  rootDir: string,
+  preset: RemoveMediaPreset,
+  players: string[] = []
+): Promise<MediaCompatReport> {
+  const targets = parsePlayers(players);
+  const report: MediaCompatReport = {
+    preset,
+    players: targets,
+    plannedRemovals: 0,
+    works: [],
+    affectedWorks: { lr2: 0, beatoraja: 0, qwilight: 0 },
+  };
+
+  for (const entry of await readDir(rootDir)) {
+    const workDir = `${rootDir}/${entry.name}`;
+    if (!entry.isDirectory || (await skipIfPinned(workDir))) {
+      continue;
+    }
+    const result = await checkWork(workDir, preset, targets);
+    if (!result) {
+      continue;
+    }
+    report.plannedRemovals += result.removed.length;
+    const affected = targets.filter((player) => result.lost[player]);
+    if (affected.length === 0) {
+      continue;
+    }
+    report.works.push(result);
+    for (const player of affected) {
+      report.affectedWorks[player]++;
+      console.warn(` !_! ${player} would lose: ${workDir}: ${result.lost[player]!.join(', ')}`);
+    }
+  }
+
+  console.log(
+    `${report.plannedRemovals} files would be removed. Works losing playback: ` +
+      targets.map((player) => `${player} ${report.affectedWorks[player]}`).join(', ')
+  );
+  return report;
+}
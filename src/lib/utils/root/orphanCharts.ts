@@ -0,0 +1,141 @@
+/**
+ * 根目录中的散落谱面
+ *
+ * 解压或手动整理时，谱面文件有时会直接落在根目录而不是作品文件夹中，播放器无法把它们识别为作品。
+ * 这里找出这类谱面，按标题（去掉结尾的难度括号）分组，为每组新建作品文件夹，
+ * 并把谱面连同同名的媒体文件（如预览音频、横幅）一起移入
+ */
+
+import { exists, mkdir, readDir, rename } from '@tauri-apps/plugin-fs';
+import { bmsQuickInfo } from '../bms/quickInfo';
+import { isChartFile, MEDIA_FILE_EXTS } from '../bms/scanner';
+import { extractWorkName } from '../bms/work';
+import { getFileExtension, getFileStem, getValidFileName } from '../fs/path';
+
+/**
+ * 一组散落谱面及其移动方案
+ */
+export interface OrphanChartGroup {
+  /** 要新建的作品文件夹名 */
+  folderName: string;
+  charts: string[];
+  /** 与谱面同名的媒体文件 */
+  media: string[];
+}
+
+/**
+ * 标题结尾的难度括号（如 "Song [ANOTHER]"、"Song (7keys HYPER)"）
+ */
+const TRAILING_BRACKET_PATTERN = /\s*[[(（［【<＜][^[\]()（）［］【】<>＜＞]*[\])）］】>＞]\s*$/;
+
+/**
+ * 找出根目录中的散落谱面并计算分组
+ */
+async function planOrphanCharts(rootDir: string): Promise<OrphanChartGroup[]> {
+  const entries = await readDir(rootDir);
+  const files = entries.filter((entry) => entry.isFile && entry.name).map((entry) => entry.name);
+  const charts = files.filter((name) => isChartFile(name)).sort();
+  if (charts.length === 0) {
+    return [];
+  }
+
+  // 按去掉难度括号后的标题和艺术家分组
+  const groups = new Map<string, { titles: string[]; charts: string[] }>();
+  for (const chart of charts) {
+    const info = await bmsQuickInfo(`${rootDir}/${chart}`);
+    const title = info?.title?.trim() || getFileStem(chart);
+    const baseTitle = title.replace(TRAILING_BRACKET_PATTERN, '') || title;
+    const key = `${baseTitle}\u0000${info?.artist?.trim() ?? ''}`;
+    const group = groups.get(key) ?? { titles: [], charts: [] };
+    group.titles.push(baseTitle);
+    group.charts.push(chart);
+    groups.set(key, group);
+  }
+
+  // 新文件夹不能与已有的文件夹或其他组重名（不区分大小写）
+  const taken = new Set(
+    entries.filter((entry) => entry.isDirectory).map((entry) => entry.name.toLowerCase())
+  );
+  const result: OrphanChartGroup[] = [];
+  for (const group of groups.values()) {
+    const baseName = getValidFileName(extractWorkName(group.titles) || group.titles[0]);
+    let folderName = baseName;
+    for (let count = 2; taken.has(folderName.toLowerCase()); count++) {
+      folderName = `${baseName} (${count})`;
+    }
+    taken.add(folderName.toLowerCase());
+
+    const stems = new Set(group.charts.map((chart) => getFileStem(chart).toLowerCase()));
+    const media = files.filter(
+      (name) =>
+        (MEDIA_FILE_EXTS as readonly string[]).includes(getFileExtension(name)) &&
+        stems.has(getFileStem(name).toLowerCase())
+    );
+    result.push({ folderName, charts: group.charts, media });
+  }
+
+  return result;
+}
+
+/**
+ * 检查根目录中的散落谱面
+ *
+ * @command
+ * @category bmsfolder
+ * @dangerous false
+ * @name 检查散落谱面
+ * @description 找出直接放在根目录（而不是作品文件夹）中的谱面文件，按标题分组并给出新建文件夹的方案
+ * @frontend true
+ *
+ * @param {string} rootDir - 根目录路径
+ *
+ * @returns {Promise<OrphanChartGroup[]>} 散落谱面的分组及新文件夹名
+ */
+export async function findOrphanCharts(rootDir: string): Promise<OrphanChartGroup[]> {
+  const groups = await planOrphanCharts(rootDir);
+  for (const group of groups) {
+    const files = [...group.charts, ...group.media].join(', ');
+    console.log(`${group.folderName}: ${files}`);
+  }
+  console.log(`Found ${groups.reduce((sum, g) => sum + g.charts.length, 0)} orphan charts.`);
+  return groups;
+}
+
+/**
+ * 将根目录中的散落谱面移入新建的作品文件夹
+ *
+ * @command
+ * @category bmsfolder
+ * @dangerous true
+ * @name 整理散落谱面
+ * @description 为直接放在根目录中的谱面按标题新建作品文件夹，并将谱面和同名的媒体文件移入
+ * @frontend true
+ *
+ * @param {string} rootDir - 根目录路径
+ * @param {boolean} dryRun - 模拟运行（不实际执行）
+ *
+ * @returns {Promise<OrphanChartGroup[]>} 处理的分组
+ */
+export async function fixOrphanCharts(
+  rootDir: string,
+  dryRun: boolean
+): Promise<OrphanChartGroup[]> {
+  const groups = await planOrphanCharts(rootDir);
+
+  for (const group of groups) {
+    const workDir = `${rootDir}/${group.folderName}`;
+    for (const file of [...group.charts, ...group.media]) {
+      console.log(`${dryRun ? '[dry-run] Would move' : 'Move'}: ${file} -> ${workDir}`);
+      if (!dryRun) {
+        if (!(await exists(workDir))) {
+          await mkdir(workDir);
+        }
+        await rename(`${rootDir}/${file}`, `${workDir}/${file}`);
+      }
+    }
+  }
+
+  const prefix = dryRun ? '[dry-run] ' : '';
+  console.log(`${prefix}Moved orphan charts into ${groups.length} new work folders.`);
+  return groups;
+}
@@ -0,0 +1,264 @@
+/**
+ * 活动作品并入曲库
+ *
+ * 活动结束后，将活动根目录中的作品（含更新版本）移入主曲库：
+ * 按谱面哈希、目录名或标题找到曲库中对应的作品，用新谱面替换旧谱面，
+ * 其余文件（键音等）按 CheckReplace 合并；找不到对应作品时整个目录移入曲库
+ */
+
+import { exists, readDir, rename } from '@tauri-apps/plugin-fs';
+import { CHART_FILE_EXTS } from '../bms/scanner';
+import { bmsQuickInfo } from '../bms/quickInfo';
+import { moveElementsAcrossDir, ReplaceAction } from '../fs/moving';
+import type { ReplaceOptions } from '../fs/moving';
+import { getAppDataPath, writeJsonFile } from '../storage';
+import { collectChartHashes } from './compare';
+import type { ChartHashMap } from './compare';
+
+/**
+ * 对应作品的匹配方式
+ */
+export type PromoteMatchKind = 'hash' | 'name' | 'title';
+
+/**
+ * 更新到曲库已有作品的记录
+ */
+export interface PromoteWorkUpdate {
+  /** 活动根目录中的作品目录名 */
+  from: string;
+  /** 曲库中的作品目录名 */
+  to: string;
+  matchedBy: PromoteMatchKind;
+  /** 新增的谱面 */
+  chartsAdded: string[];
+  /** 替换的旧谱面 */
+  chartsUpdated: string[];
+  /** 新增的其他文件（键音、BGA 等，相对作品目录的路径） */
+  filesAdded: string[];
+}
+
+/**
+ * 并入结果
+ */
+export interface PromoteResult {
+  fromDir: string;
+  toDir: string;
+  /** 执行时间（ISO 字符串） */
+  promotedAt: string;
+  dryRun: boolean;
+  /** 作为新作品移入曲库的目录 */
+  added: string[];
+  /** 更新到已有作品的记录 */
+  updated: PromoteWorkUpdate[];
+  /** 曲库中已是最新、未移动的作品 */
+  unchanged: string[];
+}
+
+/**
+ * 并入时的替换策略：谱面直接替换，其余文件内容不同时保留两份
+ */
+function promoteReplaceOptions(): ReplaceOptions {
+  return {
+    ext: Object.fromEntries(CHART_FILE_EXTS.map((ext) => [ext, ReplaceAction.Replace])),
+    default: ReplaceAction.CheckReplace,
+  };
+}
+
+/**
+ * 递归列出目录中的文件（相对路径）
+ */
+async function listRelativeFiles(dirPath: string, prefix: string = ''): Promise<string[]> {
+  const files: string[] = [];
+  for (const entry of await readDir(dirPath)) {
+    if (!entry.name) {
+      continue;
+    }
+    const relative = prefix ? `${prefix}/${entry.name}` : entry.name;
+    if (entry.isDirectory) {
+      files.push(...(await listRelativeFiles(`${dirPath}/${entry.name}`, relative)));
+    } else {
+      files.push(relative);
+    }
+  }
+  return files;
+}
+
+/**
+ * 读取作品标题（取第一个能读出标题的谱面），用于哈希和目录名都无法匹配时
+ */
+async function readWorkTitle(workDir: string, charts: ChartHashMap): Promise<string | undefined> {
+  for (const chartName of charts.keys()) {
+    const title = (await bmsQuickInfo(`${workDir}/${chartName}`))?.title?.trim();
+    if (title) {
+      return title.toLowerCase();
+    }
+  }
+  return undefined;
+}
+
+/**
+ * 将活动根目录的作品并入曲库
+ *
+ * @command
+ * @category bmsfolder
+ * @dangerous true
+ * @name 活动作品并入曲库
+ * @description 按谱面哈希、目录名或标题匹配曲库中的作品，替换旧谱面并合并新键音，新作品直接移入曲库
+ * @frontend true
+ *
+ * @param {string} fromDir - 活动根目录
+ * @param {string} toDir - 曲库根目录
+ * @param {boolean} dryRun - 模拟运行（不实际执行）
+ *
+ * @returns {Promise<PromoteResult>} 新增和更新的作品记录
+ */
+export async function promote(
+  fromDir: string,
+  toDir: string,
+  dryRun: boolean
+): Promise<PromoteResult> {
+  const [eventWorks, libraryWorks] = await Promise.all([
+    collectChartHashes(fromDir),
+    collectChartHashes(toDir),
+  ]);
+
+  const result: PromoteResult = {
+    fromDir,
+    toDir,
+    promotedAt: new Date().toISOString(),
+    dryRun,
+    added: [],
+    updated: [],
+    unchanged: [],
+  };
+
+  // 谱面哈希 -> 曲库作品
+  const hashOwner = new Map<string, string>();
+  for (const [name, charts] of libraryWorks) {
+    for (const hash of charts.values()) {
+      hashOwner.set(hash, name);
+    }
+  }
+
+  // 曲库作品标题（只在需要按标题匹配时读取）
+  let libraryTitles: Map<string, string> | null = null;
+  const findByTitle = async (title: string): Promise<string | undefined> => {
+    if (!libraryTitles) {
+      libraryTitles = new Map();
+      for (const [name, charts] of libraryWorks) {
+        const libraryTitle = await readWorkTitle(`${toDir}/${name}`, charts);
+        if (libraryTitle && !libraryTitles.has(libraryTitle)) {
+          libraryTitles.set(libraryTitle, name);
+        }
+      }
+    }
+    return libraryTitles.get(title);
+  };
+
+  for (const [eventName, eventCharts] of eventWorks) {
+    const eventDir = `${fromDir}/${eventName}`;
+    if (eventCharts.size === 0) {
+      continue;
+    }
+
+    // 共享谱面最多的作品视为同一作品
+    const sharedCounts = new Map<string, number>();
+    for (const hash of eventCharts.values()) {
+      const owner = hashOwner.get(hash);
+      if (owner !== undefined) {
+        sharedCounts.set(owner, (sharedCounts.get(owner) ?? 0) + 1);
+      }
+    }
+
+    let target: string | undefined;
+    let matchedBy: PromoteMatchKind = 'hash';
+    if (sharedCounts.size > 0) {
+      target = [...sharedCounts.entries()].sort((a, b) => b[1] - a[1])[0][0];
+    } else if (libraryWorks.has(eventName)) {
+      target = eventName;
+      matchedBy = 'name';
+    } else {
+      const title = await readWorkTitle(eventDir, eventCharts);
+      target = title ? await findByTitle(title) : undefined;
+      matchedBy = 'title';
+    }
+
+    if (target === undefined) {
+      const targetDir = `${toDir}/${eventName}`;
+      if (await exists(targetDir)) {
+        console.warn(` !_! Target exists but is not a work, skipping: ${targetDir}`);
+        continue;
+      }
+
+      if (dryRun) {
+        console.log(`[dry-run] Would add new work: ${eventName}`);
+      } else {
+        await rename(eventDir, targetDir);
+        console.log(`Added new work: ${eventName}`);
+      }
+      result.added.push(eventName);
+      continue;
+    }
+
+    const targetDir = `${toDir}/${target}`;
+    const libraryCharts = libraryWorks.get(target)!;
+    const update: PromoteWorkUpdate = {
+      from: eventName,
+      to: target,
+      matchedBy,
+      chartsAdded: [],
+      chartsUpdated: [],
+      filesAdded: [],
+    };
+
+    for (const [chartName, hash] of eventCharts) {
+      const libraryHash = libraryCharts.get(chartName);
+      if (libraryHash === undefined) {
+        if (![...libraryCharts.values()].includes(hash)) {
+          update.chartsAdded.push(chartName);
+        }
+      } else if (libraryHash !== hash) {
+        update.chartsUpdated.push(chartName);
+      }
+    }
+
+    const libraryFiles = new Set(
+      (await listRelativeFiles(targetDir)).map((file) => file.toLowerCase())
+    );
+    update.filesAdded = (await listRelativeFiles(eventDir)).filter(
+      (file) => !eventCharts.has(file) && !libraryFiles.has(file.toLowerCase())
+    );
+
+    if (
+      update.chartsAdded.length === 0 &&
+      update.chartsUpdated.length === 0 &&
+      update.filesAdded.length === 0
+    ) {
+      result.unchanged.push(eventName);
+      continue;
+    }
+
+    const summary =
+      `${eventName} -> ${target} (by ${matchedBy}): +${update.chartsAdded.length} charts, ` +
+      `~${update.chartsUpdated.length} charts, +${update.filesAdded.length} files`;
+    if (dryRun) {
+      console.log(`[dry-run] Would update: ${summary}`);
+    } else {
+      await moveElementsAcrossDir(eventDir, targetDir, promoteReplaceOptions());
+      console.log(`Updated: ${summary}`);
+    }
+    result.updated.push(update);
+  }
+
+  console.log(
+    `Promoted ${fromDir} -> ${toDir}: ${result.added.length} added, ` +
+      `${result.updated.length} updated, ${result.unchanged.length} unchanged`
+  );
+
+  if (!dryRun) {
+    const stamp = result.promotedAt.replace(/[:.]/g, '-');
+    await writeJsonFile(await getAppDataPath(`promote-history/${stamp}.json`), result);
+  }
+
+  return result;
+}
@@ -0,0 +1,303 @@
+/**
+ * 标题和艺术家互换的检查
+ *
+ * 部分谱面把 #TITLE 和 #ARTIST 写反了，整理后得到 "艺术家名 [曲名]" 这样的文件夹。
+ * 按启发式规则找出疑似写反的作品：标题与其他作品的艺术家相同、标题中含有 feat. 等艺术家写法、
+ * 艺术家中含有难度括号。确认后可以交换谱面中的两个字段并按新信息重新命名文件夹
+ */
+
+import {
+  copyFile,
+  readDir,
+  readFile,
+  readTextFile,
+  writeFile,
+  writeTextFile,
+} from '@tauri-apps/plugin-fs';
+import { bytesToLatin1, latin1ToBytes } from '../bms/encoding';
+import { setBmsHeader } from '../bms/level';
+import { bmsQuickInfo } from '../bms/quickInfo';
+import { getDirBmsOutput, getWorkDirChartFiles, isBmsonFile } from '../bms/scanner';
+import { ScoreSafety } from '../bms/scoreSafety';
+import { extractWorkName } from '../bms/work';
+import { ReplacePreset } from '../fs/moving';
+import { ConcurrencyPool } from '../media/concurrency';
+import { getAppDataPath } from '../storage';
+import { BmsFolderSetNameType, setNameByInfo } from '../work/rename';
+
+/**
+ * 判断为写反的依据
+ */
+export type SwappedFieldsReason =
+  | 'title_is_known_artist'
+  | 'title_has_artist_marker'
+  | 'artist_has_difficulty';
+
+/**
+ * 疑似标题和艺术家写反的作品
+ */
+export interface SwappedFieldsSuspect {
+  dirName: string;
+  title: string;
+  artist: string;
+  reasons: SwappedFieldsReason[];
+  /** 交换后文件夹的新名称（模拟运行或检查时为空） */
+  newDirName?: string;
+}
+
+/**
+ * 艺术家之间的分隔（feat.、vs.、& 等），用于拆出单个艺术家名
+ */
+const ARTIST_SEPARATOR_PATTERN = /\s*(?:\bfeat\.?|\bft\.|\bvs\.?|&|×|\/|／|,|、)\s*/i;
+
+/**
+ * 通常只出现在艺术家字段中的写法
+ */
+const ARTIST_MARKER_PATTERN = /(?:\bfeat\.|\bft\.|\bobj\.|\bBGA\s*[:：]|\bvo\.|\bCV\s*[:：])/i;
+
+/**
+ * 通常只出现在标题字段中的难度括号（如 "[ANOTHER]"、"(7KEYS)"）
+ */
+const DIFFICULTY_BRACKET_PATTERN = new RegExp(
+  '[[(（［【<＜-]\\s*' +
+    '(?:BEGINNER|NORMAL|HYPER|ANOTHER|INSANE|LEGGENDARIA|\\d+\\s*KEYS?|[SD]P\\b[^\\])）］】>＞-]*)' +
+    '\\s*[\\])）］】>＞-]\\s*$',
+  'i'
+);
+
+const READ_CONCURRENCY = 8;
+
+function normalizeName(name: string): string {
+  return name.normalize('NFKC').toLowerCase().replace(/\s+/g, ' ').trim();
+}
+
+/**
+ * 艺术家字段中的全部艺术家名（含完整字段）
+ */
+function artistNames(artist: string): string[] {
+  const names = artist.split(ARTIST_SEPARATOR_PATTERN).map(normalizeName);
+  return [normalizeName(artist), ...names].filter((name) => name.length > 0);
+}
+
+/**
+ * 读取作品的标题和艺术家
+ */
+async function readWorkNames(
+  workDir: string
+): Promise<{ title: string; artist: string; charts: string[] } | null> {
+  const charts = await getWorkDirChartFiles(workDir);
+  const infos = await Promise.all(charts.map((chart) => bmsQuickInfo(`${workDir}/${chart}`)));
+  const titles = infos.map((info) => info?.title?.trim() ?? '').filter((title) => title);
+  const artist = infos.map((info) => info?.artist?.trim() ?? '').find((name) => name) ?? '';
+  if (titles.length === 0 || !artist) {
+    return null;
+  }
+  return { title: extractWorkName(titles) || titles[0], artist, charts };
+}
+
+/**
+ * 找出疑似写反的作品
+ */
+async function planSwappedFields(
+  rootDir: string
+): Promise<(SwappedFieldsSuspect & { charts: string[] })[]> {
+  const dirNames = (await readDir(rootDir))
+    .filter((entry) => entry.isDirectory && entry.name)
+    .map((entry) => entry.name)
+    .sort((a, b) => a.localeCompare(b));
+
+  const pool = new ConcurrencyPool<void>(READ_CONCURRENCY);
+  const works = new Map<string, { title: string; artist: string; charts: string[] }>();
+  await Promise.all(
+    dirNames.map((dirName) =>
+      pool.add(async () => {
+        const names = await readWorkNames(`${rootDir}/${dirName}`).catch(() => null);
+        if (names) {
+          works.set(dirName, names);
+        }
+      })
+    )
+  );
+
+  // 艺术家名 -> 使用该艺术家名的作品
+  const artistWorks = new Map<string, Set<string>>();
+  for (const [dirName, { artist }] of works) {
+    for (const name of artistNames(artist)) {
+      const set = artistWorks.get(name) ?? new Set<string>();
+      set.add(dirName);
+      artistWorks.set(name, set);
+    }
+  }
+  const usedByOthers = (name: string, dirName: string) =>
+    [...(artistWorks.get(normalizeName(name)) ?? [])].some((other) => other !== dirName);
+
+  const suspects: (SwappedFieldsSuspect & { charts: string[] })[] = [];
+  for (const dirName of dirNames) {
+    const work = works.get(dirName);
+    if (!work) {
+      continue;
+    }
+
+    const reasons: SwappedFieldsReason[] = [];
+    let score = 0;
+    if (usedByOthers(work.title, dirName)) {
+      reasons.push('title_is_known_artist');
+      score += 2;
+    }
+    // 艺术家也是其他作品的艺术家时，标题与之相同多半只是巧合
+    if (artistNames(work.artist).some((name) => usedByOthers(name, dirName))) {
+      score -= 2;
+    }
+    if (ARTIST_MARKER_PATTERN.test(work.title)) {
+      reasons.push('title_has_artist_marker');
+      score += 1;
+    }
+    if (DIFFICULTY_BRACKET_PATTERN.test(work.artist)) {
+      reasons.push('artist_has_difficulty');
+      score += 1;
+    }
+
+    if (score >= 2) {
+      suspects.push({ dirName, reasons, ...work });
+    }
+  }
+  return suspects;
+}
+
+/**
+ * 交换谱面中的标题和艺术家
+ *
+ * BMS 按单字节读写，不需要识别编码
+ */
+async function swapChartFields(chartPath: string): Promise<void> {
+  if (isBmsonFile(chartPath)) {
+    const bmson = JSON.parse(await readTextFile(chartPath)) as { info?: Record<string, unknown> };
+    const info = bmson.info ?? {};
+    bmson.info = { ...info, title: info.artist ?? '', artist: info.title ?? '' };
+    await writeTextFile(chartPath, JSON.stringify(bmson));
+    return;
+  }
+
+  let content = bytesToLatin1(await readFile(chartPath));
+  const title = content.match(/^#TITLE[ \t]+(.*?)\r?$/im)?.[1] ?? '';
+  const artist = content.match(/^#ARTIST[ \t]+(.*?)\r?$/im)?.[1] ?? '';
+  content = setBmsHeader(content, 'TITLE', artist);
+  content = setBmsHeader(content, 'ARTIST', title);
+  await writeFile(chartPath, latin1ToBytes(content));
+}
+
+/**
+ * 检查标题和艺术家是否写反
+ *
+ * @command
+ * @category bmsfolder
+ * @dangerous false
+ * @name 检查标题和艺术家互换
+ * @description 找出标题与其他作品的艺术家相同、标题含有 feat. 等写法、或艺术家含有难度括号的作品，这些作品的 #TITLE 和 #ARTIST 可能写反了
+ * @frontend true
+ *
+ * @param {string} rootDir - 根目录路径
+ *
+ * @returns {Promise<SwappedFieldsSuspect[]>} 疑似写反的作品及依据
+ */
+export async function findSwappedFields(rootDir: string): Promise<SwappedFieldsSuspect[]> {
+  const suspects = await planSwappedFields(rootDir);
+  for (const suspect of suspects) {
+    console.log(
+      `${suspect.dirName}: title "${suspect.title}", artist "${suspect.artist}" ` +
+        `(${suspect.reasons.join(', ')})`
+    );
+  }
+  console.log(`Found ${suspects.length} works with possibly swapped title and artist.`);
+  return suspects.map(({ dirName, title, artist, reasons }) => ({
+    dirName,
+    title,
+    artist,
+    reasons,
+  }));
+}
+
+/**
+ * 交换标题和艺术家并重新命名文件夹
+ *
+ * @command
+ * @category bmsfolder
+ * @dangerous true
+ * @name 修正标题和艺术家互换
+ * @description 对检查出的作品（或指定的作品）交换所有谱面中的 #TITLE 和 #ARTIST，修改前备份谱面，然后按新的标题和艺术家重新命名文件夹
+ * @frontend true
+ *
+ * @param {string} rootDir - 根目录路径
+ * @param {string[]} works - 要修正的作品文件夹名（留空表示全部检查出的作品）
+ * @param {BmsFolderSetNameType} setType - 重新命名的方式
+ * @param {ReplacePreset} replacePreset - 目标文件夹已存在时的文件替换策略
+ * @param {boolean} dryRun - 模拟运行（不实际执行）
+ *
+ * @returns {Promise<SwappedFieldsSuspect[]>} 修正的作品
+ */
+export async function fixSwappedFields(
+  rootDir: string,
+  works: string[],
+  setType: BmsFolderSetNameType,
+  replacePreset: ReplacePreset,
+  dryRun: boolean
+): Promise<SwappedFieldsSuspect[]> {
+  const suspects = await planSwappedFields(rootDir);
+  const selected = works.length > 0 ? new Set(works) : null;
+  const targets = suspects.filter((suspect) => !selected || selected.has(suspect.dirName));
+  for (const name of selected ?? []) {
+    if (!suspects.some((suspect) => suspect.dirName === name)) {
+      console.warn(` !_! Not detected as swapped, skipping: ${name}`);
+    }
+  }
+
+  const hashChanged = targets.flatMap(({ dirName, charts }) =>
+    charts.map((chart) => `${dirName}/${chart}`)
+  );
+  ScoreSafety.check('Swap title and artist', { hashChanged, pathChanged: [] }, dryRun);
+
+  const backupDir = `swap-backups/${new Date().toISOString().replace(/[:.]/g, '-')}`;
+  const fixed: SwappedFieldsSuspect[] = [];
+  for (const { charts, ...suspect } of targets) {
+    const workDir = `${rootDir}/${suspect.dirName}`;
+    if (dryRun) {
+      console.log(
+        `[dry-run] Would swap in ${charts.length} charts: ${suspect.dirName} ` +
+          `-> title "${suspect.artist}", artist "${suspect.title}"`
+      );
+      fixed.push(suspect);
+      continue;
+    }
+
+    try {
+      for (const chart of charts) {
+        await copyFile(
+          `${workDir}/${chart}`,
+          await getAppDataPath(`${backupDir}/${suspect.dirName}/${chart}`)
+        );
+        await swapChartFields(`${workDir}/${chart}`);
+      }
+
+      const info = await getDirBmsOutput(workDir);
+      const { title, artist } = info?.bms.musicInfo ?? {};
+      const { dirName } = await setNameByInfo(
+        workDir,
+        title,
+        artist,
+        setType,
+        false,
+        replacePreset,
+        false
+      );
+      suspect.newDirName = dirName;
+      console.log(`Swapped title and artist: ${suspect.dirName} -> ${dirName}`);
+      fixed.push(suspect);
+    } catch (error) {
+      console.error(` !_! Failed to swap title and artist in ${workDir}:`, error);
+    }
+  }
+
+  const prefix = dryRun ? '[dry-run] ' : '';
+  console.log(`${prefix}Swapped title and artist in ${fixed.length} works.`);
+  return fixed;
+}
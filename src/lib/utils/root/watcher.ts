@@ -0,0 +1,249 @@
+/**
+ * 根目录监视
+ *
+ * 监视已登记根目录的第一层文件夹，检测外部的新增、删除和重命名，
+ * 并通过 `library-changed` 事件把变更发送给前端，避免手动重新扫描
+ */
+
+import { emit } from '@tauri-apps/api/event';
+import { readDir, watch } from '@tauri-apps/plugin-fs';
+import type { UnwatchFn, WatchEvent } from '@tauri-apps/plugin-fs';
+
+/**
+ * 根目录变更事件名
+ */
+export const LIBRARY_CHANGED_EVENT = 'library-changed';
+
+/**
+ * 文件系统事件的合并延迟（毫秒）
+ */
+const WATCH_DELAY_MS = 500;
+
+/**
+ * 收到事件后重新扫描前的等待时间（毫秒），用于合并连续的变更
+ */
+const RESCAN_DELAY_MS = 300;
+
+/**
+ * 文件夹重命名
+ */
+export interface LibraryRename {
+  from: string;
+  to: string;
+}
+
+/**
+ * 根目录变更
+ */
+export interface LibraryChangeDelta {
+  rootDir: string;
+  /** 新增的文件夹名 */
+  added: string[];
+  /** 删除的文件夹名 */
+  removed: string[];
+  /** 重命名的文件夹 */
+  renamed: LibraryRename[];
+}
+
+/**
+ * 单个根目录的监视状态
+ */
+interface RootWatchState {
+  unwatch: UnwatchFn;
+  /** 当前第一层文件夹名 */
+  folders: Set<string>;
+  /** 尚未处理的重命名（来自文件系统事件） */
+  pendingRenames: LibraryRename[];
+  rescanTimer: ReturnType<typeof setTimeout> | null;
+}
+
+/**
+ * 读取根目录第一层的文件夹名
+ */
+async function listRootFolders(rootDir: string): Promise<Set<string>> {
+  const entries = await readDir(rootDir);
+  return new Set(
+    entries.filter((entry) => entry.isDirectory && entry.name).map((entry) => entry.name)
+  );
+}
+
+/**
+ * 取路径中位于根目录第一层的名称，不在第一层时返回 null
+ */
+function topLevelName(rootDir: string, path: string): string | null {
+  const normalizedRoot = rootDir.replace(/\\/g, '/').replace(/\/+$/, '');
+  const normalizedPath = path.replace(/\\/g, '/');
+  if (!normalizedPath.startsWith(`${normalizedRoot}/`)) {
+    return null;
+  }
+
+  const relative = normalizedPath.slice(normalizedRoot.length + 1);
+  return relative && !relative.includes('/') ? relative : null;
+}
+
+/**
+ * 根目录监视器
+ */
+export class LibraryWatcher {
+  private static readonly roots = new Map<string, RootWatchState>();
+
+  /**
+   * 开始监视根目录（已在监视时不重复登记）
+   */
+  static async register(rootDir: string): Promise<void> {
+    if (this.roots.has(rootDir)) {
+      return;
+    }
+
+    const folders = await listRootFolders(rootDir);
+    const unwatch = await watch(rootDir, (event) => this.handleEvent(rootDir, event), {
+      recursive: false,
+      delayMs: WATCH_DELAY_MS,
+    });
+
+    this.roots.set(rootDir, { unwatch, folders, pendingRenames: [], rescanTimer: null });
+    console.log(`Watching root dir: ${rootDir} (${folders.size} folders)`);
+  }
+
+  /**
+   * 停止监视根目录
+   *
+   * @returns 该根目录之前是否在监视中
+   */
+  static unregister(rootDir: string): boolean {
+    const state = this.roots.get(rootDir);
+    if (!state) {
+      return false;
+    }
+
+    if (state.rescanTimer) {
+      clearTimeout(state.rescanTimer);
+    }
+    state.unwatch();
+    this.roots.delete(rootDir);
+    console.log(`Stopped watching root dir: ${rootDir}`);
+    return true;
+  }
+
+  /**
+   * 获取正在监视的根目录
+   */
+  static getRegisteredRoots(): string[] {
+    return [...this.roots.keys()];
+  }
+
+  private static handleEvent(rootDir: string, event: WatchEvent): void {
+    const state = this.roots.get(rootDir);
+    if (!state) {
+      return;
+    }
+
+    // 同时带有新旧路径的重命名事件可以直接配对
+    const type = event.type;
+    if (
+      typeof type === 'object' &&
+      'modify' in type &&
+      type.modify.kind === 'rename' &&
+      type.modify.mode === 'both' &&
+      event.paths.length === 2
+    ) {
+      const from = topLevelName(rootDir, event.paths[0]);
+      const to = topLevelName(rootDir, event.paths[1]);
+      if (from && to) {
+        state.pendingRenames.push({ from, to });
+      }
+    }
+
+    if (state.rescanTimer) {
+      clearTimeout(state.rescanTimer);
+    }
+    state.rescanTimer = setTimeout(() => {
+      state.rescanTimer = null;
+      this.rescan(rootDir).catch((error) => {
+        console.error(`Failed to rescan root dir: ${rootDir}`, error);
+      });
+    }, RESCAN_DELAY_MS);
+  }
+
+  /**
+   * 重新扫描根目录，与上次的快照比较并发送变更事件
+   */
+  private static async rescan(rootDir: string): Promise<void> {
+    const state = this.roots.get(rootDir);
+    if (!state) {
+      return;
+    }
+
+    const current = await listRootFolders(rootDir);
+    const added = new Set([...current].filter((name) => !state.folders.has(name)));
+    const removed = new Set([...state.folders].filter((name) => !current.has(name)));
+    const renamed: LibraryRename[] = [];
+
+    for (const rename of state.pendingRenames) {
+      if (removed.has(rename.from) && added.has(rename.to)) {
+        removed.delete(rename.from);
+        added.delete(rename.to);
+        renamed.push(rename);
+      }
+    }
+
+    state.folders = current;
+    state.pendingRenames = [];
+
+    if (added.size === 0 && removed.size === 0 && renamed.length === 0) {
+      return;
+    }
+
+    const delta: LibraryChangeDelta = {
+      rootDir,
+      added: [...added],
+      removed: [...removed],
+      renamed,
+    };
+    console.log(
+      `Root dir changed: ${rootDir} (+${delta.added.length} -${delta.removed.length} ` +
+        `~${delta.renamed.length})`
+    );
+    await emit(LIBRARY_CHANGED_EVENT, delta);
+  }
+}
+
+/**
+ * 监视根目录
+ *
+ * @command
+ * @category bmsfolder
+ * @dangerous false
+ * @name 监视根目录
+ * @description 监视根目录中文件夹的新增、删除和重命名，并实时通知界面
+ * @frontend true
+ *
+ * @param {string} rootDir - 根目录路径
+ *
+ * @returns {Promise<string[]>} 正在监视的根目录
+ */
+export async function watchLibraryRoot(rootDir: string): Promise<string[]> {
+  await LibraryWatcher.register(rootDir);
+  return LibraryWatcher.getRegisteredRoots();
+}
+
+/**
+ * 停止监视根目录
+ *
+ * @command
+ * @category bmsfolder
+ * @dangerous false
+ * @name 停止监视根目录
+ * @description 停止监视根目录的外部变更
+ * @frontend true
+ *
+ * @param {string} rootDir - 根目录路径
+ *
+ * @returns {Promise<string[]>} 仍在监视的根目录
+ */
+export async function unwatchLibraryRoot(rootDir: string): Promise<string[]> {
+  if (!LibraryWatcher.unregister(rootDir)) {
+    console.warn(`Root dir is not being watched: ${rootDir}`);
+  }
+  return LibraryWatcher.getRegisteredRoots();
+}
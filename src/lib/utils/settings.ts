@@ -0,0 +1,465 @@
+/**
+ * 应用设置
+ *
+ * 各模块的选项（默认替换策略、媒体清理规则、外部程序路径、并发数、默认输出格式等）
+ * 保存在应用配置目录的 settings.json 中，启动时读取并应用到各模块，
+ * 命令对话框中对应参数的默认值也从这里读取。文件中缺少的项使用默认值
+ */
+
+import { appConfigDir } from '@tauri-apps/api/path';
+import { exists, mkdir } from '@tauri-apps/plugin-fs';
+import { RemoveMediaPreset } from '../types/enums';
+import type { BMSEvent } from '../types/enums';
+import { GenreAlias } from './bigpack/genre';
+import { ScoreSafety } from './bms/scoreSafety';
+import type { ScoreSafetyMode } from './bms/scoreSafety';
+import { ArchiveExtractor, DEFAULT_ARCHIVE_LIMITS } from './fs/archive';
+import type { ArchiveLimits } from './fs/archive';
+import { CacheManager, DEFAULT_CACHE_MAX_AGE_DAYS } from './fs/cache';
+import { HashAlgorithm, HashService } from './fs/hash';
+import { DEFAULT_IGNORE_PATTERNS, IgnoreList } from './fs/ignore';
+import { ReplacePreset } from './fs/moving';
+import { OpenFileGuard } from './fs/openFiles';
+import type { OpenFilePolicy } from './fs/openFiles';
+import { DEFAULT_PERMISSION_SETTINGS, PermissionNormalizer } from './fs/permissions';
+import type { PermissionSettings } from './fs/permissions';
+import { PostHooks } from './hooks';
+import type { HookConfig } from './hooks';
+import { RunLog } from './logs/runLog';
+import { ConcurrencyPool } from './media/concurrency';
+import { ConverterLog, ConverterTimeout } from './media/converterLog';
+import type { FfmpegLogLevel } from './media/converterLog';
+import { OriginalsArchive } from './media/originals';
+import { ExecutablePaths } from './media/processRunner';
+import { AudioPreset, VideoPreset } from './media/types';
+import { CompletionNotification } from './notify';
+import { ImportAudit } from './root/journal';
+import { readJsonFile, writeJsonFile } from './storage';
+import { ResourceTelemetry } from './telemetry';
+import { DEFAULT_NAME_FALLBACK_ORDER, NameFallback, NameFallbackSource } from './work/fallback';
+import { BmsFolderSetNameType, FormattedNamePattern } from './work/rename';
+
+/**
+ * 设置文件名（位于应用配置目录）
+ */
+const SETTINGS_FILE_NAME = 'settings.json';
+
+/**
+ * 应用设置
+ */
+export interface AppSettings {
+  /** 命令的默认替换策略 */
+  replacePreset: ReplacePreset;
+  /** 默认的冗余媒体清理规则 */
+  removeMediaPreset: RemoveMediaPreset;
+  /** 外部程序路径（如 { "ffmpeg": "D:/tools/ffmpeg.exe" }），未设置的按名称在 PATH 中查找 */
+  executables: Record<string, string>;
+  /** 批量转换的默认并发数 */
+  concurrency: number;
+  /** 音频转换的默认输出格式（按顺序尝试） */
+  audioPresets: AudioPreset[];
+  /** 视频转换的默认输出格式（按顺序尝试） */
+  videoPresets: VideoPreset[];
+  /** ffmpeg 日志级别 */
+  ffmpegLogLevel: FfmpegLogLevel;
+  /** 单个文件的转换超时（秒，0 表示不限） */
+  conversionTimeout: number;
+  /** 转换成功后把原文件移入保留目录而不是删除 */
+  keepOriginals: boolean;
+  /** 原文件保留目录（为空时使用与根目录同级的 `<根目录名>_originals`） */
+  originalsRoot: string | null;
+  /** 内部比较使用的哈希算法 */
+  hashAlgorithm: string;
+  /** 每个磁盘的最大并发读取数 */
+  hashReadersPerDisk: number;
+  /** 全局忽略的文件和目录名模式 */
+  ignorePatterns: string[];
+  /** 按命名方式（0/1/2）设置的已格式化目录名正则表达式 */
+  formattedNamePatterns: Record<string, string>;
+  /** 备用名称来源顺序 */
+  nameFallbackOrder: NameFallbackSource[];
+  /** 按编号匹配作品信息时使用的活动 */
+  nameFallbackEvent: BMSEvent | null;
+  /** 流派别名 */
+  genreAliases: Record<string, string>;
+  /** 耗时操作完成时发送桌面通知 */
+  notifications: boolean;
+  /** 把命令日志写入运行日志文件 */
+  runLog: boolean;
+  /** 导入压缩包时输出审计 CSV */
+  importAudit: boolean;
+  /** 缓存位置（为空时使用应用数据目录） */
+  cacheDir: string | null;
+  /** 缓存过期天数 */
+  cacheMaxAgeDays: number;
+  /** 解压失败时尝试抢救 ZIP */
+  archiveSalvage: boolean;
+  /** 解压大小和条目数上限 */
+  archiveLimits: ArchiveLimits;
+  /** 解压后的权限整理 */
+  permissions: PermissionSettings;
+  /** 处理前发现文件被占用时的处理方式 */
+  openFilePolicy: OpenFilePolicy;
+  /** 谱面哈希会改变时的处理方式 */
+  scoreSafety: ScoreSafetyMode;
+  /** 自定义钩子 */
+  hooks: HookConfig[];
+  /** 定时输出资源统计到日志的间隔（秒，0 表示不输出） */
+  statsPrintInterval: number;
+}
+
+/**
+ * 默认设置（与各模块的内置默认值一致）
+ */
+export const DEFAULT_SETTINGS: AppSettings = {
+  replacePreset: ReplacePreset.Default,
+  removeMediaPreset: RemoveMediaPreset.Oraja,
+  executables: {},
+  concurrency: 64,
+  audioPresets: [AudioPreset.FLAC, AudioPreset.FLAC_FFMPEG],
+  videoPresets: [
+    VideoPreset.MPEG1VIDEO_512X512,
+    VideoPreset.WMV2_512X512,
+    VideoPreset.AVI_512X512,
+  ],
+  ffmpegLogLevel: 'panic',
+  conversionTimeout: 1800,
+  keepOriginals: false,
+  originalsRoot: null,
+  hashAlgorithm: HashAlgorithm.BLAKE3,
+  hashReadersPerDisk: 4,
+  ignorePatterns: [...DEFAULT_IGNORE_PATTERNS],
+  formattedNamePatterns: {},
+  nameFallbackOrder: [...DEFAULT_NAME_FALLBACK_ORDER],
+  nameFallbackEvent: null,
+  genreAliases: {},
+  notifications: true,
+  runLog: true,
+  importAudit: false,
+  cacheDir: null,
+  cacheMaxAgeDays: DEFAULT_CACHE_MAX_AGE_DAYS,
+  archiveSalvage: true,
+  archiveLimits: { ...DEFAULT_ARCHIVE_LIMITS },
+  permissions: { ...DEFAULT_PERMISSION_SETTINGS },
+  openFilePolicy: 'ignore',
+  scoreSafety: 'warn',
+  hooks: [],
+  statsPrintInterval: 0,
+};
+
+/**
+ * 各设置项应用到模块的方式（设置无效时抛出错误）
+ *
+ * 替换策略、清理规则和默认输出格式由命令对话框通过 Settings.get() 读取，不需要应用
+ */
+const SETTING_APPLIERS: { [K in keyof AppSettings]: (value: AppSettings[K]) => void } = {
+  replacePreset: () => {},
+  removeMediaPreset: () => {},
+  executables: (value) => ExecutablePaths.setPaths(value),
+  concurrency: (value) => ConcurrencyPool.setDefaultConcurrency(value),
+  audioPresets: () => {},
+  videoPresets: () => {},
+  ffmpegLogLevel: (value) => ConverterLog.setLogLevel(value),
+  conversionTimeout: (value) => ConverterTimeout.setTimeout(value),
+  keepOriginals: (value) => OriginalsArchive.setEnabled(value),
+  originalsRoot: (value) => OriginalsArchive.setRoot(value),
+  hashAlgorithm: (value) => HashService.setDefaultAlgorithm(value),
+  hashReadersPerDisk: (value) => HashService.setReadersPerDisk(value),
+  ignorePatterns: (value) => IgnoreList.setPatterns(value),
+  formattedNamePatterns: (value) => {
+    for (const setType of [
+      BmsFolderSetNameType.ReplaceTitleArtist,
+      BmsFolderSetNameType.AppendTitleArtist,
+      BmsFolderSetNameType.AppendArtist,
+    ]) {
+      FormattedNamePattern.set(setType, value[setType] ?? null);
+    }
+  },
+  nameFallbackOrder: (value) => NameFallback.setOrder(value),
+  nameFallbackEvent: (value) => NameFallback.setEvent(value),
+  genreAliases: (value) => GenreAlias.setAliases(value),
+  notifications: (value) => CompletionNotification.setEnabled(value),
+  runLog: (value) => RunLog.setEnabled(value),
+  importAudit: (value) => ImportAudit.setEnabled(value),
+  cacheDir: (value) => CacheManager.setBaseDir(value),
+  cacheMaxAgeDays: (value) => CacheManager.setMaxAgeDays(value),
+  archiveSalvage: (value) => ArchiveExtractor.setSalvageEnabled(value),
+  archiveLimits: (value) => ArchiveExtractor.setLimits(value),
+  permissions: (value) => PermissionNormalizer.setSettings(value),
+  openFilePolicy: (value) => OpenFileGuard.setPolicy(value),
+  scoreSafety: (value) => ScoreSafety.setMode(value),
+  hooks: (value) => PostHooks.setHooks(value),
+  statsPrintInterval: (value) => ResourceTelemetry.setPrintInterval(value),
+};
+
+const SETTING_KEYS = Object.keys(DEFAULT_SETTINGS) as (keyof AppSettings)[];
+
+/**
+ * 默认值为 null 的设置项，非 null 时的类型
+ */
+const NULLABLE_SETTING_TYPES: Partial<Record<keyof AppSettings, 'string' | 'number'>> = {
+  originalsRoot: 'string',
+  nameFallbackEvent: 'number',
+  cacheDir: 'string',
+};
+
+/**
+ * 取值有限的设置项（数组设置项检查每个元素）
+ */
+const SETTING_CHOICES: Partial<Record<keyof AppSettings, readonly unknown[]>> = {
+  replacePreset: Object.values(ReplacePreset),
+  removeMediaPreset: Object.values(RemoveMediaPreset),
+  audioPresets: Object.values(AudioPreset),
+  videoPresets: Object.values(VideoPreset),
+  ffmpegLogLevel: [
+    'quiet',
+    'panic',
+    'fatal',
+    'error',
+    'warning',
+    'info',
+    'verbose',
+    'debug',
+  ] satisfies FfmpegLogLevel[],
+  nameFallbackOrder: Object.values(NameFallbackSource),
+  openFilePolicy: ['ignore', 'warn', 'abort'] satisfies OpenFilePolicy[],
+  scoreSafety: ['warn', 'block'] satisfies ScoreSafetyMode[],
+};
+
+function isPlainObject(value: unknown): value is Record<string, unknown> {
+  return typeof value === 'object' && value !== null && !Array.isArray(value);
+}
+
+/**
+ * 值的类型是否与默认值一致（对象按默认值中的字段、数组按第一个元素检查）
+ */
+function matchesDefaultType(value: unknown, template: unknown): boolean {
+  if (Array.isArray(template)) {
+    return (
+      Array.isArray(value) &&
+      (template.length === 0 || value.every((item) => matchesDefaultType(item, template[0])))
+    );
+  }
+  if (isPlainObject(template)) {
+    return (
+      isPlainObject(value) &&
+      Object.entries(template).every(
+        ([field, fieldTemplate]) =>
+          !(field in value) || matchesDefaultType(value[field], fieldTemplate)
+      )
+    );
+  }
+  if (typeof template === 'number') {
+    return typeof value === 'number' && Number.isFinite(value);
+  }
+  return typeof value === typeof template;
+}
+
+/**
+ * 检查设置项的值并应用到模块（无效时抛出错误，不修改模块）
+ */
+function applySetting(key: keyof AppSettings, value: unknown): void {
+  const template = DEFAULT_SETTINGS[key];
+  const nullableType = NULLABLE_SETTING_TYPES[key];
+  const typeValid = nullableType
+    ? value === null || typeof value === nullableType
+    : matchesDefaultType(value, template);
+  if (!typeValid) {
+    const expected = nullableType ?? (Array.isArray(template) ? 'array' : typeof template);
+    throw new Error(`设置项 ${key} 的值 ${JSON.stringify(value)} 无效（应为 ${expected}）`);
+  }
+
+  const choices = SETTING_CHOICES[key];
+  const invalid = (Array.isArray(value) ? value : [value]).find(
+    (item) => choices && !choices.includes(item)
+  );
+  if (invalid !== undefined) {
+    throw new Error(
+      `设置项 ${key} 的值 ${JSON.stringify(invalid)} 无效（可用：${choices!.join(', ')}）`
+    );
+  }
+
+  (SETTING_APPLIERS[key] as (value: unknown) => void)(value);
+}
+
+/**
+ * 设置管理
+ */
+export class Settings {
+  private static current: AppSettings = structuredClone(DEFAULT_SETTINGS);
+  /** 设置文件中无效或未知的项（保存时原样写回，不用默认值覆盖） */
+  private static preserved: Record<string, unknown> = {};
+
+  /**
+   * 获取当前设置
+   */
+  static get(): AppSettings {
+    return structuredClone(this.current);
+  }
+
+  /**
+   * 设置文件路径（应用配置目录不存在时创建）
+   */
+  static async getFilePath(): Promise<string> {
+    const configDir = (await appConfigDir()).replace(/[\\/]+$/, '');
+    if (!(await exists(configDir))) {
+      await mkdir(configDir, { recursive: true });
+    }
+    return `${configDir}/${SETTINGS_FILE_NAME}`;
+  }
+
+  /**
+   * 读取设置文件并应用到各模块
+   *
+   * 无效的项使用默认值并输出警告，其他项不受影响；无效的值保留在设置文件中，不会被默认值覆盖
+   */
+  static async load(): Promise<AppSettings> {
+    const saved = await readJsonFile<Record<string, unknown>>(await this.getFilePath(), {});
+    const settings: Record<string, unknown> = structuredClone(DEFAULT_SETTINGS);
+    const preserved: Record<string, unknown> = {};
+    for (const [key, value] of Object.entries(saved)) {
+      if (!(key in DEFAULT_SETTINGS)) {
+        console.warn(` !_! Unknown setting ignored: ${key}`);
+        preserved[key] = value;
+      }
+    }
+
+    for (const key of SETTING_KEYS) {
+      if (key in saved) {
+        try {
+          applySetting(key, saved[key]);
+          settings[key] = saved[key];
+          continue;
+        } catch (error) {
+          console.warn(` !_! Invalid setting, using default: ${key}:`, error);
+          preserved[key] = saved[key];
+        }
+      }
+      applySetting(key, settings[key]);
+    }
+
+    this.current = settings as unknown as AppSettings;
+    this.preserved = preserved;
+    return this.get();
+  }
+
+  /**
+   * 修改设置：应用到各模块并保存（有无效的项时不修改并抛出错误）
+   */
+  static async update(changes: Partial<AppSettings>): Promise<AppSettings> {
+    const keys = Object.keys(changes) as (keyof AppSettings)[];
+    const applied: (keyof AppSettings)[] = [];
+    try {
+      for (const key of keys) {
+        applySetting(key, changes[key]);
+        applied.push(key);
+      }
+    } catch (error) {
+      for (const key of applied) {
+        applySetting(key, this.current[key]);
+      }
+      throw error;
+    }
+
+    this.current = { ...this.current, ...changes };
+    for (const key of keys) {
+      delete this.preserved[key];
+    }
+    await writeJsonFile(await this.getFilePath(), { ...this.current, ...this.preserved });
+    return this.get();
+  }
+}
+
+/**
+ * 检查设置项名称
+ */
+function checkSettingKey(key: string): keyof AppSettings {
+  if (!(key in DEFAULT_SETTINGS)) {
+    throw new Error(`未知的设置项：${key}（可用：${Object.keys(DEFAULT_SETTINGS).join(', ')}）`);
+  }
+  return key as keyof AppSettings;
+}
+
+/**
+ * 查看全部设置
+ *
+ * @command
+ * @category settings
+ * @dangerous false
+ * @name 查看设置
+ * @description 查看当前的全部设置及设置文件位置
+ * @frontend true
+ *
+ * @returns {Promise<AppSettings>} 当前设置
+ */
+export async function getSettings(): Promise<AppSettings> {
+  console.log(`Settings file: ${await Settings.getFilePath()}`);
+  return Settings.get();
+}
+
+/**
+ * 重新读取设置文件
+ *
+ * @command
+ * @category settings
+ * @dangerous false
+ * @name 重新读取设置
+ * @description 重新读取设置文件（手动编辑 settings.json 后使用）并应用到各模块
+ * @frontend true
+ *
+ * @returns {Promise<AppSettings>} 读取后的设置
+ */
+export async function loadSettings(): Promise<AppSettings> {
+  const settings = await Settings.load();
+  console.log(`Loaded settings from ${await Settings.getFilePath()}`);
+  return settings;
+}
+
+/**
+ * 查看一项设置
+ *
+ * @command
+ * @category settings
+ * @dangerous false
+ * @name 查看设置项
+ * @description 查看一项设置的当前值（如 concurrency、executables、audioPresets）
+ * @frontend true
+ *
+ * @param {string} key - 设置项名称
+ * @returns {Promise<unknown>} 设置项的值
+ */
+export async function getSetting(key: string): Promise<unknown> {
+  return Settings.get()[checkSettingKey(key)];
+}
+
+/**
+ * 修改一项设置
+ *
+ * 值按 JSON 解析（如 `64`、`true`、`["FLAC","OGG_Q10"]`、`{"ffmpeg":"D:/ffmpeg.exe"}`），
+ * 不是合法 JSON 时作为字符串；值为空时恢复默认。类型与默认值不一致的值不会保存
+ *
+ * @command
+ * @category settings
+ * @dangerous false
+ * @name 修改设置项
+ * @description 修改一项设置并保存到设置文件，值按 JSON 解析，为空时恢复默认值
+ * @frontend true
+ *
+ * @param {string} key - 设置项名称
+ * @param {string} value - 新的值（JSON）
+ * @returns {Promise<AppSettings>} 修改后的设置
+ */
+export async function setSetting(key: string, value: string): Promise<AppSettings> {
+  const settingKey = checkSettingKey(key);
+  let parsed: unknown = structuredClone(DEFAULT_SETTINGS[settingKey]);
+  if (value.trim() !== '') {
+    try {
+      parsed = JSON.parse(value);
+    } catch {
+      parsed = value;
+    }
+  }
+  const settings = await Settings.update({ [settingKey]: parsed });
+  console.log(`Set ${settingKey} = ${JSON.stringify(settings[settingKey])}`);
+  return settings;
+}
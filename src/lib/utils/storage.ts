@@ -0,0 +1,86 @@
+/**
+ * 应用数据存储工具
+ *
+ * 在应用数据目录下读写 JSON 文件，用于保存跨会话的状态（导入记录等）
+ */
+
+import { appDataDir } from '@tauri-apps/api/path';
+import { exists, mkdir, readTextFile, writeTextFile } from '@tauri-apps/plugin-fs';
+
+/**
+ * 获取应用数据目录下的子目录（不存在时创建）
+ *
+ * @param relativePath - 相对于应用数据目录的路径
+ * @returns 完整路径
+ */
+export async function getAppDataSubDir(relativePath: string): Promise<string> {
+  const baseDir = (await appDataDir()).replace(/[\\/]+$/, '');
+  const fullPath = `${baseDir}/${relativePath}`;
+
+  if (!(await exists(fullPath))) {
+    await mkdir(fullPath, { recursive: true });
+  }
+
+  return fullPath;
+}
+
+/**
+ * 获取应用数据目录下的路径（自动创建父目录）
+ *
+ * @param relativePath - 相对于应用数据目录的路径
+ * @returns 完整路径
+ */
+export async function getAppDataPath(relativePath: string): Promise<string> {
+  const baseDir = (await appDataDir()).replace(/[\\/]+$/, '');
+  const fullPath = `${baseDir}/${relativePath}`;
+
+  const parentDir = fullPath.slice(0, fullPath.lastIndexOf('/'));
+  if (!(await exists(parentDir))) {
+    await mkdir(parentDir, { recursive: true });
+  }
+
+  return fullPath;
+}
+
+/**
+ * 读取 JSON 文件
+ *
+ * @param filePath - 文件路径
+ * @param fallback - 文件不存在或解析失败时返回的默认值
+ */
+export async function readJsonFile<T>(filePath: string, fallback: T): Promise<T> {
+  try {
+    if (!(await exists(filePath))) {
+      return fallback;
+    }
+    return JSON.parse(await readTextFile(filePath)) as T;
+  } catch (error) {
+    console.warn(`Failed to read JSON file: ${filePath}`, error);
+    return fallback;
+  }
+}
+
+/**
+ * 写入 JSON 文件
+ *
+ * @param filePath - 文件路径
+ * @param value - 要写入的值
+ */
+export async function writeJsonFile(filePath: string, value: unknown): Promise<void> {
+  await writeTextFile(filePath, JSON.stringify(value, null, 2));
+}
+
+/**
+ * 获取按目录路径区分的数据文件路径（文件名为路径的 SHA-256 前缀）
+ *
+ * @param relativeDir - 相对于应用数据目录的目录
+ * @param dirPath - 数据所属的目录路径（如根目录）
+ * @returns 完整路径
+ */
+export async function getAppDataPathForDir(relativeDir: string, dirPath: string): Promise<string> {
+  const digest = await crypto.subtle.digest('SHA-256', new TextEncoder().encode(dirPath));
+  const key = [...new Uint8Array(digest).slice(0, 8)]
+    .map((byte) => byte.toString(16).padStart(2, '0'))
+    .join('');
+  return await getAppDataPath(`${relativeDir}/${key}.json`);
+}
@@ -0,0 +1,230 @@
+/**
+ * 资源使用统计
+ *
+ * 任务运行期间定时统计同时运行的外部进程（ffmpeg、flac 等）、并发池中排队和运行的任务数，
+ * 以及读写速度（MB/s），通过 `resource-stats` 事件发送给前端；可设置间隔定时输出到日志。
+ * 用于调整每个磁盘的读取并发数等设置：排队很多而读写速度不再上升时，说明已经到了磁盘的瓶颈
+ */
+
+import { emit } from '@tauri-apps/api/event';
+import { stat } from '@tauri-apps/plugin-fs';
+
+/**
+ * 资源统计事件名称
+ */
+export const RESOURCE_STATS_EVENT = 'resource-stats';
+
+/**
+ * 资源使用统计
+ */
+export interface ResourceStats {
+  timestamp: string;
+  /** 正在运行的任务数 */
+  jobs: number;
+  /** 正在运行的外部进程数 */
+  processes: number;
+  /** 按程序统计的外部进程数 */
+  processesByProgram: Record<string, number>;
+  /** 并发池中等待执行的任务数 */
+  queued: number;
+  /** 并发池中正在执行的任务数 */
+  running: number;
+  /** 累计读取字节数 */
+  bytesRead: number;
+  /** 累计写入字节数 */
+  bytesWritten: number;
+  /** 读取速度（MB/s） */
+  readRate: number;
+  /** 写入速度（MB/s） */
+  writeRate: number;
+}
+
+/**
+ * 发送统计事件的间隔（毫秒）
+ */
+const EVENT_INTERVAL_MS = 1000;
+
+const MB = 1024 * 1024;
+
+/**
+ * 资源使用统计
+ */
+export class ResourceTelemetry {
+  private static activeJobs = 0;
+  private static processes = new Map<string, number>();
+  private static queued = 0;
+  private static running = 0;
+  private static bytesRead = 0;
+  private static bytesWritten = 0;
+
+  /** 定时输出到日志的间隔（秒，0 表示不输出） */
+  private static printInterval = 0;
+
+  private static timer: ReturnType<typeof setInterval> | null = null;
+  private static lastTick = { time: 0, bytesRead: 0, bytesWritten: 0 };
+  private static lastPrinted = 0;
+  private static latest: ResourceStats | null = null;
+
+  /**
+   * 设置定时输出统计到日志的间隔（秒，0 表示不输出）
+   */
+  static setPrintInterval(seconds: number): void {
+    this.printInterval = Math.max(0, seconds);
+  }
+
+  static getPrintInterval(): number {
+    return this.printInterval;
+  }
+
+  /**
+   * 任务开始：有任务运行时定时发送统计
+   */
+  static beginJob(): void {
+    this.activeJobs++;
+    if (!this.timer) {
+      this.lastTick = {
+        time: Date.now(),
+        bytesRead: this.bytesRead,
+        bytesWritten: this.bytesWritten,
+      };
+      this.lastPrinted = this.lastTick.time;
+      this.timer = setInterval(() => this.tick(), EVENT_INTERVAL_MS);
+    }
+  }
+
+  /**
+   * 任务结束：没有任务运行时发送最后一次统计并停止
+   */
+  static endJob(): void {
+    this.activeJobs = Math.max(0, this.activeJobs - 1);
+    if (this.activeJobs === 0 && this.timer) {
+      clearInterval(this.timer);
+      this.timer = null;
+      this.tick();
+    }
+  }
+
+  static processStarted(program: string): void {
+    const name = program.split(/[\\/]/).pop() ?? program;
+    this.processes.set(name, (this.processes.get(name) ?? 0) + 1);
+  }
+
+  static processEnded(program: string): void {
+    const name = program.split(/[\\/]/).pop() ?? program;
+    const count = (this.processes.get(name) ?? 0) - 1;
+    if (count > 0) {
+      this.processes.set(name, count);
+    } else {
+      this.processes.delete(name);
+    }
+  }
+
+  /**
+   * 并发池中任务排队、开始或结束时调用（增量）
+   */
+  static poolChanged(queued: number, running: number): void {
+    this.queued += queued;
+    this.running += running;
+  }
+
+  static addRead(bytes: number): void {
+    this.bytesRead += bytes;
+  }
+
+  static addWritten(bytes: number): void {
+    this.bytesWritten += bytes;
+  }
+
+  /**
+   * 按文件大小计入读写（用于外部进程读写的文件，如转换的输入和输出）
+   *
+   * @returns 读取的文件总大小（供调用方计算处理速度，避免重复读取大小）
+   */
+  static async addFiles(read: string[], written: string[]): Promise<number> {
+    const sizeOf = (path: string) =>
+      stat(path).then(
+        ({ size }) => size,
+        () => 0
+      );
+    let readBytes = 0;
+    for (const path of read) {
+      const size = await sizeOf(path);
+      this.addRead(size);
+      readBytes += size;
+    }
+    for (const path of written) {
+      this.addWritten(await sizeOf(path));
+    }
+    return readBytes;
+  }
+
+  /**
+   * 获取当前统计（读写速度为最近一次发送统计时的速度）
+   */
+  static snapshot(): ResourceStats {
+    return {
+      timestamp: new Date().toISOString(),
+      jobs: this.activeJobs,
+      processes: [...this.processes.values()].reduce((sum, count) => sum + count, 0),
+      processesByProgram: Object.fromEntries(this.processes),
+      queued: this.queued,
+      running: this.running,
+      bytesRead: this.bytesRead,
+      bytesWritten: this.bytesWritten,
+      readRate: this.latest?.readRate ?? 0,
+      writeRate: this.latest?.writeRate ?? 0,
+    };
+  }
+
+  private static tick(): void {
+    const now = Date.now();
+    const seconds = Math.max(0.001, (now - this.lastTick.time) / 1000);
+    const stats: ResourceStats = {
+      ...this.snapshot(),
+      readRate: (this.bytesRead - this.lastTick.bytesRead) / MB / seconds,
+      writeRate: (this.bytesWritten - this.lastTick.bytesWritten) / MB / seconds,
+    };
+    this.lastTick = { time: now, bytesRead: this.bytesRead, bytesWritten: this.bytesWritten };
+    this.latest = stats;
+
+    emit(RESOURCE_STATS_EVENT, stats).catch(() => {});
+    if (this.printInterval > 0 && now - this.lastPrinted >= this.printInterval * 1000) {
+      this.lastPrinted = now;
+      console.log(`[stats] ${formatResourceStats(stats)}`);
+    }
+  }
+}
+
+/**
+ * 格式化资源统计（如 "ffmpeg×4, queued 120, running 64, read 12.3 MB/s, write 4.1 MB/s"）
+ */
+export function formatResourceStats(stats: ResourceStats): string {
+  const processes = Object.entries(stats.processesByProgram).map(
+    ([program, count]) => `${program}×${count}`
+  );
+  return [
+    processes.length > 0 ? processes.join(' ') : 'no processes',
+    `queued ${stats.queued}`,
+    `running ${stats.running}`,
+    `read ${stats.readRate.toFixed(1)} MB/s`,
+    `write ${stats.writeRate.toFixed(1)} MB/s`,
+  ].join(', ');
+}
+
+/**
+ * 查看资源使用情况
+ *
+ * @command
+ * @category logs
+ * @dangerous false
+ * @name 查看资源使用
+ * @description 查看正在运行的外部进程数（ffmpeg 等）、并发池中排队和运行的任务数，以及读写速度，用于调整并发设置
+ * @frontend true
+ *
+ * @returns {Promise<ResourceStats>} 资源使用统计
+ */
+export async function getResourceStats(): Promise<ResourceStats> {
+  const stats = ResourceTelemetry.snapshot();
+  console.log(formatResourceStats(stats));
+  return stats;
+}
@@ -0,0 +1,250 @@
+/**
+ * 为没有引用 BGA 的谱面补上视频
+ *
+ * 有的作品附带了视频文件，但没有任何谱面通过 #BMPxx 引用它，转换后的包在播放器中也不会显示 BGA。
+ * 作品目录中只有一个视频、谱面没有 BGA 时，可以在谱面中加入 #BMPxx 定义和指定小节的 BGA 通道
+ */
+
+import { copyFile, readDir, readFile, writeFile } from '@tauri-apps/plugin-fs';
+import { bytesToLatin1, latin1ToBytes } from '../bms/encoding';
+import { getWorkDirChartFiles, isBmsonFile, VIDEO_FILE_EXTS } from '../bms/scanner';
+import { ScoreSafety } from '../bms/scoreSafety';
+import { getFileExtension } from '../fs/path';
+import { getAppDataPath, writeJsonFile } from '../storage';
+
+/**
+ * 可以补上 BGA 的作品
+ */
+export interface BgaMatchCandidate {
+  workDir: string;
+  /** 作品中唯一的视频文件 */
+  video: string;
+  /** 没有 BGA 的谱面 */
+  charts: string[];
+}
+
+/**
+ * 补上 BGA 的结果
+ */
+export interface BgaFixResult {
+  /** 修改的谱面（相对根目录） */
+  charts: string[];
+  /** 备份目录（模拟运行或没有修改时为空） */
+  backupDir?: string;
+}
+
+const VIDEO_EXTS = new Set<string>(VIDEO_FILE_EXTS);
+
+/**
+ * 头部区域结束的位置：第一行音符数据或 #RANDOM 等控制流命令
+ */
+const HEADER_END_PATTERN = /^#(?:\d{3}[0-9A-Z]{2}:|RANDOM|SETRANDOM|IF|SWITCH|SETSWITCH)/im;
+
+/**
+ * 检查 BMS 谱面是否已有 BGA（引用了视频，或者 BGA 通道有对象）
+ */
+function hasBga(content: string): boolean {
+  for (const match of content.matchAll(/^#BMP[0-9A-Z]{2}[ \t]+(.+)$/gim)) {
+    if (VIDEO_EXTS.has(getFileExtension(match[1].trim()))) {
+      return true;
+    }
+  }
+  for (const match of content.matchAll(/^#\d{3}04:(.*)$/gim)) {
+    if (match[1].trim().match(/.{2}/g)?.some((obj) => obj !== '00')) {
+      return true;
+    }
+  }
+  return false;
+}
+
+/**
+ * 找到谱面中未使用的 #BMP 编号
+ */
+function findFreeBmpIndex(content: string): string | null {
+  const used = new Set(
+    [...content.matchAll(/^#BMP([0-9A-Z]{2})[ \t]/gim)].map((match) => match[1].toUpperCase())
+  );
+  for (let value = 1; value < 36 * 36; value++) {
+    const index = value.toString(36).toUpperCase().padStart(2, '0');
+    if (!used.has(index)) {
+      return index;
+    }
+  }
+  return null;
+}
+
+/**
+ * 在谱面中加入 BGA 定义和通道
+ *
+ * #BMPxx 插入到头部区域末尾（第一行音符数据或控制流命令之前），BGA 通道追加到文件末尾
+ */
+function addBgaLines(
+  content: string,
+  video: string,
+  measure: number
+): { content: string; lines: string[] } | null {
+  const index = findFreeBmpIndex(content);
+  if (index === null) {
+    return null;
+  }
+
+  const newline = content.includes('\r\n') ? '\r\n' : '\n';
+  const definition = `#BMP${index} ${video}`;
+  const channel = `#${measure.toString().padStart(3, '0')}04:${index}`;
+
+  const insertAt = content.match(HEADER_END_PATTERN)?.index;
+  let result =
+    insertAt === undefined
+      ? `${content.replace(/\s*$/, '')}${newline}${definition}${newline}`
+      : `${content.slice(0, insertAt)}${definition}${newline}${content.slice(insertAt)}`;
+  if (!result.endsWith(newline)) {
+    result += newline;
+  }
+  result += `${channel}${newline}`;
+
+  return { content: result, lines: [definition, channel] };
+}
+
+/**
+ * 检查作品目录：只有一个视频文件时返回没有 BGA 的谱面
+ */
+async function findWorkCandidate(workDir: string): Promise<BgaMatchCandidate | null> {
+  const videos = (await readDir(workDir))
+    .filter((entry) => entry.isFile && VIDEO_EXTS.has(getFileExtension(entry.name)))
+    .map((entry) => entry.name);
+  if (videos.length !== 1) {
+    return null;
+  }
+
+  const charts: string[] = [];
+  for (const chart of await getWorkDirChartFiles(workDir)) {
+    // BMSON 的 BGA 结构不同，不处理
+    if (isBmsonFile(chart)) {
+      continue;
+    }
+    const content = bytesToLatin1(await readFile(`${workDir}/${chart}`));
+    if (!hasBga(content)) {
+      charts.push(chart);
+    }
+  }
+
+  return charts.length > 0 ? { workDir, video: videos[0], charts: charts.sort() } : null;
+}
+
+async function findCandidates(rootDir: string): Promise<BgaMatchCandidate[]> {
+  const candidates: BgaMatchCandidate[] = [];
+  for (const entry of await readDir(rootDir)) {
+    if (!entry.isDirectory || !entry.name) {
+      continue;
+    }
+    const candidate = await findWorkCandidate(`${rootDir}/${entry.name}`);
+    if (candidate) {
+      candidates.push(candidate);
+    }
+  }
+  return candidates;
+}
+
+/**
+ * 查找附带视频但谱面没有 BGA 的作品
+ *
+ * @command
+ * @category bmsfolder
+ * @dangerous false
+ * @name 查找未引用的 BGA 视频
+ * @description 列出只有一个视频文件、但有谱面没有引用任何 BGA 的作品
+ * @frontend true
+ *
+ * @param {string} rootDir - 根目录路径
+ *
+ * @returns {Promise<BgaMatchCandidate[]>} 可以补上 BGA 的作品
+ */
+export async function findUnreferencedBga(rootDir: string): Promise<BgaMatchCandidate[]> {
+  const candidates = await findCandidates(rootDir);
+  for (const { workDir, video, charts } of candidates) {
+    console.log(`${workDir}: ${video} not used by ${charts.join(', ')}`);
+  }
+  console.log(`Found ${candidates.length} works with an unreferenced BGA video.`);
+  return candidates;
+}
+
+/**
+ * 为没有 BGA 的谱面加入作品中的视频
+ *
+ * @command
+ * @category bmsfolder
+ * @dangerous true
+ * @name 补上 BGA 视频
+ * @description 作品只有一个视频文件时，在没有 BGA 的谱面中加入 #BMPxx 定义和指定小节的 BGA 通道，修改前备份谱面
+ * @frontend true
+ *
+ * @param {string} rootDir - 根目录路径
+ * @param {number} measure - BGA 开始的小节号（0-999）
+ * @param {boolean} dryRun - 模拟运行（只列出要加入的行）
+ *
+ * @returns {Promise<BgaFixResult>} 修改的谱面
+ */
+export async function addMissingBga(
+  rootDir: string,
+  measure: number,
+  dryRun: boolean
+): Promise<BgaFixResult> {
+  if (!Number.isInteger(measure) || measure < 0 || measure > 999) {
+    throw new Error(`小节号必须是 0-999 的整数：${measure}`);
+  }
+
+  const result: BgaFixResult = { charts: [] };
+  const backupDir = `bga-backups/${new Date().toISOString().replace(/[:.]/g, '-')}`;
+  const workName = (workDir: string) => workDir.slice(rootDir.length + 1);
+
+  // 先列出所有修改，检查成绩关联后再写入
+  const planned: {
+    chartPath: string;
+    relative: string;
+    video: string;
+    added: { content: string; lines: string[] };
+  }[] = [];
+  for (const { workDir, video, charts } of await findCandidates(rootDir)) {
+    // 谱面按原编码写回，非 ASCII 文件名无法确定应写入的字节
+    if (/[^\x20-\x7e]/.test(video)) {
+      console.warn(` !_! ${workName(workDir)}: video name "${video}" is not ASCII, skipping.`);
+      continue;
+    }
+
+    for (const chart of charts) {
+      const chartPath = `${workDir}/${chart}`;
+      const relative = `${workName(workDir)}/${chart}`;
+      const added = addBgaLines(bytesToLatin1(await readFile(chartPath)), video, measure);
+      if (!added) {
+        console.warn(` !_! No free #BMP index in ${relative}, skipping.`);
+        continue;
+      }
+      planned.push({ chartPath, relative, video, added });
+    }
+  }
+
+  const hashChanged = planned.map(({ relative }) => relative);
+  ScoreSafety.check('Add BGA', { hashChanged, pathChanged: [] }, dryRun);
+
+  for (const { chartPath, relative, video, added } of planned) {
+    if (dryRun) {
+      console.log(`[dry-run] Would add to ${relative}:`);
+      for (const line of added.lines) {
+        console.log(`[dry-run]   + ${line}`);
+      }
+    } else {
+      const backupPath = await getAppDataPath(`${backupDir}/${relative}`);
+      result.backupDir ??= backupPath.slice(0, -relative.length - 1);
+      await copyFile(chartPath, backupPath);
+      await writeFile(chartPath, latin1ToBytes(added.content));
+      console.log(`Added BGA ${video} to ${relative}`);
+    }
+    result.charts.push(relative);
+  }
+
+  if (result.backupDir) {
+    await writeJsonFile(`${result.backupDir}/changes.json`, result);
+  }
+  console.log(`${dryRun ? '[dry-run] ' : ''}BGA added to ${result.charts.length} charts`);
+  return result;
+}
@@ -0,0 +1,278 @@
+/**
+ * 作品 BGA 统计
+ *
+ * 判断作品有没有 BGA（BGA 通道引用视频，或者引用连续的图片序列），
+ * 并比较 BGA 的时长和歌曲长度、读取分辨率，用于挑选 BGA 完整的作品
+ */
+
+import { readDir, readFile } from '@tauri-apps/plugin-fs';
+import { getBmsFileStr } from '../bms/encoding';
+import { getWorkDirChartFiles, isBmsonFile, VIDEO_FILE_EXTS } from '../bms/scanner';
+import { getFileExtension, getFileStem } from '../fs/path';
+import { MediaProbe } from '../media/probe';
+
+/**
+ * 作品的 BGA 信息
+ */
+export interface BgaSummary {
+  /** video：引用视频；sequence：只引用图片（逐帧的 BGA）；none：没有 BGA */
+  kind: 'video' | 'sequence' | 'none';
+  /** 统计所用的谱面 */
+  chart?: string;
+  /** 引用的视频文件（sequence 时为空） */
+  videos: string[];
+  /** BGA 通道引用的不同图片或视频数 */
+  frameCount: number;
+  /** BGA 开始的时间（秒） */
+  start?: number;
+  /** BGA 时长（秒）：视频为文件时长，图片序列为第一帧到最后一帧 */
+  duration?: number;
+  /** 歌曲长度（秒，到最后一个音符或 BGM） */
+  songLength?: number;
+  /** BGA 覆盖歌曲的比例（0-1），时长未知时为空 */
+  coverage?: number;
+  width?: number;
+  height?: number;
+}
+
+/**
+ * 谱面中的一个对象
+ */
+interface ChartObject {
+  measure: number;
+  /** 小节内的位置（0-1） */
+  position: number;
+  channel: string;
+  value: string;
+}
+
+/**
+ * 谱面中 BGA 的时间信息
+ */
+interface ChartBga {
+  chart: string;
+  /** BGA 通道引用的文件 */
+  files: string[];
+  start: number;
+  end: number;
+  /** 歌曲长度（秒） */
+  songLength: number;
+}
+
+const VIDEO_EXTS = new Set<string>(VIDEO_FILE_EXTS);
+
+/**
+ * 计入歌曲长度的通道：BGM 和 1P / 2P 的可见音符、长条
+ */
+const SONG_CHANNEL_PATTERN = /^(01|[1256][1-9])$/;
+
+function parseObjects(content: string): { objects: ChartObject[]; lengths: Map<number, number> } {
+  const objects: ChartObject[] = [];
+  const lengths = new Map<number, number>();
+  for (const match of content.matchAll(/^#(\d{3})([0-9A-Z]{2}):(.*)$/gim)) {
+    const measure = parseInt(match[1], 10);
+    const channel = match[2].toUpperCase();
+    const data = match[3].trim();
+    if (channel === '02') {
+      lengths.set(measure, parseFloat(data) || 1);
+      continue;
+    }
+    const values = data.match(/.{2}/g) ?? [];
+    values.forEach((value, i) => {
+      if (value !== '00') {
+        objects.push({ measure, position: i / values.length, channel, value: value.toUpperCase() });
+      }
+    });
+  }
+  return { objects, lengths };
+}
+
+/**
+ * 根据 BPM 变化计算对象的时间（秒），不计 STOP
+ */
+function createTimer(
+  content: string,
+  objects: ChartObject[],
+  lengths: Map<number, number>
+): (object: ChartObject) => number {
+  const maxMeasure = Math.max(0, ...objects.map((object) => object.measure));
+  const measureBeats: number[] = [0];
+  for (let measure = 0; measure <= maxMeasure; measure++) {
+    measureBeats.push(measureBeats[measure] + 4 * (lengths.get(measure) ?? 1));
+  }
+  const beatOf = (object: ChartObject) =>
+    measureBeats[object.measure] + object.position * 4 * (lengths.get(object.measure) ?? 1);
+
+  const extendedBpms = new Map<string, number>();
+  for (const match of content.matchAll(/^#BPM([0-9A-Z]{2})[ \t]+([\d.]+)/gim)) {
+    extendedBpms.set(match[1].toUpperCase(), parseFloat(match[2]));
+  }
+  const changes = objects
+    .map((object) => ({
+      beat: beatOf(object),
+      bpm:
+        object.channel === '03'
+          ? parseInt(object.value, 16)
+          : object.channel === '08'
+            ? extendedBpms.get(object.value)
+            : undefined,
+    }))
+    .filter((change): change is { beat: number; bpm: number } => !!change.bpm && change.bpm > 0)
+    .sort((a, b) => a.beat - b.beat);
+  const initialBpm = parseFloat(content.match(/^#BPM[ \t]+([\d.]+)/im)?.[1] ?? '') || 130;
+
+  return (object) => {
+    const beat = beatOf(object);
+    let time = 0;
+    let lastBeat = 0;
+    let bpm = initialBpm;
+    for (const change of changes) {
+      if (change.beat > beat) {
+        break;
+      }
+      time += ((change.beat - lastBeat) * 60) / bpm;
+      lastBeat = change.beat;
+      bpm = change.bpm;
+    }
+    return time + ((beat - lastBeat) * 60) / bpm;
+  };
+}
+
+/**
+ * 读取谱面的歌曲长度，以及 BGA 通道的时间范围和引用的文件（没有 BGA 时 files 为空）
+ */
+function readChartBga(chart: string, content: string): ChartBga {
+  const { objects, lengths } = parseObjects(content);
+  const timeOf = createTimer(content, objects, lengths);
+  const songLength = Math.max(
+    0,
+    ...objects.filter((object) => SONG_CHANNEL_PATTERN.test(object.channel)).map(timeOf)
+  );
+
+  const bgaObjects = objects.filter((object) => object.channel === '04');
+  const definitions = new Map<string, string>();
+  for (const match of content.matchAll(/^#BMP([0-9A-Z]{2})[ \t]+(.+)$/gim)) {
+    definitions.set(match[1].toUpperCase(), match[2].trim());
+  }
+  const files = [
+    ...new Set(bgaObjects.map((object) => definitions.get(object.value)).filter((f) => !!f)),
+  ] as string[];
+
+  const times = bgaObjects.map(timeOf);
+  const start = times.length > 0 ? Math.min(...times) : 0;
+  return { chart, files, start, end: Math.max(start, ...times), songLength };
+}
+
+/**
+ * 找到定义对应的文件：文件名不区分大小写，找不到时接受同名的视频（如转换后的 .mp4 对应 .wmv 定义）
+ */
+function resolveFile(name: string, files: string[]): string | undefined {
+  const lower = name.replace(/\\/g, '/').toLowerCase();
+  const exact = files.find((file) => file.toLowerCase() === lower);
+  if (exact || !VIDEO_EXTS.has(getFileExtension(lower))) {
+    return exact;
+  }
+  const stem = getFileStem(lower);
+  return files.find(
+    (file) => getFileStem(file.toLowerCase()) === stem && VIDEO_EXTS.has(getFileExtension(file))
+  );
+}
+
+/**
+ * 统计作品的 BGA
+ *
+ * 取 BGA 引用最多的谱面统计；BMSON 谱面不统计
+ *
+ * @command
+ * @category bmsfolder
+ * @dangerous false
+ * @name 统计 BGA
+ * @description 判断作品的 BGA 是视频还是图片序列，比较 BGA 时长和歌曲长度，读取分辨率（需要 ffprobe）
+ * @frontend true
+ *
+ * @param {string} workDir - 作品目录路径
+ *
+ * @returns {Promise<BgaSummary>} BGA 信息
+ */
+export async function summarizeBga(workDir: string): Promise<BgaSummary> {
+  let best: ChartBga | null = null;
+  for (const chart of (await getWorkDirChartFiles(workDir)).sort()) {
+    if (isBmsonFile(chart)) {
+      continue;
+    }
+    const content = getBmsFileStr(new Uint8Array(await readFile(`${workDir}/${chart}`)));
+    const bga = readChartBga(chart, content);
+    if (!best || bga.files.length > best.files.length) {
+      best = bga;
+    }
+  }
+  if (!best || best.files.length === 0) {
+    console.log(`${workDir}: no BGA`);
+    return { kind: 'none', videos: [], frameCount: 0, songLength: best?.songLength };
+  }
+
+  const dirFiles = (await readDir(workDir))
+    .filter((entry) => entry.isFile)
+    .map((entry) => entry.name);
+  const resolved = best.files
+    .map((name) => resolveFile(name, dirFiles))
+    .filter((file): file is string => !!file);
+  const videos = resolved.filter((file) => VIDEO_EXTS.has(getFileExtension(file)));
+
+  const summary: BgaSummary = {
+    kind: videos.length > 0 ? 'video' : 'sequence',
+    chart: best.chart,
+    videos,
+    frameCount: best.files.length,
+    start: best.start,
+    songLength: best.songLength,
+  };
+  if (videos.length > 0) {
+    // 视频从第一次出现开始播放到结束，时长取最长的视频
+    const durations = await Promise.all(
+      videos.map((video) => MediaProbe.getMediaDuration(`${workDir}/${video}`))
+    );
+    const known = durations.filter((duration): duration is number => duration !== null);
+    summary.duration = known.length > 0 ? Math.max(...known) : undefined;
+  } else {
+    summary.duration = best.end - best.start;
+  }
+  if (summary.duration !== undefined && best.songLength > 0) {
+    const covered = Math.min(best.start + summary.duration, best.songLength) - best.start;
+    summary.coverage = Math.max(0, covered / best.songLength);
+  }
+
+  const sample = videos[0] ?? resolved[0];
+  const size = sample ? await MediaProbe.getVideoSize(`${workDir}/${sample}`) : null;
+  summary.width = size?.width;
+  summary.height = size?.height;
+
+  console.log(`${workDir}: ${formatBgaSummary(summary)}`);
+  return summary;
+}
+
+function formatTime(seconds: number): string {
+  const total = Math.round(seconds);
+  return `${Math.floor(total / 60)}:${String(total % 60).padStart(2, '0')}`;
+}
+
+/**
+ * 格式化 BGA 信息（如 "video 1280x720, 1:52 / 2:05 (90%)"）
+ */
+export function formatBgaSummary(summary: BgaSummary): string {
+  if (summary.kind === 'none') {
+    return 'no BGA';
+  }
+  const parts: string[] = [
+    summary.kind === 'video' ? 'video' : `sequence (${summary.frameCount} frames)`,
+  ];
+  if (summary.width && summary.height) {
+    parts[0] += ` ${summary.width}x${summary.height}`;
+  }
+  const duration = summary.duration !== undefined ? formatTime(summary.duration) : '?';
+  const songLength = summary.songLength ? formatTime(summary.songLength) : '?';
+  const coverage =
+    summary.coverage !== undefined ? ` (${Math.round(summary.coverage * 100)}%)` : '';
+  parts.push(`${duration} / ${songLength}${coverage}`);
+  return parts.join(', ');
+}
@@ -0,0 +1,211 @@
+/**
+ * 谱面补丁
+ *
+ * 作者发布的更新包常常只包含修正后的谱面（有时附带新增的音频）。
+ * 直接解压到作品目录覆盖谱面即可，不必重新导入整个作品：替换前备份原谱面，
+ * 检查补丁谱面引用的音频和图片在作品中都存在，并在导入来源中记录这次更新
+ */
+
+import { copyFile, exists, mkdir, readDir } from '@tauri-apps/plugin-fs';
+import { isChartFile } from '../bms/scanner';
+import { ScoreSafety } from '../bms/scoreSafety';
+import { CacheManager } from '../fs/cache';
+import { cleanMacosArtifacts } from '../fs/cleanup';
+import { getDirectoryPath, getFileName } from '../fs/path';
+import { extractArchiveChecked } from '../rawpack/extract';
+import { ProvenanceIndex } from '../rawpack/provenance';
+import { getAppDataPath, writeJsonFile } from '../storage';
+import { listRelativeFiles, mediaKey, readChartMediaKeys } from './splitObj';
+
+/**
+ * 谱面补丁应用结果
+ */
+export interface ChartPatchResult {
+  workDir: string;
+  archiveName: string;
+  /** 已应用过同一补丁而跳过 */
+  alreadyApplied: boolean;
+  /** 新增的谱面 */
+  addedCharts: string[];
+  /** 替换的谱面 */
+  replacedCharts: string[];
+  /** 补丁中谱面以外的文件（新增或替换） */
+  otherFiles: string[];
+  /** 补丁谱面引用、但作品和补丁中都不存在的文件 */
+  missingMedia: string[];
+  /** 被替换文件的备份目录 */
+  backupDir?: string;
+}
+
+/**
+ * 列出时最多显示的缺失文件数
+ */
+const MAX_LISTED_MISSING = 10;
+
+/**
+ * 解压结果只有一个文件夹时进入该文件夹（补丁常带一层作品名文件夹）
+ */
+async function findPatchRoot(extractDir: string): Promise<string> {
+  let dir = extractDir;
+  while (true) {
+    const entries = (await readDir(dir)).filter((entry) => entry.name);
+    if (entries.length !== 1 || !entries[0].isDirectory) {
+      return dir;
+    }
+    dir = `${dir}/${entries[0].name}`;
+  }
+}
+
+/**
+ * 应用谱面补丁
+ *
+ * 补丁中的文件按相对路径对应到作品目录（不区分大小写，沿用作品中已有文件的大小写）
+ *
+ * @command
+ * @category bmsfolder
+ * @dangerous true
+ * @name 应用谱面补丁
+ * @description 将只包含谱面（可附带新增音频）的更新包解压到作品目录：备份被替换的谱面，检查引用的音频和图片都存在，并记录到导入来源中
+ * @frontend true
+ *
+ * @param {string} archivePath - 补丁压缩包路径
+ * @param {string} workDir - 作品目录路径
+ * @param {boolean} force - 补丁已应用过或引用的文件缺失时仍然应用
+ * @param {boolean} dryRun - 模拟运行（不实际执行）
+ *
+ * @returns {Promise<ChartPatchResult>} 应用结果
+ */
+export async function applyChartPatch(
+  archivePath: string,
+  workDir: string,
+  force: boolean = false,
+  dryRun: boolean = false
+): Promise<ChartPatchResult> {
+  const result: ChartPatchResult = {
+    workDir,
+    archiveName: getFileName(archivePath),
+    alreadyApplied: false,
+    addedCharts: [],
+    replacedCharts: [],
+    otherFiles: [],
+    missingMedia: [],
+  };
+
+  const provenance = await ProvenanceIndex.load();
+  const { record, archiveHash } = await provenance.lookup(archivePath);
+  if (record?.targetDir === workDir && !force) {
+    console.log(`Skipping ${archivePath}: already applied to ${workDir} at ${record.importedAt}`);
+    result.alreadyApplied = true;
+    return result;
+  }
+
+  const written = await CacheManager.withEntry('chart-patch', async (entryDir) => {
+    console.log(`Extracting ${archivePath} to ${entryDir}`);
+    const unsafe = await extractArchiveChecked(archivePath, entryDir);
+    if (unsafe) {
+      throw new Error(`补丁压缩包不安全：${unsafe.detail}`);
+    }
+    await cleanMacosArtifacts(entryDir, false);
+
+    const patchDir = await findPatchRoot(entryDir);
+    const patchFiles = await listRelativeFiles(patchDir);
+    if (!patchFiles.some(isChartFile)) {
+      throw new Error(`补丁中没有谱面：${archivePath}`);
+    }
+
+    // 对应到作品中已有的文件
+    const workFiles = await listRelativeFiles(workDir);
+    const existing = new Map(workFiles.map((file) => [file.toLowerCase(), file]));
+    const targets = patchFiles.map((file) => ({
+      source: `${patchDir}/${file}`,
+      target: existing.get(file.toLowerCase()) ?? file,
+      replaced: existing.has(file.toLowerCase()),
+    }));
+    for (const { target, replaced } of targets) {
+      if (!isChartFile(target)) {
+        result.otherFiles.push(target);
+      } else if (replaced) {
+        result.replacedCharts.push(target);
+      } else {
+        result.addedCharts.push(target);
+      }
+    }
+
+    // 检查补丁谱面引用的文件
+    const available = new Set([...workFiles, ...patchFiles].map(mediaKey));
+    const missing = new Set<string>();
+    for (const { source, target } of targets.filter(({ target }) => isChartFile(target))) {
+      for (const key of await readChartMediaKeys(source)) {
+        if (!available.has(key)) {
+          missing.add(`${target}: ${key}`);
+        }
+      }
+    }
+    result.missingMedia = [...missing];
+    if (result.missingMedia.length > 0) {
+      console.warn(
+        ` !_! ${result.missingMedia.length} files referenced by the patched charts are missing:`
+      );
+      for (const entry of result.missingMedia.slice(0, MAX_LISTED_MISSING)) {
+        console.warn(`   ${entry}`);
+      }
+      if (result.missingMedia.length > MAX_LISTED_MISSING) {
+        console.warn(`   ... and ${result.missingMedia.length - MAX_LISTED_MISSING} more`);
+      }
+      if (!force && !dryRun) {
+        throw new Error(
+          `补丁谱面引用的 ${result.missingMedia.length} 个文件在作品中不存在，` +
+            '可能需要完整的更新包；如确认要应用，请勾选强制应用'
+        );
+      }
+    }
+
+    ScoreSafety.check(
+      'Apply chart patch',
+      { hashChanged: result.replacedCharts, pathChanged: [] },
+      dryRun
+    );
+
+    const backupDir = `chart-patches/${new Date().toISOString().replace(/[:.]/g, '-')}`;
+    const dirName = getFileName(workDir);
+    for (const { source, target, replaced } of targets) {
+      const targetPath = `${workDir}/${target}`;
+      if (dryRun) {
+        console.log(`[dry-run] Would ${replaced ? 'replace' : 'add'} ${target}`);
+        continue;
+      }
+      if (replaced) {
+        const relative = `${dirName}/${target}`;
+        const backupPath = await getAppDataPath(`${backupDir}/${relative}`);
+        result.backupDir ??= backupPath.slice(0, -relative.length - 1);
+        await copyFile(targetPath, backupPath);
+      } else if (!(await exists(getDirectoryPath(targetPath)))) {
+        await mkdir(getDirectoryPath(targetPath), { recursive: true });
+      }
+      await copyFile(source, targetPath);
+      console.log(`${replaced ? 'Replaced' : 'Added'} ${target}`);
+    }
+    return targets.map(({ target }) => target);
+  });
+
+  if (dryRun) {
+    console.log(
+      `[dry-run] Would apply ${result.archiveName} to ${workDir}: ` +
+        `${result.replacedCharts.length} charts replaced, ${result.addedCharts.length} added`
+    );
+    return result;
+  }
+
+  if (result.backupDir) {
+    await writeJsonFile(`${result.backupDir}/changes.json`, result);
+  }
+  await provenance.record(archivePath, workDir, archiveHash, {
+    kind: 'chart_patch',
+    files: written,
+  });
+  console.log(
+    `Applied ${result.archiveName} to ${workDir}: ` +
+      `${result.replacedCharts.length} charts replaced, ${result.addedCharts.length} added`
+  );
+  return result;
+}
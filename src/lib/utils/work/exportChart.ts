@@ -0,0 +1,145 @@
+/**
+ * 导出单个谱面
+ *
+ * 向难度表提案或分享单个难度时不需要整个作品（有时多达数百 MB），
+ * 这里只复制谱面本身及其引用的媒体文件（#WAV、#BMP 和 #STAGEFILE 等头部图片），
+ * 引用的扩展名与实际文件不同时按播放器的规则匹配同名的其他格式
+ */
+
+import { copyFile, exists, mkdir, stat } from '@tauri-apps/plugin-fs';
+import { isBmsonFile, MEDIA_FILE_EXTS } from '../bms/scanner';
+import { readBmsFile } from '../bms/encoding';
+import { getDirectoryPath, getFileExtension, getFileName, normalizePath } from '../fs/path';
+import { formatSize } from '../fs/sizeTally';
+import { listRelativeFiles, mediaKey, readChartMediaKeys } from './splitObj';
+
+/**
+ * 谱面导出结果
+ */
+export interface ChartExportResult {
+  chart: string;
+  outDir: string;
+  /** 复制的文件（相对作品目录，包括谱面本身） */
+  copied: string[];
+  /** 没有找到文件的引用（不含扩展名） */
+  missing: string[];
+  /** 复制的总大小（字节） */
+  totalSize: number;
+}
+
+/**
+ * BMS 头部中引用图片或音频的命令
+ */
+const HEADER_MEDIA_PATTERN = /^#(STAGEFILE|BANNER|BACKBMP|PREVIEW)[ \t]+(.+)$/gim;
+
+/**
+ * bmson 中引用图片或音频的字段
+ */
+const BMSON_MEDIA_FIELDS = ['back_image', 'eyecatch_image', 'banner_image', 'preview_music'];
+
+/**
+ * 读取头部引用的媒体文件（匹配键）
+ */
+async function readHeaderMediaKeys(chartPath: string): Promise<string[]> {
+  const content = await readBmsFile(chartPath);
+  if (isBmsonFile(chartPath)) {
+    try {
+      const info = (JSON.parse(content) as { info?: Record<string, unknown> }).info ?? {};
+      const values = BMSON_MEDIA_FIELDS.map((field) => info[field]);
+      return values
+        .filter((value): value is string => typeof value === 'string' && value.length > 0)
+        .map(mediaKey);
+    } catch {
+      return [];
+    }
+  }
+  return [...content.matchAll(HEADER_MEDIA_PATTERN)].map((match) => mediaKey(match[2].trim()));
+}
+
+/**
+ * 导出单个谱面及其引用的媒体文件
+ *
+ * @command
+ * @category bmsfolder
+ * @dangerous true
+ * @name 导出单个谱面
+ * @description 将谱面及其引用的媒体文件（自动匹配不同扩展名）复制到输出目录，生成只含该难度的最小文件夹
+ * @frontend true
+ *
+ * @param {string} chartPath - 谱面文件路径
+ * @param {string} outDir - 输出目录
+ * @param {boolean} dryRun - 模拟运行（只列出要复制的文件）
+ *
+ * @returns {Promise<ChartExportResult>} 复制的文件和缺失的引用
+ */
+export async function exportChart(
+  chartPath: string,
+  outDir: string,
+  dryRun: boolean
+): Promise<ChartExportResult> {
+  const workDir = getDirectoryPath(chartPath);
+  const chart = getFileName(chartPath);
+  const normalizedOut = normalizePath(outDir).replace(/\/+$/, '');
+  if (`${normalizedOut}/`.startsWith(`${normalizePath(workDir)}/`)) {
+    throw new Error(`输出目录不能位于作品目录中：${outDir}`);
+  }
+
+  const keys = new Set([
+    ...(await readChartMediaKeys(chartPath)),
+    ...(await readHeaderMediaKeys(chartPath)),
+  ]);
+
+  // 同一个键可能对应多个格式的文件（如 a.wav 和 a.ogg），只复制按 MEDIA_FILE_EXTS 顺序的第一个
+  const extRank = (file: string) =>
+    (MEDIA_FILE_EXTS as readonly string[]).indexOf(getFileExtension(file));
+  const filesByKey = new Map<string, string[]>();
+  for (const file of await listRelativeFiles(workDir)) {
+    if (!(MEDIA_FILE_EXTS as readonly string[]).includes(getFileExtension(file))) {
+      continue;
+    }
+    const key = mediaKey(file);
+    if (keys.has(key)) {
+      filesByKey.set(key, [...(filesByKey.get(key) ?? []), file]);
+    }
+  }
+
+  const result: ChartExportResult = {
+    chart,
+    outDir,
+    copied: [chart],
+    missing: [],
+    totalSize: 0,
+  };
+  for (const key of [...keys].sort()) {
+    const candidates = filesByKey.get(key);
+    if (!candidates) {
+      result.missing.push(key);
+      continue;
+    }
+    result.copied.push(candidates.sort((a, b) => extRank(a) - extRank(b))[0]);
+  }
+
+  for (const file of result.copied) {
+    result.totalSize += (await stat(`${workDir}/${file}`)).size;
+    if (dryRun) {
+      console.log(`[dry-run] Would copy: ${file}`);
+      continue;
+    }
+    const target = `${outDir}/${file}`;
+    const targetDir = getDirectoryPath(target);
+    if (!(await exists(targetDir))) {
+      await mkdir(targetDir, { recursive: true });
+    }
+    await copyFile(`${workDir}/${file}`, target);
+  }
+
+  if (result.missing.length > 0) {
+    const missing = result.missing.join(', ');
+    console.warn(` !_! ${result.missing.length} referenced files not found: ${missing}`);
+  }
+  console.log(
+    `${dryRun ? '[dry-run] ' : ''}Exported ${chart} with ${result.copied.length - 1} media files ` +
+      `(${formatSize(result.totalSize)}) to ${outDir}`
+  );
+  return result;
+}
@@ -0,0 +1,195 @@
+/**
+ * 作品名称的备用来源
+ *
+ * 谱面缺少 #TITLE / #ARTIST 时按设置的优先顺序依次尝试：
+ * BMSON 的 info 字段、导入时的压缩包文件名、按编号匹配的活动作品信息、bmssearch 上的作品信息，
+ * 最后可以选择保留原文件夹名，避免生成 "!!! UnknownTitle !!!" 目录
+ */
+
+import { readDir, readTextFile } from '@tauri-apps/plugin-fs';
+import type { BMSEvent } from '../../types/enums';
+import { loadEventWorkInfo } from '../event/scrape';
+import { getFileExtension } from '../fs/path';
+import { BmsSearchClient } from '../library/bmsSearch';
+import { ProvenanceIndex } from '../rawpack/provenance';
+
+/**
+ * 备用名称来源
+ */
+export enum NameFallbackSource {
+  /** BMSON 文件的 info.title / info.artist */
+  Bmson = 'bmson',
+  /** 导入时的压缩包文件名（只提供标题） */
+  ArchiveName = 'archive_name',
+  /** 按文件夹编号匹配的活动作品信息 */
+  EventScrape = 'event_scrape',
+  /** 按谱面哈希在 bmssearch.net 上查询（需要联网并计算谱面哈希，默认不使用） */
+  BmsSearch = 'bms_search',
+  /** 保留原文件夹名，不重命名 */
+  FolderName = 'folder_name',
+}
+
+/**
+ * 默认的备用来源顺序
+ */
+export const DEFAULT_NAME_FALLBACK_ORDER: readonly NameFallbackSource[] = [
+  NameFallbackSource.Bmson,
+  NameFallbackSource.ArchiveName,
+  NameFallbackSource.EventScrape,
+  NameFallbackSource.FolderName,
+];
+
+/**
+ * 备用名称来源设置
+ */
+export class NameFallback {
+  private static order: NameFallbackSource[] = [...DEFAULT_NAME_FALLBACK_ORDER];
+  private static event: BMSEvent | null = null;
+
+  /**
+   * 设置备用来源的优先顺序（不在列表中的来源不使用），为空时恢复默认
+   */
+  static setOrder(order: NameFallbackSource[] | null): void {
+    this.order = order && order.length > 0 ? [...new Set(order)] : [...DEFAULT_NAME_FALLBACK_ORDER];
+  }
+
+  static getOrder(): NameFallbackSource[] {
+    return [...this.order];
+  }
+
+  /**
+   * 设置按编号匹配作品信息时使用的活动
+   */
+  static setEvent(event: BMSEvent | null): void {
+    this.event = event;
+  }
+
+  static getEvent(): BMSEvent | null {
+    return this.event;
+  }
+}
+
+/**
+ * 补全后的作品名称
+ */
+export interface FallbackNameInfo {
+  title?: string;
+  artist?: string;
+  /** 各字段取自哪个来源 */
+  sources: { title?: NameFallbackSource; artist?: NameFallbackSource };
+  /** 仍有缺失且设置了保留原文件夹名 */
+  keepFolderName: boolean;
+}
+
+type PartialName = { title?: string; artist?: string };
+
+function nonEmpty(value: unknown): string | undefined {
+  return typeof value === 'string' && value.trim() ? value.trim() : undefined;
+}
+
+/**
+ * 从目录中的 BMSON 文件读取 info 字段
+ */
+async function readBmsonName(workDir: string): Promise<PartialName> {
+  const result: PartialName = {};
+  for (const entry of await readDir(workDir)) {
+    if (entry.isDirectory || getFileExtension(entry.name) !== 'bmson') {
+      continue;
+    }
+    try {
+      const info = JSON.parse(await readTextFile(`${workDir}/${entry.name}`))?.info ?? {};
+      result.title ??= nonEmpty(info.title);
+      result.artist ??= nonEmpty(info.artist);
+    } catch {
+      continue;
+    }
+    if (result.title && result.artist) {
+      break;
+    }
+  }
+  return result;
+}
+
+/**
+ * 从导入记录中的压缩包文件名取标题（去掉扩展名和下载时加上的编号）
+ */
+async function readArchiveName(workDir: string): Promise<PartialName> {
+  const record = (await ProvenanceIndex.load()).findByTargetDir(workDir);
+  if (!record) {
+    return {};
+  }
+  const title = record.archiveName
+    .replace(/(\.tar)?\.[^.]+$/, '')
+    .replace(/^\d+\s+/, '')
+    .replace(/_/g, ' ');
+  return { title: nonEmpty(title) };
+}
+
+/**
+ * 按文件夹名开头的编号读取活动作品信息
+ */
+async function readEventScrape(workDir: string): Promise<PartialName> {
+  const event = NameFallback.getEvent();
+  const dirName = workDir.split(/[\\/]/).pop() ?? workDir;
+  const num = dirName.match(/^(\d+)(?:\s|$)/)?.[1];
+  if (event === null || num === undefined) {
+    return {};
+  }
+  const info = (await loadEventWorkInfo(event))[parseInt(num, 10)];
+  return { title: nonEmpty(info?.title), artist: nonEmpty(info?.artist) };
+}
+
+/**
+ * 按设置的顺序补全缺失的标题和艺术家
+ *
+ * @param workDir - 工作目录路径
+ * @param title - 从谱面读取到的标题
+ * @param artist - 从谱面读取到的艺术家
+ */
+export async function resolveFallbackName(
+  workDir: string,
+  title: string | undefined,
+  artist: string | undefined
+): Promise<FallbackNameInfo> {
+  const result: FallbackNameInfo = { title, artist, sources: {}, keepFolderName: false };
+
+  for (const source of NameFallback.getOrder()) {
+    if (result.title && result.artist) {
+      break;
+    }
+
+    let found: PartialName = {};
+    try {
+      switch (source) {
+        case NameFallbackSource.Bmson:
+          found = await readBmsonName(workDir);
+          break;
+        case NameFallbackSource.ArchiveName:
+          found = await readArchiveName(workDir);
+          break;
+        case NameFallbackSource.EventScrape:
+          found = await readEventScrape(workDir);
+          break;
+        case NameFallbackSource.BmsSearch:
+          found = (await BmsSearchClient.lookupWorkDir(workDir)) ?? {};
+          break;
+        case NameFallbackSource.FolderName:
+          result.keepFolderName = true;
+          return result;
+      }
+    } catch (error) {
+      console.warn(` !_! Failed to read fallback name (${source}) for ${workDir}:`, error);
+    }
+
+    if (!result.title && found.title) {
+      result.title = found.title;
+      result.sources.title = source;
+    }
+    if (!result.artist && found.artist) {
+      result.artist = found.artist;
+      result.sources.artist = source;
+    }
+  }
+
+  return result;
+}
@@ -4,3 +4,13 @@
  */
 
 export * from './rename';
+export * from './fallback';
+export * from './splitObj';
+export * from './mergeCharts';
+export * from './bga';
+export * from './bgaSummary';
+export * from './chartPatch';
+export * from './normalizeChartNames';
+export * from './exportChart';
+export * from './pin';
+export * from './lint';
@@ -0,0 +1,303 @@
+/**
+ * 作品检查
+ *
+ * 发布或提交作品前检查常见问题：谱面无法解析、缺少标题或艺术家、引用的媒体文件不存在、
+ * 文件名在 Windows 上非法、仅大小写不同的文件、零字节文件。错误会导致作品无法游玩，警告通常不影响游玩
+ */
+
+import { exists, readFile, rename, stat, writeFile } from '@tauri-apps/plugin-fs';
+import { bytesToLatin1, getBmsFileStr, latin1ToBytes } from '../bms/encoding';
+import { bmsQuickInfo } from '../bms/quickInfo';
+import { getWorkDirChartFiles, isBmsonFile, isChartFile, scanDirBms } from '../bms/scanner';
+import { ScoreSafety } from '../bms/scoreSafety';
+import { isFileNameValid } from '../fs/name';
+import { getDirectoryPath, getFileName, getFileStem, normalizePath } from '../fs/path';
+import { listRelativeFiles, mediaKey, readChartMediaKeys } from './splitObj';
+
+/**
+ * 检查出的问题
+ */
+export interface LintIssue {
+  level: 'error' | 'warning';
+  /** 相关文件（相对作品目录），作品整体的问题为空 */
+  file?: string;
+  message: string;
+}
+
+/**
+ * 大小写冲突的修复结果
+ */
+export interface CaseCollisionFix {
+  /** 重命名的文件（相对作品目录） */
+  renamed: { from: string; to: string }[];
+  /** 更新了引用的谱面 */
+  updatedCharts: string[];
+}
+
+/**
+ * 找出仅大小写不同的文件（合并 Windows 和 Linux 来源后常见）
+ *
+ * Windows 的文件系统不区分大小写，解压时同组的文件会互相覆盖
+ *
+ * @param files - 相对作品目录的文件路径
+ * @returns 每组冲突的文件（按名称排序，第一个保留原名）
+ */
+export function findCaseCollisions(files: string[]): string[][] {
+  const groups = new Map<string, string[]>();
+  for (const file of files) {
+    const key = normalizePath(file).toLowerCase();
+    groups.set(key, [...(groups.get(key) ?? []), file]);
+  }
+  return [...groups.values()].filter((group) => group.length > 1).map((group) => group.sort());
+}
+
+/**
+ * 检查作品目录
+ *
+ * @command
+ * @category bmsfolder
+ * @dangerous false
+ * @name 检查作品
+ * @description 检查谱面能否解析、标题和艺术家是否为空、引用的媒体文件是否存在、文件名是否合法以及零字节文件
+ * @frontend true
+ *
+ * @param {string} workDir - 作品目录路径
+ *
+ * @returns {Promise<LintIssue[]>} 检查出的问题
+ */
+export async function lint(workDir: string): Promise<LintIssue[]> {
+  const issues: LintIssue[] = [];
+  const { outcomes } = await scanDirBms(workDir);
+
+  if (outcomes.length === 0) {
+    issues.push({ level: 'error', message: '没有谱面文件' });
+  }
+  for (const outcome of outcomes) {
+    const file = getFileName(outcome.path);
+    for (const error of outcome.errors) {
+      issues.push({ level: 'error', file, message: `无法解析：${error}` });
+    }
+    if (!outcome.accepted && outcome.errors.length === 0) {
+      issues.push({ level: 'warning', file, message: outcome.skippedReason ?? '谱面被跳过' });
+    }
+  }
+
+  const files = await listRelativeFiles(workDir);
+  const keys = new Set(files.map(mediaKey));
+  for (const outcome of outcomes.filter((outcome) => outcome.accepted)) {
+    const file = getFileName(outcome.path);
+    const info = await bmsQuickInfo(outcome.path);
+    if (!info?.title?.trim()) {
+      issues.push({ level: 'error', file, message: '缺少标题' });
+    }
+    if (!info?.artist?.trim()) {
+      issues.push({ level: 'error', file, message: '缺少艺术家' });
+    }
+
+    const missing = [...(await readChartMediaKeys(outcome.path))].filter((key) => !keys.has(key));
+    if (missing.length > 0) {
+      issues.push({
+        level: 'warning',
+        file,
+        message: `引用的 ${missing.length} 个媒体文件不存在：${missing.slice(0, 5).join(', ')}`,
+      });
+    }
+  }
+
+  for (const [kept, ...others] of findCaseCollisions(files)) {
+    for (const file of others) {
+      issues.push({
+        level: 'error',
+        file,
+        message: `与 ${kept} 仅大小写不同，在 Windows 上解压时会互相覆盖`,
+      });
+    }
+  }
+
+  for (const file of files) {
+    if (file.split('/').some((part) => !isFileNameValid(part))) {
+      issues.push({ level: 'error', file, message: '文件名在 Windows 上非法' });
+    }
+    if ((await stat(`${workDir}/${file}`)).size === 0) {
+      issues.push({ level: 'warning', file, message: '零字节文件' });
+    }
+  }
+
+  for (const issue of issues) {
+    const log = issue.level === 'error' ? console.error : console.warn;
+    log(` !_! [${issue.level}] ${issue.file ? `${issue.file}: ` : ''}${issue.message}`);
+  }
+  const errors = issues.filter((issue) => issue.level === 'error').length;
+  console.log(`${workDir}: ${errors} errors, ${issues.length - errors} warnings`);
+  return issues;
+}
+
+/**
+ * 为冲突的文件选择新名称：在文件名后追加编号，不与其他文件（不区分大小写）重复
+ */
+function getCollisionFreeName(file: string, taken: Set<string>): string {
+  const dir = getDirectoryPath(file);
+  const name = getFileName(file);
+  const stem = getFileStem(name);
+  const ext = name.slice(stem.length);
+  for (let index = 2; ; index++) {
+    const candidate = `${dir ? `${dir}/` : ''}${stem}_${index}${ext}`;
+    if (!taken.has(candidate.toLowerCase())) {
+      taken.add(candidate.toLowerCase());
+      return candidate;
+    }
+  }
+}
+
+/**
+ * 更新 BMS 谱面中 #WAV / #BMP 对重命名文件的引用（区分大小写，引用的扩展名可以与文件不同）
+ *
+ * BMS 按单字节读写；引用只在文件名后追加编号，不需要按谱面编码重新编码
+ *
+ * @returns 修改后的内容，没有引用时为 null
+ */
+function updateChartRefs(content: string, renames: Map<string, string>): string | null {
+  let changed = false;
+  const updated = content.replace(
+    /^(#(?:WAV|BMP)[0-9A-Z]{2}[ \t]+)(.*?)([ \t]*\r?)$/gim,
+    (line, header: string, ref: string, tail: string) => {
+      const decoded = normalizePath(getBmsFileStr(latin1ToBytes(ref)));
+      const dotIndex = decoded.lastIndexOf('.');
+      const key = dotIndex > decoded.lastIndexOf('/') ? decoded.slice(0, dotIndex) : decoded;
+      const suffix = renames.get(key);
+      if (suffix === undefined) {
+        return line;
+      }
+      changed = true;
+      const refDot = ref.lastIndexOf('.');
+      const hasExt = refDot > Math.max(ref.lastIndexOf('/'), ref.lastIndexOf('\\'));
+      return hasExt
+        ? `${header}${ref.slice(0, refDot)}${suffix}${ref.slice(refDot)}${tail}`
+        : `${header}${ref}${suffix}${tail}`;
+    }
+  );
+  return changed ? updated : null;
+}
+
+/**
+ * 修复仅大小写不同的文件
+ *
+ * 每组保留按名称排序的第一个文件，其余在文件名后追加编号（如 "kick.wav" -> "kick_2.wav"），
+ * 并更新 BMS 谱面中完全按原大小写引用这些文件的 #WAV / #BMP 行。BMSON 谱面的引用不更新
+ *
+ * @command
+ * @category bmsfolder
+ * @dangerous true
+ * @name 修复大小写冲突
+ * @description 将作品中仅大小写不同的文件（Windows 上解压时会互相覆盖）重命名为带编号的名称，并更新谱面中的引用
+ * @frontend true
+ *
+ * @param {string} workDir - 作品目录路径
+ * @param {boolean} dryRun - 模拟运行（不实际执行）
+ *
+ * @returns {Promise<CaseCollisionFix>} 重命名的文件和更新的谱面
+ */
+export async function fixCaseCollisions(
+  workDir: string,
+  dryRun: boolean
+): Promise<CaseCollisionFix> {
+  const result: CaseCollisionFix = { renamed: [], updatedCharts: [] };
+  const files = await listRelativeFiles(workDir);
+  const taken = new Set(files.map((file) => normalizePath(file).toLowerCase()));
+
+  for (const [, ...others] of findCaseCollisions(files)) {
+    for (const file of others) {
+      result.renamed.push({ from: file, to: getCollisionFreeName(file, taken) });
+    }
+  }
+  if (result.renamed.length === 0) {
+    console.log(`No case collisions: ${workDir}`);
+    return result;
+  }
+
+  // 引用的匹配键：相对路径去掉扩展名（区分大小写）
+  const refKey = (file: string) =>
+    `${getDirectoryPath(normalizePath(file))}/${getFileStem(file)}`.replace(/^\//, '');
+  const renamedFiles = new Set(result.renamed.map(({ from }) => from));
+  const keptKeys = new Set(files.filter((file) => !renamedFiles.has(file)).map(refKey));
+  const charts = (await getWorkDirChartFiles(workDir)).filter((chart) => !isBmsonFile(chart));
+  // 只有扩展名大小写不同（如 "a.WAV" 和 "a.wav"）时无法区分引用的是哪个文件
+  for (const { from } of result.renamed.filter(({ from }) => keptKeys.has(refKey(from)))) {
+    console.warn(` !_! Only the extension case differs, references not updated: ${from}`);
+  }
+
+  /**
+   * 计算谱面的新内容
+   *
+   * @param renamed - 更新引用的重命名
+   * @param chartPath - 谱面当前的路径（谱面本身可能已被重命名）
+   * @returns 谱面当前的路径 -> 新内容
+   */
+  const planChartUpdates = async (
+    renamed: CaseCollisionFix['renamed'],
+    chartPath: (chart: string) => string = (chart) => chart
+  ) => {
+    const renames = new Map<string, string>();
+    for (const { from, to } of renamed.filter(({ from }) => !keptKeys.has(refKey(from)))) {
+      renames.set(refKey(from), getFileStem(to).slice(getFileStem(from).length));
+    }
+    const updates = new Map<string, string>();
+    for (const chart of charts.map(chartPath)) {
+      const content = bytesToLatin1(await readFile(`${workDir}/${chart}`));
+      const updated = updateChartRefs(content, renames);
+      if (updated !== null) {
+        updates.set(chart, updated);
+      }
+    }
+    return updates;
+  };
+
+  // 按全部重命名检查成绩关联，中止时不做任何修改
+  const planned = await planChartUpdates(result.renamed);
+  ScoreSafety.check(
+    'Fix case collisions',
+    {
+      hashChanged: [...planned.keys()],
+      pathChanged: result.renamed.map(({ from }) => from).filter(isChartFile),
+    },
+    dryRun
+  );
+
+  if (dryRun) {
+    result.updatedCharts = [...planned.keys()];
+    for (const { from, to } of result.renamed) {
+      console.log(`[dry-run] Would rename: ${from} -> ${to}`);
+    }
+    for (const chart of result.updatedCharts) {
+      console.log(`[dry-run] Would update references in: ${chart}`);
+    }
+    return result;
+  }
+
+  // 先重命名，只更新重命名成功的文件的引用，避免谱面指向不存在的文件
+  const renamed: CaseCollisionFix['renamed'] = [];
+  for (const item of result.renamed) {
+    if (await exists(`${workDir}/${item.to}`)) {
+      console.warn(` !_! Target exists, skipped: ${item.to}`);
+      continue;
+    }
+    try {
+      await rename(`${workDir}/${item.from}`, `${workDir}/${item.to}`);
+    } catch (error) {
+      console.warn(` !_! Failed to rename ${item.from}:`, error);
+      continue;
+    }
+    renamed.push(item);
+    console.log(`Renamed: ${item.from} -> ${item.to}`);
+  }
+  result.renamed = renamed;
+
+  const newPaths = new Map(renamed.map(({ from, to }) => [from, to]));
+  const updates = await planChartUpdates(renamed, (chart) => newPaths.get(chart) ?? chart);
+  for (const [chart, content] of updates) {
+    await writeFile(`${workDir}/${chart}`, latin1ToBytes(content));
+    result.updatedCharts.push(chart);
+    console.log(`Updated references in: ${chart}`);
+  }
+  return result;
+}
@@ -0,0 +1,222 @@
+/**
+ * 按谱面合并作品的两个版本
+ *
+ * 直接用 CheckReplace 合并时，同名但内容不同的谱面会被改名为 `file.1.bms`，难以分辨。
+ * 这里先比较同名谱面的难度和等级：不同则是不同的谱面，按难度改名后保留两份；
+ * 相同则视为同一谱面的新旧版本，按指定方式处理旧版本，再合并其余文件
+ */
+
+import { exists, remove, rename, stat } from '@tauri-apps/plugin-fs';
+import { ChartConflictStrategy } from '../../types/enums';
+import { bmsQuickInfo } from '../bms/quickInfo';
+import { getWorkDirChartFiles } from '../bms/scanner';
+import type { BmsQuickInfo } from '../bms/types';
+import { isFileSameContent } from '../fs/hash';
+import { moveElementsAcrossDir, replaceOptionsFromPreset, ReplacePreset } from '../fs/moving';
+import { getFileExtension, getFileName, getFileStem } from '../fs/path';
+import { DryRunSizeTally } from '../fs/sizeTally';
+
+/**
+ * #DIFFICULTY 对应的名称
+ */
+const DIFFICULTY_NAMES: Record<number, string> = {
+  1: 'BEGINNER',
+  2: 'NORMAL',
+  3: 'HYPER',
+  4: 'ANOTHER',
+  5: 'INSANE',
+};
+
+/**
+ * 同名谱面冲突的处理记录
+ */
+export interface ChartConflictResolution {
+  chart: string;
+  /** 难度或等级不同，改名后两份都保留 */
+  differentChart: boolean;
+  /** 新版本是否来自源目录 */
+  incomingNewer: boolean;
+  /** 改名后的文件名 */
+  renamedTo?: string;
+  /** 删除的旧版本路径 */
+  removed?: string;
+}
+
+/**
+ * 作品合并结果
+ */
+export interface ChartMergeResult {
+  fromDir: string;
+  toDir: string;
+  /** 内容相同的同名谱面 */
+  identical: string[];
+  conflicts: ChartConflictResolution[];
+}
+
+interface ChartVersion {
+  path: string;
+  info: BmsQuickInfo | null;
+  mtime: number;
+}
+
+async function readChartVersion(path: string): Promise<ChartVersion> {
+  const [info, meta] = await Promise.all([bmsQuickInfo(path), stat(path)]);
+  return { path, info, mtime: meta.mtime?.getTime() ?? 0 };
+}
+
+/**
+ * 从副标题中读取版本号（如 "ver1.2"、"v2"）
+ */
+function parseSubtitleVersion(subtitle: string | undefined): number[] | null {
+  const match = subtitle?.match(/\bv(?:er)?\.?\s*(\d+(?:\.\d+)*)/i);
+  return match ? match[1].split('.').map(Number) : null;
+}
+
+/**
+ * 比较两个版本的新旧：副标题中有版本号时按版本号，否则按修改时间
+ *
+ * @returns 大于 0 表示 a 较新
+ */
+function compareChartVersions(a: ChartVersion, b: ChartVersion): number {
+  const versionA = parseSubtitleVersion(a.info?.subtitle);
+  const versionB = parseSubtitleVersion(b.info?.subtitle);
+  if (versionA && versionB) {
+    for (let i = 0; i < Math.max(versionA.length, versionB.length); i++) {
+      const diff = (versionA[i] ?? 0) - (versionB[i] ?? 0);
+      if (diff !== 0) {
+        return diff;
+      }
+    }
+  }
+  return a.mtime - b.mtime;
+}
+
+/**
+ * 生成两个目录中都不存在的文件名
+ */
+async function getUniqueChartName(
+  dirs: string[],
+  stem: string,
+  suffix: string,
+  ext: string
+): Promise<string> {
+  for (let count = 1; ; count++) {
+    const name = `${stem} ${count === 1 ? suffix : `${suffix.slice(0, -1)} ${count}]`}.${ext}`;
+    const taken = await Promise.all(dirs.map((dir) => exists(`${dir}/${name}`)));
+    if (!taken.some(Boolean)) {
+      return name;
+    }
+  }
+}
+
+function formatDate(timestamp: number): string {
+  return new Date(timestamp).toISOString().slice(0, 10);
+}
+
+/**
+ * 按谱面合并作品
+ *
+ * @command
+ * @category bmsfolder
+ * @dangerous true
+ * @name 按谱面合并作品
+ * @description 合并同一作品的两个版本：难度不同的同名谱面按难度改名，同一谱面的旧版本按所选方式处理
+ * @frontend true
+ *
+ * @param {string} fromDir - 源作品目录（合并后删除）
+ * @param {string} toDir - 目标作品目录
+ * @param {ChartConflictStrategy} strategy - 同一谱面新旧版本的处理方式
+ * @param {boolean} dryRun - 模拟运行（不实际执行）
+ *
+ * @returns {Promise<ChartMergeResult>} 冲突处理记录
+ */
+export async function mergeWorks(
+  fromDir: string,
+  toDir: string,
+  strategy: ChartConflictStrategy,
+  dryRun: boolean
+): Promise<ChartMergeResult> {
+  const result: ChartMergeResult = { fromDir, toDir, identical: [], conflicts: [] };
+  const targetCharts = new Set(await getWorkDirChartFiles(toDir));
+  const tally = new DryRunSizeTally(`Merge ${fromDir} -> ${toDir}`);
+
+  for (const chart of await getWorkDirChartFiles(fromDir)) {
+    if (!targetCharts.has(chart)) {
+      continue;
+    }
+
+    const srcPath = `${fromDir}/${chart}`;
+    const dstPath = `${toDir}/${chart}`;
+    if (await isFileSameContent(srcPath, dstPath)) {
+      result.identical.push(chart);
+      continue;
+    }
+
+    const [incoming, existing] = await Promise.all([
+      readChartVersion(srcPath),
+      readChartVersion(dstPath),
+    ]);
+    const stem = getFileStem(chart);
+    const ext = getFileExtension(chart);
+    const incomingNewer = compareChartVersions(incoming, existing) > 0;
+    const resolution: ChartConflictResolution = {
+      chart,
+      differentChart:
+        incoming.info?.difficulty !== existing.info?.difficulty ||
+        incoming.info?.playLevel !== existing.info?.playLevel,
+      incomingNewer,
+    };
+
+    if (resolution.differentChart) {
+      // 不同的谱面：源目录中的谱面按难度和等级改名
+      const difficulty = DIFFICULTY_NAMES[incoming.info?.difficulty ?? 0] ?? 'CHART';
+      const label = [difficulty, incoming.info?.playLevel].filter(Boolean).join(' ');
+      resolution.renamedTo = await getUniqueChartName([fromDir, toDir], stem, `[${label}]`, ext);
+      if (!dryRun) {
+        await rename(srcPath, `${fromDir}/${resolution.renamedTo}`);
+      }
+    } else {
+      const older = incomingNewer ? existing : incoming;
+      const olderDir = incomingNewer ? toDir : fromDir;
+
+      if (strategy === ChartConflictStrategy.KeepNewer) {
+        resolution.removed = older.path;
+        if (dryRun) {
+          await tally.addRemove(older.path);
+        } else {
+          await remove(older.path);
+        }
+      } else {
+        const suffix =
+          strategy === ChartConflictStrategy.DateSuffix ? `[${formatDate(older.mtime)}]` : '[OLD]';
+        resolution.renamedTo = await getUniqueChartName([fromDir, toDir], stem, suffix, ext);
+        if (!dryRun) {
+          await rename(older.path, `${olderDir}/${resolution.renamedTo}`);
+        }
+      }
+    }
+
+    console.log(
+      `${dryRun ? '[dry-run] ' : ''}${chart}: ` +
+        (resolution.removed
+          ? `removed older version (${resolution.removed})`
+          : `renamed ${resolution.differentChart ? 'incoming chart' : 'older version'} ` +
+            `to ${resolution.renamedTo}`)
+    );
+    result.conflicts.push(resolution);
+  }
+
+  if (dryRun) {
+    console.log(`[dry-run] Would merge ${fromDir} -> ${toDir}`);
+    await tally.addMove(fromDir, getFileName(toDir));
+    tally.print();
+  } else {
+    await moveElementsAcrossDir(fromDir, toDir, replaceOptionsFromPreset(ReplacePreset.UpdatePack));
+  }
+
+  console.log(
+    `Merged ${fromDir} -> ${toDir}: ${result.conflicts.length} conflicts, ` +
+      `${result.identical.length} identical charts`
+  );
+  return result;
+}
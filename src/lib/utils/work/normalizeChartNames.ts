@@ -0,0 +1,160 @@
+/**
+ * 规范谱面文件名
+ *
+ * 没有 #SUBTITLE 时，部分播放器会显示谱面的文件名，文件名杂乱（如 "_7a.bme"、"ｓｏｎｇ＿ＨＹＰ.bms"）
+ * 会直接影响选曲画面。这里按统一的格式重命名谱面：全角英数字转为半角、替换非法字符，并保证重名时追加编号。
+ * 谱面内容不变，哈希和成绩不受影响（播放器需要重新扫描）
+ */
+
+import { readDir, rename } from '@tauri-apps/plugin-fs';
+import { bmsQuickInfo } from '../bms/quickInfo';
+import { getWorkDirChartFiles } from '../bms/scanner';
+import { ScoreSafety } from '../bms/scoreSafety';
+import type { BmsQuickInfo } from '../bms/types';
+import { extractWorkName } from '../bms/work';
+import { getValidFsName } from '../fs/name';
+import { getFileExtension, getFileStem } from '../fs/path';
+import { ChartNameScheme } from '../../types/enums';
+
+/**
+ * 谱面重命名
+ */
+export interface ChartRename {
+  from: string;
+  to: string;
+}
+
+/**
+ * #DIFFICULTY 1-5 对应的名称
+ */
+const DIFFICULTY_NAMES = ['BEGINNER', 'NORMAL', 'HYPER', 'ANOTHER', 'INSANE'];
+
+/**
+ * 全角英数字和全角空格转为半角，再替换文件名中的非法字符，合并多余的空白
+ */
+function normalizeNamePart(name: string): string {
+  const halfWidth = name
+    .replace(/[！-～]/g, (char) => String.fromCharCode(char.charCodeAt(0) - 0xfee0))
+    .replace(/\u3000/g, ' ');
+  return getValidFsName(halfWidth)
+    .replace(/\s+/g, ' ')
+    .trim()
+    .replace(/[. ]+$/, '');
+}
+
+/**
+ * 谱面的难度部分：#DIFFICULTY 名称，其次是副标题，再其次是标题中作品名之后的部分
+ */
+function getDifficultyPart(info: BmsQuickInfo, workTitle: string): string | undefined {
+  if (info.difficulty !== undefined && DIFFICULTY_NAMES[info.difficulty - 1]) {
+    return DIFFICULTY_NAMES[info.difficulty - 1];
+  }
+  if (info.subtitle?.trim()) {
+    return info.subtitle;
+  }
+  const rest = info.title?.startsWith(workTitle) ? info.title.slice(workTitle.length) : '';
+  return rest.replace(/^[\s\-_[(（［【]+|[\s\])）］】]+$/g, '') || info.playLevel;
+}
+
+/**
+ * 计算谱面的新文件名（不含扩展名）
+ */
+function getTargetStem(
+  chart: string,
+  info: BmsQuickInfo | null,
+  workTitle: string,
+  scheme: ChartNameScheme
+): string {
+  if (scheme === ChartNameScheme.TitleDifficulty && info && workTitle) {
+    const difficulty = getDifficultyPart(info, workTitle);
+    const stem = difficulty ? `${workTitle}_${difficulty}` : workTitle;
+    const normalized = normalizeNamePart(stem);
+    if (normalized) {
+      return normalized;
+    }
+  }
+  return normalizeNamePart(getFileStem(chart)) || '_';
+}
+
+/**
+ * 规范作品目录中的谱面文件名
+ *
+ * @command
+ * @category bmsfolder
+ * @dangerous true
+ * @name 规范谱面文件名
+ * @description 按 "<标题>_<难度>" 或原文件名重命名谱面，全角英数字转为半角、替换非法字符，重名时追加编号（谱面内容和哈希不变）
+ * @frontend true
+ *
+ * @param {string} workDir - 作品目录路径
+ * @param {ChartNameScheme} scheme - 命名方式
+ * @param {boolean} dryRun - 模拟运行（只列出重命名）
+ *
+ * @returns {Promise<ChartRename[]>} 重命名的谱面
+ */
+export async function normalizeChartNames(
+  workDir: string,
+  scheme: ChartNameScheme,
+  dryRun: boolean
+): Promise<ChartRename[]> {
+  const charts = (await getWorkDirChartFiles(workDir)).sort();
+  const infos = await Promise.all(charts.map((chart) => bmsQuickInfo(`${workDir}/${chart}`)));
+  const titles = infos.map((info) => info?.title).filter((title): title is string => !!title);
+  const workTitle = titles.length > 0 ? (extractWorkName(titles) || titles[0]).trim() : '';
+
+  // 其他文件的名称也要避开（不区分大小写，兼容 Windows 和 macOS）
+  const chartSet = new Set(charts);
+  const taken = new Set(
+    (await readDir(workDir))
+      .filter((entry) => entry.name && !chartSet.has(entry.name))
+      .map((entry) => entry.name.toLowerCase())
+  );
+
+  const planned = charts.map((chart, index) => ({
+    chart,
+    stem: getTargetStem(chart, infos[index], workTitle, scheme),
+    ext: getFileExtension(chart),
+  }));
+
+  // 已经符合规范的谱面先占用名称，不会因为其他谱面而被追加编号
+  const renames: ChartRename[] = [];
+  for (const { chart, stem, ext } of planned) {
+    if (`${stem}.${ext}` === chart) {
+      taken.add(chart.toLowerCase());
+    }
+  }
+  for (const { chart, stem, ext } of planned) {
+    if (`${stem}.${ext}` === chart) {
+      continue;
+    }
+    let target = `${stem}.${ext}`;
+    for (let count = 2; taken.has(target.toLowerCase()); count++) {
+      target = `${stem}_${count}.${ext}`;
+    }
+    taken.add(target.toLowerCase());
+    renames.push({ from: chart, to: target });
+  }
+
+  const pathChanged = renames.map(({ from }) => from);
+  ScoreSafety.check('Normalize chart names', { hashChanged: [], pathChanged }, dryRun);
+  for (const { from, to } of renames) {
+    console.log(`${dryRun ? '[dry-run] Would rename' : 'Rename'}: ${from} -> ${to}`);
+  }
+  if (dryRun || renames.length === 0) {
+    const prefix = dryRun ? '[dry-run] ' : '';
+    console.log(`${prefix}${renames.length} of ${charts.length} charts renamed`);
+    return renames;
+  }
+
+  // 先改为临时名称再改为目标名称，避免谱面之间互换名称时覆盖
+  const tempSuffix = `.renaming-${Date.now()}`;
+  for (const { from } of renames) {
+    await rename(`${workDir}/${from}`, `${workDir}/${from}${tempSuffix}`);
+  }
+  for (const { from, to } of renames) {
+    await rename(`${workDir}/${from}${tempSuffix}`, `${workDir}/${to}`);
+  }
+
+  console.log(`${renames.length} of ${charts.length} charts renamed`);
+  return renames;
+}
@@ -0,0 +1,104 @@
+/**
+ * 作品固定（保护）
+ *
+ * 手动整理过的作品目录中放一个 `.bms-toolbox-pin` 标记文件，
+ * 删除媒体文件、格式转换、合并等批量操作遇到带标记的作品时跳过，避免一次批量操作覆盖手动调整的结果
+ */
+
+import { exists, readDir, remove, writeTextFile } from '@tauri-apps/plugin-fs';
+
+/**
+ * 固定标记文件名（位于作品目录下）
+ */
+export const PIN_MARKER_FILE = '.bms-toolbox-pin';
+
+/**
+ * 作品是否已固定
+ */
+export async function isPinned(workDir: string): Promise<boolean> {
+  return await exists(`${workDir}/${PIN_MARKER_FILE}`).catch(() => false);
+}
+
+/**
+ * 批量操作中跳过已固定的作品时调用：已固定时输出日志并返回 true
+ */
+export async function skipIfPinned(workDir: string): Promise<boolean> {
+  if (!(await isPinned(workDir))) {
+    return false;
+  }
+  console.log(`Skipping pinned work: ${workDir}`);
+  return true;
+}
+
+/**
+ * 固定作品
+ *
+ * @command
+ * @category bmsfolder
+ * @dangerous false
+ * @name 固定作品
+ * @description 在作品目录中放置 .bms-toolbox-pin 标记，删除媒体文件、格式转换、合并等批量操作会跳过该作品
+ * @frontend true
+ *
+ * @param {string} workDir - 工作目录路径
+ * @param {string} note - 备注（写入标记文件，如固定的原因）
+ *
+ * @returns {Promise<void>}
+ */
+export async function pin(workDir: string, note: string = ''): Promise<void> {
+  const content = JSON.stringify({ pinnedAt: new Date().toISOString(), note }, null, 2);
+  await writeTextFile(`${workDir}/${PIN_MARKER_FILE}`, content);
+  console.log(`Pinned: ${workDir}`);
+}
+
+/**
+ * 取消固定作品
+ *
+ * @command
+ * @category bmsfolder
+ * @dangerous false
+ * @name 取消固定作品
+ * @description 删除作品目录中的 .bms-toolbox-pin 标记，批量操作将重新处理该作品
+ * @frontend true
+ *
+ * @param {string} workDir - 工作目录路径
+ *
+ * @returns {Promise<void>}
+ */
+export async function unpin(workDir: string): Promise<void> {
+  if (!(await isPinned(workDir))) {
+    console.log(`Not pinned: ${workDir}`);
+    return;
+  }
+  await remove(`${workDir}/${PIN_MARKER_FILE}`);
+  console.log(`Unpinned: ${workDir}`);
+}
+
+/**
+ * 列出根目录中已固定的作品
+ *
+ * @command
+ * @category bmsfolder
+ * @dangerous false
+ * @name 列出固定的作品
+ * @description 列出根目录中带有 .bms-toolbox-pin 标记、会被批量操作跳过的作品
+ * @frontend true
+ *
+ * @param {string} rootDir - 根目录路径
+ *
+ * @returns {Promise<string[]>} 已固定的作品文件夹名
+ */
+export async function listPinned(rootDir: string): Promise<string[]> {
+  const pinned: string[] = [];
+  for (const entry of await readDir(rootDir)) {
+    if (entry.isDirectory && entry.name && (await isPinned(`${rootDir}/${entry.name}`))) {
+      pinned.push(entry.name);
+    }
+  }
+  pinned.sort((a, b) => a.localeCompare(b));
+  for (const name of pinned) {
+    console.log(`Pinned: ${name}`);
+  }
+  console.log(`${pinned.length} pinned works in ${rootDir}`);
+  return pinned;
+}
@@ -0,0 +1,61 @@
+/**
+ * 已格式化目录名检查的测试
+ *
+ * 覆盖三种命名方式，目录名带数字编号前缀（如 "01 Title [Artist]"）
+ */
+
+import assert from 'node:assert/strict';
+import { test } from 'node:test';
+import { BmsFolderSetNameType, FormattedNamePattern, isAlreadyFormatted } from './rename';
+
+const TITLE = 'Song';
+const ARTIST = 'Artist';
+
+function check(dirName: string, setType: BmsFolderSetNameType): boolean {
+  return isAlreadyFormatted(dirName, setType, TITLE, ARTIST);
+}
+
+test('ReplaceTitleArtist only accepts the exact generated name', () => {
+  const setType = BmsFolderSetNameType.ReplaceTitleArtist;
+  assert.equal(check('Song [Artist]', setType), true);
+  assert.equal(check('01 Song [Artist]', setType), false);
+  assert.equal(check('01 [wip]', setType), false);
+  assert.equal(check('Song [Other]', setType), false);
+});
+
+test('AppendTitleArtist accepts a numeric prefix followed by the generated name', () => {
+  const setType = BmsFolderSetNameType.AppendTitleArtist;
+  assert.equal(check('01 Song [Artist]', setType), true);
+  assert.equal(check('123 Song [Artist]', setType), true);
+  assert.equal(check('01 [wip]', setType), false);
+  assert.equal(check('01 [Artist]', setType), false);
+  assert.equal(check('01', setType), false);
+});
+
+test('AppendArtist accepts a numeric prefix followed by the artist', () => {
+  const setType = BmsFolderSetNameType.AppendArtist;
+  assert.equal(check('01 [Artist]', setType), true);
+  assert.equal(check('01 Song [Artist]', setType), true);
+  assert.equal(check('01 [wip]', setType), false);
+  assert.equal(check('01[Artist]', setType), false);
+});
+
+test('generated names are compared after replacing invalid characters', () => {
+  const setType = BmsFolderSetNameType.AppendTitleArtist;
+  assert.equal(isAlreadyFormatted('01 A_B [C_D]', setType, 'A/B', 'C:D'), true);
+  assert.equal(isAlreadyFormatted('01 A/B [C:D]', setType, 'A/B', 'C:D'), false);
+});
+
+test('a custom pattern replaces the template check for its naming type', () => {
+  const setType = BmsFolderSetNameType.AppendArtist;
+  FormattedNamePattern.set(setType, '^\\d+ \\[[^\\]]+\\]$');
+  try {
+    assert.equal(check('01 [wip]', setType), true);
+    assert.equal(check('01 Song [Artist]', setType), false);
+    // 其他命名方式仍按模板判断
+    assert.equal(check('01 [wip]', BmsFolderSetNameType.AppendTitleArtist), false);
+  } finally {
+    FormattedNamePattern.set(setType, null);
+  }
+  assert.equal(check('01 [wip]', setType), false);
+});
@@ -3,8 +3,12 @@
  */
 
 import { exists, readDir, remove, rename } from '@tauri-apps/plugin-fs';
-import { getDirBmsInfo } from '../bms/scanner';
-import { getValidFileName } from '../fs/path';
+import { getDirBmsOutput, getDirBmsInfoWithSources } from '../bms/scanner';
+import { DryRunPlanner } from '../fs/dryRunPlan';
+import type { DryRunPlan } from '../fs/dryRunPlan';
+import { getDirectoryPath, getFileName, getValidFileName } from '../fs/path';
+import { runHooks } from '../hooks';
+import { resolveFallbackName } from './fallback';
 import { bmsDirSimilarity } from '../fs/similarity';
 import { moveElementsAcrossDir, replaceOptionsFromPreset, ReplacePreset } from '../fs/moving';
 
@@ -30,16 +34,54 @@ export enum BmsFolderSetNameType {
   AppendArtist = 2,
 }
 
+/**
+ * 已格式化目录名的自定义检查规则
+ *
+ * 未设置时按命名方式生成的名称判断（见 isAlreadyFormatted）
+ */
+export class FormattedNamePattern {
+  private static patterns = new Map<BmsFolderSetNameType, RegExp>();
+
+  /**
+   * 设置命名方式对应的正则表达式，为空时恢复按命名模板判断
+   */
+  static set(setType: BmsFolderSetNameType, pattern: string | null): void {
+    if (pattern) {
+      this.patterns.set(setType, new RegExp(pattern));
+    } else {
+      this.patterns.delete(setType);
+    }
+  }
+
+  static get(setType: BmsFolderSetNameType): RegExp | undefined {
+    return this.patterns.get(setType);
+  }
+}
+
 /**
  * 检查目录名是否已格式化
+ *
+ * 有自定义正则时按正则判断；否则检查目录名是否已经是命名模板生成的形式
+ * （"01 [wip]" 这类碰巧带方括号的名称不会被误判）
  */
-function isAlreadyFormatted(dirName: string, setType: BmsFolderSetNameType): boolean {
+export function isAlreadyFormatted(
+  dirName: string,
+  setType: BmsFolderSetNameType,
+  title: string,
+  artist: string
+): boolean {
+  const pattern = FormattedNamePattern.get(setType);
+  if (pattern) {
+    return pattern.test(dirName);
+  }
+
   switch (setType) {
     case BmsFolderSetNameType.ReplaceTitleArtist:
+      return dirName === getValidFileName(`${title} [${artist}]`);
     case BmsFolderSetNameType.AppendTitleArtist:
-      return dirName.includes(' [') && dirName.endsWith(']');
+      return dirName.endsWith(` ${getValidFileName(`${title} [${artist}]`)}`);
     case BmsFolderSetNameType.AppendArtist:
-      return dirName.includes(' [') && dirName.endsWith(']');
+      return dirName.endsWith(` ${getValidFileName(`[${artist}]`)}`);
   }
 }
 
@@ -106,7 +148,7 @@ async function tryMoveOutNestedFiles(workDir: string): Promise<boolean> {
  * @param {ReplacePreset} replacePreset - 文件替换策略
  * @param {boolean} skipAlreadyFormatted - 跳过已格式化的目录
  *
- * @returns {Promise<void>}
+ * @returns {Promise<DryRunPlan | null>} 模拟运行时返回计划的操作（实际执行或没有可执行的操作时为 null）
  */
 export async function setNameByBms(
   workDir: string,
@@ -114,26 +156,119 @@ export async function setNameByBms(
   dryRun: boolean,
   replacePreset: ReplacePreset,
   skipAlreadyFormatted: boolean
-): Promise<void> {
-  const bmsInfo = await getDirBmsInfo(workDir);
+): Promise<DryRunPlan | null> {
+  const result = await getDirBmsInfoWithSources(workDir);
 
-  if (!bmsInfo) {
+  if (!result) {
     console.log(`BMS file not found, skipping: ${workDir}`);
-    return;
+    return null;
   }
 
-  const title = bmsInfo.bms.musicInfo.title || DEFAULT_TITLE;
-  const artist = bmsInfo.bms.musicInfo.artist || DEFAULT_ARTIST;
+  const { title, artist, genre } = result.info.bms.musicInfo;
+  if (dryRun) {
+    // 展示重命名依据，便于在执行前发现错误的元数据
+    const describe = (value: string | undefined, source: string | undefined): string =>
+      value ? `"${value}" (from ${source})` : '(none)';
+    console.log(`[dry-run] Title: ${describe(title, result.sources.title)}`);
+    console.log(`[dry-run] Artist: ${describe(artist, result.sources.artist)}`);
+    console.log(`[dry-run] Genre: ${describe(genre, result.sources.genre)}`);
+  }
+
+  const outcome = await setNameByInfo(
+    workDir,
+    title,
+    artist,
+    setType,
+    dryRun,
+    replacePreset,
+    skipAlreadyFormatted
+  );
+  if (!dryRun) {
+    return null;
+  }
+  const planner = new DryRunPlanner(`Set name ${workDir}`);
+  await planSetName(planner, workDir, outcome);
+  return planner.plan();
+}
+
+/**
+ * 设置目录名的结果
+ */
+export interface SetNameOutcome {
+  /** 处理后的目录名（跳过时为原目录名） */
+  dirName: string;
+  /** 目标目录已存在，内容合并进了该目录 */
+  merged: boolean;
+}
 
+/**
+ * 将设置目录名的结果记入模拟运行计划（目标已存在时为合并，否则为改名）
+ */
+export async function planSetName(
+  planner: DryRunPlanner,
+  workDir: string,
+  outcome: SetNameOutcome
+): Promise<void> {
+  if (outcome.dirName === getFileName(workDir)) {
+    return;
+  }
+  const target = `${getDirectoryPath(workDir)}/${outcome.dirName}`;
+  if (outcome.merged) {
+    await planner.merge(workDir, target);
+  } else {
+    planner.rename(workDir, target);
+  }
+}
+
+/**
+ * 根据已读取的标题和艺术家设置目录名
+ *
+ * 供批量重命名在并行读取作品信息后按顺序执行重命名
+ *
+ * @returns 重命名后的目录名，以及是否合并进了已存在的目录
+ */
+export async function setNameByInfo(
+  workDir: string,
+  workTitle: string | undefined,
+  workArtist: string | undefined,
+  setType: BmsFolderSetNameType,
+  dryRun: boolean,
+  replacePreset: ReplacePreset,
+  skipAlreadyFormatted: boolean
+): Promise<SetNameOutcome> {
   // 获取当前目录名
   const workDirName = workDir.split('/').pop() || workDir.split('\\').pop() || workDir;
 
+  // 谱面缺少标题或艺术家时按设置的备用来源补全
+  if (!workTitle || !workArtist) {
+    const fallback = await resolveFallbackName(workDir, workTitle, workArtist);
+    if (fallback.keepFolderName) {
+      console.log(`Title or artist missing, keeping folder name: ${workDir}`);
+      return { dirName: workDirName, merged: false };
+    }
+    if (dryRun && (fallback.sources.title || fallback.sources.artist)) {
+      console.log(
+        `[dry-run] Fallback name: title "${fallback.title}" (${fallback.sources.title}), ` +
+          `artist "${fallback.artist}" (${fallback.sources.artist})`
+      );
+    }
+    workTitle = fallback.title;
+    workArtist = fallback.artist;
+  }
+
+  const title = workTitle || DEFAULT_TITLE;
+  const artist = workArtist || DEFAULT_ARTIST;
+
+  if (dryRun && (!workTitle || !workArtist)) {
+    console.warn(` !_! [dry-run] Missing title or artist, placeholder will be used: ${workDir}`);
+  }
+
   // 如果启用跳过已格式化目录的选项
-  if (skipAlreadyFormatted && isAlreadyFormatted(workDirName, setType)) {
+  if (skipAlreadyFormatted && isAlreadyFormatted(workDirName, setType, title, artist)) {
     if (dryRun) {
       console.log(`[dry-run] Directory already formatted, skipping: ${workDir}`);
     }
-    return;
+    return { dirName: workDirName, merged: false };
   }
 
   // 构建目标目录名
@@ -163,19 +298,38 @@ export async function setNameByBms(
     if (dryRun) {
       console.log(`[dry-run] Source and target directories are the same, skipping: ${workDir}`);
     }
-    return;
+    return { dirName: targetDirName, merged: false };
   }
 
-  console.log(`Rename work dir by moving content: ${workDir} -> ${targetWorkDir}`);
+  // 目标不存在时（或只有大小写不同时）直接重命名，只有目标已存在才逐个移动合并
+  const caseOnly = workDir.toLowerCase() === targetWorkDir.toLowerCase();
+  const targetExists = !caseOnly && (await exists(targetWorkDir));
+
+  if (targetExists) {
+    console.log(`Rename work dir by moving content: ${workDir} -> ${targetWorkDir}`);
+  } else {
+    console.log(`Rename work dir: ${workDir} -> ${targetWorkDir}`);
+  }
 
   if (!dryRun) {
-    const replaceOptions = replaceOptionsFromPreset(replacePreset);
-    await moveElementsAcrossDir(workDir, targetWorkDir, replaceOptions);
+    if (targetExists) {
+      const replaceOptions = replaceOptionsFromPreset(replacePreset);
+      await moveElementsAcrossDir(workDir, targetWorkDir, replaceOptions);
+    } else {
+      await rename(workDir, targetWorkDir);
+    }
+    await runHooks('after_rename', {
+      workPath: targetWorkDir,
+      title: workTitle,
+      artist: workArtist,
+      previousPath: workDir,
+    });
   }
 
   if (dryRun) {
     console.log(`[dry-run] End: work::setNameByBms`);
   }
+  return { dirName: targetDirName, merged: targetExists };
 }
 
 /**
@@ -285,7 +439,7 @@ export async function appendArtistNameByBms(rootDir: string, dryRun: boolean): P
       continue;
     }
 
-    const bmsInfo = await getDirBmsInfo(dirPath);
+    const bmsInfo = await getDirBmsOutput(dirPath);
     if (!bmsInfo) {
       console.log(`Dir ${dirPath} has no bms files!`);
       continue;
@@ -350,7 +504,7 @@ export async function setNameByBmsOptimized(
   replacePreset: ReplacePreset
 ): Promise<boolean> {
   // 获取 BMS 信息，如果没有则尝试处理嵌套目录
-  let bmsInfo = await getDirBmsInfo(workDir);
+  let bmsInfo = await getDirBmsOutput(workDir);
 
   while (!bmsInfo) {
     // 尝试从嵌套目录中移出文件
@@ -359,7 +513,7 @@ export async function setNameByBmsOptimized(
       return false;
     }
     // 再次尝试获取 BMS 信息
-    bmsInfo = await getDirBmsInfo(workDir);
+    bmsInfo = await getDirBmsOutput(workDir);
   }
 
   // 获取父目录路径
@@ -436,5 +590,13 @@ export async function setNameByBmsOptimized(
     }
   }
 
+  if (!dryRun) {
+    await runHooks('after_rename', {
+      workPath: targetWorkDir,
+      title: bmsInfo.bms.musicInfo.title,
+      artist: bmsInfo.bms.musicInfo.artist,
+      previousPath: workDir,
+    });
+  }
   return true;
 }
@@ -0,0 +1,188 @@
+/**
+ * 分离 obj 谱面
+ *
+ * 有的包同时包含原作谱面和他人制作的 obj 谱面。按艺术家找出 obj 谱面，
+ * 连同只被它们使用的媒体文件移动到同级的 `<name> [obj]` 文件夹。
+ * 分离后的文件夹符合 "Title [Artist]" 形式，可以用“合并带艺术家的文件夹”合并回去
+ */
+
+import { copyFile, exists, mkdir, readDir, rename } from '@tauri-apps/plugin-fs';
+import { bmsQuickInfo } from '../bms/quickInfo';
+import { getWorkDirChartFiles, readAndParseBmsFile } from '../bms/scanner';
+import { getDirectoryPath, getFileName, normalizePath } from '../fs/path';
+import { DryRunSizeTally } from '../fs/sizeTally';
+
+/**
+ * obj 谱面分离结果
+ */
+export interface SplitObjResult {
+  workDir: string;
+  /** 分离出的文件夹（没有 obj 谱面时为 null） */
+  objDir: string | null;
+  /** 原作谱面的艺术家 */
+  mainArtist?: string;
+  /** 移动的 obj 谱面 */
+  objCharts: string[];
+  /** 只被 obj 谱面使用而移动的媒体文件 */
+  movedMedia: string[];
+  /** 两边共用而复制的媒体文件 */
+  copiedMedia: string[];
+}
+
+/**
+ * 媒体引用的匹配键：相对路径去掉扩展名后转小写（BMS 引用的扩展名可能与实际文件不同）
+ */
+export function mediaKey(relativePath: string): string {
+  return normalizePath(relativePath).replace(/\.[^./]*$/, '').toLowerCase();
+}
+
+/**
+ * 递归列出目录中的文件（相对路径）
+ */
+export async function listRelativeFiles(dirPath: string, prefix: string = ''): Promise<string[]> {
+  const files: string[] = [];
+  for (const entry of await readDir(dirPath)) {
+    if (!entry.name) {
+      continue;
+    }
+    const relative = prefix ? `${prefix}/${entry.name}` : entry.name;
+    if (entry.isDirectory) {
+      files.push(...(await listRelativeFiles(`${dirPath}/${entry.name}`, relative)));
+    } else {
+      files.push(relative);
+    }
+  }
+  return files;
+}
+
+/**
+ * 读取谱面引用的媒体文件（匹配键）
+ */
+export async function readChartMediaKeys(chartPath: string): Promise<Set<string>> {
+  const output = await readAndParseBmsFile(chartPath);
+  const refs = [...Object.values(output?.bms.wav ?? {}), ...Object.values(output?.bms.bmp ?? {})];
+  return new Set(refs.map((ref) => mediaKey(ref.path ?? ref.name)));
+}
+
+/**
+ * 分离 obj 谱面
+ *
+ * 艺术家或副艺术家包含 "obj"、且与多数谱面的艺术家不同的谱面视为 obj 谱面
+ *
+ * @command
+ * @category bmsfolder
+ * @dangerous true
+ * @name 分离 obj 谱面
+ * @description 将他人制作的 obj 谱面及其独占的媒体文件移动到同级的 "<name> [obj]" 文件夹
+ * @frontend true
+ *
+ * @param {string} workDir - 作品目录路径
+ * @param {boolean} copySharedMedia - 同时复制共用的媒体文件，使分离出的文件夹可以单独游玩
+ * @param {boolean} dryRun - 模拟运行（不实际执行）
+ *
+ * @returns {Promise<SplitObjResult>} 分离结果
+ */
+export async function splitObj(
+  workDir: string,
+  copySharedMedia: boolean,
+  dryRun: boolean
+): Promise<SplitObjResult> {
+  const result: SplitObjResult = {
+    workDir,
+    objDir: null,
+    objCharts: [],
+    movedMedia: [],
+    copiedMedia: [],
+  };
+
+  const charts = await getWorkDirChartFiles(workDir);
+  const artists = new Map<string, string>();
+  const objMarked = new Set<string>();
+  for (const chart of charts) {
+    const info = await bmsQuickInfo(`${workDir}/${chart}`);
+    const artist = info?.artist?.trim() ?? '';
+    artists.set(chart, artist);
+    if (/obj/i.test(artist) || /obj/i.test(info?.subartist ?? '')) {
+      objMarked.add(chart);
+    }
+  }
+
+  // 多数谱面的艺术家视为原作
+  const artistCounts = new Map<string, number>();
+  for (const artist of artists.values()) {
+    artistCounts.set(artist, (artistCounts.get(artist) ?? 0) + 1);
+  }
+  const mainArtist = [...artistCounts.entries()].sort((a, b) => b[1] - a[1])[0]?.[0];
+  result.mainArtist = mainArtist;
+
+  const objCharts = charts.filter(
+    (chart) => objMarked.has(chart) && artists.get(chart) !== mainArtist
+  );
+  if (objCharts.length === 0 || objCharts.length === charts.length) {
+    console.log(`No obj charts to split: ${workDir}`);
+    return result;
+  }
+
+  const objMediaKeys = new Set<string>();
+  const mainMediaKeys = new Set<string>();
+  for (const chart of charts) {
+    const keys = await readChartMediaKeys(`${workDir}/${chart}`);
+    const target = objCharts.includes(chart) ? objMediaKeys : mainMediaKeys;
+    keys.forEach((key) => target.add(key));
+  }
+
+  for (const file of await listRelativeFiles(workDir)) {
+    const key = mediaKey(file);
+    if (!objMediaKeys.has(key) || charts.includes(file)) {
+      continue;
+    }
+    if (!mainMediaKeys.has(key)) {
+      result.movedMedia.push(file);
+    } else if (copySharedMedia) {
+      result.copiedMedia.push(file);
+    }
+  }
+
+  const parentDir = getDirectoryPath(workDir);
+  const objDir = `${parentDir}/${getFileName(workDir)} [obj]`;
+  result.objDir = objDir;
+  result.objCharts = objCharts;
+
+  console.log(
+    `Split ${objCharts.length} obj charts from ${workDir} (main artist: ${mainArtist}): ` +
+      `move ${result.movedMedia.length} media, copy ${result.copiedMedia.length} media`
+  );
+  if (dryRun) {
+    const objName = getFileName(objDir);
+    const tally = new DryRunSizeTally(`Split obj charts from ${workDir}`);
+    for (const chart of objCharts) {
+      console.log(`[dry-run] Would move chart: ${chart} -> ${objDir}`);
+    }
+    for (const file of [...objCharts, ...result.movedMedia]) {
+      await tally.addMove(`${workDir}/${file}`, objName);
+    }
+    for (const file of result.copiedMedia) {
+      await tally.addMove(`${workDir}/${file}`, `${objName} (copy)`);
+    }
+    tally.print();
+    return result;
+  }
+
+  const ensureParent = async (relative: string) => {
+    const dir = getDirectoryPath(`${objDir}/${relative}`);
+    if (!(await exists(dir))) {
+      await mkdir(dir, { recursive: true });
+    }
+  };
+
+  for (const file of [...objCharts, ...result.movedMedia]) {
+    await ensureParent(file);
+    await rename(`${workDir}/${file}`, `${objDir}/${file}`);
+  }
+  for (const file of result.copiedMedia) {
+    await ensureParent(file);
+    await copyFile(`${workDir}/${file}`, `${objDir}/${file}`);
+  }
+
+  return result;
+}
@@ -420,6 +420,10 @@ export class CommandGenerator {
       prefix = 'root_';
     } else if (filePath.includes('\\wasted\\') || filePath.includes('/wasted/')) {
       prefix = 'wasted_';
+    } else if (filePath.includes('\\library\\') || filePath.includes('/library/')) {
+      prefix = 'library_';
+    } else if (filePath.includes('\\logs\\') || filePath.includes('/logs/')) {
+      prefix = 'logs_';
     }
 
     // 驼峰转下划线
@@ -575,6 +579,8 @@ ${parameters}
       'fromdir',
       'todir',
       'outputdir',
+      'targetdir',
+      'sourcedir',
     ];
 
     // 文件路径参数模式
@@ -620,10 +626,10 @@ ${parameters}
     const typeImports = new Set<string>();
     frontendCommands.forEach((cmd) => {
       cmd.parameters.forEach((param) => {
-        // 检查是否是枚举类型
+        // 检查是否是枚举类型（Record<...> 等带泛型参数的内置类型不需要导入）
         if (
           param.typeString &&
-          /^[A-Z]/.test(param.typeString) &&
+          /^[A-Z]\w*(\[\])?$/.test(param.typeString) &&
           !param.typeString.includes('|')
         ) {
           // 移除数组语法（如 AudioPreset[] -> AudioPreset）
@@ -723,6 +729,10 @@ ${commandIds}
       bigpack: 'BigPack',
       pack: 'Pack',
       media: 'Media',
+      library: 'Library',
+      logs: 'Logs',
+      jobs: 'Jobs',
+      settings: 'Settings',
     };
 
     const lowerCategory = category.toLowerCase();